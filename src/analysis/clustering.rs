@@ -0,0 +1,356 @@
+//! 变更聚类
+//!
+//! 把 diff 拆成 hunk，按向量的余弦相似度把相关的 hunk 归为一组，例如一次重命名波及的
+//! 所有调用点。向量优先来自 embedding 模型，未配置时使用本地的词法向量：
+//! 对变更行中的标识符做特征哈希，共享标识符越多的 hunk 越相似。
+//! 分组结果用于大 diff 的提示词摘要和拆分提交的建议。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+static IDENTIFIER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]{2,}").unwrap());
+
+/// 词法向量的维度
+const LEXICAL_DIMENSIONS: usize = 512;
+
+/// 本地词法向量的相似度阈值
+pub const LEXICAL_THRESHOLD: f32 = 0.35;
+
+/// embedding 向量的相似度阈值
+pub const EMBEDDING_THRESHOLD: f32 = 0.8;
+
+/// 提示词中每组最多列出的标识符
+const MAX_LABEL_TERMS: usize = 3;
+
+/// 不参与相似度计算的常见关键字
+const STOP_WORDS: &[&str] = &[
+    "let",
+    "mut",
+    "pub",
+    "use",
+    "crate",
+    "self",
+    "Self",
+    "super",
+    "impl",
+    "struct",
+    "enum",
+    "return",
+    "const",
+    "static",
+    "async",
+    "await",
+    "match",
+    "Some",
+    "None",
+    "Ok",
+    "Err",
+    "String",
+    "Vec",
+    "Option",
+    "Result",
+    "new",
+    "for",
+    "while",
+    "else",
+    "true",
+    "false",
+    "function",
+    "var",
+    "def",
+    "class",
+    "import",
+    "from",
+    "func",
+    "package",
+    "the",
+    "and",
+    "this",
+    "println",
+    "format",
+    "unwrap",
+    "clone",
+    "to_string",
+];
+
+/// diff 中的一个 hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    pub file: String,
+    /// hunk 头和变更内容，作为 embedding 的输入
+    pub text: String,
+    /// 变更行中的标识符
+    pub identifiers: Vec<String>,
+}
+
+/// 把 diff 拆成 hunk，跳过没有变更行的 hunk
+pub fn split_hunks(diff: &str) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    let mut file: Option<String> = None;
+    let mut current: Option<Hunk> = None;
+
+    let finish = |hunk: Option<Hunk>, hunks: &mut Vec<Hunk>| {
+        if let Some(hunk) = hunk.filter(|hunk| !hunk.identifiers.is_empty()) {
+            hunks.push(hunk);
+        }
+    };
+
+    for line in diff.lines() {
+        if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+            finish(current.take(), &mut hunks);
+            file = Some(captures[2].to_string());
+        } else if line.starts_with("@@") {
+            finish(current.take(), &mut hunks);
+            if let Some(file) = &file {
+                current = Some(Hunk {
+                    file: file.clone(),
+                    text: format!("{}\n{}\n", file, line),
+                    identifiers: Vec::new(),
+                });
+            }
+        } else if let Some(hunk) = current.as_mut() {
+            let changed = (line.starts_with('+') && !line.starts_with("+++"))
+                || (line.starts_with('-') && !line.starts_with("---"));
+            if changed {
+                hunk.text.push_str(line);
+                hunk.text.push('\n');
+                hunk.identifiers.extend(
+                    IDENTIFIER_REGEX
+                        .find_iter(&line[1..])
+                        .map(|m| m.as_str())
+                        .filter(|word| !STOP_WORDS.contains(word))
+                        .map(str::to_string),
+                );
+            }
+        }
+    }
+    finish(current, &mut hunks);
+    hunks
+}
+
+/// 本地词法向量：标识符的特征哈希，L2 归一化
+pub fn lexical_embedding(hunk: &Hunk) -> Vec<f32> {
+    let mut vector = vec![0.0f32; LEXICAL_DIMENSIONS];
+    for identifier in &hunk.identifiers {
+        let hash = crate::cache::content_hash(identifier);
+        let bucket = u64::from_str_radix(&hash, 16).unwrap_or_default() as usize;
+        vector[bucket % LEXICAL_DIMENSIONS] += 1.0;
+    }
+    normalize(&mut vector);
+    vector
+}
+
+fn normalize(vector: &mut [f32]) {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+/// 余弦相似度，向量长度不同时返回 0
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+/// 单链接聚类：相似度不低于阈值的向量归为一组，结果按首次出现的顺序排列
+pub fn cluster(embeddings: &[Vec<f32>], threshold: f32) -> Vec<Vec<usize>> {
+    let mut parent: Vec<usize> = (0..embeddings.len()).collect();
+    fn find(parent: &mut [usize], i: usize) -> usize {
+        let mut root = i;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        parent[i] = root;
+        root
+    }
+
+    for i in 0..embeddings.len() {
+        for j in (i + 1)..embeddings.len() {
+            if cosine_similarity(&embeddings[i], &embeddings[j]) >= threshold {
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                parent[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..embeddings.len() {
+        let root = find(&mut parent, i);
+        groups.entry(root).or_default().push(i);
+    }
+    groups.into_values().collect()
+}
+
+/// 一组相关的变更
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeCluster {
+    /// 涉及的文件，按首次出现的顺序
+    pub files: Vec<String>,
+    pub hunks: usize,
+    /// 组内出现最多的标识符
+    pub terms: Vec<String>,
+}
+
+/// diff 的变更分组
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeClusters {
+    pub clusters: Vec<ChangeCluster>,
+}
+
+impl ChangeClusters {
+    /// 按给定的向量对 hunk 聚类
+    pub fn build(hunks: &[Hunk], embeddings: &[Vec<f32>], threshold: f32) -> Self {
+        let clusters = cluster(embeddings, threshold)
+            .into_iter()
+            .map(|members| {
+                let mut files: Vec<String> = Vec::new();
+                let mut counts: HashMap<&str, usize> = HashMap::new();
+                for &i in &members {
+                    if !files.contains(&hunks[i].file) {
+                        files.push(hunks[i].file.clone());
+                    }
+                    for identifier in &hunks[i].identifiers {
+                        *counts.entry(identifier).or_default() += 1;
+                    }
+                }
+                let mut terms: Vec<(&str, usize)> = counts.into_iter().collect();
+                terms.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+                ChangeCluster {
+                    files,
+                    hunks: members.len(),
+                    terms: terms
+                        .into_iter()
+                        .take(MAX_LABEL_TERMS)
+                        .map(|(term, _)| term.to_string())
+                        .collect(),
+                }
+            })
+            .collect();
+        Self { clusters }
+    }
+
+    /// 使用本地词法向量聚类
+    pub fn lexical(hunks: &[Hunk]) -> Self {
+        let embeddings: Vec<Vec<f32>> = hunks.iter().map(lexical_embedding).collect();
+        Self::build(hunks, &embeddings, LEXICAL_THRESHOLD)
+    }
+
+    /// 是否值得写入提示词：存在跨多个文件的分组，或分成了多组
+    pub fn is_informative(&self) -> bool {
+        self.clusters.len() > 1 || self.clusters.iter().any(|c| c.files.len() > 1)
+    }
+
+    /// 生成提交提示词中的分组摘要，分组没有意义时返回空字符串
+    pub fn to_prompt_context(&self) -> String {
+        if !self.is_informative() {
+            return String::new();
+        }
+        let mut context = String::from("\n相关变更分组（按内容相似度归类）：\n");
+        for (i, cluster) in self.clusters.iter().enumerate() {
+            context.push_str(&format!("{}. {}", i + 1, cluster.files.join("、")));
+            if !cluster.terms.is_empty() {
+                context.push_str(&format!("（涉及 {}）", cluster.terms.join(", ")));
+            }
+            context.push('\n');
+        }
+        context
+    }
+
+    /// 各组涉及的文件互不重叠时，建议拆分为多个提交
+    pub fn split_suggestion(&self) -> Option<Vec<&ChangeCluster>> {
+        if self.clusters.len() < 2 {
+            return None;
+        }
+        let mut seen: Vec<&str> = Vec::new();
+        for cluster in &self.clusters {
+            for file in &cluster.files {
+                if seen.contains(&file.as_str()) {
+                    return None;
+                }
+                seen.push(file);
+            }
+        }
+        Some(self.clusters.iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_support::file_diff;
+
+    fn rename_ripple_diff() -> String {
+        [
+            file_diff(
+                "src/config.rs",
+                "@@ -10,3 +10,3 @@\n-pub fn parse_config(path: &Path) -> Settings {\n+pub fn load_settings(path: &Path) -> Settings {\n",
+            ),
+            file_diff(
+                "src/main.rs",
+                "@@ -5,3 +5,3 @@\n-    let settings = parse_config(&path);\n+    let settings = load_settings(&path);\n",
+            ),
+            file_diff(
+                "src/server.rs",
+                "@@ -20,3 +20,3 @@\n-    let settings = config::parse_config(&path);\n+    let settings = config::load_settings(&path);\n",
+            ),
+            file_diff(
+                "web/theme.css",
+                "@@ -1,3 +1,3 @@\n-.header { color: blue; }\n+.header { color: navy; }\n",
+            ),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_split_hunks() {
+        let hunks = split_hunks(&rename_ripple_diff());
+        assert_eq!(hunks.len(), 4);
+        assert_eq!(hunks[1].file, "src/main.rs");
+        assert!(hunks[1].identifiers.contains(&"parse_config".to_string()));
+        assert!(!hunks[1].identifiers.contains(&"let".to_string()));
+    }
+
+    #[test]
+    fn test_lexical_clusters_group_rename_ripple() {
+        let clusters = ChangeClusters::lexical(&split_hunks(&rename_ripple_diff()));
+        assert_eq!(clusters.clusters.len(), 2);
+        assert_eq!(
+            clusters.clusters[0].files,
+            vec!["src/config.rs", "src/main.rs", "src/server.rs"]
+        );
+        assert!(clusters.clusters[0]
+            .terms
+            .contains(&"load_settings".to_string()));
+        assert_eq!(clusters.clusters[1].files, vec!["web/theme.css"]);
+
+        let context = clusters.to_prompt_context();
+        assert!(context.contains("1. src/config.rs、src/main.rs、src/server.rs"));
+        assert_eq!(clusters.split_suggestion().map(|s| s.len()), Some(2));
+    }
+
+    #[test]
+    fn test_cluster_with_embeddings() {
+        let embeddings = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.9, 0.1]];
+        assert_eq!(
+            cluster(&embeddings, EMBEDDING_THRESHOLD),
+            vec![vec![0, 2], vec![1]]
+        );
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0]), 0.0);
+        assert!(ChangeClusters::default().to_prompt_context().is_empty());
+    }
+}
@@ -0,0 +1,719 @@
+//! 函数复杂度分析
+//!
+//! 按语言识别函数边界，统计每个函数的圈复杂度（cyclomatic）、认知复杂度（cognitive）
+//! 和最大嵌套深度，用于找出需要重构的热点函数。
+//!
+//! 分析基于词法扫描而不是完整语法树：先去掉字符串和注释，再按关键字和运算符计数。
+//! 认知复杂度参照 SonarSource 的规则：控制结构按嵌套深度加权，`else`、逻辑运算符序列各加 1。
+
+use crate::languages::{analyzer_for, Language, SymbolKind};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 默认圈复杂度阈值
+pub const DEFAULT_MAX_CYCLOMATIC: u32 = 10;
+/// 默认认知复杂度阈值
+pub const DEFAULT_MAX_COGNITIVE: u32 = 15;
+/// 超过该行数的函数建议拆分
+const LONG_FUNCTION_LINES: usize = 80;
+/// 函数签名到函数体左花括号之间最多跨越的行数
+const SIGNATURE_LOOKAHEAD: usize = 6;
+
+static TOKEN_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"[A-Za-z_]\w*|&&|\|\||\?\.|\?:|=>|[?{}]").unwrap());
+
+static RUST_FN_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bfn\s+([A-Za-z_]\w*)").unwrap());
+
+static GO_FUNC_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*func\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)").unwrap());
+
+static JS_FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\bfunction\s*\*?\s*([A-Za-z_$][\w$]*)|\b(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*=\s*(?:async\s*)?(?:\([^)]*\)|[A-Za-z_$][\w$]*)\s*(?::\s*[^=]+)?=>|^\s*(?:(?:public|private|protected|static|async|override|readonly)\s+)*([A-Za-z_$][\w$]*)\s*\([^)]*\)\s*(?::\s*[^{]+)?\{",
+    )
+    .unwrap()
+});
+
+static C_FUNCTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:[\w:*&<>,\[\]]+\s+)+\**([A-Za-z_]\w*)\s*\([^;]*$").unwrap());
+
+static PYTHON_DEF_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\s*)(?:async\s+)?def\s+([A-Za-z_]\w*)").unwrap());
+
+/// Kotlin 的表达式函数体，如 `fun total(): Int = a + b`
+static EXPRESSION_BODY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\)\s*(?::\s*[\w<>?,. ]+)?\s*=[^=>]").unwrap());
+
+/// 看起来像函数调用或声明、实际是控制语句的关键字
+const CONTROL_KEYWORDS: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "return", "match", "when", "else", "sizeof", "new",
+];
+
+/// 按语法特征划分的源码类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    Rust,
+    Go,
+    /// JavaScript / TypeScript
+    Script,
+    /// C / C++ / C#
+    C,
+    Java,
+    Kotlin,
+    Python,
+}
+
+impl Syntax {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        match extension {
+            "rs" => Some(Syntax::Rust),
+            "go" => Some(Syntax::Go),
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Some(Syntax::Script),
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "cs" => Some(Syntax::C),
+            "java" => Some(Syntax::Java),
+            "kt" => Some(Syntax::Kotlin),
+            "py" => Some(Syntax::Python),
+            _ => None,
+        }
+    }
+
+    fn uses_hash_comments(&self) -> bool {
+        matches!(self, Syntax::Python)
+    }
+
+    /// 是否按缩进划分代码块
+    fn uses_indentation(&self) -> bool {
+        matches!(self, Syntax::Python)
+    }
+}
+
+/// 是否是复杂度分析支持的源文件
+pub fn is_supported(path: &Path) -> bool {
+    Syntax::from_path(path).is_some()
+}
+
+/// 单个函数的复杂度指标
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionComplexity {
+    pub name: String,
+    pub file: String,
+    /// 函数声明所在行（从 1 开始）
+    pub line: usize,
+    /// 函数总行数
+    pub length: usize,
+    pub cyclomatic: u32,
+    pub cognitive: u32,
+    pub max_nesting: u32,
+}
+
+impl FunctionComplexity {
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file, self.line)
+    }
+}
+
+/// 复杂度阈值
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ComplexityThresholds {
+    pub max_cyclomatic: u32,
+    pub max_cognitive: u32,
+}
+
+impl Default for ComplexityThresholds {
+    fn default() -> Self {
+        Self {
+            max_cyclomatic: DEFAULT_MAX_CYCLOMATIC,
+            max_cognitive: DEFAULT_MAX_COGNITIVE,
+        }
+    }
+}
+
+impl ComplexityThresholds {
+    pub fn is_exceeded_by(&self, function: &FunctionComplexity) -> bool {
+        function.cyclomatic > self.max_cyclomatic || function.cognitive > self.max_cognitive
+    }
+}
+
+/// 一组文件的复杂度分析结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComplexityReport {
+    pub files_analyzed: usize,
+    pub functions: Vec<FunctionComplexity>,
+    pub thresholds: ComplexityThresholds,
+}
+
+impl ComplexityReport {
+    pub fn new(thresholds: ComplexityThresholds) -> Self {
+        Self {
+            thresholds,
+            ..Default::default()
+        }
+    }
+
+    /// 分析单个文件的源码，不支持的语言忽略
+    pub fn add_source(&mut self, path: &str, source: &str) {
+        let Some(syntax) = Syntax::from_path(Path::new(path)) else {
+            return;
+        };
+        self.files_analyzed += 1;
+        self.functions.extend(analyze_source(path, source, syntax));
+    }
+
+    /// 超过阈值的函数
+    pub fn violations(&self) -> Vec<&FunctionComplexity> {
+        self.functions
+            .iter()
+            .filter(|f| self.thresholds.is_exceeded_by(f))
+            .collect()
+    }
+
+    /// 按认知复杂度、圈复杂度从高到低排列的前 `limit` 个函数
+    pub fn hotspots(&self, limit: usize) -> Vec<&FunctionComplexity> {
+        let mut functions: Vec<&FunctionComplexity> = self.functions.iter().collect();
+        functions.sort_by(|a, b| {
+            (b.cognitive, b.cyclomatic, b.length).cmp(&(a.cognitive, a.cyclomatic, a.length))
+        });
+        functions.truncate(limit);
+        functions
+    }
+
+    pub fn average_cyclomatic(&self) -> f64 {
+        average(self.functions.iter().map(|f| f.cyclomatic))
+    }
+
+    pub fn average_cognitive(&self) -> f64 {
+        average(self.functions.iter().map(|f| f.cognitive))
+    }
+
+    /// 针对超出阈值或过长的函数给出重构建议
+    pub fn recommendations(&self) -> Vec<String> {
+        let mut recommendations = Vec::new();
+        for function in self.hotspots(self.functions.len()) {
+            let exceeded = self.thresholds.is_exceeded_by(function);
+            if !exceeded && function.length <= LONG_FUNCTION_LINES {
+                continue;
+            }
+
+            let mut advice = Vec::new();
+            if function.max_nesting >= 4 {
+                advice.push(format!(
+                    "嵌套深度 {}，用提前返回（guard clause）或提取内层逻辑减少嵌套",
+                    function.max_nesting
+                ));
+            }
+            if function.cyclomatic > self.thresholds.max_cyclomatic {
+                advice.push(format!(
+                    "圈复杂度 {} 超过 {}，分支过多，可拆分子函数或用查表、多态替代条件分支",
+                    function.cyclomatic, self.thresholds.max_cyclomatic
+                ));
+            }
+            if function.cognitive > self.thresholds.max_cognitive && advice.is_empty() {
+                advice.push(format!(
+                    "认知复杂度 {} 超过 {}，简化条件表达式并拆分步骤",
+                    function.cognitive, self.thresholds.max_cognitive
+                ));
+            }
+            if function.length > LONG_FUNCTION_LINES {
+                advice.push(format!("函数共 {} 行，按职责拆分", function.length));
+            }
+
+            recommendations.push(format!(
+                "{} ({})：{}",
+                function.name,
+                function.location(),
+                advice.join("；")
+            ));
+        }
+        recommendations
+    }
+}
+
+fn average(values: impl Iterator<Item = u32>) -> f64 {
+    let (sum, count) = values.fold((0u64, 0u64), |(sum, count), v| (sum + v as u64, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum as f64 / count as f64
+    }
+}
+
+/// 分析一个源文件中的所有函数
+fn analyze_source(path: &str, source: &str, syntax: Syntax) -> Vec<FunctionComplexity> {
+    let raw_lines: Vec<&str> = source.lines().collect();
+    let lines = strip_comments_and_strings(&raw_lines, syntax);
+
+    let mut functions = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let Some((name, column)) = function_name(line, syntax) else {
+            continue;
+        };
+        let Some(end) = body_end(&lines, &raw_lines, index, column, syntax) else {
+            continue;
+        };
+
+        let body = &lines[index..=end];
+        let metrics = measure(body, syntax);
+        functions.push(FunctionComplexity {
+            name,
+            file: path.to_string(),
+            line: index + 1,
+            length: end - index + 1,
+            cyclomatic: metrics.cyclomatic,
+            cognitive: metrics.cognitive,
+            max_nesting: metrics.max_nesting,
+        });
+    }
+    functions
+}
+
+/// 识别函数声明，返回（函数名，名称之后的列）
+fn function_name(line: &str, syntax: Syntax) -> Option<(String, usize)> {
+    let (name, end) = match syntax {
+        Syntax::Rust => {
+            let captures = RUST_FN_REGEX.captures(line)?;
+            let name = captures.get(1)?;
+            (name.as_str(), name.end())
+        }
+        Syntax::Go => {
+            let captures = GO_FUNC_REGEX.captures(line)?;
+            let name = captures.get(1)?;
+            (name.as_str(), name.end())
+        }
+        Syntax::Python => {
+            let captures = PYTHON_DEF_REGEX.captures(line)?;
+            let name = captures.get(2)?;
+            (name.as_str(), name.end())
+        }
+        Syntax::Java => return jvm_method(line, Language::Java),
+        Syntax::Kotlin => return jvm_method(line, Language::Kotlin),
+        Syntax::Script => {
+            let captures = JS_FUNCTION_REGEX.captures(line)?;
+            let name = (1..=3).find_map(|i| captures.get(i))?;
+            (name.as_str(), name.end())
+        }
+        Syntax::C => {
+            let captures = C_FUNCTION_REGEX.captures(line)?;
+            let name = captures.get(1)?;
+            (name.as_str(), name.end())
+        }
+    };
+
+    if CONTROL_KEYWORDS.contains(&name) {
+        return None;
+    }
+    Some((name.to_string(), end))
+}
+
+/// 复用 `languages` 中的 Java / Kotlin 符号识别
+fn jvm_method(line: &str, language: Language) -> Option<(String, usize)> {
+    let symbol = analyzer_for(language)
+        .extract_symbols(line)
+        .into_iter()
+        .find(|symbol| symbol.kind == SymbolKind::Method)?;
+    let end = line.find(&symbol.name)? + symbol.name.len();
+    Some((symbol.name, end))
+}
+
+/// 找到函数体结束的行；只有声明没有函数体时返回 None
+fn body_end(
+    lines: &[String],
+    raw_lines: &[&str],
+    start: usize,
+    column: usize,
+    syntax: Syntax,
+) -> Option<usize> {
+    if syntax.uses_indentation() {
+        return Some(indented_block_end(raw_lines, start));
+    }
+
+    if syntax == Syntax::Kotlin
+        && !lines[start][column..].contains('{')
+        && EXPRESSION_BODY_REGEX.is_match(&lines[start][column..])
+    {
+        return Some(start);
+    }
+
+    let mut depth = 0usize;
+    let mut opened = false;
+    for (offset, line) in lines[start..].iter().enumerate() {
+        let text = if offset == 0 {
+            &line[column..]
+        } else {
+            line.as_str()
+        };
+        for ch in text.chars() {
+            match ch {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' if opened => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        return Some(start + offset);
+                    }
+                }
+                ';' if !opened => return None,
+                _ => {}
+            }
+        }
+        if !opened && offset >= SIGNATURE_LOOKAHEAD {
+            return None;
+        }
+    }
+    None
+}
+
+/// Python 代码块：缩进大于声明行的连续行
+fn indented_block_end(raw_lines: &[&str], start: usize) -> usize {
+    let base = indentation(raw_lines[start]);
+    let mut end = start;
+    for (offset, line) in raw_lines[start + 1..].iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indentation(line) <= base {
+            break;
+        }
+        end = start + 1 + offset;
+    }
+    end
+}
+
+fn indentation(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Metrics {
+    cyclomatic: u32,
+    cognitive: u32,
+    max_nesting: u32,
+}
+
+/// 统计函数体的复杂度，`body[0]` 为声明行
+fn measure(body: &[String], syntax: Syntax) -> Metrics {
+    let mut metrics = Metrics {
+        cyclomatic: 1,
+        ..Default::default()
+    };
+
+    // 花括号栈：true 表示由控制结构打开、计入嵌套
+    let mut braces: Vec<bool> = Vec::new();
+    // Python 控制结构的缩进栈
+    let mut indents: Vec<usize> = Vec::new();
+    let mut pending_nest = false;
+
+    for line in body {
+        let tokens: Vec<&str> = TOKEN_REGEX.find_iter(line).map(|m| m.as_str()).collect();
+
+        if syntax.uses_indentation() && !line.trim().is_empty() {
+            let indent = indentation(line);
+            while indents.last().is_some_and(|i| *i >= indent) {
+                indents.pop();
+            }
+        }
+        let mut opens_block = false;
+        let mut last_logical: Option<&str> = None;
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens[i];
+            let current_nesting = if syntax.uses_indentation() {
+                indents.len() as u32
+            } else {
+                braces.iter().filter(|nested| **nested).count() as u32
+            };
+
+            match token {
+                "else" => {
+                    metrics.cognitive += 1;
+                    pending_nest = true;
+                    opens_block = true;
+                    // else if 只计一次
+                    if tokens.get(i + 1) == Some(&"if") {
+                        metrics.cyclomatic += 1;
+                        i += 1;
+                    }
+                }
+                "elif" => {
+                    metrics.cognitive += 1;
+                    metrics.cyclomatic += 1;
+                    opens_block = true;
+                }
+                "if" | "for" | "while" | "catch" | "except" => {
+                    metrics.cyclomatic += 1;
+                    metrics.cognitive += 1 + current_nesting;
+                    pending_nest = true;
+                    opens_block = true;
+                }
+                "loop" if syntax == Syntax::Rust => {
+                    metrics.cognitive += 1 + current_nesting;
+                    pending_nest = true;
+                }
+                "switch" | "match" | "when" | "select" => {
+                    metrics.cognitive += 1 + current_nesting;
+                    pending_nest = true;
+                    opens_block = true;
+                    if token == "when" {
+                        metrics.cyclomatic += 1;
+                    }
+                }
+                "case" => metrics.cyclomatic += 1,
+                "=>" if syntax == Syntax::Rust => metrics.cyclomatic += 1,
+                "&&" | "||" | "and" | "or" => {
+                    let is_logical = !matches!(token, "and" | "or") || syntax == Syntax::Python;
+                    if is_logical {
+                        metrics.cyclomatic += 1;
+                        if last_logical != Some(token) {
+                            metrics.cognitive += 1;
+                        }
+                        last_logical = Some(token);
+                    }
+                }
+                "?" if matches!(syntax, Syntax::Script | Syntax::C | Syntax::Java) => {
+                    metrics.cyclomatic += 1;
+                    metrics.cognitive += 1 + current_nesting;
+                }
+                "?:" if syntax == Syntax::Kotlin => {
+                    metrics.cyclomatic += 1;
+                    metrics.cognitive += 1 + current_nesting;
+                }
+                "{" => {
+                    braces.push(pending_nest);
+                    pending_nest = false;
+                    let depth = braces.iter().filter(|nested| **nested).count() as u32;
+                    metrics.max_nesting = metrics.max_nesting.max(depth);
+                }
+                "}" => {
+                    braces.pop();
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+
+        // Rust 的 match 分支数比路径数多 1
+        if syntax == Syntax::Rust {
+            let matches = tokens.iter().filter(|t| **t == "match").count() as u32;
+            metrics.cyclomatic = metrics.cyclomatic.saturating_sub(matches).max(1);
+        }
+
+        if syntax.uses_indentation() && opens_block && line.trim_end().ends_with(':') {
+            indents.push(indentation(line));
+            metrics.max_nesting = metrics.max_nesting.max(indents.len() as u32);
+        }
+    }
+
+    metrics
+}
+
+/// 去掉注释和字符串内容，保留行结构
+fn strip_comments_and_strings(lines: &[&str], syntax: Syntax) -> Vec<String> {
+    let mut in_block_comment = false;
+    let mut result = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        let chars: Vec<char> = line.chars().collect();
+        let mut out = String::with_capacity(line.len());
+        let mut i = 0;
+        let mut in_string: Option<char> = None;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+
+            if in_block_comment {
+                if c == '*' && next == Some('/') {
+                    in_block_comment = false;
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+                continue;
+            }
+
+            if let Some(quote) = in_string {
+                if c == '\\' {
+                    i += 2;
+                    continue;
+                }
+                if c == quote {
+                    in_string = None;
+                    out.push(c);
+                }
+                i += 1;
+                continue;
+            }
+
+            if syntax.uses_hash_comments() && c == '#' {
+                break;
+            }
+            if !syntax.uses_hash_comments() && c == '/' && next == Some('/') {
+                break;
+            }
+            if !syntax.uses_hash_comments() && c == '/' && next == Some('*') {
+                in_block_comment = true;
+                i += 2;
+                continue;
+            }
+
+            match c {
+                '"' | '`' => {
+                    in_string = Some(c);
+                    out.push(c);
+                }
+                // Rust 的生命周期也以单引号开头，只把 'x' / '\n' 形式当作字符字面量
+                '\'' if syntax == Syntax::Rust => {
+                    if chars.get(i + 2) == Some(&'\'') {
+                        out.push_str("''");
+                        i += 3;
+                        continue;
+                    } else if next == Some('\\') && chars.get(i + 3) == Some(&'\'') {
+                        out.push_str("''");
+                        i += 4;
+                        continue;
+                    }
+                    out.push(c);
+                }
+                '\'' => {
+                    in_string = Some(c);
+                    out.push(c);
+                }
+                _ => out.push(c),
+            }
+            i += 1;
+        }
+
+        result.push(out);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(path: &str, source: &str) -> Vec<FunctionComplexity> {
+        let mut report = ComplexityReport::new(ComplexityThresholds::default());
+        report.add_source(path, source);
+        report.functions
+    }
+
+    #[test]
+    fn test_rust_metrics() {
+        let source = r#"
+fn simple() -> u32 {
+    1
+}
+
+fn branchy(x: i32, flag: bool) -> &'static str {
+    if x > 0 && flag {
+        for i in 0..x {
+            if i % 2 == 0 {
+                return "even";
+            }
+        }
+        "positive"
+    } else if x < 0 {
+        "negative"
+    } else {
+        match x {
+            0 => "zero",
+            _ => "other",
+        }
+    }
+}
+"#;
+        let functions = analyze("src/lib.rs", source);
+        assert_eq!(functions.len(), 2);
+
+        let simple = &functions[0];
+        assert_eq!(simple.name, "simple");
+        assert_eq!((simple.cyclomatic, simple.cognitive), (1, 0));
+
+        let branchy = &functions[1];
+        assert_eq!(branchy.name, "branchy");
+        assert_eq!(branchy.line, 6);
+        // if + && + for + if + else if + match(2 arms - 1)
+        assert_eq!(branchy.cyclomatic, 7);
+        // if(1) + &&(1) + for(2) + if(3) + else if(1) + else(1) + match(2)
+        assert_eq!(branchy.cognitive, 11);
+        assert_eq!(branchy.max_nesting, 3);
+    }
+
+    #[test]
+    fn test_strings_and_comments_ignored() {
+        let source = "fn quiet() {\n    // if for while\n    let s = \"if && ||\";\n    /* match x {\n    } */\n    let c = '{';\n}\n";
+        let functions = analyze("a.rs", source);
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].cyclomatic, 1);
+        assert_eq!(functions[0].length, 7);
+    }
+
+    #[test]
+    fn test_declarations_without_body_skipped() {
+        let source =
+            "trait Greeter {\n    fn greet(&self) -> String;\n    fn wave(&self) {\n    }\n}\n";
+        let functions = analyze("a.rs", source);
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "wave");
+    }
+
+    #[test]
+    fn test_other_languages() {
+        let go = "func (s *Server) Handle(w http.ResponseWriter) {\n\tif s.ready {\n\t\treturn\n\t}\n}\n";
+        let functions = analyze("server.go", go);
+        assert_eq!(functions[0].name, "Handle");
+        assert_eq!(functions[0].cyclomatic, 2);
+
+        let ts = "export function load(id: string) {\n  return id ? fetch(id) : null;\n}\nconst save = async (x) => {\n  if (x) { return 1; }\n};\n";
+        let functions = analyze("api.ts", ts);
+        let names: Vec<&str> = functions.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["load", "save"]);
+        assert_eq!(functions[0].cyclomatic, 2);
+
+        let python = "def check(x):\n    if x and x > 1:\n        for i in range(x):\n            pass\n    elif x:\n        pass\n\ndef other():\n    pass\n";
+        let functions = analyze("tool.py", python);
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].length, 6);
+        // if + and + for + elif
+        assert_eq!(functions[0].cyclomatic, 5);
+
+        let kotlin =
+            "fun total(a: Int): Int = if (a > 0) a else 0\nfun run() {\n    when (x) {\n    }\n}\n";
+        let functions = analyze("Main.kt", kotlin);
+        assert_eq!(functions.len(), 2);
+        assert_eq!(functions[0].length, 1);
+    }
+
+    #[test]
+    fn test_report_hotspots_and_recommendations() {
+        let mut report = ComplexityReport::new(ComplexityThresholds {
+            max_cyclomatic: 3,
+            max_cognitive: 5,
+        });
+        report.add_source(
+            "a.rs",
+            "fn a() {\n    if x { if y { if z { if w {} } } }\n}\nfn b() {}\n",
+        );
+        report.add_source("README.md", "# not code");
+
+        assert_eq!(report.files_analyzed, 1);
+        assert_eq!(report.hotspots(1)[0].name, "a");
+        assert_eq!(report.violations().len(), 1);
+
+        let recommendations = report.recommendations();
+        assert_eq!(recommendations.len(), 1);
+        assert!(recommendations[0].starts_with("a (a.rs:1)"));
+        assert!(recommendations[0].contains("嵌套深度 4"));
+        assert!(report.average_cyclomatic() > 1.0);
+    }
+}
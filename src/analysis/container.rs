@@ -0,0 +1,623 @@
+//! Dockerfile、docker compose 与 Kubernetes 清单的变更检测
+//!
+//! 从 diff 中识别镜像版本升级、资源配额（requests/limits）调整、被删除的健康检查和探针，
+//! 用于推断提交作用域（Dockerfile/compose 为 `chore(docker)`，新增 K8s 资源为 `feat(k8s)`），
+//! 并为提交和审查提示词补充部署相关的上下文。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+static COMPOSE_FILE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:docker-)?compose(?:\.[\w-]+)?\.ya?ml$").unwrap());
+
+/// `FROM [--platform=...] image[:tag] [AS name]`
+static FROM_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^\s*FROM\s+(?:--\S+\s+)*(\S+)").unwrap());
+
+/// YAML 中的 `image: repo/name:tag`
+static IMAGE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*(?:-\s+)?image:\s*["']?([^"'\s#]+)"#).unwrap());
+
+static RESOURCE_SECTION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(limits|requests|reservations):\s*$").unwrap());
+
+static RESOURCE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*(cpu|memory|cpus|ephemeral-storage|nvidia\.com/gpu|mem_limit|mem_reservation|cpu_shares|replicas):\s*["']?([^"'\s#]+)"#,
+    )
+    .unwrap()
+});
+
+static PROBE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(livenessProbe|readinessProbe|startupProbe|healthcheck):").unwrap()
+});
+
+static HEALTHCHECK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^\s*HEALTHCHECK\s+(\S+)").unwrap());
+
+static API_VERSION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*apiVersion:\s*\S+").unwrap());
+
+static KIND_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^kind:\s*(\w+)").unwrap());
+
+/// 路径中出现这些目录时，YAML 文件视为 Kubernetes 清单
+const K8S_DIRS: &[&str] = &[
+    "k8s",
+    "kubernetes",
+    "kube",
+    "manifests",
+    "charts",
+    "helm",
+    "kustomize",
+];
+
+/// 清单类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestKind {
+    Dockerfile,
+    Compose,
+    Kubernetes,
+}
+
+impl ManifestKind {
+    /// 根据路径识别清单类型；不在 K8s 目录下的 YAML 需要结合内容判断，这里返回 None
+    pub fn from_path(path: &str) -> Option<Self> {
+        let parts: Vec<&str> = path.split('/').collect();
+        let file_name = *parts.last()?;
+
+        if file_name == "Dockerfile"
+            || file_name == "Containerfile"
+            || file_name.starts_with("Dockerfile.")
+            || file_name.ends_with(".dockerfile")
+        {
+            return Some(ManifestKind::Dockerfile);
+        }
+        if COMPOSE_FILE_REGEX.is_match(file_name) {
+            return Some(ManifestKind::Compose);
+        }
+        let in_k8s_dir = parts[..parts.len() - 1]
+            .iter()
+            .any(|dir| K8S_DIRS.contains(dir));
+        if is_yaml(file_name) && (in_k8s_dir || file_name.starts_with("kustomization.")) {
+            return Some(ManifestKind::Kubernetes);
+        }
+        None
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ManifestKind::Dockerfile => "Dockerfile",
+            ManifestKind::Compose => "compose",
+            ManifestKind::Kubernetes => "Kubernetes",
+        }
+    }
+
+    /// 对应的提交作用域
+    pub fn scope(&self) -> &'static str {
+        match self {
+            ManifestKind::Dockerfile | ManifestKind::Compose => "docker",
+            ManifestKind::Kubernetes => "k8s",
+        }
+    }
+}
+
+fn is_yaml(file_name: &str) -> bool {
+    file_name.ends_with(".yaml") || file_name.ends_with(".yml")
+}
+
+/// 路径对应的提交作用域
+pub fn scope_for_path(path: &str) -> Option<&'static str> {
+    ManifestKind::from_path(path).map(|kind| kind.scope())
+}
+
+/// 镜像版本变更
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageChange {
+    pub file: String,
+    pub image: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// 资源配额变更，`from`/`to` 为 None 表示新增或删除该配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceChange {
+    pub file: String,
+    /// 所在的 `limits`/`requests`/`reservations` 段
+    pub section: Option<String>,
+    pub key: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl ResourceChange {
+    fn describe(&self) -> String {
+        let key = match &self.section {
+            Some(section) => format!("{}.{}", section, self.key),
+            None => self.key.clone(),
+        };
+        let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "无".to_string());
+        format!(
+            "{}: {} {} → {}",
+            self.file,
+            key,
+            value(&self.from),
+            value(&self.to)
+        )
+    }
+}
+
+/// 被删除的健康检查或探针
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeRemoval {
+    pub file: String,
+    pub probe: String,
+}
+
+/// diff 中的容器和部署清单变更
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContainerChanges {
+    pub files: Vec<(String, ManifestKind)>,
+    pub images: Vec<ImageChange>,
+    pub resources: Vec<ResourceChange>,
+    pub removed_probes: Vec<ProbeRemoval>,
+    /// 新增的 K8s 资源类型（如 Deployment、Service）
+    pub new_resources: Vec<String>,
+}
+
+impl ContainerChanges {
+    /// 解析统一 diff
+    pub fn from_diff(diff: &str) -> Self {
+        let mut changes = ContainerChanges::default();
+        let mut current: Option<FileScanner> = None;
+
+        for line in diff.lines() {
+            if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+                if let Some(scanner) = current.take() {
+                    scanner.finish(&mut changes);
+                }
+                current = FileScanner::new(&captures[2]);
+                continue;
+            }
+            if line.starts_with("+++") || line.starts_with("---") || line.starts_with("@@") {
+                continue;
+            }
+            if let Some(scanner) = current.as_mut() {
+                scanner.push_line(line);
+            }
+        }
+
+        if let Some(scanner) = current {
+            scanner.finish(&mut changes);
+        }
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn has_kind(&self, kind: ManifestKind) -> bool {
+        self.files.iter().any(|(_, k)| *k == kind)
+    }
+
+    /// 推荐的提交类型：新增 K8s 资源为 `feat`，其他部署配置调整为 `chore`
+    pub fn suggested_type(&self) -> Option<&'static str> {
+        if self.is_empty() {
+            None
+        } else if !self.new_resources.is_empty() {
+            Some("feat")
+        } else {
+            Some("chore")
+        }
+    }
+
+    /// 推荐的提交作用域：涉及 K8s 清单时为 `k8s`，否则为 `docker`
+    pub fn suggested_scope(&self) -> Option<&'static str> {
+        if self.has_kind(ManifestKind::Kubernetes) {
+            Some("k8s")
+        } else if self.is_empty() {
+            None
+        } else {
+            Some("docker")
+        }
+    }
+
+    fn describe_changes(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for (file, kind) in &self.files {
+            lines.push(format!("- {}：{}", kind.as_str(), file));
+        }
+        for image in &self.images {
+            lines.push(format!(
+                "- 镜像升级 {}: {} {} → {}",
+                image.file, image.image, image.from, image.to
+            ));
+        }
+        for resource in &self.resources {
+            lines.push(format!("- 资源配额 {}", resource.describe()));
+        }
+        for probe in &self.removed_probes {
+            lines.push(format!("- 删除探针 {}: {}", probe.file, probe.probe));
+        }
+        if !self.new_resources.is_empty() {
+            lines.push(format!(
+                "- 新增 K8s 资源：{}",
+                self.new_resources.join(", ")
+            ));
+        }
+        lines
+    }
+
+    /// 提交提示词中的部署配置上下文
+    pub fn to_prompt_context(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut lines = self.describe_changes();
+        if let (Some(commit_type), Some(scope)) = (self.suggested_type(), self.suggested_scope()) {
+            lines.push(format!("- 建议使用 {}({})", commit_type, scope));
+        }
+        format!("\n容器与部署配置变更:\n{}\n", lines.join("\n"))
+    }
+
+    /// 审查提示词中的上下文：变更摘要加上部署配置的审查重点
+    pub fn to_review_context(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut context = format!(
+            "\n容器与部署配置变更:\n{}\n",
+            self.describe_changes().join("\n")
+        );
+        context.push_str(
+            "\n部署配置审查重点:\n\
+             - 镜像是否固定版本（避免 latest），升级是否包含不兼容变更\n\
+             - 资源配额调整是否合理，limits 是否小于 requests 或导致 OOM\n\
+             - 删除健康检查或探针后，故障实例能否被及时发现和替换\n",
+        );
+        context
+    }
+}
+
+/// 逐行扫描单个清单文件
+struct FileScanner {
+    path: String,
+    kind: Option<ManifestKind>,
+    looks_like_k8s: bool,
+    section: Option<String>,
+    removed_images: Vec<String>,
+    added_images: Vec<String>,
+    removed_resources: HashMap<(Option<String>, String), String>,
+    added_resources: Vec<((Option<String>, String), String)>,
+    probe_counts: HashMap<String, i32>,
+    added_kinds: Vec<String>,
+    removed_kinds: Vec<String>,
+}
+
+impl FileScanner {
+    fn new(path: &str) -> Option<Self> {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        let kind = ManifestKind::from_path(path);
+        // 其他 YAML 文件根据内容中的 apiVersion/kind 判断是否为 K8s 清单
+        if kind.is_none() && !is_yaml(file_name) {
+            return None;
+        }
+        Some(Self {
+            path: path.to_string(),
+            kind,
+            looks_like_k8s: false,
+            section: None,
+            removed_images: Vec::new(),
+            added_images: Vec::new(),
+            removed_resources: HashMap::new(),
+            added_resources: Vec::new(),
+            probe_counts: HashMap::new(),
+            added_kinds: Vec::new(),
+            removed_kinds: Vec::new(),
+        })
+    }
+
+    fn push_line(&mut self, line: &str) {
+        let (change, code) = if let Some(code) = line.strip_prefix('+') {
+            (Some(true), code)
+        } else if let Some(code) = line.strip_prefix('-') {
+            (Some(false), code)
+        } else if let Some(code) = line.strip_prefix(' ') {
+            (None, code)
+        } else {
+            return;
+        };
+
+        if API_VERSION_REGEX.is_match(code) {
+            self.looks_like_k8s = true;
+        }
+        if let Some(captures) = RESOURCE_SECTION_REGEX.captures(code) {
+            self.section = Some(captures[1].to_string());
+        }
+        let Some(added) = change else {
+            return;
+        };
+
+        let image = if self.kind == Some(ManifestKind::Dockerfile) {
+            FROM_REGEX.captures(code)
+        } else {
+            IMAGE_REGEX.captures(code)
+        };
+        if let Some(captures) = image {
+            let images = if added {
+                &mut self.added_images
+            } else {
+                &mut self.removed_images
+            };
+            images.push(captures[1].to_string());
+        }
+
+        if let Some(captures) = RESOURCE_REGEX.captures(code) {
+            let key = (self.section.clone(), captures[1].to_string());
+            let value = captures[2].to_string();
+            if added {
+                self.added_resources.push((key, value));
+            } else {
+                self.removed_resources.insert(key, value);
+            }
+        }
+
+        // 删除的探针计 +1，新增的计 -1，最终大于 0 即为被删除；新增 `HEALTHCHECK NONE` 等同于关闭健康检查
+        if let Some(captures) = PROBE_REGEX.captures(code) {
+            *self
+                .probe_counts
+                .entry(captures[1].to_string())
+                .or_default() += if added { -1 } else { 1 };
+        } else if let Some(captures) = HEALTHCHECK_REGEX.captures(code) {
+            let disabled = captures[1].eq_ignore_ascii_case("NONE");
+            *self
+                .probe_counts
+                .entry("HEALTHCHECK".to_string())
+                .or_default() += if added == disabled { 1 } else { -1 };
+        }
+
+        if let Some(captures) = KIND_REGEX.captures(code) {
+            let kinds = if added {
+                &mut self.added_kinds
+            } else {
+                &mut self.removed_kinds
+            };
+            kinds.push(captures[1].to_string());
+        }
+    }
+
+    fn finish(mut self, changes: &mut ContainerChanges) {
+        let kind = match self.kind {
+            Some(kind) => kind,
+            None if self.looks_like_k8s => ManifestKind::Kubernetes,
+            None => return,
+        };
+        changes.files.push((self.path.clone(), kind));
+
+        for added in &self.added_images {
+            let (name, to) = split_image(added);
+            let removed = self
+                .removed_images
+                .iter()
+                .map(|image| split_image(image))
+                .find(|(removed_name, _)| *removed_name == name);
+            if let Some((_, from)) = removed.filter(|(_, from)| *from != to) {
+                changes.images.push(ImageChange {
+                    file: self.path.clone(),
+                    image: name.to_string(),
+                    from: from.to_string(),
+                    to: to.to_string(),
+                });
+            }
+        }
+
+        for (key, value) in self.added_resources {
+            let from = self.removed_resources.remove(&key);
+            if from.as_deref() == Some(value.as_str()) {
+                continue;
+            }
+            changes.resources.push(ResourceChange {
+                file: self.path.clone(),
+                section: key.0,
+                key: key.1,
+                from,
+                to: Some(value),
+            });
+        }
+        let mut removed: Vec<_> = self.removed_resources.into_iter().collect();
+        removed.sort();
+        for (key, value) in removed {
+            changes.resources.push(ResourceChange {
+                file: self.path.clone(),
+                section: key.0,
+                key: key.1,
+                from: Some(value),
+                to: None,
+            });
+        }
+
+        let mut probes: Vec<_> = self
+            .probe_counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(probe, _)| probe)
+            .collect();
+        probes.sort();
+        changes
+            .removed_probes
+            .extend(probes.into_iter().map(|probe| ProbeRemoval {
+                file: self.path.clone(),
+                probe,
+            }));
+
+        if kind == ManifestKind::Kubernetes {
+            for added_kind in self.added_kinds {
+                if let Some(index) = self.removed_kinds.iter().position(|k| *k == added_kind) {
+                    self.removed_kinds.remove(index);
+                    continue;
+                }
+                if !changes.new_resources.contains(&added_kind) {
+                    changes.new_resources.push(added_kind);
+                }
+            }
+        }
+    }
+}
+
+/// 拆分镜像引用为（名称，标签），未写标签时为 `latest`，带 digest 时以 digest 为准
+fn split_image(image: &str) -> (&str, &str) {
+    let (reference, digest) = match image.split_once('@') {
+        Some((reference, digest)) => (reference, Some(digest)),
+        None => (image, None),
+    };
+    let (name, tag) = match reference.rsplit_once(':') {
+        // 冒号之后出现 `/` 说明是仓库端口，如 `registry:5000/app`
+        Some((name, tag)) if !tag.contains('/') => (name, Some(tag)),
+        _ => (reference, None),
+    };
+    (name, digest.or(tag).unwrap_or("latest"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_kind_from_path() {
+        assert_eq!(
+            ManifestKind::from_path("Dockerfile"),
+            Some(ManifestKind::Dockerfile)
+        );
+        assert_eq!(
+            ManifestKind::from_path("docker/api.dockerfile"),
+            Some(ManifestKind::Dockerfile)
+        );
+        assert_eq!(
+            ManifestKind::from_path("docker-compose.prod.yml"),
+            Some(ManifestKind::Compose)
+        );
+        assert_eq!(
+            ManifestKind::from_path("deploy/k8s/api/deployment.yaml"),
+            Some(ManifestKind::Kubernetes)
+        );
+        assert_eq!(ManifestKind::from_path(".github/workflows/ci.yml"), None);
+        assert_eq!(scope_for_path("compose.yaml"), Some("docker"));
+        assert_eq!(scope_for_path("k8s/service.yml"), Some("k8s"));
+    }
+
+    #[test]
+    fn test_split_image() {
+        assert_eq!(split_image("rust:1.79-slim"), ("rust", "1.79-slim"));
+        assert_eq!(split_image("nginx"), ("nginx", "latest"));
+        assert_eq!(
+            split_image("registry:5000/team/app"),
+            ("registry:5000/team/app", "latest")
+        );
+        assert_eq!(
+            split_image("alpine:3.19@sha256:abc"),
+            ("alpine", "sha256:abc")
+        );
+    }
+
+    #[test]
+    fn test_dockerfile_changes() {
+        let diff = "diff --git a/Dockerfile b/Dockerfile\n\
+                    --- a/Dockerfile\n\
+                    +++ b/Dockerfile\n\
+                    @@ -1,6 +1,6 @@\n\
+                    -FROM rust:1.78 AS builder\n\
+                    +FROM --platform=linux/amd64 rust:1.79 AS builder\n\
+                    \x20WORKDIR /app\n\
+                    -HEALTHCHECK CMD curl -f http://localhost/ || exit 1\n\
+                    +HEALTHCHECK NONE\n";
+        let changes = ContainerChanges::from_diff(diff);
+
+        assert_eq!(
+            changes.files,
+            vec![("Dockerfile".to_string(), ManifestKind::Dockerfile)]
+        );
+        assert_eq!(
+            changes.images,
+            vec![ImageChange {
+                file: "Dockerfile".to_string(),
+                image: "rust".to_string(),
+                from: "1.78".to_string(),
+                to: "1.79".to_string(),
+            }]
+        );
+        assert_eq!(changes.removed_probes[0].probe, "HEALTHCHECK");
+        assert_eq!(changes.suggested_type(), Some("chore"));
+        assert_eq!(changes.suggested_scope(), Some("docker"));
+        assert!(changes.to_prompt_context().contains("chore(docker)"));
+    }
+
+    #[test]
+    fn test_kubernetes_changes() {
+        let diff = "diff --git a/deploy/api.yaml b/deploy/api.yaml\n\
+                    --- a/deploy/api.yaml\n\
+                    +++ b/deploy/api.yaml\n\
+                    @@ -1,30 +1,34 @@\n\
+                    \x20apiVersion: apps/v1\n\
+                    \x20kind: Deployment\n\
+                    -        image: ghcr.io/acme/api:1.4.2\n\
+                    +        image: \"ghcr.io/acme/api:1.5.0\"\n\
+                    \x20        resources:\n\
+                    \x20          limits:\n\
+                    -            memory: 512Mi\n\
+                    +            memory: 1Gi\n\
+                    \x20            cpu: 500m\n\
+                    \x20          requests:\n\
+                    -            cpu: 100m\n\
+                    -        livenessProbe:\n\
+                    -          httpGet:\n\
+                    +        readinessProbe:\n\
+                    +          httpGet:\n\
+                    +---\n\
+                    +apiVersion: v1\n\
+                    +kind: Service\n";
+        let changes = ContainerChanges::from_diff(diff);
+
+        assert_eq!(changes.files[0].1, ManifestKind::Kubernetes);
+        assert_eq!(changes.images[0].from, "1.4.2");
+        assert_eq!(changes.images[0].to, "1.5.0");
+        assert_eq!(
+            changes
+                .resources
+                .iter()
+                .map(|r| r.describe())
+                .collect::<Vec<_>>(),
+            vec![
+                "deploy/api.yaml: limits.memory 512Mi → 1Gi".to_string(),
+                "deploy/api.yaml: requests.cpu 100m → 无".to_string(),
+            ]
+        );
+        assert_eq!(
+            changes.removed_probes,
+            vec![ProbeRemoval {
+                file: "deploy/api.yaml".to_string(),
+                probe: "livenessProbe".to_string(),
+            }]
+        );
+        assert_eq!(changes.new_resources, vec!["Service".to_string()]);
+        assert_eq!(changes.suggested_type(), Some("feat"));
+        assert_eq!(changes.suggested_scope(), Some("k8s"));
+        assert!(changes.to_review_context().contains("部署配置审查重点"));
+    }
+
+    #[test]
+    fn test_plain_yaml_ignored() {
+        let diff = "diff --git a/.github/workflows/ci.yml b/.github/workflows/ci.yml\n\
+                    --- a/.github/workflows/ci.yml\n\
+                    +++ b/.github/workflows/ci.yml\n\
+                    @@ -1,2 +1,2 @@\n\
+                    -    image: node:18\n\
+                    +    image: node:20\n";
+        assert!(ContainerChanges::from_diff(diff).is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+//! 纯文档变更检测
+//!
+//! 判断 diff 是否只涉及文档：Markdown/reStructuredText 等文档文件、`docs/` 目录，
+//! 或源码中只修改了注释和文档字符串。纯文档变更强制使用 `docs` 类型，
+//! 避免 AI 把文档修改标成 feat/refactor。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+const DOC_EXTENSIONS: &[&str] = &[
+    "md", "mdx", "markdown", "rst", "adoc", "asciidoc", "txt", "org",
+];
+
+/// 没有扩展名也视为文档的文件名前缀
+const DOC_FILE_PREFIXES: &[&str] = &["README", "CHANGELOG", "CONTRIBUTING", "AUTHORS", "HISTORY"];
+
+const DOC_DIRS: &[&str] = &["docs", "doc"];
+
+/// 使用 `//`、`/* */` 注释的语言
+const SLASH_COMMENT_EXTENSIONS: &[&str] = &[
+    "rs", "go", "js", "jsx", "mjs", "ts", "tsx", "java", "kt", "kts", "c", "h", "cc", "cpp", "hpp",
+    "cs", "swift", "scala", "dart", "php",
+];
+
+/// 使用 `#` 注释的语言
+const HASH_COMMENT_EXTENSIONS: &[&str] = &["py", "rb", "sh", "bash", "zsh", "pl"];
+
+/// 是否为文档文件
+pub fn is_doc_file(path: &str) -> bool {
+    let parts: Vec<&str> = path.split('/').collect();
+    let file_name = parts.last().copied().unwrap_or(path);
+
+    if parts.len() > 1 && DOC_DIRS.contains(&parts[0]) {
+        return true;
+    }
+    if DOC_FILE_PREFIXES
+        .iter()
+        .any(|prefix| file_name.starts_with(prefix))
+    {
+        return true;
+    }
+    file_name
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| DOC_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommentStyle {
+    Slash,
+    Hash,
+}
+
+impl CommentStyle {
+    fn from_path(path: &str) -> Option<Self> {
+        let (_, ext) = path.rsplit_once('.')?;
+        if SLASH_COMMENT_EXTENSIONS.contains(&ext) {
+            Some(CommentStyle::Slash)
+        } else if HASH_COMMENT_EXTENSIONS.contains(&ext) {
+            Some(CommentStyle::Hash)
+        } else {
+            None
+        }
+    }
+}
+
+/// diff 是否只修改了文档（文档文件，或源码中的注释和文档字符串）
+pub fn is_docs_only(diff: &str) -> bool {
+    let mut files = 0;
+    let mut current: Option<FileCheck> = None;
+
+    for line in diff.lines() {
+        if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+            if let Some(check) = current.take() {
+                if !check.is_docs() {
+                    return false;
+                }
+            }
+            files += 1;
+            current = Some(FileCheck::new(&captures[2]));
+            continue;
+        }
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if let Some(check) = current.as_mut() {
+            check.push_line(line);
+        }
+    }
+
+    files > 0 && current.is_none_or(|check| check.is_docs())
+}
+
+/// 单个文件的检查状态
+struct FileCheck {
+    doc_file: bool,
+    style: Option<CommentStyle>,
+    in_docstring: bool,
+    doc_lines: usize,
+    code_lines: usize,
+}
+
+impl FileCheck {
+    fn new(path: &str) -> Self {
+        Self {
+            doc_file: is_doc_file(path),
+            style: CommentStyle::from_path(path),
+            in_docstring: false,
+            doc_lines: 0,
+            code_lines: 0,
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        if self.doc_file {
+            return;
+        }
+        if line.starts_with("@@") {
+            // 无法确定新 hunk 是否从文档字符串中间开始，按代码处理
+            self.in_docstring = false;
+            return;
+        }
+        let (changed, code) = if let Some(code) = line.strip_prefix(['+', '-']) {
+            (true, code)
+        } else if let Some(code) = line.strip_prefix(' ') {
+            (false, code)
+        } else {
+            return;
+        };
+
+        // 文档字符串的开闭状态只按新版本（上下文和新增行）维护
+        let is_doc = self.is_doc_line(code, !line.starts_with('-'));
+        if !changed || code.trim().is_empty() {
+            return;
+        }
+        if is_doc {
+            self.doc_lines += 1;
+        } else {
+            self.code_lines += 1;
+        }
+    }
+
+    /// 判断一行是否为注释或文档字符串，同时维护 Python 文档字符串的状态
+    fn is_doc_line(&mut self, code: &str, track_state: bool) -> bool {
+        let trimmed = code.trim();
+        match self.style {
+            Some(CommentStyle::Slash) => {
+                trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*')
+            }
+            Some(CommentStyle::Hash) => {
+                let quotes = trimmed.matches("\"\"\"").count() + trimmed.matches("'''").count();
+                let was_in_docstring = self.in_docstring;
+                if track_state && quotes % 2 == 1 {
+                    self.in_docstring = !self.in_docstring;
+                }
+                let opens_docstring = ["\"\"\"", "'''", "r\"\"\"", "r'''"]
+                    .iter()
+                    .any(|prefix| trimmed.starts_with(prefix));
+                was_in_docstring
+                    || opens_docstring
+                    || (trimmed.starts_with('#') && !trimmed.starts_with("#!"))
+            }
+            None => false,
+        }
+    }
+
+    fn is_docs(&self) -> bool {
+        self.doc_file || (self.doc_lines > 0 && self.code_lines == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_support::file_diff;
+
+    #[test]
+    fn test_is_doc_file() {
+        assert!(is_doc_file("README.md"));
+        assert!(is_doc_file("docs/guide/setup.rst"));
+        assert!(is_doc_file("docs/images/arch.png"));
+        assert!(is_doc_file("CHANGELOG"));
+        assert!(!is_doc_file("src/main.rs"));
+        assert!(!is_doc_file("src/docs.rs"));
+    }
+
+    #[test]
+    fn test_doc_files_only() {
+        let diff = format!(
+            "{}{}",
+            file_diff("README.md", "@@ -1 +1 @@\n-# Old\n+# New\n"),
+            file_diff("docs/usage.md", "@@ -3 +3 @@\n-foo\n+bar\n")
+        );
+        assert!(is_docs_only(&diff));
+
+        let mixed = format!(
+            "{}{}",
+            diff,
+            file_diff("src/lib.rs", "@@ -1 +1 @@\n-fn a() {}\n+fn b() {}\n")
+        );
+        assert!(!is_docs_only(&mixed));
+        assert!(!is_docs_only(""));
+    }
+
+    #[test]
+    fn test_comment_only_changes() {
+        let rust = file_diff(
+            "src/lib.rs",
+            "@@ -1,4 +1,5 @@\n\
+             -/// 旧的说明\n\
+             +/// 新的说明\n\
+             +///\n\
+             \x20pub fn run() {\n\
+             -    // TODO\n\
+             +\n",
+        );
+        assert!(is_docs_only(&rust));
+
+        let attribute = file_diff(
+            "src/lib.rs",
+            "@@ -1 +1 @@\n-#[derive(Debug)]\n+#[derive(Clone)]\n",
+        );
+        assert!(!is_docs_only(&attribute));
+
+        // 只有空行变化不算文档变更
+        let blank = file_diff("src/lib.rs", "@@ -1,2 +1,3 @@\n\x20fn a() {}\n+\n");
+        assert!(!is_docs_only(&blank));
+    }
+
+    #[test]
+    fn test_python_docstrings() {
+        let docstring = file_diff(
+            "app/models.py",
+            "@@ -10,6 +10,6 @@ class User:\n\
+             \x20    def save(self):\n\
+             \x20        \"\"\"Persist the user.\n\
+             \n\
+             -        Old details.\n\
+             +        New details.\n\
+             \x20        \"\"\"\n\
+             -        # old comment\n\
+             +        # new comment\n",
+        );
+        assert!(is_docs_only(&docstring));
+
+        let code = file_diff(
+            "app/models.py",
+            "@@ -10,3 +10,3 @@\n\
+             \x20        \"\"\"Persist the user.\"\"\"\n\
+             -        self.db.add(self)\n\
+             +        self.db.merge(self)\n",
+        );
+        assert!(!is_docs_only(&code));
+    }
+}
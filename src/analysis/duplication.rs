@@ -0,0 +1,582 @@
+//! 重复代码检测
+//!
+//! 以“有效行”（去掉空行、注释、纯括号行和 import 类语句）为单位，用滑动窗口查找重复片段：
+//! - 精确重复：去掉空白差异后完全相同；
+//! - 结构重复：把标识符和字面量替换为占位符后相同，即只有命名或常量不同的复制粘贴代码。
+//!
+//! 相邻的重复窗口会合并为尽可能长的代码块，同一文件内的重叠片段不会重复报告。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// 默认的最小重复行数（有效行）
+pub const DEFAULT_MIN_LINES: usize = 6;
+/// 重复块至少包含的词法单元数，过滤掉字段列表、简单赋值等短行
+const MIN_TOKENS: usize = 40;
+
+/// 代码词法单元：字符串、数字、标识符、注释起始和其他单个符号
+static TOKEN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#""(?:\\.|[^"\\])*"|'(?:\\.|[^'\\])*'|`[^`]*`|\d[\w.]*|[A-Za-z_$][\w$]*|//|\S"#)
+        .unwrap()
+});
+
+/// 结构比较时保留的关键字，其余标识符统一替换
+const KEYWORDS: &[&str] = &[
+    "if",
+    "else",
+    "for",
+    "while",
+    "loop",
+    "do",
+    "switch",
+    "case",
+    "match",
+    "when",
+    "return",
+    "break",
+    "continue",
+    "try",
+    "catch",
+    "finally",
+    "throw",
+    "fn",
+    "func",
+    "function",
+    "def",
+    "fun",
+    "let",
+    "const",
+    "var",
+    "val",
+    "mut",
+    "pub",
+    "static",
+    "async",
+    "await",
+    "new",
+    "in",
+    "class",
+    "struct",
+    "enum",
+    "impl",
+    "interface",
+    "yield",
+    "and",
+    "or",
+    "not",
+    "true",
+    "false",
+    "null",
+    "nil",
+    "None",
+    "self",
+    "this",
+];
+
+/// 不计入重复检测的语句前缀（导入、包声明等天然重复的行）
+const IGNORED_PREFIXES: &[&str] = &[
+    "use ",
+    "import ",
+    "from ",
+    "package ",
+    "#include",
+    "using ",
+    "mod ",
+    "extern crate",
+];
+
+/// 重复检测支持的源文件扩展名
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "rs", "go", "js", "jsx", "mjs", "cjs", "ts", "tsx", "c", "h", "cc", "cpp", "cxx", "hpp", "cs",
+    "java", "kt", "py", "rb", "php", "swift", "scala",
+];
+
+/// 是否是重复检测支持的源文件
+pub fn is_supported(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+}
+
+/// 重复类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicationKind {
+    Exact,
+    Structural,
+}
+
+impl DuplicationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DuplicationKind::Exact => "exact",
+            DuplicationKind::Structural => "structural",
+        }
+    }
+}
+
+/// 重复片段出现的位置（行号从 1 开始，包含两端）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fragment {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+impl Fragment {
+    pub fn location(&self) -> String {
+        format!("{}:{}-{}", self.file, self.start_line, self.end_line)
+    }
+}
+
+/// 一组互相重复的代码片段
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateBlock {
+    pub kind: DuplicationKind,
+    /// 每个片段包含的有效行数
+    pub lines: usize,
+    pub fragments: Vec<Fragment>,
+    /// 第一个片段的首行，便于在报告中识别
+    pub preview: String,
+}
+
+impl DuplicateBlock {
+    /// 是否跨文件重复
+    pub fn is_cross_file(&self) -> bool {
+        self.fragments
+            .iter()
+            .any(|fragment| fragment.file != self.fragments[0].file)
+    }
+
+    /// 可通过提取消除的冗余行数
+    pub fn redundant_lines(&self) -> usize {
+        self.lines * (self.fragments.len() - 1)
+    }
+}
+
+/// 文件级别的重复统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileDuplication {
+    pub file: String,
+    pub duplicated_lines: usize,
+    pub blocks: usize,
+}
+
+/// 一组文件的重复检测结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DuplicationReport {
+    pub files_analyzed: usize,
+    /// 所有文件的有效行数
+    pub total_lines: usize,
+    pub min_lines: usize,
+    pub blocks: Vec<DuplicateBlock>,
+}
+
+/// 单个有效行
+struct Line {
+    number: usize,
+    exact: String,
+    structural: String,
+    tokens: usize,
+}
+
+impl DuplicationReport {
+    /// 检测一组文件（路径，源码）中的重复代码
+    pub fn analyze(sources: &[(String, String)], min_lines: usize) -> Self {
+        let min_lines = min_lines.max(2);
+        let files: Vec<Vec<Line>> = sources
+            .iter()
+            .map(|(_, source)| significant_lines(source))
+            .collect();
+
+        let mut report = Self {
+            files_analyzed: sources.len(),
+            total_lines: files.iter().map(Vec::len).sum(),
+            min_lines,
+            blocks: Vec::new(),
+        };
+        report.blocks = find_blocks(sources, &files, min_lines);
+        report
+            .blocks
+            .sort_by_key(|block| std::cmp::Reverse(block.redundant_lines()));
+        report
+    }
+
+    /// 按冗余行数从多到少排列的前 `limit` 个重复块
+    pub fn hotspots(&self, limit: usize) -> Vec<&DuplicateBlock> {
+        self.blocks.iter().take(limit).collect()
+    }
+
+    pub fn cross_file_blocks(&self) -> usize {
+        self.blocks.iter().filter(|b| b.is_cross_file()).count()
+    }
+
+    /// 可通过提取消除的冗余行总数
+    pub fn duplicated_lines(&self) -> usize {
+        self.blocks
+            .iter()
+            .map(DuplicateBlock::redundant_lines)
+            .sum()
+    }
+
+    /// 冗余行占有效行的比例（百分比）
+    pub fn duplication_ratio(&self) -> f64 {
+        if self.total_lines == 0 {
+            0.0
+        } else {
+            self.duplicated_lines() as f64 * 100.0 / self.total_lines as f64
+        }
+    }
+
+    /// 按重复行数排序的文件列表
+    pub fn files(&self) -> Vec<FileDuplication> {
+        let mut files: BTreeMap<&str, FileDuplication> = BTreeMap::new();
+        for block in &self.blocks {
+            for fragment in &block.fragments {
+                let entry =
+                    files
+                        .entry(fragment.file.as_str())
+                        .or_insert_with(|| FileDuplication {
+                            file: fragment.file.clone(),
+                            duplicated_lines: 0,
+                            blocks: 0,
+                        });
+                entry.duplicated_lines += block.lines;
+                entry.blocks += 1;
+            }
+        }
+
+        let mut files: Vec<FileDuplication> = files.into_values().collect();
+        files.sort_by_key(|file| std::cmp::Reverse(file.duplicated_lines));
+        files
+    }
+
+    /// 针对排名靠前的重复块给出重构建议
+    pub fn recommendations(&self, limit: usize) -> Vec<String> {
+        self.hotspots(limit)
+            .into_iter()
+            .map(|block| {
+                let locations: Vec<String> =
+                    block.fragments.iter().map(Fragment::location).collect();
+                let advice = match (block.kind, block.is_cross_file()) {
+                    (DuplicationKind::Exact, true) => "提取到公共模块中复用",
+                    (DuplicationKind::Exact, false) => "提取为同一文件内的辅助函数",
+                    (DuplicationKind::Structural, _) => {
+                        "仅命名或常量不同，可提取为带参数的通用函数"
+                    }
+                };
+                format!(
+                    "{} 行{}重复（{}）：{}",
+                    block.lines,
+                    if block.kind == DuplicationKind::Exact {
+                        "完全"
+                    } else {
+                        "结构"
+                    },
+                    locations.join(", "),
+                    advice
+                )
+            })
+            .collect()
+    }
+}
+
+/// 提取有效行：去掉空行、注释、纯符号行和导入语句
+fn significant_lines(source: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut in_block_comment = false;
+
+    for (index, raw) in source.lines().enumerate() {
+        let trimmed = raw.trim();
+        if in_block_comment {
+            if trimmed.contains("*/") {
+                in_block_comment = false;
+            }
+            continue;
+        }
+        if trimmed.starts_with("/*") {
+            in_block_comment = !trimmed.contains("*/");
+            continue;
+        }
+        if trimmed.is_empty()
+            || trimmed.starts_with("//")
+            || trimmed.starts_with('#') && !trimmed.starts_with("#include")
+            || trimmed.starts_with('*')
+            || IGNORED_PREFIXES.iter().any(|p| trimmed.starts_with(p))
+        {
+            continue;
+        }
+
+        let tokens: Vec<&str> = TOKEN_REGEX
+            .find_iter(trimmed)
+            .map(|m| m.as_str())
+            .take_while(|token| *token != "//")
+            .collect();
+        // 只有括号和分隔符的行（如 `}`、`});`）不参与比较
+        if tokens
+            .iter()
+            .all(|token| token.chars().all(|c| "{}()[];,".contains(c)))
+        {
+            continue;
+        }
+
+        let structural: Vec<&str> = tokens.iter().map(|token| normalize_token(token)).collect();
+        lines.push(Line {
+            number: index + 1,
+            exact: tokens.join(" "),
+            structural: structural.join(" "),
+            tokens: tokens.len(),
+        });
+    }
+
+    lines
+}
+
+fn normalize_token(token: &str) -> &str {
+    let first = token.chars().next().unwrap_or_default();
+    if matches!(first, '"' | '\'' | '`') || first.is_ascii_digit() {
+        "LIT"
+    } else if (first.is_alphabetic() || first == '_' || first == '$') && !KEYWORDS.contains(&token)
+    {
+        "ID"
+    } else {
+        token
+    }
+}
+
+/// 用滑动窗口查找重复块，相邻窗口合并为最长片段
+fn find_blocks(
+    sources: &[(String, String)],
+    files: &[Vec<Line>],
+    min_lines: usize,
+) -> Vec<DuplicateBlock> {
+    let window_key = |file: usize, start: usize| -> Option<String> {
+        let lines = files[file].get(start..start + min_lines)?;
+        Some(
+            lines
+                .iter()
+                .map(|line| line.structural.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    };
+
+    let mut windows: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+    for (file, lines) in files.iter().enumerate() {
+        for start in 0..lines.len().saturating_sub(min_lines - 1) {
+            if is_repetitive(&lines[start..start + min_lines]) {
+                continue;
+            }
+            if let Some(key) = window_key(file, start) {
+                windows.entry(key).or_default().push((file, start));
+            }
+        }
+    }
+
+    let mut consumed: HashSet<(usize, usize)> = HashSet::new();
+    let mut blocks = Vec::new();
+
+    for (file, lines) in files.iter().enumerate() {
+        for start in 0..lines.len() {
+            if consumed.contains(&(file, start)) {
+                continue;
+            }
+            let Some(key) = window_key(file, start) else {
+                break;
+            };
+            let Some(candidates) = windows.get(&key) else {
+                continue;
+            };
+            // 已归入更早重复块的位置不再参与，避免错开一行的同一片段被重复报告
+            let candidates: Vec<(usize, usize)> = candidates
+                .iter()
+                .filter(|position| !consumed.contains(position))
+                .copied()
+                .collect();
+            let occurrences = non_overlapping(&candidates, min_lines);
+            if occurrences.len() < 2 || occurrences[0] != (file, start) {
+                continue;
+            }
+
+            // 所有片段的下一行结构相同且不重叠时继续延长
+            let mut length = min_lines;
+            loop {
+                let next: Vec<Option<&str>> = occurrences
+                    .iter()
+                    .map(|(f, s)| files[*f].get(s + length).map(|l| l.structural.as_str()))
+                    .collect();
+                let extendable = next[0].is_some()
+                    && next.iter().all(|line| *line == next[0])
+                    && occurrences
+                        .windows(2)
+                        .all(|pair| pair[0].0 != pair[1].0 || pair[0].1 + length < pair[1].1);
+                if !extendable {
+                    break;
+                }
+                length += 1;
+            }
+
+            let tokens: usize = lines[start..start + length].iter().map(|l| l.tokens).sum();
+            if tokens < MIN_TOKENS {
+                continue;
+            }
+
+            for (f, s) in &occurrences {
+                for offset in 0..=length - min_lines {
+                    consumed.insert((*f, s + offset));
+                }
+            }
+
+            let exact = |f: usize, s: usize| -> Vec<&str> {
+                files[f][s..s + length]
+                    .iter()
+                    .map(|line| line.exact.as_str())
+                    .collect()
+            };
+            let first = exact(occurrences[0].0, occurrences[0].1);
+            let kind = if occurrences.iter().all(|(f, s)| exact(*f, *s) == first) {
+                DuplicationKind::Exact
+            } else {
+                DuplicationKind::Structural
+            };
+
+            blocks.push(DuplicateBlock {
+                kind,
+                lines: length,
+                fragments: occurrences
+                    .iter()
+                    .map(|(f, s)| Fragment {
+                        file: sources[*f].0.clone(),
+                        start_line: files[*f][*s].number,
+                        end_line: files[*f][s + length - 1].number,
+                    })
+                    .collect(),
+                preview: first[0].to_string(),
+            });
+        }
+    }
+
+    blocks
+}
+
+/// 结构相同的行占多数的窗口（如字段列表、连续赋值）不视为可提取的重复
+fn is_repetitive(window: &[Line]) -> bool {
+    let distinct: HashSet<&str> = window.iter().map(|l| l.structural.as_str()).collect();
+    distinct.len() * 2 < window.len()
+}
+
+/// 去掉与前一个片段重叠的位置（同一文件内的重复行会产生大量相邻窗口）
+fn non_overlapping(occurrences: &[(usize, usize)], min_lines: usize) -> Vec<(usize, usize)> {
+    let mut result: Vec<(usize, usize)> = Vec::new();
+    for &(file, start) in occurrences {
+        let overlaps = result
+            .last()
+            .is_some_and(|&(f, s)| f == file && start < s + min_lines);
+        if !overlaps {
+            result.push((file, start));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORDER_TOTAL: &str = "fn order_total(items: &[Item]) -> u64 {
+    let mut total = 0;
+    for item in items {
+        if item.quantity > 0 {
+            total += item.price * item.quantity;
+        }
+    }
+    log::debug!(\"total {}\", total);
+    total
+}
+";
+
+    fn sources(files: &[(&str, &str)]) -> Vec<(String, String)> {
+        files
+            .iter()
+            .map(|(path, source)| (path.to_string(), source.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_exact_duplicate_across_files() {
+        let report = DuplicationReport::analyze(
+            &sources(&[
+                ("src/a.rs", ORDER_TOTAL),
+                ("src/b.rs", &format!("use crate::Item;\n\n{}", ORDER_TOTAL)),
+            ]),
+            DEFAULT_MIN_LINES,
+        );
+
+        assert_eq!(report.blocks.len(), 1);
+        let block = &report.blocks[0];
+        assert_eq!(block.kind, DuplicationKind::Exact);
+        assert!(block.is_cross_file());
+        assert_eq!(block.lines, 7);
+        assert_eq!(block.fragments[0].location(), "src/a.rs:1-9");
+        assert_eq!(block.fragments[1].location(), "src/b.rs:3-11");
+        assert_eq!(report.duplicated_lines(), 7);
+        assert!(report.recommendations(5)[0].contains("公共模块"));
+    }
+
+    #[test]
+    fn test_structural_duplicate_in_same_file() {
+        let renamed = ORDER_TOTAL
+            .replace("order_total", "cart_total")
+            .replace("total", "sum")
+            .replace("0", "1")
+            .replace("\"total {}\"", "\"sum {}\"");
+        let report = DuplicationReport::analyze(
+            &sources(&[("src/a.rs", &format!("{}\n{}", ORDER_TOTAL, renamed))]),
+            DEFAULT_MIN_LINES,
+        );
+
+        assert_eq!(report.blocks.len(), 1);
+        assert_eq!(report.blocks[0].kind, DuplicationKind::Structural);
+        assert!(!report.blocks[0].is_cross_file());
+        assert_eq!(report.files()[0].blocks, 2);
+        assert!(report.recommendations(5)[0].contains("带参数"));
+    }
+
+    #[test]
+    fn test_repeated_lines_do_not_overlap() {
+        let source = "x = 1\n".repeat(12);
+        let report = DuplicationReport::analyze(&sources(&[("a.py", &source)]), 4);
+
+        for block in &report.blocks {
+            let fragments = &block.fragments;
+            for pair in fragments.windows(2) {
+                assert!(pair[0].end_line < pair[1].start_line);
+            }
+        }
+        assert!(report.duplication_ratio() <= 100.0);
+    }
+
+    #[test]
+    fn test_short_and_repetitive_blocks_ignored() {
+        let fields = "pub a: String,\npub b: String,\npub c: u32,\npub d: u32,\npub e: bool,\npub f: bool,\n";
+        let report = DuplicationReport::analyze(
+            &sources(&[("src/a.rs", fields), ("src/b.rs", fields)]),
+            DEFAULT_MIN_LINES,
+        );
+        assert!(report.blocks.is_empty());
+    }
+
+    #[test]
+    fn test_significant_lines() {
+        let lines = significant_lines(
+            "use std::fmt;\n// comment\n/* block\n  comment */\nlet a = \"x\"; // trailing\n}\n",
+        );
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].number, 5);
+        assert_eq!(lines[0].exact, "let a = \"x\" ;");
+        assert_eq!(lines[0].structural, "let ID = LIT ;");
+    }
+}
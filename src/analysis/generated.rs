@@ -0,0 +1,279 @@
+//! 二进制文件、锁文件和生成代码的识别
+//!
+//! 这些文件的 diff 内容对生成提交消息没有帮助，反而会挤占提示词长度。
+//! 从 diff 中剔除后改为一句摘要，例如「更新锁文件 Cargo.lock；2 个二进制文件」。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+/// 生成代码文件头部的标记
+static GENERATED_MARKER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Code generated .*DO NOT EDIT|@generated\b|<auto-generated|This file is automatically generated").unwrap()
+});
+
+const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "poetry.lock",
+    "Pipfile.lock",
+    "uv.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+    "flake.lock",
+    "mix.lock",
+    "pubspec.lock",
+    "Podfile.lock",
+];
+
+/// 代码生成工具的输出文件后缀（protobuf、gRPC、Dart build_runner 等）
+const GENERATED_SUFFIXES: &[&str] = &[
+    ".pb.go",
+    ".pb.h",
+    ".pb.cc",
+    ".pb.ts",
+    "_pb.js",
+    "_pb.d.ts",
+    "_pb2.py",
+    "_pb2.pyi",
+    "_pb2_grpc.py",
+    ".g.dart",
+    ".freezed.dart",
+    ".gen.go",
+    ".generated.ts",
+    ".generated.cs",
+];
+
+const MINIFIED_SUFFIXES: &[&str] = &[".min.js", ".min.mjs", ".min.css"];
+
+/// 超过该长度的新增行视为压缩后的代码
+const MINIFIED_LINE_LENGTH: usize = 1000;
+
+/// 生成代码标记只在文件开头几行中查找
+const MARKER_SCAN_LINES: usize = 5;
+
+/// 不需要 AI 阅读内容的文件类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseKind {
+    Binary,
+    Lockfile,
+    Generated,
+    Minified,
+}
+
+impl NoiseKind {
+    pub fn description(&self) -> &'static str {
+        match self {
+            NoiseKind::Binary => "二进制文件",
+            NoiseKind::Lockfile => "锁文件",
+            NoiseKind::Generated => "生成代码文件",
+            NoiseKind::Minified => "压缩文件",
+        }
+    }
+}
+
+/// 从 diff 中剔除的文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoiseFile {
+    pub path: String,
+    pub kind: NoiseKind,
+}
+
+/// 是否为依赖锁文件
+pub fn is_lockfile(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    LOCKFILES.contains(&file_name)
+}
+
+/// 只根据路径判断文件类别
+fn kind_for_path(path: &str) -> Option<NoiseKind> {
+    if is_lockfile(path) {
+        Some(NoiseKind::Lockfile)
+    } else if GENERATED_SUFFIXES
+        .iter()
+        .any(|suffix| path.ends_with(suffix))
+    {
+        Some(NoiseKind::Generated)
+    } else if MINIFIED_SUFFIXES
+        .iter()
+        .any(|suffix| path.ends_with(suffix))
+    {
+        Some(NoiseKind::Minified)
+    } else {
+        None
+    }
+}
+
+/// 根据路径和该文件的 diff 片段判断文件类别
+fn classify(path: &str, section: &[&str]) -> Option<NoiseKind> {
+    if section
+        .iter()
+        .any(|line| line.starts_with("Binary files ") || *line == "GIT binary patch")
+    {
+        return Some(NoiseKind::Binary);
+    }
+    if let Some(kind) = kind_for_path(path) {
+        return Some(kind);
+    }
+
+    let added: Vec<&str> = section
+        .iter()
+        .filter(|line| line.starts_with('+') && !line.starts_with("+++"))
+        .map(|line| &line[1..])
+        .collect();
+    if added
+        .iter()
+        .take(MARKER_SCAN_LINES)
+        .any(|line| GENERATED_MARKER_REGEX.is_match(line))
+    {
+        return Some(NoiseKind::Generated);
+    }
+    let is_web_asset = [".js", ".mjs", ".css"]
+        .iter()
+        .any(|ext| path.ends_with(ext));
+    if is_web_asset && added.iter().any(|line| line.len() > MINIFIED_LINE_LENGTH) {
+        return Some(NoiseKind::Minified);
+    }
+    None
+}
+
+/// 把 diff 拆成需要 AI 阅读的部分和被剔除的文件列表
+pub fn split_noise(diff: &str) -> (String, Vec<NoiseFile>) {
+    let mut kept = String::new();
+    let mut noise = Vec::new();
+
+    let mut flush = |section: &mut Vec<&str>, path: Option<&str>| {
+        if section.is_empty() {
+            return;
+        }
+        match path.and_then(|path| classify(path, section).map(|kind| (path, kind))) {
+            Some((path, kind)) => noise.push(NoiseFile {
+                path: path.to_string(),
+                kind,
+            }),
+            None => {
+                for line in section.iter() {
+                    kept.push_str(line);
+                    kept.push('\n');
+                }
+            }
+        }
+        section.clear();
+    };
+
+    let mut section: Vec<&str> = Vec::new();
+    let mut path: Option<&str> = None;
+    for line in diff.lines() {
+        if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+            flush(&mut section, path);
+            path = captures.get(2).map(|m| m.as_str());
+        }
+        section.push(line);
+    }
+    flush(&mut section, path);
+
+    (kept, noise)
+}
+
+/// 被剔除文件的摘要，例如「更新锁文件 Cargo.lock；2 个二进制文件」
+pub fn summarize(files: &[NoiseFile]) -> Option<String> {
+    if files.is_empty() {
+        return None;
+    }
+
+    let mut parts = Vec::new();
+    let lockfiles: Vec<&str> = files
+        .iter()
+        .filter(|file| file.kind == NoiseKind::Lockfile)
+        .map(|file| file.path.as_str())
+        .collect();
+    if !lockfiles.is_empty() {
+        parts.push(format!("更新锁文件 {}", lockfiles.join("、")));
+    }
+    for kind in [NoiseKind::Generated, NoiseKind::Binary, NoiseKind::Minified] {
+        let count = files.iter().filter(|file| file.kind == kind).count();
+        if count > 0 {
+            parts.push(format!("{} 个{}", count, kind.description()));
+        }
+    }
+    Some(parts.join("；"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_support::file_diff;
+
+    #[test]
+    fn test_split_noise() {
+        let source = file_diff("src/main.rs", "@@ -1 +1 @@\n-fn a() {}\n+fn b() {}\n");
+        let diff = format!(
+            "{}{}{}{}",
+            file_diff("Cargo.lock", "@@ -1 +1 @@\n-version = \"1.0.0\"\n+version = \"1.0.1\"\n"),
+            source,
+            "diff --git a/assets/logo.png b/assets/logo.png\n\
+             index 1234567..89abcde 100644\n\
+             Binary files a/assets/logo.png and b/assets/logo.png differ\n",
+            file_diff(
+                "api/user.go",
+                "@@ -0,0 +1,2 @@\n+// Code generated by protoc-gen-go. DO NOT EDIT.\n+package api\n"
+            ),
+        );
+
+        let (kept, noise) = split_noise(&diff);
+        assert_eq!(kept, source);
+        assert_eq!(
+            noise,
+            vec![
+                NoiseFile {
+                    path: "Cargo.lock".to_string(),
+                    kind: NoiseKind::Lockfile
+                },
+                NoiseFile {
+                    path: "assets/logo.png".to_string(),
+                    kind: NoiseKind::Binary
+                },
+                NoiseFile {
+                    path: "api/user.go".to_string(),
+                    kind: NoiseKind::Generated
+                },
+            ]
+        );
+        assert_eq!(
+            summarize(&noise).as_deref(),
+            Some("更新锁文件 Cargo.lock；1 个生成代码文件；1 个二进制文件")
+        );
+    }
+
+    #[test]
+    fn test_path_and_minified_detection() {
+        assert_eq!(
+            kind_for_path("web/package-lock.json"),
+            Some(NoiseKind::Lockfile)
+        );
+        assert_eq!(
+            kind_for_path("proto/user.pb.go"),
+            Some(NoiseKind::Generated)
+        );
+        assert_eq!(
+            kind_for_path("static/app.min.js"),
+            Some(NoiseKind::Minified)
+        );
+        assert_eq!(kind_for_path("src/lib.rs"), None);
+
+        let bundle = format!("+{}", "var a=1;".repeat(200));
+        assert_eq!(
+            classify("static/bundle.js", &["@@ -0,0 +1 @@", &bundle]),
+            Some(NoiseKind::Minified)
+        );
+        assert_eq!(classify("src/app.js", &["+const a = 1;"]), None);
+        assert_eq!(summarize(&[]), None);
+    }
+}
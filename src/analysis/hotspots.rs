@@ -0,0 +1,255 @@
+//! 变更频率与复杂度热点
+//!
+//! 用 `git log --numstat` 统计每个文件在一段时间内的修改次数和修改行数（churn），
+//! 再对这些文件做复杂度分析。经常修改且复杂度高的文件最容易引入缺陷，
+//! 按两项指标的归一化乘积排序，作为重构和加强审查的优先级参考。
+
+use crate::analysis::complexity::{self, ComplexityReport, ComplexityThresholds};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use tokio::process::Command;
+
+/// 默认统计的时间范围
+pub const DEFAULT_SINCE: &str = "12 months ago";
+
+/// 得分不低于该值为高风险
+const HIGH_RISK_SCORE: f64 = 50.0;
+/// 得分不低于该值为中风险
+const MEDIUM_RISK_SCORE: f64 = 20.0;
+
+/// 单个文件的修改统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChurn {
+    pub file: String,
+    /// 修改过该文件的提交数
+    pub commits: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl FileChurn {
+    pub fn lines_changed(&self) -> usize {
+        self.added + self.removed
+    }
+}
+
+/// 解析 `git log --numstat --format=` 的输出，结果按提交数从多到少排列
+///
+/// 二进制文件的行数为 `-`，只计入提交数。
+pub fn parse_numstat(output: &str) -> Vec<FileChurn> {
+    let mut churn: HashMap<&str, FileChurn> = HashMap::new();
+    for line in output.lines() {
+        let mut parts = line.splitn(3, '\t');
+        let (Some(added), Some(removed), Some(file)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let entry = churn.entry(file).or_insert_with(|| FileChurn {
+            file: file.to_string(),
+            ..Default::default()
+        });
+        entry.commits += 1;
+        entry.added += added.parse::<usize>().unwrap_or(0);
+        entry.removed += removed.parse::<usize>().unwrap_or(0);
+    }
+
+    let mut churn: Vec<FileChurn> = churn.into_values().collect();
+    churn.sort_by(|a, b| b.commits.cmp(&a.commits).then(a.file.cmp(&b.file)));
+    churn
+}
+
+/// 统计指定时间以来路径下文件的修改情况，路径相对于当前目录
+pub async fn churn_since(since: &str, paths: &[String]) -> anyhow::Result<Vec<FileChurn>> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--no-merges",
+            "--no-renames",
+            "--relative",
+            "--numstat",
+            "--format=",
+            &format!("--since={}", since),
+            "--",
+        ])
+        .args(paths)
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read git history: {}", e))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to read git history since {}: {}",
+            since,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(parse_numstat(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 热点文件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hotspot {
+    pub file: String,
+    pub commits: usize,
+    pub lines_changed: usize,
+    pub functions: usize,
+    /// 文件中所有函数的圈复杂度之和
+    pub total_cyclomatic: u32,
+    pub max_cyclomatic: u32,
+    /// 0–100，修改频率与复杂度归一化后的乘积
+    pub score: f64,
+}
+
+impl Hotspot {
+    pub fn risk(&self) -> &'static str {
+        if self.score >= HIGH_RISK_SCORE {
+            "high"
+        } else if self.score >= MEDIUM_RISK_SCORE {
+            "medium"
+        } else {
+            "low"
+        }
+    }
+}
+
+/// 热点分析结果，按得分从高到低排列
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HotspotReport {
+    pub since: String,
+    /// 时间范围内修改过的文件数
+    pub files_changed: usize,
+    pub hotspots: Vec<Hotspot>,
+}
+
+impl HotspotReport {
+    /// 合并修改统计和复杂度：只保留仍然存在且能分析出函数的文件
+    pub fn build(since: &str, churn: &[FileChurn], complexity: &ComplexityReport) -> Self {
+        let mut per_file: HashMap<&str, (usize, u32, u32)> = HashMap::new();
+        for function in &complexity.functions {
+            let entry = per_file.entry(function.file.as_str()).or_default();
+            entry.0 += 1;
+            entry.1 += function.cyclomatic;
+            entry.2 = entry.2.max(function.cyclomatic);
+        }
+
+        let candidates: Vec<(&FileChurn, (usize, u32, u32))> = churn
+            .iter()
+            .filter_map(|c| Some((c, *per_file.get(c.file.as_str())?)))
+            .collect();
+        let max_commits = candidates.iter().map(|(c, _)| c.commits).max().unwrap_or(0);
+        let max_complexity = candidates.iter().map(|(_, m)| m.1).max().unwrap_or(0);
+
+        let mut hotspots: Vec<Hotspot> = candidates
+            .into_iter()
+            .map(|(churn, (functions, total, max))| {
+                let churn_ratio = churn.commits as f64 / max_commits.max(1) as f64;
+                let complexity_ratio = total as f64 / max_complexity.max(1) as f64;
+                Hotspot {
+                    file: churn.file.clone(),
+                    commits: churn.commits,
+                    lines_changed: churn.lines_changed(),
+                    functions,
+                    total_cyclomatic: total,
+                    max_cyclomatic: max,
+                    score: (churn_ratio * complexity_ratio * 1000.0).round() / 10.0,
+                }
+            })
+            .collect();
+        hotspots.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then(b.commits.cmp(&a.commits))
+                .then(a.file.cmp(&b.file))
+        });
+
+        Self {
+            since: since.to_string(),
+            files_changed: churn.len(),
+            hotspots,
+        }
+    }
+
+    pub fn high_risk(&self) -> usize {
+        self.hotspots.iter().filter(|h| h.risk() == "high").count()
+    }
+
+    /// 针对高风险文件给出的建议
+    pub fn recommendations(&self, limit: usize) -> Vec<String> {
+        self.hotspots
+            .iter()
+            .filter(|h| h.risk() == "high")
+            .take(limit)
+            .map(|h| {
+                format!(
+                    "{}：{} 次修改，圈复杂度合计 {}（最高 {}），建议优先拆分复杂函数并补充测试",
+                    h.file, h.commits, h.total_cyclomatic, h.max_cyclomatic
+                )
+            })
+            .collect()
+    }
+}
+
+/// 统计修改情况并分析这些文件当前的复杂度
+pub async fn analyze(since: &str, paths: &[String]) -> anyhow::Result<HotspotReport> {
+    let churn = churn_since(since, paths).await?;
+    let mut complexity = ComplexityReport::new(ComplexityThresholds::default());
+    for file in &churn {
+        let path = Path::new(&file.file);
+        if !complexity::is_supported(path) {
+            continue;
+        }
+        // 已删除的文件和非 UTF-8 文件跳过
+        if let Ok(source) = tokio::fs::read_to_string(path).await {
+            complexity.add_source(&file.file, &source);
+        }
+    }
+    Ok(HotspotReport::build(since, &churn, &complexity))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_numstat() {
+        let output = "10\t2\tsrc/main.rs\n1\t1\tsrc/lib.rs\n\n3\t0\tsrc/main.rs\n-\t-\tlogo.png\n";
+        let churn = parse_numstat(output);
+        assert_eq!(churn.len(), 3);
+        assert_eq!(churn[0].file, "src/main.rs");
+        assert_eq!(churn[0].commits, 2);
+        assert_eq!(churn[0].lines_changed(), 15);
+        assert_eq!(churn[1].file, "logo.png");
+        assert_eq!(churn[1].lines_changed(), 0);
+    }
+
+    #[test]
+    fn test_hotspots_rank_churn_and_complexity() {
+        let churn = parse_numstat(
+            "5\t1\tsrc/busy.rs\n5\t1\tsrc/busy.rs\n5\t1\tsrc/busy.rs\n\
+             2\t0\tsrc/calm.rs\n1\t0\tsrc/gone.rs\n1\t0\tREADME.md\n",
+        );
+        let mut complexity = ComplexityReport::new(ComplexityThresholds::default());
+        complexity.add_source(
+            "src/busy.rs",
+            "fn pick(a: bool, b: bool) -> u8 {\n    if a && b {\n        1\n    } else if a {\n        2\n    } else {\n        3\n    }\n}\n",
+        );
+        complexity.add_source("src/calm.rs", "fn id(x: u8) -> u8 {\n    x\n}\n");
+
+        let report = HotspotReport::build(DEFAULT_SINCE, &churn, &complexity);
+        assert_eq!(report.files_changed, 4);
+        assert_eq!(report.hotspots.len(), 2);
+
+        let busy = &report.hotspots[0];
+        assert_eq!(busy.file, "src/busy.rs");
+        assert_eq!(busy.commits, 3);
+        assert_eq!(busy.score, 100.0);
+        assert_eq!(busy.risk(), "high");
+
+        let calm = &report.hotspots[1];
+        assert!(calm.score < MEDIUM_RISK_SCORE);
+        assert_eq!(calm.risk(), "low");
+        assert_eq!(report.high_risk(), 1);
+        assert_eq!(report.recommendations(5).len(), 1);
+    }
+}
@@ -0,0 +1,695 @@
+//! 许可证变更检测
+//!
+//! 在 diff 中识别三类与许可证合规相关的变更：
+//! - 许可证文件（LICENSE、COPYING、NOTICE 等）被新增、删除或改写；
+//! - Cargo.toml / package.json 中项目自身的 `license` 字段被修改；
+//! - 新引入的依赖使用了与项目许可证不兼容的许可证（如 MIT 项目引入 GPL 依赖）。
+//!
+//! 依赖的许可证优先从 diff 本身读取（package-lock.json 记录了 `license`），
+//! 否则从本地的 cargo registry 缓存或 node_modules 中查找，查不到时不报告。
+
+use crate::report::{ReviewFinding, Severity};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+/// Cargo.toml 的 `license = "..."`
+static TOML_LICENSE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*license\s*=\s*"([^"]+)""#).unwrap());
+
+/// package.json / package-lock.json 的 `"license": "..."`
+static JSON_LICENSE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*"license"\s*:\s*"([^"]+)""#).unwrap());
+
+static TOML_NAME_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*name\s*=\s*"([^"]+)""#).unwrap());
+
+static TOML_VERSION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*version\s*=\s*"([^"]+)""#).unwrap());
+
+/// package-lock.json v2+ 的 `"node_modules/<name>": {`
+static NPM_PACKAGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*"(?:.*/)?node_modules/((?:@[^/"]+/)?[^/"]+)"\s*:\s*\{"#).unwrap()
+});
+
+static JSON_VERSION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*"version"\s*:\s*"([^"]+)""#).unwrap());
+
+/// 许可证类别，按限制从弱到强排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LicenseCategory {
+    Permissive,
+    /// LGPL、MPL 等文件级/库级 copyleft
+    WeakCopyleft,
+    /// GPL 等传染整个衍生作品的 copyleft
+    StrongCopyleft,
+    /// AGPL、SSPL、BUSL 等对网络服务或商业使用也有要求的许可证
+    Restrictive,
+    Unknown,
+}
+
+impl LicenseCategory {
+    pub fn description(&self) -> &'static str {
+        match self {
+            LicenseCategory::Permissive => "permissive",
+            LicenseCategory::WeakCopyleft => "weak copyleft",
+            LicenseCategory::StrongCopyleft => "strong copyleft",
+            LicenseCategory::Restrictive => "network/commercial restrictive",
+            LicenseCategory::Unknown => "unknown",
+        }
+    }
+}
+
+/// 对 SPDX 表达式分类：`OR` 取限制最弱的选项，`AND` 取限制最强的部分
+pub fn classify(expression: &str) -> LicenseCategory {
+    let expression = expression.replace(['(', ')'], " ");
+    let alternatives: Vec<LicenseCategory> = expression
+        .split(" OR ")
+        .flat_map(|alt| alt.split(" or "))
+        .flat_map(|alt| alt.split('/'))
+        .map(|alternative| {
+            alternative
+                .split(" AND ")
+                .map(classify_identifier)
+                .max()
+                .unwrap_or(LicenseCategory::Unknown)
+        })
+        .collect();
+
+    // 存在已知选项时忽略无法识别的选项
+    alternatives
+        .iter()
+        .copied()
+        .filter(|category| *category != LicenseCategory::Unknown)
+        .min()
+        .unwrap_or(LicenseCategory::Unknown)
+}
+
+fn classify_identifier(identifier: &str) -> LicenseCategory {
+    // `GPL-2.0 WITH Classpath-exception-2.0` 之类的例外条款按主许可证处理
+    let id = identifier
+        .split(" WITH ")
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_uppercase();
+
+    const PERMISSIVE: &[&str] = &[
+        "MIT",
+        "APACHE",
+        "BSD",
+        "0BSD",
+        "ISC",
+        "ZLIB",
+        "UNLICENSE",
+        "CC0",
+        "BSL-1.0",
+        "UNICODE",
+        "PYTHON",
+        "POSTGRESQL",
+        "X11",
+        "WTFPL",
+        "ARTISTIC",
+        "CC-BY-4.0",
+        "BLUEOAK",
+    ];
+
+    if id.starts_with("AGPL") || id.starts_with("SSPL") || id.starts_with("BUSL") {
+        LicenseCategory::Restrictive
+    } else if id.starts_with("LGPL")
+        || id.starts_with("MPL")
+        || id.starts_with("EPL")
+        || id.starts_with("CDDL")
+    {
+        LicenseCategory::WeakCopyleft
+    } else if id.starts_with("GPL") || id.starts_with("EUPL") || id.starts_with("OSL") {
+        LicenseCategory::StrongCopyleft
+    } else if PERMISSIVE.iter().any(|prefix| id.starts_with(prefix)) {
+        LicenseCategory::Permissive
+    } else {
+        LicenseCategory::Unknown
+    }
+}
+
+/// 从许可证正文识别 SPDX 标识
+pub fn identify_license_text(text: &str) -> Option<&'static str> {
+    let text = text.to_lowercase();
+    let version_3 = text.contains("version 3");
+
+    if text.contains("gnu affero general public license") {
+        Some("AGPL-3.0")
+    } else if text.contains("gnu lesser general public license") {
+        Some(if version_3 { "LGPL-3.0" } else { "LGPL-2.1" })
+    } else if text.contains("gnu general public license") {
+        Some(if version_3 { "GPL-3.0" } else { "GPL-2.0" })
+    } else if text.contains("server side public license") {
+        Some("SSPL-1.0")
+    } else if text.contains("business source license") {
+        Some("BUSL-1.1")
+    } else if text.contains("mozilla public license") {
+        Some("MPL-2.0")
+    } else if text.contains("apache license") {
+        Some("Apache-2.0")
+    } else if text.contains("mit license")
+        || text.contains("permission is hereby granted, free of charge")
+    {
+        Some("MIT")
+    } else if text.contains("redistribution and use in source and binary forms") {
+        Some(if text.contains("neither the name") {
+            "BSD-3-Clause"
+        } else {
+            "BSD-2-Clause"
+        })
+    } else if text.contains("isc license")
+        || text.contains("permission to use, copy, modify, and/or distribute")
+    {
+        Some("ISC")
+    } else if text.contains("this is free and unencumbered software") {
+        Some("Unlicense")
+    } else {
+        None
+    }
+}
+
+/// 是否是许可证相关文件
+pub fn is_license_file(path: &str) -> bool {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    ["license", "licence", "copying", "notice", "unlicense"]
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// 许可证问题类型
+#[derive(Debug, Clone, PartialEq)]
+pub enum LicenseIssueKind {
+    /// 新增许可证文件
+    LicenseFileAdded { license: Option<String> },
+    /// 删除许可证文件
+    LicenseFileRemoved { license: Option<String> },
+    /// 许可证文件改为另一种许可证
+    Relicensed { from: String, to: String },
+    /// 许可证文件内容被修改（未改变许可证类型）
+    LicenseFileModified,
+    /// 清单中项目许可证字段变更
+    ProjectLicense { from: Option<String>, to: String },
+    /// 新依赖的许可证与项目许可证不兼容
+    IncompatibleDependency {
+        name: String,
+        version: Option<String>,
+        license: String,
+    },
+}
+
+/// 检测到的许可证问题
+#[derive(Debug, Clone, PartialEq)]
+pub struct LicenseIssue {
+    pub kind: LicenseIssueKind,
+    pub severity: Severity,
+    pub file: String,
+}
+
+impl fmt::Display for LicenseIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |license: &Option<String>| license.clone().unwrap_or_else(|| "?".to_string());
+        match &self.kind {
+            LicenseIssueKind::LicenseFileAdded { license } => {
+                write!(f, "{}: 新增许可证文件（{}）", self.file, name(license))
+            }
+            LicenseIssueKind::LicenseFileRemoved { license } => {
+                write!(f, "{}: 删除许可证文件（{}）", self.file, name(license))
+            }
+            LicenseIssueKind::Relicensed { from, to } => {
+                write!(f, "{}: 许可证由 {} 变更为 {}", self.file, from, to)
+            }
+            LicenseIssueKind::LicenseFileModified => {
+                write!(f, "{}: 许可证文件内容被修改", self.file)
+            }
+            LicenseIssueKind::ProjectLicense { from, to } => write!(
+                f,
+                "{}: 项目许可证由 {} 变更为 {}",
+                self.file,
+                name(from),
+                to
+            ),
+            LicenseIssueKind::IncompatibleDependency {
+                name: package,
+                version,
+                license,
+            } => write!(
+                f,
+                "{}: 新依赖 {}{} 使用 {}（{}），与项目许可证可能不兼容",
+                self.file,
+                package,
+                version
+                    .as_ref()
+                    .map(|v| format!(" {}", v))
+                    .unwrap_or_default(),
+                license,
+                classify(license).description()
+            ),
+        }
+    }
+}
+
+impl LicenseIssue {
+    pub fn to_finding(&self) -> ReviewFinding {
+        ReviewFinding {
+            severity: self.severity,
+            category: "license".to_string(),
+            message: self.to_string(),
+            file: Some(self.file.clone()),
+            line: None,
+        }
+    }
+}
+
+/// diff 中新增的依赖
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewDependency {
+    pub ecosystem: Ecosystem,
+    pub name: String,
+    pub version: Option<String>,
+    /// diff 中直接给出的许可证（package-lock.json）
+    pub license: Option<String>,
+    pub file: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+}
+
+/// 项目许可证类别下，依赖许可证类别对应的问题严重程度；兼容时返回 `None`
+pub fn incompatibility(project: LicenseCategory, dependency: LicenseCategory) -> Option<Severity> {
+    use LicenseCategory::*;
+    match (project, dependency) {
+        (_, Unknown) => None,
+        // 项目许可证未知（可能是闭源项目）时按宽松许可证处理
+        (Permissive | Unknown | WeakCopyleft, StrongCopyleft | Restrictive) => Some(Severity::High),
+        (Permissive | Unknown, WeakCopyleft) => Some(Severity::Low),
+        (StrongCopyleft, Restrictive) => Some(Severity::Medium),
+        _ => None,
+    }
+}
+
+/// 按文件拆分 diff 后的单个文件变更
+struct FileDiff<'a> {
+    path: String,
+    added: Vec<&'a str>,
+    removed: Vec<&'a str>,
+}
+
+fn split_files(diff: &str) -> Vec<FileDiff<'_>> {
+    let mut files: Vec<FileDiff> = Vec::new();
+    for line in diff.lines() {
+        if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+            files.push(FileDiff {
+                path: captures[2].to_string(),
+                added: Vec::new(),
+                removed: Vec::new(),
+            });
+            continue;
+        }
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if let Some(added) = line.strip_prefix('+') {
+            file.added.push(added);
+        } else if let Some(removed) = line.strip_prefix('-') {
+            file.removed.push(removed);
+        }
+    }
+    files
+}
+
+/// 扫描 diff 中的许可证变更，`root` 为仓库根目录，用于读取项目许可证和本地依赖缓存
+pub fn scan_diff(diff: &str, root: &Path) -> Vec<LicenseIssue> {
+    scan_diff_with(diff, project_license(root).as_deref(), |dependency| {
+        lookup_dependency_license(dependency, root)
+    })
+}
+
+/// 使用给定的项目许可证和依赖许可证查询函数扫描 diff
+pub fn scan_diff_with(
+    diff: &str,
+    project_license: Option<&str>,
+    lookup: impl Fn(&NewDependency) -> Option<String>,
+) -> Vec<LicenseIssue> {
+    let mut issues = Vec::new();
+    let mut project_license = project_license.map(str::to_string);
+
+    for file in split_files(diff) {
+        if is_license_file(&file.path) {
+            let from = identify_license_text(&file.removed.join("\n")).map(str::to_string);
+            let to = identify_license_text(&file.added.join("\n")).map(str::to_string);
+            let (kind, severity) = if file.added.is_empty() && !file.removed.is_empty() {
+                (
+                    LicenseIssueKind::LicenseFileRemoved { license: from },
+                    Severity::High,
+                )
+            } else if file.removed.is_empty() && !file.added.is_empty() {
+                (
+                    LicenseIssueKind::LicenseFileAdded { license: to },
+                    Severity::Medium,
+                )
+            } else {
+                match (from, to) {
+                    (Some(from), Some(to)) if from != to => {
+                        (LicenseIssueKind::Relicensed { from, to }, Severity::High)
+                    }
+                    _ => (LicenseIssueKind::LicenseFileModified, Severity::Low),
+                }
+            };
+            issues.push(LicenseIssue {
+                kind,
+                severity,
+                file: file.path.clone(),
+            });
+            continue;
+        }
+
+        if let Some(to) = manifest_license(&file.path, &file.added) {
+            // 清单里的许可证变更后，依赖兼容性以新许可证为准
+            project_license = Some(to.clone());
+            issues.push(LicenseIssue {
+                kind: LicenseIssueKind::ProjectLicense {
+                    from: manifest_license(&file.path, &file.removed),
+                    to,
+                },
+                severity: Severity::High,
+                file: file.path.clone(),
+            });
+        }
+    }
+
+    let project_category = project_license
+        .as_deref()
+        .map(classify)
+        .unwrap_or(LicenseCategory::Unknown);
+
+    for dependency in new_dependencies(diff) {
+        let Some(license) = dependency.license.clone().or_else(|| lookup(&dependency)) else {
+            continue;
+        };
+        if let Some(severity) = incompatibility(project_category, classify(&license)) {
+            issues.push(LicenseIssue {
+                kind: LicenseIssueKind::IncompatibleDependency {
+                    name: dependency.name,
+                    version: dependency.version,
+                    license,
+                },
+                severity,
+                file: dependency.file,
+            });
+        }
+    }
+
+    issues
+}
+
+/// 清单文件中项目自身的许可证字段
+fn manifest_license(path: &str, lines: &[&str]) -> Option<String> {
+    let file_name = Path::new(path).file_name()?.to_str()?;
+    let regex = match file_name {
+        "Cargo.toml" => &TOML_LICENSE_REGEX,
+        "package.json" => &JSON_LICENSE_REGEX,
+        _ => return None,
+    };
+    lines
+        .iter()
+        .find_map(|line| regex.captures(line).map(|c| c[1].to_string()))
+}
+
+/// 从 Cargo.lock 和 package-lock.json 的新增内容中提取新依赖
+pub fn new_dependencies(diff: &str) -> Vec<NewDependency> {
+    let mut dependencies = Vec::new();
+    for file in split_files(diff) {
+        match Path::new(&file.path).file_name().and_then(|n| n.to_str()) {
+            Some("Cargo.lock") => cargo_lock_dependencies(&file, &mut dependencies),
+            Some("package-lock.json") => npm_lock_dependencies(&file, &mut dependencies),
+            _ => {}
+        }
+    }
+    dependencies
+}
+
+/// Cargo.lock 中新增的 `[[package]]` 块（版本升级只修改 version 行，不视为新依赖）
+fn cargo_lock_dependencies(file: &FileDiff, dependencies: &mut Vec<NewDependency>) {
+    let removed_names: Vec<String> = file
+        .removed
+        .iter()
+        .filter_map(|line| TOML_NAME_REGEX.captures(line).map(|c| c[1].to_string()))
+        .collect();
+
+    let mut current: Option<NewDependency> = None;
+    for line in &file.added {
+        if let Some(captures) = TOML_NAME_REGEX.captures(line) {
+            dependencies.extend(current.take());
+            let name = captures[1].to_string();
+            if !removed_names.contains(&name) {
+                current = Some(NewDependency {
+                    ecosystem: Ecosystem::Cargo,
+                    name,
+                    version: None,
+                    license: None,
+                    file: file.path.clone(),
+                });
+            }
+        } else if let Some(captures) = TOML_VERSION_REGEX.captures(line) {
+            if let Some(dependency) = current.as_mut() {
+                dependency.version.get_or_insert(captures[1].to_string());
+            }
+        }
+    }
+    dependencies.extend(current);
+}
+
+/// package-lock.json 中新增的 `node_modules/<name>` 条目
+fn npm_lock_dependencies(file: &FileDiff, dependencies: &mut Vec<NewDependency>) {
+    let mut current: Option<NewDependency> = None;
+    for line in &file.added {
+        if let Some(captures) = NPM_PACKAGE_REGEX.captures(line) {
+            dependencies.extend(current.take());
+            current = Some(NewDependency {
+                ecosystem: Ecosystem::Npm,
+                name: captures[1].to_string(),
+                version: None,
+                license: None,
+                file: file.path.clone(),
+            });
+        } else if let Some(dependency) = current.as_mut() {
+            if let Some(captures) = JSON_VERSION_REGEX.captures(line) {
+                dependency.version.get_or_insert(captures[1].to_string());
+            } else if let Some(captures) = JSON_LICENSE_REGEX.captures(line) {
+                dependency.license = Some(captures[1].to_string());
+            }
+        }
+    }
+    dependencies.extend(current);
+}
+
+/// 读取仓库的项目许可证：优先使用清单字段，其次识别许可证文件正文
+pub fn project_license(root: &Path) -> Option<String> {
+    for (manifest, regex) in [
+        ("Cargo.toml", &TOML_LICENSE_REGEX),
+        ("package.json", &JSON_LICENSE_REGEX),
+    ] {
+        if let Ok(content) = std::fs::read_to_string(root.join(manifest)) {
+            if let Some(license) = content
+                .lines()
+                .find_map(|line| regex.captures(line).map(|c| c[1].to_string()))
+            {
+                return Some(license);
+            }
+        }
+    }
+
+    ["LICENSE", "LICENSE.md", "LICENSE.txt", "COPYING"]
+        .iter()
+        .filter_map(|name| std::fs::read_to_string(root.join(name)).ok())
+        .find_map(|text| identify_license_text(&text).map(str::to_string))
+}
+
+/// 从本地缓存查找依赖的许可证
+fn lookup_dependency_license(dependency: &NewDependency, root: &Path) -> Option<String> {
+    match dependency.ecosystem {
+        Ecosystem::Cargo => {
+            let version = dependency.version.as_deref()?;
+            cargo_registry_dirs().into_iter().find_map(|dir| {
+                let manifest = dir.join(format!("{}-{}", dependency.name, version));
+                let content = std::fs::read_to_string(manifest.join("Cargo.toml")).ok()?;
+                content
+                    .lines()
+                    .find_map(|line| TOML_LICENSE_REGEX.captures(line).map(|c| c[1].to_string()))
+            })
+        }
+        Ecosystem::Npm => {
+            let manifest = root
+                .join("node_modules")
+                .join(&dependency.name)
+                .join("package.json");
+            let content = std::fs::read_to_string(manifest).ok()?;
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            json.get("license")?.as_str().map(str::to_string)
+        }
+    }
+}
+
+/// `$CARGO_HOME/registry/src/*` 下的各个 registry 源码目录
+fn cargo_registry_dirs() -> Vec<PathBuf> {
+    let cargo_home = std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+    let Some(src) = cargo_home.map(|home| home.join("registry").join("src")) else {
+        return Vec::new();
+    };
+    std::fs::read_dir(src)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify("MIT"), LicenseCategory::Permissive);
+        assert_eq!(classify("MIT OR Apache-2.0"), LicenseCategory::Permissive);
+        assert_eq!(
+            classify("GPL-3.0-or-later"),
+            LicenseCategory::StrongCopyleft
+        );
+        assert_eq!(classify("LGPL-2.1 OR MIT"), LicenseCategory::Permissive);
+        assert_eq!(
+            classify("(MIT AND GPL-2.0)"),
+            LicenseCategory::StrongCopyleft
+        );
+        assert_eq!(classify("MPL-2.0"), LicenseCategory::WeakCopyleft);
+        assert_eq!(classify("AGPL-3.0-only"), LicenseCategory::Restrictive);
+        assert_eq!(
+            classify("GPL-2.0 WITH Classpath-exception-2.0"),
+            LicenseCategory::StrongCopyleft
+        );
+        assert_eq!(classify("SEE LICENSE IN LICENSE"), LicenseCategory::Unknown);
+    }
+
+    #[test]
+    fn test_identify_license_text() {
+        assert_eq!(
+            identify_license_text("MIT License\n\nPermission is hereby granted, free of charge"),
+            Some("MIT")
+        );
+        assert_eq!(
+            identify_license_text("GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007"),
+            Some("GPL-3.0")
+        );
+        assert_eq!(identify_license_text("Hello"), None);
+        assert!(is_license_file("LICENSE-APACHE"));
+        assert!(is_license_file("docs/COPYING.txt"));
+        assert!(!is_license_file("src/licensing.rs"));
+    }
+
+    #[test]
+    fn test_license_file_change() {
+        let diff = "diff --git a/LICENSE b/LICENSE\n\
+            --- a/LICENSE\n\
+            +++ b/LICENSE\n\
+            -MIT License\n\
+            +GNU GENERAL PUBLIC LICENSE\n\
+            +Version 3, 29 June 2007\n";
+        let issues = scan_diff_with(diff, Some("MIT"), |_| None);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::High);
+        assert_eq!(
+            issues[0].to_string(),
+            "LICENSE: 许可证由 MIT 变更为 GPL-3.0"
+        );
+        assert_eq!(issues[0].to_finding().category, "license");
+    }
+
+    #[test]
+    fn test_project_license_field_change() {
+        let diff = "diff --git a/Cargo.toml b/Cargo.toml\n\
+            -license = \"MIT\"\n\
+            +license = \"GPL-3.0\"\n";
+        let issues = scan_diff_with(diff, Some("MIT"), |_| None);
+
+        assert_eq!(
+            issues[0].kind,
+            LicenseIssueKind::ProjectLicense {
+                from: Some("MIT".to_string()),
+                to: "GPL-3.0".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_incompatible_dependencies() {
+        let diff = "diff --git a/Cargo.lock b/Cargo.lock\n\
+            @@ -10,6 +10,12 @@\n\
+            +[[package]]\n\
+            +name = \"gpl-crate\"\n\
+            +version = \"1.0.0\"\n\
+            +\n\
+            +[[package]]\n\
+            +name = \"mit-crate\"\n\
+            +version = \"2.0.0\"\n\
+            \x20[[package]]\n\
+            \x20name = \"serde\"\n\
+            -version = \"1.0.1\"\n\
+            +version = \"1.0.2\"\n\
+            diff --git a/web/package-lock.json b/web/package-lock.json\n\
+            +    \"node_modules/@acme/agpl-lib\": {\n\
+            +      \"version\": \"0.3.0\",\n\
+            +      \"license\": \"AGPL-3.0\"\n\
+            +    },\n";
+
+        let dependencies = new_dependencies(diff);
+        assert_eq!(dependencies.len(), 3);
+        assert_eq!(dependencies[0].version.as_deref(), Some("1.0.0"));
+        assert_eq!(dependencies[2].name, "@acme/agpl-lib");
+
+        let issues = scan_diff_with(
+            diff,
+            Some("MIT OR Apache-2.0"),
+            |dependency| match dependency.name.as_str() {
+                "gpl-crate" => Some("GPL-2.0".to_string()),
+                "mit-crate" => Some("MIT".to_string()),
+                _ => None,
+            },
+        );
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, Severity::High);
+        assert_eq!(
+            issues[0].to_string(),
+            "Cargo.lock: 新依赖 gpl-crate 1.0.0 使用 GPL-2.0（strong copyleft），与项目许可证可能不兼容"
+        );
+        assert_eq!(issues[1].file, "web/package-lock.json");
+
+        // GPL 项目可以使用 GPL 依赖
+        let issues = scan_diff_with(diff, Some("GPL-3.0"), |dependency| {
+            (dependency.name == "gpl-crate").then(|| "GPL-2.0".to_string())
+        });
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Medium);
+    }
+}
@@ -0,0 +1,737 @@
+//! 数据库迁移与 SQL 变更检测
+//!
+//! 识别 diesel、sqlx、flyway 的迁移文件、独立的 `.sql` 文件以及代码中内嵌的 SQL，
+//! 用于推断提交类型和作用域（新建表/字段为 `feat(db)`，其他迁移调整为 `chore(migration)`），
+//! 并在审查时标记破坏性语句：DROP、TRUNCATE、不带 WHERE 的 DELETE、
+//! 新增 NOT NULL 字段却没有默认值等。回滚迁移（down）中的 DROP 属于预期行为，不报告。
+
+use crate::report::{ReviewFinding, Severity};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+static HUNK_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,\d+)? @@").unwrap());
+
+/// flyway 命名规则：`V1.2__desc.sql`、`U1.2__desc.sql`、`R__desc.sql`
+static FLYWAY_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:[VU][\d._]+|R)__\w[\w.-]*\.sql$").unwrap());
+
+/// sqlx 命名规则：`20240101120000_desc.sql`，可带 `.up`/`.down`
+static SQLX_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\d{4,}_\w+(?:\.(?:up|down))?\.sql$").unwrap());
+
+/// 代码字符串中的 SQL 语句
+static RAW_SQL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)["'`].*\b(?:SELECT\s+.+\s+FROM|INSERT\s+INTO|UPDATE\s+\w+\s+SET|DELETE\s+FROM|CREATE\s+(?:TABLE|INDEX)|ALTER\s+TABLE|DROP\s+(?:TABLE|INDEX|COLUMN)|TRUNCATE)\b"#,
+    )
+    .unwrap()
+});
+
+static SQL_KEYWORD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\b(?:SELECT|INSERT|UPDATE|DELETE|CREATE|ALTER|DROP|TRUNCATE)\b").unwrap()
+});
+
+static DROP_TABLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bDROP\s+(?:TABLE|DATABASE|SCHEMA)\b").unwrap());
+static DROP_COLUMN_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bDROP\s+COLUMN\b").unwrap());
+static DROP_INDEX_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bDROP\s+INDEX\b").unwrap());
+static TRUNCATE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^TRUNCATE\b").unwrap());
+static DELETE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^DELETE\s+FROM\b").unwrap());
+static WHERE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bWHERE\b").unwrap());
+static ALTER_TABLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^ALTER\s+TABLE\s+(?:IF\s+EXISTS\s+)?\S+\s+(.*)$").unwrap());
+static ADD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^ADD\b").unwrap());
+/// `ADD` 后跟约束或索引时不是新增字段
+static ADD_CONSTRAINT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^ADD\s+(?:CONSTRAINT|PRIMARY|UNIQUE|FOREIGN|INDEX|KEY|CHECK)\b").unwrap()
+});
+static NOT_NULL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bNOT\s+NULL\b").unwrap());
+static DEFAULT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bDEFAULT\b").unwrap());
+static SET_NOT_NULL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:ALTER\s+(?:COLUMN\s+)?\S+\s+SET\s+NOT\s+NULL|MODIFY\s+(?:COLUMN\s+)?\S+\s+.*\bNOT\s+NULL\b)")
+        .unwrap()
+});
+static COLUMN_TYPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:ALTER\s+(?:COLUMN\s+)?\S+\s+(?:SET\s+DATA\s+)?TYPE\b|MODIFY\b)").unwrap()
+});
+static RENAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^RENAME\b").unwrap());
+static CREATE_TABLE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^CREATE\s+(?:TEMP(?:ORARY)?\s+)?TABLE\b").unwrap());
+
+/// 迁移工具
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationTool {
+    Diesel,
+    Sqlx,
+    Flyway,
+    /// 位于 `migrations/` 目录但不符合上述命名规则的 SQL 文件
+    Generic,
+}
+
+impl MigrationTool {
+    /// 根据路径识别迁移文件
+    pub fn detect(path: &str) -> Option<Self> {
+        let parts: Vec<&str> = path.split('/').collect();
+        let file_name = *parts.last()?;
+        if !file_name.ends_with(".sql") {
+            return None;
+        }
+        let dirs = &parts[..parts.len() - 1];
+
+        if FLYWAY_REGEX.is_match(file_name) {
+            return Some(MigrationTool::Flyway);
+        }
+        // diesel：migrations/<版本>_<名称>/up.sql、down.sql
+        if matches!(file_name, "up.sql" | "down.sql")
+            && dirs.len() >= 2
+            && dirs[dirs.len() - 2] == "migrations"
+        {
+            return Some(MigrationTool::Diesel);
+        }
+        let in_migrations = dirs
+            .iter()
+            .any(|dir| matches!(*dir, "migrations" | "migration"));
+        if in_migrations && SQLX_REGEX.is_match(file_name) {
+            return Some(MigrationTool::Sqlx);
+        }
+        in_migrations.then_some(MigrationTool::Generic)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MigrationTool::Diesel => "diesel",
+            MigrationTool::Sqlx => "sqlx",
+            MigrationTool::Flyway => "flyway",
+            MigrationTool::Generic => "sql",
+        }
+    }
+}
+
+/// 是否为回滚迁移（diesel 的 down.sql、sqlx 的 `.down.sql`、flyway 的 `U` 前缀）
+fn is_rollback(path: &str, tool: MigrationTool) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    match tool {
+        MigrationTool::Flyway => file_name.starts_with('U'),
+        _ => file_name == "down.sql" || file_name.ends_with(".down.sql"),
+    }
+}
+
+/// 路径对应的提交作用域：迁移文件为 `migration`，其他 SQL 文件为 `db`
+pub fn scope_for_path(path: &str) -> Option<&'static str> {
+    if MigrationTool::detect(path).is_some() {
+        Some("migration")
+    } else if path.ends_with(".sql") {
+        Some("db")
+    } else {
+        None
+    }
+}
+
+/// diff 中变更的迁移文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationFile {
+    pub path: String,
+    pub tool: MigrationTool,
+    pub rollback: bool,
+}
+
+/// 破坏性或有风险的 SQL 语句类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestructiveKind {
+    DropTable,
+    DropColumn,
+    DropIndex,
+    Truncate,
+    DeleteWithoutWhere,
+    /// 新增 NOT NULL 字段但没有默认值，已有数据的表上会执行失败
+    AddNotNullWithoutDefault,
+    SetNotNull,
+    AlterColumnType,
+    Rename,
+}
+
+impl DestructiveKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            DestructiveKind::DropTable
+            | DestructiveKind::DropColumn
+            | DestructiveKind::Truncate
+            | DestructiveKind::DeleteWithoutWhere
+            | DestructiveKind::AddNotNullWithoutDefault => Severity::High,
+            DestructiveKind::SetNotNull
+            | DestructiveKind::AlterColumnType
+            | DestructiveKind::Rename => Severity::Medium,
+            DestructiveKind::DropIndex => Severity::Low,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            DestructiveKind::DropTable => "删除表/库，数据将无法恢复",
+            DestructiveKind::DropColumn => "删除字段，数据将无法恢复",
+            DestructiveKind::DropIndex => "删除索引，可能影响查询性能",
+            DestructiveKind::Truncate => "清空表数据",
+            DestructiveKind::DeleteWithoutWhere => "DELETE 没有 WHERE 条件，会删除全表数据",
+            DestructiveKind::AddNotNullWithoutDefault => {
+                "新增 NOT NULL 字段但没有 DEFAULT，已有数据的表上会执行失败"
+            }
+            DestructiveKind::SetNotNull => "字段改为 NOT NULL，已有空值时会执行失败",
+            DestructiveKind::AlterColumnType => "修改字段类型，可能截断数据或长时间锁表",
+            DestructiveKind::Rename => "重命名表/字段，未同步修改的代码会失效",
+        }
+    }
+}
+
+/// 新增代码中的破坏性语句
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DestructiveStatement {
+    pub kind: DestructiveKind,
+    pub file: String,
+    /// 语句起始行（新版本中的行号）
+    pub line: Option<u32>,
+    pub statement: String,
+}
+
+impl fmt::Display for DestructiveStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: ", self.file, line)?,
+            None => write!(f, "{}: ", self.file)?,
+        }
+        write!(f, "{}（{}）", self.kind.description(), self.statement)
+    }
+}
+
+impl DestructiveStatement {
+    /// 转换为审查报告中的发现项
+    pub fn to_finding(&self) -> ReviewFinding {
+        ReviewFinding {
+            severity: self.kind.severity(),
+            category: "database".to_string(),
+            message: format!("{}（{}）", self.kind.description(), self.statement),
+            file: Some(self.file.clone()),
+            line: self.line,
+        }
+    }
+}
+
+/// diff 中与数据库相关的变更
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SqlChanges {
+    pub migrations: Vec<MigrationFile>,
+    /// 迁移目录之外的 `.sql` 文件
+    pub sql_files: Vec<String>,
+    /// 新增代码中内嵌了 SQL 的源文件
+    pub raw_sql_files: Vec<String>,
+    /// 正向迁移是否新建了表或字段
+    pub creates_schema: bool,
+    pub destructive: Vec<DestructiveStatement>,
+}
+
+impl SqlChanges {
+    /// 解析统一 diff，只检查新增的行
+    pub fn from_diff(diff: &str) -> Self {
+        let mut changes = SqlChanges::default();
+        let mut current: Option<FileScanner> = None;
+
+        for line in diff.lines() {
+            if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+                if let Some(scanner) = current.take() {
+                    scanner.finish(&mut changes);
+                }
+                current = Some(FileScanner::new(&captures[2]));
+                continue;
+            }
+            let Some(scanner) = current.as_mut() else {
+                continue;
+            };
+            if let Some(captures) = HUNK_HEADER_REGEX.captures(line) {
+                scanner.flush();
+                scanner.next_line = captures[1].parse().ok();
+                continue;
+            }
+            if line.starts_with("+++") || line.starts_with("---") {
+                continue;
+            }
+            scanner.push_line(line);
+        }
+
+        if let Some(scanner) = current {
+            scanner.finish(&mut changes);
+        }
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty() && self.sql_files.is_empty() && self.raw_sql_files.is_empty()
+    }
+
+    /// 推荐的提交类型：新建表/字段为 `feat`，其他迁移调整为 `chore`
+    pub fn suggested_type(&self) -> Option<&'static str> {
+        if self.migrations.is_empty() {
+            None
+        } else if self.creates_schema {
+            Some("feat")
+        } else {
+            Some("chore")
+        }
+    }
+
+    /// 推荐的提交作用域
+    pub fn suggested_scope(&self) -> Option<&'static str> {
+        match self.suggested_type() {
+            Some("chore") => Some("migration"),
+            Some(_) => Some("db"),
+            None if self.is_empty() => None,
+            None => Some("db"),
+        }
+    }
+
+    /// 提交提示词中的数据库变更上下文
+    pub fn to_prompt_context(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut lines = Vec::new();
+        for migration in &self.migrations {
+            lines.push(format!(
+                "- {} 迁移{}：{}",
+                migration.tool.as_str(),
+                if migration.rollback {
+                    "（回滚）"
+                } else {
+                    ""
+                },
+                migration.path
+            ));
+        }
+        for file in &self.sql_files {
+            lines.push(format!("- SQL 文件：{}", file));
+        }
+        for file in &self.raw_sql_files {
+            lines.push(format!("- 代码中的 SQL：{}", file));
+        }
+        if let (Some(commit_type), Some(scope)) = (self.suggested_type(), self.suggested_scope()) {
+            lines.push(format!("- 建议使用 {}({})", commit_type, scope));
+        }
+        format!("\n数据库变更:\n{}\n", lines.join("\n"))
+    }
+
+    /// 审查提示词中的上下文：数据库变更、已识别的破坏性语句和审查重点
+    pub fn to_review_context(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut context = self.to_prompt_context();
+        if !self.destructive.is_empty() {
+            context.push_str("\n已识别的破坏性 SQL:\n");
+            for statement in &self.destructive {
+                context.push_str(&format!("- {}\n", statement));
+            }
+        }
+        context.push_str(
+            "\n数据库变更审查重点:\n\
+             - 迁移是否可回滚，回滚脚本是否与正向迁移对应\n\
+             - 大表上的 ALTER 是否会长时间锁表\n\
+             - 代码中拼接的 SQL 是否存在注入风险\n",
+        );
+        context
+    }
+}
+
+/// 逐行扫描单个文件的新增内容
+struct FileScanner {
+    path: String,
+    migration: Option<MigrationTool>,
+    is_sql: bool,
+    has_raw_sql: bool,
+    next_line: Option<u32>,
+    statement: String,
+    statement_line: Option<u32>,
+    statements: Vec<(Option<u32>, String)>,
+}
+
+impl FileScanner {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            migration: MigrationTool::detect(path),
+            is_sql: path.ends_with(".sql"),
+            has_raw_sql: false,
+            next_line: None,
+            statement: String::new(),
+            statement_line: None,
+            statements: Vec::new(),
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        let number = self.next_line;
+        if let Some(added) = line.strip_prefix('+') {
+            if self.is_sql {
+                self.push_sql(added, number);
+            } else if let Some(sql) = embedded_sql(added) {
+                self.has_raw_sql = true;
+                self.statements.push((number, sql));
+            }
+            self.next_line = number.map(|n| n + 1);
+        } else if !line.starts_with('-') && !line.starts_with('\\') {
+            // 上下文行会打断新增的语句，之前累积的部分单独处理
+            self.flush();
+            self.next_line = number.map(|n| n + 1);
+        }
+    }
+
+    /// 按分号把新增的 SQL 行拼成完整语句
+    fn push_sql(&mut self, line: &str, number: Option<u32>) {
+        let code = line.split("--").next().unwrap_or_default();
+        for (i, part) in code.split(';').enumerate() {
+            if i > 0 {
+                self.flush();
+            }
+            if part.trim().is_empty() {
+                continue;
+            }
+            if self.statement.is_empty() {
+                self.statement_line = number;
+            }
+            self.statement.push(' ');
+            self.statement.push_str(part.trim());
+        }
+    }
+
+    fn flush(&mut self) {
+        let statement = std::mem::take(&mut self.statement);
+        if !statement.trim().is_empty() {
+            self.statements
+                .push((self.statement_line, statement.trim().to_string()));
+        }
+    }
+
+    fn finish(mut self, changes: &mut SqlChanges) {
+        self.flush();
+
+        let rollback = self
+            .migration
+            .is_some_and(|tool| is_rollback(&self.path, tool));
+        match self.migration {
+            Some(tool) => changes.migrations.push(MigrationFile {
+                path: self.path.clone(),
+                tool,
+                rollback,
+            }),
+            None if self.is_sql => changes.sql_files.push(self.path.clone()),
+            None if self.has_raw_sql => changes.raw_sql_files.push(self.path.clone()),
+            None => return,
+        }
+        if rollback {
+            return;
+        }
+
+        for (line, statement) in self.statements {
+            let normalized = normalize(&statement);
+            if self.migration.is_some() && creates_schema(&normalized) {
+                changes.creates_schema = true;
+            }
+            for kind in classify(&normalized) {
+                changes.destructive.push(DestructiveStatement {
+                    kind,
+                    file: self.path.clone(),
+                    line,
+                    statement: truncate(&statement),
+                });
+            }
+        }
+    }
+}
+
+/// 提取代码字符串中的 SQL：从第一个 SQL 关键字到字符串结束
+fn embedded_sql(line: &str) -> Option<String> {
+    let found = RAW_SQL_REGEX.find(line)?;
+    let quoted = &line[found.start() + 1..];
+    let start = SQL_KEYWORD_REGEX.find(quoted)?.start();
+    let sql = &quoted[start..];
+    let end = sql.find(['"', '\'', '`']).unwrap_or(sql.len());
+    Some(sql[..end].trim().to_string())
+}
+
+/// 大写并合并空白，便于匹配
+fn normalize(statement: &str) -> String {
+    let statement =
+        statement.trim_matches(|c: char| matches!(c, '"' | '\'' | '`' | ',' | ')' | '(' | ' '));
+    statement
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_uppercase()
+}
+
+fn truncate(statement: &str) -> String {
+    const MAX_CHARS: usize = 80;
+    let statement = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+    if statement.chars().count() > MAX_CHARS {
+        format!("{}…", statement.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        statement
+    }
+}
+
+fn creates_schema(statement: &str) -> bool {
+    if CREATE_TABLE_REGEX.is_match(statement) {
+        return true;
+    }
+    ALTER_TABLE_REGEX
+        .captures(statement)
+        .is_some_and(|captures| {
+            alter_clauses(&captures[1])
+                .iter()
+                .any(|clause| is_add_column(clause))
+        })
+}
+
+/// 识别语句中的破坏性操作
+fn classify(statement: &str) -> Vec<DestructiveKind> {
+    let mut kinds = Vec::new();
+
+    if DROP_TABLE_REGEX.is_match(statement) {
+        kinds.push(DestructiveKind::DropTable);
+    }
+    if DROP_COLUMN_REGEX.is_match(statement) {
+        kinds.push(DestructiveKind::DropColumn);
+    }
+    if DROP_INDEX_REGEX.is_match(statement) {
+        kinds.push(DestructiveKind::DropIndex);
+    }
+    if TRUNCATE_REGEX.is_match(statement) {
+        kinds.push(DestructiveKind::Truncate);
+    }
+    if DELETE_REGEX.is_match(statement) && !WHERE_REGEX.is_match(statement) {
+        kinds.push(DestructiveKind::DeleteWithoutWhere);
+    }
+
+    if let Some(captures) = ALTER_TABLE_REGEX.captures(statement) {
+        for clause in alter_clauses(&captures[1]) {
+            let kind = if is_add_column(&clause)
+                && NOT_NULL_REGEX.is_match(&clause)
+                && !DEFAULT_REGEX.is_match(&clause)
+            {
+                Some(DestructiveKind::AddNotNullWithoutDefault)
+            } else if SET_NOT_NULL_REGEX.is_match(&clause) {
+                Some(DestructiveKind::SetNotNull)
+            } else if COLUMN_TYPE_REGEX.is_match(&clause) {
+                Some(DestructiveKind::AlterColumnType)
+            } else if RENAME_REGEX.is_match(&clause) {
+                Some(DestructiveKind::Rename)
+            } else {
+                None
+            };
+            if let Some(kind) = kind.filter(|kind| !kinds.contains(kind)) {
+                kinds.push(kind);
+            }
+        }
+    }
+
+    kinds
+}
+
+/// 新增字段（排除新增约束、索引等）
+fn is_add_column(clause: &str) -> bool {
+    ADD_REGEX.is_match(clause) && !ADD_CONSTRAINT_REGEX.is_match(clause)
+}
+
+/// 按顶层逗号拆分 ALTER TABLE 的子句（忽略括号内的逗号，如 `DECIMAL(10,2)`）
+fn alter_clauses(body: &str) -> Vec<String> {
+    let mut clauses = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in body.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                clauses.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    clauses.push(current.trim().to_string());
+    clauses.retain(|clause| !clause.is_empty());
+    clauses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn added_file(path: &str, lines: &[&str]) -> String {
+        let mut diff = format!(
+            "diff --git a/{path} b/{path}\n\
+             new file mode 100644\n\
+             --- /dev/null\n\
+             +++ b/{path}\n\
+             @@ -0,0 +1,{} @@\n",
+            lines.len()
+        );
+        for line in lines {
+            diff.push_str(&format!("+{}\n", line));
+        }
+        diff
+    }
+
+    #[test]
+    fn test_detect_migration_tools() {
+        assert_eq!(
+            MigrationTool::detect("migrations/2024-01-01-000000_create_users/up.sql"),
+            Some(MigrationTool::Diesel)
+        );
+        assert_eq!(
+            MigrationTool::detect("migrations/20240101120000_add_email.up.sql"),
+            Some(MigrationTool::Sqlx)
+        );
+        assert_eq!(
+            MigrationTool::detect("src/main/resources/db/migration/V2__add_orders.sql"),
+            Some(MigrationTool::Flyway)
+        );
+        assert_eq!(
+            MigrationTool::detect("db/migrations/seed.sql"),
+            Some(MigrationTool::Generic)
+        );
+        assert_eq!(MigrationTool::detect("queries/report.sql"), None);
+        assert_eq!(MigrationTool::detect("migrations/README.md"), None);
+
+        assert_eq!(scope_for_path("migrations/1_init.sql"), Some("migration"));
+        assert_eq!(scope_for_path("queries/report.sql"), Some("db"));
+        assert_eq!(scope_for_path("src/db.rs"), None);
+    }
+
+    #[test]
+    fn test_new_table_suggests_feat_db() {
+        let diff = added_file(
+            "migrations/2024-01-01-000000_create_users/up.sql",
+            &[
+                "CREATE TABLE users (",
+                "    id SERIAL PRIMARY KEY,",
+                "    price DECIMAL(10,2) NOT NULL",
+                ");",
+            ],
+        );
+        let changes = SqlChanges::from_diff(&diff);
+
+        assert_eq!(changes.migrations.len(), 1);
+        assert!(changes.creates_schema);
+        assert!(changes.destructive.is_empty());
+        assert_eq!(changes.suggested_type(), Some("feat"));
+        assert_eq!(changes.suggested_scope(), Some("db"));
+        assert!(changes.to_prompt_context().contains("feat(db)"));
+    }
+
+    #[test]
+    fn test_destructive_statements_flagged() {
+        let diff = added_file(
+            "migrations/20240301000000_cleanup.sql",
+            &[
+                "-- remove legacy data",
+                "DROP TABLE legacy_orders;",
+                "ALTER TABLE users",
+                "    ADD COLUMN tenant_id INTEGER NOT NULL,",
+                "    ADD COLUMN status TEXT NOT NULL DEFAULT 'active',",
+                "    DROP COLUMN nickname;",
+                "DELETE FROM sessions;",
+                "DELETE FROM tokens WHERE expired = true;",
+                "CREATE INDEX idx_users_tenant ON users (tenant_id);",
+            ],
+        );
+        let changes = SqlChanges::from_diff(&diff);
+        let kinds: Vec<DestructiveKind> = changes.destructive.iter().map(|d| d.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                DestructiveKind::DropTable,
+                DestructiveKind::DropColumn,
+                DestructiveKind::AddNotNullWithoutDefault,
+                DestructiveKind::DeleteWithoutWhere,
+            ]
+        );
+        assert_eq!(changes.destructive[0].line, Some(2));
+        assert_eq!(changes.destructive[1].line, Some(3));
+
+        let finding = changes.destructive[0].to_finding();
+        assert_eq!(finding.severity, Severity::High);
+        assert_eq!(finding.category, "database");
+        assert!(changes.to_review_context().contains("已识别的破坏性 SQL"));
+    }
+
+    #[test]
+    fn test_rollback_migrations_not_flagged() {
+        let diff = added_file(
+            "migrations/2024-01-01-000000_create_users/down.sql",
+            &["DROP TABLE users;"],
+        );
+        let changes = SqlChanges::from_diff(&diff);
+
+        assert!(changes.migrations[0].rollback);
+        assert!(changes.destructive.is_empty());
+        assert_eq!(changes.suggested_type(), Some("chore"));
+        assert_eq!(changes.suggested_scope(), Some("migration"));
+    }
+
+    #[test]
+    fn test_alter_clauses() {
+        let diff = added_file(
+            "db/migration/V3__alter.sql",
+            &[
+                "ALTER TABLE orders ALTER COLUMN total TYPE NUMERIC(12,2);",
+                "ALTER TABLE orders ALTER COLUMN note SET NOT NULL;",
+                "ALTER TABLE orders RENAME COLUMN note TO memo;",
+                "ALTER TABLE orders ADD CONSTRAINT fk_user FOREIGN KEY (user_id) REFERENCES users (id);",
+            ],
+        );
+        let changes = SqlChanges::from_diff(&diff);
+        let kinds: Vec<DestructiveKind> = changes.destructive.iter().map(|d| d.kind).collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                DestructiveKind::AlterColumnType,
+                DestructiveKind::SetNotNull,
+                DestructiveKind::Rename,
+            ]
+        );
+        assert!(!changes.creates_schema);
+        assert_eq!(changes.suggested_type(), Some("chore"));
+    }
+
+    #[test]
+    fn test_raw_sql_in_code() {
+        let diff = "diff --git a/src/repo.rs b/src/repo.rs\n\
+                    --- a/src/repo.rs\n\
+                    +++ b/src/repo.rs\n\
+                    @@ -10,2 +10,3 @@\n\
+                    \x20fn purge(pool: &PgPool) {\n\
+                    +    sqlx::query(\"DELETE FROM audit_log\").execute(pool);\n\
+                    +    let name = \"select the user\";\n";
+        let changes = SqlChanges::from_diff(diff);
+
+        assert_eq!(changes.raw_sql_files, vec!["src/repo.rs".to_string()]);
+        assert_eq!(changes.destructive.len(), 1);
+        assert_eq!(
+            changes.destructive[0].kind,
+            DestructiveKind::DeleteWithoutWhere
+        );
+        assert_eq!(changes.destructive[0].line, Some(11));
+        assert_eq!(changes.destructive[0].statement, "DELETE FROM audit_log");
+        assert_eq!(changes.suggested_type(), None);
+        assert_eq!(changes.suggested_scope(), Some("db"));
+    }
+}
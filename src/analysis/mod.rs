@@ -0,0 +1,114 @@
+//! 代码变更的静态分析
+//!
+//! 不依赖 AI 的本地检查，在提交或审查流程中对 diff 运行，或通过 `analyze` 子命令扫描工作区。
+
+pub mod clustering;
+pub mod complexity;
+pub mod container;
+pub mod docs;
+pub mod duplication;
+pub mod generated;
+pub mod hotspots;
+pub mod license;
+pub mod migration;
+pub mod ownership;
+pub mod sensitive;
+pub mod static_analysis;
+pub mod terraform;
+pub mod test_changes;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod trend;
+
+use std::path::{Path, PathBuf};
+
+/// 扫描工作区时跳过的目录
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", "vendor", "build", "dist"];
+
+/// 收集路径下满足条件的源文件（目录递归遍历，跳过隐藏目录和构建产物），结果按路径排序
+pub fn collect_source_files(
+    paths: &[PathBuf],
+    accept: impl Fn(&Path) -> bool,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            walk(path, &accept, &mut files)?;
+        } else if path.is_file() {
+            // 显式指定的文件不受目录过滤影响
+            if accept(path) {
+                files.push(path.clone());
+            }
+        } else {
+            anyhow::bail!("Path not found: {}", path.display());
+        }
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn walk(
+    dir: &Path,
+    accept: &impl Fn(&Path) -> bool,
+    files: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+
+        if path.is_dir() {
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name) {
+                walk(&path, accept, files)?;
+            }
+        } else if accept(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 报告中显示的路径：去掉开头的 `./`
+pub fn display_path(path: &Path) -> String {
+    let display = path.to_string_lossy();
+    display.strip_prefix("./").unwrap_or(&display).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_source_files_skips_build_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::create_dir_all(root.join("target/debug")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        std::fs::write(root.join("src/nested/mod.rs"), "").unwrap();
+        std::fs::write(root.join("src/notes.txt"), "").unwrap();
+        std::fs::write(root.join("target/debug/build.rs"), "").unwrap();
+        std::fs::write(root.join(".git/hook.rs"), "").unwrap();
+
+        let files = collect_source_files(&[root.to_path_buf()], |path| {
+            path.extension().is_some_and(|ext| ext == "rs")
+        })
+        .unwrap();
+
+        assert_eq!(
+            files,
+            vec![root.join("src/lib.rs"), root.join("src/nested/mod.rs")]
+        );
+        assert!(collect_source_files(&[root.join("missing")], |_| true).is_err());
+    }
+
+    #[test]
+    fn test_display_path() {
+        assert_eq!(display_path(Path::new("./src/main.rs")), "src/main.rs");
+        assert_eq!(display_path(Path::new("src/main.rs")), "src/main.rs");
+    }
+}
@@ -0,0 +1,423 @@
+//! 代码所有权与巴士因子
+//!
+//! 从 `git log --numstat` 统计每位作者在各目录中修改的行数，得到目录的主要作者、
+//! 知识集中度（最大作者占比）和巴士因子（累计占比超过一半所需的最少作者数）。
+//! 主要作者都已长期没有提交的目录标记为无人维护。统计结果也用于为 PR 推荐审查人。
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tokio::process::Command;
+
+/// 作者超过该天数没有提交视为不活跃
+pub const DEFAULT_INACTIVE_DAYS: u64 = 180;
+
+/// 默认按前两级目录汇总，如 `src/analysis`
+pub const DEFAULT_DEPTH: usize = 2;
+
+/// 计算巴士因子时需要覆盖的修改占比
+const BUS_FACTOR_COVERAGE: f64 = 0.5;
+
+/// 一次提交的作者和修改的文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitStat {
+    pub author: String,
+    pub email: String,
+    /// 提交时间（Unix 秒）
+    pub timestamp: i64,
+    /// （文件，修改行数）
+    pub files: Vec<(String, usize)>,
+}
+
+/// 解析 `git log --format=%x1e%aN%x1f%aE%x1f%ct --numstat` 的输出
+pub fn parse_log(output: &str) -> Vec<CommitStat> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let (header, body) = record.split_once('\n').unwrap_or((record, ""));
+            let mut fields = header.split('\x1f');
+            let author = fields.next()?.trim().to_string();
+            let email = fields.next()?.trim().to_lowercase();
+            let timestamp = fields.next()?.trim().parse().ok()?;
+            let files = body
+                .lines()
+                .filter_map(|line| {
+                    let mut parts = line.splitn(3, '\t');
+                    let added = parts.next()?.parse::<usize>().unwrap_or(0);
+                    let removed = parts.next()?.parse::<usize>().unwrap_or(0);
+                    // 二进制文件的行数为 `-`，按一行计入
+                    Some((parts.next()?.to_string(), (added + removed).max(1)))
+                })
+                .collect();
+            Some(CommitStat {
+                author,
+                email,
+                timestamp,
+                files,
+            })
+        })
+        .collect()
+}
+
+/// 读取路径下的提交历史，路径相对于当前目录，`since` 为空时读取全部历史
+pub async fn load_history(
+    since: Option<&str>,
+    paths: &[String],
+) -> anyhow::Result<Vec<CommitStat>> {
+    let mut command = Command::new("git");
+    command.args([
+        "log",
+        "--no-merges",
+        "--no-renames",
+        "--relative",
+        "--numstat",
+        "--format=%x1e%aN%x1f%aE%x1f%ct",
+    ]);
+    if let Some(since) = since {
+        command.arg(format!("--since={}", since));
+    }
+    let output = command
+        .arg("--")
+        .args(paths)
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read git history: {}", e))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to read git history: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(parse_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// 文件所在的前 `depth` 级目录，根目录下的文件归为 `.`
+pub fn directory_of(path: &str, depth: usize) -> String {
+    let parts: Vec<&str> = path.split('/').collect();
+    let dirs = &parts[..parts.len().saturating_sub(1)];
+    if dirs.is_empty() {
+        ".".to_string()
+    } else {
+        dirs[..dirs.len().min(depth.max(1))].join("/")
+    }
+}
+
+/// 作者在某个目录中的贡献
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorShare {
+    pub name: String,
+    pub email: String,
+    pub lines: usize,
+    pub commits: usize,
+    /// 占目录修改行数的比例（0–1）
+    pub share: f64,
+    pub active: bool,
+}
+
+/// 目录的所有权统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DirectoryOwnership {
+    pub directory: String,
+    pub lines: usize,
+    /// 按修改行数从多到少排列
+    pub authors: Vec<AuthorShare>,
+    pub bus_factor: usize,
+}
+
+impl DirectoryOwnership {
+    pub fn main_author(&self) -> Option<&AuthorShare> {
+        self.authors.first()
+    }
+
+    /// 知识集中度：最大作者的占比
+    pub fn concentration(&self) -> f64 {
+        self.main_author().map(|a| a.share).unwrap_or(0.0)
+    }
+
+    /// 覆盖巴士因子的主要作者都已不活跃
+    pub fn is_orphaned(&self) -> bool {
+        !self.authors.is_empty()
+            && self
+                .authors
+                .iter()
+                .take(self.bus_factor)
+                .all(|author| !author.active)
+    }
+}
+
+/// 所有权分析结果
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OwnershipReport {
+    pub depth: usize,
+    pub inactive_days: u64,
+    /// 按修改行数从多到少排列
+    pub directories: Vec<DirectoryOwnership>,
+}
+
+impl OwnershipReport {
+    /// 按目录汇总提交历史，`now` 为判断作者是否活跃的当前时间（Unix 秒）
+    pub fn build(commits: &[CommitStat], depth: usize, inactive_days: u64, now: i64) -> Self {
+        // 作者最近一次提交的时间
+        let mut last_seen: HashMap<&str, i64> = HashMap::new();
+        // 目录 -> 作者邮箱 -> (名字, 行数, 提交数)
+        let mut per_dir: BTreeMap<String, HashMap<&str, (&str, usize, usize)>> = BTreeMap::new();
+
+        for commit in commits {
+            let seen = last_seen.entry(&commit.email).or_insert(commit.timestamp);
+            *seen = (*seen).max(commit.timestamp);
+
+            let mut touched: HashMap<String, usize> = HashMap::new();
+            for (file, lines) in &commit.files {
+                *touched.entry(directory_of(file, depth)).or_default() += lines;
+            }
+            for (directory, lines) in touched {
+                let entry = per_dir
+                    .entry(directory)
+                    .or_default()
+                    .entry(&commit.email)
+                    .or_insert((&commit.author, 0, 0));
+                entry.1 += lines;
+                entry.2 += 1;
+            }
+        }
+
+        let cutoff = now - (inactive_days as i64) * 24 * 60 * 60;
+        let mut directories: Vec<DirectoryOwnership> = per_dir
+            .into_iter()
+            .map(|(directory, authors)| {
+                let lines: usize = authors.values().map(|(_, lines, _)| lines).sum();
+                let mut authors: Vec<AuthorShare> = authors
+                    .into_iter()
+                    .map(|(email, (name, author_lines, commits))| AuthorShare {
+                        name: name.to_string(),
+                        email: email.to_string(),
+                        lines: author_lines,
+                        commits,
+                        share: author_lines as f64 / lines.max(1) as f64,
+                        active: last_seen.get(email).is_some_and(|&t| t >= cutoff),
+                    })
+                    .collect();
+                authors.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.email.cmp(&b.email)));
+                let bus_factor = bus_factor(&authors);
+                DirectoryOwnership {
+                    directory,
+                    lines,
+                    authors,
+                    bus_factor,
+                }
+            })
+            .collect();
+        directories.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.directory.cmp(&b.directory)));
+
+        Self {
+            depth,
+            inactive_days,
+            directories,
+        }
+    }
+
+    pub fn orphaned(&self) -> Vec<&DirectoryOwnership> {
+        self.directories
+            .iter()
+            .filter(|d| d.is_orphaned())
+            .collect()
+    }
+
+    /// 巴士因子为 1 的目录
+    pub fn single_owner(&self) -> Vec<&DirectoryOwnership> {
+        self.directories
+            .iter()
+            .filter(|d| d.bus_factor == 1)
+            .collect()
+    }
+
+    /// 为修改了这些文件的变更推荐审查人：按在相关目录中的修改行数排序，
+    /// 跳过不活跃的作者和 `exclude` 中的邮箱（通常是变更的作者本人）
+    pub fn suggest_reviewers(
+        &self,
+        changed_files: &[String],
+        exclude: &[String],
+        limit: usize,
+    ) -> Vec<AuthorShare> {
+        let mut directories: Vec<String> = changed_files
+            .iter()
+            .map(|file| directory_of(file, self.depth))
+            .collect();
+        directories.sort();
+        directories.dedup();
+
+        let mut candidates: HashMap<&str, AuthorShare> = HashMap::new();
+        for directory in self
+            .directories
+            .iter()
+            .filter(|d| directories.contains(&d.directory))
+        {
+            for author in directory.authors.iter().filter(|a| a.active) {
+                if exclude
+                    .iter()
+                    .any(|e| e.eq_ignore_ascii_case(&author.email))
+                {
+                    continue;
+                }
+                let entry = candidates
+                    .entry(&author.email)
+                    .or_insert_with(|| AuthorShare {
+                        lines: 0,
+                        commits: 0,
+                        ..author.clone()
+                    });
+                entry.lines += author.lines;
+                entry.commits += author.commits;
+            }
+        }
+
+        let mut reviewers: Vec<AuthorShare> = candidates.into_values().collect();
+        reviewers.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.email.cmp(&b.email)));
+        reviewers.truncate(limit);
+        reviewers
+    }
+
+    /// 针对无人维护和单人维护的目录给出建议
+    pub fn recommendations(&self, limit: usize) -> Vec<String> {
+        let orphaned = self.orphaned().into_iter().take(limit).map(|d| {
+            format!(
+                "{} 的主要作者已超过 {} 天没有提交，建议指定新的维护者",
+                d.directory, self.inactive_days
+            )
+        });
+        let single = self
+            .single_owner()
+            .into_iter()
+            .filter(|d| !d.is_orphaned() && d.authors.len() > 1)
+            .take(limit)
+            .map(|d| {
+                let main = d.main_author().map(|a| a.name.as_str()).unwrap_or("-");
+                format!(
+                    "{} 的修改 {:.0}% 来自 {}，建议安排其他成员参与审查以分散知识",
+                    d.directory,
+                    d.concentration() * 100.0,
+                    main
+                )
+            });
+        orphaned.chain(single).collect()
+    }
+}
+
+/// 累计占比超过一半所需的最少作者数，作者按占比从高到低排列
+fn bus_factor(authors: &[AuthorShare]) -> usize {
+    let mut covered = 0.0;
+    for (i, author) in authors.iter().enumerate() {
+        covered += author.share;
+        if covered > BUS_FACTOR_COVERAGE {
+            return i + 1;
+        }
+    }
+    authors.len()
+}
+
+/// 统计路径下的所有权
+pub async fn analyze(
+    since: Option<&str>,
+    paths: &[String],
+    depth: usize,
+    inactive_days: u64,
+) -> anyhow::Result<OwnershipReport> {
+    let commits = load_history(since, paths).await?;
+    Ok(OwnershipReport::build(
+        &commits,
+        depth,
+        inactive_days,
+        chrono::Utc::now().timestamp(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+    const NOW: i64 = 1_700_000_000;
+
+    fn commit(author: &str, days_ago: i64, files: &[(&str, usize)]) -> CommitStat {
+        CommitStat {
+            author: author.to_string(),
+            email: format!("{}@example.com", author.to_lowercase()),
+            timestamp: NOW - days_ago * DAY,
+            files: files.iter().map(|(f, l)| (f.to_string(), *l)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_log_and_directory() {
+        let output = "\x1eAlice\x1fAlice@Example.com\x1f1700000000\n\n10\t2\tsrc/core/a.rs\n-\t-\tlogo.png\n\x1eBob\x1fbob@example.com\x1f1690000000\n\n1\t0\tREADME.md\n";
+        let commits = parse_log(output);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].email, "alice@example.com");
+        assert_eq!(
+            commits[0].files,
+            vec![
+                ("src/core/a.rs".to_string(), 12),
+                ("logo.png".to_string(), 1)
+            ]
+        );
+
+        assert_eq!(directory_of("src/core/deep/a.rs", 2), "src/core");
+        assert_eq!(directory_of("src/a.rs", 2), "src");
+        assert_eq!(directory_of("README.md", 2), ".");
+    }
+
+    #[test]
+    fn test_bus_factor_and_orphaned_areas() {
+        let commits = vec![
+            commit("Alice", 10, &[("src/api/a.rs", 80), ("src/ui/b.rs", 30)]),
+            commit("Bob", 20, &[("src/api/a.rs", 20), ("src/ui/b.rs", 30)]),
+            commit("Carol", 400, &[("src/legacy/c.rs", 100)]),
+            commit("Dave", 5, &[("src/legacy/c.rs", 10), ("src/ui/b.rs", 40)]),
+        ];
+        let report = OwnershipReport::build(&commits, 2, DEFAULT_INACTIVE_DAYS, NOW);
+
+        let api = report
+            .directories
+            .iter()
+            .find(|d| d.directory == "src/api")
+            .unwrap();
+        assert_eq!(api.main_author().unwrap().name, "Alice");
+        assert_eq!(api.concentration(), 0.8);
+        assert_eq!(api.bus_factor, 1);
+
+        let ui = report
+            .directories
+            .iter()
+            .find(|d| d.directory == "src/ui")
+            .unwrap();
+        assert_eq!(ui.bus_factor, 2);
+
+        let orphaned: Vec<&str> = report
+            .orphaned()
+            .iter()
+            .map(|d| d.directory.as_str())
+            .collect();
+        assert_eq!(orphaned, vec!["src/legacy"]);
+        assert_eq!(report.recommendations(5).len(), 2);
+    }
+
+    #[test]
+    fn test_suggest_reviewers() {
+        let commits = vec![
+            commit("Alice", 10, &[("src/api/a.rs", 80)]),
+            commit("Bob", 20, &[("src/api/a.rs", 20), ("src/ui/b.rs", 90)]),
+            commit("Carol", 400, &[("src/api/a.rs", 200)]),
+        ];
+        let report = OwnershipReport::build(&commits, 2, DEFAULT_INACTIVE_DAYS, NOW);
+
+        let changed = vec!["src/api/new.rs".to_string(), "src/ui/b.rs".to_string()];
+        let reviewers = report.suggest_reviewers(&changed, &[], 3);
+        let names: Vec<&str> = reviewers.iter().map(|r| r.name.as_str()).collect();
+        // Carol 已不活跃，Bob 在两个目录中的修改合计最多
+        assert_eq!(names, vec!["Bob", "Alice"]);
+
+        let reviewers = report.suggest_reviewers(&changed, &["BOB@example.com".to_string()], 3);
+        assert_eq!(reviewers.len(), 1);
+        assert_eq!(reviewers[0].name, "Alice");
+    }
+}
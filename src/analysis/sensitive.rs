@@ -0,0 +1,283 @@
+//! 敏感信息检测
+//!
+//! 扫描 diff 中新增的行，识别 API Key、访问令牌、私钥和硬编码密码，
+//! 在提交前拦截，避免密钥进入仓库历史（也避免被发送给 AI 提供商）。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+static HUNK_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,\d+)? @@").unwrap());
+
+/// 敏感信息类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SensitiveKind {
+    AwsAccessKey,
+    GithubToken,
+    SlackToken,
+    OpenAiKey,
+    PrivateKey,
+    Jwt,
+    ApiKey,
+    Password,
+}
+
+impl SensitiveKind {
+    pub fn description(&self) -> &'static str {
+        match self {
+            SensitiveKind::AwsAccessKey => "AWS Access Key",
+            SensitiveKind::GithubToken => "GitHub token",
+            SensitiveKind::SlackToken => "Slack token",
+            SensitiveKind::OpenAiKey => "OpenAI-style API key",
+            SensitiveKind::PrivateKey => "private key",
+            SensitiveKind::Jwt => "JWT",
+            SensitiveKind::ApiKey => "API key / secret",
+            SensitiveKind::Password => "hard-coded password",
+        }
+    }
+}
+
+/// 检测规则：第 1 个捕获组为需要遮盖的密钥值
+static RULES: Lazy<Vec<(SensitiveKind, Regex)>> = Lazy::new(|| {
+    vec![
+        (
+            SensitiveKind::PrivateKey,
+            Regex::new(r"(-----BEGIN (?:RSA |EC |DSA |OPENSSH |PGP )?PRIVATE KEY(?: BLOCK)?-----)")
+                .unwrap(),
+        ),
+        (
+            SensitiveKind::AwsAccessKey,
+            Regex::new(r"\b((?:AKIA|ASIA)[0-9A-Z]{16})\b").unwrap(),
+        ),
+        (
+            SensitiveKind::GithubToken,
+            Regex::new(r"\b((?:ghp|gho|ghu|ghs|ghr)_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{40,})")
+                .unwrap(),
+        ),
+        (
+            SensitiveKind::SlackToken,
+            Regex::new(r"\b(xox[abprs]-[A-Za-z0-9-]{10,})").unwrap(),
+        ),
+        (
+            SensitiveKind::OpenAiKey,
+            Regex::new(r"\b(sk-[A-Za-z0-9_-]{20,})").unwrap(),
+        ),
+        (
+            SensitiveKind::Jwt,
+            Regex::new(r"\b(eyJ[A-Za-z0-9_-]{10,}\.eyJ[A-Za-z0-9_-]{10,}\.[A-Za-z0-9_-]{10,})")
+                .unwrap(),
+        ),
+        (
+            SensitiveKind::ApiKey,
+            Regex::new(
+                r#"(?i)(?:api[_-]?key|secret[_-]?key|access[_-]?token|auth[_-]?token|client[_-]?secret|secret)["']?\s*[:=]\s*["']([A-Za-z0-9_\-./+=]{16,})["']?"#,
+            )
+            .unwrap(),
+        ),
+        (
+            SensitiveKind::Password,
+            Regex::new(r#"(?i)(?:password|passwd|pwd)["']?\s*[:=]\s*["']([^"'\s]{6,})["']"#)
+                .unwrap(),
+        ),
+    ]
+});
+
+/// 明显是占位符的值，不视为泄露
+const PLACEHOLDER_MARKERS: &[&str] = &[
+    "example",
+    "placeholder",
+    "changeme",
+    "your_",
+    "your-",
+    "xxxx",
+    "****",
+    "${",
+    "{{",
+    "<",
+    "dummy",
+    "test",
+];
+
+/// 单条检测结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitiveFinding {
+    pub kind: SensitiveKind,
+    pub file: String,
+    /// 新文件中的行号（diff 缺少 hunk 头时为空）
+    pub line: Option<u32>,
+    /// 遮盖后的值，只保留前 4 个字符
+    pub masked: String,
+}
+
+impl fmt::Display for SensitiveFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(
+                f,
+                "{}:{}  {}  {}",
+                self.file,
+                line,
+                self.kind.description(),
+                self.masked
+            ),
+            None => write!(
+                f,
+                "{}  {}  {}",
+                self.file,
+                self.kind.description(),
+                self.masked
+            ),
+        }
+    }
+}
+
+/// 扫描 diff 中新增的行，只报告新引入的敏感信息
+pub fn scan_diff(diff: &str) -> Vec<SensitiveFinding> {
+    let mut findings = Vec::new();
+    let mut file = String::new();
+    let mut next_line: Option<u32> = None;
+
+    for line in diff.lines() {
+        if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+            file = captures[2].to_string();
+            next_line = None;
+            continue;
+        }
+        if let Some(captures) = HUNK_HEADER_REGEX.captures(line) {
+            next_line = captures[1].parse().ok();
+            continue;
+        }
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+
+        if let Some(added) = line.strip_prefix('+') {
+            for (kind, value) in scan_line(added) {
+                findings.push(SensitiveFinding {
+                    kind,
+                    file: file.clone(),
+                    line: next_line,
+                    masked: mask(&value),
+                });
+            }
+            next_line = next_line.map(|n| n + 1);
+        } else if !line.starts_with('-') {
+            next_line = next_line.map(|n| n + 1);
+        }
+    }
+
+    findings
+}
+
+/// 扫描单行内容，返回（类型，原始值）
+pub fn scan_line(line: &str) -> Vec<(SensitiveKind, String)> {
+    let mut matches: Vec<(SensitiveKind, String)> = Vec::new();
+
+    for (kind, regex) in RULES.iter() {
+        for captures in regex.captures_iter(line) {
+            let value = captures[1].to_string();
+            if is_placeholder(&value) {
+                continue;
+            }
+            // 同一个值只按最具体的规则报告一次
+            if matches
+                .iter()
+                .any(|(_, existing)| existing.contains(&value) || value.contains(existing.as_str()))
+            {
+                continue;
+            }
+            matches.push((*kind, value));
+        }
+    }
+
+    matches
+}
+
+fn is_placeholder(value: &str) -> bool {
+    let lower = value.to_lowercase();
+    PLACEHOLDER_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker))
+}
+
+/// 遮盖敏感值，只保留前 4 个字符
+pub fn mask(value: &str) -> String {
+    let visible: String = value.chars().take(4).collect();
+    let hidden = value.chars().count().saturating_sub(4).min(16);
+    format!("{}{}", visible, "*".repeat(hidden.max(4)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_line_known_tokens() {
+        // 测试数据拼接生成，避免本文件被当作泄露
+        let found = scan_line(&format!("let key = \"AKIA{}\";", "IOSFODNN7REALKEY"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, SensitiveKind::AwsAccessKey);
+
+        let found = scan_line(&format!(
+            "token: ghp_{}",
+            "abcdefghijklmnopqrstuvwxyz0123456789"
+        ));
+        assert_eq!(found[0].0, SensitiveKind::GithubToken);
+
+        let found = scan_line(&format!("-----BEGIN RSA {}-----", "PRIVATE KEY"));
+        assert_eq!(found[0].0, SensitiveKind::PrivateKey);
+    }
+
+    #[test]
+    fn test_scan_line_assignments() {
+        let found = scan_line("DB_PASSWORD = \"s3cr3t-P4ss\"");
+        assert_eq!(
+            found,
+            vec![(SensitiveKind::Password, "s3cr3t-P4ss".to_string())]
+        );
+
+        let found = scan_line("api_key: \"9f8e7d6c5b4a39281706f5e4d3c2b1a0\"");
+        assert_eq!(found[0].0, SensitiveKind::ApiKey);
+
+        // 具体规则命中后，通用规则不重复报告
+        let found = scan_line("OPENAI_API_KEY=\"sk-abcdefghijklmnopqrstuvwx\"");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, SensitiveKind::OpenAiKey);
+    }
+
+    #[test]
+    fn test_placeholders_ignored() {
+        assert!(scan_line("api_key = \"your_api_key_goes_here\"").is_empty());
+        assert!(scan_line("password = \"${DB_PASSWORD}\"").is_empty());
+        assert!(scan_line("let password = read_password();").is_empty());
+    }
+
+    #[test]
+    fn test_scan_diff_tracks_lines() {
+        let diff = "diff --git a/config/app.yml b/config/app.yml\n\
+            --- a/config/app.yml\n\
+            +++ b/config/app.yml\n\
+            @@ -10,3 +10,4 @@\n\
+            \x20database:\n\
+            -  password: \"old-p4ssword\"\n\
+            +  password: \"n3w-p4ssword\"\n\
+            +  host: db.internal\n";
+
+        let findings = scan_diff(diff);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].file, "config/app.yml");
+        assert_eq!(findings[0].line, Some(11));
+        assert_eq!(findings[0].kind, SensitiveKind::Password);
+        assert!(!findings[0].to_string().contains("n3w-p4ssword"));
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("AKIAIOSFODNN7EXAMPL"), "AKIA***************");
+        assert_eq!(mask("abc"), "abc****");
+    }
+}
@@ -0,0 +1,415 @@
+//! 外部分析工具
+//!
+//! `.ai-commit.toml` 中的 `[[tools]]` 把团队自己的命令行工具（如内部 linter）接入审查流程：
+//! 变更中有 `files` 匹配的文件时运行 `command`，参数中的 `{files}` 展开为这些文件，
+//! 再按 `format` 解析标准输出（SARIF 或每行一个 JSON 对象）。只保留变更文件中的问题，
+//! 工具未安装时跳过，不影响审查流程。
+
+use super::rules::glob_to_regex;
+use super::tools::severity_from_label;
+use crate::config::ToolConfig;
+use crate::report::{ReviewFinding, Severity};
+use regex::Regex;
+use serde_json::Value;
+use std::fmt;
+use tokio::process::Command;
+
+/// 参数中展开为变更文件的占位符
+const FILES_PLACEHOLDER: &str = "{files}";
+
+/// 外部工具的输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Sarif,
+    /// 每行一个 JSON 对象：`file`、`line`、`severity`、`message`、`rule`
+    JsonLines,
+}
+
+/// 配置好的外部分析工具
+#[derive(Debug, Clone)]
+pub struct ExternalTool {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub format: OutputFormat,
+    /// 输出中没有严重程度时使用
+    pub severity: Severity,
+    files: Vec<Regex>,
+}
+
+impl ExternalTool {
+    pub fn compile(config: &ToolConfig) -> anyhow::Result<Self> {
+        if let Some(problem) = config.problem() {
+            anyhow::bail!("Invalid tool '{}': {}", config.name, problem);
+        }
+        let format = match config.format.as_deref() {
+            Some("jsonl") => OutputFormat::JsonLines,
+            _ => OutputFormat::Sarif,
+        };
+        let severity = match &config.severity {
+            Some(severity) => severity.parse()?,
+            None => Severity::Medium,
+        };
+        let files = config
+            .files
+            .iter()
+            .flatten()
+            .map(|pattern| glob_to_regex(pattern))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            name: config.name.clone(),
+            command: config.command.trim().to_string(),
+            args: config.args.clone().unwrap_or_default(),
+            format,
+            severity,
+            files,
+        })
+    }
+
+    /// 工具是否检查该文件：未设置 `files` 时检查所有文件，
+    /// 不含 `/` 的模式匹配文件名，其余匹配完整路径
+    pub fn applies_to(&self, path: &str) -> bool {
+        if self.files.is_empty() {
+            return true;
+        }
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        self.files
+            .iter()
+            .any(|regex| regex.is_match(path) || regex.is_match(file_name))
+    }
+
+    /// 展开参数中的 `{files}`
+    fn expand_args(&self, files: &[&str]) -> Vec<String> {
+        let mut args = Vec::new();
+        for arg in &self.args {
+            if arg == FILES_PLACEHOLDER {
+                args.extend(files.iter().map(|file| file.to_string()));
+            } else {
+                args.push(arg.clone());
+            }
+        }
+        args
+    }
+
+    /// 解析工具输出
+    pub fn parse(&self, output: &str) -> anyhow::Result<Vec<ToolFinding>> {
+        match self.format {
+            OutputFormat::Sarif => parse_sarif(&self.name, output, self.severity),
+            OutputFormat::JsonLines => parse_json_lines(&self.name, output, self.severity),
+        }
+    }
+
+    /// 对变更中适用的文件运行工具；没有适用的文件或工具未安装时返回 `Ok(None)`
+    pub async fn run(&self, changed_files: &[&str]) -> anyhow::Result<Option<Vec<ToolFinding>>> {
+        let files: Vec<&str> = changed_files
+            .iter()
+            .copied()
+            .filter(|file| self.applies_to(file))
+            .collect();
+        if files.is_empty() {
+            return Ok(None);
+        }
+
+        let output = match Command::new(&self.command)
+            .args(self.expand_args(&files))
+            .output()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        // linter 发现问题时通常以非零状态退出，以能否解析输出判断是否成功
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.trim().is_empty() {
+            if output.status.success() {
+                return Ok(Some(Vec::new()));
+            }
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("{} failed: {}", self.name, stderr.trim());
+        }
+
+        let findings = self.parse(&stdout)?;
+        // 只报告变更文件中的问题，没有位置的问题视为针对整个变更
+        Ok(Some(
+            findings
+                .into_iter()
+                .filter(|finding| {
+                    finding
+                        .file
+                        .as_deref()
+                        .is_none_or(|file| files.contains(&file))
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// 编译配置中的工具，跳过无法使用的配置（加载配置时已报告）
+pub fn compile_tools(configs: &[ToolConfig]) -> Vec<ExternalTool> {
+    configs
+        .iter()
+        .filter_map(|config| ExternalTool::compile(config).ok())
+        .collect()
+}
+
+/// 外部工具报告的一个问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolFinding {
+    pub tool: String,
+    pub rule: Option<String>,
+    pub severity: Severity,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+impl fmt::Display for ToolFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}", file)?;
+            if let Some(line) = self.line {
+                write!(f, ":{}", line)?;
+            }
+            write!(f, " ")?;
+        }
+        write!(f, "[{}] {}", self.tool, self.message)
+    }
+}
+
+impl ToolFinding {
+    /// 转换为审查报告中的问题，类别为工具名，消息前注明工具的规则
+    pub fn to_finding(&self) -> ReviewFinding {
+        let message = match &self.rule {
+            Some(rule) => format!("[{}] {}", rule, self.message),
+            None => self.message.clone(),
+        };
+        ReviewFinding {
+            severity: self.severity,
+            category: self.tool.clone(),
+            message,
+            file: self.file.clone(),
+            line: self.line,
+        }
+    }
+}
+
+/// SARIF 的 `level`，以及工具常用的严重程度标签
+fn severity_from_level(level: &str, default: Severity) -> Severity {
+    match level.trim().to_lowercase().as_str() {
+        "" => default,
+        "error" => Severity::High,
+        "warning" | "warn" => Severity::Medium,
+        "note" => Severity::Low,
+        "none" => Severity::Info,
+        other => severity_from_label(other),
+    }
+}
+
+/// SARIF 中的路径可能是 `file://` URI 或带 `./` 的相对路径，统一为仓库内的相对路径
+fn normalize_path(uri: &str) -> String {
+    let path = uri.strip_prefix("file://").unwrap_or(uri);
+    if let Ok(cwd) = std::env::current_dir() {
+        let root = format!("{}/", cwd.display());
+        if let Some(relative) = path.strip_prefix(root.as_str()) {
+            return relative.to_string();
+        }
+    }
+    path.trim_start_matches("./").to_string()
+}
+
+/// 解析 SARIF 2.1：`runs[].results[]`
+pub fn parse_sarif(
+    tool: &str,
+    output: &str,
+    default: Severity,
+) -> anyhow::Result<Vec<ToolFinding>> {
+    let json: Value = serde_json::from_str(output)
+        .map_err(|e| anyhow::anyhow!("{} did not print valid SARIF: {}", tool, e))?;
+    let mut findings = Vec::new();
+    for run in json["runs"].as_array().into_iter().flatten() {
+        for result in run["results"].as_array().into_iter().flatten() {
+            let location = &result["locations"][0]["physicalLocation"];
+            findings.push(ToolFinding {
+                tool: tool.to_string(),
+                rule: result["ruleId"].as_str().map(str::to_string),
+                severity: severity_from_level(result["level"].as_str().unwrap_or(""), default),
+                message: result["message"]["text"]
+                    .as_str()
+                    .unwrap_or("")
+                    .trim()
+                    .to_string(),
+                file: location["artifactLocation"]["uri"]
+                    .as_str()
+                    .map(normalize_path),
+                line: location["region"]["startLine"]
+                    .as_u64()
+                    .map(|line| line as u32),
+            });
+        }
+    }
+    Ok(findings)
+}
+
+/// 解析 JSON Lines：每行一个对象，不以 `{` 开头的行（如进度信息）忽略
+pub fn parse_json_lines(
+    tool: &str,
+    output: &str,
+    default: Severity,
+) -> anyhow::Result<Vec<ToolFinding>> {
+    let mut findings = Vec::new();
+    for (index, line) in output.lines().enumerate() {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            continue;
+        }
+        let item: Value = serde_json::from_str(line)
+            .map_err(|e| anyhow::anyhow!("{} output line {}: {}", tool, index + 1, e))?;
+        let field = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| item[*name].as_str())
+                .map(str::to_string)
+        };
+        findings.push(ToolFinding {
+            tool: tool.to_string(),
+            rule: field(&["rule", "code"]),
+            severity: severity_from_level(
+                field(&["severity", "level"]).as_deref().unwrap_or(""),
+                default,
+            ),
+            message: field(&["message"]).unwrap_or_default(),
+            file: field(&["file", "path"]).as_deref().map(normalize_path),
+            line: item["line"].as_u64().map(|line| line as u32),
+        });
+    }
+    Ok(findings)
+}
+
+/// 对变更运行所有配置的外部工具，返回审查报告中的问题
+pub async fn run_external_tools<'a>(
+    tools: &[ExternalTool],
+    changed_files: impl IntoIterator<Item = &'a str>,
+    debug: bool,
+) -> Vec<ReviewFinding> {
+    let changed_files: Vec<&str> = changed_files.into_iter().collect();
+    let mut findings = Vec::new();
+    for tool in tools {
+        match tool.run(&changed_files).await {
+            Ok(Some(results)) => findings.extend(results.iter().map(ToolFinding::to_finding)),
+            Ok(None) => {
+                if debug {
+                    println!("{} 未安装或没有适用的变更文件，跳过", tool.name);
+                }
+            }
+            Err(e) => eprintln!("⚠️  Analysis tool {} failed: {}", tool.name, e),
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(name: &str, command: &str) -> ToolConfig {
+        ToolConfig {
+            name: name.to_string(),
+            command: command.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_sarif_results() {
+        let output = r#"{
+  "version": "2.1.0",
+  "runs": [{
+    "tool": {"driver": {"name": "house-lint"}},
+    "results": [
+      {
+        "ruleId": "HL001",
+        "level": "error",
+        "message": {"text": "Do not call the legacy client"},
+        "locations": [{"physicalLocation": {
+          "artifactLocation": {"uri": "./src/api.rs"},
+          "region": {"startLine": 12}
+        }}]
+      },
+      {"message": {"text": "Project-wide warning"}}
+    ]
+  }]
+}"#;
+        let findings = parse_sarif("house-lint", output, Severity::Low).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].file.as_deref(), Some("src/api.rs"));
+        assert_eq!(findings[0].line, Some(12));
+        assert_eq!(findings[0].severity, Severity::High);
+        assert_eq!(findings[1].severity, Severity::Low);
+        assert_eq!(findings[1].file, None);
+
+        let finding = findings[0].to_finding();
+        assert_eq!(finding.category, "house-lint");
+        assert_eq!(finding.message, "[HL001] Do not call the legacy client");
+        assert!(parse_sarif("house-lint", "not json", Severity::Low).is_err());
+    }
+
+    #[test]
+    fn test_parse_json_lines() {
+        let output = "checking 2 files...\n\
+            {\"path\": \"web/app.ts\", \"line\": 3, \"severity\": \"critical\", \"message\": \"secret\", \"code\": \"S1\"}\n\
+            {\"file\": \"web/util.ts\", \"level\": \"warning\", \"message\": \"unused\"}\n";
+        let findings = parse_json_lines("scan", output, Severity::Info).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].rule.as_deref(), Some("S1"));
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(findings[0].line, Some(3));
+        assert_eq!(findings[1].severity, Severity::Medium);
+        assert!(parse_json_lines("scan", "{broken", Severity::Info).is_err());
+    }
+
+    #[test]
+    fn test_compile_and_file_selection() {
+        let mut config = tool("house-lint", "house-lint");
+        config.args = Some(vec!["--check".to_string(), "{files}".to_string()]);
+        config.format = Some("jsonl".to_string());
+        config.files = Some(vec!["*.rs".to_string()]);
+        let compiled = ExternalTool::compile(&config).unwrap();
+        assert_eq!(compiled.format, OutputFormat::JsonLines);
+        assert!(compiled.applies_to("src/main.rs"));
+        assert!(!compiled.applies_to("README.md"));
+        assert_eq!(
+            compiled.expand_args(&["a.rs", "b.rs"]),
+            vec!["--check", "a.rs", "b.rs"]
+        );
+
+        let mut invalid = tool("legacy", "legacy-lint");
+        invalid.format = Some("xml".to_string());
+        assert!(ExternalTool::compile(&invalid).is_err());
+        assert_eq!(
+            compile_tools(&[tool("", "x"), invalid, tool("ok", "x")]).len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_keeps_findings_in_changed_files() {
+        let mut config = tool("echo-lint", "sh");
+        config.format = Some("jsonl".to_string());
+        config.args = Some(vec![
+            "-c".to_string(),
+            r#"echo '{"file": "a.rs", "message": "in change"}'; echo '{"file": "z.rs", "message": "elsewhere"}'; exit 1"#
+                .to_string(),
+        ]);
+        let compiled = ExternalTool::compile(&config).unwrap();
+        let findings = compiled.run(&["a.rs"]).await.unwrap().unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].message, "in change");
+
+        let missing = ExternalTool::compile(&tool("missing", "ai-commit-no-such-tool")).unwrap();
+        assert!(missing.run(&["a.rs"]).await.unwrap().is_none());
+        assert!(compiled.run(&[]).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,275 @@
+//! 增量静态分析
+//!
+//! 提交时只分析暂存区中变更的文件，并且只报告被改动的函数。
+//! 文件级分析结果按内容哈希缓存，未修改的文件直接复用，保持提交前检查的低延迟。
+
+use crate::analysis::complexity::{
+    self, ComplexityReport, ComplexityThresholds, FunctionComplexity,
+};
+use crate::cache::ContentCache;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::BTreeSet;
+use tokio::process::Command;
+
+/// 缓存文件名，分析规则变化时更新版本号使旧缓存失效
+const CACHE_FILE: &str = "complexity-v1.json";
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+static HUNK_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,\d+)? @@").unwrap());
+
+/// diff 中一个文件的变更行（新版本中的行号，删除行记在删除位置）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    pub path: String,
+    pub lines: BTreeSet<usize>,
+}
+
+impl ChangedFile {
+    /// 函数体是否包含变更行
+    pub fn touches(&self, function: &FunctionComplexity) -> bool {
+        let end = function.line + function.length.max(1) - 1;
+        self.lines.range(function.line..=end).next().is_some()
+    }
+}
+
+/// 解析 diff 中每个文件的变更行
+pub fn changed_files(diff: &str) -> Vec<ChangedFile> {
+    let mut files: Vec<ChangedFile> = Vec::new();
+    let mut next_line: Option<usize> = None;
+
+    for line in diff.lines() {
+        if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+            files.push(ChangedFile {
+                path: captures[2].to_string(),
+                lines: BTreeSet::new(),
+            });
+            next_line = None;
+            continue;
+        }
+        if let Some(captures) = HUNK_HEADER_REGEX.captures(line) {
+            next_line = captures[1].parse().ok();
+            continue;
+        }
+        let (Some(file), Some(number)) = (files.last_mut(), next_line) else {
+            continue;
+        };
+
+        if line.starts_with('+') {
+            file.lines.insert(number);
+            next_line = Some(number + 1);
+        } else if line.starts_with('-') {
+            // 删除的行在新版本中不存在，记在删除位置，使包含它的函数被视为已修改
+            file.lines.insert(number.max(1));
+        } else if !line.starts_with('\\') {
+            next_line = Some(number + 1);
+        }
+    }
+
+    files.retain(|file| !file.lines.is_empty());
+    files
+}
+
+/// 增量分析结果
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalAnalysis {
+    /// 分析过的变更文件数
+    pub files_analyzed: usize,
+    /// 命中缓存的文件数
+    pub cache_hits: usize,
+    /// 被改动的函数
+    pub functions: Vec<FunctionComplexity>,
+    pub thresholds: ComplexityThresholds,
+}
+
+impl IncrementalAnalysis {
+    /// 被改动且超过复杂度阈值的函数
+    pub fn violations(&self) -> Vec<&FunctionComplexity> {
+        self.functions
+            .iter()
+            .filter(|f| self.thresholds.is_exceeded_by(f))
+            .collect()
+    }
+}
+
+/// 分析变更文件中被改动的函数，`read` 返回文件的暂存内容（已删除的文件返回 None）
+pub fn analyze_changes(
+    diff: &str,
+    thresholds: ComplexityThresholds,
+    cache: &mut ContentCache<Vec<FunctionComplexity>>,
+    read: impl Fn(&str) -> Option<String>,
+) -> IncrementalAnalysis {
+    let mut analysis = IncrementalAnalysis {
+        thresholds,
+        ..Default::default()
+    };
+    let hits_before = cache.hits();
+
+    for file in changed_files(diff) {
+        if !complexity::is_supported(std::path::Path::new(&file.path)) {
+            continue;
+        }
+        let Some(source) = read(&file.path) else {
+            continue;
+        };
+        analysis.files_analyzed += 1;
+
+        let functions = match cache.get(&source) {
+            Some(functions) => functions,
+            None => {
+                let mut report = ComplexityReport::new(thresholds);
+                report.add_source(&file.path, &source);
+                cache.insert(&source, report.functions.clone());
+                report.functions
+            }
+        };
+
+        // 缓存按内容共享，同样内容的文件可能位于其他路径
+        analysis.functions.extend(
+            functions
+                .into_iter()
+                .filter(|function| file.touches(function))
+                .map(|function| FunctionComplexity {
+                    file: file.path.clone(),
+                    ..function
+                }),
+        );
+    }
+
+    analysis.cache_hits = cache.hits() - hits_before;
+    analysis
+}
+
+/// 分析暂存区中的变更，结果缓存在数据目录下
+pub async fn analyze_staged(
+    diff: &str,
+    thresholds: ComplexityThresholds,
+) -> anyhow::Result<IncrementalAnalysis> {
+    let mut sources = std::collections::HashMap::new();
+    for file in changed_files(diff) {
+        if complexity::is_supported(std::path::Path::new(&file.path)) {
+            if let Some(source) = staged_content(&file.path).await? {
+                sources.insert(file.path, source);
+            }
+        }
+    }
+
+    let cache_path = crate::cache::cache_dir()?.join(CACHE_FILE);
+    let mut cache = ContentCache::load(&cache_path);
+    let analysis = analyze_changes(diff, thresholds, &mut cache, |path| {
+        sources.get(path).cloned()
+    });
+    // 缓存只用于加速，写入失败不影响提交
+    let _ = cache.save();
+    Ok(analysis)
+}
+
+/// 读取文件在暂存区中的内容，文件已删除或不是 UTF-8 文本时返回 None
+async fn staged_content(path: &str) -> anyhow::Result<Option<String>> {
+    let output = Command::new("git")
+        .args(["show", &format!(":{}", path)])
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run git show: {}", e))?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(String::from_utf8(output.stdout).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "fn simple() -> u32 {\n\
+                          \x20   1\n\
+                          }\n\
+                          \n\
+                          fn branchy(a: bool, b: bool, c: bool) -> u8 {\n\
+                          \x20   if a && b {\n\
+                          \x20       1\n\
+                          \x20   } else if c {\n\
+                          \x20       2\n\
+                          \x20   } else {\n\
+                          \x20       3\n\
+                          \x20   }\n\
+                          }\n";
+
+    fn diff_touching(line: usize) -> String {
+        format!(
+            "diff --git a/src/lib.rs b/src/lib.rs\n\
+             --- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -{line},1 +{line},1 @@\n\
+             -old\n\
+             +new\n"
+        )
+    }
+
+    #[test]
+    fn test_changed_files_tracks_lines() {
+        let diff = "diff --git a/a.rs b/a.rs\n\
+                    --- a/a.rs\n\
+                    +++ b/a.rs\n\
+                    @@ -3,3 +3,4 @@\n\
+                    \x20context\n\
+                    +added\n\
+                    -removed\n\
+                    \x20context\n\
+                    diff --git a/b.rs b/b.rs\n\
+                    --- a/b.rs\n\
+                    +++ b/b.rs\n\
+                    @@ -10,2 +10,2 @@\n\
+                    \x20unchanged\n\
+                    \\ No newline at end of file\n";
+
+        let files = changed_files(diff);
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "a.rs");
+        assert_eq!(files[0].lines, BTreeSet::from([4, 5]));
+    }
+
+    #[test]
+    fn test_only_touched_functions_are_reported() {
+        let strict = ComplexityThresholds {
+            max_cyclomatic: 2,
+            max_cognitive: 15,
+        };
+        let mut cache = ContentCache::in_memory();
+        let read = |_: &str| Some(SOURCE.to_string());
+
+        let analysis = analyze_changes(&diff_touching(2), strict, &mut cache, read);
+        assert_eq!(analysis.files_analyzed, 1);
+        assert_eq!(analysis.functions.len(), 1);
+        assert_eq!(analysis.functions[0].name, "simple");
+        assert!(analysis.violations().is_empty());
+
+        let analysis = analyze_changes(&diff_touching(8), strict, &mut cache, read);
+        assert_eq!(analysis.functions[0].name, "branchy");
+        assert_eq!(analysis.violations().len(), 1);
+    }
+
+    #[test]
+    fn test_unchanged_content_uses_cache() {
+        let mut cache = ContentCache::in_memory();
+        let read = |_: &str| Some(SOURCE.to_string());
+        let diff = diff_touching(8);
+
+        let first = analyze_changes(&diff, ComplexityThresholds::default(), &mut cache, read);
+        assert_eq!(first.cache_hits, 0);
+        let second = analyze_changes(&diff, ComplexityThresholds::default(), &mut cache, read);
+        assert_eq!(second.cache_hits, 1);
+        assert_eq!(first.functions, second.functions);
+
+        // 不支持的语言和已删除的文件不参与分析
+        let docs = diff.replace("src/lib.rs", "README.md");
+        let analysis = analyze_changes(&docs, ComplexityThresholds::default(), &mut cache, read);
+        assert_eq!(analysis.files_analyzed, 0);
+        let deleted = analyze_changes(&diff, ComplexityThresholds::default(), &mut cache, |_| None);
+        assert_eq!(deleted.files_analyzed, 0);
+    }
+}
@@ -0,0 +1,10 @@
+//! 静态分析流程集成
+//!
+//! `tools` 调用本机已安装的分析工具，并把结果转换为审查报告使用的 `Severity`；
+//! `incremental` 在提交时只分析暂存区中变更的函数；`rules` 运行 `.ai-commit.toml`
+//! 中定义的自定义规则，`external` 运行其中注册的外部分析工具。
+
+pub mod external;
+pub mod incremental;
+pub mod rules;
+pub mod tools;
@@ -0,0 +1,315 @@
+//! 仓库自定义分析规则
+//!
+//! `.ai-commit.toml` 中的 `[[rules]]` 定义团队自己的检查：`regex` 规则用正则匹配新增行，
+//! `symbol` 规则用正则匹配新增代码中声明的符号名（由 `languages` 的分析器提取，
+//! 可按符号类型过滤）。`files` 限定规则检查的文件。匹配结果与内置检查一起写入审查报告。
+
+use crate::config::RuleConfig;
+use crate::languages::{self, Language, SymbolKind};
+use crate::report::{ReviewFinding, Severity};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+static HUNK_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,\d+)? @@").unwrap());
+
+/// 匹配内容在提示中最多显示的字符数
+const MAX_MATCHED_CHARS: usize = 80;
+
+/// 规则的匹配方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    /// 匹配新增行的内容
+    Regex,
+    /// 匹配新增代码中声明的符号名，可限定符号类型
+    Symbol(Option<SymbolKind>),
+}
+
+/// 编译后的自定义规则
+#[derive(Debug, Clone)]
+pub struct CustomRule {
+    pub name: String,
+    pub kind: RuleKind,
+    pub severity: Severity,
+    pub message: String,
+    pattern: Regex,
+    files: Vec<Regex>,
+}
+
+impl CustomRule {
+    pub fn compile(config: &RuleConfig) -> anyhow::Result<Self> {
+        if let Some(problem) = config.problem() {
+            anyhow::bail!("Invalid rule '{}': {}", config.name, problem);
+        }
+        let kind = match config.kind.as_deref() {
+            Some("symbol") => {
+                RuleKind::Symbol(config.symbol.as_deref().and_then(SymbolKind::from_name))
+            }
+            _ => RuleKind::Regex,
+        };
+        let severity = match &config.severity {
+            Some(severity) => severity.parse()?,
+            None => Severity::Medium,
+        };
+        let files = config
+            .files
+            .iter()
+            .flatten()
+            .map(|pattern| glob_to_regex(pattern))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            name: config.name.clone(),
+            kind,
+            severity,
+            message: config
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("匹配规则 {}", config.name)),
+            pattern: Regex::new(&config.pattern)?,
+            files,
+        })
+    }
+
+    /// 规则是否检查该文件：未设置 `files` 时检查所有文件，
+    /// 不含 `/` 的模式匹配文件名，其余匹配完整路径
+    pub fn applies_to(&self, path: &str) -> bool {
+        if self.files.is_empty() {
+            return true;
+        }
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        self.files
+            .iter()
+            .any(|regex| regex.is_match(path) || regex.is_match(file_name))
+    }
+
+    /// 检查一行新增代码，返回匹配到的内容
+    fn check_line(&self, language: Option<Language>, line: &str) -> Vec<String> {
+        match self.kind {
+            RuleKind::Regex => self
+                .pattern
+                .find(line)
+                .map(|m| vec![m.as_str().to_string()])
+                .unwrap_or_default(),
+            RuleKind::Symbol(filter) => {
+                let Some(language) = language else {
+                    return Vec::new();
+                };
+                languages::analyzer_for(language)
+                    .extract_symbols(line)
+                    .into_iter()
+                    .filter(|symbol| filter.is_none_or(|kind| symbol.kind == kind))
+                    .filter(|symbol| self.pattern.is_match(&symbol.name))
+                    .map(|symbol| symbol.name)
+                    .collect()
+            }
+        }
+    }
+}
+
+/// 规则的 `files` 模式转为正则：`**/` 匹配任意层目录，`*` 和 `?` 不跨越 `/`
+pub(crate) fn glob_to_regex(pattern: &str) -> anyhow::Result<Regex> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).map_err(|e| anyhow::anyhow!("Invalid file pattern '{}': {}", pattern, e))
+}
+
+/// 编译配置中的规则，跳过无法编译的规则（加载配置时已报告）
+pub fn compile_rules(configs: &[RuleConfig]) -> Vec<CustomRule> {
+    configs
+        .iter()
+        .filter_map(|config| CustomRule::compile(config).ok())
+        .collect()
+}
+
+/// 一次规则匹配
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMatch {
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    pub file: String,
+    /// 新文件中的行号
+    pub line: u32,
+    pub matched: String,
+}
+
+impl fmt::Display for RuleMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} [{}] {}（{}）",
+            self.file, self.line, self.rule, self.message, self.matched
+        )
+    }
+}
+
+impl RuleMatch {
+    /// 转换为审查报告中的问题，类别为规则名
+    pub fn to_finding(&self) -> ReviewFinding {
+        ReviewFinding {
+            severity: self.severity,
+            category: self.rule.clone(),
+            message: format!("{}（{}）", self.message, self.matched),
+            file: Some(self.file.clone()),
+            line: Some(self.line),
+        }
+    }
+}
+
+/// 对 diff 的新增行运行自定义规则
+pub fn scan_diff(diff: &str, rules: &[CustomRule]) -> Vec<RuleMatch> {
+    let mut matches = Vec::new();
+    if rules.is_empty() {
+        return matches;
+    }
+
+    let mut file: Option<String> = None;
+    let mut active: Vec<&CustomRule> = Vec::new();
+    let mut language = None;
+    let mut line_number = 0u32;
+
+    for line in diff.lines() {
+        if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+            let path = captures[2].to_string();
+            active = rules.iter().filter(|rule| rule.applies_to(&path)).collect();
+            language = Language::from_path(&path);
+            file = Some(path);
+            continue;
+        }
+        if let Some(captures) = HUNK_HEADER_REGEX.captures(line) {
+            line_number = captures[1].parse().unwrap_or(1);
+            continue;
+        }
+        let Some(path) = &file else {
+            continue;
+        };
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if let Some(added) = line.strip_prefix('+') {
+            for rule in &active {
+                for matched in rule.check_line(language, added) {
+                    matches.push(RuleMatch {
+                        rule: rule.name.clone(),
+                        severity: rule.severity,
+                        message: rule.message.clone(),
+                        file: path.clone(),
+                        line: line_number,
+                        matched: truncate(&matched),
+                    });
+                }
+            }
+            line_number += 1;
+        } else if !line.starts_with('-') && !line.starts_with('\\') {
+            line_number += 1;
+        }
+    }
+    matches
+}
+
+fn truncate(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() <= MAX_MATCHED_CHARS {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(MAX_MATCHED_CHARS).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_support::file_diff;
+
+    fn rule(name: &str, pattern: &str) -> RuleConfig {
+        RuleConfig {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_regex_rule_matches_added_lines_in_selected_files() {
+        let mut config = rule("no-println", r"println!\(");
+        config.severity = Some("low".to_string());
+        config.message = Some("使用 tracing 输出日志".to_string());
+        config.files = Some(vec!["src/**/*.rs".to_string()]);
+        let rules = compile_rules(&[config]);
+
+        let diff = [
+            file_diff(
+                "src/main.rs",
+                "@@ -10,3 +10,4 @@\n fn main() {\n-    println!(\"old\");\n+    let x = 1;\n+    println!(\"{}\", x);\n",
+            ),
+            file_diff("build.rs", "@@ -1,1 +1,1 @@\n+println!(\"cargo:rerun\");\n"),
+        ]
+        .concat();
+        let matches = scan_diff(&diff, &rules);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].file, "src/main.rs");
+        assert_eq!(matches[0].line, 12);
+        assert_eq!(matches[0].severity, Severity::Low);
+
+        let finding = matches[0].to_finding();
+        assert_eq!(finding.category, "no-println");
+        assert!(finding.message.starts_with("使用 tracing 输出日志"));
+    }
+
+    #[test]
+    fn test_symbol_rule_filters_by_kind() {
+        let mut config = rule("service-suffix", r"Manager$");
+        config.kind = Some("symbol".to_string());
+        config.symbol = Some("class".to_string());
+        let rules = compile_rules(&[config]);
+
+        let diff = file_diff(
+            "src/main/java/App.java",
+            "@@ -1,0 +1,3 @@\n+public class UserManager {\n+    void taskManager() {}\n+}\n",
+        );
+        let matches = scan_diff(&diff, &rules);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].matched, "UserManager");
+        assert_eq!(matches[0].severity, Severity::Medium);
+        assert_eq!(matches[0].message, "匹配规则 service-suffix");
+    }
+
+    #[test]
+    fn test_invalid_rules_and_file_patterns() {
+        assert!(CustomRule::compile(&rule("broken", "(")).is_err());
+        let mut config = rule("typo", "x");
+        config.kind = Some("ast".to_string());
+        assert!(CustomRule::compile(&config).is_err());
+        assert_eq!(compile_rules(&[rule("", "x"), rule("ok", "x")]).len(), 1);
+
+        let mut config = rule("lock", "x");
+        config.files = Some(vec!["*.lock".to_string()]);
+        let rule = CustomRule::compile(&config).unwrap();
+        assert!(rule.applies_to("Cargo.lock"));
+        assert!(rule.applies_to("web/yarn.lock"));
+        assert!(!rule.applies_to("Cargo.toml"));
+    }
+}
@@ -0,0 +1,492 @@
+//! 依赖安全审计工具
+//!
+//! 当变更涉及依赖清单或锁文件时，在对应目录运行 `cargo audit`、`npm audit` 或 `govulncheck`，
+//! 把报告的漏洞转换为审查报告中的 security 问题。工具未安装时跳过，不影响审查流程。
+
+use crate::report::{ReviewFinding, Severity};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// 支持的安全审计工具
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuditTool {
+    CargoAudit,
+    NpmAudit,
+    Govulncheck,
+}
+
+impl AuditTool {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuditTool::CargoAudit => "cargo-audit",
+            AuditTool::NpmAudit => "npm-audit",
+            AuditTool::Govulncheck => "govulncheck",
+        }
+    }
+
+    /// 变更后需要重新审计的依赖文件
+    fn manifests(&self) -> &'static [&'static str] {
+        match self {
+            AuditTool::CargoAudit => &["Cargo.toml", "Cargo.lock"],
+            AuditTool::NpmAudit => &["package.json", "package-lock.json", "npm-shrinkwrap.json"],
+            AuditTool::Govulncheck => &["go.mod", "go.sum"],
+        }
+    }
+
+    /// 根据文件名判断对应的审计工具
+    pub fn for_manifest(path: &str) -> Option<Self> {
+        let file_name = Path::new(path).file_name()?.to_str()?;
+        [
+            AuditTool::CargoAudit,
+            AuditTool::NpmAudit,
+            AuditTool::Govulncheck,
+        ]
+        .into_iter()
+        .find(|tool| tool.manifests().contains(&file_name))
+    }
+
+    fn command(&self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            AuditTool::CargoAudit => ("cargo", &["audit", "--json"]),
+            AuditTool::NpmAudit => ("npm", &["audit", "--json"]),
+            AuditTool::Govulncheck => ("govulncheck", &["-json", "./..."]),
+        }
+    }
+
+    /// 解析工具的 JSON 输出
+    pub fn parse(&self, output: &str, manifest: &str) -> anyhow::Result<Vec<SecurityAdvisory>> {
+        match self {
+            AuditTool::CargoAudit => parse_cargo_audit(output, manifest),
+            AuditTool::NpmAudit => parse_npm_audit(output, manifest),
+            AuditTool::Govulncheck => parse_govulncheck(output, manifest),
+        }
+    }
+}
+
+/// 一次审计：工具和运行目录
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditTarget {
+    pub tool: AuditTool,
+    pub dir: PathBuf,
+    /// 触发审计的依赖文件（用于在报告中定位）
+    pub manifest: String,
+}
+
+/// 审计工具报告的漏洞或警告
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityAdvisory {
+    pub tool: AuditTool,
+    pub id: String,
+    pub package: String,
+    pub version: Option<String>,
+    pub title: String,
+    pub severity: Severity,
+    pub fixed_in: Option<String>,
+    pub manifest: String,
+}
+
+impl SecurityAdvisory {
+    pub fn to_finding(&self) -> ReviewFinding {
+        let mut message = format!("[{}] {} {}", self.tool.name(), self.id, self.package);
+        if let Some(version) = &self.version {
+            message.push_str(&format!(" {}", version));
+        }
+        message.push_str(&format!(": {}", self.title));
+        if let Some(fixed) = &self.fixed_in {
+            message.push_str(&format!("（修复版本 {}）", fixed));
+        }
+
+        ReviewFinding {
+            severity: self.severity,
+            category: "security".to_string(),
+            message,
+            file: Some(self.manifest.clone()),
+            line: None,
+        }
+    }
+}
+
+/// 根据变更的文件确定需要运行的审计，同一目录的同一工具只运行一次
+pub fn targets_for_changes<'a>(
+    changed_files: impl IntoIterator<Item = &'a str>,
+) -> Vec<AuditTarget> {
+    let mut targets: Vec<AuditTarget> = Vec::new();
+    for path in changed_files {
+        let Some(tool) = AuditTool::for_manifest(path) else {
+            continue;
+        };
+        let dir = Path::new(path)
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        if !targets.iter().any(|t| t.tool == tool && t.dir == dir) {
+            targets.push(AuditTarget {
+                tool,
+                dir,
+                manifest: path.to_string(),
+            });
+        }
+    }
+    targets
+}
+
+/// 运行一次审计；工具未安装时返回 `Ok(None)`
+pub async fn run_audit(target: &AuditTarget) -> anyhow::Result<Option<Vec<SecurityAdvisory>>> {
+    let (program, args) = target.tool.command();
+    let output = match Command::new(program)
+        .args(args)
+        .current_dir(&target.dir)
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    // 发现漏洞时工具以非零状态退出，以能否解析输出判断是否成功
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // cargo 子命令不存在时不视为错误
+        if stderr.contains("no such command") || stderr.contains("no such subcommand") {
+            return Ok(None);
+        }
+        anyhow::bail!("{} failed: {}", target.tool.name(), stderr.trim());
+    }
+
+    target.tool.parse(&stdout, &target.manifest).map(Some)
+}
+
+/// 对变更涉及的依赖运行所有可用的审计，返回审查报告中的问题
+pub async fn audit_changed_dependencies<'a>(
+    changed_files: impl IntoIterator<Item = &'a str>,
+    debug: bool,
+) -> Vec<ReviewFinding> {
+    let mut findings = Vec::new();
+    for target in targets_for_changes(changed_files) {
+        match run_audit(&target).await {
+            Ok(Some(advisories)) => {
+                findings.extend(advisories.iter().map(SecurityAdvisory::to_finding))
+            }
+            Ok(None) => {
+                if debug {
+                    println!(
+                        "{} 未安装，跳过 {} 的依赖审计",
+                        target.tool.name(),
+                        target.manifest
+                    );
+                }
+            }
+            Err(e) => eprintln!("⚠️  Dependency audit for {} failed: {}", target.manifest, e),
+        }
+    }
+    findings
+}
+
+/// 工具给出的严重程度标签映射，`moderate` 为 npm 的叫法
+pub fn severity_from_label(label: &str) -> Severity {
+    match label.trim().to_lowercase().as_str() {
+        "critical" => Severity::Critical,
+        "high" => Severity::High,
+        "moderate" | "medium" => Severity::Medium,
+        "low" => Severity::Low,
+        _ => Severity::Info,
+    }
+}
+
+/// 从 CVSS 向量（如 `CVSS:3.1/AV:N/AC:L/...`）估算严重程度
+///
+/// cargo audit 只给出向量，不含分数；这里按影响项粗略估计，不追求与官方计算器一致。
+fn severity_from_cvss_vector(vector: &str) -> Severity {
+    let metric = |name: &str| {
+        vector
+            .split('/')
+            .find_map(|part| part.strip_prefix(name)?.strip_prefix(':'))
+            .unwrap_or("N")
+    };
+    let high_impacts = ["C", "I", "A"]
+        .iter()
+        .filter(|name| metric(name) == "H")
+        .count();
+    let remote = metric("AV") == "N" && metric("PR") == "N";
+
+    match (high_impacts, remote) {
+        (3, true) => Severity::Critical,
+        (1.., _) => Severity::High,
+        _ if ["C", "I", "A"].iter().any(|name| metric(name) == "L") => Severity::Medium,
+        _ => Severity::Low,
+    }
+}
+
+fn str_field(value: &Value, key: &str) -> Option<String> {
+    value.get(key)?.as_str().map(str::to_string)
+}
+
+/// 解析 `cargo audit --json`
+fn parse_cargo_audit(output: &str, manifest: &str) -> anyhow::Result<Vec<SecurityAdvisory>> {
+    let json: Value = serde_json::from_str(output)?;
+    let mut advisories = Vec::new();
+
+    let vulnerabilities = json
+        .pointer("/vulnerabilities/list")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for entry in &vulnerabilities {
+        let advisory = &entry["advisory"];
+        let severity = str_field(advisory, "cvss")
+            .map(|vector| severity_from_cvss_vector(&vector))
+            .unwrap_or(Severity::High);
+        advisories.push(SecurityAdvisory {
+            tool: AuditTool::CargoAudit,
+            id: str_field(advisory, "id").unwrap_or_default(),
+            package: str_field(&entry["package"], "name").unwrap_or_default(),
+            version: str_field(&entry["package"], "version"),
+            title: str_field(advisory, "title").unwrap_or_default(),
+            severity,
+            fixed_in: entry
+                .pointer("/versions/patched")
+                .and_then(Value::as_array)
+                .and_then(|patched| patched.first())
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            manifest: manifest.to_string(),
+        });
+    }
+
+    // unmaintained / yanked 等警告不是漏洞，按低优先级报告
+    if let Some(warnings) = json.get("warnings").and_then(Value::as_object) {
+        for (kind, entries) in warnings {
+            for entry in entries.as_array().into_iter().flatten() {
+                let advisory = &entry["advisory"];
+                advisories.push(SecurityAdvisory {
+                    tool: AuditTool::CargoAudit,
+                    id: str_field(advisory, "id").unwrap_or_else(|| kind.clone()),
+                    package: str_field(&entry["package"], "name").unwrap_or_default(),
+                    version: str_field(&entry["package"], "version"),
+                    title: str_field(advisory, "title").unwrap_or_else(|| kind.clone()),
+                    severity: Severity::Low,
+                    fixed_in: None,
+                    manifest: manifest.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(advisories)
+}
+
+/// 解析 `npm audit --json`（npm 7+ 格式）
+fn parse_npm_audit(output: &str, manifest: &str) -> anyhow::Result<Vec<SecurityAdvisory>> {
+    let json: Value = serde_json::from_str(output)?;
+    let mut advisories = Vec::new();
+
+    let Some(vulnerabilities) = json.get("vulnerabilities").and_then(Value::as_object) else {
+        return Ok(advisories);
+    };
+    for (package, entry) in vulnerabilities {
+        // `via` 中的字符串表示经由其他包间接引入，只报告直接的漏洞描述
+        for via in entry["via"].as_array().into_iter().flatten() {
+            if !via.is_object() {
+                continue;
+            }
+            let id = via
+                .get("url")
+                .and_then(Value::as_str)
+                .and_then(|url| url.rsplit('/').next())
+                .map(str::to_string)
+                .or_else(|| via.get("source").map(|source| source.to_string()))
+                .unwrap_or_default();
+            advisories.push(SecurityAdvisory {
+                tool: AuditTool::NpmAudit,
+                id,
+                package: package.clone(),
+                version: str_field(via, "range"),
+                title: str_field(via, "title").unwrap_or_default(),
+                severity: severity_from_label(
+                    via.get("severity")
+                        .or(entry.get("severity"))
+                        .and_then(Value::as_str)
+                        .unwrap_or_default(),
+                ),
+                fixed_in: entry
+                    .pointer("/fixAvailable/version")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                manifest: manifest.to_string(),
+            });
+        }
+    }
+
+    Ok(advisories)
+}
+
+/// 解析 `govulncheck -json` 的消息流
+///
+/// govulncheck 不提供严重程度：代码实际调用到的漏洞视为 high，只在依赖中存在的视为 medium。
+fn parse_govulncheck(output: &str, manifest: &str) -> anyhow::Result<Vec<SecurityAdvisory>> {
+    let mut summaries = std::collections::HashMap::new();
+    let mut findings: Vec<(String, Value)> = Vec::new();
+
+    for message in serde_json::Deserializer::from_str(output).into_iter::<Value>() {
+        let message = message?;
+        if let Some(osv) = message.get("osv") {
+            if let Some(id) = str_field(osv, "id") {
+                summaries.insert(id, str_field(osv, "summary").unwrap_or_default());
+            }
+        } else if let Some(finding) = message.get("finding") {
+            if let Some(id) = str_field(finding, "osv") {
+                findings.push((id, finding.clone()));
+            }
+        }
+    }
+
+    let mut advisories: Vec<SecurityAdvisory> = Vec::new();
+    for (id, finding) in findings {
+        let frame = finding.pointer("/trace/0").cloned().unwrap_or(Value::Null);
+        let called = frame.get("function").is_some();
+        let package = str_field(&frame, "module").unwrap_or_default();
+
+        // 同一漏洞会按调用路径报告多次，只保留最严重的一条
+        let severity = if called {
+            Severity::High
+        } else {
+            Severity::Medium
+        };
+        if let Some(existing) = advisories
+            .iter_mut()
+            .find(|a| a.id == id && a.package == package)
+        {
+            existing.severity = existing.severity.max(severity);
+            continue;
+        }
+
+        advisories.push(SecurityAdvisory {
+            tool: AuditTool::Govulncheck,
+            title: summaries.get(&id).cloned().unwrap_or_default(),
+            id,
+            package,
+            version: str_field(&frame, "version"),
+            severity,
+            fixed_in: str_field(&finding, "fixed_version"),
+            manifest: manifest.to_string(),
+        });
+    }
+
+    Ok(advisories)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_targets_for_changes() {
+        let targets = targets_for_changes([
+            "Cargo.toml",
+            "Cargo.lock",
+            "src/main.rs",
+            "web/package-lock.json",
+            "services/api/go.sum",
+        ]);
+
+        assert_eq!(targets.len(), 3);
+        assert_eq!(targets[0].tool, AuditTool::CargoAudit);
+        assert_eq!(targets[0].dir, PathBuf::from("."));
+        assert_eq!(targets[1].tool, AuditTool::NpmAudit);
+        assert_eq!(targets[1].dir, PathBuf::from("web"));
+        assert_eq!(targets[2].tool, AuditTool::Govulncheck);
+        assert_eq!(targets[2].manifest, "services/api/go.sum");
+    }
+
+    #[test]
+    fn test_severity_mapping() {
+        assert_eq!(severity_from_label("moderate"), Severity::Medium);
+        assert_eq!(
+            severity_from_cvss_vector("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Severity::Critical
+        );
+        assert_eq!(
+            severity_from_cvss_vector("CVSS:3.1/AV:L/AC:L/PR:L/UI:N/S:U/C:N/I:N/A:H"),
+            Severity::High
+        );
+        assert_eq!(
+            severity_from_cvss_vector("CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:U/C:L/I:N/A:N"),
+            Severity::Medium
+        );
+    }
+
+    #[test]
+    fn test_parse_cargo_audit() {
+        let output = r#"{
+            "vulnerabilities": {"found": true, "count": 1, "list": [{
+                "advisory": {"id": "RUSTSEC-2024-0001", "title": "Use after free",
+                             "cvss": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"},
+                "package": {"name": "foo", "version": "0.1.0"},
+                "versions": {"patched": [">=0.1.1"]}
+            }]},
+            "warnings": {"unmaintained": [{
+                "advisory": {"id": "RUSTSEC-2024-0002", "title": "foo-utils is unmaintained"},
+                "package": {"name": "foo-utils", "version": "1.0.0"}
+            }]}
+        }"#;
+        let advisories = AuditTool::CargoAudit.parse(output, "Cargo.lock").unwrap();
+
+        assert_eq!(advisories.len(), 2);
+        assert_eq!(advisories[0].severity, Severity::Critical);
+        assert_eq!(advisories[0].fixed_in.as_deref(), Some(">=0.1.1"));
+        assert_eq!(advisories[1].severity, Severity::Low);
+
+        let finding = advisories[0].to_finding();
+        assert_eq!(finding.category, "security");
+        assert_eq!(finding.file.as_deref(), Some("Cargo.lock"));
+        assert_eq!(
+            finding.message,
+            "[cargo-audit] RUSTSEC-2024-0001 foo 0.1.0: Use after free（修复版本 >=0.1.1）"
+        );
+    }
+
+    #[test]
+    fn test_parse_npm_audit() {
+        let output = r#"{
+            "auditReportVersion": 2,
+            "vulnerabilities": {
+                "minimist": {"name": "minimist", "severity": "critical",
+                    "via": [{"source": 1179, "title": "Prototype Pollution", "severity": "critical",
+                             "url": "https://github.com/advisories/GHSA-xvch-5gv4-984h", "range": "<0.2.4"}],
+                    "fixAvailable": {"name": "minimist", "version": "1.2.8"}},
+                "mkdirp": {"name": "mkdirp", "severity": "moderate", "via": ["minimist"], "fixAvailable": true}
+            }
+        }"#;
+        let advisories = AuditTool::NpmAudit
+            .parse(output, "package-lock.json")
+            .unwrap();
+
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(advisories[0].id, "GHSA-xvch-5gv4-984h");
+        assert_eq!(advisories[0].severity, Severity::Critical);
+        assert_eq!(advisories[0].fixed_in.as_deref(), Some("1.2.8"));
+    }
+
+    #[test]
+    fn test_parse_govulncheck() {
+        let output = r#"{"config": {"scanner_name": "govulncheck"}}
+{
+  "osv": {"id": "GO-2023-1571", "summary": "Denial of service in net/http"}
+}
+{"finding": {"osv": "GO-2023-1571", "fixed_version": "v0.7.0",
+  "trace": [{"module": "golang.org/x/net", "version": "v0.5.0"}]}}
+{"finding": {"osv": "GO-2023-1571", "fixed_version": "v0.7.0",
+  "trace": [{"module": "golang.org/x/net", "version": "v0.5.0", "function": "ServeConn"}]}}"#;
+        let advisories = AuditTool::Govulncheck.parse(output, "go.sum").unwrap();
+
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(advisories[0].title, "Denial of service in net/http");
+        assert_eq!(advisories[0].package, "golang.org/x/net");
+        assert_eq!(advisories[0].severity, Severity::High);
+        assert_eq!(advisories[0].fixed_in.as_deref(), Some("v0.7.0"));
+    }
+}
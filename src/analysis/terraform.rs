@@ -0,0 +1,636 @@
+//! Terraform / HCL 变更检测
+//!
+//! 从 diff 中识别新增和删除的 resource、module，以及可能造成数据丢失的变更：
+//! 删除资源（apply 时会被销毁）、改名但没有 `moved` 块（先销毁再重建）、
+//! 去掉 `prevent_destroy`/`deletion_protection`、开启 `force_destroy` 等。
+//! 破坏性变更写入审查报告，只涉及 Terraform 文件的提交推荐使用 `infra` 作用域。
+
+use crate::report::{ReviewFinding, Severity};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+/// hunk 头中 git 给出的上下文，通常是变更所在的块声明
+static HUNK_CONTEXT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^@@ [^@]+ @@ ?(.*)$").unwrap());
+
+static RESOURCE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*resource\s+"([^"]+)"\s+"([^"]+)""#).unwrap());
+
+static MODULE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\s*module\s+"([^"]+)""#).unwrap());
+
+static MOVED_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*moved\s*\{").unwrap());
+
+/// `prevent_destroy = true`、`force_destroy = false` 等布尔属性
+static FLAG_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(prevent_destroy|deletion_protection|force_destroy|skip_final_snapshot)\s*=\s*(true|false)\b",
+    )
+    .unwrap()
+});
+
+/// 有状态资源类型中的关键字，删除这些资源会丢失数据
+const STATEFUL_KEYWORDS: &[&str] = &[
+    "db_instance",
+    "rds",
+    "database",
+    "sql",
+    "bucket",
+    "storage",
+    "dynamodb",
+    "volume",
+    "disk",
+    "efs",
+    "elasticache",
+    "redis",
+    "kms_key",
+];
+
+/// 是否为 Terraform 文件
+pub fn is_terraform_file(path: &str) -> bool {
+    path.ends_with(".tf")
+        || path.ends_with(".tf.json")
+        || path.ends_with(".tfvars")
+        || path.ends_with(".tfvars.json")
+        || path.ends_with("terragrunt.hcl")
+}
+
+/// 路径对应的提交作用域
+pub fn scope_for_path(path: &str) -> Option<&'static str> {
+    is_terraform_file(path).then_some("infra")
+}
+
+fn is_stateful(resource_type: &str) -> bool {
+    STATEFUL_KEYWORDS
+        .iter()
+        .any(|keyword| resource_type.contains(keyword))
+}
+
+/// 变更的块（resource 或 module）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerraformBlock {
+    /// `aws_s3_bucket.logs` 或 `module.network`
+    pub address: String,
+    pub file: String,
+}
+
+impl TerraformBlock {
+    fn resource_type(&self) -> &str {
+        self.address.split('.').next().unwrap_or_default()
+    }
+}
+
+/// 破坏性变更类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TerraformIssueKind {
+    /// 删除资源，apply 时会被销毁
+    Destroyed { stateful: bool },
+    /// 同类型资源改名且没有 `moved` 块，会先销毁旧资源再创建新资源
+    Replaced { new_address: String },
+    /// 删除 module，其中的所有资源都会被销毁
+    ModuleRemoved,
+    /// 去掉 `prevent_destroy`/`deletion_protection`
+    ProtectionRemoved { flag: String },
+    /// 开启 `force_destroy`，非空的存储桶等也会被直接删除
+    ForceDestroy,
+    /// 开启 `skip_final_snapshot`，删除数据库时不保留快照
+    SkipFinalSnapshot,
+}
+
+/// 破坏性变更
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerraformIssue {
+    pub kind: TerraformIssueKind,
+    pub severity: Severity,
+    /// 涉及的资源地址
+    pub address: String,
+    pub file: String,
+}
+
+impl fmt::Display for TerraformIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.file)?;
+        match &self.kind {
+            TerraformIssueKind::Destroyed { stateful: true } => {
+                write!(f, "删除有状态资源 {}，apply 时数据将被销毁", self.address)
+            }
+            TerraformIssueKind::Destroyed { stateful: false } => {
+                write!(f, "删除资源 {}，apply 时会被销毁", self.address)
+            }
+            TerraformIssueKind::Replaced { new_address } => write!(
+                f,
+                "{} 改名为 {} 但没有 moved 块，apply 时会先销毁再重建",
+                self.address, new_address
+            ),
+            TerraformIssueKind::ModuleRemoved => {
+                write!(f, "删除 {}，其中的资源都会被销毁", self.address)
+            }
+            TerraformIssueKind::ProtectionRemoved { flag } => {
+                write!(f, "{} 去掉了 {} 保护", self.address, flag)
+            }
+            TerraformIssueKind::ForceDestroy => {
+                write!(
+                    f,
+                    "{} 开启 force_destroy，删除时不检查是否为空",
+                    self.address
+                )
+            }
+            TerraformIssueKind::SkipFinalSnapshot => {
+                write!(
+                    f,
+                    "{} 开启 skip_final_snapshot，删除时不保留快照",
+                    self.address
+                )
+            }
+        }
+    }
+}
+
+impl TerraformIssue {
+    /// 转换为审查报告中的发现项
+    pub fn to_finding(&self) -> ReviewFinding {
+        ReviewFinding {
+            severity: self.severity,
+            category: "infrastructure".to_string(),
+            message: self.to_string(),
+            file: Some(self.file.clone()),
+            line: None,
+        }
+    }
+}
+
+/// diff 中的 Terraform 变更
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TerraformChanges {
+    pub files: Vec<String>,
+    pub added: Vec<TerraformBlock>,
+    pub removed: Vec<TerraformBlock>,
+    pub issues: Vec<TerraformIssue>,
+}
+
+impl TerraformChanges {
+    /// 解析统一 diff
+    pub fn from_diff(diff: &str) -> Self {
+        let mut changes = TerraformChanges::default();
+        let mut current: Option<FileScanner> = None;
+
+        for line in diff.lines() {
+            if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+                if let Some(scanner) = current.take() {
+                    scanner.finish(&mut changes);
+                }
+                let path = &captures[2];
+                current = is_terraform_file(path).then(|| FileScanner::new(path));
+                continue;
+            }
+            if line.starts_with("+++") || line.starts_with("---") {
+                continue;
+            }
+            if let Some(scanner) = current.as_mut() {
+                scanner.push_line(line);
+            }
+        }
+
+        if let Some(scanner) = current {
+            scanner.finish(&mut changes);
+        }
+
+        // 同一资源从一个文件移到另一个文件不算销毁
+        let moved_between_files: Vec<String> = changes
+            .removed
+            .iter()
+            .filter(|removed| {
+                changes
+                    .added
+                    .iter()
+                    .any(|added| added.address == removed.address)
+            })
+            .map(|block| block.address.clone())
+            .collect();
+        changes
+            .added
+            .retain(|block| !moved_between_files.contains(&block.address));
+        changes
+            .removed
+            .retain(|block| !moved_between_files.contains(&block.address));
+        changes.issues.retain(|issue| {
+            !matches!(
+                issue.kind,
+                TerraformIssueKind::Destroyed { .. } | TerraformIssueKind::ModuleRemoved
+            ) || !moved_between_files.contains(&issue.address)
+        });
+
+        changes
+            .issues
+            .sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+        changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// 推荐的提交类型：新增资源为 `feat`，其他基础设施调整为 `chore`
+    pub fn suggested_type(&self) -> Option<&'static str> {
+        if self.is_empty() {
+            None
+        } else if !self.added.is_empty() && self.removed.is_empty() {
+            Some("feat")
+        } else {
+            Some("chore")
+        }
+    }
+
+    /// 推荐的提交作用域
+    pub fn suggested_scope(&self) -> Option<&'static str> {
+        (!self.is_empty()).then_some("infra")
+    }
+
+    fn describe_changes(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for file in &self.files {
+            lines.push(format!("- Terraform：{}", file));
+        }
+        let addresses = |blocks: &[TerraformBlock]| {
+            blocks
+                .iter()
+                .map(|block| block.address.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        if !self.added.is_empty() {
+            lines.push(format!("- 新增资源：{}", addresses(&self.added)));
+        }
+        if !self.removed.is_empty() {
+            lines.push(format!("- 删除资源：{}", addresses(&self.removed)));
+        }
+        lines
+    }
+
+    /// 提交提示词中的基础设施变更上下文
+    pub fn to_prompt_context(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut lines = self.describe_changes();
+        if let (Some(commit_type), Some(scope)) = (self.suggested_type(), self.suggested_scope()) {
+            lines.push(format!("- 建议使用 {}({})", commit_type, scope));
+        }
+        format!("\n基础设施变更:\n{}\n", lines.join("\n"))
+    }
+
+    /// 审查提示词中的上下文：资源变更、已识别的破坏性变更和审查重点
+    pub fn to_review_context(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut context = format!("\n基础设施变更:\n{}\n", self.describe_changes().join("\n"));
+        if !self.issues.is_empty() {
+            context.push_str("\n已识别的破坏性变更:\n");
+            for issue in &self.issues {
+                context.push_str(&format!("- {}\n", issue));
+            }
+        }
+        context.push_str(
+            "\nTerraform 审查重点:\n\
+             - 是否有会导致资源被替换（先销毁再创建）的属性修改\n\
+             - 安全组、IAM 策略是否放开了过大的权限\n\
+             - 敏感变量是否标记 sensitive，是否有硬编码的凭据\n",
+        );
+        context
+    }
+}
+
+/// 逐行扫描单个 Terraform 文件
+struct FileScanner {
+    path: String,
+    /// 当前所在的块地址（由新增、删除、上下文行以及 hunk 头中的声明确定）
+    block: Option<String>,
+    added: Vec<String>,
+    removed: Vec<String>,
+    has_moved_block: bool,
+    /// (块地址, 属性) -> (删除的值, 新增的值)
+    flags: HashMap<(String, String), (Option<bool>, Option<bool>)>,
+}
+
+impl FileScanner {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            block: None,
+            added: Vec::new(),
+            removed: Vec::new(),
+            has_moved_block: false,
+            flags: HashMap::new(),
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        if let Some(captures) = HUNK_CONTEXT_REGEX.captures(line) {
+            if let Some(address) = block_address(&captures[1]) {
+                self.block = Some(address);
+            }
+            return;
+        }
+
+        let (change, code) = if let Some(code) = line.strip_prefix('+') {
+            (Some(true), code)
+        } else if let Some(code) = line.strip_prefix('-') {
+            (Some(false), code)
+        } else if let Some(code) = line.strip_prefix(' ') {
+            (None, code)
+        } else {
+            return;
+        };
+
+        if let Some(address) = block_address(code) {
+            match change {
+                Some(true) => self.added.push(address.clone()),
+                Some(false) => self.removed.push(address.clone()),
+                None => {}
+            }
+            self.block = Some(address);
+            return;
+        }
+        let Some(added) = change else {
+            return;
+        };
+
+        if added && MOVED_REGEX.is_match(code) {
+            self.has_moved_block = true;
+        }
+        if let Some(captures) = FLAG_REGEX.captures(code) {
+            let block = self.block.clone().unwrap_or_else(|| "?".to_string());
+            let entry = self
+                .flags
+                .entry((block, captures[1].to_string()))
+                .or_default();
+            let value = Some(&captures[2] == "true");
+            if added {
+                entry.1 = value;
+            } else {
+                entry.0 = value;
+            }
+        }
+    }
+
+    fn finish(self, changes: &mut TerraformChanges) {
+        let file = self.path.clone();
+        let issue = |kind, severity, address: &str| TerraformIssue {
+            kind,
+            severity,
+            address: address.to_string(),
+            file: file.clone(),
+        };
+
+        // 同一地址在文件内既删除又新增，属于原地修改
+        let added: Vec<&String> = self
+            .added
+            .iter()
+            .filter(|a| !self.removed.contains(a))
+            .collect();
+        let removed: Vec<&String> = self
+            .removed
+            .iter()
+            .filter(|r| !self.added.contains(r))
+            .collect();
+
+        let mut replaced = Vec::new();
+        for address in &removed {
+            let block = TerraformBlock {
+                address: address.to_string(),
+                file: file.clone(),
+            };
+            if address.starts_with("module.") {
+                changes.issues.push(issue(
+                    TerraformIssueKind::ModuleRemoved,
+                    Severity::High,
+                    address,
+                ));
+            } else if let Some(new_address) = added
+                .iter()
+                .find(|a| !replaced.contains(*a) && same_type(a, address))
+            {
+                replaced.push(*new_address);
+                // 有 moved 块时 Terraform 会迁移状态而不是重建
+                if self.has_moved_block {
+                    changes.removed.push(block);
+                    continue;
+                }
+                changes.issues.push(issue(
+                    TerraformIssueKind::Replaced {
+                        new_address: new_address.to_string(),
+                    },
+                    Severity::Medium,
+                    address,
+                ));
+            } else {
+                let stateful = is_stateful(block.resource_type());
+                let severity = if stateful {
+                    Severity::Critical
+                } else {
+                    Severity::High
+                };
+                changes.issues.push(issue(
+                    TerraformIssueKind::Destroyed { stateful },
+                    severity,
+                    address,
+                ));
+            }
+            changes.removed.push(block);
+        }
+        changes
+            .added
+            .extend(added.iter().map(|address| TerraformBlock {
+                address: address.to_string(),
+                file: file.clone(),
+            }));
+
+        let mut flags: Vec<_> = self.flags.into_iter().collect();
+        flags.sort();
+        for ((address, flag), (before, after)) in flags {
+            let kind = match (flag.as_str(), before, after) {
+                ("prevent_destroy" | "deletion_protection", Some(true), None | Some(false))
+                | ("prevent_destroy" | "deletion_protection", None, Some(false)) => Some((
+                    TerraformIssueKind::ProtectionRemoved { flag },
+                    Severity::High,
+                )),
+                ("force_destroy", _, Some(true)) if before != Some(true) => {
+                    Some((TerraformIssueKind::ForceDestroy, Severity::High))
+                }
+                ("skip_final_snapshot", _, Some(true)) if before != Some(true) => {
+                    Some((TerraformIssueKind::SkipFinalSnapshot, Severity::Medium))
+                }
+                _ => None,
+            };
+            // 整个资源被删除时已报告销毁，不再重复报告其属性
+            if let Some((kind, severity)) = kind.filter(|_| !removed.contains(&&address)) {
+                changes.issues.push(issue(kind, severity, &address));
+            }
+        }
+
+        changes.files.push(self.path);
+    }
+}
+
+/// 从块声明中提取地址：`aws_s3_bucket.logs`、`module.network`
+fn block_address(code: &str) -> Option<String> {
+    if let Some(captures) = RESOURCE_REGEX.captures(code) {
+        return Some(format!("{}.{}", &captures[1], &captures[2]));
+    }
+    MODULE_REGEX
+        .captures(code)
+        .map(|captures| format!("module.{}", &captures[1]))
+}
+
+fn same_type(a: &str, b: &str) -> bool {
+    !a.starts_with("module.") && a.split('.').next() == b.split('.').next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tf_diff(path: &str, body: &str) -> String {
+        format!(
+            "diff --git a/{path} b/{path}\n\
+             --- a/{path}\n\
+             +++ b/{path}\n\
+             {body}"
+        )
+    }
+
+    #[test]
+    fn test_terraform_file_detection() {
+        assert!(is_terraform_file("infra/main.tf"));
+        assert!(is_terraform_file("envs/prod.tfvars"));
+        assert!(is_terraform_file("live/prod/terragrunt.hcl"));
+        assert!(!is_terraform_file("src/main.rs"));
+        assert_eq!(scope_for_path("modules/vpc/variables.tf"), Some("infra"));
+        assert_eq!(scope_for_path("README.md"), None);
+    }
+
+    #[test]
+    fn test_added_resources_suggest_feat() {
+        let diff = tf_diff(
+            "main.tf",
+            "@@ -10,0 +11,6 @@\n\
+             +resource \"aws_sqs_queue\" \"events\" {\n\
+             +  name = \"events\"\n\
+             +}\n\
+             +module \"cache\" {\n\
+             +  source = \"./modules/cache\"\n\
+             +}\n",
+        );
+        let changes = TerraformChanges::from_diff(&diff);
+
+        let added: Vec<&str> = changes.added.iter().map(|b| b.address.as_str()).collect();
+        assert_eq!(added, vec!["aws_sqs_queue.events", "module.cache"]);
+        assert!(changes.issues.is_empty());
+        assert_eq!(changes.suggested_type(), Some("feat"));
+        assert_eq!(changes.suggested_scope(), Some("infra"));
+        assert!(changes.to_prompt_context().contains("feat(infra)"));
+    }
+
+    #[test]
+    fn test_destroyed_resources_flagged() {
+        let diff = tf_diff(
+            "storage.tf",
+            "@@ -1,12 +1,3 @@\n\
+             -resource \"aws_s3_bucket\" \"logs\" {\n\
+             -  bucket = \"acme-logs\"\n\
+             -}\n\
+             -resource \"aws_iam_role\" \"ci\" {\n\
+             -  name = \"ci\"\n\
+             -}\n\
+             -module \"legacy\" {\n\
+             -  source = \"./legacy\"\n\
+             -}\n\
+             \x20resource \"aws_db_instance\" \"main\" {\n\
+             -  deletion_protection = true\n\
+             +  skip_final_snapshot = true\n\
+             \x20}\n",
+        );
+        let changes = TerraformChanges::from_diff(&diff);
+        let issues: Vec<(String, Severity)> = changes
+            .issues
+            .iter()
+            .map(|issue| (issue.address.clone(), issue.severity))
+            .collect();
+
+        assert_eq!(
+            issues,
+            vec![
+                ("aws_s3_bucket.logs".to_string(), Severity::Critical),
+                ("aws_iam_role.ci".to_string(), Severity::High),
+                ("module.legacy".to_string(), Severity::High),
+                ("aws_db_instance.main".to_string(), Severity::High),
+                ("aws_db_instance.main".to_string(), Severity::Medium),
+            ]
+        );
+        assert_eq!(
+            changes.issues[3].to_string(),
+            "storage.tf: aws_db_instance.main 去掉了 deletion_protection 保护"
+        );
+        assert_eq!(changes.issues[0].to_finding().category, "infrastructure");
+        assert_eq!(changes.suggested_type(), Some("chore"));
+        assert!(changes.to_review_context().contains("已识别的破坏性变更"));
+    }
+
+    #[test]
+    fn test_rename_without_moved_block() {
+        let rename = "@@ -1,3 +1,3 @@\n\
+                      -resource \"aws_instance\" \"web\" {\n\
+                      +resource \"aws_instance\" \"frontend\" {\n\
+                      \x20 ami = var.ami\n";
+        let changes = TerraformChanges::from_diff(&tf_diff("main.tf", rename));
+        assert_eq!(changes.issues.len(), 1);
+        assert_eq!(
+            changes.issues[0].kind,
+            TerraformIssueKind::Replaced {
+                new_address: "aws_instance.frontend".to_string()
+            }
+        );
+
+        let with_moved = format!(
+            "{}+moved {{\n+  from = aws_instance.web\n+  to   = aws_instance.frontend\n+}}\n",
+            rename
+        );
+        let changes = TerraformChanges::from_diff(&tf_diff("main.tf", &with_moved));
+        assert!(changes.issues.is_empty());
+    }
+
+    #[test]
+    fn test_flags_use_hunk_context_and_file_moves() {
+        let diff = tf_diff(
+            "buckets.tf",
+            "@@ -4,3 +4,3 @@ resource \"aws_s3_bucket\" \"assets\" {\n\
+             -  force_destroy = false\n\
+             +  force_destroy = true\n",
+        );
+        let changes = TerraformChanges::from_diff(&diff);
+        assert_eq!(changes.issues[0].kind, TerraformIssueKind::ForceDestroy);
+        assert_eq!(changes.issues[0].address, "aws_s3_bucket.assets");
+
+        // 资源从一个文件移动到另一个文件不算销毁
+        let moved = format!(
+            "{}{}",
+            tf_diff(
+                "old.tf",
+                "@@ -1 +0,0 @@\n-resource \"aws_vpc\" \"main\" {\n"
+            ),
+            tf_diff(
+                "network.tf",
+                "@@ -0,0 +1 @@\n+resource \"aws_vpc\" \"main\" {\n"
+            )
+        );
+        let changes = TerraformChanges::from_diff(&moved);
+        assert!(changes.issues.is_empty());
+        assert!(changes.added.is_empty() && changes.removed.is_empty());
+    }
+}
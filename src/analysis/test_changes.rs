@@ -0,0 +1,304 @@
+//! 测试变更识别
+//!
+//! 区分 diff 中的测试代码和业务代码：测试文件（`*_test.go`、`tests/` 目录、
+//! `*.spec.ts` 等）以及 Rust 源文件中 `#[cfg(test)]` 之后的测试模块。
+//! 只改测试时引导 AI 使用 `test` 类型；与业务代码混合时在提交消息正文中注明测试数量。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+/// 测试用例的声明
+static TEST_CASE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*(#\[(tokio::)?test\b|#\[rstest\b|func Test\w*\(|(async\s+)?def test_\w+|(it|test)\(\s*['"`]|@(Test|ParameterizedTest)\b)"#,
+    )
+    .unwrap()
+});
+
+/// 测试目录
+const TEST_DIRS: &[&str] = &["tests", "test", "__tests__", "spec", "testdata"];
+
+/// 测试文件名后缀
+const TEST_FILE_SUFFIXES: &[&str] = &[
+    "_test.go",
+    "_test.py",
+    "_test.rs",
+    "_spec.rb",
+    "_test.rb",
+    "Test.java",
+    "Tests.java",
+    "Test.kt",
+    "Tests.kt",
+];
+
+/// 是否为测试文件
+pub fn is_test_file(path: &str) -> bool {
+    let parts: Vec<&str> = path.split('/').collect();
+    let file_name = parts.last().copied().unwrap_or(path);
+
+    if parts[..parts.len() - 1]
+        .iter()
+        .any(|dir| TEST_DIRS.contains(dir))
+    {
+        return true;
+    }
+    if TEST_FILE_SUFFIXES
+        .iter()
+        .any(|suffix| file_name.ends_with(suffix))
+    {
+        return true;
+    }
+    // test_foo.py、foo.test.ts、foo.spec.js
+    file_name.starts_with("test_") && file_name.ends_with(".py")
+        || file_name.contains(".test.")
+        || file_name.contains(".spec.")
+}
+
+/// diff 中的测试变更统计
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestChanges {
+    /// 修改了测试代码的文件
+    pub test_files: Vec<String>,
+    /// 修改了非测试代码的文件
+    pub source_files: Vec<String>,
+    /// 新增的测试用例数量
+    pub tests_added: usize,
+    /// 删除的测试用例数量
+    pub tests_removed: usize,
+}
+
+impl TestChanges {
+    pub fn from_diff(diff: &str) -> Self {
+        let mut changes = TestChanges::default();
+        let mut current: Option<FileScan> = None;
+
+        for line in diff.lines() {
+            if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+                if let Some(scan) = current.take() {
+                    changes.record(scan);
+                }
+                current = Some(FileScan::new(&captures[2]));
+                continue;
+            }
+            if line.starts_with("+++") || line.starts_with("---") {
+                continue;
+            }
+            if let Some(scan) = current.as_mut() {
+                scan.push_line(line);
+            }
+        }
+        if let Some(scan) = current {
+            changes.record(scan);
+        }
+        changes
+    }
+
+    fn record(&mut self, scan: FileScan) {
+        if scan.test_lines > 0 {
+            self.test_files.push(scan.path.clone());
+        }
+        if scan.source_lines > 0 {
+            self.source_files.push(scan.path);
+        }
+        self.tests_added += scan.tests_added;
+        self.tests_removed += scan.tests_removed;
+    }
+
+    /// 是否只修改了测试代码
+    pub fn is_test_only(&self) -> bool {
+        !self.test_files.is_empty() && self.source_files.is_empty()
+    }
+
+    /// 是否同时修改了测试和业务代码
+    pub fn is_mixed(&self) -> bool {
+        !self.test_files.is_empty() && !self.source_files.is_empty()
+    }
+
+    /// 测试和业务代码混合时写入提交消息正文的测试统计
+    pub fn commit_body(&self) -> Option<String> {
+        if !self.is_mixed() {
+            return None;
+        }
+        let added = self.tests_added.saturating_sub(self.tests_removed);
+        Some(if added > 0 {
+            format!(
+                "测试：新增 {} 个测试用例，修改 {} 个测试文件",
+                added,
+                self.test_files.len()
+            )
+        } else {
+            format!("测试：修改 {} 个测试文件", self.test_files.len())
+        })
+    }
+
+    /// 生成提交提示词中的测试变更说明，没有测试变更时返回空字符串
+    pub fn to_prompt_context(&self) -> String {
+        if self.is_test_only() {
+            format!(
+                "\n测试变更：本次只修改了测试代码（{}），type 应使用 test\n",
+                self.test_files.join(", ")
+            )
+        } else if self.is_mixed() {
+            format!(
+                "\n测试变更：同时修改了 {} 个测试文件，type 按业务代码的变更判断\n",
+                self.test_files.len()
+            )
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// 单个文件的扫描状态
+struct FileScan {
+    path: String,
+    test_file: bool,
+    /// Rust 源文件已进入 `#[cfg(test)]` 测试模块
+    in_test_module: bool,
+    test_lines: usize,
+    source_lines: usize,
+    tests_added: usize,
+    tests_removed: usize,
+}
+
+impl FileScan {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            test_file: is_test_file(path),
+            in_test_module: false,
+            test_lines: 0,
+            source_lines: 0,
+            tests_added: 0,
+            tests_removed: 0,
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        if let Some(header) = line.strip_prefix("@@") {
+            // hunk 头附带的上下文（所在的函数或模块）位于测试模块内
+            let context = header.split_once("@@").map(|(_, rest)| rest).unwrap_or("");
+            if self.path.ends_with(".rs") && context.contains("mod tests") {
+                self.in_test_module = true;
+            }
+            return;
+        }
+        let Some(first) = line.chars().next() else {
+            return;
+        };
+        let code = &line[first.len_utf8()..];
+        if first != '-' && code.trim_start().starts_with("#[cfg(test)]") {
+            // 测试模块通常位于文件末尾，之后的 hunk 也按测试代码处理
+            self.in_test_module = true;
+        }
+        if first != '+' && first != '-' {
+            return;
+        }
+        if code.trim().is_empty() {
+            return;
+        }
+
+        if self.test_file || self.in_test_module {
+            self.test_lines += 1;
+            if TEST_CASE_REGEX.is_match(code) {
+                if first == '+' {
+                    self.tests_added += 1;
+                } else {
+                    self.tests_removed += 1;
+                }
+            }
+        } else {
+            self.source_lines += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_support::file_diff;
+
+    #[test]
+    fn test_is_test_file() {
+        assert!(is_test_file("pkg/user/user_test.go"));
+        assert!(is_test_file("tests/integration.rs"));
+        assert!(is_test_file("web/src/__tests__/App.tsx"));
+        assert!(is_test_file("web/src/button.spec.ts"));
+        assert!(is_test_file("app/test_models.py"));
+        assert!(is_test_file("src/test/java/com/acme/UserServiceTest.java"));
+        assert!(!is_test_file("src/main.rs"));
+        assert!(!is_test_file("src/testing.rs"));
+        assert!(!is_test_file("tests"));
+    }
+
+    #[test]
+    fn test_test_only_diff() {
+        let diff = file_diff(
+            "pkg/user/user_test.go",
+            "@@ -1,3 +1,8 @@\n\
+             +func TestCreateUser(t *testing.T) {\n\
+             +\tif CreateUser(\"a\") == nil {\n\
+             +\t\tt.Fatal(\"expected user\")\n\
+             +\t}\n\
+             +}\n",
+        );
+        let changes = TestChanges::from_diff(&diff);
+        assert!(changes.is_test_only());
+        assert_eq!(changes.tests_added, 1);
+        assert!(changes.commit_body().is_none());
+        assert!(changes.to_prompt_context().contains("type 应使用 test"));
+    }
+
+    #[test]
+    fn test_rust_cfg_test_module() {
+        let diff = file_diff(
+            "src/parser.rs",
+            "@@ -40,6 +40,12 @@ fn parse(input: &str) -> Ast {\n\
+             \x20}\n\
+             \n\
+             \x20#[cfg(test)]\n\
+             \x20mod tests {\n\
+             +    #[test]\n\
+             +    fn test_parse_empty() {\n\
+             +        assert!(parse(\"\").is_empty());\n\
+             +    }\n\
+             @@ -80,3 +86,3 @@ mod tests {\n\
+             -        assert_eq!(ast.len(), 1);\n\
+             +        assert_eq!(ast.len(), 2);\n",
+        );
+        let changes = TestChanges::from_diff(&diff);
+        assert!(changes.is_test_only());
+        assert_eq!(changes.test_files, vec!["src/parser.rs"]);
+        assert_eq!(changes.tests_added, 1);
+    }
+
+    #[test]
+    fn test_mixed_changes_body() {
+        let diff = format!(
+            "{}{}",
+            file_diff(
+                "src/parser.rs",
+                "@@ -1,3 +1,3 @@\n-fn parse() {}\n+fn parse(input: &str) {}\n"
+            ),
+            file_diff(
+                "tests/parser.rs",
+                "@@ -1,3 +1,9 @@\n\
+                 +#[test]\n\
+                 +fn parses_input() {}\n\
+                 +#[test]\n\
+                 +fn parses_empty() {}\n"
+            )
+        );
+        let changes = TestChanges::from_diff(&diff);
+        assert!(changes.is_mixed());
+        assert_eq!(
+            changes.commit_body().as_deref(),
+            Some("测试：新增 2 个测试用例，修改 1 个测试文件")
+        );
+
+        assert_eq!(TestChanges::from_diff(""), TestChanges::default());
+    }
+}
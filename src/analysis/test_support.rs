@@ -0,0 +1,6 @@
+//! 分析模块测试共用的辅助函数
+
+/// 构造单个文件的 git diff 片段，`body` 为 hunk 内容
+pub(crate) fn file_diff(path: &str, body: &str) -> String {
+    format!("diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n{body}")
+}
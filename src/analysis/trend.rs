@@ -0,0 +1,371 @@
+//! 复杂度趋势
+//!
+//! 沿第一父提交链在指定日期之后均匀抽取若干提交，用 `git ls-tree` 和 `git cat-file --batch`
+//! 直接读取各提交中的源码（不检出工作区），计算与 `analyze complexity` 相同的指标。
+//! 每个提交的结果保存在存储中，再次分析时不重复计算。
+
+use crate::analysis::complexity::{self, ComplexityReport, ComplexityThresholds};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// 默认抽样的提交数
+pub const DEFAULT_SAMPLES: usize = 12;
+
+/// 迷你图使用的字符，从低到高
+const SPARK_CHARS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// 单个提交的复杂度指标
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrendPoint {
+    pub commit: String,
+    /// 提交日期（YYYY-MM-DD）
+    pub date: String,
+    pub files: usize,
+    pub functions: usize,
+    pub average_cyclomatic: f64,
+    pub average_cognitive: f64,
+    pub max_cyclomatic: u32,
+    pub violations: usize,
+}
+
+impl TrendPoint {
+    pub fn from_report(commit: &str, date: &str, report: &ComplexityReport) -> Self {
+        Self {
+            commit: commit.to_string(),
+            date: date.to_string(),
+            files: report.files_analyzed,
+            functions: report.functions.len(),
+            average_cyclomatic: report.average_cyclomatic(),
+            average_cognitive: report.average_cognitive(),
+            max_cyclomatic: report
+                .functions
+                .iter()
+                .map(|f| f.cyclomatic)
+                .max()
+                .unwrap_or(0),
+            violations: report.violations().len(),
+        }
+    }
+
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(8)]
+    }
+}
+
+/// 按时间排列的趋势数据
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComplexityTrend {
+    pub since: String,
+    pub points: Vec<TrendPoint>,
+}
+
+impl ComplexityTrend {
+    pub fn cyclomatic_sparkline(&self) -> String {
+        sparkline(
+            &self
+                .points
+                .iter()
+                .map(|p| p.average_cyclomatic)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn cognitive_sparkline(&self) -> String {
+        sparkline(
+            &self
+                .points
+                .iter()
+                .map(|p| p.average_cognitive)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn violations_sparkline(&self) -> String {
+        sparkline(
+            &self
+                .points
+                .iter()
+                .map(|p| p.violations as f64)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// 第一个和最后一个样本的平均圈复杂度之差
+    pub fn cyclomatic_change(&self) -> f64 {
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => last.average_cyclomatic - first.average_cyclomatic,
+            _ => 0.0,
+        }
+    }
+
+    /// 根据变化趋势给出的建议
+    pub fn recommendations(&self) -> Vec<String> {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return Vec::new();
+        };
+        let mut recommendations = Vec::new();
+        if self.cyclomatic_change() >= 0.5 {
+            recommendations.push(format!(
+                "平均圈复杂度从 {:.1} 上升到 {:.1}，建议用 `ai-commit analyze complexity` 查看当前热点函数",
+                first.average_cyclomatic, last.average_cyclomatic
+            ));
+        }
+        if last.violations > first.violations {
+            recommendations.push(format!(
+                "超过阈值的函数从 {} 个增加到 {} 个",
+                first.violations, last.violations
+            ));
+        }
+        recommendations
+    }
+}
+
+/// 用方块字符绘制迷你图，所有值相同时画在中间高度
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if (max - min).abs() < f64::EPSILON {
+                return SPARK_CHARS[SPARK_CHARS.len() / 2 - 1];
+            }
+            let ratio = (value - min) / (max - min);
+            SPARK_CHARS[(ratio * (SPARK_CHARS.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
+/// 从 `total` 个按时间排列的提交中均匀取 `samples` 个，总是包含第一个和最后一个
+pub fn sample_indices(total: usize, samples: usize) -> Vec<usize> {
+    if total == 0 || samples == 0 {
+        return Vec::new();
+    }
+    if samples >= total {
+        return (0..total).collect();
+    }
+    if samples == 1 {
+        return vec![total - 1];
+    }
+    let mut indices: Vec<usize> = (0..samples)
+        .map(|i| i * (total - 1) / (samples - 1))
+        .collect();
+    indices.dedup();
+    indices
+}
+
+/// 指定日期之后第一父提交链上的提交（SHA，日期），按时间从旧到新排列
+pub async fn commits_since(since: &str) -> anyhow::Result<Vec<(String, String)>> {
+    let output = Command::new("git")
+        .args([
+            "log",
+            "--first-parent",
+            "--reverse",
+            &format!("--since={}", since),
+            "--format=%H %cs",
+            "HEAD",
+        ])
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list commits: {}", e))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list commits since {}: {}",
+            since,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(sha, date)| (sha.to_string(), date.to_string()))
+        .collect())
+}
+
+/// 读取提交中路径下复杂度分析支持的源文件，路径相对于当前目录
+pub async fn read_commit_sources(
+    commit: &str,
+    paths: &[String],
+) -> anyhow::Result<Vec<(String, String)>> {
+    let output = Command::new("git")
+        .args(["ls-tree", "-r", "--name-only", commit, "--"])
+        .args(paths)
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list files of {}: {}", commit, e))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list files of {}: {}",
+            commit,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|path| complexity::is_supported(Path::new(path)))
+        .map(str::to_string)
+        .collect();
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // 一个 cat-file 进程读取所有文件，`<commit>:./<path>` 中的路径相对于当前目录
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run git cat-file: {}", e))?;
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("Failed to open git cat-file input"))?;
+    let request: String = files
+        .iter()
+        .map(|path| format!("{}:./{}\n", commit, path))
+        .collect();
+    // 边写边读，避免输出填满管道时互相等待
+    let writer = tokio::spawn(async move {
+        let result = stdin.write_all(request.as_bytes()).await;
+        drop(stdin);
+        result
+    });
+    let output = child.wait_with_output().await?;
+    writer.await??;
+
+    let blobs = parse_batch_output(&output.stdout);
+    Ok(files
+        .into_iter()
+        .zip(blobs)
+        .filter_map(|(path, blob)| Some((path, String::from_utf8(blob?).ok()?)))
+        .collect())
+}
+
+/// 解析 `git cat-file --batch` 的输出，对象不存在时对应 `None`
+fn parse_batch_output(mut output: &[u8]) -> Vec<Option<Vec<u8>>> {
+    let mut blobs = Vec::new();
+    while let Some(end) = output.iter().position(|&b| b == b'\n') {
+        let header = String::from_utf8_lossy(&output[..end]).to_string();
+        output = &output[end + 1..];
+        let size = match header.split(' ').collect::<Vec<_>>().as_slice() {
+            [_, "blob", size] => size.parse::<usize>().ok(),
+            [_, _, size] => {
+                // 不是文件内容（如子模块），跳过对象数据
+                let size = size.parse::<usize>().unwrap_or(0);
+                output = output.get(size + 1..).unwrap_or_default();
+                blobs.push(None);
+                continue;
+            }
+            _ => {
+                blobs.push(None);
+                continue;
+            }
+        };
+        let Some(size) = size.filter(|&size| size <= output.len()) else {
+            break;
+        };
+        blobs.push(Some(output[..size].to_vec()));
+        output = output.get(size + 1..).unwrap_or_default();
+    }
+    blobs
+}
+
+/// 样本的存储范围：分析的目录、路径和阈值不同时结果分开保存
+pub fn trend_scope(base: &Path, paths: &[String], thresholds: ComplexityThresholds) -> String {
+    crate::cache::content_hash(&format!(
+        "{}\n{}\n{}/{}",
+        base.display(),
+        paths.join("\n"),
+        thresholds.max_cyclomatic,
+        thresholds.max_cognitive
+    ))
+}
+
+/// 分析单个提交
+pub async fn analyze_commit(
+    commit: &str,
+    date: &str,
+    paths: &[String],
+    thresholds: ComplexityThresholds,
+) -> anyhow::Result<TrendPoint> {
+    let mut report = ComplexityReport::new(thresholds);
+    for (path, source) in read_commit_sources(commit, paths).await? {
+        report.add_source(&path, &source);
+    }
+    Ok(TrendPoint::from_report(commit, date, &report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(date: &str, cyclomatic: f64, violations: usize) -> TrendPoint {
+        TrendPoint {
+            commit: "0123456789abcdef".to_string(),
+            date: date.to_string(),
+            files: 1,
+            functions: 2,
+            average_cyclomatic: cyclomatic,
+            average_cognitive: cyclomatic * 2.0,
+            max_cyclomatic: 8,
+            violations,
+        }
+    }
+
+    #[test]
+    fn test_sparkline_and_sampling() {
+        assert_eq!(sparkline(&[1.0, 2.0, 3.0, 8.0]), "▁▂▃█");
+        assert_eq!(sparkline(&[2.0, 2.0]), "▄▄");
+        assert_eq!(sparkline(&[]), "");
+
+        assert_eq!(sample_indices(10, 4), vec![0, 3, 6, 9]);
+        assert_eq!(sample_indices(3, 12), vec![0, 1, 2]);
+        assert_eq!(sample_indices(5, 1), vec![4]);
+        assert!(sample_indices(0, 3).is_empty());
+    }
+
+    #[test]
+    fn test_trend_point_and_recommendations() {
+        let mut report = ComplexityReport::new(ComplexityThresholds {
+            max_cyclomatic: 1,
+            max_cognitive: 15,
+        });
+        report.add_source(
+            "lib.rs",
+            "fn pick(a: bool) -> u8 {\n    if a { 1 } else { 2 }\n}\n",
+        );
+        let sample = TrendPoint::from_report("abc", "2024-05-01", &report);
+        assert_eq!(sample.files, 1);
+        assert_eq!(sample.functions, 1);
+        assert_eq!(sample.violations, 1);
+        assert_eq!(sample.max_cyclomatic, 2);
+
+        let trend = ComplexityTrend {
+            since: "2024-01-01".to_string(),
+            points: vec![point("2024-01-02", 2.0, 1), point("2024-03-01", 3.0, 3)],
+        };
+        assert_eq!(trend.cyclomatic_change(), 1.0);
+        assert_eq!(trend.cyclomatic_sparkline(), "▁█");
+        assert_eq!(trend.recommendations().len(), 2);
+        assert_eq!(trend.points[0].short_commit(), "01234567");
+
+        let thresholds = ComplexityThresholds::default();
+        let scope = trend_scope(Path::new("/repo"), &["src".to_string()], thresholds);
+        assert_eq!(scope.len(), 16);
+        assert_ne!(scope, trend_scope(Path::new("/repo"), &[], thresholds));
+    }
+
+    #[test]
+    fn test_parse_batch_output() {
+        let output = b"1111 blob 5\nhello\n2222 missing\n3333 blob 0\n\n";
+        assert_eq!(
+            parse_batch_output(output),
+            vec![Some(b"hello".to_vec()), None, Some(Vec::new())]
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! 按内容哈希缓存的本地分析结果
+//!
+//! 以文件内容的哈希为键，内容不变时直接复用上次的结果，避免每次提交重复分析未修改的文件。
+//! 缓存保存在数据目录下的 JSON 文件中，读写失败时退化为空缓存，不影响主流程。
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// 单个缓存文件最多保留的条目数，超出时只保留本次使用过的条目
+const MAX_ENTRIES: usize = 2000;
+
+/// 计算内容哈希（FNV-1a 64 位），结果在不同版本和平台间保持稳定
+pub fn content_hash(content: &str) -> String {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = content.bytes().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+/// 缓存文件所在目录：`<data_dir>/cache`
+pub fn cache_dir() -> anyhow::Result<PathBuf> {
+    Ok(crate::config::data_dir()?.join("cache"))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile<T> {
+    entries: HashMap<String, T>,
+}
+
+/// 以内容哈希为键的持久化缓存
+#[derive(Debug)]
+pub struct ContentCache<T> {
+    path: Option<PathBuf>,
+    entries: HashMap<String, T>,
+    touched: HashSet<String>,
+    hits: usize,
+    dirty: bool,
+}
+
+impl<T> Default for ContentCache<T> {
+    fn default() -> Self {
+        Self {
+            path: None,
+            entries: HashMap::new(),
+            touched: HashSet::new(),
+            hits: 0,
+            dirty: false,
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Clone> ContentCache<T> {
+    /// 仅在内存中使用的缓存（不落盘）
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// 从文件加载缓存，文件不存在或损坏时返回空缓存
+    pub fn load(path: &Path) -> Self {
+        let entries = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CacheFile<T>>(&content).ok())
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self {
+            path: Some(path.to_path_buf()),
+            entries,
+            ..Default::default()
+        }
+    }
+
+    /// 按内容查找缓存结果
+    pub fn get(&mut self, content: &str) -> Option<T> {
+        let key = content_hash(content);
+        let value = self.entries.get(&key).cloned()?;
+        self.touched.insert(key);
+        self.hits += 1;
+        Some(value)
+    }
+
+    /// 写入内容对应的结果
+    pub fn insert(&mut self, content: &str, value: T) {
+        let key = content_hash(content);
+        self.touched.insert(key.clone());
+        self.entries.insert(key, value);
+        self.dirty = true;
+    }
+
+    /// 本次命中缓存的次数
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 有新条目时写回文件，条目过多时丢弃本次未使用的条目
+    pub fn save(&mut self) -> anyhow::Result<()> {
+        let Some(path) = self.path.as_ref().filter(|_| self.dirty) else {
+            return Ok(());
+        };
+
+        if self.entries.len() > MAX_ENTRIES {
+            let touched = &self.touched;
+            self.entries.retain(|key, _| touched.contains(key));
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = CacheFile {
+            entries: std::mem::take(&mut self.entries),
+        };
+        let result = serde_json::to_string(&file)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| std::fs::write(path, json).map_err(anyhow::Error::from));
+        self.entries = file.entries;
+        result?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+        assert_eq!(content_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(content_hash("fn a() {}"), content_hash("fn b() {}"));
+    }
+
+    #[test]
+    fn test_content_cache_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/cache.json");
+
+        let mut cache: ContentCache<Vec<u32>> = ContentCache::load(&path);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get("source"), None);
+        cache.insert("source", vec![1, 2]);
+        cache.save().unwrap();
+
+        let mut cache: ContentCache<Vec<u32>> = ContentCache::load(&path);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get("source"), Some(vec![1, 2]));
+        assert_eq!(cache.get("changed source"), None);
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn test_corrupted_cache_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        std::fs::write(&path, "not json").unwrap();
+
+        let cache: ContentCache<String> = ContentCache::load(&path);
+        assert!(cache.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
-use clap::Parser;
+use crate::report::Section;
+use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug, Default)]
 #[command(
@@ -9,13 +10,17 @@ use clap::Parser;
 )]
 pub struct Args {
     /// AI provider to use (ollama, deepseek, siliconflow, or kimi)
-    #[arg(short = 'P', long, default_value = "")] // 空字符串表示未指定
+    #[arg(short = 'P', long, default_value = "", global = true)] // 空字符串表示未指定
     pub provider: String,
 
     /// Model to use (default: mistral)
-    #[arg(short, long, default_value = "")] // 空字符串表示未指定
+    #[arg(short, long, default_value = "", global = true)] // 空字符串表示未指定
     pub model: String,
 
+    /// 在指定的仓库中运行，默认为当前目录
+    #[arg(long, value_name = "PATH")]
+    pub repo: Option<String>,
+
     /// 不自动执行 git add .
     #[arg(short = 'n', long, default_value_t = false)]
     pub no_add: bool,
@@ -204,6 +209,28 @@ pub struct Args {
     #[arg(long = "watch", default_value_t = false)]
     pub watch: bool,
 
+    /// 监控模式下自动暂存并提交变更（需配合 --watch 使用）
+    #[arg(long = "auto-commit", default_value_t = false, requires = "watch")]
+    pub auto_commit: bool,
+
+    /// 自动提交前的静默期（秒），期间的连续保存会合并为一次提交
+    #[arg(
+        long = "auto-commit-delay",
+        value_name = "SECONDS",
+        default_value_t = 30,
+        global = true
+    )]
+    pub auto_commit_delay: u64,
+
+    /// 自动提交时忽略的文件模式（支持 * ? ** 通配符，可重复指定或用逗号分隔）
+    #[arg(
+        long = "auto-commit-ignore",
+        value_name = "PATTERN",
+        value_delimiter = ',',
+        global = true
+    )]
+    pub auto_commit_ignore: Vec<String>,
+
     /// 显示增强的差异查看
     #[arg(long = "diff-view", value_name = "COMMIT")]
     pub diff_view: Option<String>,
@@ -243,6 +270,20 @@ pub struct Args {
     #[arg(long = "yes", short = 'y', default_value_t = false)]
     pub skip_confirm: bool,
 
+    // =============== 敏感信息检测相关参数 ===============
+    /// 暂存的变更中检测到 API Key、密码等敏感信息时仅警告，不中止提交
+    #[arg(long = "allow-sensitive", default_value_t = false)]
+    pub allow_sensitive: bool,
+
+    // =============== 审查门禁相关参数 ===============
+    /// 提交前运行代码审查，发现不低于该严重程度（info、low、medium、high、critical）的问题时阻止提交
+    #[arg(long = "review-gate", value_name = "SEVERITY")]
+    pub review_gate: Option<String>,
+
+    /// 审查门禁发现问题时仍然提交（只打印问题）
+    #[arg(long = "force", default_value_t = false, requires = "review_gate")]
+    pub force: bool,
+
     // =============== Gitmoji 相关参数 ===============
     /// 在 commit message 前添加 gitmoji（如 ✨ feat, 🐛 fix）
     #[arg(long = "emoji", short = 'e', default_value_t = false)]
@@ -253,6 +294,29 @@ pub struct Args {
     #[arg(long = "candidates", short = 'c', default_value_t = 1)]
     pub candidates: u8,
 
+    // =============== 工单号相关参数 ===============
+    /// 从分支名提取工单号的正则（如 PROJ-\d+），多个正则重复指定，匹配结果自动附加到 commit message
+    #[arg(long = "ticket-pattern", value_name = "REGEX")]
+    pub ticket_pattern: Vec<String>,
+
+    /// 工单号位置：trailer（追加 Refs: PROJ-123，默认）或 prefix（标题前缀）
+    #[arg(long = "ticket-position", value_name = "POSITION")]
+    pub ticket_position: Option<String>,
+
+    // =============== Jira 集成相关参数 ===============
+    /// 提交或审查后在分支关联的 Jira 工单下评论（需设置 AI_COMMIT_JIRA_URL、AI_COMMIT_JIRA_TOKEN）
+    #[arg(long = "jira-comment", default_value_t = false, global = true)]
+    pub jira_comment: bool,
+
+    // =============== Pull Request 相关参数 ===============
+    /// 提交并推送后在 GitHub 上创建 PR，描述由 AI 生成（需设置 GITHUB_TOKEN，需配合 --push）
+    #[arg(long = "create-pr", default_value_t = false, requires = "push")]
+    pub create_pr: bool,
+
+    /// PR 的目标分支（默认使用仓库默认分支）
+    #[arg(long = "pr-base", value_name = "BRANCH", requires = "create_pr")]
+    pub pr_base: Option<String>,
+
     // =============== Git Hook 相关参数 ===============
     /// 安装 prepare-commit-msg hook 到 .git/hooks/
     #[arg(long = "hook-install", default_value_t = false)]
@@ -275,6 +339,376 @@ pub struct Args {
     /// 重置项目记忆
     #[arg(long = "memory-reset", default_value_t = false)]
     pub memory_reset: bool,
+
+    // =============== AI 解释相关参数 ===============
+    /// 用 AI 解释变更内容、可能动机和潜在风险（可指定提交或范围，如 HEAD~3..HEAD，不指定时解释已暂存的变更）
+    #[arg(long = "explain", value_name = "COMMIT|RANGE", num_args = 0..=1, default_missing_value = "", action = clap::ArgAction::Set)]
+    pub explain: Option<String>,
+
+    // =============== 代码审查相关参数 ===============
+    /// 审查/分析报告输出格式：text、markdown、json、html、sarif（仅审查报告）
+    #[arg(
+        long = "report-format",
+        value_name = "FORMAT",
+        default_value = "text",
+        global = true
+    )]
+    pub report_format: String,
+
+    /// 将审查/分析报告写入文件而不是输出到终端
+    #[arg(long = "report-output", value_name = "PATH", global = true)]
+    pub report_output: Option<String>,
+
+    /// 将审查/分析报告上传到对象存储（s3://bucket/prefix 或 gs://bucket/prefix），按 仓库/分支/提交 组织路径并输出访问地址
+    #[arg(long = "report-publish", value_name = "URL", global = true)]
+    pub report_publish: Option<String>,
+
+    /// 只输出报告的这些部分（逗号分隔）：summary、stats、issues、ai-review、suggestions
+    #[arg(
+        long = "report-sections",
+        value_name = "SECTIONS",
+        value_delimiter = ',',
+        global = true
+    )]
+    pub report_sections: Vec<Section>,
+
+    /// 不输出报告的这些部分，可重复指定或逗号分隔
+    #[arg(
+        long = "exclude-section",
+        value_name = "SECTION",
+        value_delimiter = ',',
+        global = true
+    )]
+    pub exclude_section: Vec<Section>,
+
+    // =============== 签名校验相关参数 ===============
+    /// 校验提交的 GPG/SSH 签名（可指定范围，如 --verify-signatures origin/main..HEAD，默认最近 50 个提交）
+    #[arg(long = "verify-signatures", value_name = "RANGE", num_args = 0..=1, default_missing_value = "", action = clap::ArgAction::Set)]
+    pub verify_signatures: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// 子命令
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// 本地静态分析，不调用 AI
+    Analyze {
+        #[command(subcommand)]
+        target: AnalyzeCommand,
+    },
+
+    /// 运行代码审查，查看和汇总已保存的审查报告（未指定操作时审查已暂存的变更）
+    Review {
+        #[command(subcommand)]
+        action: Option<ReviewCommand>,
+    },
+
+    /// 比较、导出和导入已保存的审查报告
+    #[command(visible_alias = "reports")]
+    Report {
+        #[command(subcommand)]
+        action: ReportCommand,
+    },
+
+    /// 管理审查报告的存储
+    Storage {
+        #[command(subcommand)]
+        action: StorageCommand,
+    },
+
+    /// 导出和导入配置包
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+
+    /// 管理当前仓库的后台自动提交守护进程
+    Daemon {
+        #[command(subcommand)]
+        action: DaemonCommand,
+    },
+}
+
+/// `config` 的操作
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ConfigCommand {
+    /// 把配置文件和 ~/.ai-commit 下的 .env 导出为一个配置包，默认不含 API Key 等敏感信息
+    Export {
+        /// 配置包路径，不指定时输出到终端
+        #[arg(value_name = "FILE")]
+        file: Option<String>,
+
+        /// 包含 API Key、令牌等敏感信息
+        #[arg(long = "include-secrets")]
+        include_secrets: bool,
+    },
+
+    /// 导入 `config export` 生成的配置包
+    Import {
+        /// 配置包路径
+        #[arg(value_name = "FILE")]
+        file: String,
+    },
+}
+
+/// `daemon` 的操作
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum DaemonCommand {
+    /// 在后台启动自动提交守护进程（使用 --auto-commit-delay/--auto-commit-ignore 配置）
+    Start,
+
+    /// 停止自动提交守护进程
+    Stop,
+
+    /// 查看守护进程状态（PID、启动时间、日志路径）
+    Status,
+}
+
+impl Default for ReviewCommand {
+    /// 只输入 `review` 时审查已暂存的变更
+    fn default() -> Self {
+        Self::Run {
+            commit: None,
+            staged: false,
+            range: None,
+        }
+    }
+}
+
+/// `storage` 的操作
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum StorageCommand {
+    /// 显示存储后端的设置和已保存数据的统计：报告数、大小、日期范围、平均得分等
+    Stats,
+
+    /// 列出共用存储中的项目命名空间及其报告数
+    Namespaces,
+
+    /// 检查存储后端是否可用，不可用时以非零状态退出
+    Health,
+
+    /// 加密启用 [storage] encryption_enabled 之前以明文保存的数据，加密后不再读取未加密的值
+    Encrypt,
+
+    /// 立即备份审查报告到备份目录，校验后删除超过 [storage] backup_keep 的旧备份
+    Backup {
+        /// 只列出已有的备份
+        #[arg(long = "list")]
+        list: bool,
+    },
+
+    /// 从备份恢复审查报告，未指定文件时使用最新的备份
+    Restore {
+        /// 备份文件路径
+        #[arg(value_name = "FILE")]
+        file: Option<String>,
+
+        /// 覆盖已存在的同一提交的报告
+        #[arg(long = "overwrite")]
+        overwrite: bool,
+    },
+
+    /// 按保留策略删除较早的审查报告，未指定的限制使用 [storage] 配置
+    Purge {
+        /// 只列出将删除的报告，不实际删除
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// 删除保存超过 N 天的报告
+        #[arg(long = "max-age-days", value_name = "N")]
+        max_age_days: Option<u64>,
+
+        /// 最多保留 N 份报告
+        #[arg(long = "max-reports", value_name = "N")]
+        max_reports: Option<usize>,
+
+        /// 报告总大小上限（MB）
+        #[arg(long = "max-size-mb", value_name = "MB")]
+        max_size_mb: Option<u64>,
+    },
+}
+
+/// `report` 的操作
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ReportCommand {
+    /// 比较两个提交的审查报告：新增、已修复和严重程度升高的问题以及得分变化
+    Diff {
+        /// 之前的提交（SHA、短 SHA 或分支名等引用）
+        #[arg(value_name = "BASE")]
+        base: String,
+
+        /// 之后的提交
+        #[arg(value_name = "HEAD")]
+        head: String,
+    },
+
+    /// 把审查报告导出为归档（默认 zstd 压缩的 tar，`.tar.gz`/`.tar` 结尾时使用 gzip/不压缩）
+    Export {
+        /// 归档文件路径，如 reports.tar.zst
+        #[arg(value_name = "FILE")]
+        file: String,
+
+        /// 只导出该日期（YYYY-MM-DD）及之后保存的报告
+        #[arg(long = "since", value_name = "DATE")]
+        since: Option<String>,
+
+        /// 只导出这些提交的报告（可重复指定或逗号分隔）
+        #[arg(long = "commit", value_name = "COMMIT", value_delimiter = ',')]
+        commits: Vec<String>,
+    },
+
+    /// 从归档导入审查报告到当前存储后端
+    Import {
+        /// 归档文件路径
+        #[arg(value_name = "FILE")]
+        file: String,
+
+        /// 覆盖已存在的同一提交的报告
+        #[arg(long = "overwrite")]
+        overwrite: bool,
+    },
+}
+
+/// `review` 的操作
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ReviewCommand {
+    /// 运行代码审查并生成报告，默认审查已暂存的变更
+    Run {
+        /// 要审查的提交（SHA、短 SHA 或分支名等引用）
+        #[arg(value_name = "COMMIT", conflicts_with_all = ["staged", "range"])]
+        commit: Option<String>,
+
+        /// 审查已暂存的变更（默认）
+        #[arg(long = "staged", conflicts_with = "range")]
+        staged: bool,
+
+        /// 审查提交范围，如 main..HEAD
+        #[arg(long = "range", value_name = "A..B")]
+        range: Option<String>,
+    },
+
+    /// 显示提交关联的审查报告（提交时的审查门禁或 `review run <COMMIT>` 生成）
+    Show {
+        /// 提交 SHA、短 SHA 或分支名等引用
+        #[arg(value_name = "COMMIT")]
+        commit: String,
+    },
+
+    /// 汇总已保存的审查报告：得分和各严重程度问题数的变化，以及反复出现的问题
+    Trend {
+        /// 只汇总该日期（YYYY-MM-DD）及之后保存的报告
+        #[arg(long = "since", value_name = "DATE")]
+        since: Option<String>,
+
+        /// 反复出现的问题显示的数量
+        #[arg(long = "top", value_name = "N", default_value_t = 10)]
+        top: usize,
+    },
+}
+
+/// `analyze` 的分析类型
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum AnalyzeCommand {
+    /// 函数复杂度分析：圈复杂度、认知复杂度、热点函数和重构建议，超过阈值时以退出码 1 结束
+    Complexity {
+        /// 要分析的文件或目录，默认当前目录
+        #[arg(value_name = "PATH")]
+        paths: Vec<String>,
+
+        /// 单个函数允许的最大圈复杂度
+        #[arg(long = "max-cyclomatic", value_name = "N", default_value_t = crate::analysis::complexity::DEFAULT_MAX_CYCLOMATIC)]
+        max_cyclomatic: u32,
+
+        /// 单个函数允许的最大认知复杂度
+        #[arg(long = "max-cognitive", value_name = "N", default_value_t = crate::analysis::complexity::DEFAULT_MAX_COGNITIVE)]
+        max_cognitive: u32,
+
+        /// 热点函数列表显示的数量
+        #[arg(long = "top", value_name = "N", default_value_t = 10)]
+        top: usize,
+    },
+
+    /// 重复代码检测：精确重复、结构重复（仅命名/常量不同）和跨文件重复，按冗余行数排序并给出重构建议
+    Duplication {
+        /// 要分析的文件或目录，默认当前目录
+        #[arg(value_name = "PATH")]
+        paths: Vec<String>,
+
+        /// 视为重复的最少有效行数（不含空行、注释和纯括号行）
+        #[arg(long = "min-lines", value_name = "N", default_value_t = crate::analysis::duplication::DEFAULT_MIN_LINES)]
+        min_lines: usize,
+
+        /// 只报告跨文件的重复
+        #[arg(long = "cross-file-only", default_value_t = false)]
+        cross_file_only: bool,
+
+        /// 重复热点列表显示的数量
+        #[arg(long = "top", value_name = "N", default_value_t = 10)]
+        top: usize,
+    },
+
+    /// 变更热点：结合一段时间内的修改次数和当前复杂度，列出经常修改且复杂的高风险文件
+    Hotspots {
+        /// 要分析的文件或目录，默认当前目录
+        #[arg(value_name = "PATH")]
+        paths: Vec<String>,
+
+        /// 统计修改次数的起始日期，git 能识别的格式均可
+        #[arg(long = "since", value_name = "DATE", default_value = crate::analysis::hotspots::DEFAULT_SINCE)]
+        since: String,
+
+        /// 热点文件列表显示的数量
+        #[arg(long = "top", value_name = "N", default_value_t = 10)]
+        top: usize,
+    },
+
+    /// 复杂度趋势：在历史提交中抽样计算复杂度指标，显示迷你图，样本保存在数据目录中
+    Trend {
+        /// 要分析的文件或目录，默认当前目录
+        #[arg(value_name = "PATH")]
+        paths: Vec<String>,
+
+        /// 起始日期，git 能识别的格式均可，如 2024-01-01、"3 months ago"
+        #[arg(long = "since", value_name = "DATE")]
+        since: String,
+
+        /// 抽样的提交数，总是包含区间内的第一个和最后一个提交
+        #[arg(long = "samples", value_name = "N", default_value_t = crate::analysis::trend::DEFAULT_SAMPLES)]
+        samples: usize,
+
+        /// 统计超过阈值的函数时使用的最大圈复杂度
+        #[arg(long = "max-cyclomatic", value_name = "N", default_value_t = crate::analysis::complexity::DEFAULT_MAX_CYCLOMATIC)]
+        max_cyclomatic: u32,
+
+        /// 统计超过阈值的函数时使用的最大认知复杂度
+        #[arg(long = "max-cognitive", value_name = "N", default_value_t = crate::analysis::complexity::DEFAULT_MAX_COGNITIVE)]
+        max_cognitive: u32,
+    },
+
+    /// 代码所有权：按目录统计主要作者、知识集中度和巴士因子，列出主要作者已不活跃的目录
+    Ownership {
+        /// 要分析的文件或目录，默认当前目录
+        #[arg(value_name = "PATH")]
+        paths: Vec<String>,
+
+        /// 只统计该日期以来的提交，默认统计全部历史
+        #[arg(long = "since", value_name = "DATE")]
+        since: Option<String>,
+
+        /// 按前几级目录汇总
+        #[arg(long = "depth", value_name = "N", default_value_t = crate::analysis::ownership::DEFAULT_DEPTH)]
+        depth: usize,
+
+        /// 作者超过多少天没有提交视为不活跃
+        #[arg(long = "inactive-days", value_name = "DAYS", default_value_t = crate::analysis::ownership::DEFAULT_INACTIVE_DAYS)]
+        inactive_days: u64,
+
+        /// 目录列表显示的数量
+        #[arg(long = "top", value_name = "N", default_value_t = 20)]
+        top: usize,
+    },
 }
 
 #[cfg(test)]
@@ -1069,5 +1503,616 @@ mod tests {
         assert!(!args.memory_show);
         assert!(!args.memory_reset);
     }
+
+    #[test]
+    fn test_args_auto_commit_flags() {
+        let args = Args::try_parse_from(["ai-commit"]).unwrap();
+        assert!(!args.auto_commit);
+        assert_eq!(args.auto_commit_delay, 30);
+        assert!(args.auto_commit_ignore.is_empty());
+
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "--watch",
+            "--auto-commit",
+            "--auto-commit-delay",
+            "10",
+            "--auto-commit-ignore",
+            "*.log,target/",
+            "--auto-commit-ignore",
+            "*.tmp",
+        ])
+        .unwrap();
+        assert!(args.watch);
+        assert!(args.auto_commit);
+        assert_eq!(args.auto_commit_delay, 10);
+        assert_eq!(args.auto_commit_ignore, vec!["*.log", "target/", "*.tmp"]);
+
+        // --auto-commit 必须配合 --watch
+        assert!(Args::try_parse_from(["ai-commit", "--auto-commit"]).is_err());
+    }
+
+    #[test]
+    fn test_args_explain() {
+        let args = Args::try_parse_from(["ai-commit"]).unwrap();
+        assert_eq!(args.explain, None);
+
+        // 不带参数时解释已暂存的变更
+        let args = Args::try_parse_from(["ai-commit", "--explain"]).unwrap();
+        assert_eq!(args.explain, Some("".to_string()));
+
+        let args = Args::try_parse_from(["ai-commit", "--explain", "HEAD~3..HEAD"]).unwrap();
+        assert_eq!(args.explain, Some("HEAD~3..HEAD".to_string()));
+    }
+
+    #[test]
+    fn test_args_ticket() {
+        let args = Args::try_parse_from(["ai-commit"]).unwrap();
+        assert!(args.ticket_pattern.is_empty());
+        assert_eq!(args.ticket_position, None);
+
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "--ticket-pattern",
+            r"PROJ-\d+",
+            "--ticket-pattern",
+            r"ENG-\d{2,5}",
+            "--ticket-position",
+            "prefix",
+        ])
+        .unwrap();
+        // 正则中的逗号不会被拆开
+        assert_eq!(args.ticket_pattern, vec![r"PROJ-\d+", r"ENG-\d{2,5}"]);
+        assert_eq!(args.ticket_position, Some("prefix".to_string()));
+
+        let args = Args::try_parse_from(["ai-commit", "--jira-comment"]).unwrap();
+        assert!(args.jira_comment);
+    }
+
+    #[test]
+    fn test_args_allow_sensitive() {
+        let args = Args::try_parse_from(["ai-commit"]).unwrap();
+        assert!(!args.allow_sensitive);
+
+        let args = Args::try_parse_from(["ai-commit", "--allow-sensitive"]).unwrap();
+        assert!(args.allow_sensitive);
+    }
+
+    #[test]
+    fn test_args_create_pr() {
+        let args = Args::try_parse_from(["ai-commit", "-p", "--create-pr"]).unwrap();
+        assert!(args.create_pr);
+        assert_eq!(args.pr_base, None);
+
+        let args = Args::try_parse_from(["ai-commit", "-p", "--create-pr", "--pr-base", "develop"])
+            .unwrap();
+        assert_eq!(args.pr_base, Some("develop".to_string()));
+
+        // --create-pr 必须配合 --push
+        assert!(Args::try_parse_from(["ai-commit", "--create-pr"]).is_err());
+        assert!(Args::try_parse_from(["ai-commit", "-p", "--pr-base", "main"]).is_err());
+    }
+
+    #[test]
+    fn test_args_review() {
+        let args = Args::try_parse_from(["ai-commit"]).unwrap();
+        assert_eq!(args.command, None);
+        assert_eq!(args.report_format, "text");
+        assert_eq!(args.report_output, None);
+
+        let args = Args::try_parse_from(["ai-commit", "review", "run"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Review {
+                action: Some(ReviewCommand::Run {
+                    commit: None,
+                    staged: false,
+                    range: None
+                })
+            })
+        );
+
+        let args = Args::try_parse_from(["ai-commit", "review"]).unwrap();
+        assert_eq!(args.command, Some(Command::Review { action: None }));
+
+        let args = Args::try_parse_from(["ai-commit", "review", "run", "--staged", "-P", "ollama"])
+            .unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Review {
+                action: Some(ReviewCommand::Run { staged: true, .. })
+            })
+        ));
+        assert_eq!(args.provider, "ollama");
+
+        let args = Args::try_parse_from(["ai-commit", "review", "run", "3f2a9c0"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Review {
+                action: Some(ReviewCommand::Run { commit: Some(ref c), .. })
+            }) if c == "3f2a9c0"
+        ));
+
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "review",
+            "run",
+            "--range",
+            "main..HEAD",
+            "--jira-comment",
+            "--report-format",
+            "markdown",
+            "--report-output",
+            "review.md",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Review {
+                action: Some(ReviewCommand::Run {
+                    commit: None,
+                    staged: false,
+                    range: Some("main..HEAD".to_string())
+                })
+            })
+        );
+        assert!(args.jira_comment);
+        assert_eq!(args.report_format, "markdown");
+        assert_eq!(args.report_output, Some("review.md".to_string()));
+        assert_eq!(args.report_publish, None);
+
+        assert!(Args::try_parse_from([
+            "ai-commit",
+            "review",
+            "run",
+            "--staged",
+            "--range",
+            "a..b"
+        ])
+        .is_err());
+        assert!(Args::try_parse_from(["ai-commit", "review", "run", "HEAD", "--staged"]).is_err());
+        assert!(Args::try_parse_from(["ai-commit", "--review"]).is_err());
+
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "review",
+            "run",
+            "--report-publish",
+            "s3://reports/ci",
+        ])
+        .unwrap();
+        assert_eq!(args.report_publish, Some("s3://reports/ci".to_string()));
+
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "review",
+            "run",
+            "--report-sections",
+            "summary,issues,stats",
+            "--exclude-section",
+            "suggestions",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.report_sections,
+            vec![Section::Summary, Section::Issues, Section::Stats]
+        );
+        assert_eq!(args.exclude_section, vec![Section::Suggestions]);
+        assert!(Args::try_parse_from(["ai-commit", "--report-sections", "charts"]).is_err());
+    }
+
+    #[test]
+    fn test_args_verify_signatures() {
+        let args = Args::try_parse_from(["ai-commit"]).unwrap();
+        assert_eq!(args.verify_signatures, None);
+
+        let args = Args::try_parse_from(["ai-commit", "--verify-signatures"]).unwrap();
+        assert_eq!(args.verify_signatures, Some("".to_string()));
+
+        let args = Args::try_parse_from(["ai-commit", "--verify-signatures", "origin/main..HEAD"])
+            .unwrap();
+        assert_eq!(
+            args.verify_signatures,
+            Some("origin/main..HEAD".to_string())
+        );
+    }
+
+    #[test]
+    fn test_args_daemon_subcommand() {
+        let args = Args::try_parse_from(["ai-commit"]).unwrap();
+        assert_eq!(args.command, None);
+
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "daemon",
+            "start",
+            "--auto-commit-delay",
+            "120",
+            "--auto-commit-ignore",
+            "*.log",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Daemon {
+                action: DaemonCommand::Start
+            })
+        );
+        assert_eq!(args.auto_commit_delay, 120);
+        assert_eq!(args.auto_commit_ignore, vec!["*.log"]);
+
+        let args = Args::try_parse_from(["ai-commit", "daemon", "stop"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Daemon {
+                action: DaemonCommand::Stop
+            })
+        );
+
+        let args = Args::try_parse_from(["ai-commit", "daemon", "status"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Daemon {
+                action: DaemonCommand::Status
+            })
+        );
+
+        assert!(Args::try_parse_from(["ai-commit", "--daemon-start"]).is_err());
+    }
+
+    #[test]
+    fn test_args_config_subcommand() {
+        let args = Args::try_parse_from(["ai-commit", "config", "export"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Config {
+                action: ConfigCommand::Export {
+                    file: None,
+                    include_secrets: false
+                }
+            })
+        );
+
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "config",
+            "export",
+            "setup.toml",
+            "--include-secrets",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Config {
+                action: ConfigCommand::Export {
+                    file: Some("setup.toml".to_string()),
+                    include_secrets: true
+                }
+            })
+        );
+
+        let args = Args::try_parse_from(["ai-commit", "config", "import", "setup.toml"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Config {
+                action: ConfigCommand::Import {
+                    file: "setup.toml".to_string()
+                }
+            })
+        );
+
+        // 敏感信息只在导出时使用
+        assert!(Args::try_parse_from([
+            "ai-commit",
+            "config",
+            "import",
+            "a.toml",
+            "--include-secrets"
+        ])
+        .is_err());
+        assert!(Args::try_parse_from(["ai-commit", "config", "import"]).is_err());
+        assert!(Args::try_parse_from(["ai-commit", "--config-export"]).is_err());
+    }
+
+    #[test]
+    fn test_args_analyze_complexity() {
+        let args = Args::try_parse_from(["ai-commit"]).unwrap();
+        assert_eq!(args.command, None);
+
+        let args = Args::try_parse_from(["ai-commit", "analyze", "complexity"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Analyze {
+                target: AnalyzeCommand::Complexity {
+                    paths: vec![],
+                    max_cyclomatic: 10,
+                    max_cognitive: 15,
+                    top: 10,
+                }
+            })
+        );
+
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "analyze",
+            "complexity",
+            "src",
+            "tests",
+            "--max-cyclomatic",
+            "8",
+            "--report-format",
+            "json",
+        ])
+        .unwrap();
+        let Some(Command::Analyze {
+            target:
+                AnalyzeCommand::Complexity {
+                    paths,
+                    max_cyclomatic,
+                    ..
+                },
+        }) = args.command
+        else {
+            panic!("expected analyze complexity");
+        };
+        assert_eq!(paths, vec!["src".to_string(), "tests".to_string()]);
+        assert_eq!(max_cyclomatic, 8);
+        assert_eq!(args.report_format, "json");
+    }
+
+    #[test]
+    fn test_args_review_show() {
+        let args = Args::try_parse_from(["ai-commit", "review", "show", "3f2a9c0"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Review {
+                action: Some(ReviewCommand::Show {
+                    commit: "3f2a9c0".to_string()
+                })
+            })
+        );
+        assert!(Args::try_parse_from(["ai-commit", "review", "show"]).is_err());
+    }
+
+    #[test]
+    fn test_args_report_diff() {
+        let args =
+            Args::try_parse_from(["ai-commit", "report", "diff", "3f2a9c0", "HEAD"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Report {
+                action: ReportCommand::Diff {
+                    base: "3f2a9c0".to_string(),
+                    head: "HEAD".to_string(),
+                }
+            })
+        );
+        assert!(Args::try_parse_from(["ai-commit", "report", "diff", "HEAD"]).is_err());
+    }
+
+    #[test]
+    fn test_args_report_export_import() {
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "reports",
+            "export",
+            "backup.tar.zst",
+            "--since",
+            "2024-05-01",
+            "--commit",
+            "HEAD,3f2a9c0",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Report {
+                action: ReportCommand::Export {
+                    file: "backup.tar.zst".to_string(),
+                    since: Some("2024-05-01".to_string()),
+                    commits: vec!["HEAD".to_string(), "3f2a9c0".to_string()],
+                }
+            })
+        );
+
+        let args =
+            Args::try_parse_from(["ai-commit", "report", "import", "backup.tar.zst"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Report {
+                action: ReportCommand::Import {
+                    file: "backup.tar.zst".to_string(),
+                    overwrite: false,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_args_storage_purge() {
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "storage",
+            "purge",
+            "--dry-run",
+            "--max-reports",
+            "200",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Storage {
+                action: StorageCommand::Purge {
+                    dry_run: true,
+                    max_age_days: None,
+                    max_reports: Some(200),
+                    max_size_mb: None,
+                }
+            })
+        );
+        assert!(Args::try_parse_from(["ai-commit", "storage"]).is_err());
+
+        let args =
+            Args::try_parse_from(["ai-commit", "storage", "restore", "--overwrite"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Storage {
+                action: StorageCommand::Restore {
+                    file: None,
+                    overwrite: true,
+                }
+            })
+        );
+
+        let args = Args::try_parse_from(["ai-commit", "storage", "health"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Storage {
+                action: StorageCommand::Health
+            })
+        );
+
+        let args = Args::try_parse_from(["ai-commit", "storage", "encrypt"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Storage {
+                action: StorageCommand::Encrypt
+            })
+        );
+
+        let args = Args::try_parse_from(["ai-commit", "storage", "namespaces"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Storage {
+                action: StorageCommand::Namespaces
+            })
+        );
+
+        let args = Args::try_parse_from(["ai-commit", "storage", "stats"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Storage {
+                action: StorageCommand::Stats
+            })
+        );
+    }
+
+    #[test]
+    fn test_args_review_trend() {
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "review",
+            "trend",
+            "--since",
+            "2024-05-01",
+            "--report-format",
+            "html",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Review {
+                action: Some(ReviewCommand::Trend {
+                    since: Some("2024-05-01".to_string()),
+                    top: 10,
+                })
+            })
+        );
+        assert_eq!(args.report_format, "html");
+    }
+
+    #[test]
+    fn test_args_analyze_duplication() {
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "analyze",
+            "duplication",
+            "src",
+            "--min-lines",
+            "4",
+            "--cross-file-only",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Analyze {
+                target: AnalyzeCommand::Duplication {
+                    paths: vec!["src".to_string()],
+                    min_lines: 4,
+                    cross_file_only: true,
+                    top: 10,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_args_analyze_hotspots() {
+        let args = Args::try_parse_from(["ai-commit", "analyze", "hotspots", "src", "--top", "5"])
+            .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Analyze {
+                target: AnalyzeCommand::Hotspots {
+                    paths: vec!["src".to_string()],
+                    since: "12 months ago".to_string(),
+                    top: 5,
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_args_analyze_trend() {
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "analyze",
+            "trend",
+            "--since",
+            "2024-01-01",
+            "--samples",
+            "6",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Analyze {
+                target: AnalyzeCommand::Trend {
+                    paths: vec![],
+                    since: "2024-01-01".to_string(),
+                    samples: 6,
+                    max_cyclomatic: 10,
+                    max_cognitive: 15,
+                }
+            })
+        );
+        assert!(Args::try_parse_from(["ai-commit", "analyze", "trend"]).is_err());
+    }
+
+    #[test]
+    fn test_args_analyze_ownership() {
+        let args = Args::try_parse_from([
+            "ai-commit",
+            "analyze",
+            "ownership",
+            "src",
+            "--depth",
+            "1",
+            "--inactive-days",
+            "90",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.command,
+            Some(Command::Analyze {
+                target: AnalyzeCommand::Ownership {
+                    paths: vec!["src".to_string()],
+                    since: None,
+                    depth: 1,
+                    inactive_days: 90,
+                    top: 20,
+                }
+            })
+        );
+    }
 }
 // CLI参数修改
@@ -0,0 +1,620 @@
+use crate::analysis::complexity::{self, ComplexityReport, ComplexityThresholds};
+use crate::analysis::duplication::{self, DuplicationReport};
+use crate::analysis::hotspots::{self, HotspotReport};
+use crate::analysis::ownership::{self, OwnershipReport};
+use crate::analysis::trend::{self, ComplexityTrend};
+use crate::analysis::{collect_source_files, display_path};
+use crate::cli::args::{AnalyzeCommand, Args};
+use crate::commands::review::publish_report;
+use crate::config::Config;
+use crate::report::{formatter_with, AnalysisReport, ReportFormat, ReportFormatter, ReportTable};
+use crate::storage::StorageManager;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 处理 `analyze` 子命令，返回是否通过阈值检查（未通过时调用方以退出码 1 结束）
+pub async fn handle_analyze_command(command: &AnalyzeCommand, args: &Args) -> anyhow::Result<bool> {
+    let format: ReportFormat = args.report_format.parse()?;
+
+    let (report, passed) = match command {
+        AnalyzeCommand::Complexity {
+            paths,
+            max_cyclomatic,
+            max_cognitive,
+            top,
+        } => {
+            let thresholds = ComplexityThresholds {
+                max_cyclomatic: *max_cyclomatic,
+                max_cognitive: *max_cognitive,
+            };
+            let complexity = analyze_complexity(paths, thresholds)?;
+            let passed = complexity.violations().is_empty();
+            (complexity_report(&complexity, *top)?, passed)
+        }
+        AnalyzeCommand::Duplication {
+            paths,
+            min_lines,
+            cross_file_only,
+            top,
+        } => {
+            let sources = read_sources(paths, duplication::is_supported)?;
+            let mut duplication = DuplicationReport::analyze(&sources, *min_lines);
+            if *cross_file_only {
+                duplication.blocks.retain(|block| block.is_cross_file());
+            }
+            // 重复检测只生成报告，不设阈值
+            (duplication_report(&duplication, *top)?, true)
+        }
+        AnalyzeCommand::Hotspots { paths, since, top } => {
+            let report = hotspots::analyze(since, paths).await?;
+            // 热点只生成报告，不设阈值
+            (hotspots_report(&report, *top)?, true)
+        }
+        AnalyzeCommand::Trend {
+            paths,
+            since,
+            samples,
+            max_cyclomatic,
+            max_cognitive,
+        } => {
+            let thresholds = ComplexityThresholds {
+                max_cyclomatic: *max_cyclomatic,
+                max_cognitive: *max_cognitive,
+            };
+            let trend = complexity_trend(paths, since, *samples, thresholds).await?;
+            // 趋势只生成报告，不设阈值
+            (trend_report(&trend)?, true)
+        }
+        AnalyzeCommand::Ownership {
+            paths,
+            since,
+            depth,
+            inactive_days,
+            top,
+        } => {
+            let report =
+                ownership::analyze(since.as_deref(), paths, *depth, *inactive_days).await?;
+            // 所有权只生成报告，不设阈值
+            (ownership_report(&report, *top)?, true)
+        }
+    };
+
+    let mut config = Config::new();
+    config.update_from_args(args);
+    let formatter = formatter_with(format, &config.report);
+    // 以报告标题作为上传的文件名，如 `complexity-analysis.html`
+    let name = report.title.to_lowercase().replace(' ', "-");
+    match args.report_output.as_deref() {
+        Some(path) => {
+            write_report(formatter.as_ref(), &report, Path::new(path))?;
+            println!("✓ Analysis report written to {}", path);
+            // 指定了输出文件时上传该文件，不需要报告内容
+            publish_report(args, format, "", &name, None).await?;
+        }
+        None => {
+            let output = formatter.format_analysis(&report)?;
+            println!("{}", output.trim_end());
+            publish_report(args, format, &output, &name, None).await?;
+        }
+    }
+    Ok(passed)
+}
+
+/// 把报告逐节写入文件，渲染结果不在内存中拼接为整份文档（`AnalysisReport` 本身仍完整保存在内存中）
+fn write_report(
+    formatter: &dyn ReportFormatter,
+    report: &AnalysisReport,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let failed = |e: &dyn std::fmt::Display| {
+        anyhow::anyhow!("Failed to write report to {}: {}", path.display(), e)
+    };
+    let file = std::fs::File::create(path).map_err(|e| failed(&e))?;
+    let mut out = std::io::BufWriter::new(file);
+    formatter
+        .write_analysis(report, &mut out)
+        .map_err(|e| failed(&e))?;
+    out.flush().map_err(|e| failed(&e))
+}
+
+/// 读取路径下所有满足条件的源文件，返回（显示路径，源码）
+fn read_sources(
+    paths: &[String],
+    accept: impl Fn(&Path) -> bool,
+) -> anyhow::Result<Vec<(String, String)>> {
+    let roots: Vec<PathBuf> = if paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        paths.iter().map(PathBuf::from).collect()
+    };
+
+    let mut sources = Vec::new();
+    for file in collect_source_files(&roots, accept)? {
+        // 非 UTF-8 文件（如二进制误用源码扩展名）直接跳过
+        let Ok(source) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        sources.push((display_path(&file), source));
+    }
+    Ok(sources)
+}
+
+/// 分析路径下所有支持语言的源文件
+fn analyze_complexity(
+    paths: &[String],
+    thresholds: ComplexityThresholds,
+) -> anyhow::Result<ComplexityReport> {
+    let mut report = ComplexityReport::new(thresholds);
+    for (path, source) in read_sources(paths, complexity::is_supported)? {
+        report.add_source(&path, &source);
+    }
+    Ok(report)
+}
+
+fn complexity_report(complexity: &ComplexityReport, top: usize) -> anyhow::Result<AnalysisReport> {
+    let columns = [
+        "Function",
+        "Location",
+        "Lines",
+        "Cyclomatic",
+        "Cognitive",
+        "Nesting",
+    ];
+    let row = |function: &complexity::FunctionComplexity| {
+        vec![
+            function.name.clone(),
+            function.location(),
+            function.length.to_string(),
+            function.cyclomatic.to_string(),
+            function.cognitive.to_string(),
+            function.max_nesting.to_string(),
+        ]
+    };
+
+    let mut hotspots = ReportTable::new("Hotspots", &columns);
+    hotspots.rows = complexity.hotspots(top).into_iter().map(row).collect();
+
+    let violations = complexity.violations();
+    let mut exceeded = ReportTable::new(
+        &format!(
+            "Threshold violations (cyclomatic > {} or cognitive > {})",
+            complexity.thresholds.max_cyclomatic, complexity.thresholds.max_cognitive
+        ),
+        &columns,
+    );
+    exceeded.rows = violations.iter().map(|f| row(f)).collect();
+
+    let mut report = AnalysisReport::new("Complexity Analysis")
+        .metric("Files analyzed", complexity.files_analyzed)
+        .metric("Functions", complexity.functions.len())
+        .metric(
+            "Average cyclomatic",
+            format!("{:.1}", complexity.average_cyclomatic()),
+        )
+        .metric(
+            "Average cognitive",
+            format!("{:.1}", complexity.average_cognitive()),
+        )
+        .metric("Threshold violations", violations.len())
+        .table(hotspots)
+        .table(exceeded);
+    report.recommendations = complexity.recommendations();
+    report.details = Some(serde_json::to_value(complexity)?);
+    Ok(report)
+}
+
+fn hotspots_report(report: &HotspotReport, top: usize) -> anyhow::Result<AnalysisReport> {
+    let mut files = ReportTable::new(
+        "Hotspots",
+        &[
+            "File",
+            "Score",
+            "Risk",
+            "Commits",
+            "Lines changed",
+            "Functions",
+            "Cyclomatic",
+            "Max cyclomatic",
+        ],
+    );
+    files.rows = report
+        .hotspots
+        .iter()
+        .take(top)
+        .map(|hotspot| {
+            vec![
+                hotspot.file.clone(),
+                format!("{:.1}", hotspot.score),
+                hotspot.risk().to_string(),
+                hotspot.commits.to_string(),
+                hotspot.lines_changed.to_string(),
+                hotspot.functions.to_string(),
+                hotspot.total_cyclomatic.to_string(),
+                hotspot.max_cyclomatic.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut analysis = AnalysisReport::new("Churn vs Complexity Hotspots")
+        .metric("Since", &report.since)
+        .metric("Files changed", report.files_changed)
+        .metric("Files analyzed", report.hotspots.len())
+        .metric("High risk", report.high_risk())
+        .table(files);
+    analysis.recommendations = report.recommendations(top);
+    analysis.details = Some(serde_json::to_value(report)?);
+    Ok(analysis)
+}
+
+fn ownership_report(report: &OwnershipReport, top: usize) -> anyhow::Result<AnalysisReport> {
+    let mut directories = ReportTable::new(
+        "Directories",
+        &[
+            "Directory",
+            "Main author",
+            "Share",
+            "Bus factor",
+            "Authors",
+            "Lines changed",
+            "Orphaned",
+        ],
+    );
+    directories.rows = report
+        .directories
+        .iter()
+        .take(top)
+        .map(|directory| {
+            vec![
+                directory.directory.clone(),
+                directory
+                    .main_author()
+                    .map(|a| a.name.clone())
+                    .unwrap_or_default(),
+                format!("{:.0}%", directory.concentration() * 100.0),
+                directory.bus_factor.to_string(),
+                directory.authors.len().to_string(),
+                directory.lines.to_string(),
+                if directory.is_orphaned() { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut orphaned = ReportTable::new("Orphaned areas", &["Directory", "Main authors"]);
+    orphaned.rows = report
+        .orphaned()
+        .into_iter()
+        .map(|directory| {
+            let authors: Vec<&str> = directory
+                .authors
+                .iter()
+                .take(directory.bus_factor)
+                .map(|a| a.name.as_str())
+                .collect();
+            vec![directory.directory.clone(), authors.join(", ")]
+        })
+        .collect();
+
+    let mut analysis = AnalysisReport::new("Code Ownership")
+        .metric("Directories", report.directories.len())
+        .metric("Bus factor 1", report.single_owner().len())
+        .metric("Orphaned", report.orphaned().len())
+        .metric("Inactive after (days)", report.inactive_days)
+        .table(directories)
+        .table(orphaned);
+    analysis.recommendations = report.recommendations(top);
+    analysis.details = Some(serde_json::to_value(report)?);
+    Ok(analysis)
+}
+
+/// 在历史提交中抽样计算复杂度，已保存的样本直接复用
+async fn complexity_trend(
+    paths: &[String],
+    since: &str,
+    samples: usize,
+    thresholds: ComplexityThresholds,
+) -> anyhow::Result<ComplexityTrend> {
+    let commits = trend::commits_since(since).await?;
+    if commits.is_empty() {
+        anyhow::bail!("No commits since {}", since);
+    }
+
+    // 存储不可用时照常计算，只是不保存样本
+    let storage = StorageManager::from_config(&Config::new()).ok();
+    let scope = trend::trend_scope(&std::env::current_dir()?, paths, thresholds);
+
+    let mut points = Vec::new();
+    for index in trend::sample_indices(commits.len(), samples) {
+        let (commit, date) = &commits[index];
+        if let Some(storage) = &storage {
+            if let Ok(Some(point)) = storage.load_trend_point(&scope, commit).await {
+                points.push(point);
+                continue;
+            }
+        }
+        let point = trend::analyze_commit(commit, date, paths, thresholds).await?;
+        if let Some(storage) = &storage {
+            if let Err(e) = storage.save_trend_point(&scope, &point).await {
+                eprintln!("⚠️ Failed to store trend sample: {}", e);
+            }
+        }
+        points.push(point);
+    }
+
+    Ok(ComplexityTrend {
+        since: since.to_string(),
+        points,
+    })
+}
+
+fn trend_report(trend: &ComplexityTrend) -> anyhow::Result<AnalysisReport> {
+    let mut samples = ReportTable::new(
+        "Samples",
+        &[
+            "Commit",
+            "Date",
+            "Files",
+            "Functions",
+            "Avg cyclomatic",
+            "Avg cognitive",
+            "Max cyclomatic",
+            "Violations",
+        ],
+    );
+    samples.rows = trend
+        .points
+        .iter()
+        .map(|point| {
+            vec![
+                point.short_commit().to_string(),
+                point.date.clone(),
+                point.files.to_string(),
+                point.functions.to_string(),
+                format!("{:.1}", point.average_cyclomatic),
+                format!("{:.1}", point.average_cognitive),
+                point.max_cyclomatic.to_string(),
+                point.violations.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut report = AnalysisReport::new("Complexity Trend")
+        .metric("Since", &trend.since)
+        .metric("Commits sampled", trend.points.len())
+        .metric(
+            "Cyclomatic change",
+            format!("{:+.1}", trend.cyclomatic_change()),
+        )
+        .metric("Avg cyclomatic", trend.cyclomatic_sparkline())
+        .metric("Avg cognitive", trend.cognitive_sparkline())
+        .metric("Violations", trend.violations_sparkline())
+        .table(samples);
+    report.recommendations = trend.recommendations();
+    report.details = Some(serde_json::to_value(trend)?);
+    Ok(report)
+}
+
+fn duplication_report(
+    duplication: &DuplicationReport,
+    top: usize,
+) -> anyhow::Result<AnalysisReport> {
+    let mut blocks = ReportTable::new(
+        "Duplicate blocks",
+        &["Kind", "Lines", "Copies", "Cross-file", "Locations"],
+    );
+    blocks.rows = duplication
+        .hotspots(top)
+        .into_iter()
+        .map(|block| {
+            let locations: Vec<String> = block.fragments.iter().map(|f| f.location()).collect();
+            vec![
+                block.kind.as_str().to_string(),
+                block.lines.to_string(),
+                block.fragments.len().to_string(),
+                if block.is_cross_file() { "yes" } else { "no" }.to_string(),
+                locations.join(", "),
+            ]
+        })
+        .collect();
+
+    let mut files = ReportTable::new("Files", &["File", "Duplicated lines", "Blocks"]);
+    files.rows = duplication
+        .files()
+        .into_iter()
+        .take(top)
+        .map(|file| {
+            vec![
+                file.file,
+                file.duplicated_lines.to_string(),
+                file.blocks.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut report = AnalysisReport::new("Duplication Analysis")
+        .metric("Files analyzed", duplication.files_analyzed)
+        .metric("Significant lines", duplication.total_lines)
+        .metric("Duplicate blocks", duplication.blocks.len())
+        .metric("Cross-file blocks", duplication.cross_file_blocks())
+        .metric("Redundant lines", duplication.duplicated_lines())
+        .metric(
+            "Duplication ratio",
+            format!("{:.1}%", duplication.duplication_ratio()),
+        )
+        .table(blocks)
+        .table(files);
+    report.recommendations = duplication.recommendations(top);
+    report.details = Some(serde_json::to_value(duplication)?);
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_complexity_thresholds() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "fn pick(a: bool, b: bool, c: bool) -> u8 {\n\
+             \x20   if a && b {\n\
+             \x20       1\n\
+             \x20   } else if c {\n\
+             \x20       2\n\
+             \x20   } else {\n\
+             \x20       3\n\
+             \x20   }\n\
+             }\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("README.md"), "# docs").unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let complexity = analyze_complexity(&paths, ComplexityThresholds::default()).unwrap();
+        assert_eq!(complexity.files_analyzed, 1);
+        assert_eq!(complexity.functions.len(), 1);
+        assert!(complexity.violations().is_empty());
+
+        let strict = ComplexityThresholds {
+            max_cyclomatic: 2,
+            max_cognitive: 15,
+        };
+        let complexity = analyze_complexity(&paths, strict).unwrap();
+        assert_eq!(complexity.violations().len(), 1);
+
+        let report = complexity_report(&complexity, 5).unwrap();
+        assert_eq!(report.tables[0].rows[0][0], "pick");
+        assert_eq!(report.tables[1].rows.len(), 1);
+        assert!(report.details.is_some());
+    }
+
+    #[test]
+    fn test_write_report_streams_same_output() {
+        let mut table = ReportTable::new("Hotspots", &["File", "Score"]);
+        for i in 0..50 {
+            table
+                .rows
+                .push(vec![format!("src/file_{}.rs", i), format!("{}.0", i)]);
+        }
+        let mut report = AnalysisReport::new("Hotspots")
+            .metric("Files", 50)
+            .table(table);
+        report
+            .recommendations
+            .push("拆分 src/file_49.rs".to_string());
+
+        let dir = tempfile::tempdir().unwrap();
+        for format in ["text", "markdown", "csv", "html"] {
+            let formatter = formatter_with(format.parse().unwrap(), &Default::default());
+            let path = dir.path().join(format);
+            write_report(formatter.as_ref(), &report, &path).unwrap();
+            assert_eq!(
+                std::fs::read_to_string(&path).unwrap(),
+                formatter.format_analysis(&report).unwrap(),
+                "{}",
+                format
+            );
+        }
+
+        let missing = dir.path().join("missing/report.txt");
+        let formatter = formatter_with(ReportFormat::Text, &Default::default());
+        let err = write_report(formatter.as_ref(), &report, &missing).unwrap_err();
+        assert!(err.to_string().starts_with("Failed to write report to"));
+    }
+
+    #[test]
+    fn test_duplication_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let body = "def load(path):\n\
+                    \x20   with open(path) as f:\n\
+                    \x20       data = f.read()\n\
+                    \x20   rows = data.splitlines()\n\
+                    \x20   rows = [r.strip() for r in rows]\n\
+                    \x20   return [r for r in rows if r]\n";
+        std::fs::write(dir.path().join("a.py"), body).unwrap();
+        std::fs::write(dir.path().join("b.py"), body.replace("load", "read")).unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+
+        let sources = read_sources(&paths, duplication::is_supported).unwrap();
+        let duplication = DuplicationReport::analyze(&sources, 5);
+        let report = duplication_report(&duplication, 5).unwrap();
+
+        assert_eq!(report.tables[0].rows.len(), 1);
+        assert_eq!(report.tables[0].rows[0][0], "structural");
+        assert_eq!(report.tables[0].rows[0][3], "yes");
+        assert_eq!(report.tables[1].rows.len(), 2);
+        assert_eq!(report.recommendations.len(), 1);
+    }
+
+    #[test]
+    fn test_hotspots_report() {
+        let hotspot = |file: &str, score: f64| hotspots::Hotspot {
+            file: file.to_string(),
+            commits: 4,
+            lines_changed: 120,
+            functions: 6,
+            total_cyclomatic: 30,
+            max_cyclomatic: 11,
+            score,
+        };
+        let report = HotspotReport {
+            since: "12 months ago".to_string(),
+            files_changed: 9,
+            hotspots: vec![hotspot("src/a.rs", 100.0), hotspot("src/b.rs", 12.5)],
+        };
+
+        let analysis = hotspots_report(&report, 1).unwrap();
+        assert_eq!(analysis.tables[0].rows.len(), 1);
+        assert_eq!(analysis.tables[0].rows[0][0], "src/a.rs");
+        assert_eq!(analysis.tables[0].rows[0][2], "high");
+        assert_eq!(analysis.recommendations.len(), 1);
+    }
+
+    #[test]
+    fn test_ownership_report() {
+        let commit = |author: &str, timestamp: i64, file: &str| ownership::CommitStat {
+            author: author.to_string(),
+            email: format!("{}@example.com", author),
+            timestamp,
+            files: vec![(file.to_string(), 10)],
+        };
+        let commits = vec![
+            commit("alice", 1_700_000_000, "src/api/a.rs"),
+            commit("bob", 1_000_000_000, "src/legacy/b.rs"),
+        ];
+        let report = OwnershipReport::build(&commits, 2, 180, 1_700_000_000);
+
+        let analysis = ownership_report(&report, 10).unwrap();
+        assert_eq!(analysis.tables[0].rows.len(), 2);
+        assert_eq!(analysis.tables[1].rows, vec![vec!["src/legacy", "bob"]]);
+        assert_eq!(analysis.recommendations.len(), 1);
+    }
+
+    #[test]
+    fn test_trend_report() {
+        let point = |commit: &str, cyclomatic: f64| trend::TrendPoint {
+            commit: commit.to_string(),
+            date: "2024-05-01".to_string(),
+            files: 4,
+            functions: 20,
+            average_cyclomatic: cyclomatic,
+            average_cognitive: cyclomatic,
+            max_cyclomatic: 12,
+            violations: 1,
+        };
+        let trend = ComplexityTrend {
+            since: "2024-01-01".to_string(),
+            points: vec![point("aaaa1111bbbb", 2.0), point("cccc2222dddd", 3.5)],
+        };
+
+        let report = trend_report(&trend).unwrap();
+        assert_eq!(report.tables[0].rows.len(), 2);
+        assert_eq!(report.tables[0].rows[1][0], "cccc2222");
+        assert!(report
+            .metrics
+            .iter()
+            .any(|m| m.name == "Cyclomatic change" && m.value == "+1.5"));
+        assert!(report.metrics.iter().any(|m| m.value == "▁█"));
+        assert_eq!(report.recommendations.len(), 1);
+    }
+}
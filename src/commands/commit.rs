@@ -1,15 +1,23 @@
+use crate::analysis::clustering::{self, ChangeClusters};
 use crate::cli::args::Args;
 use crate::config::Config;
 use crate::core::ai::agents::{AgentConfig, AgentContext, AgentManager, AgentTask, TaskType};
 use crate::core::ai::memory::ProjectMemory;
+use crate::core::ai::message_history::{self, MessageHistory, MessageRecord};
+use crate::core::ticket;
+use crate::languages::LanguageSummary;
+use crate::report::{CodeReviewReport, ReviewFinding, Severity};
 use crate::{git, ui};
 use std::collections::HashMap;
 use std::time::Instant;
 
+/// 作为 AI 上下文和快捷选项的相关历史提交信息数
+const RELATED_MESSAGES: usize = 3;
+
 /// 处理常规的 commit 相关命令
 pub async fn handle_commit_commands(args: &Args, config: &Config) -> anyhow::Result<()> {
     // git add（如果需要）
-    if !args.no_add {
+    if !config.no_add {
         git::git_add_all().await?;
     }
 
@@ -22,6 +30,22 @@ pub async fn handle_commit_commands(args: &Args, config: &Config) -> anyhow::Res
         return Ok(());
     }
 
+    // 在发送给 AI 和提交之前拦截密钥等敏感信息
+    check_sensitive_diff(&diff, args.allow_sensitive)?;
+    let review = match &args.review_gate {
+        Some(gate) => Some(check_review_gate(&diff, gate, args.force, config).await?),
+        None => None,
+    };
+    warn_license_changes(&diff);
+    warn_complex_changes(&diff, config.debug).await;
+    if config.debug {
+        if let Some(backends) = LanguageSummary::from_diff(&diff).describe_backends() {
+            println!("代码结构解析: {}", backends);
+        }
+    }
+    let context = CommitContext::collect(&diff, config).await;
+    suggest_commit_split(&context.clusters);
+
     // 加载项目记忆
     let working_dir = std::env::current_dir()?;
     let mut memory = ProjectMemory::load(&working_dir).unwrap_or_default();
@@ -35,12 +59,19 @@ pub async fn handle_commit_commands(args: &Args, config: &Config) -> anyhow::Res
         let _ = memory.save(&working_dir);
     }
 
+    // 修改过相同文件的历史提交信息，作为快捷选项
+    let suggestions: Vec<String> = context
+        .related
+        .iter()
+        .map(|record| record.message.clone())
+        .collect();
+
     // 生成 commit message（单个或多候选）
     let start_time = Instant::now();
     let ai_message = if config.candidates > 1 {
-        generate_and_select_candidates(&diff, config, &memory).await?
+        generate_and_select_candidates(&diff, config, &memory, &context, &suggestions).await?
     } else {
-        generate_commit_message_with_agent(&diff, config, &memory).await?
+        generate_commit_message_with_agent(&diff, config, &memory, &context).await?
     };
     let elapsed_time = start_time.elapsed();
 
@@ -63,15 +94,19 @@ pub async fn handle_commit_commands(args: &Args, config: &Config) -> anyhow::Res
         ai_message
     };
 
+    // 附加分支名中的工单号（如果配置）
+    let ai_message = apply_branch_ticket(&ai_message, config).await?;
+
     // 用户确认 commit message（多候选模式已选择过，可跳过二次确认）
     let skip = args.skip_confirm || config.candidates > 1;
-    let final_message = match ui::confirm_commit_message(&ai_message, skip)? {
-        ui::ConfirmResult::Confirmed(message) => message,
-        ui::ConfirmResult::Rejected => {
-            println!("操作已取消。");
-            return Ok(());
-        }
-    };
+    let final_message =
+        match ui::confirm_commit_message_with_suggestions(&ai_message, &suggestions, skip)? {
+            ui::ConfirmResult::Confirmed(message) => message,
+            ui::ConfirmResult::Rejected => {
+                println!("操作已取消。");
+                return Ok(());
+            }
+        };
 
     // 记录用户修正（如有）并更新记忆
     memory.record_correction(&ai_message, &final_message);
@@ -80,24 +115,40 @@ pub async fn handle_commit_commands(args: &Args, config: &Config) -> anyhow::Res
 
     // 提交更改
     git::git_commit(&final_message).await?;
+    store_commit_review(review.as_ref(), config).await;
+    record_accepted_message(&final_message, final_message != ai_message, &diff, config).await;
+
+    // 在关联的 Jira 工单下评论提交信息（失败不影响提交结果）
+    if args.jira_comment {
+        if let Err(e) = crate::commands::jira::post_commit_comment(&final_message, config).await {
+            eprintln!("⚠️  Failed to comment on Jira ticket: {}", e);
+        }
+    }
 
     // 推送（如果需要）
-    if args.push {
+    if config.push {
         if args.force_push {
             git::git_force_push().await?;
         } else {
             git::git_push().await?;
         }
+
+        // 推送后创建 Pull Request
+        if args.create_pr {
+            crate::commands::pr::create_pull_request(args, config, &final_message).await?;
+        }
     }
 
     Ok(())
 }
 
-/// 生成多个候选 commit message 并让用户选择
+/// 生成多个候选 commit message 并让用户选择，历史提交信息附在候选之后
 async fn generate_and_select_candidates(
     diff: &str,
     config: &Config,
     memory: &ProjectMemory,
+    context: &CommitContext,
+    suggestions: &[String],
 ) -> anyhow::Result<String> {
     let n = config.candidates.min(5) as usize; // 最多5个候选
 
@@ -108,7 +159,7 @@ async fn generate_and_select_candidates(
     // 生成 N 个候选（顺序生成，因为 AgentManager 不是 Send）
     let mut candidates = Vec::with_capacity(n);
     for i in 0..n {
-        match generate_commit_message_with_agent(diff, config, memory).await {
+        match generate_commit_message_with_agent(diff, config, memory, context).await {
             Ok(msg) if !msg.trim().is_empty() => {
                 if config.debug {
                     println!("候选 {} 已生成", i + 1);
@@ -135,14 +186,26 @@ async fn generate_and_select_candidates(
     // 去重
     candidates.dedup();
 
-    if candidates.len() == 1 {
+    if candidates.len() == 1 && suggestions.is_empty() {
         return Ok(candidates.into_iter().next().unwrap());
     }
 
     // 显示候选列表
-    let options: Vec<&str> = candidates.iter().map(|s| s.as_str()).collect();
+    let history_options: Vec<String> = suggestions
+        .iter()
+        .map(|message| format!("📜 {}", message))
+        .collect();
+    let options: Vec<&str> = candidates
+        .iter()
+        .chain(history_options.iter())
+        .map(|s| s.as_str())
+        .collect();
     let choice = ui::show_menu_and_get_choice(&options)?;
-    Ok(candidates.into_iter().nth(choice).unwrap())
+    Ok(candidates
+        .into_iter()
+        .chain(suggestions.iter().cloned())
+        .nth(choice)
+        .unwrap())
 }
 
 /// 处理 tag 创建相关的 commit 逻辑
@@ -151,6 +214,15 @@ pub async fn handle_tag_creation_commit(
     config: &Config,
     diff: &str,
 ) -> anyhow::Result<()> {
+    // 提交信息来自 --tag-note 时也要扫描暂存的变更并运行审查门禁
+    check_sensitive_diff(diff, args.allow_sensitive)?;
+    let review = match &args.review_gate {
+        Some(gate) if !diff.trim().is_empty() => {
+            Some(check_review_gate(diff, gate, args.force, config).await?)
+        }
+        _ => None,
+    };
+
     // 先生成下一个 tag 名字
     let tag_name = git::get_next_tag_name(args.new_tag.as_deref()).await?;
 
@@ -161,17 +233,25 @@ pub async fn handle_tag_creation_commit(
     } else {
         // 没有提供 tag_note，使用 AI 生成或默认使用 tag_name
         if !diff.trim().is_empty() {
+            warn_license_changes(diff);
+            warn_complex_changes(diff, config.debug).await;
+
             // 加载项目记忆
             let working_dir = std::env::current_dir()?;
             let memory = ProjectMemory::load(&working_dir).unwrap_or_default();
 
             // 有代码变更，使用 Agent 生成 commit message
-            let mut ai_message = generate_commit_message_with_agent(diff, config, &memory).await?;
+            let context = CommitContext::collect(diff, config).await;
+            let mut ai_message =
+                generate_commit_message_with_agent(diff, config, &memory, &context).await?;
 
             // 应用 gitmoji（如果启用）
             if config.emoji {
                 ai_message = crate::core::gitmoji::add_emoji(&ai_message);
             }
+            if !ai_message.is_empty() {
+                ai_message = apply_branch_ticket(&ai_message, config).await?;
+            }
 
             if !ai_message.is_empty() {
                 // 用户确认 AI 生成的消息
@@ -194,6 +274,7 @@ pub async fn handle_tag_creation_commit(
 
     if !diff.trim().is_empty() {
         git::git_commit(&commit_message).await?;
+        store_commit_review(review.as_ref(), config).await;
     } else {
         git::git_commit_allow_empty(&commit_message).await?;
     }
@@ -204,7 +285,7 @@ pub async fn handle_tag_creation_commit(
     if config.debug {
         println!("Created new tag: {}", &tag_name);
     }
-    if args.push {
+    if config.push {
         if args.force_push {
             // 对于tag推送，先尝试强制推送commit，再推送tag
             git::git_force_push().await?;
@@ -217,16 +298,322 @@ pub async fn handle_tag_creation_commit(
     Ok(())
 }
 
-/// 使用 Agent 生成 commit message
-async fn generate_commit_message_with_agent(
+/// 扫描暂存的 diff，发现敏感信息时打印遮盖后的结果并中止提交（`allow` 为真时只警告）
+pub(crate) fn check_sensitive_diff(diff: &str, allow: bool) -> anyhow::Result<()> {
+    let findings = crate::analysis::sensitive::scan_diff(diff);
+    if findings.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!("🔐 检测到 {} 处疑似敏感信息：", findings.len());
+    for finding in &findings {
+        eprintln!("  {}", finding);
+    }
+
+    if allow {
+        eprintln!("⚠️  已使用 --allow-sensitive，继续提交。");
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "提交已中止：暂存的变更中包含疑似敏感信息。请移除后重新暂存，或确认无误后使用 --allow-sensitive"
+        )
+    }
+}
+
+/// 提交前的审查门禁：对暂存的变更运行代码审查，发现不低于 `gate` 的问题时中止提交。
+/// 通过（或 `--force`）时返回审查报告，提交后按提交 SHA 保存
+pub(crate) async fn check_review_gate(
     diff: &str,
+    gate: &str,
+    force: bool,
     config: &Config,
-    memory: &ProjectMemory,
-) -> anyhow::Result<String> {
-    // 创建 Agent 管理器
-    let mut agent_manager = AgentManager::with_default_context();
+) -> anyhow::Result<CodeReviewReport> {
+    // 先校验严重程度，避免审查完成后才报错
+    let threshold: Severity = gate.parse()?;
+    let report = crate::commands::review::build_review_report("", diff, config).await?;
+    apply_review_gate(&report.findings_at_least(threshold), threshold, force)?;
+    Ok(report)
+}
+
+/// 把提交前生成的审查报告与新提交关联保存
+async fn store_commit_review(report: Option<&CodeReviewReport>, config: &Config) {
+    let Some(report) = report else {
+        return;
+    };
+    match git::GitCore::get_latest_commit_hash().await {
+        Ok(commit) => crate::commands::review::store_review_report(&commit, report, config).await,
+        Err(e) => eprintln!("⚠️  Failed to store review report: {}", e),
+    }
+}
+
+/// 根据达到门槛的问题决定是否继续提交（`force` 为真时只警告）
+fn apply_review_gate(
+    blocking: &[&ReviewFinding],
+    threshold: Severity,
+    force: bool,
+) -> anyhow::Result<()> {
+    if blocking.is_empty() {
+        println!("✓ 审查门禁通过：没有 {} 及以上的问题", threshold.as_str());
+        return Ok(());
+    }
+
+    eprintln!(
+        "🚦 审查发现 {} 个 {} 及以上的问题：",
+        blocking.len(),
+        threshold.as_str()
+    );
+    for finding in blocking {
+        let location = match (&finding.file, finding.line) {
+            (Some(file), Some(line)) => format!("{}:{} ", file, line),
+            (Some(file), None) => format!("{} ", file),
+            _ => String::new(),
+        };
+        eprintln!(
+            "  {} [{}] {}{}",
+            finding.severity.icon(),
+            finding.severity.as_str(),
+            location,
+            finding.message
+        );
+    }
+
+    if force {
+        eprintln!("⚠️  已使用 --force，继续提交。");
+        Ok(())
+    } else {
+        anyhow::bail!(
+            "提交已中止：审查发现 {} 个 {} 及以上的问题。修复后重新暂存，或确认无误后使用 --force",
+            blocking.len(),
+            threshold.as_str()
+        )
+    }
+}
+
+/// 提示暂存的变更中的许可证变更和许可证不兼容的新依赖（只警告，不阻止提交）
+pub(crate) fn warn_license_changes(diff: &str) {
+    let issues = crate::analysis::license::scan_diff(diff, std::path::Path::new("."));
+    if issues.is_empty() {
+        return;
+    }
+
+    eprintln!("⚖️  检测到 {} 处许可证相关变更：", issues.len());
+    for issue in &issues {
+        eprintln!("  {} {}", issue.severity.icon(), issue);
+    }
+}
+
+/// 增量分析暂存区中被改动的函数，提示超过复杂度阈值的函数（只警告，不阻止提交）
+pub(crate) async fn warn_complex_changes(diff: &str, debug: bool) {
+    use crate::analysis::complexity::ComplexityThresholds;
+    use crate::analysis::static_analysis::incremental;
+
+    let start = Instant::now();
+    let analysis = match incremental::analyze_staged(diff, ComplexityThresholds::default()).await {
+        Ok(analysis) => analysis,
+        Err(e) => {
+            if debug {
+                eprintln!("增量静态分析失败: {}", e);
+            }
+            return;
+        }
+    };
+    if debug {
+        println!(
+            "增量静态分析: {} 个文件（缓存命中 {}），{} 个变更函数，耗时 {:.2?}",
+            analysis.files_analyzed,
+            analysis.cache_hits,
+            analysis.functions.len(),
+            start.elapsed()
+        );
+    }
+
+    let violations = analysis.violations();
+    if violations.is_empty() {
+        return;
+    }
+    eprintln!("🧮 {} 个变更函数超过复杂度阈值：", violations.len());
+    for function in violations {
+        eprintln!(
+            "  {} {}（圈复杂度 {}，认知复杂度 {}）",
+            function.location(),
+            function.name,
+            function.cyclomatic,
+            function.cognitive
+        );
+    }
+}
+
+/// 提示拆分提交时最多列出的分组数，分组过多时多半是同一功能的分散修改
+const MAX_SPLIT_GROUPS: usize = 4;
+
+/// 按内容对暂存的变更聚类：配置了 embedding 模型时使用模型向量（结果按内容缓存），
+/// 未配置或请求失败时使用本地词法向量
+async fn cluster_changes(diff: &str, config: &Config) -> ChangeClusters {
+    let (code_diff, _) = crate::analysis::generated::split_noise(diff);
+    let hunks = clustering::split_hunks(&code_diff);
+    if hunks.len() < 2 {
+        return ChangeClusters::default();
+    }
+
+    if let Some(model) = &config.embedding_model {
+        match embed_hunks(&hunks, model, config).await {
+            Ok(embeddings) => {
+                return ChangeClusters::build(&hunks, &embeddings, clustering::EMBEDDING_THRESHOLD)
+            }
+            Err(e) => {
+                if config.debug {
+                    eprintln!("embedding 聚类失败，改用本地词法向量: {}", e);
+                }
+            }
+        }
+    }
+    ChangeClusters::lexical(&hunks)
+}
+
+async fn embed_hunks(
+    hunks: &[clustering::Hunk],
+    model: &str,
+    config: &Config,
+) -> anyhow::Result<Vec<Vec<f32>>> {
+    use crate::core::ai::embedding::{self, EmbeddingEndpoint};
+
+    let endpoint = EmbeddingEndpoint::from_chat_url(&config.get_url()).ok_or_else(|| {
+        anyhow::anyhow!("Provider {} does not support embeddings", config.provider)
+    })?;
+    let mut cache =
+        crate::cache::ContentCache::load(&crate::cache::cache_dir()?.join("embeddings-v1.json"));
+    let texts: Vec<String> = hunks.iter().map(|hunk| hunk.text.clone()).collect();
+    let embeddings = embedding::embed_cached(
+        &endpoint,
+        model,
+        config.get_api_key().as_deref(),
+        &texts,
+        &mut cache,
+    )
+    .await?;
+
+    if let Err(e) = cache.save() {
+        if config.debug {
+            eprintln!("保存 embedding 缓存失败: {}", e);
+        }
+    }
+    Ok(embeddings)
+}
+
+/// 暂存的变更可分为几组互不相关的修改时，提示拆分为多个提交（只提示，不阻止提交）
+pub(crate) fn suggest_commit_split(clusters: &ChangeClusters) {
+    let Some(groups) = clusters
+        .split_suggestion()
+        .filter(|groups| groups.len() <= MAX_SPLIT_GROUPS)
+    else {
+        return;
+    };
+
+    eprintln!(
+        "✂️  暂存的变更可分为 {} 组互不相关的修改，可考虑拆分为多个提交：",
+        groups.len()
+    );
+    for (i, group) in groups.iter().enumerate() {
+        eprintln!("  {}. {}", i + 1, group.files.join(", "));
+    }
+}
+
+/// 根据配置的分支名正则识别当前分支的工单号，未配置或未匹配时返回 None
+pub(crate) async fn detect_branch_ticket(config: &Config) -> anyhow::Result<Option<String>> {
+    if config.ticket_patterns.is_empty() {
+        return Ok(None);
+    }
+
+    // 分离 HEAD 等无法获取分支名的情况不识别工单号
+    let branch = match git::GitCore::get_current_branch().await {
+        Ok(branch) => branch,
+        Err(_) => return Ok(None),
+    };
+
+    let ticket = ticket::extract_ticket(&branch, &config.ticket_patterns)?;
+    if let Some(ticket) = ticket.as_ref().filter(|_| config.debug) {
+        println!("从分支 '{}' 识别到工单号: {}", branch, ticket);
+    }
+    Ok(ticket)
+}
+
+/// 将当前分支的工单号附加到 commit message，未配置或未匹配时原样返回
+pub(crate) async fn apply_branch_ticket(message: &str, config: &Config) -> anyhow::Result<String> {
+    match detect_branch_ticket(config).await? {
+        Some(ticket) => Ok(ticket::apply_ticket(
+            message,
+            &ticket,
+            config.ticket_position,
+        )),
+        None => Ok(message.to_string()),
+    }
+}
 
-    // 更新 Agent 配置
+/// 生成提交信息时提供给 AI 的变更上下文，每次提交只收集一次，多候选生成时共用
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CommitContext {
+    /// 修改过相同文件的历史提交信息
+    pub related: Vec<MessageRecord>,
+    /// 按内容相似度归类的变更分组
+    pub clusters: ChangeClusters,
+}
+
+impl CommitContext {
+    pub(crate) async fn collect(diff: &str, config: &Config) -> Self {
+        Self {
+            related: related_messages(diff, config).await,
+            clusters: cluster_changes(diff, config).await,
+        }
+    }
+}
+
+/// 当前项目中修改过 diff 涉及文件的近期提交信息，读取失败时返回空列表
+async fn related_messages(diff: &str, config: &Config) -> Vec<MessageRecord> {
+    let result = async {
+        let storage = crate::storage::StorageManager::from_config(config)?;
+        let history = MessageHistory::load(&storage, &std::env::current_dir()?).await?;
+        let files = diff_files(diff);
+        anyhow::Ok(
+            history
+                .related(&files, RELATED_MESSAGES)
+                .into_iter()
+                .cloned()
+                .collect(),
+        )
+    }
+    .await;
+    result.unwrap_or_else(|e| {
+        if config.debug {
+            eprintln!("Failed to load commit message history: {}", e);
+        }
+        Vec::new()
+    })
+}
+
+/// 记录已确认的提交信息（失败不影响提交结果）
+async fn record_accepted_message(message: &str, edited: bool, diff: &str, config: &Config) {
+    let result = async {
+        let storage = crate::storage::StorageManager::from_config(config)?;
+        let working_dir = std::env::current_dir()?;
+        let mut history = MessageHistory::load(&storage, &working_dir).await?;
+        history.record(message, diff_files(diff), edited);
+        history.save(&storage, &working_dir).await
+    }
+    .await;
+    if let Err(e) = result {
+        eprintln!("⚠️  Failed to record commit message history: {}", e);
+    }
+}
+
+fn diff_files(diff: &str) -> Vec<String> {
+    crate::analysis::static_analysis::incremental::changed_files(diff)
+        .into_iter()
+        .map(|file| file.path)
+        .collect()
+}
+
+/// 根据配置构建 Agent 执行上下文（API Key、API URL 通过环境变量传递给 Agent）
+pub(crate) fn build_agent_context(config: &Config) -> anyhow::Result<AgentContext> {
     let mut env_vars = std::env::vars().collect::<HashMap<String, String>>();
 
     // 添加 API Key 配置
@@ -237,12 +624,7 @@ async fn generate_commit_message_with_agent(
     // 设置 API URL
     let api_url = config.get_url();
     env_vars.insert("API_URL".to_string(), api_url);
-
-    // 注入项目记忆上下文
-    let memory_context = memory.to_prompt_context();
-    if !memory_context.is_empty() {
-        env_vars.insert("MEMORY_CONTEXT".to_string(), memory_context);
-    }
+    config.insert_conventions(&mut env_vars);
 
     let agent_config = AgentConfig {
         provider: config.provider.clone(),
@@ -254,12 +636,57 @@ async fn generate_commit_message_with_agent(
         timeout_secs: 60,
     };
 
-    let context = AgentContext {
+    Ok(AgentContext {
         working_dir: std::env::current_dir()?,
         env_vars,
         config: agent_config,
         history: vec![],
-    };
+    })
+}
+
+/// 使用 Agent 生成 commit message
+pub(crate) async fn generate_commit_message_with_agent(
+    diff: &str,
+    config: &Config,
+    memory: &ProjectMemory,
+    commit_context: &CommitContext,
+) -> anyhow::Result<String> {
+    // 创建 Agent 管理器
+    let mut agent_manager = AgentManager::with_default_context();
+
+    let mut context = build_agent_context(config)?;
+
+    // 注入项目记忆上下文
+    let memory_context = memory.to_prompt_context();
+    if !memory_context.is_empty() {
+        context
+            .env_vars
+            .insert("MEMORY_CONTEXT".to_string(), memory_context);
+    }
+
+    // 注入关联的 Jira 工单标题（如果配置）
+    if let Some(ticket_context) = crate::commands::jira::ticket_prompt_context(config).await {
+        context
+            .env_vars
+            .insert("TICKET_CONTEXT".to_string(), ticket_context);
+    }
+
+    // 注入修改过相同文件的历史提交信息，保持 type、scope 和措辞一致
+    let history_context =
+        message_history::to_prompt_context(&commit_context.related.iter().collect::<Vec<_>>());
+    if !history_context.is_empty() {
+        context
+            .env_vars
+            .insert("HISTORY_CONTEXT".to_string(), history_context);
+    }
+
+    // 注入按内容相似度归类的变更分组，帮助 AI 概括多文件的修改
+    let cluster_context = commit_context.clusters.to_prompt_context();
+    if !cluster_context.is_empty() {
+        context
+            .env_vars
+            .insert("CLUSTER_CONTEXT".to_string(), cluster_context);
+    }
 
     // 更新管理器上下文
     agent_manager.update_context(context);
@@ -323,13 +750,84 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_tag_note_commit_scans_sensitive_diff() {
+        let config = Config::new();
+        let args = Args {
+            tag_note: "release notes".to_string(),
+            ..create_test_args()
+        };
+        let leaked = format!(
+            "diff --git a/.env b/.env\n+AWS_KEY=AKIA{}\n",
+            "IOSFODNN7REALKEY"
+        );
+
+        // 在生成 tag 名字和提交之前中止
+        let err = handle_tag_creation_commit(&args, &config, &leaked)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("--allow-sensitive"));
+    }
+
+    #[tokio::test]
+    async fn test_tag_note_commit_runs_review_gate() {
+        let config = Config::new();
+        let args = Args {
+            tag_note: "release notes".to_string(),
+            review_gate: Some("blocker".to_string()),
+            ..create_test_args()
+        };
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\n+pub mod analysis;\n";
+
+        // 门禁在生成 tag 名字和提交之前运行，无效的门槛直接报错
+        let err = handle_tag_creation_commit(&args, &config, diff)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("blocker"));
+    }
+
+    #[test]
+    fn test_check_sensitive_diff() {
+        let clean = "diff --git a/src/lib.rs b/src/lib.rs\n+pub mod analysis;\n";
+        assert!(check_sensitive_diff(clean, false).is_ok());
+
+        let leaked = format!(
+            "diff --git a/.env b/.env\n+AWS_KEY=AKIA{}\n",
+            "IOSFODNN7REALKEY"
+        );
+        assert!(check_sensitive_diff(&leaked, false).is_err());
+        assert!(check_sensitive_diff(&leaked, true).is_ok());
+    }
+
+    #[test]
+    fn test_apply_review_gate() {
+        let finding = ReviewFinding {
+            severity: Severity::High,
+            category: "bug".to_string(),
+            message: "unwrap 可能导致崩溃".to_string(),
+            file: Some("src/main.rs".to_string()),
+            line: Some(42),
+        };
+
+        assert!(apply_review_gate(&[], Severity::Medium, false).is_ok());
+        let err = apply_review_gate(&[&finding], Severity::Medium, false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert!(apply_review_gate(&[&finding], Severity::Medium, true).is_ok());
+    }
+
     #[tokio::test]
     async fn test_generate_commit_message_with_agent() {
         let config = Config::new();
         let test_diff = "diff --git a/test.txt b/test.txt\n+new line";
         let memory = ProjectMemory::default();
 
-        let result = generate_commit_message_with_agent(test_diff, &config, &memory).await;
+        let result = generate_commit_message_with_agent(
+            test_diff,
+            &config,
+            &memory,
+            &CommitContext::default(),
+        )
+        .await;
 
         match result {
             Ok(message) => {
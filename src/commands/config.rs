@@ -0,0 +1,64 @@
+use crate::cli::args::ConfigCommand;
+use crate::config::ConfigBundle;
+
+/// 处理 `config` 子命令
+pub fn handle_config_subcommand(action: &ConfigCommand) -> anyhow::Result<()> {
+    match action {
+        ConfigCommand::Export {
+            file,
+            include_secrets,
+        } => export_config(file.as_deref(), *include_secrets),
+        ConfigCommand::Import { file } => import_config(file),
+    }
+}
+
+/// 导出配置包，未指定文件时输出到终端；提示信息输出到 stderr，便于重定向
+fn export_config(path: Option<&str>, include_secrets: bool) -> anyhow::Result<()> {
+    let (mut bundle, issues) = ConfigBundle::collect()?;
+    for issue in &issues {
+        eprintln!("⚠️  Skipped: {}", issue);
+    }
+    if !include_secrets {
+        let removed = bundle.strip_secrets();
+        if removed > 0 {
+            eprintln!(
+                "🔒 Left out {} secret(s), use --include-secrets to export them",
+                removed
+            );
+        }
+    }
+
+    let content = bundle.to_toml()?;
+    let Some(path) = path else {
+        print!("{}", content);
+        return Ok(());
+    };
+    std::fs::write(path, content)
+        .map_err(|e| anyhow::anyhow!("Failed to write config bundle to {}: {}", path, e))?;
+    println!(
+        "✓ Config exported to {} ({} profile(s))",
+        path,
+        bundle.profiles.len()
+    );
+    Ok(())
+}
+
+fn import_config(path: &str) -> anyhow::Result<()> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read config bundle {}: {}", path, e))?;
+    let (bundle, issues) = ConfigBundle::parse(&content)?;
+    for issue in &issues {
+        eprintln!("⚠️  {}: {}", path, issue);
+    }
+
+    let written = bundle.install()?;
+    if written.is_empty() {
+        println!("Config bundle {} is empty, nothing imported.", path);
+        return Ok(());
+    }
+    println!("✓ Config imported from {}:", path);
+    for file in &written {
+        println!("  {}", file.display());
+    }
+    Ok(())
+}
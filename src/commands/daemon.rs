@@ -0,0 +1,329 @@
+use crate::cli::args::{Args, DaemonCommand};
+use crate::config::Config;
+use crate::core::ai::memory::compute_project_hash;
+use crate::git::GitCore;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+/// 守护进程状态文件
+const STATE_FILE: &str = "daemon.json";
+
+/// 守护进程日志文件
+const LOG_FILE: &str = "daemon.log";
+
+/// 后台自动提交守护进程的状态，写入 `<数据目录>/daemon/<hash>/daemon.json`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DaemonState {
+    pub pid: u32,
+    pub repo_path: PathBuf,
+    pub started_at: String,
+    pub quiet_period_secs: u64,
+    pub ignore_patterns: Vec<String>,
+    pub log_file: PathBuf,
+}
+
+/// 处理守护进程相关命令
+pub async fn handle_daemon_commands(
+    action: &DaemonCommand,
+    args: &Args,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let repo_root = GitCore::get_repo_root().await?;
+
+    let message = match action {
+        DaemonCommand::Start => start_daemon(&repo_root, args, config)?,
+        DaemonCommand::Stop => stop_daemon(&repo_root)?,
+        DaemonCommand::Status => daemon_status(&repo_root)?,
+    };
+
+    println!("{}", message);
+    Ok(())
+}
+
+/// 获取仓库对应的守护进程目录
+pub fn daemon_dir(repo_root: &Path) -> anyhow::Result<PathBuf> {
+    let hash = compute_project_hash(repo_root);
+    Ok(crate::config::data_dir()?.join("daemon").join(hash))
+}
+
+/// 读取仓库的守护进程状态，进程已退出时清理残留的状态文件
+pub fn load_running_state(repo_root: &Path) -> anyhow::Result<Option<DaemonState>> {
+    let state_file = daemon_dir(repo_root)?.join(STATE_FILE);
+    if !state_file.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&state_file)?;
+    let state: DaemonState = match serde_json::from_str(&content) {
+        Ok(state) => state,
+        Err(_) => {
+            let _ = std::fs::remove_file(&state_file);
+            return Ok(None);
+        }
+    };
+
+    if !is_process_alive(state.pid) {
+        let _ = std::fs::remove_file(&state_file);
+        return Ok(None);
+    }
+
+    Ok(Some(state))
+}
+
+/// 以分离的子进程启动 `--watch --auto-commit`
+fn start_daemon(repo_root: &Path, args: &Args, config: &Config) -> anyhow::Result<String> {
+    if let Some(state) = load_running_state(repo_root)? {
+        return Ok(format!(
+            "Auto-commit daemon is already running (PID {})",
+            state.pid
+        ));
+    }
+
+    let dir = daemon_dir(repo_root)?;
+    std::fs::create_dir_all(&dir)?;
+    let log_path = dir.join(LOG_FILE);
+
+    let log = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)?;
+
+    let exe = std::env::current_exe()?;
+    let mut command = std::process::Command::new(exe);
+    command
+        .args(build_daemon_args(args, config))
+        .current_dir(repo_root)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+
+    // 放入独立进程组，终端关闭或 Ctrl+C 不会影响守护进程
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let child = command
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to start auto-commit daemon: {}", e))?;
+
+    let state = DaemonState {
+        pid: child.id(),
+        repo_path: repo_root.to_path_buf(),
+        started_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        quiet_period_secs: args.auto_commit_delay,
+        ignore_patterns: args.auto_commit_ignore.clone(),
+        log_file: log_path.clone(),
+    };
+    std::fs::write(dir.join(STATE_FILE), serde_json::to_string_pretty(&state)?)?;
+
+    Ok(format!(
+        "✓ Auto-commit daemon started (PID {})\n  Log: {}",
+        state.pid,
+        log_path.display()
+    ))
+}
+
+/// 停止守护进程并清理状态文件
+fn stop_daemon(repo_root: &Path) -> anyhow::Result<String> {
+    let state = match load_running_state(repo_root)? {
+        Some(state) => state,
+        None => return Ok("Auto-commit daemon is not running".to_string()),
+    };
+
+    terminate_process(state.pid)?;
+    let _ = std::fs::remove_file(daemon_dir(repo_root)?.join(STATE_FILE));
+
+    Ok(format!("✓ Auto-commit daemon stopped (PID {})", state.pid))
+}
+
+/// 输出守护进程状态
+fn daemon_status(repo_root: &Path) -> anyhow::Result<String> {
+    match load_running_state(repo_root)? {
+        Some(state) => Ok(format_status(&state)),
+        None => Ok(format!(
+            "Auto-commit daemon is not running for {}",
+            repo_root.display()
+        )),
+    }
+}
+
+fn format_status(state: &DaemonState) -> String {
+    let ignore = if state.ignore_patterns.is_empty() {
+        "(none)".to_string()
+    } else {
+        state.ignore_patterns.join(", ")
+    };
+
+    format!(
+        "🤖 Auto-commit daemon is running\n  PID:          {}\n  Repository:   {}\n  Started at:   {}\n  Quiet period: {}s\n  Ignoring:     {}\n  Log:          {}",
+        state.pid,
+        state.repo_path.display(),
+        state.started_at,
+        state.quiet_period_secs,
+        ignore,
+        state.log_file.display()
+    )
+}
+
+/// 构造守护进程的命令行参数
+fn build_daemon_args(args: &Args, config: &Config) -> Vec<String> {
+    let mut daemon_args = vec![
+        "--watch".to_string(),
+        "--auto-commit".to_string(),
+        "--auto-commit-delay".to_string(),
+        args.auto_commit_delay.to_string(),
+    ];
+
+    if !args.auto_commit_ignore.is_empty() {
+        daemon_args.push("--auto-commit-ignore".to_string());
+        daemon_args.push(args.auto_commit_ignore.join(","));
+    }
+    // 只传递命令行中指定的提供商和模型，其余来自配置文件，修改后守护进程会重新加载
+    if !args.provider.is_empty() {
+        daemon_args.push("--provider".to_string());
+        daemon_args.push(args.provider.clone());
+    }
+    if !args.model.is_empty() {
+        daemon_args.push("--model".to_string());
+        daemon_args.push(args.model.clone());
+    }
+    if config.emoji {
+        daemon_args.push("--emoji".to_string());
+    }
+    if !config.ticket_patterns.is_empty() {
+        // 正则中可能含有逗号（如 \d{2,5}），每个正则单独传递
+        for pattern in &config.ticket_patterns {
+            daemon_args.push("--ticket-pattern".to_string());
+            daemon_args.push(pattern.clone());
+        }
+        if config.ticket_position == crate::core::ticket::TicketPosition::Prefix {
+            daemon_args.push("--ticket-position".to_string());
+            daemon_args.push("prefix".to_string());
+        }
+    }
+
+    daemon_args
+}
+
+/// 检查进程是否存活
+fn is_process_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(unix))]
+    {
+        std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+            .unwrap_or(false)
+    }
+}
+
+/// 结束进程
+fn terminate_process(pid: u32) -> anyhow::Result<()> {
+    #[cfg(unix)]
+    let status = std::process::Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .status();
+
+    #[cfg(not(unix))]
+    let status = std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/F"])
+        .status();
+
+    let status = status.map_err(|e| anyhow::anyhow!("Failed to stop process {}: {}", pid, e))?;
+    if !status.success() {
+        anyhow::bail!(
+            "Failed to stop process {} (exit code: {:?})",
+            pid,
+            status.code()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_daemon_dir() {
+        let dir = daemon_dir(Path::new("/tmp/some-repo")).unwrap();
+        assert!(dir.to_string_lossy().contains(".ai-commit/daemon"));
+        assert_ne!(dir, daemon_dir(Path::new("/tmp/other-repo")).unwrap());
+    }
+
+    #[test]
+    fn test_build_daemon_args() {
+        let args = Args {
+            auto_commit_delay: 45,
+            auto_commit_ignore: vec!["*.log".to_string(), "target/".to_string()],
+            provider: "ollama".to_string(),
+            ..Default::default()
+        };
+        let config = Config {
+            provider: "ollama".to_string(),
+            model: "from-config".to_string(),
+            emoji: true,
+            ticket_patterns: vec![r"ENG-\d{2,5}".to_string(), r"[A-Z]+-\d+".to_string()],
+            ..Default::default()
+        };
+
+        let daemon_args = build_daemon_args(&args, &config);
+        assert_eq!(
+            daemon_args,
+            vec![
+                "--watch",
+                "--auto-commit",
+                "--auto-commit-delay",
+                "45",
+                "--auto-commit-ignore",
+                "*.log,target/",
+                "--provider",
+                "ollama",
+                "--emoji",
+                "--ticket-pattern",
+                r"ENG-\d{2,5}",
+                "--ticket-pattern",
+                r"[A-Z]+-\d+"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_state_serialization() {
+        let state = DaemonState {
+            pid: 4242,
+            repo_path: PathBuf::from("/tmp/repo"),
+            started_at: "2024-01-01 10:00:00".to_string(),
+            quiet_period_secs: 30,
+            ignore_patterns: vec!["*.tmp".to_string()],
+            log_file: PathBuf::from("/tmp/daemon.log"),
+        };
+
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: DaemonState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state, restored);
+
+        let status = format_status(&state);
+        assert!(status.contains("4242"));
+        assert!(status.contains("*.tmp"));
+    }
+
+    #[test]
+    fn test_is_process_alive() {
+        assert!(is_process_alive(std::process::id()));
+        assert!(!is_process_alive(u32::MAX - 1));
+    }
+}
@@ -199,6 +199,7 @@ async fn generate_message_with_agent(diff: &str, config: &Config) -> anyhow::Res
         env_vars.insert("API_KEY".to_string(), api_key);
     }
     env_vars.insert("API_URL".to_string(), config.get_url());
+    config.insert_conventions(&mut env_vars);
 
     let agent_config = AgentConfig {
         provider: config.provider.clone(),
@@ -147,7 +147,7 @@ mod tests {
 
     fn parse_interactive_command(input: &str) -> InteractiveCommand {
         let parts: Vec<&str> = input.split_whitespace().collect();
-        match parts.get(0) {
+        match parts.first() {
             Some(&"h") | Some(&"help") => InteractiveCommand::Help,
             Some(&"q") | Some(&"quit") => InteractiveCommand::Quit,
             Some(&"s") if parts.len() > 1 => InteractiveCommand::Search(parts[1..].join(" ")),
@@ -123,12 +123,13 @@ mod tests {
     #[test]
     fn test_enhanced_command_priority() {
         // 测试命令优先级逻辑
-        let mut args = Args::default();
-
         // 查询命令应该有最高优先级
-        args.query = Some("test".to_string());
-        args.diff_view = Some("HEAD".to_string());
-        args.watch = true;
+        let args = Args {
+            query: Some("test".to_string()),
+            diff_view: Some("HEAD".to_string()),
+            watch: true,
+            ..Default::default()
+        };
 
         // 在实际函数中，查询命令会先被处理
         assert!(args.query.is_some());
@@ -141,43 +142,59 @@ mod tests {
         // 测试所有增强命令的检测
 
         // 查询命令
-        let mut args = Args::default();
-        args.query = Some("test".to_string());
+        let args = Args {
+            query: Some("test".to_string()),
+            ..Default::default()
+        };
         assert!(has_enhanced_commands(&args));
 
         // 差异查看命令
-        let mut args = Args::default();
-        args.diff_view = Some("HEAD".to_string());
+        let args = Args {
+            diff_view: Some("HEAD".to_string()),
+            ..Default::default()
+        };
         assert!(has_enhanced_commands(&args));
 
         // 监控命令
-        let mut args = Args::default();
-        args.watch = true;
+        let args = Args {
+            watch: true,
+            ..Default::default()
+        };
         assert!(has_enhanced_commands(&args));
 
         // 统计命令
-        let mut args = Args::default();
-        args.log_stats = true;
+        let args = Args {
+            log_stats: true,
+            ..Default::default()
+        };
         assert!(has_enhanced_commands(&args));
 
         // 贡献者命令
-        let mut args = Args::default();
-        args.log_contributors = true;
+        let args = Args {
+            log_contributors: true,
+            ..Default::default()
+        };
         assert!(has_enhanced_commands(&args));
 
         // 搜索命令
-        let mut args = Args::default();
-        args.log_search = Some("fix".to_string());
+        let args = Args {
+            log_search: Some("fix".to_string()),
+            ..Default::default()
+        };
         assert!(has_enhanced_commands(&args));
 
         // 分支图命令
-        let mut args = Args::default();
-        args.log_branches = true;
+        let args = Args {
+            log_branches: true,
+            ..Default::default()
+        };
         assert!(has_enhanced_commands(&args));
 
         // 交互式历史命令
-        let mut args = Args::default();
-        args.interactive_history = true;
+        let args = Args {
+            interactive_history: true,
+            ..Default::default()
+        };
         assert!(has_enhanced_commands(&args));
     }
 }
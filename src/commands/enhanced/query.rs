@@ -5,7 +5,7 @@ use crate::query_history::QueryHistory;
 /// 处理查询命令
 pub async fn handle_query_command(query: &str, config: &Config) -> anyhow::Result<()> {
     // 初始化查询历史
-    let mut history = QueryHistory::new(1000)?;
+    let mut history = QueryHistory::new(1000).await?;
 
     if config.debug {
         println!("Executing query: {}", query);
@@ -24,11 +24,29 @@ pub async fn handle_query_command(query: &str, config: &Config) -> anyhow::Resul
     }
 
     if query == "history-clear" {
-        history.clear()?;
+        history.clear().await?;
         println!("Query history cleared.");
         return Ok(());
     }
 
+    if let Some(pattern) = query.strip_prefix("history-search:") {
+        let matches = history.search(pattern);
+        if matches.is_empty() {
+            println!("No queries in history match '{}'.", pattern);
+        }
+        for entry in matches.iter().rev() {
+            let status_icon = if entry.success { "✅" } else { "❌" };
+            println!(
+                "{} {} ×{} {}",
+                status_icon,
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                entry.run_count,
+                entry.query
+            );
+        }
+        return Ok(());
+    }
+
     if query == "history-browse" || query == "--browse" {
         if let Some(selected_query) = history.interactive_browse()? {
             println!("Executing selected query: {}", selected_query);
@@ -39,18 +57,22 @@ pub async fn handle_query_command(query: &str, config: &Config) -> anyhow::Resul
 
             if results.trim().is_empty() {
                 println!("No results found for query: {}", selected_query);
-                history.add_entry(selected_query, Some("execute".to_string()), Some(0), true)?;
+                history
+                    .add_entry(selected_query, Some("execute".to_string()), Some(0), true)
+                    .await?;
             } else {
                 println!("🔍 Query Results: {}", selected_query);
                 println!("{}", "─".repeat(60));
                 println!("{}", results);
 
-                history.add_entry(
-                    selected_query,
-                    Some("execute".to_string()),
-                    Some(result_count),
-                    true,
-                )?;
+                history
+                    .add_entry(
+                        selected_query,
+                        Some("execute".to_string()),
+                        Some(result_count),
+                        true,
+                    )
+                    .await?;
             }
         }
         return Ok(());
@@ -62,6 +84,7 @@ pub async fn handle_query_command(query: &str, config: &Config) -> anyhow::Resul
         println!("  history, --history      Show query history");
         println!("  history-stats, --stats  Show history statistics");
         println!("  history-clear           Clear query history");
+        println!("  history-search:PATTERN  Search query history");
         println!("  history-browse, --browse Interactive history browser");
         return Ok(());
     }
@@ -80,7 +103,9 @@ pub async fn handle_query_command(query: &str, config: &Config) -> anyhow::Resul
             GitQuery::save_query(name, query_content).await?;
 
             // 记录到历史
-            history.add_entry(query.to_string(), Some("save".to_string()), None, true)?;
+            history
+                .add_entry(query.to_string(), Some("save".to_string()), None, true)
+                .await?;
 
             return Ok(());
         }
@@ -92,7 +117,9 @@ pub async fn handle_query_command(query: &str, config: &Config) -> anyhow::Resul
         Err(e) => {
             eprintln!("Failed to parse query: {}", e);
             // 记录失败的查询
-            history.add_entry(query.to_string(), Some("execute".to_string()), None, false)?;
+            history
+                .add_entry(query.to_string(), Some("execute".to_string()), None, false)
+                .await?;
             return Err(e);
         }
     };
@@ -102,7 +129,9 @@ pub async fn handle_query_command(query: &str, config: &Config) -> anyhow::Resul
         Err(e) => {
             eprintln!("Failed to execute query: {}", e);
             // 记录失败的查询
-            history.add_entry(query.to_string(), Some("execute".to_string()), None, false)?;
+            history
+                .add_entry(query.to_string(), Some("execute".to_string()), None, false)
+                .await?;
             return Err(e);
         }
     };
@@ -112,24 +141,28 @@ pub async fn handle_query_command(query: &str, config: &Config) -> anyhow::Resul
     if results.trim().is_empty() {
         println!("No results found for query: {}", query);
         // 记录无结果的查询
-        history.add_entry(
-            query.to_string(),
-            Some("execute".to_string()),
-            Some(0),
-            true,
-        )?;
+        history
+            .add_entry(
+                query.to_string(),
+                Some("execute".to_string()),
+                Some(0),
+                true,
+            )
+            .await?;
     } else {
         println!("🔍 Query Results: {}", query);
         println!("{}", "─".repeat(60));
         println!("{}", results);
 
         // 记录成功的查询
-        history.add_entry(
-            query.to_string(),
-            Some("execute".to_string()),
-            Some(result_count),
-            true,
-        )?;
+        history
+            .add_entry(
+                query.to_string(),
+                Some("execute".to_string()),
+                Some(result_count),
+                true,
+            )
+            .await?;
     }
 
     if config.debug {
@@ -322,10 +355,12 @@ mod tests {
         // 测试查询命令的集成逻辑
 
         // 模拟配置
-        let mut _config = Config::default();
-        _config.provider = "test".to_string();
-        _config.model = "test-model".to_string();
-        _config.debug = false;
+        let mut _config = Config {
+            provider: "test".to_string(),
+            model: "test-model".to_string(),
+            debug: false,
+            ..Default::default()
+        };
 
         // 测试不同的查询类型应该如何处理
         let query_scenarios = vec![
@@ -1,9 +1,27 @@
 use crate::cli::args::Args;
-use crate::config::Config;
-use crate::git::GitWatcher;
+use crate::commands::commit::{
+    apply_branch_ticket, check_sensitive_diff, generate_commit_message_with_agent, CommitContext,
+};
+use crate::commands::storage::{purge_stored_reports, run_scheduled_backup};
+use crate::config::{Config, ConfigReload, ConfigWatcher};
+use crate::core::ai::memory::ProjectMemory;
+use crate::git::{self, GitCore, GitWatcher};
+use regex::Regex;
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+use tokio::time::sleep;
+
+/// 自动提交模式下检查工作区的间隔
+const AUTO_COMMIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 自动提交模式（守护进程）下按保留策略清理审查报告的间隔
+const STORAGE_PURGE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 检查定时备份是否到期的间隔
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
 
 /// 处理监控命令
-pub async fn handle_watch_command(_args: &Args, config: &Config) -> anyhow::Result<()> {
+pub async fn handle_watch_command(args: &Args, config: &Config) -> anyhow::Result<()> {
     if config.debug {
         println!("Starting repository monitoring...");
     }
@@ -21,6 +39,11 @@ pub async fn handle_watch_command(_args: &Args, config: &Config) -> anyhow::Resu
         println!();
     }
 
+    // 自动提交模式
+    if args.auto_commit {
+        return handle_auto_commit_watch(args, config).await;
+    }
+
     // 开始持续监控
     let interval = if config.debug { 2 } else { 5 }; // debug模式更频繁检查
     GitWatcher::start_watching(interval).await?;
@@ -28,8 +51,279 @@ pub async fn handle_watch_command(_args: &Args, config: &Config) -> anyhow::Resu
     Ok(())
 }
 
+/// 监控工作区并在静默期结束后自动提交
+///
+/// 静默期内的连续保存只会刷新计时，最终合并为一次由 AI 描述的提交。
+async fn handle_auto_commit_watch(args: &Args, config: &Config) -> anyhow::Result<()> {
+    // git status 输出的路径相对于仓库根目录，统一切换到根目录执行
+    let repo_root = GitCore::get_repo_root().await?;
+    std::env::set_current_dir(&repo_root)?;
+
+    let quiet_period = Duration::from_secs(args.auto_commit_delay);
+    let ignore_patterns = compile_ignore_patterns(&args.auto_commit_ignore)?;
+
+    println!(
+        "🤖 Auto-commit enabled (quiet period: {}s)",
+        args.auto_commit_delay
+    );
+    if !args.auto_commit_ignore.is_empty() {
+        println!("   Ignoring: {}", args.auto_commit_ignore.join(", "));
+    }
+    println!("Press Ctrl+C to stop watching");
+    println!("{}", "─".repeat(60));
+
+    let mut last_snapshot: Option<String> = None;
+    let mut last_change = Instant::now();
+    let mut config = config.clone();
+    let mut config_watcher = ConfigWatcher::new();
+    let mut last_purge: Option<Instant> = None;
+    let mut last_backup_check: Option<Instant> = None;
+
+    loop {
+        sleep(AUTO_COMMIT_POLL_INTERVAL).await;
+
+        if let Some(reload) = config_watcher.poll() {
+            apply_config_reload(reload, args, &mut config);
+        }
+
+        if last_purge.is_none_or(|at| at.elapsed() >= STORAGE_PURGE_INTERVAL) {
+            last_purge = Some(Instant::now());
+            match purge_stored_reports(&config).await {
+                Ok(Some(summary)) if !summary.removed.is_empty() => {
+                    println!("🧹 {}", summary.format(false));
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("❌ Storage purge failed: {}", e),
+            }
+        }
+
+        if last_backup_check.is_none_or(|at| at.elapsed() >= BACKUP_CHECK_INTERVAL) {
+            last_backup_check = Some(Instant::now());
+            match run_scheduled_backup(&config).await {
+                Ok(Some(summary)) => println!("💾 {}", summary.format()),
+                Ok(None) => {}
+                Err(e) => eprintln!("❌ Storage backup failed: {}", e),
+            }
+        }
+
+        let status_output = match get_porcelain_status().await {
+            Ok(output) => output,
+            Err(e) => {
+                eprintln!("❌ Error checking repository status: {}", e);
+                continue;
+            }
+        };
+
+        let files: Vec<String> = parse_porcelain_paths(&status_output)
+            .into_iter()
+            .filter(|path| !is_ignored(path, &ignore_patterns))
+            .collect();
+
+        if files.is_empty() {
+            last_snapshot = None;
+            continue;
+        }
+
+        // 文件列表或修改时间发生变化，重新开始计时
+        let snapshot = build_snapshot(&status_output, &files);
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            if config.debug {
+                println!("🔄 Changes detected in {} file(s)", files.len());
+            }
+            last_snapshot = Some(snapshot);
+            last_change = Instant::now();
+            continue;
+        }
+
+        if last_change.elapsed() < quiet_period {
+            continue;
+        }
+
+        match auto_commit_files(&files, &config).await {
+            Ok(Some(message)) => {
+                println!(
+                    "✅ Auto-committed {} file(s): {}",
+                    files.len(),
+                    message.lines().next().unwrap_or_default()
+                );
+            }
+            Ok(None) => {
+                if config.debug {
+                    println!("No staged changes after filtering, skipping auto-commit.");
+                }
+            }
+            Err(e) => {
+                eprintln!("❌ Auto-commit failed: {}", e);
+            }
+        }
+
+        // 无论成功与否都等待下一轮变更，避免失败时反复请求 AI
+        last_snapshot = None;
+    }
+}
+
+/// 配置文件变化后重新合并配置，命令行参数仍然优先；新配置无效时保留原来的配置
+fn apply_config_reload(reload: ConfigReload, args: &Args, config: &mut Config) {
+    let reloaded = match reload {
+        ConfigReload::Reloaded(issues) => {
+            for issue in issues {
+                eprintln!("⚠️  {}", issue);
+            }
+            let mut reloaded = Config::new();
+            reloaded.update_from_args(args);
+            reloaded.validate().map(|_| reloaded)
+        }
+        ConfigReload::Invalid(e) => Err(e),
+    };
+    match reloaded {
+        Ok(reloaded) => {
+            println!(
+                "🔄 Configuration reloaded (provider: {}, model: {})",
+                reloaded.provider, reloaded.model
+            );
+            *config = reloaded;
+        }
+        Err(e) => eprintln!(
+            "❌ Invalid configuration, keeping previous settings: {:#}",
+            e
+        ),
+    }
+}
+
+/// 暂存文件并使用 AI 生成提交信息完成提交，没有可提交内容时返回 None
+async fn auto_commit_files(files: &[String], config: &Config) -> anyhow::Result<Option<String>> {
+    git::git_add_paths(files).await?;
+
+    let diff = git::get_git_diff().await?;
+    if diff.trim().is_empty() {
+        return Ok(None);
+    }
+
+    // 无人值守的自动提交不允许跳过敏感信息检查
+    check_sensitive_diff(&diff, false)?;
+
+    let working_dir = std::env::current_dir()?;
+    let mut memory = ProjectMemory::load(&working_dir).unwrap_or_default();
+
+    let context = CommitContext::collect(&diff, config).await;
+    let message = generate_commit_message_with_agent(&diff, config, &memory, &context).await?;
+    if message.trim().is_empty() {
+        anyhow::bail!("AI 生成 commit message 为空，请检查 AI 服务。");
+    }
+
+    let message = if config.emoji {
+        crate::core::gitmoji::add_emoji(&message)
+    } else {
+        message
+    };
+    let message = apply_branch_ticket(&message, config).await?;
+
+    git::git_commit(&message).await?;
+
+    memory.record_commit(&message);
+    let _ = memory.save(&working_dir);
+
+    Ok(Some(message))
+}
+
+/// 获取机器可读的工作区状态（-z 格式，路径不做转义）
+async fn get_porcelain_status() -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain", "-z", "--untracked-files=all"])
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run git status: {}", e))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Git status failed with exit code: {:?}",
+            output.status.code()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 解析 `git status --porcelain -z` 输出，返回所有变更路径（重命名同时包含新旧路径）
+fn parse_porcelain_paths(output: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut entries = output.split('\0').filter(|entry| !entry.is_empty());
+
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+
+        let index_status = entry.chars().next().unwrap_or(' ');
+        paths.push(entry[3..].to_string());
+
+        // 重命名和复制条目后紧跟原始路径
+        if matches!(index_status, 'R' | 'C') {
+            if let Some(original) = entries.next() {
+                paths.push(original.to_string());
+            }
+        }
+    }
+
+    paths
+}
+
+/// 由状态输出和文件修改时间组成快照，用于判断静默期内是否仍有保存
+fn build_snapshot(status_output: &str, files: &[String]) -> String {
+    let mut snapshot = status_output.to_string();
+    for file in files {
+        let modified = std::fs::metadata(file)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        snapshot.push_str(&format!("\n{}:{}", file, modified));
+    }
+    snapshot
+}
+
+/// 将忽略模式编译为正则（`*`、`?` 不跨目录，`**` 可跨目录，结尾的 `/` 表示目录）
+fn compile_ignore_patterns(patterns: &[String]) -> anyhow::Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| pattern.trim().trim_end_matches('/'))
+        .filter(|pattern| !pattern.is_empty())
+        .map(|pattern| {
+            let mut regex = String::from("^");
+            let mut chars = pattern.chars().peekable();
+            while let Some(c) = chars.next() {
+                match c {
+                    '*' if chars.peek() == Some(&'*') => {
+                        chars.next();
+                        regex.push_str(".*");
+                    }
+                    '*' => regex.push_str("[^/]*"),
+                    '?' => regex.push_str("[^/]"),
+                    _ => regex.push_str(&regex::escape(&c.to_string())),
+                }
+            }
+            regex.push('$');
+            Regex::new(&regex)
+                .map_err(|e| anyhow::anyhow!("Invalid ignore pattern '{}': {}", pattern, e))
+        })
+        .collect()
+}
+
+/// 判断路径是否被忽略：模式可匹配任意一级目录/文件名，或匹配从根开始的路径前缀
+fn is_ignored(path: &str, patterns: &[Regex]) -> bool {
+    let components: Vec<&str> = path.split('/').collect();
+
+    patterns.iter().any(|pattern| {
+        (0..components.len()).any(|i| {
+            pattern.is_match(components[i]) || pattern.is_match(&components[..=i].join("/"))
+        })
+    })
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
 
     #[test]
     fn test_watch_interval() {
@@ -48,4 +342,57 @@ mod tests {
             5
         }
     }
+
+    #[test]
+    fn test_parse_porcelain_paths() {
+        let output = " M src/main.rs\0?? notes.txt\0R  src/new.rs\0src/old.rs\0D  removed.rs\0";
+        let paths = parse_porcelain_paths(output);
+
+        assert_eq!(
+            paths,
+            vec![
+                "src/main.rs",
+                "notes.txt",
+                "src/new.rs",
+                "src/old.rs",
+                "removed.rs"
+            ]
+        );
+        assert!(parse_porcelain_paths("").is_empty());
+    }
+
+    #[test]
+    fn test_ignore_patterns() {
+        let patterns = compile_ignore_patterns(&[
+            "*.log".to_string(),
+            "target/".to_string(),
+            "docs/**/*.tmp".to_string(),
+        ])
+        .unwrap();
+
+        assert!(is_ignored("debug.log", &patterns));
+        assert!(is_ignored("logs/app.log", &patterns));
+        assert!(is_ignored("target/debug/ai-commit", &patterns));
+        assert!(is_ignored("docs/a/b/c.tmp", &patterns));
+        assert!(!is_ignored("src/main.rs", &patterns));
+        assert!(!is_ignored("src/target.rs", &patterns));
+        assert!(!is_ignored("other/c.tmp", &patterns));
+    }
+
+    #[test]
+    fn test_ignore_patterns_empty() {
+        let patterns = compile_ignore_patterns(&["".to_string(), " / ".to_string()]).unwrap();
+        assert!(patterns.is_empty());
+        assert!(!is_ignored("src/main.rs", &patterns));
+    }
+
+    #[test]
+    fn test_build_snapshot_changes_with_status() {
+        let files = vec!["does-not-exist.txt".to_string()];
+        let first = build_snapshot("?? does-not-exist.txt\0", &files);
+        let second = build_snapshot(" M does-not-exist.txt\0", &files);
+
+        assert_ne!(first, second);
+        assert_eq!(first, build_snapshot("?? does-not-exist.txt\0", &files));
+    }
 }
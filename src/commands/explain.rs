@@ -0,0 +1,71 @@
+use crate::commands::commit::build_agent_context;
+use crate::config::Config;
+use crate::core::ai::agents::{AgentManager, AgentTask, TaskType};
+use crate::git;
+use std::time::Instant;
+
+/// 处理 explain 命令：用 AI 解释提交、提交范围或已暂存的变更（只读，不修改仓库）
+pub async fn handle_explain_command(target: &str, config: &Config) -> anyhow::Result<()> {
+    let target = target.trim();
+    let diff = if target.is_empty() {
+        git::get_git_diff().await?
+    } else {
+        git::get_revision_diff(target).await?
+    };
+
+    if diff.trim().is_empty() {
+        if target.is_empty() {
+            println!("No staged changes to explain. 请先 git add 或指定提交/范围。");
+        } else {
+            println!("No changes found for '{}'.", target);
+        }
+        return Ok(());
+    }
+
+    let start_time = Instant::now();
+    let explanation = explain_with_agent(&diff, config).await?;
+
+    if config.debug {
+        println!("AI 解释变更耗时: {:.2?}", start_time.elapsed());
+    }
+
+    let subject = if target.is_empty() {
+        "staged changes"
+    } else {
+        target
+    };
+    println!("🔍 Explaining {}:", subject);
+    println!("{}", "─".repeat(60));
+    println!("{}", explanation.trim());
+
+    Ok(())
+}
+
+/// 使用 Review Agent 生成变更解释
+async fn explain_with_agent(diff: &str, config: &Config) -> anyhow::Result<String> {
+    let mut agent_manager = AgentManager::with_default_context();
+    agent_manager.update_context(build_agent_context(config)?);
+
+    let review_agent = agent_manager.get_or_create_agent("review").await?;
+    let task = AgentTask::new(TaskType::ExplainChanges, diff);
+    let result = review_agent.execute(task, agent_manager.context()).await?;
+
+    if !result.success {
+        anyhow::bail!("Agent failed to explain changes");
+    }
+
+    Ok(result.content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_handle_explain_invalid_revision() {
+        let config = Config::new();
+
+        let result = handle_explain_command("definitely-not-a-revision", &config).await;
+        assert!(result.is_err());
+    }
+}
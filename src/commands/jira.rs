@@ -0,0 +1,161 @@
+use crate::commands::commit::detect_branch_ticket;
+use crate::config::Config;
+use crate::git::GitCore;
+use crate::integrations::commit_web_url;
+use crate::integrations::jira::{format_commit_comment, JiraClient};
+use crate::report::{CodeReviewReport, Severity};
+
+/// 评论中最多列出的审查问题数
+const MAX_COMMENT_FINDINGS: usize = 10;
+
+/// 获取当前分支关联工单的提示词上下文，未配置 Jira 或获取失败时返回 None
+pub(crate) async fn ticket_prompt_context(config: &Config) -> Option<String> {
+    let client = JiraClient::from_env()?;
+    let key = detect_branch_ticket(config).await.ok().flatten()?;
+
+    match client.fetch_issue(&key).await {
+        Ok(issue) => Some(issue.to_prompt_context()),
+        Err(e) => {
+            if config.debug {
+                eprintln!("获取 Jira 工单 {} 失败: {}", key, e);
+            }
+            None
+        }
+    }
+}
+
+/// 在当前分支关联的工单下评论最新提交
+pub async fn post_commit_comment(message: &str, config: &Config) -> anyhow::Result<()> {
+    let (client, key) = resolve_ticket(config).await?;
+
+    let hash = GitCore::get_latest_commit_hash().await?;
+    let branch = GitCore::get_current_branch().await.unwrap_or_default();
+    let commit_url = match GitCore::get_remote_url("origin").await {
+        Ok(remote) => commit_web_url(&remote, &hash),
+        Err(_) => None,
+    };
+
+    let comment = format_commit_comment(message, &hash, &branch, commit_url.as_deref());
+    client.add_comment(&key, &comment).await?;
+
+    println!("✓ Commented on {}: {}", key, client.issue_url(&key));
+    Ok(())
+}
+
+/// 在当前分支关联的工单下评论代码审查摘要
+pub async fn post_review_comment(report: &CodeReviewReport, config: &Config) -> anyhow::Result<()> {
+    let (client, key) = resolve_ticket(config).await?;
+
+    client
+        .add_comment(&key, &format_review_comment(report))
+        .await?;
+
+    println!(
+        "✓ Posted review summary to {}: {}",
+        key,
+        client.issue_url(&key)
+    );
+    Ok(())
+}
+
+async fn resolve_ticket(config: &Config) -> anyhow::Result<(JiraClient, String)> {
+    let client = JiraClient::from_env().ok_or_else(|| {
+        anyhow::anyhow!("Jira is not configured. Set AI_COMMIT_JIRA_URL and AI_COMMIT_JIRA_TOKEN")
+    })?;
+    let key = detect_branch_ticket(config).await?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "No ticket id found in the current branch name (configure --ticket-pattern or AI_COMMIT_TICKET_PATTERNS)"
+        )
+    })?;
+    Ok((client, key))
+}
+
+/// 生成代码审查摘要评论
+fn format_review_comment(report: &CodeReviewReport) -> String {
+    let mut comment = format!(
+        "Code review for {}: {} file(s), +{} / -{}, {} finding(s)",
+        report.target,
+        report.summary.total_files,
+        report.summary.total_additions,
+        report.summary.total_deletions,
+        report.findings.len()
+    );
+
+    let counts: Vec<String> = [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+    ]
+    .into_iter()
+    .map(|severity| (severity, report.count_by_severity(severity)))
+    .filter(|(_, count)| *count > 0)
+    .map(|(severity, count)| format!("{} {}", count, severity.as_str()))
+    .collect();
+    if !counts.is_empty() {
+        comment.push_str(&format!(" ({})", counts.join(", ")));
+    }
+
+    let mut findings: Vec<_> = report.findings.iter().collect();
+    findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+    for finding in findings.iter().take(MAX_COMMENT_FINDINGS) {
+        comment.push_str(&format!(
+            "\n* [{}] {}",
+            finding.severity.as_str(),
+            finding.message
+        ));
+    }
+    if findings.len() > MAX_COMMENT_FINDINGS {
+        comment.push_str(&format!(
+            "\n* ... and {} more",
+            findings.len() - MAX_COMMENT_FINDINGS
+        ));
+    }
+
+    comment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ReviewFinding;
+
+    #[test]
+    fn test_format_review_comment() {
+        let report = CodeReviewReport {
+            target: "main..HEAD".to_string(),
+            findings: vec![
+                ReviewFinding {
+                    severity: Severity::Low,
+                    category: "maintainability".to_string(),
+                    message: "补充单元测试".to_string(),
+                    file: None,
+                    line: None,
+                },
+                ReviewFinding {
+                    severity: Severity::Critical,
+                    category: "bug".to_string(),
+                    message: "unwrap 可能导致崩溃".to_string(),
+                    file: None,
+                    line: None,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let comment = format_review_comment(&report);
+        assert!(comment.starts_with("Code review for main..HEAD"));
+        assert!(comment.contains("(1 critical, 1 low)"));
+        // 严重问题排在前面
+        let critical = comment.find("unwrap").unwrap();
+        let low = comment.find("补充单元测试").unwrap();
+        assert!(critical < low);
+    }
+
+    #[tokio::test]
+    async fn test_post_commit_comment_requires_config() {
+        std::env::remove_var("AI_COMMIT_JIRA_URL");
+        let config = Config::new();
+        assert!(post_commit_comment("feat: x", &config).await.is_err());
+    }
+}
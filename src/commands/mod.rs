@@ -1,18 +1,36 @@
+pub mod analyze;
 pub mod commit;
+pub mod config;
+pub mod daemon;
 pub mod edit;
 pub mod enhanced;
+pub mod explain;
 pub mod flow;
 pub mod history;
+pub mod jira;
+pub mod pr;
+pub mod review;
+pub mod signature;
+pub mod storage;
 pub mod tag;
 
+pub use analyze::*;
 pub use commit::*;
+pub use config::*;
+pub use daemon::*;
 pub use edit::*;
 pub use enhanced::*;
+pub use explain::*;
 pub use flow::*;
 pub use history::*;
+pub use jira::*;
+pub use pr::*;
+pub use review::*;
+pub use signature::*;
+pub use storage::*;
 pub use tag::*;
 
-use crate::cli::args::Args;
+use crate::cli::args::{Args, Command};
 use crate::config::Config;
 
 /// 命令路由器，根据参数决定执行哪个命令
@@ -60,6 +78,25 @@ pub async fn route_command(args: &Args, config: &Config) -> anyhow::Result<bool>
         return Ok(true);
     }
 
+    // AI 解释变更（只读）
+    if let Some(target) = &args.explain {
+        return handle_explain_command(target, config).await.map(|_| true);
+    }
+
+    // 提交签名校验
+    if let Some(range) = &args.verify_signatures {
+        return handle_verify_signatures_command(range, config)
+            .await
+            .map(|_| true);
+    }
+
+    // 后台自动提交守护进程
+    if let Some(Command::Daemon { action }) = &args.command {
+        return handle_daemon_commands(action, args, config)
+            .await
+            .map(|_| true);
+    }
+
     // 增强功能命令（最高优先级，基于GRV功能）
     if has_enhanced_commands(args) {
         return handle_enhanced_commands(args, config).await.map(|_| true);
@@ -128,17 +165,20 @@ mod tests {
     use crate::config::Config;
 
     fn create_test_config() -> Config {
-        let mut config = Config::default();
-        config.provider = "test".to_string();
-        config.model = "test-model".to_string();
-        config.debug = false;
-        config
+        Config {
+            provider: "test".to_string(),
+            model: "test-model".to_string(),
+            debug: false,
+            ..Default::default()
+        }
     }
 
     #[tokio::test]
     async fn test_route_command_tag_list() {
-        let mut args = Args::default();
-        args.tag_list = true;
+        let args = Args {
+            tag_list: true,
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -157,8 +197,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_tag_delete() {
-        let mut args = Args::default();
-        args.tag_delete = Some("v1.0.0".to_string());
+        let args = Args {
+            tag_delete: Some("v1.0.0".to_string()),
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -175,8 +217,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_flow_init() {
-        let mut args = Args::default();
-        args.flow_init = true;
+        let args = Args {
+            flow_init: true,
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -193,8 +237,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_flow_feature() {
-        let mut args = Args::default();
-        args.flow_feature_start = Some("test-feature".to_string());
+        let args = Args {
+            flow_feature_start: Some("test-feature".to_string()),
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -211,8 +257,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_history() {
-        let mut args = Args::default();
-        args.history = true;
+        let args = Args {
+            history: true,
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -229,8 +277,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_log_author() {
-        let mut args = Args::default();
-        args.log_author = Some("test-author".to_string());
+        let args = Args {
+            log_author: Some("test-author".to_string()),
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -247,8 +297,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_edit_amend() {
-        let mut args = Args::default();
-        args.amend = true;
+        let args = Args {
+            amend: true,
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -265,8 +317,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_edit_commit() {
-        let mut args = Args::default();
-        args.edit_commit = Some("abc1234".to_string());
+        let args = Args {
+            edit_commit: Some("abc1234".to_string()),
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -304,10 +358,12 @@ mod tests {
     #[test]
     fn test_command_priority_tag() {
         // 测试多个命令标志同时存在时的优先级
-        let mut args = Args::default();
-        args.tag_list = true;
-        args.history = true;
-        args.amend = true;
+        let args = Args {
+            tag_list: true,
+            history: true,
+            amend: true,
+            ..Default::default()
+        };
 
         // Tag 命令应该有最高优先级（在 route_command 中首先检查）
         // 这里我们只能测试参数设置，实际优先级需要在集成测试中验证
@@ -321,26 +377,34 @@ mod tests {
         // 测试命令检测逻辑
 
         // Tag commands
-        let mut args = Args::default();
-        args.tag_info = Some("v1.0.0".to_string());
+        let args = Args {
+            tag_info: Some("v1.0.0".to_string()),
+            ..Default::default()
+        };
         assert!(args.tag_info.is_some(), "Tag info should be detected");
 
         // Flow commands
-        let mut args = Args::default();
-        args.flow_hotfix_finish = Some("hotfix".to_string());
+        let args = Args {
+            flow_hotfix_finish: Some("hotfix".to_string()),
+            ..Default::default()
+        };
         assert!(
             args.flow_hotfix_finish.is_some(),
             "Flow hotfix finish should be detected"
         );
 
         // History commands
-        let mut args = Args::default();
-        args.log_graph = true;
+        let args = Args {
+            log_graph: true,
+            ..Default::default()
+        };
         assert!(args.log_graph, "Log graph should be detected");
 
         // Edit commands
-        let mut args = Args::default();
-        args.undo_commit = true;
+        let args = Args {
+            undo_commit: true,
+            ..Default::default()
+        };
         assert!(args.undo_commit, "Undo commit should be detected");
     }
 
@@ -349,48 +413,60 @@ mod tests {
         // 测试参数组合的有效性
 
         // 有效的 tag 组合
-        let mut args = Args::default();
-        args.tag_compare = Some("v1.0.0,v1.0.1".to_string());
+        let args = Args {
+            tag_compare: Some("v1.0.0,v1.0.1".to_string()),
+            ..Default::default()
+        };
         assert!(
             args.tag_compare.is_some(),
             "Tag compare should accept valid format"
         );
 
         // 有效的 flow 组合
-        let mut args = Args::default();
-        args.flow_release_start = Some("v1.1.0".to_string());
+        let args = Args {
+            flow_release_start: Some("v1.1.0".to_string()),
+            ..Default::default()
+        };
         assert!(
             args.flow_release_start.is_some(),
             "Flow release start should accept version"
         );
 
         // 有效的 history 组合
-        let mut args = Args::default();
-        args.log_since = Some("2024-01-01".to_string());
-        args.log_until = Some("2024-12-31".to_string());
+        let args = Args {
+            log_since: Some("2024-01-01".to_string()),
+            log_until: Some("2024-12-31".to_string()),
+            ..Default::default()
+        };
         assert!(
             args.log_since.is_some() && args.log_until.is_some(),
             "Date range should be valid"
         );
 
         // 有效的 edit 组合
-        let mut args = Args::default();
-        args.reword_commit = Some("abc1234,New message".to_string());
+        let args = Args {
+            reword_commit: Some("abc1234,New message".to_string()),
+            ..Default::default()
+        };
         assert!(
             args.reword_commit.is_some(),
             "Reword commit should accept hash and message"
         );
 
         // 有效的 git init 组合
-        let mut args = Args::default();
-        args.git_init = true;
+        let args = Args {
+            git_init: true,
+            ..Default::default()
+        };
         assert!(args.git_init, "Git init should be set");
     }
 
     #[tokio::test]
     async fn test_route_command_git_init() {
-        let mut args = Args::default();
-        args.git_init = true;
+        let args = Args {
+            git_init: true,
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -413,11 +489,13 @@ mod tests {
     #[test]
     fn test_command_priority_git_init() {
         // 测试 git init 命令的优先级（应该是最高）
-        let mut args = Args::default();
-        args.git_init = true;
-        args.tag_list = true;
-        args.history = true;
-        args.flow_init = true;
+        let args = Args {
+            git_init: true,
+            tag_list: true,
+            history: true,
+            flow_init: true,
+            ..Default::default()
+        };
 
         // Git init 命令应该有最高优先级
         assert!(args.git_init, "Git init should be set");
@@ -428,8 +506,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_hook_install() {
-        let mut args = Args::default();
-        args.hook_install = true;
+        let args = Args {
+            hook_install: true,
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -445,8 +525,10 @@ mod tests {
 
     #[tokio::test]
     async fn test_route_command_hook_uninstall() {
-        let mut args = Args::default();
-        args.hook_uninstall = true;
+        let args = Args {
+            hook_uninstall: true,
+            ..Default::default()
+        };
         let config = create_test_config();
 
         let result = route_command(&args, &config).await;
@@ -462,8 +544,10 @@ mod tests {
 
     #[test]
     fn test_mcp_server_flag_detection() {
-        let mut args = Args::default();
-        args.mcp_server = true;
+        let args = Args {
+            mcp_server: true,
+            ..Default::default()
+        };
         assert!(args.mcp_server, "MCP server flag should be detected");
     }
 }
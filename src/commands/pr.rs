@@ -0,0 +1,234 @@
+use crate::analysis::ownership::{self, AuthorShare, OwnershipReport};
+use crate::analysis::static_analysis::incremental;
+use crate::cli::args::Args;
+use crate::commands::commit::build_agent_context;
+use crate::config::Config;
+use crate::core::ai::agents::{AgentManager, AgentTask, TaskType};
+use crate::git::{self, GitCore};
+use crate::integrations::github::{parse_github_repo, GitHubClient};
+
+/// 生成 PR 描述时 diff 的最大字符数
+const MAX_PR_DIFF_CHARS: usize = 20000;
+
+/// PR 描述中最多推荐的审查人数
+const MAX_SUGGESTED_REVIEWERS: usize = 3;
+
+/// 推送后在 GitHub 上创建 Pull Request，AI 生成的标题不可用时使用 `fallback_title`
+pub async fn create_pull_request(
+    args: &Args,
+    config: &Config,
+    fallback_title: &str,
+) -> anyhow::Result<()> {
+    let client = GitHubClient::from_env()
+        .ok_or_else(|| anyhow::anyhow!("GITHUB_TOKEN is not set, cannot create pull request"))?;
+
+    let remote = GitCore::get_remote_url("origin").await?;
+    let allow_any_host = std::env::var("GITHUB_API_URL").is_ok();
+    let (owner, repo) = parse_github_repo(&remote, allow_any_host)
+        .ok_or_else(|| anyhow::anyhow!("Remote 'origin' is not a GitHub repository: {}", remote))?;
+
+    let head = GitCore::get_current_branch().await?;
+    if head.is_empty() {
+        anyhow::bail!("Cannot create pull request from a detached HEAD");
+    }
+
+    let base = match &args.pr_base {
+        Some(base) => base.clone(),
+        None => client.default_branch(&owner, &repo).await?,
+    };
+    if head == base {
+        anyhow::bail!(
+            "Current branch '{}' is the base branch, switch to a feature branch first",
+            head
+        );
+    }
+
+    // 优先使用相对于远程 base 分支的全部提交，本地没有远程分支时退回最新提交
+    let diff = match git::get_revision_diff(&format!("origin/{}..HEAD", base)).await {
+        Ok(diff) if !diff.trim().is_empty() => diff,
+        _ => git::get_revision_diff("HEAD").await?,
+    };
+
+    println!("📝 Generating pull request description...");
+    let description = generate_pr_description(&diff, config).await?;
+    let (title, mut body) = split_pr_description(&description, fallback_title);
+
+    // 推荐审查人只是附加信息，失败时不影响创建 PR
+    match suggest_reviewers(&diff).await {
+        Ok(reviewers) => body = append_reviewers(&body, &reviewers),
+        Err(e) => eprintln!("⚠️ Failed to suggest reviewers: {}", e),
+    }
+
+    let pr = client
+        .create_pull_request(&owner, &repo, &title, &body, &head, &base)
+        .await?;
+
+    println!("✓ Created pull request #{}: {}", pr.number, pr.html_url);
+    Ok(())
+}
+
+/// 使用 Review Agent 生成 PR 描述
+async fn generate_pr_description(diff: &str, config: &Config) -> anyhow::Result<String> {
+    let diff = if diff.len() > MAX_PR_DIFF_CHARS {
+        let truncated: String = diff.chars().take(MAX_PR_DIFF_CHARS).collect();
+        format!("{}\n... (diff 内容已截断)", truncated)
+    } else {
+        diff.to_string()
+    };
+
+    let mut agent_manager = AgentManager::with_default_context();
+    agent_manager.update_context(build_agent_context(config)?);
+
+    let review_agent = agent_manager.get_or_create_agent("review").await?;
+    let task = AgentTask::new(TaskType::GeneratePrDescription, diff);
+    let result = review_agent.execute(task, agent_manager.context()).await?;
+
+    if !result.success {
+        anyhow::bail!("Agent failed to generate pull request description");
+    }
+
+    Ok(result.content)
+}
+
+/// 根据变更目录的提交历史推荐审查人，排除当前 git 用户
+async fn suggest_reviewers(diff: &str) -> anyhow::Result<Vec<AuthorShare>> {
+    let files: Vec<String> = incremental::changed_files(diff)
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut directories: Vec<String> = files
+        .iter()
+        .map(|file| ownership::directory_of(file, ownership::DEFAULT_DEPTH))
+        .collect();
+    directories.sort();
+    directories.dedup();
+
+    let commits = ownership::load_history(None, &directories).await?;
+    let report = OwnershipReport::build(
+        &commits,
+        ownership::DEFAULT_DEPTH,
+        ownership::DEFAULT_INACTIVE_DAYS,
+        chrono::Utc::now().timestamp(),
+    );
+
+    let exclude: Vec<String> = current_user_email().await.into_iter().collect();
+    Ok(report.suggest_reviewers(&files, &exclude, MAX_SUGGESTED_REVIEWERS))
+}
+
+async fn current_user_email() -> Option<String> {
+    let output = tokio::process::Command::new("git")
+        .args(["config", "user.email"])
+        .output()
+        .await
+        .ok()?;
+    let email = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !email.is_empty()).then_some(email)
+}
+
+/// 在 PR 正文末尾追加推荐审查人
+fn append_reviewers(body: &str, reviewers: &[AuthorShare]) -> String {
+    if reviewers.is_empty() {
+        return body.to_string();
+    }
+    let lines: Vec<String> = reviewers
+        .iter()
+        .map(|r| {
+            format!(
+                "- {} <{}>（相关目录中 {} 次提交，修改 {} 行）",
+                r.name, r.email, r.commits, r.lines
+            )
+        })
+        .collect();
+    format!(
+        "{}\n\n## 建议审查者\n\n{}",
+        body.trim_end(),
+        lines.join("\n")
+    )
+}
+
+/// 将 AI 输出拆分为标题和正文，首行不是标题时使用 `fallback_title`
+fn split_pr_description(description: &str, fallback_title: &str) -> (String, String) {
+    let description = description.trim();
+    let (first_line, rest) = description.split_once('\n').unwrap_or((description, ""));
+
+    let first_line = first_line.trim();
+    if first_line.is_empty() || first_line.starts_with("##") {
+        return (
+            fallback_title
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            description.to_string(),
+        );
+    }
+
+    let title = first_line
+        .trim_start_matches('#')
+        .trim()
+        .trim_start_matches("标题：")
+        .trim_start_matches("Title:")
+        .trim()
+        .trim_matches(['"', '`'])
+        .to_string();
+
+    (title, rest.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_pr_description() {
+        let (title, body) = split_pr_description(
+            "feat(api): 添加用户认证\n\n## 概述\n新增登录接口",
+            "fallback",
+        );
+        assert_eq!(title, "feat(api): 添加用户认证");
+        assert_eq!(body, "## 概述\n新增登录接口");
+
+        let (title, _) = split_pr_description("标题：\"fix: 修复崩溃\"\n\n正文", "fallback");
+        assert_eq!(title, "fix: 修复崩溃");
+    }
+
+    #[test]
+    fn test_split_pr_description_without_title() {
+        let (title, body) =
+            split_pr_description("## 概述\n新增登录接口", "feat: add login\n\nRefs: PROJ-1");
+        assert_eq!(title, "feat: add login");
+        assert_eq!(body, "## 概述\n新增登录接口");
+    }
+
+    #[test]
+    fn test_append_reviewers() {
+        assert_eq!(append_reviewers("## 概述\n", &[]), "## 概述\n");
+
+        let reviewer = AuthorShare {
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+            lines: 120,
+            commits: 4,
+            share: 0.6,
+            active: true,
+        };
+        assert_eq!(
+            append_reviewers("## 概述\n新增登录接口\n", &[reviewer]),
+            "## 概述\n新增登录接口\n\n## 建议审查者\n\n- Alice <alice@example.com>（相关目录中 4 次提交，修改 120 行）"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_pull_request_requires_token() {
+        std::env::remove_var("GITHUB_TOKEN");
+        let args = Args::default();
+        let config = Config::new();
+        assert!(create_pull_request(&args, &config, "feat: x")
+            .await
+            .is_err());
+    }
+}
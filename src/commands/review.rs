@@ -0,0 +1,650 @@
+use crate::analysis::license::{self, LicenseIssue};
+use crate::analysis::migration::{DestructiveStatement, SqlChanges};
+use crate::analysis::static_analysis::external::{compile_tools, run_external_tools};
+use crate::analysis::static_analysis::rules::{self, RuleMatch};
+use crate::analysis::static_analysis::tools::audit_changed_dependencies;
+use crate::analysis::terraform::{TerraformChanges, TerraformIssue};
+use crate::cli::args::{Args, ReportCommand, ReviewCommand};
+use crate::commands::commit::build_agent_context;
+use crate::config::Config;
+use crate::core::ai::agents::{AgentManager, AgentTask, TaskType};
+use crate::core::ai::diff_analyzer::DiffAnalysis;
+use crate::git;
+use crate::languages::typescript::{self, TypeScriptIssue};
+use crate::languages::LanguageSummary;
+use crate::report::diff::ReportDiff;
+use crate::report::publish;
+use crate::report::trend::ReviewTrend;
+use crate::report::{
+    formatter_for, formatter_with, AnalysisReport, CodeReviewReport, ReportConfig, ReportFormat,
+    ReportTable, ReviewFinding,
+};
+use crate::storage::{archive, StorageManager, StoredReport};
+use std::path::Path;
+use std::time::Instant;
+
+/// 处理 review 命令：分析 diff、调用 AI 审查并输出代码审查报告
+pub async fn handle_review_command(
+    target: &str,
+    args: &Args,
+    config: &Config,
+) -> anyhow::Result<()> {
+    // 先校验格式，避免 AI 调用完成后才报错
+    let format: ReportFormat = args.report_format.parse()?;
+
+    let target = target.trim();
+    let diff = if target.is_empty() {
+        git::get_git_diff().await?
+    } else {
+        git::get_combined_diff(target).await?
+    };
+
+    if diff.trim().is_empty() {
+        if target.is_empty() {
+            println!("No staged changes to review. 请先 git add 或指定提交/范围。");
+        } else {
+            println!("No changes found for '{}'.", target);
+        }
+        return Ok(());
+    }
+
+    if config.debug {
+        if let Some(backends) = LanguageSummary::from_diff(&diff).describe_backends() {
+            println!("代码结构解析: {}", backends);
+        }
+    }
+
+    let start_time = Instant::now();
+    let report = build_review_report(target, &diff, config).await?;
+
+    if config.debug {
+        println!("AI 代码审查耗时: {:.2?}", start_time.elapsed());
+    }
+
+    // 审查单个提交时按 SHA 保存报告，之后可用 `review show` 查看
+    let mut commit = None;
+    if !target.is_empty() && !target.contains("..") {
+        if let Ok(Some(sha)) = git::GitCore::resolve_commit(target).await {
+            store_review_report(&sha, &report, config).await;
+            commit = Some(sha);
+        }
+    }
+
+    let content = write_report(
+        &report,
+        format,
+        &config.report,
+        args.report_output.as_deref(),
+    )?;
+    publish_report(args, format, &content, "review", commit.as_deref()).await?;
+
+    if args.jira_comment {
+        if let Err(e) = crate::commands::jira::post_review_comment(&report, config).await {
+            eprintln!("⚠️  Failed to comment on Jira ticket: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// 处理 `review` 子命令
+pub async fn handle_review_subcommand(
+    action: &ReviewCommand,
+    args: &Args,
+    config: &Config,
+) -> anyhow::Result<()> {
+    match action {
+        ReviewCommand::Run { commit, range, .. } => {
+            if let Some(range) = range {
+                if !range.contains("..") {
+                    anyhow::bail!("Expected a commit range like main..HEAD, got '{}'", range);
+                }
+            }
+            // 运行审查需要调用 AI，要求 provider 配置可用
+            config.validate()?;
+            let target = range.as_deref().or(commit.as_deref()).unwrap_or("");
+            handle_review_command(target, args, config).await
+        }
+        ReviewCommand::Show { commit } => {
+            let format: ReportFormat = args.report_format.parse()?;
+            let storage = StorageManager::from_config(config)?;
+            let stored = load_stored_report(&storage, commit).await?;
+            if config.debug {
+                println!(
+                    "Review report for {} stored at {}",
+                    stored.commit, stored.stored_at
+                );
+            }
+            let content = write_report(
+                &stored.report,
+                format,
+                &config.report,
+                args.report_output.as_deref(),
+            )?;
+            publish_report(args, format, &content, "review", Some(&stored.commit)).await
+        }
+        ReviewCommand::Trend { since, top } => {
+            let format: ReportFormat = args.report_format.parse()?;
+            let reports = StorageManager::from_config(config)?
+                .load_reports(since.as_deref())
+                .await?;
+            if reports.is_empty() {
+                println!("No stored review reports to summarize.");
+                return Ok(());
+            }
+            let trend = review_trend_report(&ReviewTrend::build(&reports, *top))?;
+            let content = write_analysis(
+                &trend,
+                format,
+                &config.report,
+                args.report_output.as_deref(),
+            )?;
+            publish_report(args, format, &content, "review-trend", None).await
+        }
+    }
+}
+
+/// 处理 `report` 子命令
+pub async fn handle_report_subcommand(
+    action: &ReportCommand,
+    args: &Args,
+    config: &Config,
+) -> anyhow::Result<()> {
+    match action {
+        ReportCommand::Diff { base, head } => {
+            let format: ReportFormat = args.report_format.parse()?;
+            let storage = StorageManager::from_config(config)?;
+            let before = load_stored_report(&storage, base).await?;
+            let after = load_stored_report(&storage, head).await?;
+            let diff = ReportDiff::compare(&before.report, &after.report);
+            let report = report_diff_report(&before, &after, &diff)?;
+            let content = write_analysis(
+                &report,
+                format,
+                &config.report,
+                args.report_output.as_deref(),
+            )?;
+            publish_report(args, format, &content, "report-diff", Some(&after.commit)).await
+        }
+        ReportCommand::Export {
+            file,
+            since,
+            commits,
+        } => {
+            let storage = StorageManager::from_config(config)?;
+            let reports = if commits.is_empty() {
+                storage.load_reports(since.as_deref()).await?
+            } else {
+                let mut reports = Vec::new();
+                for commit in commits {
+                    let stored = load_stored_report(&storage, commit).await?;
+                    if since
+                        .as_deref()
+                        .is_none_or(|since| stored.stored_at.as_str() >= since)
+                    {
+                        reports.push(stored);
+                    }
+                }
+                reports.sort_by(|a, b| a.stored_at.cmp(&b.stored_at));
+                reports
+            };
+            if reports.is_empty() {
+                anyhow::bail!("No stored review reports match the selection");
+            }
+            let manifest =
+                archive::write_archive(Path::new(file), &reports, storage.backend().name())?;
+            println!(
+                "✓ Exported {} report(s) to {}",
+                manifest.commits.len(),
+                file
+            );
+            Ok(())
+        }
+        ReportCommand::Import { file, overwrite } => {
+            let storage = StorageManager::from_config(config)?;
+            let (manifest, reports) = archive::read_archive(Path::new(file))?;
+            if config.debug {
+                println!(
+                    "Archive exported at {} by ai-commit {} from {} storage",
+                    manifest.exported_at, manifest.tool_version, manifest.source_backend
+                );
+            }
+            let mut imported = 0;
+            for report in &reports {
+                if storage.import_report(report, *overwrite).await? {
+                    imported += 1;
+                }
+            }
+            println!(
+                "✓ Imported {} report(s) from {} ({} already stored, skipped)",
+                imported,
+                file,
+                reports.len() - imported
+            );
+            Ok(())
+        }
+    }
+}
+
+/// 读取提交的审查报告，能在当前仓库解析的引用（HEAD、分支名、短 SHA）先换成完整 SHA
+async fn load_stored_report(
+    storage: &StorageManager,
+    commit: &str,
+) -> anyhow::Result<StoredReport> {
+    let sha = match git::GitCore::resolve_commit(commit).await {
+        Ok(Some(sha)) => sha,
+        _ => commit.to_string(),
+    };
+    storage
+        .load_report(&sha)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("No stored review report for commit {}", commit))
+}
+
+/// 按格式输出汇总报告，指定路径时写入文件
+fn write_analysis(
+    report: &AnalysisReport,
+    format: ReportFormat,
+    settings: &ReportConfig,
+    output: Option<&str>,
+) -> anyhow::Result<String> {
+    let content = formatter_with(format, settings).format_analysis(report)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, &content)
+                .map_err(|e| anyhow::anyhow!("Failed to write report to {}: {}", path, e))?;
+            println!("✓ {} written to {}", report.title, path);
+        }
+        None => println!("{}", content.trim_end()),
+    }
+    Ok(content)
+}
+
+/// 指定 `--report-publish` 时把报告上传到对象存储并输出访问地址
+///
+/// 写入了 `--report-output` 时上传该文件，否则以 `<name>.<扩展名>` 上传报告内容。
+pub(crate) async fn publish_report(
+    args: &Args,
+    format: ReportFormat,
+    content: &str,
+    name: &str,
+    commit: Option<&str>,
+) -> anyhow::Result<()> {
+    let Some(location) = args.report_publish.as_deref() else {
+        return Ok(());
+    };
+    let file_name = format!("{}.{}", name, formatter_for(format).extension());
+    let url = publish::publish(
+        location,
+        content,
+        args.report_output.as_deref().map(Path::new),
+        &file_name,
+        commit,
+    )
+    .await?;
+    println!("✓ Report published to {}", url);
+    Ok(())
+}
+
+/// 两份审查报告的差异转换为报告
+fn report_diff_report(
+    base: &StoredReport,
+    head: &StoredReport,
+    diff: &ReportDiff,
+) -> anyhow::Result<AnalysisReport> {
+    let columns = ["Severity", "Category", "File", "Message"];
+    let row = |finding: &ReviewFinding, severity: String| {
+        let file = match (&finding.file, finding.line) {
+            (Some(file), Some(line)) => format!("{}:{}", file, line),
+            (Some(file), None) => file.clone(),
+            _ => "-".to_string(),
+        };
+        vec![
+            severity,
+            finding.category.clone(),
+            file,
+            finding.message.clone(),
+        ]
+    };
+
+    let mut new = ReportTable::new("New issues", &columns);
+    new.rows = diff
+        .new
+        .iter()
+        .map(|f| row(f, f.severity.as_str().to_string()))
+        .collect();
+    let mut regressed = ReportTable::new("Regressed issues", &columns);
+    regressed.rows = diff
+        .regressed
+        .iter()
+        .map(|r| {
+            let severity = format!("{} → {}", r.before.as_str(), r.finding.severity.as_str());
+            row(&r.finding, severity)
+        })
+        .collect();
+    let mut fixed = ReportTable::new("Fixed issues", &columns);
+    fixed.rows = diff
+        .fixed
+        .iter()
+        .map(|f| row(f, f.severity.as_str().to_string()))
+        .collect();
+
+    let short = |sha: &str| sha[..sha.len().min(8)].to_string();
+    let mut report = AnalysisReport::new("Review Report Diff")
+        .metric(
+            "Base",
+            format!("{} ({})", short(&base.commit), base.stored_at),
+        )
+        .metric(
+            "Head",
+            format!("{} ({})", short(&head.commit), head.stored_at),
+        )
+        .metric(
+            "Score",
+            format!(
+                "{} → {} ({:+})",
+                diff.base_score,
+                diff.head_score,
+                diff.score_delta()
+            ),
+        )
+        .metric("New", diff.new.len())
+        .metric("Fixed", diff.fixed.len())
+        .metric("Regressed", diff.regressed.len())
+        .metric("Unchanged", diff.unchanged)
+        .table(new)
+        .table(regressed)
+        .table(fixed);
+    if !diff.is_improvement() {
+        report.recommendations.push(format!(
+            "{} 相对 {} 新增 {} 个问题、{} 个问题严重程度升高，建议处理后再合并",
+            short(&head.commit),
+            short(&base.commit),
+            diff.new.len(),
+            diff.regressed.len()
+        ));
+    }
+    report.details = Some(serde_json::to_value(diff)?);
+    Ok(report)
+}
+
+/// 审查报告趋势转换为报告，按配置的格式输出
+fn review_trend_report(trend: &ReviewTrend) -> anyhow::Result<AnalysisReport> {
+    let mut reports = ReportTable::new(
+        "Reports",
+        &[
+            "Commit",
+            "Stored at",
+            "Score",
+            "Critical",
+            "High",
+            "Medium",
+            "Low",
+            "Info",
+        ],
+    );
+    reports.rows = trend
+        .points
+        .iter()
+        .map(|point| {
+            vec![
+                point.short_commit().to_string(),
+                point.stored_at.clone(),
+                point.score.to_string(),
+                point.critical.to_string(),
+                point.high.to_string(),
+                point.medium.to_string(),
+                point.low.to_string(),
+                point.info.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut recurring = ReportTable::new(
+        "Recurring issues",
+        &["Reports", "Severity", "Category", "Message", "Last commit"],
+    );
+    recurring.rows = trend
+        .recurring
+        .iter()
+        .map(|issue| {
+            vec![
+                issue.reports.to_string(),
+                issue.severity.as_str().to_string(),
+                issue.category.clone(),
+                issue.message.clone(),
+                issue.last_commit[..issue.last_commit.len().min(8)].to_string(),
+            ]
+        })
+        .collect();
+
+    let mut report = AnalysisReport::new("Review Trend")
+        .metric("Reports", trend.points.len())
+        .metric("Score change", format!("{:+}", trend.score_change()))
+        .metric("Score", trend.score_sparkline())
+        .metric("Findings", trend.findings_sparkline())
+        .table(reports)
+        .table(recurring);
+    report.recommendations = trend.recommendations();
+    report.details = Some(serde_json::to_value(trend)?);
+    Ok(report)
+}
+
+/// 按提交 SHA 保存审查报告，失败时只警告
+pub(crate) async fn store_review_report(commit: &str, report: &CodeReviewReport, config: &Config) {
+    let result = match StorageManager::from_config(config) {
+        Ok(storage) => storage.save_report(commit, report).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) if config.debug => println!(
+            "Review report stored for commit {}",
+            &commit[..8.min(commit.len())]
+        ),
+        Ok(()) => {}
+        Err(e) => eprintln!("⚠️  Failed to store review report: {}", e),
+    }
+}
+
+/// 按格式输出报告，指定路径时写入文件
+fn write_report(
+    report: &CodeReviewReport,
+    format: ReportFormat,
+    settings: &ReportConfig,
+    output: Option<&str>,
+) -> anyhow::Result<String> {
+    let content = formatter_with(format, settings).format(report)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, &content)
+                .map_err(|e| anyhow::anyhow!("Failed to write report to {}: {}", path, e))?;
+            println!(
+                "✓ Review report written to {} ({} finding(s))",
+                path,
+                report.findings.len()
+            );
+        }
+        None => println!("{}", content.trim_end()),
+    }
+    Ok(content)
+}
+
+/// 对 diff 做静态分析并结合 AI 审查意见生成报告
+pub async fn build_review_report(
+    target: &str,
+    diff: &str,
+    config: &Config,
+) -> anyhow::Result<CodeReviewReport> {
+    let analysis = DiffAnalysis::analyze_diff(diff);
+    let ai_review = review_with_agent(diff, config).await?;
+
+    let subject = if target.is_empty() {
+        "staged changes"
+    } else {
+        target
+    };
+    let mut report = CodeReviewReport::new(subject, &analysis, &ai_review);
+
+    // 依赖清单或锁文件有变更时，用本机的安全审计工具检查已知漏洞
+    let changed_files = analysis
+        .file_changes
+        .iter()
+        .map(|change| change.file_path.as_str());
+    report
+        .findings
+        .extend(audit_changed_dependencies(changed_files, config.debug).await);
+
+    // 许可证文件、项目许可证字段和新依赖的许可证兼容性
+    report.findings.extend(
+        license::scan_diff(diff, Path::new("."))
+            .iter()
+            .map(LicenseIssue::to_finding),
+    );
+
+    // 迁移和 SQL 中的 DROP、不带默认值的 NOT NULL 字段等破坏性语句
+    report.findings.extend(
+        SqlChanges::from_diff(diff)
+            .destructive
+            .iter()
+            .map(DestructiveStatement::to_finding),
+    );
+
+    // Terraform 中会销毁资源或去掉删除保护的变更
+    report.findings.extend(
+        TerraformChanges::from_diff(diff)
+            .issues
+            .iter()
+            .map(TerraformIssue::to_finding),
+    );
+
+    // TypeScript / React 新增代码中的 Hook 依赖、列表 key、any 类型和未 await 的 Promise
+    report.findings.extend(
+        typescript::scan_diff(diff)
+            .iter()
+            .map(TypeScriptIssue::to_finding),
+    );
+
+    // .ai-commit.toml 中定义的自定义规则
+    report.findings.extend(
+        rules::scan_diff(diff, &rules::compile_rules(&config.rules))
+            .iter()
+            .map(RuleMatch::to_finding),
+    );
+
+    // .ai-commit.toml 中注册的外部分析工具
+    let changed_files = analysis
+        .file_changes
+        .iter()
+        .map(|change| change.file_path.as_str());
+    report.findings.extend(
+        run_external_tools(&compile_tools(&config.tools), changed_files, config.debug).await,
+    );
+
+    Ok(report)
+}
+
+/// 使用 Review Agent 审查代码变更
+async fn review_with_agent(diff: &str, config: &Config) -> anyhow::Result<String> {
+    let mut agent_manager = AgentManager::with_default_context();
+    agent_manager.update_context(build_agent_context(config)?);
+
+    let review_agent = agent_manager.get_or_create_agent("review").await?;
+    let task = AgentTask::new(TaskType::ReviewCode, diff);
+    let result = review_agent.execute(task, agent_manager.context()).await?;
+
+    if !result.success {
+        anyhow::bail!("Agent failed to review changes");
+    }
+
+    Ok(result.content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    #[tokio::test]
+    async fn test_handle_review_invalid_format() {
+        let args = Args {
+            report_format: "pdf".to_string(),
+            ..Default::default()
+        };
+        let config = Config::new();
+
+        let result = handle_review_command("HEAD", &args, &config).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_report_diff_report() {
+        let finding = |severity: Severity, message: &str| ReviewFinding {
+            severity,
+            category: "bug".to_string(),
+            message: message.to_string(),
+            file: Some("src/main.rs".to_string()),
+            line: Some(3),
+        };
+        let stored = |commit: &str, findings: Vec<ReviewFinding>| StoredReport {
+            commit: commit.to_string(),
+            stored_at: "2024-05-01 10:00:00".to_string(),
+            report: CodeReviewReport {
+                findings,
+                ..Default::default()
+            },
+        };
+        let base = stored(
+            "aaaa1111bbbb",
+            vec![
+                finding(Severity::Low, "函数过长"),
+                finding(Severity::High, "SQL 拼接"),
+            ],
+        );
+        let head = stored(
+            "cccc2222dddd",
+            vec![
+                finding(Severity::Medium, "函数过长"),
+                finding(Severity::Low, "命名"),
+            ],
+        );
+
+        let diff = ReportDiff::compare(&base.report, &head.report);
+        let report = report_diff_report(&base, &head, &diff).unwrap();
+        assert_eq!(report.metrics[2].value, "88 → 93 (+5)");
+        assert_eq!(report.tables[0].rows[0][3], "命名");
+        assert_eq!(report.tables[1].rows[0][0], "low → medium");
+        assert_eq!(report.tables[1].rows[0][2], "src/main.rs:3");
+        assert_eq!(report.tables[2].rows[0][3], "SQL 拼接");
+        assert_eq!(report.recommendations.len(), 1);
+    }
+
+    #[test]
+    fn test_review_trend_report() {
+        let stored = |commit: &str, stored_at: &str, severity: Severity| StoredReport {
+            commit: commit.to_string(),
+            stored_at: stored_at.to_string(),
+            report: CodeReviewReport {
+                findings: vec![ReviewFinding {
+                    severity,
+                    category: "bug".to_string(),
+                    message: "unwrap 可能导致崩溃".to_string(),
+                    file: None,
+                    line: None,
+                }],
+                ..Default::default()
+            },
+        };
+        let reports = vec![
+            stored("aaaa1111bbbb", "2024-05-01 10:00:00", Severity::Low),
+            stored("cccc2222dddd", "2024-05-02 10:00:00", Severity::Critical),
+        ];
+
+        let report = review_trend_report(&ReviewTrend::build(&reports, 5)).unwrap();
+        assert_eq!(report.metrics[1].value, "-18");
+        assert_eq!(report.tables[0].rows.len(), 2);
+        assert_eq!(report.tables[0].rows[1][2], "80");
+        assert_eq!(report.tables[1].rows[0][0], "2");
+        assert_eq!(report.tables[1].rows[0][1], "critical");
+        assert_eq!(report.recommendations.len(), 2);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::config::Config;
+use crate::git::{GitSignature, SignatureStatus};
+use std::path::Path;
+
+/// 处理签名校验命令，存在未签名或签名无效的提交时返回错误（便于在 CI 中使用）
+pub async fn handle_verify_signatures_command(range: &str, config: &Config) -> anyhow::Result<()> {
+    verify_signatures_in(Path::new("."), range, config).await
+}
+
+async fn verify_signatures_in(repo: &Path, range: &str, config: &Config) -> anyhow::Result<()> {
+    let range = if range.trim().is_empty() {
+        None
+    } else {
+        Some(range)
+    };
+
+    if config.debug {
+        println!(
+            "Verifying commit signatures in range: {}",
+            range.unwrap_or("(recent commits)")
+        );
+    }
+
+    let signatures = GitSignature::verify_range_in(repo, range).await?;
+    if signatures.is_empty() {
+        println!("No commits found in the given range.");
+        return Ok(());
+    }
+
+    println!("🔏 Commit Signature Verification:");
+    println!("{}", "─".repeat(60));
+
+    for signature in &signatures {
+        let signer = if signature.signer.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", signature.signer)
+        };
+        println!(
+            "{} {} {} - {}{}",
+            signature.status.symbol(),
+            signature.short_hash,
+            signature.subject,
+            signature.status.description(),
+            signer
+        );
+    }
+
+    let valid = signatures.iter().filter(|s| s.status.is_valid()).count();
+    let unsigned = signatures
+        .iter()
+        .filter(|s| s.status == SignatureStatus::Unsigned)
+        .count();
+    let invalid = signatures.len() - valid - unsigned;
+
+    println!("{}", "─".repeat(60));
+    println!(
+        "Total: {}  ✓ Valid: {}  ✗ Invalid: {}  ✗ Unsigned: {}",
+        signatures.len(),
+        valid,
+        invalid,
+        unsigned
+    );
+
+    if valid < signatures.len() {
+        anyhow::bail!(
+            "{} of {} commits failed signature verification",
+            signatures.len() - valid,
+            signatures.len()
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::git::signature::tests::unsigned_repo;
+
+    #[tokio::test]
+    async fn test_handle_verify_signatures_command() {
+        let repo = unsigned_repo();
+        let err = verify_signatures_in(repo.path(), "HEAD", &Config::default())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "1 of 1 commits failed signature verification"
+        );
+    }
+}
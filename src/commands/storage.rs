@@ -0,0 +1,163 @@
+use crate::cli::args::StorageCommand;
+use crate::config::Config;
+use crate::storage::{
+    backup, BackupManager, BackupSummary, PurgeSummary, RetentionPolicy, StorageManager,
+};
+use std::path::PathBuf;
+
+/// 处理 `storage` 子命令
+pub async fn handle_storage_subcommand(
+    action: &StorageCommand,
+    config: &Config,
+) -> anyhow::Result<()> {
+    match action {
+        StorageCommand::Stats => {
+            let storage = StorageManager::from_config(config)?;
+            println!("{}", storage.stats().await?.format());
+            Ok(())
+        }
+        StorageCommand::Health => {
+            let storage = StorageManager::from_config(config)?;
+            let backend = storage.backend().name();
+            match storage.ping().await {
+                Ok(elapsed) => {
+                    println!(
+                        "✅ Storage backend '{}' is reachable ({} ms)",
+                        backend,
+                        elapsed.as_millis()
+                    );
+                    Ok(())
+                }
+                Err(e) => anyhow::bail!("Storage backend '{}' is unavailable: {}", backend, e),
+            }
+        }
+        StorageCommand::Encrypt => {
+            let encrypted = StorageManager::encrypt_existing(config).await?;
+            println!("✓ Encrypted {} plaintext value(s)", encrypted);
+            Ok(())
+        }
+        StorageCommand::Backup { list } => {
+            let storage = StorageManager::from_config(config)?;
+            let manager = BackupManager::from_config(config, storage.namespace())?;
+            if *list {
+                let backups = manager.list()?;
+                if backups.is_empty() {
+                    println!("No backups in {}", manager.dir().display());
+                }
+                for backup in backups {
+                    let modified: chrono::DateTime<chrono::Local> = backup.modified.into();
+                    println!(
+                        "{}  {:>10} bytes  {}",
+                        modified.format("%Y-%m-%d %H:%M:%S"),
+                        backup.size,
+                        backup.path.display()
+                    );
+                }
+                return Ok(());
+            }
+            println!("✓ {}", manager.create(&storage).await?.format());
+            Ok(())
+        }
+        StorageCommand::Restore { file, overwrite } => {
+            let storage = StorageManager::from_config(config)?;
+            let path = match file {
+                Some(file) => PathBuf::from(file),
+                None => {
+                    let manager = BackupManager::from_config(config, storage.namespace())?;
+                    manager
+                        .latest()?
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("No backups found in {}", manager.dir().display())
+                        })?
+                        .path
+                }
+            };
+            // 恢复前完整校验，损坏的备份不会写入任何报告
+            let (_, reports) = backup::verify(&path)?;
+            let mut restored = 0;
+            for report in &reports {
+                if storage.import_report(report, *overwrite).await? {
+                    restored += 1;
+                }
+            }
+            println!(
+                "✓ Restored {} report(s) from {} ({} already stored, skipped)",
+                restored,
+                path.display(),
+                reports.len() - restored
+            );
+            Ok(())
+        }
+        StorageCommand::Namespaces => {
+            let storage = StorageManager::from_config(config)?;
+            println!("{}", format_namespaces(&storage.list_namespaces().await?));
+            Ok(())
+        }
+        StorageCommand::Purge {
+            dry_run,
+            max_age_days,
+            max_reports,
+            max_size_mb,
+        } => {
+            let configured = RetentionPolicy::from_config(config);
+            // 命令行指定的限制覆盖配置文件
+            let policy = RetentionPolicy {
+                max_age_days: max_age_days.or(configured.max_age_days),
+                max_count: max_reports.or(configured.max_count),
+                max_size_bytes: max_size_mb
+                    .map(|mb| mb.saturating_mul(1024 * 1024))
+                    .or(configured.max_size_bytes),
+            };
+            if policy.is_unlimited() {
+                anyhow::bail!(
+                    "No retention limits configured. Set [storage] max_age_days, max_reports or max_size_mb, or pass --max-age-days/--max-reports/--max-size-mb"
+                );
+            }
+
+            let storage = StorageManager::from_config(config)?.with_retention(policy);
+            let summary = storage.purge(*dry_run).await?;
+            println!("{}", summary.format(*dry_run));
+            Ok(())
+        }
+    }
+}
+
+fn format_namespaces(namespaces: &[(String, usize)]) -> String {
+    if namespaces.is_empty() {
+        return "No namespaced reports stored. Set [storage] namespace to separate projects sharing a backend".to_string();
+    }
+    let width = namespaces
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::from("📁 Namespaces");
+    for (name, reports) in namespaces {
+        out.push_str(&format!(
+            "\n  {:<width$}  {} reports",
+            name,
+            reports,
+            width = width
+        ));
+    }
+    out
+}
+
+/// 配置了定时备份且已到期时备份审查报告，否则不做任何事
+pub async fn run_scheduled_backup(config: &Config) -> anyhow::Result<Option<BackupSummary>> {
+    let storage = StorageManager::from_config(config)?;
+    let manager = BackupManager::from_config(config, storage.namespace())?;
+    if !manager.is_due(std::time::SystemTime::now())? {
+        return Ok(None);
+    }
+    manager.create(&storage).await.map(Some)
+}
+
+/// 按配置的保留策略清理审查报告，没有配置限制时不做任何事
+pub async fn purge_stored_reports(config: &Config) -> anyhow::Result<Option<PurgeSummary>> {
+    let storage = StorageManager::from_config(config)?;
+    if storage.retention().is_unlimited() {
+        return Ok(None);
+    }
+    storage.purge(false).await.map(Some)
+}
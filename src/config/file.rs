@@ -0,0 +1,579 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::providers::CustomProvider;
+use super::schema::{validate, ConfigIssue, ValueKind, CONFIG_SCHEMA, REPO_CONFIG_SCHEMA};
+
+/// 配置文件 `~/.config/ai-commit/config.toml`，所有项都可省略
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    /// 配置格式版本，见 `schema::CONFIG_VERSION`
+    pub version: Option<u32>,
+    pub debug: Option<bool>,
+    /// 允许运行其中 `[[tools]]` 的仓库配置，值为 `.ai-commit.toml` 内容的 SHA-256
+    pub trusted_repo_configs: Vec<String>,
+    pub provider: ProviderSection,
+    pub git: GitSection,
+    pub tui: TuiSection,
+    pub notifications: NotificationsSection,
+    pub storage: StorageSection,
+    pub network: NetworkSection,
+    pub report: ReportSection,
+    /// `[[providers]]`：自定义提供商，与内置提供商合并
+    pub providers: Vec<CustomProvider>,
+    /// `[[tools]]`：外部分析工具，审查时对变更文件运行
+    pub tools: Vec<ToolConfig>,
+}
+
+/// `[provider]`：AI 提供商
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProviderSection {
+    pub name: Option<String>,
+    pub model: Option<String>,
+    pub api_key: Option<String>,
+    pub url: Option<String>,
+    /// 变更聚类使用的 embedding 模型，未设置时使用本地词法向量
+    pub embedding_model: Option<String>,
+}
+
+/// `[git]`：提交行为
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GitSection {
+    /// 不自动执行 git add .
+    pub no_add: Option<bool>,
+    /// 提交后自动推送
+    pub push: Option<bool>,
+    pub emoji: Option<bool>,
+    pub candidates: Option<u8>,
+    pub ticket_patterns: Option<Vec<String>>,
+    pub ticket_position: Option<String>,
+    /// 提交说明使用的语言，如 `English`
+    pub language: Option<String>,
+    /// 追加到提示词中的额外要求
+    pub prompt: Option<String>,
+    /// 允许使用的 scope
+    pub scopes: Option<Vec<String>>,
+}
+
+/// `[tui]`：TUI 的默认设置，TUI 设置面板保存的配置优先
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TuiSection {
+    pub theme: Option<String>,
+    pub refresh_interval: Option<u64>,
+    pub watch_files: Option<bool>,
+}
+
+/// `[notifications]`：TUI 提示的显示时间
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotificationsSection {
+    /// 普通提示显示的秒数
+    pub toast_seconds: Option<u64>,
+    /// 警告显示的秒数
+    pub warning_seconds: Option<u64>,
+}
+
+/// `[storage]`：数据目录和审查报告等结果的存储后端
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageSection {
+    /// 查询历史、项目记忆、TUI 状态等数据的目录，默认 `~/.ai-commit`
+    pub data_dir: Option<PathBuf>,
+    /// 审查报告和分析结果的存储后端：`file`（默认，无需配置）、`redis` 或 `s3`
+    pub backend: Option<String>,
+    /// Redis 地址，如 `redis://:password@cache.internal:6379/0`
+    pub redis_url: Option<String>,
+    /// Redis 键前缀，默认 `ai-commit:`
+    pub redis_namespace: Option<String>,
+    /// Redis 中的结果保存的秒数，0 或未设置表示不过期
+    pub redis_ttl: Option<u64>,
+    /// S3 存储桶，凭证读取 `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`
+    pub s3_bucket: Option<String>,
+    /// 对象键前缀，如 `ai-commit/team-a`
+    pub s3_prefix: Option<String>,
+    /// 区域，默认读取 `AWS_REGION`，都未设置时为 `us-east-1`
+    pub s3_region: Option<String>,
+    /// S3 兼容服务的地址，如 MinIO 的 `http://localhost:9000`
+    pub s3_endpoint: Option<String>,
+    /// 压缩保存的对象
+    pub s3_compress: Option<bool>,
+    /// 服务端加密：`AES256` 或 `aws:kms`
+    pub s3_sse: Option<String>,
+    /// 审查报告保存的天数，超过的在清理时删除
+    pub max_age_days: Option<u64>,
+    /// 最多保留的审查报告数，超过时删除最早的
+    pub max_reports: Option<usize>,
+    /// 审查报告占用的总空间上限（MB），超过时删除最早的
+    pub max_size_mb: Option<u64>,
+    /// 保存前用 AES-256-GCM 加密，读取时解密，适用于所有后端
+    pub encryption_enabled: Option<bool>,
+    /// 加密密钥（64 位十六进制），未设置时使用系统钥匙串中的密钥
+    pub encryption_key: Option<String>,
+    /// 项目命名空间，多个仓库共用后端时隔离审查报告；`auto` 从 `origin` 远程地址推导
+    pub namespace: Option<String>,
+    /// Redis、S3 操作失败时的重试次数（指数退避），默认 2
+    pub retries: Option<u32>,
+    /// Redis、S3 不可用时改用数据目录下的本地存储，恢复后自动切回
+    pub fallback: Option<bool>,
+    /// 审查报告备份的目录，默认数据目录下的 `backups/`
+    pub backup_dir: Option<PathBuf>,
+    /// 守护进程和 TUI 自动备份的间隔（小时），未设置时只手动备份
+    pub backup_interval_hours: Option<u64>,
+    /// 保留的备份数，默认 7
+    pub backup_keep: Option<usize>,
+}
+
+/// `[network]`：访问 AI 提供商、GitHub 和 Jira 的 HTTP 客户端设置，修改后需要重启
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkSection {
+    /// 代理地址，如 `http://proxy.example.com:8080`，未设置时使用 `HTTPS_PROXY` 等环境变量
+    pub proxy: Option<String>,
+    /// 额外信任的 CA 证书（PEM，可包含多个证书）
+    pub ca_cert: Option<PathBuf>,
+    /// 建立连接的超时秒数，0 表示不限制
+    pub connect_timeout: Option<u64>,
+    /// 整个请求（包括读取响应）的超时秒数，0 表示不限制
+    pub timeout: Option<u64>,
+    /// 空闲连接保留的秒数，0 表示不复用连接
+    pub keep_alive: Option<u64>,
+}
+
+/// `[report]`：审查/分析报告的输出设置
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportSection {
+    /// CSV/TSV 输出的列名（不区分大小写），按列出的顺序输出
+    pub columns: Option<Vec<String>>,
+    /// 只输出报告的这些部分：summary、stats、issues、ai-review、suggestions
+    pub sections: Option<Vec<String>>,
+    /// 不输出报告的这些部分
+    pub exclude_sections: Option<Vec<String>>,
+}
+
+impl ConfigFile {
+    /// 配置文件路径，Linux 下为 `~/.config/ai-commit/config.toml`
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("ai-commit").join("config.toml"))
+    }
+
+    /// 读取配置文件，文件不存在时返回空配置；
+    /// 不认识或类型错误的配置项被忽略，并在返回的问题列表中说明
+    pub fn load() -> anyhow::Result<(Self, Vec<ConfigIssue>)> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Ok((Self::default(), Vec::new())),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> anyhow::Result<(Self, Vec<ConfigIssue>)> {
+        let (mut file, mut issues): (Self, _) = load_checked(path, CONFIG_SCHEMA)?;
+        retain_valid_tools(&mut file.tools, &mut issues);
+        Ok((file, issues))
+    }
+}
+
+/// 仓库级配置文件名
+pub const REPO_CONFIG_FILE: &str = ".ai-commit.toml";
+
+/// 仓库根目录的 `.ai-commit.toml`，随仓库提交，让团队使用统一的提交设置
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RepoConfigFile {
+    pub version: Option<u32>,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub language: Option<String>,
+    pub prompt: Option<String>,
+    pub scopes: Option<Vec<String>>,
+    /// `[glossary]`：项目术语及其含义
+    pub glossary: Option<BTreeMap<String, String>>,
+    /// 提交规范文档，相对路径以 `.ai-commit.toml` 所在目录为准
+    pub style_guide: Option<PathBuf>,
+    /// `[[rules]]`：自定义分析规则，审查和提交时与内置检查一起运行
+    pub rules: Option<Vec<RuleConfig>>,
+    /// `[[tools]]`：外部分析工具，只有个人配置的 `trusted_repo_configs` 包含本文件的摘要时才运行
+    pub tools: Option<Vec<ToolConfig>>,
+    /// 文件内容的 SHA-256，用于判断其中的 `[[tools]]` 是否可信
+    #[serde(skip)]
+    pub digest: String,
+}
+
+/// 自定义分析规则
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleConfig {
+    pub name: String,
+    /// 正则表达式：`regex` 规则匹配新增行，`symbol` 规则匹配新增的符号名
+    pub pattern: String,
+    /// info、low、medium（默认）、high、critical
+    pub severity: Option<String>,
+    pub message: Option<String>,
+    /// `regex`（默认）或 `symbol`
+    pub kind: Option<String>,
+    /// `symbol` 规则只匹配该类型的符号：class、interface、enum、method、component 等
+    pub symbol: Option<String>,
+    /// 只检查匹配这些 glob 的文件，如 `src/**/*.rs`
+    pub files: Option<Vec<String>>,
+}
+
+impl RuleConfig {
+    /// 检查规则能否使用，返回不能使用的原因
+    pub fn problem(&self) -> Option<String> {
+        if self.name.trim().is_empty() {
+            return Some("missing `name`".to_string());
+        }
+        if self.pattern.is_empty() {
+            return Some("missing `pattern`".to_string());
+        }
+        if let Err(e) = regex::Regex::new(&self.pattern) {
+            return Some(format!("invalid pattern: {}", e));
+        }
+        if let Some(severity) = &self.severity {
+            if let Err(e) = severity.parse::<crate::report::Severity>() {
+                return Some(e.to_string());
+            }
+        }
+        if let Some(symbol) = &self.symbol {
+            if crate::languages::SymbolKind::from_name(symbol).is_none() {
+                return Some(format!("unknown symbol kind '{}'", symbol));
+            }
+        }
+        match self.kind.as_deref() {
+            None | Some("regex") | Some("symbol") => None,
+            Some(other) => Some(format!(
+                "unknown kind '{}' (expected: regex, symbol)",
+                other
+            )),
+        }
+    }
+}
+
+/// 外部分析工具，如团队内部的 linter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolConfig {
+    pub name: String,
+    /// 可执行文件，在仓库根目录运行
+    pub command: String,
+    /// 参数，`{files}` 展开为变更中适用的文件
+    pub args: Option<Vec<String>>,
+    /// 输出格式：`sarif`（默认）或 `jsonl`（每行一个 JSON 对象）
+    pub format: Option<String>,
+    /// 输出中没有严重程度时使用：info、low、medium（默认）、high、critical
+    pub severity: Option<String>,
+    /// 只在这些 glob 匹配的文件有变更时运行，如 `src/**/*.rs`
+    pub files: Option<Vec<String>>,
+}
+
+impl ToolConfig {
+    /// 检查工具能否使用，返回不能使用的原因
+    pub fn problem(&self) -> Option<String> {
+        if self.name.trim().is_empty() {
+            return Some("missing `name`".to_string());
+        }
+        if self.command.trim().is_empty() {
+            return Some("missing `command`".to_string());
+        }
+        if let Some(severity) = &self.severity {
+            if let Err(e) = severity.parse::<crate::report::Severity>() {
+                return Some(e.to_string());
+            }
+        }
+        match self.format.as_deref() {
+            None | Some("sarif") | Some("jsonl") => None,
+            Some(other) => Some(format!(
+                "unknown format '{}' (expected: sarif, jsonl)",
+                other
+            )),
+        }
+    }
+}
+
+impl RepoConfigFile {
+    /// 从目录开始逐级向上查找 `.ai-commit.toml`
+    pub fn find(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(REPO_CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// 读取仓库配置，`style_guide` 解析为绝对路径；文件不存在或不在仓库目录内时忽略并报告，
+    /// 避免仓库配置把 `~/.ssh/id_rsa` 之类的文件读进提示词
+    pub fn load_from(path: &Path) -> anyhow::Result<(Self, Vec<ConfigIssue>)> {
+        let (mut repo, mut issues): (Self, _) = load_checked(path, REPO_CONFIG_SCHEMA)?;
+        repo.digest = file_digest(path)?;
+        if let Some(style_guide) = repo.style_guide.take() {
+            let base = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => Path::new("."),
+            };
+            let joined = base.join(&style_guide);
+            match (joined.canonicalize(), base.canonicalize()) {
+                (Ok(file), Ok(root)) if !file.starts_with(&root) => {
+                    issues.push(ConfigIssue::OutsideRepo {
+                        key: "style_guide".to_string(),
+                        path: style_guide.display().to_string(),
+                    });
+                }
+                (Ok(file), Ok(_)) if file.is_file() => repo.style_guide = Some(file),
+                _ => issues.push(ConfigIssue::MissingFile {
+                    key: "style_guide".to_string(),
+                    path: joined.display().to_string(),
+                }),
+            }
+        }
+        if let Some(rules) = repo.rules.as_mut() {
+            rules.retain(|rule| match rule.problem() {
+                Some(reason) => {
+                    issues.push(ConfigIssue::InvalidRule {
+                        name: rule.name.clone(),
+                        reason,
+                    });
+                    false
+                }
+                None => true,
+            });
+        }
+        if let Some(tools) = repo.tools.as_mut() {
+            retain_valid_tools(tools, &mut issues);
+        }
+        Ok((repo, issues))
+    }
+}
+
+/// 去掉无法使用的外部分析工具并报告原因
+fn retain_valid_tools(tools: &mut Vec<ToolConfig>, issues: &mut Vec<ConfigIssue>) {
+    tools.retain(|tool| match tool.problem() {
+        Some(reason) => {
+            issues.push(ConfigIssue::InvalidTool {
+                name: tool.name.clone(),
+                reason,
+            });
+            false
+        }
+        None => true,
+    });
+}
+
+/// 文件内容的 SHA-256（十六进制）
+pub fn file_digest(path: &Path) -> anyhow::Result<String> {
+    let content =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(ring::digest::digest(&ring::digest::SHA256, &content)
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// 读取 TOML 文件并按 schema 检查，只有语法错误才返回错误
+fn load_checked<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    schema: &[(&str, ValueKind)],
+) -> anyhow::Result<(T, Vec<ConfigIssue>)> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut value: toml::Value =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+    let issues = validate(&mut value, schema);
+    let config = value
+        .try_into()
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok((config, issues))
+}
+
+/// 展开路径开头的 `~`
+pub fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+debug = true
+
+[provider]
+name = "deepseek"
+model = "deepseek-chat"
+
+[git]
+push = true
+ticket_patterns = ["PROJ-\\d+"]
+
+[tui]
+refresh_interval = 30
+
+[notifications]
+toast_seconds = 2
+
+[storage]
+data_dir = "~/data/ai-commit"
+"#,
+        )
+        .unwrap();
+
+        let (file, issues) = ConfigFile::load_from(&path).unwrap();
+        assert!(issues.is_empty());
+        assert_eq!(file.debug, Some(true));
+        assert_eq!(file.provider.name.as_deref(), Some("deepseek"));
+        assert_eq!(file.provider.api_key, None);
+        assert_eq!(file.git.push, Some(true));
+        assert_eq!(
+            file.git.ticket_patterns,
+            Some(vec![r"PROJ-\d+".to_string()])
+        );
+        assert_eq!(file.tui.refresh_interval, Some(30));
+        assert_eq!(file.notifications.toast_seconds, Some(2));
+
+        let data_dir = expand_home(file.storage.data_dir.as_deref().unwrap());
+        assert!(data_dir.is_absolute());
+        assert!(data_dir.ends_with("data/ai-commit"));
+
+        // 拼错的配置项被报告并忽略，其余配置照常生效
+        std::fs::write(&path, "[git]\npsuh = true\nemoji = true\n").unwrap();
+        let (file, issues) = ConfigFile::load_from(&path).unwrap();
+        assert_eq!(issues, [ConfigIssue::UnknownKey("git.psuh".to_string())]);
+        assert_eq!(file.git.emoji, Some(true));
+
+        // 语法错误时整个文件无法使用
+        std::fs::write(&path, "[git\n").unwrap();
+        assert!(ConfigFile::load_from(&path).is_err());
+    }
+
+    #[test]
+    fn test_find_repo_config_in_ancestors() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("src").join("core");
+        std::fs::create_dir_all(&nested).unwrap();
+        assert_eq!(RepoConfigFile::find(&nested), None);
+
+        let path = dir.path().join(REPO_CONFIG_FILE);
+        std::fs::write(&path, "model = \"qwen\"\nscopes = [\"api\", \"ui\"]\n").unwrap();
+        assert_eq!(RepoConfigFile::find(&nested), Some(path.clone()));
+
+        let (repo, issues) = RepoConfigFile::load_from(&path).unwrap();
+        assert!(issues.is_empty());
+        assert_eq!(repo.model.as_deref(), Some("qwen"));
+        assert_eq!(repo.scopes, Some(vec!["api".to_string(), "ui".to_string()]));
+
+        // 规范文档相对于 .ai-commit.toml 所在目录
+        std::fs::write(dir.path().join("STYLE.md"), "Use imperative mood.\n").unwrap();
+        std::fs::write(
+            &path,
+            "style_guide = \"STYLE.md\"\n[glossary]\nMR = \"merge request\"\n",
+        )
+        .unwrap();
+        let (repo, issues) = RepoConfigFile::load_from(&path).unwrap();
+        assert!(issues.is_empty());
+        assert_eq!(
+            repo.style_guide,
+            Some(dir.path().canonicalize().unwrap().join("STYLE.md"))
+        );
+        assert_eq!(repo.glossary.unwrap()["MR"], "merge request");
+
+        std::fs::write(
+            &path,
+            r#"
+[[rules]]
+name = "no-println"
+pattern = 'println!\('
+severity = "low"
+files = ["src/**/*.rs"]
+
+[[rules]]
+name = "broken"
+pattern = "("
+"#,
+        )
+        .unwrap();
+        let (repo, issues) = RepoConfigFile::load_from(&path).unwrap();
+        let rules = repo.rules.unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].name, "no-println");
+        assert_eq!(rules[0].severity.as_deref(), Some("low"));
+        assert!(matches!(&issues[..], [ConfigIssue::InvalidRule { name, .. }] if name == "broken"));
+
+        std::fs::write(
+            &path,
+            r#"
+[[tools]]
+name = "house-lint"
+command = "house-lint"
+args = ["--sarif", "{files}"]
+files = ["src/**/*.rs"]
+
+[[tools]]
+name = "legacy"
+command = "legacy-lint"
+format = "xml"
+"#,
+        )
+        .unwrap();
+        let (repo, issues) = RepoConfigFile::load_from(&path).unwrap();
+        let tools = repo.tools.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].command, "house-lint");
+        assert!(matches!(&issues[..], [ConfigIssue::InvalidTool { name, .. }] if name == "legacy"));
+
+        std::fs::write(&path, "style_guide = \"missing.md\"\n").unwrap();
+        let (repo, issues) = RepoConfigFile::load_from(&path).unwrap();
+        assert_eq!(repo.style_guide, None);
+        assert!(
+            matches!(&issues[..], [ConfigIssue::MissingFile { key, .. }] if key == "style_guide")
+        );
+    }
+
+    #[test]
+    fn test_style_guide_must_stay_in_repo() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret = dir.path().join("secret.txt");
+        std::fs::write(&secret, "token").unwrap();
+        let repo_dir = dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        let path = repo_dir.join(REPO_CONFIG_FILE);
+
+        for style_guide in ["../secret.txt", &secret.display().to_string()] {
+            std::fs::write(&path, format!("style_guide = {:?}\n", style_guide)).unwrap();
+            let (repo, issues) = RepoConfigFile::load_from(&path).unwrap();
+            assert_eq!(repo.style_guide, None);
+            assert!(
+                matches!(&issues[..], [ConfigIssue::OutsideRepo { key, .. }] if key == "style_guide")
+            );
+        }
+
+        // 仓库配置中的 ~ 不展开为用户目录
+        std::fs::write(&path, "style_guide = \"~/.ssh/id_rsa\"\n").unwrap();
+        let (repo, issues) = RepoConfigFile::load_from(&path).unwrap();
+        assert_eq!(repo.style_guide, None);
+        assert!(matches!(&issues[..], [ConfigIssue::MissingFile { .. }]));
+
+        // 指向仓库外的符号链接同样被拒绝
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&secret, repo_dir.join("STYLE.md")).unwrap();
+            std::fs::write(&path, "style_guide = \"STYLE.md\"\n").unwrap();
+            let (repo, issues) = RepoConfigFile::load_from(&path).unwrap();
+            assert_eq!(repo.style_guide, None);
+            assert!(matches!(&issues[..], [ConfigIssue::OutsideRepo { .. }]));
+        }
+    }
+}
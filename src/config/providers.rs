@@ -23,6 +23,22 @@ pub struct ProviderInfo {
     pub env_prefix: String,
     /// 描述
     pub description: String,
+    /// API Key 的传递方式，只对 OpenAI 兼容格式的自定义提供商生效
+    #[serde(default)]
+    pub auth: AuthStyle,
+}
+
+/// API Key 的传递方式
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthStyle {
+    /// `Authorization: Bearer <key>`
+    #[default]
+    Bearer,
+    /// 放在指定的请求头中，如 Azure OpenAI 的 `api-key`
+    Header(String),
+    /// 不需要认证
+    None,
 }
 
 /// API 格式类型
@@ -61,6 +77,123 @@ struct ProviderConfig {
     description: String,
 }
 
+/// 配置文件 `[[providers]]` 中的自定义提供商，用于接入内置提供商以外的网关
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CustomProvider {
+    pub name: Option<String>,
+    pub display_name: Option<String>,
+    pub url: Option<String>,
+    /// openai（默认）、ollama、anthropic 或 google
+    pub api_format: Option<String>,
+    /// bearer（默认）、header 或 none
+    pub auth: Option<String>,
+    /// `auth = "header"` 时使用的请求头名称
+    pub auth_header: Option<String>,
+    pub default_model: Option<String>,
+    pub models: Option<Vec<String>>,
+    pub description: Option<String>,
+}
+
+impl CustomProvider {
+    /// 转换为提供商信息，配置不完整时返回原因
+    pub fn to_info(&self) -> Result<ProviderInfo, String> {
+        let name = self
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .ok_or("missing `name`")?;
+        let url = self.url.clone().ok_or("missing `url`")?;
+        let default_model = self
+            .default_model
+            .clone()
+            .ok_or("missing `default_model`")?;
+
+        let api_format = match self.api_format.as_deref().unwrap_or("openai") {
+            "openai" => ApiFormat::OpenAI,
+            "ollama" => ApiFormat::Ollama,
+            "anthropic" => ApiFormat::Anthropic,
+            "google" => ApiFormat::Google,
+            other => {
+                return Err(format!(
+                    "unsupported api_format `{}`, expected openai, ollama, anthropic or google",
+                    other
+                ))
+            }
+        };
+        let auth = match (self.auth.as_deref().unwrap_or("bearer"), &self.auth_header) {
+            ("bearer", _) => AuthStyle::Bearer,
+            ("none", _) => AuthStyle::None,
+            ("header", Some(header)) if !header.trim().is_empty() => {
+                AuthStyle::Header(header.trim().to_string())
+            }
+            ("header", _) => return Err("`auth = \"header\"` requires `auth_header`".to_string()),
+            (other, _) => {
+                return Err(format!(
+                    "unsupported auth `{}`, expected bearer, header or none",
+                    other
+                ))
+            }
+        };
+
+        let mut supported_models = self.models.clone().unwrap_or_default();
+        if !supported_models.contains(&default_model) {
+            supported_models.insert(0, default_model.clone());
+        }
+
+        Ok(ProviderInfo {
+            name: name.to_string(),
+            display_name: self
+                .display_name
+                .clone()
+                .unwrap_or_else(|| name.to_string()),
+            default_url: url,
+            requires_api_key: auth != AuthStyle::None,
+            default_model,
+            supported_models,
+            api_format,
+            env_prefix: format!(
+                "AI_COMMIT_{}",
+                name.to_uppercase()
+                    .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+            ),
+            description: self
+                .description
+                .clone()
+                .unwrap_or_else(|| "自定义提供商".to_string()),
+            auth,
+        })
+    }
+}
+
+/// 把自定义提供商合并到内置提供商中，返回被跳过的提供商及原因
+pub fn merge_custom_providers(
+    providers: &mut HashMap<String, ProviderInfo>,
+    custom: &[CustomProvider],
+) -> Vec<(String, String)> {
+    let mut skipped = Vec::new();
+    for (index, provider) in custom.iter().enumerate() {
+        let label = provider
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("providers[{}]", index));
+        match provider.to_info() {
+            Ok(info) if providers.contains_key(&info.name) => {
+                skipped.push((
+                    label,
+                    "name is already used by another provider".to_string(),
+                ));
+            }
+            Ok(info) => {
+                providers.insert(info.name.clone(), info);
+            }
+            Err(reason) => skipped.push((label, reason)),
+        }
+    }
+    skipped
+}
+
 impl From<ProviderConfig> for ProviderInfo {
     fn from(config: ProviderConfig) -> Self {
         Self {
@@ -73,6 +206,7 @@ impl From<ProviderConfig> for ProviderInfo {
             api_format: config.api_format,
             env_prefix: config.env_prefix,
             description: config.description,
+            auth: AuthStyle::default(),
         }
     }
 }
@@ -127,6 +261,7 @@ fn get_default_providers() -> HashMap<String, ProviderInfo> {
             api_format: ApiFormat::Ollama,
             env_prefix: "AI_COMMIT_OLLAMA".to_string(),
             description: "本地 Ollama 服务，无需 API Key".to_string(),
+            auth: AuthStyle::None,
         },
     );
 
@@ -143,6 +278,7 @@ fn get_default_providers() -> HashMap<String, ProviderInfo> {
             api_format: ApiFormat::OpenAI,
             env_prefix: "AI_COMMIT_DEEPSEEK".to_string(),
             description: "深度求索 AI 服务，需要 API Key".to_string(),
+            auth: AuthStyle::Bearer,
         },
     );
 
@@ -164,6 +300,7 @@ fn get_default_providers() -> HashMap<String, ProviderInfo> {
             api_format: ApiFormat::OpenAI,
             env_prefix: "AI_COMMIT_SILICONFLOW".to_string(),
             description: "硅基流动 AI 服务，需要 API Key".to_string(),
+            auth: AuthStyle::Bearer,
         },
     );
 
@@ -184,6 +321,7 @@ fn get_default_providers() -> HashMap<String, ProviderInfo> {
             api_format: ApiFormat::OpenAI,
             env_prefix: "AI_COMMIT_KIMI".to_string(),
             description: "月之暗面 Kimi AI 服务，需要 API Key".to_string(),
+            auth: AuthStyle::Bearer,
         },
     );
 
@@ -205,6 +343,7 @@ fn get_default_providers() -> HashMap<String, ProviderInfo> {
             api_format: ApiFormat::OpenAI,
             env_prefix: "AI_COMMIT_OPENAI".to_string(),
             description: "OpenAI GPT 系列模型，需要 API Key".to_string(),
+            auth: AuthStyle::Bearer,
         },
     );
 
@@ -225,6 +364,7 @@ fn get_default_providers() -> HashMap<String, ProviderInfo> {
             api_format: ApiFormat::Anthropic,
             env_prefix: "AI_COMMIT_CLAUDE".to_string(),
             description: "Anthropic Claude 系列模型，需要 API Key".to_string(),
+            auth: AuthStyle::Bearer,
         },
     );
 
@@ -245,6 +385,7 @@ fn get_default_providers() -> HashMap<String, ProviderInfo> {
             api_format: ApiFormat::Google,
             env_prefix: "AI_COMMIT_GEMINI".to_string(),
             description: "Google Gemini 系列模型，需要 API Key".to_string(),
+            auth: AuthStyle::Bearer,
         },
     );
 
@@ -266,16 +407,25 @@ fn get_default_providers() -> HashMap<String, ProviderInfo> {
             api_format: ApiFormat::OpenAI,
             env_prefix: "AI_COMMIT_QWEN".to_string(),
             description: "阿里云通义千问 AI 服务，需要 API Key".to_string(),
+            auth: AuthStyle::Bearer,
         },
     );
 
     providers
 }
 
-/// 全局提供商配置映射
-pub static PROVIDER_REGISTRY: Lazy<HashMap<String, ProviderInfo>> =
+/// 内置提供商配置映射，不包含配置文件中的自定义提供商
+pub static BUILTIN_PROVIDERS: Lazy<HashMap<String, ProviderInfo>> =
     Lazy::new(load_providers_from_config);
 
+/// 全局提供商配置映射：内置提供商加上配置文件中的自定义提供商
+pub static PROVIDER_REGISTRY: Lazy<HashMap<String, ProviderInfo>> = Lazy::new(|| {
+    let mut providers = BUILTIN_PROVIDERS.clone();
+    // 被跳过的提供商在读取配置文件时已经提示过
+    merge_custom_providers(&mut providers, &super::user_config_file().providers);
+    providers
+});
+
 impl ProviderInfo {
     /// 获取 API URL 环境变量名
     pub fn url_env_var(&self) -> String {
@@ -430,6 +580,51 @@ mod tests {
         assert!(info.contains("当前加载的提供商数量"));
     }
 
+    #[test]
+    fn test_merge_custom_providers() {
+        let custom: Vec<CustomProvider> = toml::from_str::<toml::Value>(
+            r#"
+[[providers]]
+name = "azure-gw"
+url = "https://gw.example.com/v1/chat/completions"
+auth = "header"
+auth_header = "api-key"
+default_model = "gpt-4o"
+
+[[providers]]
+name = "deepseek"
+url = "https://example.com"
+default_model = "x"
+
+[[providers]]
+name = "broken"
+url = "https://example.com"
+"#,
+        )
+        .unwrap()["providers"]
+            .clone()
+            .try_into()
+            .unwrap();
+
+        let mut providers = get_default_providers();
+        let skipped = merge_custom_providers(&mut providers, &custom);
+
+        let gateway = &providers["azure-gw"];
+        assert_eq!(gateway.api_format, ApiFormat::OpenAI);
+        assert_eq!(gateway.auth, AuthStyle::Header("api-key".to_string()));
+        assert_eq!(gateway.supported_models, ["gpt-4o"]);
+        assert_eq!(gateway.api_key_env_var(), "AI_COMMIT_AZURE_GW_API_KEY");
+        assert!(gateway.requires_api_key);
+
+        // 不能覆盖内置提供商，缺少必填项的被跳过
+        assert_eq!(providers["deepseek"].default_model, "deepseek-chat");
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(
+            skipped[1],
+            ("broken".to_string(), "missing `default_model`".to_string())
+        );
+    }
+
     #[test]
     fn test_provider_config_conversion() {
         let config = ProviderConfig {
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{reload_config_file, ConfigFile, ConfigIssue};
+
+/// 检查配置文件是否变化的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 重新加载配置文件的结果
+#[derive(Debug)]
+pub enum ConfigReload {
+    /// 已使用新的配置，附带其中被忽略的配置项
+    Reloaded(Vec<ConfigIssue>),
+    /// 新的配置无法解析，继续使用原来的配置
+    Invalid(anyhow::Error),
+}
+
+/// 监视配置文件的修改时间，供 TUI、`--watch --auto-commit` 和守护进程等长时间运行的模式使用
+pub struct ConfigWatcher {
+    path: Option<PathBuf>,
+    modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+impl Default for ConfigWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigWatcher {
+    pub fn new() -> Self {
+        Self::for_path(ConfigFile::path())
+    }
+
+    pub fn for_path(path: Option<PathBuf>) -> Self {
+        Self {
+            modified: path.as_deref().and_then(modified_time),
+            path,
+            last_check: Instant::now(),
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// 配置文件被修改、创建或删除时返回 true
+    pub fn check(&mut self) -> bool {
+        let modified = self.path.as_deref().and_then(modified_time);
+        if modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        true
+    }
+
+    /// 每隔一段时间检查一次，配置文件变化时重新加载
+    pub fn poll(&mut self) -> Option<ConfigReload> {
+        if self.last_check.elapsed() < CHECK_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+        if !self.check() {
+            return None;
+        }
+        Some(match reload_config_file() {
+            Ok(issues) => ConfigReload::Reloaded(issues),
+            Err(e) => ConfigReload::Invalid(e),
+        })
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_detects_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut watcher = ConfigWatcher::for_path(Some(path.clone()));
+        assert!(!watcher.check());
+
+        std::fs::write(&path, "debug = true\n").unwrap();
+        assert!(watcher.check());
+        assert!(!watcher.check());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.check());
+
+        // 没有配置目录时从不触发
+        let mut watcher = ConfigWatcher::for_path(None);
+        assert!(!watcher.check());
+        assert!(watcher.poll().is_none());
+    }
+}
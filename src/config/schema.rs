@@ -0,0 +1,482 @@
+use std::fmt;
+
+use super::providers::PROVIDER_REGISTRY;
+
+/// 当前支持的配置文件版本，写在文件顶层的 `version = 1`
+pub const CONFIG_VERSION: i64 = 1;
+
+/// 配置项的取值类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueKind {
+    Bool,
+    /// 不超过上限的非负整数
+    Integer(u64),
+    String,
+    StringList,
+    /// 值都是字符串的表，如 `[glossary]`
+    StringMap,
+    /// 表数组，如 `[[providers]]`，每个表按子 schema 检查
+    TableList(&'static [(&'static str, ValueKind)]),
+}
+
+impl ValueKind {
+    fn matches(self, value: &toml::Value) -> bool {
+        match (self, value) {
+            (ValueKind::Bool, toml::Value::Boolean(_)) => true,
+            (ValueKind::Integer(max), toml::Value::Integer(n)) => *n >= 0 && (*n as u64) <= max,
+            (ValueKind::String, toml::Value::String(_)) => true,
+            (ValueKind::StringList, toml::Value::Array(items)) => {
+                items.iter().all(|item| item.is_str())
+            }
+            (ValueKind::StringMap, toml::Value::Table(table)) => {
+                table.values().all(|item| item.is_str())
+            }
+            (ValueKind::TableList(_), toml::Value::Array(items)) => {
+                items.iter().all(|item| item.is_table())
+            }
+            _ => false,
+        }
+    }
+
+    fn describe(self) -> String {
+        match self {
+            ValueKind::Bool => "a boolean".to_string(),
+            ValueKind::Integer(max) => format!("an integer between 0 and {}", max),
+            ValueKind::String => "a string".to_string(),
+            ValueKind::StringList => "a list of strings".to_string(),
+            ValueKind::StringMap => "a table of strings".to_string(),
+            ValueKind::TableList(_) => "a list of tables".to_string(),
+        }
+    }
+}
+
+/// `~/.config/ai-commit/config.toml` 的所有配置项，`a.b` 表示 `[a]` 中的 `b`
+pub const CONFIG_SCHEMA: &[(&str, ValueKind)] = &[
+    ("version", ValueKind::Integer(u32::MAX as u64)),
+    ("debug", ValueKind::Bool),
+    ("provider.name", ValueKind::String),
+    ("provider.model", ValueKind::String),
+    ("provider.api_key", ValueKind::String),
+    ("provider.url", ValueKind::String),
+    ("provider.embedding_model", ValueKind::String),
+    ("git.no_add", ValueKind::Bool),
+    ("git.push", ValueKind::Bool),
+    ("git.emoji", ValueKind::Bool),
+    ("git.candidates", ValueKind::Integer(u8::MAX as u64)),
+    ("git.ticket_patterns", ValueKind::StringList),
+    ("git.ticket_position", ValueKind::String),
+    ("git.language", ValueKind::String),
+    ("git.prompt", ValueKind::String),
+    ("git.scopes", ValueKind::StringList),
+    ("tui.theme", ValueKind::String),
+    ("tui.refresh_interval", ValueKind::Integer(u64::MAX)),
+    ("tui.watch_files", ValueKind::Bool),
+    ("notifications.toast_seconds", ValueKind::Integer(u64::MAX)),
+    (
+        "notifications.warning_seconds",
+        ValueKind::Integer(u64::MAX),
+    ),
+    ("storage.data_dir", ValueKind::String),
+    ("storage.backend", ValueKind::String),
+    ("storage.redis_url", ValueKind::String),
+    ("storage.redis_namespace", ValueKind::String),
+    ("storage.redis_ttl", ValueKind::Integer(u64::MAX)),
+    ("storage.s3_bucket", ValueKind::String),
+    ("storage.s3_prefix", ValueKind::String),
+    ("storage.s3_region", ValueKind::String),
+    ("storage.s3_endpoint", ValueKind::String),
+    ("storage.s3_compress", ValueKind::Bool),
+    ("storage.s3_sse", ValueKind::String),
+    ("storage.max_age_days", ValueKind::Integer(u64::MAX)),
+    ("storage.max_reports", ValueKind::Integer(u64::MAX)),
+    ("storage.max_size_mb", ValueKind::Integer(u64::MAX)),
+    ("storage.encryption_enabled", ValueKind::Bool),
+    ("storage.encryption_key", ValueKind::String),
+    ("storage.namespace", ValueKind::String),
+    ("storage.retries", ValueKind::Integer(u32::MAX as u64)),
+    ("storage.fallback", ValueKind::Bool),
+    ("storage.backup_dir", ValueKind::String),
+    (
+        "storage.backup_interval_hours",
+        ValueKind::Integer(u64::MAX),
+    ),
+    ("storage.backup_keep", ValueKind::Integer(u64::MAX)),
+    ("network.proxy", ValueKind::String),
+    ("network.ca_cert", ValueKind::String),
+    ("network.connect_timeout", ValueKind::Integer(u64::MAX)),
+    ("network.timeout", ValueKind::Integer(u64::MAX)),
+    ("network.keep_alive", ValueKind::Integer(u64::MAX)),
+    ("report.columns", ValueKind::StringList),
+    ("report.sections", ValueKind::StringList),
+    ("report.exclude_sections", ValueKind::StringList),
+    ("providers", ValueKind::TableList(CUSTOM_PROVIDER_SCHEMA)),
+    ("tools", ValueKind::TableList(TOOL_SCHEMA)),
+    ("trusted_repo_configs", ValueKind::StringList),
+];
+
+/// `[[providers]]` 中每个自定义提供商的配置项
+pub const CUSTOM_PROVIDER_SCHEMA: &[(&str, ValueKind)] = &[
+    ("name", ValueKind::String),
+    ("display_name", ValueKind::String),
+    ("url", ValueKind::String),
+    ("api_format", ValueKind::String),
+    ("auth", ValueKind::String),
+    ("auth_header", ValueKind::String),
+    ("default_model", ValueKind::String),
+    ("models", ValueKind::StringList),
+    ("description", ValueKind::String),
+];
+
+/// 仓库配置 `.ai-commit.toml` 的所有配置项
+pub const REPO_CONFIG_SCHEMA: &[(&str, ValueKind)] = &[
+    ("version", ValueKind::Integer(u32::MAX as u64)),
+    ("provider", ValueKind::String),
+    ("model", ValueKind::String),
+    ("language", ValueKind::String),
+    ("prompt", ValueKind::String),
+    ("scopes", ValueKind::StringList),
+    ("glossary", ValueKind::StringMap),
+    ("style_guide", ValueKind::String),
+    ("rules", ValueKind::TableList(RULE_SCHEMA)),
+    ("tools", ValueKind::TableList(TOOL_SCHEMA)),
+];
+
+/// `[[rules]]` 中每条自定义分析规则的配置项
+pub const RULE_SCHEMA: &[(&str, ValueKind)] = &[
+    ("name", ValueKind::String),
+    ("pattern", ValueKind::String),
+    ("severity", ValueKind::String),
+    ("message", ValueKind::String),
+    ("kind", ValueKind::String),
+    ("symbol", ValueKind::String),
+    ("files", ValueKind::StringList),
+];
+
+/// `[[tools]]` 中每个外部分析工具的配置项
+pub const TOOL_SCHEMA: &[(&str, ValueKind)] = &[
+    ("name", ValueKind::String),
+    ("command", ValueKind::String),
+    ("args", ValueKind::StringList),
+    ("format", ValueKind::String),
+    ("severity", ValueKind::String),
+    ("files", ValueKind::StringList),
+];
+
+/// 配置检查发现的问题
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigIssue {
+    /// 不认识的配置项，已忽略
+    UnknownKey(String),
+    /// 类型不对的配置项，已忽略
+    InvalidType { key: String, expected: String },
+    /// 比当前程序更新的配置版本
+    UnsupportedVersion(i64),
+    /// 已弃用的环境变量，仍然生效
+    DeprecatedEnv { name: String, replacement: String },
+    /// 无法使用的自定义提供商，已跳过
+    InvalidProvider { name: String, reason: String },
+    /// 配置项引用的文件不存在，已忽略
+    MissingFile { key: String, path: String },
+    /// 仓库配置引用了仓库目录之外的文件，已忽略
+    OutsideRepo { key: String, path: String },
+    /// 无法使用的自定义分析规则，已跳过
+    InvalidRule { name: String, reason: String },
+    /// 无法使用的外部分析工具，已跳过
+    InvalidTool { name: String, reason: String },
+    /// 未被信任的仓库配置中的外部分析工具，不会运行
+    UntrustedTools { digest: String },
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigIssue::UnknownKey(key) => write!(f, "unknown key `{}` is ignored", key),
+            ConfigIssue::InvalidType { key, expected } => {
+                write!(f, "`{}` should be {}, ignored", key, expected)
+            }
+            ConfigIssue::UnsupportedVersion(version) => write!(
+                f,
+                "config version {} is newer than supported version {}, some settings may be ignored",
+                version, CONFIG_VERSION
+            ),
+            ConfigIssue::DeprecatedEnv { name, replacement } => write!(
+                f,
+                "{} is deprecated, rename it to {} (still applied for now)",
+                name, replacement
+            ),
+            ConfigIssue::InvalidProvider { name, reason } => {
+                write!(f, "custom provider `{}` is skipped: {}", name, reason)
+            }
+            ConfigIssue::MissingFile { key, path } => {
+                write!(f, "`{}` file {} does not exist, ignored", key, path)
+            }
+            ConfigIssue::OutsideRepo { key, path } => write!(
+                f,
+                "`{}` file {} is outside the repository, ignored",
+                key, path
+            ),
+            ConfigIssue::InvalidRule { name, reason } => {
+                write!(f, "analysis rule `{}` is skipped: {}", name, reason)
+            }
+            ConfigIssue::InvalidTool { name, reason } => {
+                write!(f, "analysis tool `{}` is skipped: {}", name, reason)
+            }
+            ConfigIssue::UntrustedTools { digest } => write!(
+                f,
+                "`[[tools]]` is ignored because this file is not trusted; review the commands and add \"{}\" to `trusted_repo_configs` in your config.toml to run them",
+                digest
+            ),
+        }
+    }
+}
+
+/// 按 schema 检查配置，移除不认识和类型错误的配置项，返回发现的问题
+pub fn validate(value: &mut toml::Value, schema: &[(&str, ValueKind)]) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    if let Some(table) = value.as_table_mut() {
+        if let Some(version) = table.get("version").and_then(toml::Value::as_integer) {
+            if version > CONFIG_VERSION {
+                issues.push(ConfigIssue::UnsupportedVersion(version));
+            }
+        }
+        validate_table(table, "", schema, &mut issues);
+    }
+    issues
+}
+
+fn validate_table(
+    table: &mut toml::value::Table,
+    prefix: &str,
+    schema: &[(&str, ValueKind)],
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let keys: Vec<String> = table.keys().cloned().collect();
+    for key in keys {
+        let path = format!("{}{}", prefix, key);
+        let section = format!("{}.", path);
+        let is_section = schema.iter().any(|(name, _)| name.starts_with(&section));
+        let kind = schema
+            .iter()
+            .find(|(name, _)| *name == path)
+            .map(|(_, kind)| *kind);
+
+        let keep = match (table.get_mut(&key), kind) {
+            (Some(toml::Value::Table(inner)), _) if is_section => {
+                validate_table(inner, &section, schema, issues);
+                true
+            }
+            (Some(toml::Value::Array(items)), Some(ValueKind::TableList(item_schema))) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    if let Some(inner) = item.as_table_mut() {
+                        // 子 schema 中的键不带前缀，报告问题时再加上所在的位置
+                        let mut item_issues = Vec::new();
+                        validate_table(inner, "", item_schema, &mut item_issues);
+                        let item_prefix = format!("{}[{}].", path, index);
+                        issues.extend(item_issues.into_iter().map(|issue| match issue {
+                            ConfigIssue::UnknownKey(key) => {
+                                ConfigIssue::UnknownKey(format!("{}{}", item_prefix, key))
+                            }
+                            ConfigIssue::InvalidType { key, expected } => {
+                                ConfigIssue::InvalidType {
+                                    key: format!("{}{}", item_prefix, key),
+                                    expected,
+                                }
+                            }
+                            other => other,
+                        }));
+                    }
+                }
+                let valid = items.iter().all(toml::Value::is_table);
+                if !valid {
+                    issues.push(ConfigIssue::InvalidType {
+                        key: path,
+                        expected: ValueKind::TableList(item_schema).describe(),
+                    });
+                }
+                valid
+            }
+            (Some(value), Some(kind)) => {
+                let valid = kind.matches(value);
+                if !valid {
+                    issues.push(ConfigIssue::InvalidType {
+                        key: path,
+                        expected: kind.describe(),
+                    });
+                }
+                valid
+            }
+            (Some(_), None) if is_section => {
+                issues.push(ConfigIssue::InvalidType {
+                    key: path,
+                    expected: "a table".to_string(),
+                });
+                false
+            }
+            _ => {
+                issues.push(ConfigIssue::UnknownKey(path));
+                false
+            }
+        };
+        if !keep {
+            table.remove(&key);
+        }
+    }
+}
+
+/// 旧版按提供商区分的环境变量名，如 `AI_COMMIT_DEEPSEEK_URL`，以及对应的新名称
+pub fn legacy_env_names(provider: &str) -> Option<[(String, &'static str); 2]> {
+    let info = PROVIDER_REGISTRY.get(provider)?;
+    Some([
+        (info.api_key_env_var(), "AI_COMMIT_PROVIDER_API_KEY"),
+        (info.url_env_var(), "AI_COMMIT_PROVIDER_URL"),
+    ])
+}
+
+/// 检查当前进程中设置了的旧版环境变量
+pub fn deprecated_env_vars() -> Vec<ConfigIssue> {
+    let mut providers: Vec<&String> = PROVIDER_REGISTRY.keys().collect();
+    providers.sort();
+    providers
+        .into_iter()
+        .filter_map(|provider| legacy_env_names(provider))
+        .flatten()
+        .filter(|(name, _)| std::env::var_os(name).is_some())
+        .map(|(name, replacement)| ConfigIssue::DeprecatedEnv {
+            name,
+            replacement: replacement.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_reports_and_removes_bad_keys() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+version = 2
+colour = "red"
+storage = 1
+
+[git]
+psuh = true
+push = "yes"
+candidates = 300
+emoji = true
+
+[tui]
+theme = "dark"
+"#,
+        )
+        .unwrap();
+
+        let issues = validate(&mut value, CONFIG_SCHEMA);
+        assert!(issues.contains(&ConfigIssue::UnsupportedVersion(2)));
+        assert!(issues.contains(&ConfigIssue::UnknownKey("colour".to_string())));
+        assert!(issues.contains(&ConfigIssue::UnknownKey("git.psuh".to_string())));
+        assert!(issues.contains(&ConfigIssue::InvalidType {
+            key: "git.push".to_string(),
+            expected: "a boolean".to_string(),
+        }));
+        assert!(issues.iter().any(
+            |issue| matches!(issue, ConfigIssue::InvalidType { key, .. } if key == "git.candidates")
+        ));
+        assert!(issues.contains(&ConfigIssue::InvalidType {
+            key: "storage".to_string(),
+            expected: "a table".to_string(),
+        }));
+        assert_eq!(issues.len(), 6);
+
+        // 有效的配置项保留下来
+        assert_eq!(value["git"]["emoji"].as_bool(), Some(true));
+        assert_eq!(value["tui"]["theme"].as_str(), Some("dark"));
+        assert!(value["git"].get("push").is_none());
+    }
+
+    #[test]
+    fn test_validate_table_list_items() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+[[rules]]
+name = "no-todo"
+pattern = "TODO"
+level = "high"
+files = "src"
+"#,
+        )
+        .unwrap();
+
+        let issues = validate(&mut value, REPO_CONFIG_SCHEMA);
+        assert_eq!(
+            issues,
+            vec![
+                ConfigIssue::InvalidType {
+                    key: "rules[0].files".to_string(),
+                    expected: "a list of strings".to_string(),
+                },
+                ConfigIssue::UnknownKey("rules[0].level".to_string()),
+            ]
+        );
+        assert_eq!(value["rules"][0]["name"].as_str(), Some("no-todo"));
+    }
+
+    #[test]
+    fn test_schema_matches_config_structs() {
+        fn sample(schema: &[(&str, ValueKind)]) -> toml::Value {
+            let mut root = toml::value::Table::new();
+            for (path, kind) in schema {
+                let value = match kind {
+                    ValueKind::Bool => toml::Value::Boolean(true),
+                    ValueKind::Integer(_) => toml::Value::Integer(1),
+                    ValueKind::String => toml::Value::String("x".to_string()),
+                    ValueKind::StringList => toml::Value::Array(vec!["x".into()]),
+                    ValueKind::StringMap => toml::Value::Table(
+                        [("x".to_string(), toml::Value::from("x"))]
+                            .into_iter()
+                            .collect(),
+                    ),
+                    ValueKind::TableList(item_schema) => {
+                        toml::Value::Array(vec![sample(item_schema)])
+                    }
+                };
+                let (table, key) = match path.split_once('.') {
+                    Some((section, key)) => (
+                        root.entry(section)
+                            .or_insert_with(|| toml::Value::Table(Default::default()))
+                            .as_table_mut()
+                            .unwrap(),
+                        key,
+                    ),
+                    None => (&mut root, *path),
+                };
+                table.insert(key.to_string(), value);
+            }
+            toml::Value::Table(root)
+        }
+
+        // 每个 schema 配置项都能被配置结构体接受
+        let config: super::super::ConfigFile = sample(CONFIG_SCHEMA).try_into().unwrap();
+        assert_eq!(
+            config.storage.data_dir.as_deref(),
+            Some(std::path::Path::new("x"))
+        );
+        let repo: super::super::RepoConfigFile = sample(REPO_CONFIG_SCHEMA).try_into().unwrap();
+        assert_eq!(repo.scopes, Some(vec!["x".to_string()]));
+        assert_eq!(repo.glossary.unwrap()["x"], "x");
+        assert_eq!(repo.rules.unwrap()[0].files, Some(vec!["x".to_string()]));
+        assert_eq!(repo.tools.unwrap()[0].args, Some(vec!["x".to_string()]));
+        assert_eq!(config.providers[0].auth_header.as_deref(), Some("x"));
+    }
+
+    #[test]
+    fn test_legacy_env_names() {
+        let [(api_key, new_api_key), (url, new_url)] = legacy_env_names("deepseek").unwrap();
+        assert_eq!(api_key, "AI_COMMIT_DEEPSEEK_API_KEY");
+        assert_eq!(new_api_key, "AI_COMMIT_PROVIDER_API_KEY");
+        assert_eq!(url, "AI_COMMIT_DEEPSEEK_URL");
+        assert_eq!(new_url, "AI_COMMIT_PROVIDER_URL");
+        assert!(legacy_env_names("unknown").is_none());
+    }
+}
@@ -1,8 +1,15 @@
 use super::*;
+use crate::analysis::container::ContainerChanges;
+use crate::analysis::migration::SqlChanges;
+use crate::analysis::terraform::TerraformChanges;
+use crate::analysis::test_changes::TestChanges;
+use crate::analysis::{docs, generated};
 use crate::core::ai::diff_analyzer::DiffAnalysis;
 use crate::core::ai::provider::{AIProvider, ProviderConfig};
-use crate::core::ai::validation::COMMIT_FORMAT_REGEX;
+use crate::core::ai::validation::{enforce_commit_type, COMMIT_FORMAT_REGEX};
+use crate::languages::LanguageSummary;
 use async_trait::async_trait;
+use futures_util::StreamExt;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -35,23 +42,100 @@ impl CommitAgent {
 
     /// 生成提交消息
     async fn generate_commit_message(&self, diff: &str, context: &AgentContext) -> Result<String> {
+        let (provider, prompt, provider_config) = self.prepare_request(diff, context)?;
+
+        let response = provider.generate(&prompt, &provider_config).await?;
+        self.finalize_commit_message(diff, &response)
+    }
+
+    /// 流式生成提交消息，原始片段通过 `chunks` 发送，返回清理后的消息
+    async fn stream_commit_message(
+        &self,
+        diff: &str,
+        context: &AgentContext,
+        chunks: &tokio::sync::mpsc::UnboundedSender<String>,
+    ) -> Result<String> {
+        let (provider, prompt, provider_config) = self.prepare_request(diff, context)?;
+
+        let mut stream = provider.stream_generate(&prompt, &provider_config).await?;
+        let mut response = String::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            response.push_str(&chunk);
+            // 接收方已关闭时继续生成，结果仍然返回给调用者
+            let _ = chunks.send(chunk);
+        }
+
+        self.finalize_commit_message(diff, &response)
+    }
+
+    /// 清理并验证 AI 响应；纯文档、纯测试变更强制使用 docs/test 类型，
+    /// 测试与业务代码混合时在正文中附加测试统计
+    fn finalize_commit_message(&self, diff: &str, response: &str) -> Result<String> {
+        let mut message = self.clean_commit_message(response);
+        let tests = TestChanges::from_diff(diff);
+        if docs::is_docs_only(diff) {
+            message = enforce_commit_type(&message, "docs");
+        } else if tests.is_test_only() {
+            message = enforce_commit_type(&message, "test");
+        }
+        self.validate_commit_message(&message)?;
+
+        if let Some(body) = tests.commit_body() {
+            message = format!("{}\n\n{}", message, body);
+        }
+        Ok(message)
+    }
+
+    /// 构建提示词和提供商配置
+    fn prepare_request(
+        &self,
+        diff: &str,
+        context: &AgentContext,
+    ) -> Result<(Arc<dyn AIProvider>, String, ProviderConfig)> {
         let provider = self
             .provider
-            .as_ref()
+            .clone()
             .ok_or_else(|| anyhow::anyhow!("AI provider not initialized"))?;
 
         // 分析 diff
         let analysis = DiffAnalysis::analyze_diff(diff);
 
         // 加载项目记忆上下文
-        let memory_context = context
+        let mut memory_context = context
             .env_vars
             .get("MEMORY_CONTEXT")
             .cloned()
             .unwrap_or_default();
 
+        // 附加关联工单上下文
+        if let Some(ticket_context) = context.env_vars.get("TICKET_CONTEXT") {
+            memory_context.push_str(ticket_context);
+        }
+
+        // 附加修改相同文件的历史提交信息
+        if let Some(history_context) = context.env_vars.get("HISTORY_CONTEXT") {
+            memory_context.push_str(history_context);
+        }
+
+        // 附加变更分组
+        if let Some(cluster_context) = context.env_vars.get("CLUSTER_CONTEXT") {
+            memory_context.push_str(cluster_context);
+        }
+
+        // 附加仓库或个人配置中的团队约定
+        if let Some(conventions) = context.env_vars.get("CONVENTIONS_CONTEXT") {
+            memory_context.push_str(conventions);
+        }
+
         // 构建增强的提示词
-        let enhanced_prompt = self.build_enhanced_prompt(diff, &analysis, &memory_context)?;
+        let language = context
+            .env_vars
+            .get("COMMIT_LANGUAGE")
+            .map(String::as_str)
+            .unwrap_or("中文");
+        let enhanced_prompt =
+            self.build_enhanced_prompt(diff, &analysis, &memory_context, language)?;
 
         // 调用 AI 生成
         let provider_config = ProviderConfig {
@@ -67,14 +151,7 @@ impl CommitAgent {
             stream: context.config.stream,
         };
 
-        let response = provider
-            .generate(&enhanced_prompt, &provider_config)
-            .await?;
-
-        // 先清理响应，再验证格式
-        let cleaned_response = self.clean_commit_message(&response);
-        self.validate_commit_message(&cleaned_response)?;
-        Ok(cleaned_response)
+        Ok((provider, enhanced_prompt, provider_config))
     }
 
     /// 构建增强的提示词
@@ -83,6 +160,7 @@ impl CommitAgent {
         diff: &str,
         analysis: &DiffAnalysis,
         memory_context: &str,
+        language: &str,
     ) -> Result<String> {
         let mut prompt = String::new();
 
@@ -93,9 +171,18 @@ impl CommitAgent {
         prompt.push_str("严格要求：\n");
         prompt.push_str("1. type 必须是：feat, fix, docs, style, refactor, test, chore, perf, ci, build, revert\n");
         prompt.push_str("2. scope 可选，用括号包围，表示影响范围\n");
-        prompt.push_str("3. subject 必须是中文，简洁描述变更内容，不超过50字\n");
+        prompt.push_str(&format!(
+            "3. subject 必须是{}，简洁描述变更内容，不超过50字\n",
+            language
+        ));
         prompt.push_str("4. 禁止任何解释、分析或额外文字\n");
-        prompt.push_str("5. 只输出一行标准格式的提交消息\n\n");
+        prompt.push_str("5. 只输出一行标准格式的提交消息\n");
+        if docs::is_docs_only(diff) {
+            prompt.push_str("6. 本次变更只涉及文档或注释，type 必须是 docs\n");
+        } else if TestChanges::from_diff(diff).is_test_only() {
+            prompt.push_str("6. 本次变更只涉及测试代码，type 必须是 test\n");
+        }
+        prompt.push('\n');
 
         prompt.push_str("禁止输出的错误格式示例：\n");
         prompt.push_str("- \"添加依赖：test，作用域：Cargo\" ❌\n");
@@ -114,6 +201,17 @@ impl CommitAgent {
             prompt.push_str(&format!("- 推荐作用域：{}\n", scope));
         }
         prompt.push_str(&format!("- 文件变更：{} 个\n", analysis.total_files));
+        for rename in analysis.rename_descriptions() {
+            prompt.push_str(&format!("- 重命名/移动：{}\n", rename));
+        }
+        if let Some(noise) = analysis.noise_summary() {
+            prompt.push_str(&format!("- 省略内容的文件：{}\n", noise));
+        }
+        prompt.push_str(&LanguageSummary::from_diff(diff).to_prompt_context());
+        prompt.push_str(&SqlChanges::from_diff(diff).to_prompt_context());
+        prompt.push_str(&ContainerChanges::from_diff(diff).to_prompt_context());
+        prompt.push_str(&TerraformChanges::from_diff(diff).to_prompt_context());
+        prompt.push_str(&TestChanges::from_diff(diff).to_prompt_context());
 
         // 注入项目记忆上下文
         if !memory_context.is_empty() {
@@ -123,12 +221,13 @@ impl CommitAgent {
         prompt.push_str("\n现在直接输出符合格式的提交消息：\n\n");
         prompt.push_str("Diff 内容：\n");
 
-        // 如果 diff 太长，截取关键部分
-        if diff.len() > 5000 {
-            prompt.push_str(&diff.chars().take(5000).collect::<String>());
+        // 锁文件、二进制和生成代码只保留摘要；如果 diff 太长，截取关键部分
+        let (code_diff, _) = generated::split_noise(diff);
+        if code_diff.len() > 5000 {
+            prompt.push_str(&code_diff.chars().take(5000).collect::<String>());
             prompt.push_str("\n... (diff 内容已截断)");
         } else {
-            prompt.push_str(diff);
+            prompt.push_str(&code_diff);
         }
 
         Ok(prompt)
@@ -270,6 +369,32 @@ impl Agent for CommitAgent {
         Ok(result)
     }
 
+    async fn execute_streaming(
+        &self,
+        task: AgentTask,
+        context: &AgentContext,
+        chunks: tokio::sync::mpsc::UnboundedSender<String>,
+    ) -> Result<AgentResult> {
+        self.validate_task(&task)?;
+
+        if task.task_type != TaskType::GenerateCommit {
+            anyhow::bail!("Unsupported task type: {:?}", task.task_type);
+        }
+
+        let start_time = Instant::now();
+        let message = self
+            .stream_commit_message(&task.input, context, &chunks)
+            .await?;
+
+        Ok(AgentResult {
+            success: true,
+            content: message,
+            duration_ms: start_time.elapsed().as_millis() as u64,
+            tokens_used: None,
+            data: HashMap::new(),
+        })
+    }
+
     fn status(&self) -> AgentStatus {
         self.status.clone()
     }
@@ -321,6 +446,46 @@ mod tests {
         assert_eq!(agent.clean_commit_message(message), "feat(api): 添加功能");
     }
 
+    #[test]
+    fn test_finalize_forces_docs_type() {
+        let agent = CommitAgent::new();
+        let diff = "diff --git a/README.md b/README.md\n--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n-old\n+new\n";
+
+        let message = agent
+            .finalize_commit_message(diff, "feat(readme): 更新安装说明")
+            .unwrap();
+        assert_eq!(message, "docs(readme): 更新安装说明");
+
+        let code_diff = diff.replace("README.md", "src/main.rs");
+        let message = agent
+            .finalize_commit_message(&code_diff, "feat: 添加功能")
+            .unwrap();
+        assert_eq!(message, "feat: 添加功能");
+    }
+
+    #[test]
+    fn test_finalize_handles_test_changes() {
+        let agent = CommitAgent::new();
+        let test_diff = "diff --git a/tests/cli.rs b/tests/cli.rs\n--- a/tests/cli.rs\n+++ b/tests/cli.rs\n@@ -0,0 +1,2 @@\n+#[test]\n+fn runs() {}\n";
+
+        let message = agent
+            .finalize_commit_message(test_diff, "feat(cli): 添加命令行测试")
+            .unwrap();
+        assert_eq!(message, "test(cli): 添加命令行测试");
+
+        let mixed = format!(
+            "diff --git a/src/cli.rs b/src/cli.rs\n--- a/src/cli.rs\n+++ b/src/cli.rs\n@@ -1 +1 @@\n-fn run() {{}}\n+fn run(args: &[String]) {{}}\n{}",
+            test_diff
+        );
+        let message = agent
+            .finalize_commit_message(&mixed, "feat(cli): 支持命令行参数")
+            .unwrap();
+        assert_eq!(
+            message,
+            "feat(cli): 支持命令行参数\n\n测试：新增 1 个测试用例，修改 1 个测试文件"
+        );
+    }
+
     #[tokio::test]
     async fn test_commit_agent_task_validation() {
         let agent = CommitAgent::new();
@@ -331,4 +496,46 @@ mod tests {
         let invalid_task = AgentTask::new(TaskType::GenerateCommit, "");
         assert!(agent.validate_task(&invalid_task).is_err());
     }
+
+    /// 按固定片段流式返回的提供商
+    struct ChunkedProvider;
+
+    #[async_trait]
+    impl AIProvider for ChunkedProvider {
+        async fn generate(&self, _prompt: &str, _config: &ProviderConfig) -> Result<String> {
+            Ok("feat(tui): 支持流式生成".to_string())
+        }
+
+        async fn stream_generate(
+            &self,
+            _prompt: &str,
+            _config: &ProviderConfig,
+        ) -> Result<crate::core::ai::provider::StreamResponse> {
+            let chunks = vec![
+                Ok("feat(tui): ".to_string()),
+                Ok("支持流式生成".to_string()),
+            ];
+            Ok(Box::pin(futures_util::stream::iter(chunks)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_streaming_sends_chunks() {
+        let mut agent = CommitAgent::new();
+        agent.provider = Some(Arc::new(ChunkedProvider));
+        let context = AgentContext {
+            working_dir: std::env::temp_dir(),
+            env_vars: HashMap::new(),
+            config: AgentConfig::default(),
+            history: vec![],
+        };
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let task = AgentTask::new(TaskType::GenerateCommit, "diff --git a/x b/x");
+        let result = agent.execute_streaming(task, &context, tx).await.unwrap();
+
+        assert_eq!(result.content, "feat(tui): 支持流式生成");
+        assert_eq!(rx.recv().await.unwrap(), "feat(tui): ");
+        assert_eq!(rx.recv().await.unwrap(), "支持流式生成");
+    }
 }
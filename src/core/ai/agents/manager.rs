@@ -246,6 +246,8 @@ impl AgentManager {
             TaskType::GenerateCommit => "CommitAgent".to_string(),
             TaskType::GenerateTag => "TagAgent".to_string(),
             TaskType::ReviewCode => "ReviewAgent".to_string(),
+            TaskType::ExplainChanges => "ReviewAgent".to_string(),
+            TaskType::GeneratePrDescription => "ReviewAgent".to_string(),
             TaskType::RefactorSuggestion => "RefactorAgent".to_string(),
             TaskType::GenerateDocumentation => "TagAgent".to_string(), // TagAgent 也处理文档
             TaskType::GenerateTests => "ReviewAgent".to_string(),      // ReviewAgent 也生成测试
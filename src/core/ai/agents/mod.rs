@@ -141,6 +141,20 @@ pub trait Agent: Send + Sync {
     /// 执行任务
     async fn execute(&self, task: AgentTask, context: &AgentContext) -> Result<AgentResult>;
 
+    /// 以流式方式执行任务，生成过程中的文本片段通过 `chunks` 发送
+    ///
+    /// 默认实现执行完成后一次性发送完整结果。
+    async fn execute_streaming(
+        &self,
+        task: AgentTask,
+        context: &AgentContext,
+        chunks: tokio::sync::mpsc::UnboundedSender<String>,
+    ) -> Result<AgentResult> {
+        let result = self.execute(task, context).await?;
+        let _ = chunks.send(result.content.clone());
+        Ok(result)
+    }
+
     /// 验证任务是否可执行
     fn validate_task(&self, task: &AgentTask) -> Result<()> {
         // 默认验证：检查必需参数
@@ -223,6 +237,10 @@ pub enum TaskType {
     GenerateTag,
     /// 审查代码
     ReviewCode,
+    /// 解释变更（做了什么、为什么、有什么风险）
+    ExplainChanges,
+    /// 生成 Pull Request 描述
+    GeneratePrDescription,
     /// 重构建议
     RefactorSuggestion,
     /// 生成文档
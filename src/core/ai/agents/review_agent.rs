@@ -1,5 +1,10 @@
 use super::*;
+use crate::analysis::container::ContainerChanges;
+use crate::analysis::generated;
+use crate::analysis::migration::SqlChanges;
+use crate::analysis::terraform::TerraformChanges;
 use crate::core::ai::provider::{AIProvider, ProviderConfig};
+use crate::languages::LanguageSummary;
 use async_trait::async_trait;
 use std::sync::Arc;
 use std::time::Instant;
@@ -36,6 +41,12 @@ impl ReviewAgent {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("AI provider not initialized"))?;
 
+        // 锁文件、二进制和生成代码不交给 AI 审查，只列出摘要
+        let (code_diff, noise) = generated::split_noise(code);
+        let noise_context = generated::summarize(&noise)
+            .map(|summary| format!("\n省略内容的文件：{}\n", summary))
+            .unwrap_or_default();
+
         let prompt = format!(
             "请审查以下代码变更，按以下结构提供审查结果：\n\n\
             ## 严重问题\n\
@@ -47,12 +58,71 @@ impl ReviewAgent {
             ## 改进建议\n\
             列出代码风格、可读性和可维护性方面的改进建议。\n\n\
             ## 总结\n\
-            用 1-2 句话概括代码质量和主要发现。\n\n\
+            用 1-2 句话概括代码质量和主要发现。\n{}{}{}{}{}\n\
             代码变更：\n{}",
-            code
+            LanguageSummary::from_diff(code).to_review_context(),
+            SqlChanges::from_diff(code).to_review_context(),
+            ContainerChanges::from_diff(code).to_review_context(),
+            TerraformChanges::from_diff(code).to_review_context(),
+            noise_context,
+            code_diff
+        );
+
+        provider
+            .generate(&prompt, &Self::provider_config(context))
+            .await
+    }
+
+    async fn explain_changes(&self, diff: &str, context: &AgentContext) -> Result<String> {
+        let provider = self
+            .provider
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("AI provider not initialized"))?;
+
+        let prompt = format!(
+            "请阅读以下 Git 变更（可能包含提交信息），用通俗的语言解释这些变更，按以下结构输出：\n\n\
+            ## 变更内容\n\
+            概括修改了哪些模块、新增或删除了哪些行为。\n\n\
+            ## 可能的动机\n\
+            结合提交信息和代码推断这些变更的原因。不确定时请说明是推测。\n\n\
+            ## 潜在风险\n\
+            列出可能引入的回归、兼容性问题或需要额外测试的地方。如果没有，写「无」。\n\n\
+            变更内容：\n{}",
+            diff
+        );
+
+        provider
+            .generate(&prompt, &Self::provider_config(context))
+            .await
+    }
+
+    async fn generate_pr_description(&self, diff: &str, context: &AgentContext) -> Result<String> {
+        let provider = self
+            .provider
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("AI provider not initialized"))?;
+
+        let prompt = format!(
+            "请根据以下分支上的提交和代码变更撰写 Pull Request 描述。\n\
+            第一行输出 PR 标题（Conventional Commits 格式，如 feat(api): 添加用户认证，不超过 72 字符），\
+            空一行后按以下结构输出正文：\n\n\
+            ## 概述\n\
+            用 1-3 句话说明这个 PR 解决的问题和整体方案。\n\n\
+            ## 主要变更\n\
+            按模块列出关键改动。\n\n\
+            ## 测试说明\n\
+            说明如何验证这些变更，以及需要评审者重点关注的地方。\n\n\
+            变更内容：\n{}",
+            diff
         );
 
-        let provider_config = ProviderConfig {
+        provider
+            .generate(&prompt, &Self::provider_config(context))
+            .await
+    }
+
+    fn provider_config(context: &AgentContext) -> ProviderConfig {
+        ProviderConfig {
             model: context.config.model.clone(),
             api_key: context.env_vars.get("API_KEY").cloned(),
             api_url: context
@@ -63,9 +133,7 @@ impl ReviewAgent {
             timeout_secs: context.config.timeout_secs,
             max_retries: context.config.max_retries,
             stream: false,
-        };
-
-        provider.generate(&prompt, &provider_config).await
+        }
     }
 }
 
@@ -115,6 +183,28 @@ impl Agent for ReviewAgent {
                     data: HashMap::new(),
                 }
             }
+            TaskType::ExplainChanges => {
+                let explanation = self.explain_changes(&task.input, context).await?;
+
+                AgentResult {
+                    success: true,
+                    content: explanation,
+                    duration_ms: start_time.elapsed().as_millis() as u64,
+                    tokens_used: None,
+                    data: HashMap::new(),
+                }
+            }
+            TaskType::GeneratePrDescription => {
+                let description = self.generate_pr_description(&task.input, context).await?;
+
+                AgentResult {
+                    success: true,
+                    content: description,
+                    duration_ms: start_time.elapsed().as_millis() as u64,
+                    tokens_used: None,
+                    data: HashMap::new(),
+                }
+            }
             _ => {
                 anyhow::bail!("Unsupported task type: {:?}", task.task_type);
             }
@@ -105,6 +105,11 @@ impl TagAgent {
         Ok(version.to_string())
     }
 
+    /// 根据提交推断下一个版本号，不调用 AI
+    pub fn suggest_next_version(&self, current: &str, commits: &str) -> Result<String> {
+        self.generate_next_version(current, self.analyze_commits(commits))
+    }
+
     /// 生成发布说明
     async fn generate_release_notes(
         &self,
@@ -439,6 +444,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_suggest_next_version() {
+        let agent = TagAgent::new();
+        assert_eq!(
+            agent
+                .suggest_next_version(
+                    "0.4.1",
+                    "feat(tui): add tag dialog
+fix: typo"
+                )
+                .unwrap(),
+            "0.5.0"
+        );
+        assert!(agent.suggest_next_version("v1", "fix: typo").is_err());
+    }
+
     #[test]
     fn test_format_release_notes() {
         let agent = TagAgent::new();
@@ -1,3 +1,5 @@
+use crate::analysis::generated::{self, NoiseFile};
+use crate::analysis::{container, docs, migration, terraform, test_changes};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
@@ -17,6 +19,8 @@ static DELETION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^-").unwrap());
 #[derive(Debug, Clone)]
 pub struct FileChange {
     pub file_path: String,
+    /// 重命名或复制前的路径
+    pub old_path: Option<String>,
     pub additions: usize,
     pub deletions: usize,
     pub change_type: ChangeType,
@@ -28,6 +32,7 @@ pub enum ChangeType {
     Modified,
     Deleted,
     Renamed,
+    Copied,
 }
 
 #[derive(Debug)]
@@ -40,6 +45,8 @@ pub struct DiffAnalysis {
     pub is_multi_file: bool,
     pub primary_change_type: String,
     pub dominant_scope: Option<String>,
+    /// 二进制文件、锁文件和生成代码，不把内容交给 AI
+    pub noise_files: Vec<NoiseFile>,
 }
 
 impl DiffAnalysis {
@@ -47,50 +54,62 @@ impl DiffAnalysis {
         let mut file_changes = Vec::new();
         let mut total_additions = 0;
         let mut total_deletions = 0;
-        let mut current_file: Option<String> = None;
-        let mut current_additions = 0;
-        let mut current_deletions = 0;
+        let mut current: Option<FileChange> = None;
 
         for line in diff.lines() {
             if let Some(captures) = FILE_CHANGE_REGEX.captures(line) {
-                if let Some(file_path) = current_file.take() {
-                    file_changes.push(FileChange {
-                        file_path,
-                        additions: current_additions,
-                        deletions: current_deletions,
-                        change_type: Self::determine_change_type(
-                            current_additions,
-                            current_deletions,
-                        ),
-                    });
-                    current_additions = 0;
-                    current_deletions = 0;
+                file_changes.extend(current.take().map(Self::finish_file_change));
+                current = Some(FileChange {
+                    file_path: captures.get(2).unwrap().as_str().to_string(),
+                    old_path: None,
+                    additions: 0,
+                    deletions: 0,
+                    change_type: ChangeType::Modified,
+                });
+            } else if let Some(change) = current.as_mut() {
+                // `git diff -M -C` 输出的重命名和复制信息
+                if let Some(from) = line.strip_prefix("rename from ") {
+                    change.old_path = Some(from.to_string());
+                    change.change_type = ChangeType::Renamed;
+                } else if let Some(from) = line.strip_prefix("copy from ") {
+                    change.old_path = Some(from.to_string());
+                    change.change_type = ChangeType::Copied;
+                } else if ADDITION_REGEX.is_match(line) && !line.starts_with("+++") {
+                    change.additions += 1;
+                    total_additions += 1;
+                } else if DELETION_REGEX.is_match(line) && !line.starts_with("---") {
+                    change.deletions += 1;
+                    total_deletions += 1;
                 }
-                current_file = Some(captures.get(2).unwrap().as_str().to_string());
-            } else if ADDITION_REGEX.is_match(line) && !line.starts_with("+++") {
-                current_additions += 1;
-                total_additions += 1;
-            } else if DELETION_REGEX.is_match(line) && !line.starts_with("---") {
-                current_deletions += 1;
-                total_deletions += 1;
             }
         }
-
-        if let Some(file_path) = current_file {
-            file_changes.push(FileChange {
-                file_path,
-                additions: current_additions,
-                deletions: current_deletions,
-                change_type: Self::determine_change_type(current_additions, current_deletions),
-            });
-        }
+        file_changes.extend(current.map(Self::finish_file_change));
 
         let total_files = file_changes.len();
         let is_large_diff = diff.len() > LARGE_DIFF_THRESHOLD;
         let is_multi_file = total_files > MULTI_FILE_THRESHOLD;
 
-        let primary_change_type = Self::determine_primary_change_type(&file_changes);
-        let dominant_scope = Self::determine_dominant_scope(&file_changes);
+        let mut primary_change_type = Self::determine_primary_change_type(&file_changes);
+        let mut dominant_scope = Self::determine_dominant_scope(&file_changes);
+
+        // 只涉及数据库迁移或只涉及容器与部署清单时，按对应的规则推荐类型和作用域
+        if let Some((change_type, scope)) = Self::config_only_suggestion(diff, &file_changes) {
+            if let Some(change_type) = change_type {
+                primary_change_type = change_type.to_string();
+            }
+            dominant_scope = Some(scope.to_string());
+        }
+
+        if test_changes::TestChanges::from_diff(diff).is_test_only() {
+            primary_change_type = "test".to_string();
+        }
+
+        // 只修改文档或注释时固定为 docs，避免被判断为 feat/refactor
+        if docs::is_docs_only(diff) {
+            primary_change_type = "docs".to_string();
+        }
+
+        let (_, noise_files) = generated::split_noise(diff);
 
         DiffAnalysis {
             total_files,
@@ -101,7 +120,16 @@ impl DiffAnalysis {
             is_multi_file,
             primary_change_type,
             dominant_scope,
+            noise_files,
+        }
+    }
+
+    /// 非重命名/复制的文件按增删行数确定变更类型
+    fn finish_file_change(mut change: FileChange) -> FileChange {
+        if change.old_path.is_none() {
+            change.change_type = Self::determine_change_type(change.additions, change.deletions);
         }
+        change
     }
 
     fn determine_change_type(additions: usize, deletions: usize) -> ChangeType {
@@ -149,6 +177,7 @@ impl DiffAnalysis {
                 }
             }
             ChangeType::Renamed => "refactor".to_string(),
+            ChangeType::Copied => "feat".to_string(),
         }
     }
 
@@ -169,7 +198,51 @@ impl DiffAnalysis {
             .map(|(scope, _)| scope.clone())
     }
 
+    /// 所有变更文件都属于同一类专门配置（迁移/SQL、Terraform、容器与部署清单）时的推荐（类型，作用域）
+    fn config_only_suggestion(
+        diff: &str,
+        file_changes: &[FileChange],
+    ) -> Option<(Option<&'static str>, &'static str)> {
+        if file_changes.is_empty() {
+            return None;
+        }
+        let all = |matches: &dyn Fn(&str) -> bool| {
+            file_changes.iter().all(|change| matches(&change.file_path))
+        };
+
+        if all(&|path| migration::scope_for_path(path).is_some()) {
+            let sql = migration::SqlChanges::from_diff(diff);
+            return sql
+                .suggested_scope()
+                .map(|scope| (sql.suggested_type(), scope));
+        }
+        if all(&|path| terraform::is_terraform_file(path)) {
+            let infra = terraform::TerraformChanges::from_diff(diff);
+            return infra
+                .suggested_scope()
+                .map(|scope| (infra.suggested_type(), scope));
+        }
+        let containers = container::ContainerChanges::from_diff(diff);
+        if all(&|path| containers.files.iter().any(|(file, _)| file == path)) {
+            return containers
+                .suggested_scope()
+                .map(|scope| (containers.suggested_type(), scope));
+        }
+        None
+    }
+
     fn extract_scope_from_path(file_path: &str) -> Option<String> {
+        // JVM 源文件按包名、前端 monorepo 按包名、构建文件按 build 归类
+        if let Some(scope) = crate::languages::scope_for_path(file_path) {
+            return Some(scope);
+        }
+        if let Some(scope) = migration::scope_for_path(file_path)
+            .or_else(|| terraform::scope_for_path(file_path))
+            .or_else(|| container::scope_for_path(file_path))
+        {
+            return Some(scope.to_string());
+        }
+
         let path_parts: Vec<&str> = file_path.split('/').collect();
 
         if path_parts.len() >= 2 {
@@ -239,6 +312,59 @@ impl DiffAnalysis {
         summary_parts.join("，")
     }
 
+    /// 重命名、移动和复制的描述，同一目录下的多个文件一起移动时合并为一条
+    pub fn rename_descriptions(&self) -> Vec<String> {
+        let mut moved_dirs: Vec<(&str, &str, usize)> = Vec::new();
+        let mut descriptions = Vec::new();
+
+        for change in &self.file_changes {
+            let Some(old_path) = change.old_path.as_deref() else {
+                continue;
+            };
+            if change.change_type == ChangeType::Copied {
+                descriptions.push(format!("从 {} 复制为 {}", old_path, change.file_path));
+                continue;
+            }
+
+            let (old_dir, old_name) = old_path.rsplit_once('/').unwrap_or(("", old_path));
+            let (new_dir, new_name) = change
+                .file_path
+                .rsplit_once('/')
+                .unwrap_or(("", &change.file_path));
+            if old_name == new_name && old_dir != new_dir {
+                match moved_dirs
+                    .iter_mut()
+                    .find(|(from, to, _)| *from == old_dir && *to == new_dir)
+                {
+                    Some((_, _, count)) => *count += 1,
+                    None => moved_dirs.push((old_dir, new_dir, 1)),
+                }
+            } else {
+                descriptions.push(format!("将 {} 重命名为 {}", old_path, change.file_path));
+            }
+        }
+
+        let mut moves: Vec<String> = moved_dirs
+            .into_iter()
+            .map(|(from, to, count)| {
+                let from = if from.is_empty() { "." } else { from };
+                let to = if to.is_empty() { "." } else { to };
+                if count > 1 {
+                    format!("将模块 {} 移动到 {}（{} 个文件）", from, to, count)
+                } else {
+                    format!("将文件从 {} 移动到 {}", from, to)
+                }
+            })
+            .collect();
+        moves.append(&mut descriptions);
+        moves
+    }
+
+    /// 被剔除的二进制、锁文件和生成代码的摘要
+    pub fn noise_summary(&self) -> Option<String> {
+        generated::summarize(&self.noise_files)
+    }
+
     /// 为大型或多文件 diff 生成优化的 prompt
     pub fn create_optimized_prompt(&self, original_diff: &str) -> String {
         if !self.is_large_diff && !self.is_multi_file {
@@ -256,12 +382,17 @@ impl DiffAnalysis {
                 ChangeType::Modified => "修改",
                 ChangeType::Deleted => "删除",
                 ChangeType::Renamed => "重命名",
+                ChangeType::Copied => "复制",
+            };
+            let path = match &change.old_path {
+                Some(old_path) => format!("{} → {}", old_path, change.file_path),
+                None => change.file_path.clone(),
             };
             optimized_prompt.push_str(&format!(
                 "{}. {} {} (+{} -{} lines)\n",
                 i + 1,
                 change_type_desc,
-                change.file_path,
+                path,
                 change.additions,
                 change.deletions
             ));
@@ -271,12 +402,17 @@ impl DiffAnalysis {
             optimized_prompt.push_str(&format!("...还有{}个文件\n", self.file_changes.len() - 10));
         }
 
+        if let Some(noise) = self.noise_summary() {
+            optimized_prompt.push_str(&format!("省略内容的文件：{}\n", noise));
+        }
+
+        let (code_diff, _) = generated::split_noise(original_diff);
         if self.is_large_diff {
             optimized_prompt.push_str("\n核心变更片段：\n");
-            optimized_prompt.push_str(&Self::extract_key_changes(original_diff));
+            optimized_prompt.push_str(&Self::extract_key_changes(&code_diff));
         } else {
             optimized_prompt.push_str("\n完整diff：\n");
-            optimized_prompt.push_str(original_diff);
+            optimized_prompt.push_str(&code_diff);
         }
 
         optimized_prompt
@@ -409,6 +545,165 @@ index 1234567..abcdefg 100644
             DiffAnalysis::extract_scope_from_path("Cargo.toml"),
             Some("cargo".to_string())
         );
+        assert_eq!(
+            DiffAnalysis::extract_scope_from_path("src/main/java/com/acme/order/OrderService.java"),
+            Some("order".to_string())
+        );
+        assert_eq!(
+            DiffAnalysis::extract_scope_from_path("pom.xml"),
+            Some("build".to_string())
+        );
+        assert_eq!(
+            DiffAnalysis::extract_scope_from_path("migrations/20240101_add_users.sql"),
+            Some("migration".to_string())
+        );
+    }
+
+    #[test]
+    fn test_migration_only_diff() {
+        let diff = "diff --git a/migrations/2024-01-01-000000_create_users/up.sql b/migrations/2024-01-01-000000_create_users/up.sql
+new file mode 100644
+--- /dev/null
++++ b/migrations/2024-01-01-000000_create_users/up.sql
+@@ -0,0 +1 @@
++CREATE TABLE users (id SERIAL PRIMARY KEY);
+diff --git a/migrations/2024-01-01-000000_create_users/down.sql b/migrations/2024-01-01-000000_create_users/down.sql
+new file mode 100644
+--- /dev/null
++++ b/migrations/2024-01-01-000000_create_users/down.sql
+@@ -0,0 +1 @@
++DROP TABLE users;
+";
+        let analysis = DiffAnalysis::analyze_diff(diff);
+        assert_eq!(analysis.primary_change_type, "feat");
+        assert_eq!(analysis.dominant_scope, Some("db".to_string()));
+
+        let mixed = format!(
+            "{}diff --git a/src/db.rs b/src/db.rs\n--- a/src/db.rs\n+++ b/src/db.rs\n@@ -1 +1 @@\n-old\n+new\n",
+            diff
+        );
+        let analysis = DiffAnalysis::analyze_diff(&mixed);
+        assert_eq!(analysis.dominant_scope, Some("migration".to_string()));
+    }
+
+    #[test]
+    fn test_container_only_diff() {
+        let diff = "diff --git a/Dockerfile b/Dockerfile
+--- a/Dockerfile
++++ b/Dockerfile
+@@ -1 +1 @@
+-FROM node:18-alpine
++FROM node:20-alpine
+";
+        let analysis = DiffAnalysis::analyze_diff(diff);
+        assert_eq!(analysis.primary_change_type, "chore");
+        assert_eq!(analysis.dominant_scope, Some("docker".to_string()));
+    }
+
+    #[test]
+    fn test_terraform_only_diff() {
+        let diff = "diff --git a/infra/queues.tf b/infra/queues.tf
+--- a/infra/queues.tf
++++ b/infra/queues.tf
+@@ -0,0 +1,3 @@
++resource \"aws_sqs_queue\" \"events\" {
++  name = \"events\"
++}
+";
+        let analysis = DiffAnalysis::analyze_diff(diff);
+        assert_eq!(analysis.primary_change_type, "feat");
+        assert_eq!(analysis.dominant_scope, Some("infra".to_string()));
+    }
+
+    #[test]
+    fn test_test_only_diff() {
+        let diff = "diff --git a/pkg/user/user_test.go b/pkg/user/user_test.go
+--- a/pkg/user/user_test.go
++++ b/pkg/user/user_test.go
+@@ -0,0 +1,3 @@
++func TestCreateUser(t *testing.T) {
++\tCreateUser(\"a\")
++}
+";
+        let analysis = DiffAnalysis::analyze_diff(diff);
+        assert_eq!(analysis.primary_change_type, "test");
+    }
+
+    #[test]
+    fn test_rename_detection() {
+        let diff = "diff --git a/src/utils/mod.rs b/src/core/utils/mod.rs
+similarity index 100%
+rename from src/utils/mod.rs
+rename to src/core/utils/mod.rs
+diff --git a/src/utils/fs.rs b/src/core/utils/fs.rs
+similarity index 92%
+rename from src/utils/fs.rs
+rename to src/core/utils/fs.rs
+--- a/src/utils/fs.rs
++++ b/src/core/utils/fs.rs
+@@ -1 +1 @@
+-use crate::utils::path;
++use crate::core::utils::path;
+diff --git a/src/app.rs b/src/application.rs
+similarity index 100%
+rename from src/app.rs
+rename to src/application.rs
+diff --git a/templates/base.html b/templates/admin.html
+similarity index 80%
+copy from templates/base.html
+copy to templates/admin.html
+";
+        let analysis = DiffAnalysis::analyze_diff(diff);
+        assert_eq!(analysis.total_files, 4);
+        assert_eq!(analysis.total_additions, 1);
+        assert_eq!(analysis.file_changes[0].change_type, ChangeType::Renamed);
+        assert_eq!(
+            analysis.file_changes[1].old_path.as_deref(),
+            Some("src/utils/fs.rs")
+        );
+        assert_eq!(analysis.file_changes[3].change_type, ChangeType::Copied);
+        assert_eq!(analysis.primary_change_type, "refactor");
+        assert_eq!(
+            analysis.rename_descriptions(),
+            vec![
+                "将模块 src/utils 移动到 src/core/utils（2 个文件）",
+                "将 src/app.rs 重命名为 src/application.rs",
+                "从 templates/base.html 复制为 templates/admin.html",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_noise_files_summary() {
+        let diff = "diff --git a/Cargo.lock b/Cargo.lock
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -1 +1 @@
+-version = \"1.0.0\"
++version = \"1.0.1\"
+diff --git a/assets/logo.png b/assets/logo.png
+Binary files a/assets/logo.png and b/assets/logo.png differ
+";
+        let analysis = DiffAnalysis::analyze_diff(diff);
+        assert_eq!(analysis.total_files, 2);
+        assert_eq!(
+            analysis.noise_summary().as_deref(),
+            Some("更新锁文件 Cargo.lock；1 个二进制文件")
+        );
+    }
+
+    #[test]
+    fn test_docs_only_diff() {
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,2 @@
+-/// 旧的说明
++/// 新的说明
+ pub fn run() {}
+";
+        let analysis = DiffAnalysis::analyze_diff(diff);
+        assert_eq!(analysis.primary_change_type, "docs");
     }
 
     #[test]
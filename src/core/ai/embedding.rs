@@ -0,0 +1,184 @@
+//! 文本向量化（embedding）
+//!
+//! 根据提供商的接口地址推断 embedding 接口：Ollama 的 `/api/generate` 对应 `/api/embed`，
+//! OpenAI 兼容接口的 `/chat/completions` 对应 `/embeddings`。其他接口不支持时返回错误，
+//! 调用方退化为本地的词法向量。
+
+use crate::cache::ContentCache;
+use crate::core::ai::http::shared_client;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// embedding 请求超时时间
+const EMBEDDING_TIMEOUT_SECS: u64 = 30;
+
+/// embedding 接口的请求格式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmbeddingEndpoint {
+    /// Ollama `/api/embed`
+    Ollama(String),
+    /// OpenAI 兼容 `/embeddings`
+    OpenAI(String),
+}
+
+impl EmbeddingEndpoint {
+    /// 由对话接口地址推断 embedding 接口地址
+    pub fn from_chat_url(url: &str) -> Option<Self> {
+        let url = url.trim_end_matches('/');
+        for suffix in ["/api/generate", "/api/chat"] {
+            if let Some(base) = url.strip_suffix(suffix) {
+                return Some(EmbeddingEndpoint::Ollama(format!("{}/api/embed", base)));
+            }
+        }
+        url.strip_suffix("/chat/completions")
+            .map(|base| EmbeddingEndpoint::OpenAI(format!("{}/embeddings", base)))
+    }
+}
+
+#[derive(Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbeddingResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbeddingResponse {
+    data: Vec<OpenAIEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OpenAIEmbedding {
+    embedding: Vec<f32>,
+    #[serde(default)]
+    index: usize,
+}
+
+/// 调用 embedding 接口，返回与输入顺序一致的向量
+pub async fn embed(
+    endpoint: &EmbeddingEndpoint,
+    model: &str,
+    api_key: Option<&str>,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let url = match endpoint {
+        EmbeddingEndpoint::Ollama(url) | EmbeddingEndpoint::OpenAI(url) => url,
+    };
+    let mut builder = shared_client()
+        .post(url)
+        .json(&EmbeddingRequest {
+            model,
+            input: texts,
+        })
+        .timeout(Duration::from_secs(EMBEDDING_TIMEOUT_SECS));
+    if let Some(api_key) = api_key {
+        builder = builder.bearer_auth(api_key);
+    }
+
+    let response = builder.send().await?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        anyhow::bail!("Embedding request failed: {} - {}", status, text);
+    }
+
+    let embeddings = match endpoint {
+        EmbeddingEndpoint::Ollama(_) => {
+            response.json::<OllamaEmbeddingResponse>().await?.embeddings
+        }
+        EmbeddingEndpoint::OpenAI(_) => {
+            let mut data = response.json::<OpenAIEmbeddingResponse>().await?.data;
+            data.sort_by_key(|item| item.index);
+            data.into_iter().map(|item| item.embedding).collect()
+        }
+    };
+
+    if embeddings.len() != texts.len() {
+        anyhow::bail!(
+            "Embedding response has {} vectors for {} inputs",
+            embeddings.len(),
+            texts.len()
+        );
+    }
+    Ok(embeddings)
+}
+
+/// 带缓存的向量化：已缓存的文本不再请求接口，缓存以模型名和文本内容为键
+pub async fn embed_cached(
+    endpoint: &EmbeddingEndpoint,
+    model: &str,
+    api_key: Option<&str>,
+    texts: &[String],
+    cache: &mut ContentCache<Vec<f32>>,
+) -> Result<Vec<Vec<f32>>> {
+    let key = |text: &str| format!("{}\n{}", model, text);
+
+    let mut results: Vec<Option<Vec<f32>>> =
+        texts.iter().map(|text| cache.get(&key(text))).collect();
+    let missing: Vec<usize> = (0..texts.len()).filter(|&i| results[i].is_none()).collect();
+
+    if !missing.is_empty() {
+        let inputs: Vec<String> = missing.iter().map(|&i| texts[i].clone()).collect();
+        let embeddings = embed(endpoint, model, api_key, &inputs).await?;
+        for (i, embedding) in missing.into_iter().zip(embeddings) {
+            cache.insert(&key(&texts[i]), embedding.clone());
+            results[i] = Some(embedding);
+        }
+    }
+
+    Ok(results.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_from_chat_url() {
+        assert_eq!(
+            EmbeddingEndpoint::from_chat_url("http://localhost:11434/api/generate"),
+            Some(EmbeddingEndpoint::Ollama(
+                "http://localhost:11434/api/embed".to_string()
+            ))
+        );
+        assert_eq!(
+            EmbeddingEndpoint::from_chat_url("https://api.openai.com/v1/chat/completions"),
+            Some(EmbeddingEndpoint::OpenAI(
+                "https://api.openai.com/v1/embeddings".to_string()
+            ))
+        );
+        assert_eq!(
+            EmbeddingEndpoint::from_chat_url("https://api.anthropic.com/v1/messages"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embed_cached_skips_request_on_hit() {
+        let mut cache = ContentCache::in_memory();
+        cache.insert("model\nfn a()", vec![1.0, 0.0]);
+
+        // 地址不可用，全部命中缓存时不会发起请求
+        let endpoint = EmbeddingEndpoint::OpenAI("http://127.0.0.1:9/embeddings".to_string());
+        let embeddings = embed_cached(
+            &endpoint,
+            "model",
+            None,
+            &["fn a()".to_string()],
+            &mut cache,
+        )
+        .await
+        .unwrap();
+        assert_eq!(embeddings, vec![vec![1.0, 0.0]]);
+        assert_eq!(cache.hits(), 1);
+    }
+}
@@ -1,15 +1,17 @@
+use anyhow::Context;
 use once_cell::sync::Lazy;
-use reqwest::Client;
+use reqwest::{Certificate, Client, Proxy};
 use std::time::Duration;
 
-/// 全局共享 HTTP 客户端
+use crate::config::{file::expand_home, NetworkSection};
+
+/// 全局共享 HTTP 客户端，按配置文件 `[network]` 创建；设置无效时提示并使用默认设置
 static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
-    Client::builder()
-        .pool_max_idle_per_host(10)
-        .pool_idle_timeout(Duration::from_secs(30))
-        .timeout(Duration::from_secs(60))
-        .build()
-        .expect("Failed to create HTTP client")
+    let network = crate::config::Config::new().network;
+    build_client(&network).unwrap_or_else(|e| {
+        eprintln!("⚠️  Invalid [network] settings, using defaults: {:#}", e);
+        build_client(&NetworkSection::default()).expect("Failed to create HTTP client")
+    })
 });
 
 /// 获取共享的 HTTP 客户端引用
@@ -17,6 +19,43 @@ pub fn shared_client() -> &'static Client {
     &HTTP_CLIENT
 }
 
+/// 按网络设置创建 HTTP 客户端，未设置的项使用默认值
+pub fn build_client(network: &NetworkSection) -> anyhow::Result<Client> {
+    let keep_alive = network.keep_alive.unwrap_or(30);
+    let mut builder = Client::builder()
+        .pool_max_idle_per_host(if keep_alive == 0 { 0 } else { 10 })
+        .pool_idle_timeout(Duration::from_secs(keep_alive));
+
+    if let Some(timeout) = non_zero_secs(network.timeout.unwrap_or(60)) {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = network.connect_timeout.and_then(non_zero_secs) {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(proxy) = &network.proxy {
+        builder = builder.proxy(Proxy::all(proxy).context("Invalid proxy URL")?);
+    }
+    if let Some(path) = &network.ca_cert {
+        let path = expand_home(path);
+        let pem = std::fs::read(&path)
+            .with_context(|| format!("Failed to read CA certificate {}", path.display()))?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("Invalid CA certificate {}", path.display()))?;
+        if certs.is_empty() {
+            anyhow::bail!("No certificate found in {}", path.display());
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    builder.build().context("Failed to create HTTP client")
+}
+
+fn non_zero_secs(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,4 +66,31 @@ mod tests {
         let c2 = shared_client();
         assert!(std::ptr::eq(c1, c2));
     }
+
+    #[test]
+    fn test_build_client_from_network_settings() {
+        let network = NetworkSection {
+            proxy: Some("http://127.0.0.1:8080".to_string()),
+            connect_timeout: Some(5),
+            timeout: Some(0),
+            keep_alive: Some(0),
+            ..Default::default()
+        };
+        assert!(build_client(&network).is_ok());
+
+        let network = NetworkSection {
+            proxy: Some("not a url".to_string()),
+            ..Default::default()
+        };
+        assert!(build_client(&network).is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let ca_cert = dir.path().join("ca.pem");
+        std::fs::write(&ca_cert, "not a certificate").unwrap();
+        let network = NetworkSection {
+            ca_cert: Some(ca_cert),
+            ..Default::default()
+        };
+        assert!(build_client(&network).is_err());
+    }
 }
@@ -45,9 +45,8 @@ impl ProjectMemory {
 
     /// 获取项目记忆存储目录
     pub fn memory_dir(project_path: &Path) -> Result<PathBuf> {
-        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
         let hash = compute_project_hash(project_path);
-        let dir = home.join(".ai-commit").join("memory").join(hash);
+        let dir = crate::config::data_dir()?.join("memory").join(hash);
         Ok(dir)
     }
 
@@ -279,7 +278,7 @@ impl ProjectMemory {
 }
 
 /// 计算项目路径的短 hash
-fn compute_project_hash(path: &Path) -> String {
+pub(crate) fn compute_project_hash(path: &Path) -> String {
     use std::hash::{Hash, Hasher};
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     path.hash(&mut hasher);
@@ -287,7 +286,7 @@ fn compute_project_hash(path: &Path) -> String {
 }
 
 /// 解析 commit message 中的 type 和 scope
-fn parse_commit_parts(message: &str) -> Option<(String, Option<String>)> {
+pub(crate) fn parse_commit_parts(message: &str) -> Option<(String, Option<String>)> {
     let message = message.trim();
 
     // 匹配 type(scope): subject 或 type: subject
@@ -0,0 +1,213 @@
+//! 提交信息历史
+//!
+//! 每次确认提交后记录最终的提交信息、type、scope 和修改的文件，按项目保存在存储层的
+//! `history/commits/<项目哈希>.json`。再次修改相同或相邻的文件时，找出最近的相关提交信息，
+//! 作为上下文提供给 AI，同时在确认时作为快捷选项列出。
+
+use super::memory::{compute_project_hash, parse_commit_parts};
+use crate::storage::StorageManager;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// 每个项目保留的记录数
+const MAX_RECORDS: usize = 200;
+
+/// 一条已确认的提交信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MessageRecord {
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// 提交修改的文件
+    pub files: Vec<String>,
+    /// 用户是否修改过 AI 生成的信息
+    #[serde(default)]
+    pub edited: bool,
+    pub recorded_at: String,
+}
+
+impl MessageRecord {
+    /// 与给定文件的相关程度：相同文件计 2 分，同目录的文件计 1 分（根目录下的文件不算同目录）
+    fn relevance(&self, files: &[String]) -> usize {
+        let dirs: HashSet<&str> = self.files.iter().map(|f| parent_dir(f)).collect();
+        files
+            .iter()
+            .map(|file| {
+                if self.files.contains(file) {
+                    2
+                } else if !parent_dir(file).is_empty() && dirs.contains(parent_dir(file)) {
+                    1
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+}
+
+fn parent_dir(file: &str) -> &str {
+    file.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// 一个项目的提交信息历史，最近的在后
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MessageHistory {
+    pub records: Vec<MessageRecord>,
+}
+
+impl MessageHistory {
+    /// 读取项目的提交信息历史
+    pub async fn load(storage: &StorageManager, project_path: &Path) -> anyhow::Result<Self> {
+        let project = compute_project_hash(project_path);
+        Ok(storage
+            .load_message_history(&project)
+            .await?
+            .unwrap_or_default())
+    }
+
+    pub async fn save(&self, storage: &StorageManager, project_path: &Path) -> anyhow::Result<()> {
+        let project = compute_project_hash(project_path);
+        storage.save_message_history(&project, self).await
+    }
+
+    /// 记录一条已确认的提交信息
+    pub fn record(&mut self, message: &str, files: Vec<String>, edited: bool) {
+        let message = message.trim();
+        if message.is_empty() {
+            return;
+        }
+        let (commit_type, scope) = match parse_commit_parts(message) {
+            Some((commit_type, scope)) => (Some(commit_type), scope),
+            None => (None, None),
+        };
+        self.records.push(MessageRecord {
+            message: message.to_string(),
+            commit_type,
+            scope,
+            files,
+            edited,
+            recorded_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        });
+        if self.records.len() > MAX_RECORDS {
+            let excess = self.records.len() - MAX_RECORDS;
+            self.records.drain(..excess);
+        }
+    }
+
+    /// 修改过给定文件（或同目录文件）的最近提交信息，按相关程度排序，相同信息只保留一条
+    pub fn related(&self, files: &[String], limit: usize) -> Vec<&MessageRecord> {
+        let mut scored: Vec<(usize, usize, &MessageRecord)> = self
+            .records
+            .iter()
+            .enumerate()
+            .map(|(index, record)| (record.relevance(files), index, record))
+            .filter(|(score, _, _)| *score > 0)
+            .collect();
+        // 相关程度相同时较新的优先
+        scored.sort_by_key(|(score, index, _)| std::cmp::Reverse((*score, *index)));
+
+        let mut seen = HashSet::new();
+        scored
+            .into_iter()
+            .map(|(_, _, record)| record)
+            .filter(|record| seen.insert(record.message.as_str()))
+            .take(limit)
+            .collect()
+    }
+}
+
+/// 把相关的历史提交信息格式化为提示词上下文
+pub fn to_prompt_context(related: &[&MessageRecord]) -> String {
+    if related.is_empty() {
+        return String::new();
+    }
+    let mut context =
+        String::from("\n修改相同文件的近期提交信息（参考其 type、scope 和措辞，不要照抄）：\n");
+    for record in related {
+        let subject = record.message.lines().next().unwrap_or_default();
+        context.push_str(&format!("- {}\n", subject));
+    }
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::FileBackend;
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_related_messages_ranked_by_overlap() {
+        let mut history = MessageHistory::default();
+        history.record(
+            "feat(storage): add purge",
+            files(&["src/storage/mod.rs"]),
+            false,
+        );
+        history.record("docs: update readme", files(&["README.md"]), false);
+        history.record(
+            "fix(storage): handle missing dir",
+            files(&["src/storage/file.rs"]),
+            true,
+        );
+        history.record(
+            "feat(storage): add purge",
+            files(&["src/storage/mod.rs"]),
+            false,
+        );
+
+        let related = history.related(&files(&["src/storage/mod.rs"]), 5);
+        let messages: Vec<&str> = related.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "feat(storage): add purge",
+                "fix(storage): handle missing dir"
+            ]
+        );
+        assert_eq!(related[0].commit_type.as_deref(), Some("feat"));
+        assert_eq!(related[0].scope.as_deref(), Some("storage"));
+        assert!(history.related(&files(&["Cargo.toml"]), 5).is_empty());
+
+        let context = to_prompt_context(&related);
+        assert!(context.contains("- feat(storage): add purge"));
+        assert!(to_prompt_context(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_record_keeps_latest() {
+        let mut history = MessageHistory::default();
+        history.record("  ", Vec::new(), false);
+        assert!(history.records.is_empty());
+        for i in 0..MAX_RECORDS + 5 {
+            history.record(&format!("chore: change {}", i), Vec::new(), false);
+        }
+        assert_eq!(history.records.len(), MAX_RECORDS);
+        assert_eq!(history.records[0].message, "chore: change 5");
+    }
+
+    #[tokio::test]
+    async fn test_history_persisted_per_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = StorageManager::new(Box::new(FileBackend::new(dir.path().to_path_buf())));
+
+        let mut history = MessageHistory::default();
+        history.record("feat: first", files(&["a.rs"]), false);
+        history.save(&storage, Path::new("/repo/a")).await.unwrap();
+
+        let loaded = MessageHistory::load(&storage, Path::new("/repo/a"))
+            .await
+            .unwrap();
+        assert_eq!(loaded, history);
+        let other = MessageHistory::load(&storage, Path::new("/repo/b"))
+            .await
+            .unwrap();
+        assert!(other.records.is_empty());
+    }
+}
@@ -3,8 +3,10 @@ use std::sync::Arc;
 
 pub mod agents;
 pub mod diff_analyzer;
+pub mod embedding;
 pub mod http;
 pub mod memory;
+pub mod message_history;
 pub mod prompt;
 pub mod provider;
 pub mod providers;
@@ -64,7 +64,32 @@ impl ProviderFactory {
             "claude" => Ok(Box::new(ClaudeProvider::new())),
             "gemini" => Ok(Box::new(GeminiProvider::new())),
             "qwen" => Ok(Box::new(QwenProvider::new())),
-            _ => anyhow::bail!("Unknown AI provider: {}", name),
+            _ => match crate::config::ProviderRegistry::get_provider(name) {
+                Some(info) => Self::create_custom(info),
+                None => anyhow::bail!("Unknown AI provider: {}", name),
+            },
+        }
+    }
+
+    /// 按 API 格式创建配置文件中注册的自定义提供商
+    fn create_custom(info: &crate::config::ProviderInfo) -> Result<Box<dyn AIProvider>> {
+        use crate::config::ApiFormat;
+        use crate::core::ai::providers::{
+            ClaudeProvider, GatewayProvider, GeminiProvider, OllamaProvider,
+        };
+
+        match info.api_format {
+            ApiFormat::OpenAI => Ok(Box::new(GatewayProvider::new(
+                info.display_name.clone(),
+                info.auth.clone(),
+            ))),
+            ApiFormat::Ollama => Ok(Box::new(OllamaProvider::new())),
+            ApiFormat::Anthropic => Ok(Box::new(ClaudeProvider::new())),
+            ApiFormat::Google => Ok(Box::new(GeminiProvider::new())),
+            ApiFormat::Custom => anyhow::bail!(
+                "Provider {} uses an unsupported API format",
+                info.display_name
+            ),
         }
     }
 
@@ -0,0 +1,56 @@
+use crate::config::AuthStyle;
+use crate::core::ai::provider::{AIProvider, ProviderConfig, StreamResponse};
+use crate::core::ai::providers::openai_compat::OpenAICompatibleBase;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// 配置文件中注册的 OpenAI 兼容网关
+pub struct GatewayProvider {
+    base: OpenAICompatibleBase,
+    display_name: String,
+}
+
+impl GatewayProvider {
+    pub fn new(display_name: impl Into<String>, auth: AuthStyle) -> Self {
+        Self {
+            base: OpenAICompatibleBase::with_auth(auth),
+            display_name: display_name.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl AIProvider for GatewayProvider {
+    async fn generate(&self, prompt: &str, config: &ProviderConfig) -> Result<String> {
+        self.base
+            .generate_chat(prompt, config, &self.display_name, None)
+            .await
+    }
+
+    async fn stream_generate(
+        &self,
+        prompt: &str,
+        config: &ProviderConfig,
+    ) -> Result<StreamResponse> {
+        self.base
+            .stream_chat(prompt, config, &self.display_name, None)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_header_auth_requires_api_key() {
+        let provider = GatewayProvider::new("Gateway", AuthStyle::Header("api-key".to_string()));
+        let config = ProviderConfig {
+            api_url: "http://127.0.0.1:9/v1/chat/completions".to_string(),
+            api_key: None,
+            ..ProviderConfig::default()
+        };
+        let err = provider.generate("hi", &config).await.unwrap_err();
+        assert!(err.to_string().contains("Gateway API key is required"));
+    }
+}
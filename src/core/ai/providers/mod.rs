@@ -3,6 +3,7 @@ pub mod openai_compat;
 
 pub mod claude;
 pub mod deepseek;
+pub mod gateway;
 pub mod gemini;
 pub mod kimi;
 pub mod ollama;
@@ -12,6 +13,7 @@ pub mod siliconflow;
 
 pub use claude::ClaudeProvider;
 pub use deepseek::DeepseekProvider;
+pub use gateway::GatewayProvider;
 pub use gemini::GeminiProvider;
 pub use kimi::KimiProvider;
 pub use ollama::OllamaProvider;
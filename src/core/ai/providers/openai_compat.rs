@@ -1,3 +1,4 @@
+use crate::config::AuthStyle;
 use crate::core::ai::http::shared_client;
 use crate::core::ai::provider::{ProviderConfig, StreamResponse};
 use crate::core::ai::stream::map_sse_stream;
@@ -65,6 +66,7 @@ pub fn extract_chat_content(json_str: &str) -> Option<String> {
 /// Deepseek/Kimi/SiliconFlow/OpenAI/Qwen 等 OpenAI 兼容 API 均可复用。
 pub struct OpenAICompatibleBase {
     client: &'static Client,
+    auth: AuthStyle,
 }
 
 impl Default for OpenAICompatibleBase {
@@ -75,8 +77,14 @@ impl Default for OpenAICompatibleBase {
 
 impl OpenAICompatibleBase {
     pub fn new() -> Self {
+        Self::with_auth(AuthStyle::Bearer)
+    }
+
+    /// 使用指定的 API Key 传递方式，用于自定义网关
+    pub fn with_auth(auth: AuthStyle) -> Self {
         Self {
             client: shared_client(),
+            auth,
         }
     }
 
@@ -88,11 +96,6 @@ impl OpenAICompatibleBase {
         provider_name: &str,
         top_p: Option<f32>,
     ) -> Result<reqwest::Response> {
-        let api_key = config
-            .api_key
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("{} API key is required", provider_name))?;
-
         let request = ChatCompletionRequest {
             model: &config.model,
             messages: vec![ChatMessage {
@@ -105,10 +108,22 @@ impl OpenAICompatibleBase {
             top_p,
         };
 
-        let response = self
-            .client
-            .post(&config.api_url)
-            .bearer_auth(api_key)
+        let builder = self.client.post(&config.api_url);
+        let builder = match &self.auth {
+            AuthStyle::None => builder,
+            auth => {
+                let api_key = config
+                    .api_key
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("{} API key is required", provider_name))?;
+                match auth {
+                    AuthStyle::Header(header) => builder.header(header.as_str(), api_key.as_str()),
+                    _ => builder.bearer_auth(api_key),
+                }
+            }
+        };
+
+        let response = builder
             .json(&request)
             .timeout(Duration::from_secs(config.timeout_secs))
             .send()
@@ -53,6 +53,23 @@ pub fn validate_commit_message(message: &str) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// 把提交消息首行的 type 替换为指定类型（保留 scope 和描述），不符合格式时原样返回
+pub fn enforce_commit_type(message: &str, commit_type: &str) -> String {
+    let mut lines = message.lines();
+    let first_line = lines.next().unwrap_or("");
+    let Some(captures) = COMMIT_FORMAT_REGEX.captures(first_line) else {
+        return message.to_string();
+    };
+
+    let rest = &first_line[captures[1].len()..];
+    let mut result = format!("{}{}", commit_type, rest);
+    for line in lines {
+        result.push('\n');
+        result.push_str(line);
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,6 +119,19 @@ mod tests {
         assert!(!is_valid_commit_format("描述\nfeat(api): 添加功能"));
     }
 
+    #[test]
+    fn test_enforce_commit_type() {
+        assert_eq!(
+            enforce_commit_type("feat(readme): 补充安装说明", "docs"),
+            "docs(readme): 补充安装说明"
+        );
+        assert_eq!(
+            enforce_commit_type("refactor: 更新注释\n\n详细描述", "docs"),
+            "docs: 更新注释\n\n详细描述"
+        );
+        assert_eq!(enforce_commit_type("invalid", "docs"), "invalid");
+    }
+
     #[test]
     fn test_is_invalid_response() {
         assert!(is_invalid_response(""));
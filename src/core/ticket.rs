@@ -0,0 +1,142 @@
+/// 工单号支持模块
+/// 从分支名中识别工单号（如 `feature/PROJ-123-login` 中的 `PROJ-123`），并附加到 commit message
+use regex::Regex;
+
+/// 工单号在 commit message 中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TicketPosition {
+    /// 作为 `Refs: PROJ-123` trailer 追加到末尾
+    #[default]
+    Trailer,
+    /// 作为标题前缀：`PROJ-123 feat: ...`
+    Prefix,
+}
+
+impl TicketPosition {
+    /// 解析位置配置，无法识别时使用 trailer
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "prefix" => Self::Prefix,
+            _ => Self::Trailer,
+        }
+    }
+}
+
+/// trailer 使用的键名
+pub const TICKET_TRAILER_KEY: &str = "Refs";
+
+/// 按配置顺序用正则匹配分支名，返回第一个匹配到的工单号
+pub fn extract_ticket(branch: &str, patterns: &[String]) -> anyhow::Result<Option<String>> {
+    for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let regex = Regex::new(pattern)
+            .map_err(|e| anyhow::anyhow!("Invalid ticket pattern '{}': {}", pattern, e))?;
+        if let Some(m) = regex.find(branch) {
+            return Ok(Some(m.as_str().to_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// 将工单号附加到 commit message，消息中已包含该工单号时保持不变
+///
+/// 输入: `feat(api): 添加用户认证功能`, `PROJ-123`
+/// 输出 (trailer): `feat(api): 添加用户认证功能\n\nRefs: PROJ-123`
+/// 输出 (prefix):  `PROJ-123 feat(api): 添加用户认证功能`
+pub fn apply_ticket(message: &str, ticket: &str, position: TicketPosition) -> String {
+    if ticket.is_empty() || message.contains(ticket) {
+        return message.to_string();
+    }
+
+    match position {
+        TicketPosition::Prefix => format!("{} {}", ticket, message),
+        TicketPosition::Trailer => {
+            let body = message.trim_end();
+            // 已有 trailer 段落时直接追加到同一段落
+            let last_paragraph = body.rsplit("\n\n").next().unwrap_or_default();
+            if body.contains("\n\n") && is_trailer_block(last_paragraph) {
+                format!("{}\n{}: {}", body, TICKET_TRAILER_KEY, ticket)
+            } else {
+                format!("{}\n\n{}: {}", body, TICKET_TRAILER_KEY, ticket)
+            }
+        }
+    }
+}
+
+/// 判断段落是否全部由 `Key: value` 形式的 trailer 组成
+fn is_trailer_block(paragraph: &str) -> bool {
+    paragraph.lines().all(|line| {
+        line.split_once(": ").is_some_and(|(key, _)| {
+            !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_extract_ticket() {
+        let patterns = patterns(&[r"PROJ-\d+", r"[A-Z]+-\d+"]);
+
+        assert_eq!(
+            extract_ticket("feature/PROJ-123-login", &patterns).unwrap(),
+            Some("PROJ-123".to_string())
+        );
+        assert_eq!(
+            extract_ticket("fix/ENG-42", &patterns).unwrap(),
+            Some("ENG-42".to_string())
+        );
+        assert_eq!(extract_ticket("main", &patterns).unwrap(), None);
+        assert_eq!(extract_ticket("feature/PROJ-1", &[]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_extract_ticket_invalid_pattern() {
+        assert!(extract_ticket("feature/x", &patterns(&["("])).is_err());
+    }
+
+    #[test]
+    fn test_apply_ticket_trailer() {
+        assert_eq!(
+            apply_ticket("feat: add login", "PROJ-1", TicketPosition::Trailer),
+            "feat: add login\n\nRefs: PROJ-1"
+        );
+        assert_eq!(
+            apply_ticket(
+                "feat: add login\n\nSigned-off-by: A <a@b.c>",
+                "PROJ-1",
+                TicketPosition::Trailer
+            ),
+            "feat: add login\n\nSigned-off-by: A <a@b.c>\nRefs: PROJ-1"
+        );
+    }
+
+    #[test]
+    fn test_apply_ticket_prefix() {
+        assert_eq!(
+            apply_ticket("feat: add login", "PROJ-1", TicketPosition::Prefix),
+            "PROJ-1 feat: add login"
+        );
+    }
+
+    #[test]
+    fn test_apply_ticket_already_present() {
+        let message = "feat: add login (PROJ-1)";
+        assert_eq!(
+            apply_ticket(message, "PROJ-1", TicketPosition::Trailer),
+            message
+        );
+    }
+
+    #[test]
+    fn test_ticket_position_parse() {
+        assert_eq!(TicketPosition::parse("prefix"), TicketPosition::Prefix);
+        assert_eq!(TicketPosition::parse("Trailer"), TicketPosition::Trailer);
+        assert_eq!(TicketPosition::parse("other"), TicketPosition::Trailer);
+    }
+}
@@ -9,8 +9,17 @@ use ratatui::{
     },
     Frame,
 };
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
 use tokio::process::Command;
 
+/// diff 上下文行数的上限
+pub const MAX_CONTEXT_LINES: u32 = 20;
+/// 折叠区域两侧紧邻修改处保留显示的未修改行数
+const FOLD_EDGE_LINES: usize = 3;
+/// 折叠标记行的前缀
+const FOLD_MARKER_PREFIX: &str = "⋯ ";
+
 /// Diff 文件信息
 #[derive(Clone, Debug)]
 pub struct DiffFile {
@@ -47,12 +56,20 @@ pub struct DiffViewer {
     pub search_term: String,
     /// 是否显示文件列表
     pub show_file_list: bool,
+    /// 统一视图中是否显示修改块跳转列表
+    pub show_hunk_list: bool,
     /// 当前文件的修改块列表
     pub hunks: Vec<DiffHunk>,
     /// 当前选中的修改块索引
     pub current_hunk: usize,
     /// 上次渲染的视口高度（用于 clamp_scroll 计算）
     pub viewport_height: u16,
+    /// 生成 diff 时使用的选项
+    pub options: DiffOptions,
+    /// 当前文件中可折叠的未修改区域（`current_diff` 的行范围）及其是否已展开
+    pub folds: Vec<(Range<usize>, bool)>,
+    /// 折叠后用于显示的 diff 内容
+    display_diff: String,
 }
 
 /// Diff 修改块（hunk）
@@ -84,13 +101,50 @@ pub struct CommitInfo {
 }
 
 /// 查看模式
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum DiffViewMode {
     Split,      // 分屏查看
     Unified,    // 统一查看
     SideBySide, // 并排查看
 }
 
+/// diff 显示选项
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiffOptions {
+    /// 打开 diff 时的默认查看模式
+    pub view_mode: DiffViewMode,
+    /// 修改块上下文行数
+    pub context_lines: u32,
+    /// 忽略空白字符的变化
+    pub ignore_whitespace: bool,
+    /// 连续未修改行达到该数量时折叠，0 表示不折叠
+    pub fold_threshold: usize,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            view_mode: DiffViewMode::SideBySide, // 默认使用左右对比视图
+            context_lines: 3,
+            ignore_whitespace: false,
+            fold_threshold: 12,
+        }
+    }
+}
+
+impl DiffOptions {
+    /// 传给 `git show` 的参数
+    pub fn git_args(&self) -> Vec<String> {
+        let mut args = vec![format!("-U{}", self.context_lines)];
+        if self.ignore_whitespace {
+            args.push("--ignore-all-space".to_string());
+        }
+        args
+    }
+}
+
 /// Diff 行类型
 #[derive(Clone, Debug, PartialEq)]
 pub enum DiffLineType {
@@ -112,6 +166,11 @@ pub struct DiffLine {
 impl DiffViewer {
     /// 创建新的 Diff 查看器
     pub async fn new(commit_hash: &str) -> Result<Self> {
+        Self::with_options(commit_hash, DiffOptions::default()).await
+    }
+
+    /// 使用指定的 diff 选项创建查看器
+    pub async fn with_options(commit_hash: &str, options: DiffOptions) -> Result<Self> {
         // 首先验证提交是否存在
         let commit_exists = Command::new("git")
             .args(["rev-parse", "--verify", commit_hash])
@@ -135,12 +194,12 @@ impl DiffViewer {
         }
 
         let current_diff = if !files.is_empty() {
-            Self::load_file_diff(commit_hash, &files[0].path)
+            Self::load_file_diff(commit_hash, &files[0].path, &options)
                 .await
                 .unwrap_or_else(|e| format!("Failed to load diff: {}", e))
         } else {
             // 如果没有文件，尝试获取完整的提交diff
-            Self::load_commit_diff(commit_hash)
+            Self::load_commit_diff(commit_hash, &options)
                 .await
                 .unwrap_or_else(|e| format!("No files changed in this commit. Error: {}", e))
         };
@@ -153,18 +212,23 @@ impl DiffViewer {
             file_list_state,
             current_diff,
             diff_scroll: 0,
-            view_mode: DiffViewMode::SideBySide, // 默认使用左右对比视图
+            view_mode: options.view_mode.clone(),
             syntax_highlight: true,
             search_mode: false,
             search_term: String::new(),
             show_file_list: true, // 默认显示文件列表
+            show_hunk_list: true,
             hunks: Vec::new(),
             current_hunk: 0,
             viewport_height: 40, // 合理默认值，渲染时会更新
+            options,
+            folds: Vec::new(),
+            display_diff: String::new(),
         };
 
         // 解析当前文件的修改块
         viewer.parse_hunks();
+        viewer.refresh_folds();
 
         Ok(viewer)
     }
@@ -261,7 +325,11 @@ impl DiffViewer {
     }
 
     /// 加载单个文件的 diff
-    async fn load_file_diff(commit_hash: &str, file_path: &str) -> Result<String> {
+    async fn load_file_diff(
+        commit_hash: &str,
+        file_path: &str,
+        options: &DiffOptions,
+    ) -> Result<String> {
         let output = Command::new("git")
             .args(["show", &format!("{}:{}", commit_hash, file_path)])
             .output()
@@ -271,7 +339,9 @@ impl DiffViewer {
             Ok(result) if result.status.success() => {
                 // 如果可以显示文件内容，则获取完整的diff
                 let diff_output = Command::new("git")
-                    .args(["show", "--format=", commit_hash, "--", file_path])
+                    .args(["show", "--format="])
+                    .args(options.git_args())
+                    .args([commit_hash, "--", file_path])
                     .output()
                     .await?;
 
@@ -284,7 +354,9 @@ impl DiffViewer {
             _ => {
                 // 如果文件不存在，可能是新增或删除的文件
                 let diff_output = Command::new("git")
-                    .args(["show", "--format=", commit_hash, "--", file_path])
+                    .args(["show", "--format="])
+                    .args(options.git_args())
+                    .args([commit_hash, "--", file_path])
                     .output()
                     .await?;
 
@@ -294,9 +366,11 @@ impl DiffViewer {
     }
 
     /// 加载完整提交的 diff
-    async fn load_commit_diff(commit_hash: &str) -> Result<String> {
+    async fn load_commit_diff(commit_hash: &str, options: &DiffOptions) -> Result<String> {
         let output = Command::new("git")
-            .args(["show", "--format=", commit_hash])
+            .args(["show", "--format="])
+            .args(options.git_args())
+            .arg(commit_hash)
             .output()
             .await?;
 
@@ -334,10 +408,22 @@ impl DiffViewer {
         self.diff_scroll = 0;
     }
 
+    /// 选中指定路径的文件并加载其 diff，找不到时返回 false
+    pub async fn select_file(&mut self, path: &str) -> bool {
+        let Some(index) = self.files.iter().position(|f| f.path == path) else {
+            return false;
+        };
+        self.selected_file = index;
+        self.file_list_state.select(Some(index));
+        self.diff_scroll = 0;
+        self.load_current_file_diff().await;
+        true
+    }
+
     /// 加载当前选中文件的 diff
     pub async fn load_current_file_diff(&mut self) {
         if let Some(file) = self.files.get(self.selected_file) {
-            match Self::load_file_diff(&self.commit_hash, &file.path).await {
+            match Self::load_file_diff(&self.commit_hash, &file.path, &self.options).await {
                 Ok(diff) => {
                     self.current_diff = diff;
                     self.parse_hunks();
@@ -345,7 +431,143 @@ impl DiffViewer {
                 }
                 Err(e) => self.current_diff = format!("Error loading diff: {}", e),
             }
+            self.refresh_folds();
+        }
+    }
+
+    /// 修改上下文行数并重新加载当前文件的 diff
+    pub async fn set_context_lines(&mut self, lines: u32) {
+        let lines = lines.min(MAX_CONTEXT_LINES);
+        if lines == self.options.context_lines {
+            return;
+        }
+        self.options.context_lines = lines;
+        if self.files.is_empty() {
+            if let Ok(diff) = Self::load_commit_diff(&self.commit_hash, &self.options).await {
+                self.current_diff = diff;
+                self.parse_hunks();
+                self.current_hunk = 0;
+                self.refresh_folds();
+            }
+        } else {
+            self.load_current_file_diff().await;
+        }
+        self.clamp_scroll();
+    }
+
+    /// 折叠后用于显示的 diff 内容
+    pub fn display_diff(&self) -> &str {
+        &self.display_diff
+    }
+
+    /// 重新计算当前文件的折叠区域，全部收起
+    fn refresh_folds(&mut self) {
+        let lines: Vec<&str> = self.current_diff.lines().collect();
+        self.folds = compute_folds(&lines, self.options.fold_threshold)
+            .into_iter()
+            .map(|range| (range, false))
+            .collect();
+        self.rebuild_display_diff();
+    }
+
+    /// 根据折叠状态生成显示内容
+    fn rebuild_display_diff(&mut self) {
+        if self.folds.iter().all(|(_, expanded)| *expanded) {
+            self.display_diff = self.current_diff.clone();
+            return;
+        }
+
+        let mut display = Vec::new();
+        let mut folds = self
+            .folds
+            .iter()
+            .filter(|(_, expanded)| !expanded)
+            .peekable();
+        for (index, line) in self.current_diff.lines().enumerate() {
+            match folds.peek() {
+                Some((range, _)) if range.contains(&index) => {
+                    if index == range.start {
+                        display.push(fold_marker(range.len()));
+                    }
+                    if index + 1 == range.end {
+                        folds.next();
+                    }
+                }
+                _ => display.push(line.to_string()),
+            }
+        }
+        self.display_diff = display.join("\n");
+    }
+
+    /// 原始 diff 行号对应的显示行号
+    fn display_line(&self, raw_line: usize) -> usize {
+        let hidden: usize = self
+            .folds
+            .iter()
+            .filter(|(range, expanded)| !expanded && range.end <= raw_line)
+            .map(|(range, _)| range.len() - 1)
+            .sum();
+        raw_line - hidden
+    }
+
+    /// 原始 diff 行号对应的滚动位置，统一视图不显示提交元数据和 index/---/+++ 行
+    fn scroll_line(&self, raw_line: usize) -> usize {
+        let display_line = self.display_line(raw_line);
+        if self.view_mode != DiffViewMode::Unified {
+            return display_line;
+        }
+
+        let mut in_diff = false;
+        let mut rendered = 0;
+        for line in self.display_diff.lines().take(display_line) {
+            if line.starts_with("diff --git") {
+                in_diff = true;
+            }
+            let skipped = line.is_empty()
+                || line.starts_with("index")
+                || line.starts_with("---")
+                || line.starts_with("+++");
+            if in_diff && !skipped {
+                rendered += 1;
+            }
+        }
+        rendered
+    }
+
+    /// 收起的折叠区域数量
+    pub fn collapsed_fold_count(&self) -> usize {
+        self.folds.iter().filter(|(_, expanded)| !expanded).count()
+    }
+
+    /// 展开视口中第一个收起的区域，视口下方没有时展开上方最近的一个
+    pub fn expand_fold(&mut self) {
+        let scroll = self.diff_scroll as usize;
+        let collapsed: Vec<(usize, usize)> = self
+            .folds
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, expanded))| !expanded)
+            .map(|(i, (range, _))| (i, self.display_line(range.start)))
+            .collect();
+        let target = collapsed
+            .iter()
+            .find(|(_, line)| *line >= scroll)
+            .or_else(|| collapsed.last())
+            .map(|(i, _)| *i);
+        if let Some(index) = target {
+            self.folds[index].1 = true;
+            self.rebuild_display_diff();
+        }
+    }
+
+    /// 全部展开；已全部展开时重新全部收起
+    pub fn toggle_all_folds(&mut self) {
+        let expand = self.folds.iter().any(|(_, expanded)| !expanded);
+        for fold in &mut self.folds {
+            fold.1 = expand;
         }
+        self.rebuild_display_diff();
+        self.clamp_scroll();
     }
 
     /// 切换视图模式
@@ -600,24 +822,67 @@ impl DiffViewer {
         }
     }
 
+    /// 跳转到包含新文件第 `line` 行的修改块，没有时跳到它之前最近的一块
+    pub fn jump_to_line(&mut self, line: u32) {
+        if let Some(index) = self.hunks.iter().rposition(|hunk| hunk.new_start <= line) {
+            self.current_hunk = index;
+            self.scroll_to_current_hunk();
+        }
+    }
+
     /// 滚动到当前选中的修改块
     fn scroll_to_current_hunk(&mut self) {
         if let Some(hunk) = self.hunks.get(self.current_hunk) {
             // 将当前 hunk 滚动到视图中央
-            self.diff_scroll = hunk.start_line.saturating_sub(5) as u16;
+            self.diff_scroll = self.scroll_line(hunk.start_line).saturating_sub(5) as u16;
         }
         self.clamp_scroll();
     }
 
     /// 限制 diff_scroll 不超过内容可滚动范围
     pub fn clamp_scroll(&mut self) {
-        let total_lines = self.current_diff.lines().count() as u16;
+        let total_lines = self.display_diff.lines().count() as u16;
         let max = total_lines.saturating_sub(self.viewport_height);
         if self.diff_scroll > max {
             self.diff_scroll = max;
         }
     }
 
+    /// 获取当前修改块的文本（包含 `@@` 头）
+    pub fn current_hunk_text(&self) -> Option<String> {
+        let hunk = self.hunks.get(self.current_hunk)?;
+        let lines: Vec<&str> = self
+            .current_diff
+            .lines()
+            .skip(hunk.start_line)
+            .take(hunk.end_line + 1 - hunk.start_line)
+            .collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// 获取当前选中文件的路径
+    pub fn selected_file_path(&self) -> Option<&str> {
+        self.files.get(self.selected_file).map(|f| f.path.as_str())
+    }
+
+    /// 修改块跳转列表的条目：新文件起始行和 `@@` 之后的上下文
+    pub fn hunk_labels(&self) -> Vec<String> {
+        self.hunks
+            .iter()
+            .map(|hunk| {
+                let context = hunk
+                    .header
+                    .splitn(3, "@@")
+                    .nth(2)
+                    .map(str::trim)
+                    .unwrap_or_default();
+                format!("+{} {}", hunk.new_start, context)
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
     /// 获取当前修改块信息（用于状态栏显示）
     pub fn current_hunk_info(&self) -> String {
         if self.hunks.is_empty() {
@@ -628,6 +893,64 @@ impl DiffViewer {
     }
 }
 
+/// 生成折叠标记行
+pub fn fold_marker(hidden: usize) -> String {
+    format!(
+        "{}{} unchanged lines (press o to expand)",
+        FOLD_MARKER_PREFIX, hidden
+    )
+}
+
+/// 解析折叠标记行，返回被折叠的行数
+pub fn parse_fold_marker(line: &str) -> Option<usize> {
+    line.strip_prefix(FOLD_MARKER_PREFIX)?
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// 找出可折叠的连续未修改行，紧邻修改处的行保持可见
+fn compute_folds(lines: &[&str], threshold: usize) -> Vec<Range<usize>> {
+    let is_change = |line: &str| {
+        (line.starts_with('+') && !line.starts_with("+++"))
+            || (line.starts_with('-') && !line.starts_with("---"))
+    };
+
+    let mut folds = Vec::new();
+    if threshold == 0 {
+        return folds;
+    }
+
+    let mut in_hunk = false;
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        if line.starts_with("@@") {
+            in_hunk = true;
+        } else if line.starts_with("diff --git") {
+            in_hunk = false;
+        }
+        if !in_hunk || !line.starts_with(' ') {
+            index += 1;
+            continue;
+        }
+
+        let start = index;
+        while index < lines.len() && lines[index].starts_with(' ') {
+            index += 1;
+        }
+        let change_before = start > 0 && is_change(lines[start - 1]);
+        let change_after = index < lines.len() && is_change(lines[index]);
+        let hidden_start = start + if change_before { FOLD_EDGE_LINES } else { 0 };
+        let hidden_end = index.saturating_sub(if change_after { FOLD_EDGE_LINES } else { 0 });
+        if hidden_end > hidden_start && hidden_end - hidden_start >= threshold {
+            folds.push(hidden_start..hidden_end);
+        }
+    }
+    folds
+}
+
 /// 解析 hunk header 获取行号
 fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
     // 解析 @@ -10,5 +10,8 @@ 格式
@@ -1187,3 +1510,81 @@ fn render_secondary_menu_bar(f: &mut Frame, area: Rect, viewer: &DiffViewer) {
 
     f.render_widget(menu, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_folds_keeps_edges_next_to_changes() {
+        let mut lines = vec!["diff --git a/f b/f", "@@ -1,30 +1,30 @@"];
+        lines.extend(std::iter::repeat_n(" same", 14));
+        lines.push("-old");
+        lines.push("+new");
+        lines.extend(std::iter::repeat_n(" same", 14));
+
+        // 开头的区域只保留靠近修改的 3 行，结尾同理
+        assert_eq!(compute_folds(&lines, 10), vec![2..13, 21..32]);
+        assert!(compute_folds(&lines, 12).is_empty());
+        assert!(compute_folds(&lines, 0).is_empty());
+    }
+
+    fn viewer_with_diff(diff: &str) -> DiffViewer {
+        let mut viewer = DiffViewer {
+            commit_hash: "abc".to_string(),
+            commit_info: CommitInfo {
+                hash: "abc".to_string(),
+                author: String::new(),
+                date: Local::now(),
+                message: String::new(),
+            },
+            files: Vec::new(),
+            selected_file: 0,
+            file_list_state: ListState::default(),
+            current_diff: diff.to_string(),
+            diff_scroll: 0,
+            view_mode: DiffViewMode::Unified,
+            syntax_highlight: false,
+            search_mode: false,
+            search_term: String::new(),
+            show_file_list: false,
+            show_hunk_list: true,
+            hunks: Vec::new(),
+            current_hunk: 0,
+            viewport_height: 1,
+            options: DiffOptions::default(),
+            folds: Vec::new(),
+            display_diff: String::new(),
+        };
+        viewer.parse_hunks();
+        viewer.refresh_folds();
+        viewer
+    }
+
+    #[test]
+    fn test_hunk_jump_targets_rendered_unified_lines() {
+        let mut viewer = viewer_with_diff(
+            "diff --git a/f b/f\nindex 1..2 100644\n--- a/f\n+++ b/f\n\
+             @@ -1,2 +1,2 @@ fn first\n a\n-b\n+c\n@@ -10,2 +10,2 @@\n x\n-y\n+z",
+        );
+
+        assert_eq!(viewer.hunks.len(), 2);
+        // 统一视图跳过 index/---/+++ 行，第二个修改块渲染在第 5 行
+        assert_eq!(viewer.scroll_line(viewer.hunks[1].start_line), 5);
+        assert_eq!(viewer.hunk_labels(), vec!["+1 fn first", "+10"]);
+
+        // 按新文件行号跳转到所在的修改块
+        viewer.jump_to_line(11);
+        assert_eq!(viewer.current_hunk, 1);
+        viewer.jump_to_line(2);
+        assert_eq!(viewer.current_hunk, 0);
+    }
+
+    #[test]
+    fn test_fold_marker_round_trip() {
+        let marker = fold_marker(120);
+        assert_eq!(marker, "⋯ 120 unchanged lines (press o to expand)");
+        assert_eq!(parse_fold_marker(&marker), Some(120));
+        assert_eq!(parse_fold_marker(" context"), None);
+    }
+}
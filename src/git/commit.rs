@@ -4,7 +4,9 @@ use tokio::process::Command;
 pub async fn git_status_and_diff() -> anyhow::Result<(String, String)> {
     let (status_result, diff_result) = tokio::join!(
         Command::new("git").args(["status", "--porcelain"]).output(),
-        Command::new("git").args(["diff", "--cached"]).output()
+        Command::new("git")
+            .args(["diff", "--cached", "-M", "-C"])
+            .output()
     );
 
     let status_output =
@@ -43,6 +45,25 @@ pub async fn git_add_all() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// 暂存指定路径（包括删除和重命名）
+pub async fn git_add_paths(paths: &[String]) -> anyhow::Result<()> {
+    if paths.is_empty() {
+        return Ok(());
+    }
+
+    let status = Command::new("git")
+        .args(["add", "-A", "--"])
+        .args(paths)
+        .status()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run git add: {}", e))?;
+
+    if !status.success() {
+        anyhow::bail!("Git add failed with exit code: {:?}", status.code());
+    }
+    Ok(())
+}
+
 pub async fn git_commit(message: &str) -> anyhow::Result<()> {
     let status = Command::new("git")
         .args(["commit", "-m", message])
@@ -113,9 +134,10 @@ pub async fn git_force_push() -> anyhow::Result<()> {
     git_push().await
 }
 
+/// 获取暂存区的 diff，开启重命名和复制检测，移动文件不会显示为整文件的删除加新增
 pub async fn get_git_diff() -> anyhow::Result<String> {
     let output = Command::new("git")
-        .args(["diff", "--cached"])
+        .args(["diff", "--cached", "-M", "-C"])
         .output()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to run git diff: {}", e))?;
@@ -127,11 +149,71 @@ pub async fn get_git_diff() -> anyhow::Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// 获取提交或提交范围（如 `main..feature`）的提交信息和补丁
+pub async fn get_revision_diff(revision: &str) -> anyhow::Result<String> {
+    let args: Vec<&str> = if revision.contains("..") {
+        vec![
+            "log",
+            "--reverse",
+            "-M",
+            "-C",
+            "--stat",
+            "--patch",
+            revision,
+        ]
+    } else {
+        vec!["show", "-M", "-C", "--stat", "--patch", revision]
+    };
+
+    let output = Command::new("git")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run git {}: {}", args[0], e))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Git {} failed for '{}': {}",
+            args[0],
+            revision,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 获取提交或提交范围合并后的补丁（范围内多次修改同一文件只出现一次）
+pub async fn get_combined_diff(revision: &str) -> anyhow::Result<String> {
+    let args: Vec<&str> = if revision.contains("..") {
+        vec!["diff", "-M", "-C", revision]
+    } else {
+        vec!["show", "-M", "-C", "--format=", "--patch", revision]
+    };
+
+    let output = Command::new("git")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run git {}: {}", args[0], e))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Git {} failed for '{}': {}",
+            args[0],
+            revision,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 /// 获取所有变更（包括未暂存的工作区变更）用于 AI commit
 pub async fn get_all_changes_diff() -> anyhow::Result<String> {
     // 首先检查是否有暂存的变更
     let staged_output = Command::new("git")
-        .args(["diff", "--cached"])
+        .args(["diff", "--cached", "-M", "-C"])
         .output()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to run git diff --cached: {}", e))?;
@@ -145,7 +227,7 @@ pub async fn get_all_changes_diff() -> anyhow::Result<String> {
 
     // 没有暂存变更，获取工作区变更
     let unstaged_output = Command::new("git")
-        .args(["diff"])
+        .args(["diff", "-M", "-C"])
         .output()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to run git diff: {}", e))?;
@@ -248,6 +248,47 @@ impl GitCore {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
+    /// 把分支名、tag、短 SHA 等解析为完整的提交 SHA，无法解析时返回 `None`
+    pub async fn resolve_commit(rev: &str) -> anyhow::Result<Option<String>> {
+        let output = Command::new("git")
+            .args([
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{}^{{commit}}", rev),
+            ])
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to resolve commit {}: {}", rev, e))?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    /// 获取仓库根目录
+    pub async fn get_repo_root() -> anyhow::Result<std::path::PathBuf> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get repository root: {}", e))?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Git rev-parse command failed with exit code: {:?}",
+                output.status.code()
+            );
+        }
+
+        Ok(std::path::PathBuf::from(
+            String::from_utf8_lossy(&output.stdout).trim(),
+        ))
+    }
+
     /// 检查提交是否存在
     pub async fn commit_exists(commit_hash: &str) -> anyhow::Result<bool> {
         let output = Command::new("git")
@@ -283,6 +324,25 @@ impl GitCore {
         Ok(remotes)
     }
 
+    /// 获取远程仓库的 URL
+    pub async fn get_remote_url(remote: &str) -> anyhow::Result<String> {
+        let output = Command::new("git")
+            .args(["remote", "get-url", remote])
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to get remote url: {}", e))?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Git remote get-url failed for '{}': {}",
+                remote,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
     /// 创建新分支
     pub async fn create_branch(branch: &str, base: Option<&str>) -> anyhow::Result<()> {
         let mut args = vec!["branch", branch];
@@ -360,9 +420,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_git_repo_check() {
-        let result = GitCore::is_git_repo().await;
-        // 在 git 仓库中应该返回 true，否则返回 false
-        assert!(result == true || result == false);
+        // 在 git 仓库中应该返回 true，否则返回 false，这里只验证调用不会 panic
+        let _is_repo: bool = GitCore::is_git_repo().await;
     }
 
     #[tokio::test]
@@ -397,6 +456,19 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_resolve_commit() {
+        if let Ok(Some(hash)) = GitCore::resolve_commit("HEAD").await {
+            assert_eq!(hash.len(), 40);
+        }
+        assert_eq!(
+            GitCore::resolve_commit("no-such-ref-for-ai-commit")
+                .await
+                .unwrap_or_default(),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn test_get_latest_commit_hash() {
         let result = GitCore::get_latest_commit_hash().await;
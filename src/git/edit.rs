@@ -797,10 +797,7 @@ mod tests {
             .await;
 
         match output {
-            Ok(o) => assert!(
-                !o.status.success(),
-                "git log should fail in non-git dir"
-            ),
+            Ok(o) => assert!(!o.status.success(), "git log should fail in non-git dir"),
             Err(e) => println!("Command failed as expected: {}", e),
         }
     }
@@ -630,6 +630,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::double_comparisons, clippy::assertions_on_constants)]
     fn test_limit_parameter_validation() {
         // Test limit parameter edge cases
         let valid_limits = vec![Some(1), Some(5), Some(10), Some(100), None];
@@ -661,10 +662,7 @@ mod tests {
             .await;
 
         match output {
-            Ok(o) => assert!(
-                !o.status.success(),
-                "git log should fail in non-git dir"
-            ),
+            Ok(o) => assert!(!o.status.success(), "git log should fail in non-git dir"),
             Err(e) => println!("Command failed as expected: {}", e),
         }
     }
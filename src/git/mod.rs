@@ -6,14 +6,16 @@ pub mod flow;
 pub mod history;
 pub mod hooks;
 pub mod query;
+pub mod signature;
 pub mod tag;
 pub mod watcher;
 pub mod worktree;
 
 // commit: 异步 git 操作函数
 pub use commit::{
-    get_all_changes_diff, get_git_diff, git_add_all, git_commit, git_commit_allow_empty,
-    git_force_push, git_push, git_status_and_diff,
+    get_all_changes_diff, get_combined_diff, get_git_diff, get_revision_diff, git_add_all,
+    git_add_paths, git_commit, git_commit_allow_empty, git_force_push, git_push,
+    git_status_and_diff,
 };
 
 // core: 基础 Git 操作
@@ -37,6 +39,9 @@ pub use query::{GitQuery, QueryFilter};
 // watcher: 仓库监听
 pub use watcher::{ChangeEvent, ChangeType, GitWatcher, RepoStatus};
 
+// signature: 提交签名校验
+pub use signature::{CommitSignature, GitSignature, SignatureStatus};
+
 // tag: 常用 tag 操作（完整 API 通过 git::tag:: 访问）
 pub use tag::{create_tag_with_note, get_latest_tag, get_next_tag_name, push_tag};
 
@@ -336,8 +336,10 @@ mod tests {
         let empty_filter = QueryFilter::default();
         assert!(GitQuery::is_filter_empty(&empty_filter));
 
-        let mut non_empty_filter = QueryFilter::default();
-        non_empty_filter.author = Some("john".to_string());
+        let non_empty_filter = QueryFilter {
+            author: Some("john".to_string()),
+            ..Default::default()
+        };
         assert!(!GitQuery::is_filter_empty(&non_empty_filter));
     }
 
@@ -520,10 +522,11 @@ mod tests {
     #[test]
     fn test_filter_validation() {
         // 测试过滤器字段验证
-        let mut filter = QueryFilter::default();
-
         // 设置各种字段
-        filter.author = Some("".to_string()); // 空字符串
+        let mut filter = QueryFilter {
+            author: Some("".to_string()), // 空字符串
+            ..Default::default()
+        };
         assert!(GitQuery::is_filter_empty(&filter)); // 空字符串应该被视为空
 
         filter.author = Some("valid-author".to_string());
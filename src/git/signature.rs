@@ -0,0 +1,232 @@
+use std::path::Path;
+use tokio::process::Command;
+
+/// 未指定范围时默认检查的提交数量
+pub const DEFAULT_VERIFY_LIMIT: u32 = 50;
+
+/// 提交签名状态（对应 `git log --format=%G?` 的输出）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// G: 有效签名
+    Good,
+    /// U: 有效签名，但密钥可信度未知
+    UnknownValidity,
+    /// B: 签名无效
+    Bad,
+    /// X: 签名已过期
+    Expired,
+    /// Y: 签名密钥已过期
+    ExpiredKey,
+    /// R: 签名密钥已吊销
+    Revoked,
+    /// E: 无法校验（如缺少公钥）
+    CannotCheck,
+    /// N: 未签名
+    Unsigned,
+}
+
+impl SignatureStatus {
+    /// 从 `%G?` 状态码解析
+    pub fn from_code(code: char) -> Self {
+        match code {
+            'G' => Self::Good,
+            'U' => Self::UnknownValidity,
+            'B' => Self::Bad,
+            'X' => Self::Expired,
+            'Y' => Self::ExpiredKey,
+            'R' => Self::Revoked,
+            'E' => Self::CannotCheck,
+            _ => Self::Unsigned,
+        }
+    }
+
+    /// 签名是否通过校验
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Good | Self::UnknownValidity)
+    }
+
+    /// 显示用的符号
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::Good | Self::UnknownValidity => "✓",
+            Self::CannotCheck => "?",
+            _ => "✗",
+        }
+    }
+
+    /// 状态说明
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Good => "good signature",
+            Self::UnknownValidity => "good signature (unknown validity)",
+            Self::Bad => "bad signature",
+            Self::Expired => "expired signature",
+            Self::ExpiredKey => "signed by expired key",
+            Self::Revoked => "signed by revoked key",
+            Self::CannotCheck => "cannot check signature (missing key?)",
+            Self::Unsigned => "unsigned",
+        }
+    }
+}
+
+/// 单个提交的签名校验结果
+#[derive(Debug, Clone)]
+pub struct CommitSignature {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub subject: String,
+    pub status: SignatureStatus,
+    pub signer: String,
+}
+
+/// 提交签名校验工具
+pub struct GitSignature;
+
+impl GitSignature {
+    /// 校验指定范围内提交的 GPG/SSH 签名，未指定范围时检查最近的提交
+    pub async fn verify_range(range: Option<&str>) -> anyhow::Result<Vec<CommitSignature>> {
+        Self::verify_range_in(Path::new("."), range).await
+    }
+
+    /// 在指定仓库中校验提交签名
+    pub async fn verify_range_in(
+        repo: &Path,
+        range: Option<&str>,
+    ) -> anyhow::Result<Vec<CommitSignature>> {
+        let mut args = vec![
+            "log".to_string(),
+            "--format=%H%x1f%h%x1f%G?%x1f%GS%x1f%an%x1f%s".to_string(),
+        ];
+
+        match range {
+            Some(range) if !range.trim().is_empty() => args.push(range.trim().to_string()),
+            _ => {
+                args.push("-n".to_string());
+                args.push(DEFAULT_VERIFY_LIMIT.to_string());
+            }
+        }
+
+        let output = Command::new("git")
+            .args(&args)
+            .current_dir(repo)
+            .output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to run git log: {}", e))?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Git log failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(Self::parse_signature_log(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// 解析 `%H %h %G? %GS %an %s`（以 \x1f 分隔）格式的 git log 输出
+    pub fn parse_signature_log(output: &str) -> Vec<CommitSignature> {
+        output
+            .lines()
+            .filter_map(|line| {
+                let parts: Vec<&str> = line.split('\x1f').collect();
+                if parts.len() < 6 {
+                    return None;
+                }
+
+                Some(CommitSignature {
+                    hash: parts[0].to_string(),
+                    short_hash: parts[1].to_string(),
+                    status: SignatureStatus::from_code(parts[2].chars().next().unwrap_or('N')),
+                    signer: parts[3].to_string(),
+                    author: parts[4].to_string(),
+                    subject: parts[5].to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_status_from_code() {
+        assert_eq!(SignatureStatus::from_code('G'), SignatureStatus::Good);
+        assert_eq!(SignatureStatus::from_code('B'), SignatureStatus::Bad);
+        assert_eq!(
+            SignatureStatus::from_code('E'),
+            SignatureStatus::CannotCheck
+        );
+        assert_eq!(SignatureStatus::from_code('N'), SignatureStatus::Unsigned);
+        assert_eq!(SignatureStatus::from_code('?'), SignatureStatus::Unsigned);
+    }
+
+    #[test]
+    fn test_signature_status_validity() {
+        assert!(SignatureStatus::Good.is_valid());
+        assert!(SignatureStatus::UnknownValidity.is_valid());
+        assert!(!SignatureStatus::Bad.is_valid());
+        assert!(!SignatureStatus::Revoked.is_valid());
+        assert!(!SignatureStatus::Unsigned.is_valid());
+
+        assert_eq!(SignatureStatus::Good.symbol(), "✓");
+        assert_eq!(SignatureStatus::Unsigned.symbol(), "✗");
+        assert_eq!(SignatureStatus::CannotCheck.symbol(), "?");
+    }
+
+    #[test]
+    fn test_parse_signature_log() {
+        let output = "abc123\x1fabc\x1fG\x1fAlice <alice@example.com>\x1fAlice\x1ffeat: add x\n\
+                      def456\x1fdef\x1fN\x1f\x1fBob\x1ffix: y\n\
+                      malformed line\n";
+        let signatures = GitSignature::parse_signature_log(output);
+
+        assert_eq!(signatures.len(), 2);
+        assert_eq!(signatures[0].status, SignatureStatus::Good);
+        assert_eq!(signatures[0].signer, "Alice <alice@example.com>");
+        assert_eq!(signatures[0].subject, "feat: add x");
+        assert_eq!(signatures[1].status, SignatureStatus::Unsigned);
+        assert_eq!(signatures[1].author, "Bob");
+    }
+
+    /// 创建只有一个未签名提交的临时仓库
+    pub(crate) fn unsigned_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for args in [
+            vec!["init", "-q"],
+            vec!["config", "user.name", "Test"],
+            vec!["config", "user.email", "test@example.com"],
+            vec!["config", "commit.gpgsign", "false"],
+            vec!["commit", "-q", "--allow-empty", "-m", "chore: unsigned"],
+        ] {
+            let status = std::process::Command::new("git")
+                .args(&args)
+                .current_dir(dir.path())
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {:?} failed", args);
+        }
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_verify_range() {
+        let repo = unsigned_repo();
+        let signatures = GitSignature::verify_range_in(repo.path(), Some("HEAD"))
+            .await
+            .unwrap();
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(signatures[0].status, SignatureStatus::Unsigned);
+        assert_eq!(signatures[0].subject, "chore: unsigned");
+
+        assert!(
+            GitSignature::verify_range_in(repo.path(), Some("no-such-ref"))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -173,20 +173,22 @@ pub async fn create_tag(tag: &str) -> anyhow::Result<()> {
 
 /// 创建新的带 note 的 tag
 pub async fn create_tag_with_note(tag: &str, note: &str) -> anyhow::Result<()> {
-    let status = Command::new("git")
+    let output = Command::new("git")
         .args(["tag", "-a", tag, "-m", note])
-        .status()
+        .output()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to create tag: {}", e))?;
 
-    if !status.success() {
+    if !output.status.success() {
         anyhow::bail!(
-            "Failed to create tag '{}' with note, exit code: {:?}",
+            "Failed to create tag '{}' with note: {}",
             tag,
-            status.code()
+            String::from_utf8_lossy(&output.stderr).trim()
         );
     }
 
+    // 标签列表已变化，下次计算下一个标签名时重新读取
+    *TAGS_CACHE.lock().await = None;
     Ok(())
 }
 
@@ -388,6 +390,33 @@ pub async fn compare_tags_log(tag1: &str, tag2: &str) -> anyhow::Result<String>
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// 获取某个标签之后的提交标题，每行一个；没有标签时返回最近的提交
+pub async fn get_commit_subjects_since(tag: Option<&str>, limit: usize) -> anyhow::Result<String> {
+    let mut args = vec![
+        "log".to_string(),
+        "--pretty=format:%s".to_string(),
+        format!("--max-count={}", limit),
+    ];
+    if let Some(tag) = tag {
+        args.push(format!("{}..HEAD", tag));
+    }
+
+    let output = Command::new("git")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get commit log: {}", e))?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 /// 列出所有标签（带格式化信息）
 pub async fn list_tags_formatted() -> anyhow::Result<String> {
     let output = Command::new("git")
@@ -649,10 +649,7 @@ mod tests {
             .await;
 
         match output {
-            Ok(o) => assert!(
-                !o.status.success(),
-                "git status should fail in non-git dir"
-            ),
+            Ok(o) => assert!(!o.status.success(), "git status should fail in non-git dir"),
             Err(e) => println!("Command failed as expected: {}", e),
         }
     }
@@ -6,16 +6,18 @@ pub async fn create_worktree(branch: &str, custom_path: Option<&str>) -> anyhow:
     let path = generate_worktree_path(branch, custom_path)?;
     let path_str = path.to_string_lossy();
 
-    let status = Command::new("git")
+    // 捕获输出而不是直接打印，TUI 中调用时不会破坏界面
+    let output = Command::new("git")
         .args(["worktree", "add", &path_str, branch])
-        .status()
+        .output()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to run git worktree add: {}", e))?;
 
-    if !status.success() {
+    if !output.status.success() {
         anyhow::bail!(
-            "Git worktree add failed with exit code: {:?}",
-            status.code()
+            "Git worktree add failed with exit code {:?}: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
         );
     }
 
@@ -30,16 +32,17 @@ pub async fn create_worktree_with_new_branch(
     let path = generate_worktree_path(branch, custom_path)?;
     let path_str = path.to_string_lossy();
 
-    let status = Command::new("git")
+    let output = Command::new("git")
         .args(["worktree", "add", "-b", branch, &path_str])
-        .status()
+        .output()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to run git worktree add with new branch: {}", e))?;
 
-    if !status.success() {
+    if !output.status.success() {
         anyhow::bail!(
-            "Git worktree add with new branch failed with exit code: {:?}",
-            status.code()
+            "Git worktree add with new branch failed with exit code {:?}: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
         );
     }
 
@@ -9,6 +9,9 @@ pub struct WorktreeInfo {
     pub commit: String,
     pub is_bare: bool,
     pub is_detached: bool,
+    /// 是否被 `git worktree lock` 锁定（仅 porcelain 输出包含）
+    #[serde(default)]
+    pub is_locked: bool,
 }
 
 impl WorktreeInfo {
@@ -26,6 +29,7 @@ impl WorktreeInfo {
             commit,
             is_bare,
             is_detached,
+            is_locked: false,
         }
     }
 }
@@ -179,23 +179,27 @@ fn parse_worktree_list(output: &str) -> anyhow::Result<Vec<WorktreeInfo>> {
     let mut current_commit = String::new();
     let mut current_is_bare = false;
     let mut current_is_detached = false;
+    let mut current_is_locked = false;
 
     for line in output.lines() {
         if line.starts_with("worktree ") {
             // Save previous worktree if exists
             if let Some(path) = current_path.take() {
-                worktrees.push(WorktreeInfo::new(
+                let mut worktree = WorktreeInfo::new(
                     path,
                     current_branch.clone(),
                     current_commit.clone(),
                     current_is_bare,
                     current_is_detached,
-                ));
+                );
+                worktree.is_locked = current_is_locked;
+                worktrees.push(worktree);
                 // Reset for next worktree
                 current_branch.clear();
                 current_commit.clear();
                 current_is_bare = false;
                 current_is_detached = false;
+                current_is_locked = false;
             }
 
             let path_str = line.strip_prefix("worktree ").unwrap_or("");
@@ -208,18 +212,22 @@ fn parse_worktree_list(output: &str) -> anyhow::Result<Vec<WorktreeInfo>> {
             current_is_bare = true;
         } else if line == "detached" {
             current_is_detached = true;
+        } else if line == "locked" || line.starts_with("locked ") {
+            current_is_locked = true;
         }
     }
 
     // Add the last worktree if exists
     if let Some(path) = current_path {
-        worktrees.push(WorktreeInfo::new(
+        let mut worktree = WorktreeInfo::new(
             path,
             current_branch,
             current_commit,
             current_is_bare,
             current_is_detached,
-        ));
+        );
+        worktree.is_locked = current_is_locked;
+        worktrees.push(worktree);
     }
 
     Ok(worktrees)
@@ -279,7 +287,7 @@ mod tests {
 
     #[test]
     fn test_parse_worktree_list_porcelain_complex() {
-        let output = "worktree /path/to/main\nHEAD abc123\nbranch refs/heads/main\n\nworktree /path/to/feature\nHEAD def456\nbranch refs/heads/feature/complex-name\n\nworktree /path/to/detached\nHEAD ghi789\ndetached\n";
+        let output = "worktree /path/to/main\nHEAD abc123\nbranch refs/heads/main\n\nworktree /path/to/feature\nHEAD def456\nbranch refs/heads/feature/complex-name\nlocked moved to usb disk\n\nworktree /path/to/detached\nHEAD ghi789\ndetached\n";
 
         let result = parse_worktree_list(output);
         assert!(result.is_ok());
@@ -289,6 +297,8 @@ mod tests {
 
         assert_eq!(worktrees[0].commit, "abc123");
         assert_eq!(worktrees[1].branch, "refs/heads/feature/complex-name");
+        assert!(worktrees[1].is_locked);
+        assert!(!worktrees[0].is_locked);
         assert!(worktrees[2].is_detached);
     }
 
@@ -5,24 +5,31 @@ use tokio::process::Command;
 pub async fn remove_worktree(path_or_name: &str) -> anyhow::Result<()> {
     let worktrees = list_worktrees().await?;
 
-    let target_path = if let Some(worktree) = worktrees.iter().find(|w| {
-        w.path.to_string_lossy().contains(path_or_name) || w.branch.contains(path_or_name)
+    // 完整路径优先精确匹配，避免误删路径相近的其他 worktree
+    let exact = worktrees
+        .iter()
+        .find(|w| w.path == std::path::Path::new(path_or_name));
+    let target_path = if let Some(worktree) = exact.or_else(|| {
+        worktrees.iter().find(|w| {
+            w.path.to_string_lossy().contains(path_or_name) || w.branch.contains(path_or_name)
+        })
     }) {
         worktree.path.to_string_lossy().to_string()
     } else {
         path_or_name.to_string()
     };
 
-    let status = Command::new("git")
+    let output = Command::new("git")
         .args(["worktree", "remove", &target_path])
-        .status()
+        .output()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to run git worktree remove: {}", e))?;
 
-    if !status.success() {
+    if !output.status.success() {
         anyhow::bail!(
-            "Git worktree remove failed with exit code: {:?}",
-            status.code()
+            "Git worktree remove failed with exit code {:?}: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
         );
     }
 
@@ -31,16 +38,17 @@ pub async fn remove_worktree(path_or_name: &str) -> anyhow::Result<()> {
 
 /// 清理worktree引用
 pub async fn prune_worktrees() -> anyhow::Result<()> {
-    let status = Command::new("git")
+    let output = Command::new("git")
         .args(["worktree", "prune"])
-        .status()
+        .output()
         .await
         .map_err(|e| anyhow::anyhow!("Failed to run git worktree prune: {}", e))?;
 
-    if !status.success() {
+    if !output.status.success() {
         anyhow::bail!(
-            "Git worktree prune failed with exit code: {:?}",
-            status.code()
+            "Git worktree prune failed with exit code {:?}: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
         );
     }
 
@@ -109,7 +117,7 @@ mod tests {
         use super::super::info::WorktreeInfo;
         use std::path::PathBuf;
 
-        let worktrees = vec![
+        let worktrees = [
             WorktreeInfo::new(
                 PathBuf::from("/repo/main"),
                 "main".to_string(),
@@ -145,7 +153,7 @@ mod tests {
         use std::path::PathBuf;
 
         let current_dir = PathBuf::from("/repo/main");
-        let worktrees = vec![
+        let worktrees = [
             WorktreeInfo::new(
                 current_dir.clone(),
                 "main".to_string(),
@@ -293,7 +301,7 @@ mod tests {
         use super::super::info::WorktreeInfo;
         use std::path::PathBuf;
 
-        let worktrees = vec![WorktreeInfo::new(
+        let worktrees = [WorktreeInfo::new(
             PathBuf::from("/repo/worktree-feature-ui-test"),
             "feature/ui/test".to_string(),
             "abc123".to_string(),
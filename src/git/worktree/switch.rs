@@ -5,14 +5,18 @@ use std::path::PathBuf;
 pub async fn switch_to_worktree(path_or_name: &str) -> anyhow::Result<PathBuf> {
     let worktrees = list_worktrees().await?;
 
+    // 完整路径优先精确匹配
     let target_worktree = worktrees
         .iter()
-        .find(|w| {
-            w.path.to_string_lossy().contains(path_or_name)
-                || w.branch.contains(path_or_name)
-                || w.path
-                    .file_name()
-                    .is_some_and(|f| f.to_string_lossy().contains(path_or_name))
+        .find(|w| w.path == std::path::Path::new(path_or_name))
+        .or_else(|| {
+            worktrees.iter().find(|w| {
+                w.path.to_string_lossy().contains(path_or_name)
+                    || w.branch.contains(path_or_name)
+                    || w.path
+                        .file_name()
+                        .is_some_and(|f| f.to_string_lossy().contains(path_or_name))
+            })
         })
         .ok_or_else(|| anyhow::anyhow!("找不到指定的 worktree: {}", path_or_name))?;
 
@@ -0,0 +1,202 @@
+use crate::core::ai::http::shared_client;
+use serde::Deserialize;
+use std::env;
+
+/// GitHub API 默认地址
+const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// 已创建的 Pull Request
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepoResponse {
+    default_branch: String,
+}
+
+/// GitHub REST API 客户端
+///
+/// 使用 `GITHUB_TOKEN` 认证；GitHub Enterprise 可通过 `GITHUB_API_URL` 指定 API 地址。
+#[derive(Debug, Clone)]
+pub struct GitHubClient {
+    api_url: String,
+    token: String,
+}
+
+impl GitHubClient {
+    pub fn new(api_url: &str, token: &str) -> Self {
+        Self {
+            api_url: api_url.trim().trim_end_matches('/').to_string(),
+            token: token.to_string(),
+        }
+    }
+
+    /// 从环境变量创建客户端，未设置 `GITHUB_TOKEN` 时返回 None
+    pub fn from_env() -> Option<Self> {
+        let token = env::var("GITHUB_TOKEN")
+            .ok()
+            .filter(|t| !t.trim().is_empty())?;
+        let api_url = env::var("GITHUB_API_URL").unwrap_or_else(|_| DEFAULT_API_URL.to_string());
+        Some(Self::new(&api_url, token.trim()))
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        shared_client()
+            .request(method, format!("{}/{}", self.api_url, path))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "ai-commit")
+    }
+
+    /// 获取仓库默认分支
+    pub async fn default_branch(&self, owner: &str, repo: &str) -> anyhow::Result<String> {
+        let response = self
+            .request(reqwest::Method::GET, &format!("repos/{}/{}", owner, repo))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to request GitHub repository: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "GitHub returned {} for {}/{}: {}",
+                status,
+                owner,
+                repo,
+                error_message(&body)
+            );
+        }
+
+        let repo_info: RepoResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to parse GitHub repository: {}", e))?;
+        Ok(repo_info.default_branch)
+    }
+
+    /// 创建 Pull Request
+    pub async fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> anyhow::Result<PullRequest> {
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("repos/{}/{}/pulls", owner, repo),
+            )
+            .json(&serde_json::json!({
+                "title": title,
+                "body": body,
+                "head": head,
+                "base": base,
+            }))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create pull request: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "GitHub returned {} when creating pull request: {}",
+                status,
+                error_message(&body)
+            );
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to parse pull request response: {}", e))
+    }
+}
+
+/// 从远程地址解析 GitHub 仓库的 owner 和 repo
+///
+/// 未设置 `GITHUB_API_URL` 时只接受 github.com 的远程地址。
+pub fn parse_github_repo(remote_url: &str, allow_any_host: bool) -> Option<(String, String)> {
+    let web_url = super::remote_web_url(remote_url)?;
+    let rest = web_url.strip_prefix("https://")?;
+    let (host, path) = rest.split_once('/')?;
+    if !allow_any_host && host != "github.com" {
+        return None;
+    }
+
+    let mut parts = path.split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// 提取 GitHub 错误响应中的可读信息
+fn error_message(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.trim().to_string();
+    };
+
+    let mut message = value["message"].as_str().unwrap_or_default().to_string();
+    if let Some(errors) = value["errors"].as_array() {
+        let details: Vec<&str> = errors
+            .iter()
+            .filter_map(|error| error["message"].as_str())
+            .collect();
+        if !details.is_empty() {
+            message.push_str(&format!(" ({})", details.join("; ")));
+        }
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_github_repo() {
+        assert_eq!(
+            parse_github_repo("git@github.com:costa92/ai-commit.git", false),
+            Some(("costa92".to_string(), "ai-commit".to_string()))
+        );
+        assert_eq!(
+            parse_github_repo("https://github.com/costa92/ai-commit", false),
+            Some(("costa92".to_string(), "ai-commit".to_string()))
+        );
+        assert_eq!(parse_github_repo("git@gitlab.com:o/r.git", false), None);
+        assert_eq!(
+            parse_github_repo("git@github.example.com:o/r.git", true),
+            Some(("o".to_string(), "r".to_string()))
+        );
+        assert_eq!(parse_github_repo("https://github.com/o", false), None);
+    }
+
+    #[test]
+    fn test_error_message() {
+        let body = r#"{"message":"Validation Failed","errors":[{"message":"A pull request already exists for o:feature."}]}"#;
+        assert_eq!(
+            error_message(body),
+            "Validation Failed (A pull request already exists for o:feature.)"
+        );
+        assert_eq!(error_message("Bad Gateway"), "Bad Gateway");
+    }
+
+    #[test]
+    fn test_pull_request_deserialize() {
+        let json =
+            r#"{"number": 42, "html_url": "https://github.com/o/r/pull/42", "state": "open"}"#;
+        let pr: PullRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.html_url, "https://github.com/o/r/pull/42");
+    }
+}
@@ -0,0 +1,227 @@
+use crate::core::ai::http::shared_client;
+use serde::Deserialize;
+use std::env;
+
+/// Jira 工单信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct JiraIssue {
+    pub key: String,
+    pub summary: String,
+    pub status: Option<String>,
+    pub issue_type: Option<String>,
+}
+
+impl JiraIssue {
+    /// 生成注入 AI 提示词的工单上下文
+    pub fn to_prompt_context(&self) -> String {
+        let mut context = format!(
+            "\n关联工单（仅供参考）：\n- {}: {}\n",
+            self.key, self.summary
+        );
+        if let Some(issue_type) = &self.issue_type {
+            context.push_str(&format!("- 工单类型：{}\n", issue_type));
+        }
+        context
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueResponse {
+    key: String,
+    fields: IssueFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueFields {
+    #[serde(default)]
+    summary: String,
+    status: Option<NamedField>,
+    issuetype: Option<NamedField>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedField {
+    name: String,
+}
+
+/// Jira REST API 客户端
+///
+/// 通过环境变量配置：
+/// - `AI_COMMIT_JIRA_URL`：Jira 地址，如 `https://your-team.atlassian.net`
+/// - `AI_COMMIT_JIRA_TOKEN`：API Token（Jira Cloud）或个人访问令牌（Jira Server）
+/// - `AI_COMMIT_JIRA_EMAIL`：Jira Cloud 账号邮箱，设置后使用 Basic 认证，否则使用 Bearer 认证
+#[derive(Debug, Clone)]
+pub struct JiraClient {
+    base_url: String,
+    email: Option<String>,
+    token: String,
+}
+
+impl JiraClient {
+    pub fn new(base_url: &str, email: Option<String>, token: &str) -> Self {
+        Self {
+            base_url: base_url.trim().trim_end_matches('/').to_string(),
+            email: email.filter(|e| !e.trim().is_empty()),
+            token: token.to_string(),
+        }
+    }
+
+    /// 从环境变量创建客户端，未配置 URL 或 Token 时返回 None
+    pub fn from_env() -> Option<Self> {
+        let base_url = env::var("AI_COMMIT_JIRA_URL").ok()?;
+        let token = env::var("AI_COMMIT_JIRA_TOKEN").ok()?;
+        if base_url.trim().is_empty() || token.trim().is_empty() {
+            return None;
+        }
+        Some(Self::new(
+            &base_url,
+            env::var("AI_COMMIT_JIRA_EMAIL").ok(),
+            &token,
+        ))
+    }
+
+    /// 工单的网页地址
+    pub fn issue_url(&self, key: &str) -> String {
+        format!("{}/browse/{}", self.base_url, key)
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/rest/api/2/{}", self.base_url, path)
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.email {
+            Some(email) => request.basic_auth(email, Some(&self.token)),
+            None => request.bearer_auth(&self.token),
+        }
+    }
+
+    /// 获取工单标题、状态和类型
+    pub async fn fetch_issue(&self, key: &str) -> anyhow::Result<JiraIssue> {
+        let url = self.api_url(&format!("issue/{}?fields=summary,status,issuetype", key));
+        let response = self
+            .authorize(shared_client().get(&url))
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to request Jira issue {}: {}", key, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Jira returned {} for issue {}: {}",
+                status,
+                key,
+                body.trim()
+            );
+        }
+
+        let issue: IssueResponse = response
+            .json()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to parse Jira issue {}: {}", key, e))?;
+
+        Ok(Self::convert_issue(issue))
+    }
+
+    /// 在工单下添加评论
+    pub async fn add_comment(&self, key: &str, body: &str) -> anyhow::Result<()> {
+        let url = self.api_url(&format!("issue/{}/comment", key));
+        let response = self
+            .authorize(shared_client().post(&url))
+            .json(&serde_json::json!({ "body": body }))
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to post Jira comment on {}: {}", key, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Jira returned {} when commenting on {}: {}",
+                status,
+                key,
+                body.trim()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn convert_issue(issue: IssueResponse) -> JiraIssue {
+        JiraIssue {
+            key: issue.key,
+            summary: issue.fields.summary,
+            status: issue.fields.status.map(|s| s.name),
+            issue_type: issue.fields.issuetype.map(|t| t.name),
+        }
+    }
+}
+
+/// 生成提交后的工单评论
+pub fn format_commit_comment(
+    message: &str,
+    hash: &str,
+    branch: &str,
+    commit_url: Option<&str>,
+) -> String {
+    let subject = message.lines().next().unwrap_or_default();
+    let short_hash: String = hash.chars().take(7).collect();
+    let mut comment = format!("Commit {} on branch {}: {}", short_hash, branch, subject);
+    if let Some(url) = commit_url {
+        comment.push_str(&format!("\n{}", url));
+    }
+    comment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_urls() {
+        let client = JiraClient::new("https://team.atlassian.net/", None, "token");
+        assert_eq!(
+            client.issue_url("PROJ-1"),
+            "https://team.atlassian.net/browse/PROJ-1"
+        );
+        assert_eq!(
+            client.api_url("issue/PROJ-1/comment"),
+            "https://team.atlassian.net/rest/api/2/issue/PROJ-1/comment"
+        );
+    }
+
+    #[test]
+    fn test_parse_issue_response() {
+        let json = r#"{
+            "key": "PROJ-7",
+            "fields": {
+                "summary": "登录页支持 SSO",
+                "status": { "name": "In Progress" },
+                "issuetype": { "name": "Story" }
+            }
+        }"#;
+        let issue = JiraClient::convert_issue(serde_json::from_str(json).unwrap());
+
+        assert_eq!(issue.key, "PROJ-7");
+        assert_eq!(issue.summary, "登录页支持 SSO");
+        assert_eq!(issue.status.as_deref(), Some("In Progress"));
+        assert!(issue.to_prompt_context().contains("PROJ-7: 登录页支持 SSO"));
+        assert!(issue.to_prompt_context().contains("Story"));
+    }
+
+    #[test]
+    fn test_format_commit_comment() {
+        let comment = format_commit_comment(
+            "feat: add sso\n\nRefs: PROJ-7",
+            "0123456789abcdef",
+            "feature/PROJ-7-sso",
+            Some("https://github.com/o/r/commit/0123456789abcdef"),
+        );
+        assert_eq!(
+            comment,
+            "Commit 0123456 on branch feature/PROJ-7-sso: feat: add sso\nhttps://github.com/o/r/commit/0123456789abcdef"
+        );
+    }
+}
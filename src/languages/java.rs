@@ -0,0 +1,308 @@
+//! Java 分析器与 JVM 构建文件（Maven / Gradle）变更识别
+
+use super::{jvm_package_scope, Language, LanguageAnalyzer, Symbol, SymbolKind, ANNOTATION_REGEX};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static TYPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:public|protected|private|abstract|final|static|sealed|non-sealed|strictfp)\s+)*(class|interface|enum|record|@interface)\s+([A-Za-z_$][\w$]*)",
+    )
+    .unwrap()
+});
+
+static METHOD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:public|protected|private|static|final|abstract|synchronized|native|default|strictfp)\s+)*(?:<[^>]+>\s*)?([\w$.]+(?:<[^()]*>)?(?:\[\])*)\s+([A-Za-z_$][\w$]*)\s*\(",
+    )
+    .unwrap()
+});
+
+static CONSTRUCTOR_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:public|protected|private)\s+([A-Z][\w$]*)\s*\(").unwrap());
+
+/// 看起来像方法声明、实际是语句的前缀
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "return",
+    "new",
+    "throw",
+    "else",
+    "case",
+    "yield",
+    "await",
+    "assert",
+    "if",
+    "for",
+    "while",
+    "switch",
+    "catch",
+    "synchronized",
+];
+
+static MAVEN_ARTIFACT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<artifactId>\s*([^<\s]+)\s*</artifactId>").unwrap());
+
+static MAVEN_GROUP_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<groupId>\s*([^<\s]+)\s*</groupId>").unwrap());
+
+static GRADLE_DEPENDENCY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*(?:implementation|api|compileOnly|runtimeOnly|testImplementation|testRuntimeOnly|testCompileOnly|kapt|ksp|annotationProcessor|classpath)\s*\(?\s*["']([^"']+)["']"#,
+    )
+    .unwrap()
+});
+
+/// Java 分析器
+pub struct JavaAnalyzer;
+
+impl LanguageAnalyzer for JavaAnalyzer {
+    fn language(&self) -> Language {
+        Language::Java
+    }
+
+    fn extract_symbols(&self, line: &str) -> Vec<Symbol> {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
+            return Vec::new();
+        }
+
+        if let Some(captures) = TYPE_REGEX.captures(line) {
+            let kind = match &captures[1] {
+                "interface" | "@interface" => SymbolKind::Interface,
+                "enum" => SymbolKind::Enum,
+                _ => SymbolKind::Class,
+            };
+            return vec![Symbol::new(kind, &captures[2])];
+        }
+
+        if let Some(captures) = ANNOTATION_REGEX.captures(line) {
+            return vec![Symbol::new(SymbolKind::Annotation, &captures[1])];
+        }
+
+        if let Some(captures) = CONSTRUCTOR_REGEX.captures(line) {
+            return vec![Symbol::new(SymbolKind::Method, &captures[1])];
+        }
+
+        if let Some(captures) = METHOD_REGEX.captures(line) {
+            let return_type = &captures[1];
+            let name = &captures[2];
+            if !STATEMENT_KEYWORDS.contains(&return_type) && !STATEMENT_KEYWORDS.contains(&name) {
+                return vec![Symbol::new(SymbolKind::Method, name)];
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn scope_for_path(&self, path: &str) -> Option<String> {
+        jvm_package_scope(path)
+    }
+
+    fn review_focus(&self) -> &'static [&'static str] {
+        &[
+            "空指针风险：Optional 的误用、未判空的返回值",
+            "资源泄漏：流、连接未使用 try-with-resources 关闭",
+            "equals/hashCode 是否成对重写",
+            "Spring 注解（@Transactional 等）的作用范围和代理失效问题",
+            "并发：共享可变状态、非线程安全的集合",
+        ]
+    }
+}
+
+/// JVM 构建系统
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildSystem {
+    Maven,
+    Gradle,
+}
+
+impl BuildSystem {
+    /// 根据文件名识别构建文件
+    pub fn from_path(path: &str) -> Option<Self> {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        match file_name {
+            "pom.xml" => Some(BuildSystem::Maven),
+            "build.gradle"
+            | "build.gradle.kts"
+            | "settings.gradle"
+            | "settings.gradle.kts"
+            | "gradle.properties"
+            | "libs.versions.toml" => Some(BuildSystem::Gradle),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildSystem::Maven => "Maven",
+            BuildSystem::Gradle => "Gradle",
+        }
+    }
+}
+
+/// 构建文件的变更
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildFileChange {
+    pub path: String,
+    pub system: BuildSystem,
+    /// 新增的依赖坐标（Maven 为 `groupId:artifactId`）
+    pub added_dependencies: Vec<String>,
+    pub removed_dependencies: Vec<String>,
+    /// 同一 diff 块中最近出现的 groupId，用于拼接 Maven 坐标
+    last_group: Option<String>,
+}
+
+impl BuildFileChange {
+    pub fn new(path: &str, system: BuildSystem) -> Self {
+        Self {
+            path: path.to_string(),
+            system,
+            added_dependencies: Vec::new(),
+            removed_dependencies: Vec::new(),
+            last_group: None,
+        }
+    }
+
+    /// 记录一行新增（`added`）或删除的内容
+    pub fn record_line(&mut self, line: &str, added: bool) {
+        let dependency = match self.system {
+            BuildSystem::Maven => {
+                if let Some(captures) = MAVEN_GROUP_REGEX.captures(line) {
+                    self.last_group = Some(captures[1].to_string());
+                    None
+                } else {
+                    MAVEN_ARTIFACT_REGEX.captures(line).map(|captures| {
+                        match self.last_group.take() {
+                            Some(group) => format!("{}:{}", group, &captures[1]),
+                            None => captures[1].to_string(),
+                        }
+                    })
+                }
+            }
+            BuildSystem::Gradle => GRADLE_DEPENDENCY_REGEX
+                .captures(line)
+                .map(|captures| captures[1].to_string()),
+        };
+
+        if let Some(dependency) = dependency {
+            let target = if added {
+                &mut self.added_dependencies
+            } else {
+                &mut self.removed_dependencies
+            };
+            if !target.contains(&dependency) {
+                target.push(dependency);
+            }
+        }
+    }
+
+    pub fn has_dependency_changes(&self) -> bool {
+        !self.added_dependencies.is_empty() || !self.removed_dependencies.is_empty()
+    }
+
+    /// 单行描述，如 `构建文件 pom.xml（Maven）：新增依赖 a；移除依赖 b`
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.added_dependencies.is_empty() {
+            parts.push(format!("新增依赖 {}", self.added_dependencies.join(", ")));
+        }
+        if !self.removed_dependencies.is_empty() {
+            parts.push(format!("移除依赖 {}", self.removed_dependencies.join(", ")));
+        }
+
+        let header = format!("构建文件 {}（{}）", self.path, self.system.as_str());
+        if parts.is_empty() {
+            format!("{}：构建配置变更", header)
+        } else {
+            format!("{}：{}", header, parts.join("；"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(line: &str) -> Vec<Symbol> {
+        JavaAnalyzer.extract_symbols(line)
+    }
+
+    #[test]
+    fn test_extract_types() {
+        assert_eq!(
+            symbols("public final class UserService implements Service {"),
+            vec![Symbol::new(SymbolKind::Class, "UserService")]
+        );
+        assert_eq!(
+            symbols("interface Repository<T> {"),
+            vec![Symbol::new(SymbolKind::Interface, "Repository")]
+        );
+        assert_eq!(
+            symbols("public enum Status {"),
+            vec![Symbol::new(SymbolKind::Enum, "Status")]
+        );
+        assert_eq!(
+            symbols("public record Point(int x, int y) {}"),
+            vec![Symbol::new(SymbolKind::Class, "Point")]
+        );
+    }
+
+    #[test]
+    fn test_extract_methods_and_annotations() {
+        assert_eq!(
+            symbols("    public static <T> List<T> findAll(Class<T> type) {"),
+            vec![Symbol::new(SymbolKind::Method, "findAll")]
+        );
+        assert_eq!(
+            symbols("    public UserService(UserRepository repo) {"),
+            vec![Symbol::new(SymbolKind::Method, "UserService")]
+        );
+        assert_eq!(
+            symbols("    @GetMapping(\"/users\")"),
+            vec![Symbol::new(SymbolKind::Annotation, "GetMapping")]
+        );
+    }
+
+    #[test]
+    fn test_ignores_statements() {
+        assert!(symbols("        return repository.save(user);").is_empty());
+        assert!(symbols("        if (user == null) {").is_empty());
+        assert!(symbols("        User user = new User(name);").is_empty());
+        assert!(symbols("    // public void legacy() {").is_empty());
+    }
+
+    #[test]
+    fn test_build_system_detection() {
+        assert_eq!(BuildSystem::from_path("pom.xml"), Some(BuildSystem::Maven));
+        assert_eq!(
+            BuildSystem::from_path("service/build.gradle.kts"),
+            Some(BuildSystem::Gradle)
+        );
+        assert_eq!(
+            BuildSystem::from_path("gradle/libs.versions.toml"),
+            Some(BuildSystem::Gradle)
+        );
+        assert_eq!(BuildSystem::from_path("Cargo.toml"), None);
+    }
+
+    #[test]
+    fn test_gradle_dependency_changes() {
+        let mut change = BuildFileChange::new("build.gradle", BuildSystem::Gradle);
+        change.record_line(
+            "    implementation(\"com.squareup.okhttp3:okhttp:4.12.0\")",
+            true,
+        );
+        change.record_line("    testImplementation 'junit:junit:4.13'", false);
+        change.record_line("    version = '1.0'", true);
+
+        assert_eq!(
+            change.added_dependencies,
+            vec!["com.squareup.okhttp3:okhttp:4.12.0".to_string()]
+        );
+        assert_eq!(
+            change.removed_dependencies,
+            vec!["junit:junit:4.13".to_string()]
+        );
+        assert!(change.describe().contains("移除依赖 junit:junit:4.13"));
+    }
+}
@@ -0,0 +1,107 @@
+//! Kotlin 分析器
+
+use super::{jvm_package_scope, Language, LanguageAnalyzer, Symbol, SymbolKind, ANNOTATION_REGEX};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static TYPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:public|private|internal|protected|open|abstract|sealed|data|enum|annotation|inner|value|final|expect|actual|companion)\s+)*(class|interface|object)\s+([A-Za-z_][\w]*)",
+    )
+    .unwrap()
+});
+
+static FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:public|private|internal|protected|open|abstract|override|suspend|inline|operator|infix|tailrec|external|final|expect|actual)\s+)*fun\s+(?:<[^>]+>\s*)?(?:[\w.<>?]+\.)?([A-Za-z_][\w]*)\s*\(",
+    )
+    .unwrap()
+});
+
+/// Kotlin 分析器
+pub struct KotlinAnalyzer;
+
+impl LanguageAnalyzer for KotlinAnalyzer {
+    fn language(&self) -> Language {
+        Language::Kotlin
+    }
+
+    fn extract_symbols(&self, line: &str) -> Vec<Symbol> {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
+            return Vec::new();
+        }
+
+        if let Some(captures) = TYPE_REGEX.captures(line) {
+            let declaration = captures.get(0).map(|m| m.as_str()).unwrap_or_default();
+            let kind = match &captures[1] {
+                "interface" => SymbolKind::Interface,
+                "object" => SymbolKind::Object,
+                _ if declaration.contains("enum ") => SymbolKind::Enum,
+                _ => SymbolKind::Class,
+            };
+            return vec![Symbol::new(kind, &captures[2])];
+        }
+
+        if let Some(captures) = FUNCTION_REGEX.captures(line) {
+            return vec![Symbol::new(SymbolKind::Method, &captures[1])];
+        }
+
+        if let Some(captures) = ANNOTATION_REGEX.captures(line) {
+            return vec![Symbol::new(SymbolKind::Annotation, &captures[1])];
+        }
+
+        Vec::new()
+    }
+
+    fn scope_for_path(&self, path: &str) -> Option<String> {
+        jvm_package_scope(path)
+    }
+
+    fn review_focus(&self) -> &'static [&'static str] {
+        &[
+            "空安全：`!!` 强制解包和平台类型",
+            "协程：在错误的 Dispatcher 上阻塞、未处理的取消和异常",
+            "data class 中可变属性和暴露的可变集合",
+            "lateinit 属性在初始化前被访问的可能",
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(line: &str) -> Vec<Symbol> {
+        KotlinAnalyzer.extract_symbols(line)
+    }
+
+    #[test]
+    fn test_extract_declarations() {
+        assert_eq!(
+            symbols("data class Invoice(val id: Long)"),
+            vec![Symbol::new(SymbolKind::Class, "Invoice")]
+        );
+        assert_eq!(
+            symbols("enum class Status { OPEN, CLOSED }"),
+            vec![Symbol::new(SymbolKind::Enum, "Status")]
+        );
+        assert_eq!(
+            symbols("object BillingConfig {"),
+            vec![Symbol::new(SymbolKind::Object, "BillingConfig")]
+        );
+        assert_eq!(
+            symbols("    override suspend fun <T> fetch(id: T): Invoice {"),
+            vec![Symbol::new(SymbolKind::Method, "fetch")]
+        );
+        assert_eq!(
+            symbols("fun String.toSlug(): String ="),
+            vec![Symbol::new(SymbolKind::Method, "toSlug")]
+        );
+        assert_eq!(
+            symbols("    @Composable"),
+            vec![Symbol::new(SymbolKind::Annotation, "Composable")]
+        );
+        assert!(symbols("    val total = items.sumOf { it.price }").is_empty());
+    }
+}
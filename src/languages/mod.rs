@@ -0,0 +1,638 @@
+//! 语言感知的 diff 分析
+//!
+//! 按文件扩展名识别语言，从变更行和 hunk 头中提取类、方法、注解等符号，
+//! 并识别构建文件（pom.xml、build.gradle）的依赖变更。结果用于推断提交作用域，
+//! 以及为提交和审查提示词补充代码结构上下文。TypeScript / JavaScript 文件
+//! 还会运行 `typescript` 中的 React 相关规则，发现的问题写入审查上下文。
+//!
+//! 符号默认用逐行正则提取；启用 `tree-sitter` feature 时改用 `syntax` 中的语法后端，
+//! 没有对应语法的语言仍使用正则。
+
+pub mod java;
+pub mod kotlin;
+pub mod syntax;
+pub mod typescript;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use syntax::{Fragment, SyntaxBackend};
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+/// hunk 头中 git 给出的函数上下文，如 `@@ -10,7 +10,8 @@ public class UserService {`
+static HUNK_CONTEXT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^@@ [^@]+ @@ ?(.*)$").unwrap());
+
+/// 注解使用，Java 与 Kotlin 通用
+pub(crate) static ANNOTATION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*@([A-Za-z_][\w.]*)").unwrap());
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    Java,
+    Kotlin,
+    TypeScript,
+    JavaScript,
+}
+
+impl Language {
+    /// 根据文件路径识别语言
+    pub fn from_path(path: &str) -> Option<Self> {
+        let extension = path.rsplit_once('.').map(|(_, ext)| ext)?;
+        match extension {
+            "java" => Some(Language::Java),
+            "kt" | "kts" if !is_gradle_script(path) => Some(Language::Kotlin),
+            "ts" | "tsx" | "mts" | "cts" => Some(Language::TypeScript),
+            "js" | "jsx" | "mjs" | "cjs" if !path.contains(".min.") => Some(Language::JavaScript),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::Java => "Java",
+            Language::Kotlin => "Kotlin",
+            Language::TypeScript => "TypeScript",
+            Language::JavaScript => "JavaScript",
+        }
+    }
+}
+
+/// 符号类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Class,
+    Interface,
+    Enum,
+    Object,
+    Method,
+    Annotation,
+    Component,
+}
+
+impl SymbolKind {
+    /// 按英文名称解析，用于配置中的符号类型
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "class" => Some(SymbolKind::Class),
+            "interface" => Some(SymbolKind::Interface),
+            "enum" => Some(SymbolKind::Enum),
+            "object" => Some(SymbolKind::Object),
+            "method" | "function" => Some(SymbolKind::Method),
+            "annotation" => Some(SymbolKind::Annotation),
+            "component" => Some(SymbolKind::Component),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            SymbolKind::Class => "类",
+            SymbolKind::Interface => "接口",
+            SymbolKind::Enum => "枚举",
+            SymbolKind::Object => "对象",
+            SymbolKind::Method => "方法",
+            SymbolKind::Annotation => "注解",
+            SymbolKind::Component => "组件",
+        }
+    }
+}
+
+/// 从源码中提取的符号
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Symbol {
+    pub kind: SymbolKind,
+    pub name: String,
+}
+
+impl Symbol {
+    pub fn new(kind: SymbolKind, name: &str) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+        }
+    }
+}
+
+/// 单一语言的分析器
+pub trait LanguageAnalyzer: Send + Sync {
+    fn language(&self) -> Language;
+
+    /// 从一行源码中提取符号
+    fn extract_symbols(&self, line: &str) -> Vec<Symbol>;
+
+    /// 根据源文件路径推断提交作用域
+    fn scope_for_path(&self, path: &str) -> Option<String>;
+
+    /// 审查该语言代码时需要重点关注的问题
+    fn review_focus(&self) -> &'static [&'static str];
+
+    /// 文件在审查提示词中的类型说明，如「React 组件（JSX）」
+    fn file_context(&self, _path: &str) -> Option<&'static str> {
+        None
+    }
+}
+
+/// 获取语言对应的分析器
+pub fn analyzer_for(language: Language) -> Box<dyn LanguageAnalyzer> {
+    match language {
+        Language::Java => Box::new(java::JavaAnalyzer),
+        Language::Kotlin => Box::new(kotlin::KotlinAnalyzer),
+        Language::TypeScript | Language::JavaScript => {
+            Box::new(typescript::TypeScriptAnalyzer(language))
+        }
+    }
+}
+
+/// 根据文件路径推断作用域：构建文件归为 `build`，源文件交给对应语言的分析器
+pub fn scope_for_path(path: &str) -> Option<String> {
+    if java::BuildSystem::from_path(path).is_some() {
+        return Some("build".to_string());
+    }
+    Language::from_path(path).and_then(|language| analyzer_for(language).scope_for_path(path))
+}
+
+/// 单个源文件中变更涉及的符号
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileSymbols {
+    pub path: String,
+    pub language: Language,
+    /// 在新增或删除行中声明/使用的符号
+    pub changed: Vec<Symbol>,
+    /// hunk 头给出的外层上下文（变更所在的类或方法）
+    pub enclosing: Vec<Symbol>,
+}
+
+/// diff 中的语言相关信息
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LanguageSummary {
+    pub files: Vec<FileSymbols>,
+    pub build_files: Vec<java::BuildFileChange>,
+    /// TypeScript / JavaScript 新增代码的本地检查结果
+    pub typescript_issues: Vec<typescript::TypeScriptIssue>,
+}
+
+impl LanguageSummary {
+    /// 解析统一 diff，收集各源文件的符号和构建文件变更
+    pub fn from_diff(diff: &str) -> Self {
+        let mut summary = LanguageSummary::default();
+        let mut current: Option<FileCollector> = None;
+
+        for line in diff.lines() {
+            if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+                if let Some(collector) = current.take() {
+                    collector.finish(&mut summary);
+                }
+                current = FileCollector::new(&captures[2]);
+                continue;
+            }
+
+            if let Some(collector) = current.as_mut() {
+                collector.push_line(line);
+            }
+        }
+
+        if let Some(collector) = current {
+            collector.finish(&mut summary);
+        }
+
+        summary.typescript_issues = typescript::scan_diff(diff);
+        summary
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.build_files.is_empty()
+    }
+
+    /// 各语言使用的符号提取后端，如 `Java: tree-sitter, Kotlin: regex`，没有源文件时为 `None`
+    pub fn describe_backends(&self) -> Option<String> {
+        let mut languages: Vec<Language> = Vec::new();
+        for file in &self.files {
+            if !languages.contains(&file.language) {
+                languages.push(file.language);
+            }
+        }
+        if languages.is_empty() {
+            return None;
+        }
+        let backends: Vec<String> = languages
+            .iter()
+            .map(|language| format!("{}: {}", language.as_str(), syntax::backend_name(*language)))
+            .collect();
+        Some(backends.join(", "))
+    }
+
+    /// 提交提示词中的代码结构上下文
+    pub fn to_prompt_context(&self) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+
+        let mut lines = Vec::new();
+        for file in &self.files {
+            let symbols = describe_symbols(&file.changed, &file.enclosing);
+            if !symbols.is_empty() {
+                lines.push(format!(
+                    "- {} {}：{}",
+                    file.language.as_str(),
+                    file.path,
+                    symbols
+                ));
+            }
+        }
+        for build in &self.build_files {
+            lines.push(format!("- {}", build.describe()));
+        }
+
+        if lines.is_empty() {
+            return String::new();
+        }
+        format!("\n代码结构变更:\n{}\n", lines.join("\n"))
+    }
+
+    /// 审查提示词中的上下文：结构变更、文件类型、各语言的审查重点和本地规则发现的问题
+    pub fn to_review_context(&self) -> String {
+        let mut context = self.to_prompt_context();
+
+        let mut languages: Vec<Language> = Vec::new();
+        for file in &self.files {
+            if !languages.contains(&file.language) {
+                languages.push(file.language);
+            }
+        }
+        let file_types: Vec<String> = self
+            .files
+            .iter()
+            .filter_map(|file| {
+                analyzer_for(file.language)
+                    .file_context(&file.path)
+                    .map(|description| format!("- {}：{}", file.path, description))
+            })
+            .collect();
+        if !file_types.is_empty() {
+            context.push_str(&format!("\n文件类型:\n{}\n", file_types.join("\n")));
+        }
+
+        for language in languages {
+            let focus = analyzer_for(language).review_focus();
+            context.push_str(&format!("\n{} 审查重点:\n", language.as_str()));
+            for item in focus {
+                context.push_str(&format!("- {}\n", item));
+            }
+        }
+        if self.build_files.iter().any(|b| b.has_dependency_changes()) {
+            context.push_str("\n构建文件审查重点:\n- 新增依赖的来源、许可证和版本范围是否合理\n");
+        }
+        if !self.typescript_issues.is_empty() {
+            context.push_str("\n本地规则发现的问题（请核实并给出修复建议）:\n");
+            for issue in &self.typescript_issues {
+                context.push_str(&format!("- {}\n", issue));
+            }
+        }
+
+        context
+    }
+}
+
+/// 逐行收集单个文件的信息
+enum FileCollector {
+    Source(SourceCollector),
+    Build(java::BuildFileChange),
+}
+
+impl FileCollector {
+    fn new(path: &str) -> Option<Self> {
+        if let Some(system) = java::BuildSystem::from_path(path) {
+            return Some(FileCollector::Build(java::BuildFileChange::new(
+                path, system,
+            )));
+        }
+        let language = Language::from_path(path)?;
+        Some(FileCollector::Source(SourceCollector::new(path, language)))
+    }
+
+    fn push_line(&mut self, line: &str) {
+        if line.starts_with("+++") || line.starts_with("---") {
+            return;
+        }
+
+        match self {
+            FileCollector::Source(source) => source.push_line(line),
+            FileCollector::Build(build) => {
+                if let Some(code) = line.strip_prefix('+') {
+                    build.record_line(code, true);
+                } else if let Some(code) = line.strip_prefix('-') {
+                    build.record_line(code, false);
+                }
+            }
+        }
+    }
+
+    fn finish(self, summary: &mut LanguageSummary) {
+        match self {
+            FileCollector::Source(source) => summary.files.push(source.finish()),
+            FileCollector::Build(build) => summary.build_files.push(build),
+        }
+    }
+}
+
+/// 源文件的符号收集
+///
+/// 有语法后端时按 hunk 还原新旧两侧的代码片段再解析，只把包含修改行的方法记为变更；
+/// 否则逐行用正则提取变更行中声明或使用的符号。
+struct SourceCollector {
+    path: String,
+    analyzer: Box<dyn LanguageAnalyzer>,
+    backend: Option<Box<dyn SyntaxBackend>>,
+    changed: Vec<Symbol>,
+    enclosing: Vec<Symbol>,
+    old_side: Fragment,
+    new_side: Fragment,
+}
+
+impl SourceCollector {
+    fn new(path: &str, language: Language) -> Self {
+        Self {
+            path: path.to_string(),
+            analyzer: analyzer_for(language),
+            backend: syntax::backend_for(language),
+            changed: Vec::new(),
+            enclosing: Vec::new(),
+            old_side: Fragment::default(),
+            new_side: Fragment::default(),
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        if let Some(captures) = HUNK_CONTEXT_REGEX.captures(line) {
+            self.flush_hunk();
+            push_unique(
+                &mut self.enclosing,
+                self.analyzer.extract_symbols(&captures[1]),
+            );
+            return;
+        }
+
+        if self.backend.is_none() {
+            if let Some(code) = line.strip_prefix(['+', '-']) {
+                push_unique(&mut self.changed, self.analyzer.extract_symbols(code));
+            }
+            return;
+        }
+
+        if let Some(code) = line.strip_prefix('+') {
+            self.new_side.push(code, true);
+        } else if let Some(code) = line.strip_prefix('-') {
+            self.old_side.push(code, true);
+        } else if let Some(code) = line.strip_prefix(' ') {
+            self.old_side.push(code, false);
+            self.new_side.push(code, false);
+        }
+    }
+
+    fn flush_hunk(&mut self) {
+        let old_side = std::mem::take(&mut self.old_side);
+        let new_side = std::mem::take(&mut self.new_side);
+        let Some(backend) = &self.backend else {
+            return;
+        };
+
+        for fragment in [new_side, old_side] {
+            if !fragment.has_changes() {
+                continue;
+            }
+            let spans = backend.parse_symbols(&fragment.source());
+            let (changed, enclosing) = syntax::classify_spans(&spans, &fragment.changed);
+            push_unique(&mut self.changed, changed);
+            push_unique(&mut self.enclosing, enclosing);
+        }
+    }
+
+    fn finish(mut self) -> FileSymbols {
+        self.flush_hunk();
+        FileSymbols {
+            path: self.path,
+            language: self.analyzer.language(),
+            changed: self.changed,
+            enclosing: self.enclosing,
+        }
+    }
+}
+
+fn push_unique(target: &mut Vec<Symbol>, symbols: Vec<Symbol>) {
+    for symbol in symbols {
+        if !target.contains(&symbol) {
+            target.push(symbol);
+        }
+    }
+}
+
+/// 按类型分组描述符号，如 `类 UserService；方法 findById, save；位于 UserService`
+fn describe_symbols(changed: &[Symbol], enclosing: &[Symbol]) -> String {
+    let kinds = [
+        SymbolKind::Class,
+        SymbolKind::Interface,
+        SymbolKind::Enum,
+        SymbolKind::Object,
+        SymbolKind::Component,
+        SymbolKind::Method,
+        SymbolKind::Annotation,
+    ];
+
+    let mut parts = Vec::new();
+    for kind in kinds {
+        let names: Vec<String> = changed
+            .iter()
+            .filter(|symbol| symbol.kind == kind)
+            .map(|symbol| match kind {
+                SymbolKind::Annotation => format!("@{}", symbol.name),
+                _ => symbol.name.clone(),
+            })
+            .collect();
+        if !names.is_empty() {
+            parts.push(format!("{} {}", kind.label(), names.join(", ")));
+        }
+    }
+
+    let enclosing: Vec<&str> = enclosing
+        .iter()
+        .filter(|symbol| !changed.contains(symbol))
+        .map(|symbol| symbol.name.as_str())
+        .collect();
+    if !enclosing.is_empty() {
+        parts.push(format!("位于 {}", enclosing.join(", ")));
+    }
+
+    parts.join("；")
+}
+
+/// JVM 源文件的作用域：取 `src/<set>/<java|kotlin>/` 之后包路径的最后一级目录
+pub(crate) fn jvm_package_scope(path: &str) -> Option<String> {
+    let parts: Vec<&str> = path.split('/').collect();
+    let root = parts
+        .windows(3)
+        .position(|w| w[0] == "src" && (w[2] == "java" || w[2] == "kotlin"))
+        .map(|i| i + 3)
+        .unwrap_or(0);
+
+    let dirs = &parts[root..parts.len().saturating_sub(1)];
+    dirs.last().map(|dir| dir.to_string())
+}
+
+fn is_gradle_script(path: &str) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    file_name.ends_with(".gradle.kts")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JVM_DIFF: &str = "diff --git a/src/main/java/com/acme/user/UserService.java b/src/main/java/com/acme/user/UserService.java\n\
+        --- a/src/main/java/com/acme/user/UserService.java\n\
+        +++ b/src/main/java/com/acme/user/UserService.java\n\
+        @@ -10,7 +10,9 @@ public class UserService {\n\
+        +    @Transactional\n\
+        +    public User save(User user) {\n\
+        -    public void remove(long id) {\n\
+        diff --git a/pom.xml b/pom.xml\n\
+        --- a/pom.xml\n\
+        +++ b/pom.xml\n\
+        @@ -20,6 +20,10 @@\n\
+        +        <dependency>\n\
+        +            <groupId>org.springframework.boot</groupId>\n\
+        +            <artifactId>spring-boot-starter-web</artifactId>\n\
+        +        </dependency>\n";
+
+    #[test]
+    fn test_language_from_path() {
+        assert_eq!(Language::from_path("src/A.java"), Some(Language::Java));
+        assert_eq!(Language::from_path("src/A.kt"), Some(Language::Kotlin));
+        assert_eq!(Language::from_path("build.gradle.kts"), None);
+        assert_eq!(
+            Language::from_path("web/src/App.tsx"),
+            Some(Language::TypeScript)
+        );
+        assert_eq!(
+            Language::from_path("web/src/index.mjs"),
+            Some(Language::JavaScript)
+        );
+        assert_eq!(Language::from_path("static/app.min.js"), None);
+        assert_eq!(Language::from_path("src/main.rs"), None);
+    }
+
+    #[test]
+    fn test_summary_from_diff() {
+        let summary = LanguageSummary::from_diff(JVM_DIFF);
+
+        assert_eq!(summary.files.len(), 1);
+        let file = &summary.files[0];
+        assert_eq!(file.language, Language::Java);
+        assert!(file
+            .changed
+            .contains(&Symbol::new(SymbolKind::Method, "save")));
+        assert!(file
+            .changed
+            .contains(&Symbol::new(SymbolKind::Method, "remove")));
+        assert!(file
+            .changed
+            .contains(&Symbol::new(SymbolKind::Annotation, "Transactional")));
+        assert_eq!(
+            file.enclosing,
+            vec![Symbol::new(SymbolKind::Class, "UserService")]
+        );
+
+        assert_eq!(summary.build_files.len(), 1);
+        assert_eq!(
+            summary.build_files[0].added_dependencies,
+            vec!["org.springframework.boot:spring-boot-starter-web".to_string()]
+        );
+
+        let backends = summary.describe_backends().unwrap();
+        assert_eq!(
+            backends,
+            format!("Java: {}", syntax::backend_name(Language::Java))
+        );
+        assert_eq!(LanguageSummary::default().describe_backends(), None);
+    }
+
+    #[test]
+    fn test_prompt_and_review_context() {
+        let summary = LanguageSummary::from_diff(JVM_DIFF);
+
+        let context = summary.to_prompt_context();
+        assert!(context.contains("代码结构变更"));
+        assert!(context.contains("方法 save, remove"));
+        assert!(context.contains("注解 @Transactional"));
+        assert!(context.contains("位于 UserService"));
+        assert!(context.contains("spring-boot-starter-web"));
+
+        let review = summary.to_review_context();
+        assert!(review.contains("Java 审查重点"));
+        assert!(review.contains("构建文件审查重点"));
+
+        let tsx = LanguageSummary::from_diff(
+            "diff --git a/web/src/UserList.tsx b/web/src/UserList.tsx\n\
+            @@ -1,2 +1,4 @@\n\
+            +export function UserList() {\n\
+            +  useEffect(() => load());\n",
+        );
+        assert_eq!(
+            tsx.files[0].changed,
+            vec![Symbol::new(SymbolKind::Component, "UserList")]
+        );
+        let review = tsx.to_review_context();
+        assert!(review.contains("web/src/UserList.tsx：React 组件（JSX）"));
+        assert!(review.contains("TypeScript 审查重点"));
+        assert!(review.contains("useEffect 没有依赖数组"));
+
+        assert!(LanguageSummary::from_diff("diff --git a/a.rs b/a.rs\n+x\n")
+            .to_prompt_context()
+            .is_empty());
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_tree_sitter_detects_changed_method_body() {
+        let diff =
+            "diff --git a/src/main/java/com/acme/Order.java b/src/main/java/com/acme/Order.java\n\
+            @@ -3,6 +3,6 @@ public class Order {\n\
+            \x20   public long total() {\n\
+            -        return price;\n\
+            +        return price * quantity;\n\
+            \x20   }\n\
+            \x20   public void cancel() {\n\
+            \x20   }\n";
+
+        let summary = LanguageSummary::from_diff(diff);
+        assert_eq!(
+            summary.files[0].changed,
+            vec![Symbol::new(SymbolKind::Method, "total")]
+        );
+    }
+
+    #[test]
+    fn test_scope_for_path() {
+        assert_eq!(
+            scope_for_path("src/main/java/com/acme/user/UserService.java"),
+            Some("user".to_string())
+        );
+        assert_eq!(
+            scope_for_path("app/src/test/kotlin/com/acme/billing/InvoiceTest.kt"),
+            Some("billing".to_string())
+        );
+        assert_eq!(scope_for_path("pom.xml"), Some("build".to_string()));
+        assert_eq!(
+            scope_for_path("app/build.gradle.kts"),
+            Some("build".to_string())
+        );
+        assert_eq!(scope_for_path("Main.java"), None);
+        assert_eq!(scope_for_path("src/main.rs"), None);
+        assert_eq!(
+            scope_for_path("packages/ui/src/Button.tsx"),
+            Some("ui".to_string())
+        );
+        assert_eq!(scope_for_path("web/src/App.tsx"), None);
+    }
+}
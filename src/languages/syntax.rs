@@ -0,0 +1,260 @@
+//! 可选的语法解析后端
+//!
+//! 启用 `tree-sitter` feature 后，对有语法支持的语言（目前只有 Java）用 tree-sitter
+//! 解析 diff 中的代码片段，得到带行号范围的符号，从而判断哪些方法真正被修改。
+//! Kotlin、TypeScript 和 JavaScript 尚未接入语法，即使启用 feature 也使用正则。
+//! 未启用或没有对应语法时 `backend_for` 返回 `None`，调用方退回逐行正则匹配，
+//! `backend_name` 给出每种语言实际使用的后端，调试模式下会输出。
+
+use super::{Language, Symbol, SymbolKind};
+
+/// 带位置的符号，行号从 0 开始、相对于被解析的片段
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolSpan {
+    pub symbol: Symbol,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// 符号名称所在行（类声明前可能有注解和修饰符）
+    pub name_line: usize,
+}
+
+impl SymbolSpan {
+    fn contains(&self, line: usize) -> bool {
+        (self.start_line..=self.end_line).contains(&line)
+    }
+}
+
+/// 语法解析后端
+pub trait SyntaxBackend: Send + Sync {
+    /// 解析源码片段，返回其中声明的类型、方法和注解
+    fn parse_symbols(&self, source: &str) -> Vec<SymbolSpan>;
+}
+
+/// 获取语言对应的语法后端
+pub fn backend_for(language: Language) -> Option<Box<dyn SyntaxBackend>> {
+    #[cfg(feature = "tree-sitter")]
+    {
+        treesitter::backend_for(language)
+    }
+    #[cfg(not(feature = "tree-sitter"))]
+    {
+        let _ = language;
+        None
+    }
+}
+
+/// 语言实际使用的符号提取后端：`tree-sitter` 或 `regex`
+pub fn backend_name(language: Language) -> &'static str {
+    if backend_for(language).is_some() {
+        "tree-sitter"
+    } else {
+        "regex"
+    }
+}
+
+/// diff 中一侧（新增或删除）的连续代码片段
+#[derive(Debug, Clone, Default)]
+pub struct Fragment {
+    pub lines: Vec<String>,
+    /// 被修改的行在 `lines` 中的下标
+    pub changed: Vec<usize>,
+}
+
+impl Fragment {
+    pub fn push(&mut self, line: &str, changed: bool) {
+        if changed {
+            self.changed.push(self.lines.len());
+        }
+        self.lines.push(line.to_string());
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !self.changed.is_empty()
+    }
+
+    pub fn source(&self) -> String {
+        self.lines.join("\n")
+    }
+}
+
+/// 根据修改行划分符号：返回（被修改的符号，包含修改的外层类型）
+///
+/// 方法只要包含修改行即视为被修改；类型只有声明行被修改时才算修改，否则作为外层上下文。
+pub fn classify_spans(spans: &[SymbolSpan], changed_lines: &[usize]) -> (Vec<Symbol>, Vec<Symbol>) {
+    let mut changed = Vec::new();
+    let mut enclosing = Vec::new();
+
+    for span in spans {
+        if !changed_lines.iter().any(|line| span.contains(*line)) {
+            continue;
+        }
+
+        let target = match span.symbol.kind {
+            SymbolKind::Method | SymbolKind::Annotation => &mut changed,
+            _ if changed_lines.contains(&span.name_line) => &mut changed,
+            _ => &mut enclosing,
+        };
+        if !target.contains(&span.symbol) {
+            target.push(span.symbol.clone());
+        }
+    }
+
+    (changed, enclosing)
+}
+
+#[cfg(feature = "tree-sitter")]
+mod treesitter {
+    use super::{SymbolSpan, SyntaxBackend};
+    use crate::languages::{Language, Symbol, SymbolKind};
+    use tree_sitter::{Node, Parser};
+
+    pub fn backend_for(language: Language) -> Option<Box<dyn SyntaxBackend>> {
+        match language {
+            Language::Java => Some(Box::new(JavaSyntax)),
+            // 暂无可用的 Kotlin、TypeScript 语法，使用正则
+            Language::Kotlin | Language::TypeScript | Language::JavaScript => None,
+        }
+    }
+
+    struct JavaSyntax;
+
+    impl SyntaxBackend for JavaSyntax {
+        fn parse_symbols(&self, source: &str) -> Vec<SymbolSpan> {
+            let mut parser = Parser::new();
+            if parser
+                .set_language(&tree_sitter_java::LANGUAGE.into())
+                .is_err()
+            {
+                return Vec::new();
+            }
+            let Some(tree) = parser.parse(source, None) else {
+                return Vec::new();
+            };
+
+            let mut spans = Vec::new();
+            collect(tree.root_node(), source.as_bytes(), &mut spans);
+            spans
+        }
+    }
+
+    fn symbol_kind(node_kind: &str) -> Option<SymbolKind> {
+        match node_kind {
+            "class_declaration" | "record_declaration" => Some(SymbolKind::Class),
+            "interface_declaration" | "annotation_type_declaration" => Some(SymbolKind::Interface),
+            "enum_declaration" => Some(SymbolKind::Enum),
+            "method_declaration"
+            | "constructor_declaration"
+            | "compact_constructor_declaration" => Some(SymbolKind::Method),
+            "marker_annotation" | "annotation" => Some(SymbolKind::Annotation),
+            _ => None,
+        }
+    }
+
+    fn collect(node: Node, source: &[u8], spans: &mut Vec<SymbolSpan>) {
+        if let Some(kind) = symbol_kind(node.kind()) {
+            if let Some(name) = node.child_by_field_name("name") {
+                if let Ok(text) = name.utf8_text(source) {
+                    spans.push(SymbolSpan {
+                        symbol: Symbol::new(kind, text),
+                        start_line: node.start_position().row,
+                        end_line: node.end_position().row,
+                        name_line: name.start_position().row,
+                    });
+                }
+            }
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            collect(child, source, spans);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_java_spans() {
+            let source = "public class UserService {\n\
+                \x20   @Transactional\n\
+                \x20   public User save(User user) {\n\
+                \x20       return repository.save(user);\n\
+                \x20   }\n\
+                }";
+            let spans = JavaSyntax.parse_symbols(source);
+
+            let class = spans
+                .iter()
+                .find(|s| s.symbol.name == "UserService")
+                .unwrap();
+            assert_eq!(class.symbol.kind, SymbolKind::Class);
+            assert_eq!((class.start_line, class.end_line), (0, 5));
+
+            let method = spans.iter().find(|s| s.symbol.name == "save").unwrap();
+            assert_eq!(method.symbol.kind, SymbolKind::Method);
+            assert_eq!(method.name_line, 2);
+            assert_eq!(method.end_line, 4);
+
+            assert!(spans
+                .iter()
+                .any(|s| s.symbol == Symbol::new(SymbolKind::Annotation, "Transactional")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(kind: SymbolKind, name: &str, start: usize, end: usize) -> SymbolSpan {
+        SymbolSpan {
+            symbol: Symbol::new(kind, name),
+            start_line: start,
+            end_line: end,
+            name_line: start,
+        }
+    }
+
+    #[test]
+    fn test_classify_spans() {
+        let spans = vec![
+            span(SymbolKind::Class, "UserService", 0, 20),
+            span(SymbolKind::Method, "save", 2, 6),
+            span(SymbolKind::Method, "delete", 8, 12),
+        ];
+
+        let (changed, enclosing) = classify_spans(&spans, &[4]);
+        assert_eq!(changed, vec![Symbol::new(SymbolKind::Method, "save")]);
+        assert_eq!(
+            enclosing,
+            vec![Symbol::new(SymbolKind::Class, "UserService")]
+        );
+
+        let (changed, _) = classify_spans(&spans, &[0]);
+        assert_eq!(changed, vec![Symbol::new(SymbolKind::Class, "UserService")]);
+    }
+
+    #[test]
+    fn test_fragment() {
+        let mut fragment = Fragment::default();
+        fragment.push("class A {", false);
+        fragment.push("  void b() {}", true);
+
+        assert!(fragment.has_changes());
+        assert_eq!(fragment.changed, vec![1]);
+        assert_eq!(fragment.source(), "class A {\n  void b() {}");
+    }
+
+    #[test]
+    fn test_backend_name() {
+        // Kotlin 没有语法后端，始终使用正则
+        assert_eq!(backend_name(Language::Kotlin), "regex");
+        let java = if cfg!(feature = "tree-sitter") {
+            "tree-sitter"
+        } else {
+            "regex"
+        };
+        assert_eq!(backend_name(Language::Java), java);
+    }
+}
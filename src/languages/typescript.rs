@@ -0,0 +1,668 @@
+//! TypeScript / JavaScript 分析器与 React 相关的审查规则
+//!
+//! 除了提取类、函数和组件符号，还对新增代码做几项框架相关的本地检查：
+//! Hook 缺少依赖数组、列表渲染缺少 `key`、`any` 类型增多、Promise 未被 await。
+//! 检查结果作为审查发现项，同时写入审查提示词让 AI 核实。
+
+use super::{Language, LanguageAnalyzer, Symbol, SymbolKind};
+use crate::report::{ReviewFinding, Severity};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+
+static FILE_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^diff --git a/(.+?) b/(.+?)$").unwrap());
+
+static HUNK_HEADER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^@@ -\d+(?:,\d+)? \+(\d+)(?:,\d+)? @@").unwrap());
+
+static CLASS_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+([A-Za-z_$][\w$]*)")
+        .unwrap()
+});
+
+static TYPE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:export\s+)?(?:declare\s+)?(?:const\s+)?(interface|type|enum)\s+([A-Za-z_$][\w$]*)",
+    )
+    .unwrap()
+});
+
+static FUNCTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)")
+        .unwrap()
+});
+
+static ARROW_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:export\s+)?(?:const|let)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:\([^)]*\)|[A-Za-z_$][\w$]*)\s*(?::[^=]+)?=>",
+    )
+    .unwrap()
+});
+
+static METHOD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:public|private|protected|static|async|readonly|override|get|set)\s+)*([A-Za-z_$][\w$]*)\s*\([^)]*\)\s*(?::\s*[^{=]+)?\{\s*$",
+    )
+    .unwrap()
+});
+
+/// 看起来像方法声明、实际是控制语句的关键字
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "function", "return", "with",
+];
+
+/// 需要依赖数组的 Hook
+static HOOK_CALL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(useEffect|useLayoutEffect|useCallback|useMemo)\s*\(").unwrap());
+
+static MAP_CALL_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\.map\s*\(").unwrap());
+
+/// JSX 元素或片段的开始
+static JSX_OPEN_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<(?:[A-Za-z][\w.]*[\s>/]|>)").unwrap());
+
+static ANY_TYPE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r":\s*any\b|\bas\s+any\b|<any>|\bany\[\]").unwrap());
+
+/// 声明为 async 的函数
+static ASYNC_DECLARATION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:async\s+function\s+([A-Za-z_$][\w$]*)|(?:const|let)\s+([A-Za-z_$][\w$]*)\s*=\s*async\b|^\s*(?:(?:public|private|protected|static)\s+)*async\s+([A-Za-z_$][\w$]*)\s*\()",
+    )
+    .unwrap()
+});
+
+/// 作为独立语句的函数调用，如 `saveUser(user);`、`this.api.fetchAsync();`
+static CALL_STATEMENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:this\.)?((?:[A-Za-z_$][\w$]*\.)*([A-Za-z_$][\w$]*))\s*\(").unwrap()
+});
+
+static ASYNC_FOREACH_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\.forEach\s*\(\s*async\b").unwrap());
+
+/// 多行结构最多向后查看的行数
+const MAX_LOOKAHEAD_LINES: usize = 40;
+
+/// TypeScript / JavaScript 分析器
+pub struct TypeScriptAnalyzer(pub Language);
+
+impl LanguageAnalyzer for TypeScriptAnalyzer {
+    fn language(&self) -> Language {
+        self.0
+    }
+
+    fn extract_symbols(&self, line: &str) -> Vec<Symbol> {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
+            return Vec::new();
+        }
+
+        if let Some(captures) = CLASS_REGEX.captures(line) {
+            return vec![Symbol::new(SymbolKind::Class, &captures[1])];
+        }
+        if let Some(captures) = TYPE_REGEX.captures(line) {
+            let kind = match &captures[1] {
+                "enum" => SymbolKind::Enum,
+                _ => SymbolKind::Interface,
+            };
+            return vec![Symbol::new(kind, &captures[2])];
+        }
+        if let Some(captures) = FUNCTION_REGEX
+            .captures(line)
+            .or_else(|| ARROW_REGEX.captures(line))
+        {
+            return vec![function_symbol(&captures[1])];
+        }
+        if let Some(captures) = METHOD_REGEX.captures(line) {
+            let name = &captures[1];
+            if !STATEMENT_KEYWORDS.contains(&name) {
+                return vec![Symbol::new(SymbolKind::Method, name)];
+            }
+        }
+        Vec::new()
+    }
+
+    fn scope_for_path(&self, path: &str) -> Option<String> {
+        // monorepo 中按包名归类，其余交给通用的目录规则
+        let parts: Vec<&str> = path.split('/').collect();
+        parts
+            .windows(3)
+            .find(|w| w[0] == "packages" || w[0] == "apps")
+            .map(|w| w[1].to_string())
+    }
+
+    fn review_focus(&self) -> &'static [&'static str] {
+        match self.0 {
+            Language::TypeScript => &[
+                "类型安全：新增的 any、as 断言和 @ts-ignore 是否必要",
+                "Promise：未 await 的异步调用、未处理的 rejection、forEach 中的 async 回调",
+                "React Hook：依赖数组是否完整，Hook 是否在条件或循环中调用",
+                "列表渲染是否提供稳定的 key（不要使用数组下标）",
+                "可选链和空值合并是否掩盖了本应报错的空值",
+            ],
+            _ => &[
+                "Promise：未 await 的异步调用、未处理的 rejection、forEach 中的 async 回调",
+                "React Hook：依赖数组是否完整，Hook 是否在条件或循环中调用",
+                "列表渲染是否提供稳定的 key（不要使用数组下标）",
+                "== 与 === 的混用和隐式类型转换",
+            ],
+        }
+    }
+
+    fn file_context(&self, path: &str) -> Option<&'static str> {
+        if path.ends_with(".d.ts") {
+            Some("类型声明文件")
+        } else if path.contains(".test.") || path.contains(".spec.") {
+            Some("测试文件")
+        } else if is_jsx_file(path) {
+            Some("React 组件（JSX）")
+        } else {
+            None
+        }
+    }
+}
+
+/// 首字母大写的函数按 React 组件处理，`use` 开头的按自定义 Hook 处理
+fn function_symbol(name: &str) -> Symbol {
+    if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        Symbol::new(SymbolKind::Component, name)
+    } else {
+        Symbol::new(SymbolKind::Method, name)
+    }
+}
+
+fn is_jsx_file(path: &str) -> bool {
+    path.ends_with(".tsx") || path.ends_with(".jsx")
+}
+
+/// 本地检查发现的问题类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeScriptIssueKind {
+    /// Hook 没有依赖数组
+    HookWithoutDeps { hook: String },
+    /// 列表渲染的元素没有 key
+    MissingKey,
+    /// 新增的 any 多于删除的
+    AnyTypeCreep { added: usize, removed: usize },
+    /// 异步函数调用的结果没有被 await
+    UnawaitedPromise { call: String },
+    /// forEach 的回调是 async 函数，不会等待其完成
+    AsyncForEach,
+}
+
+/// TypeScript / JavaScript 本地检查发现的问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeScriptIssue {
+    pub kind: TypeScriptIssueKind,
+    pub severity: Severity,
+    pub file: String,
+    /// 新文件中的行号
+    pub line: u32,
+}
+
+impl fmt::Display for TypeScriptIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.file, self.line)?;
+        match &self.kind {
+            TypeScriptIssueKind::HookWithoutDeps { hook } => {
+                write!(f, "{} 没有依赖数组，每次渲染都会重新执行", hook)
+            }
+            TypeScriptIssueKind::MissingKey => {
+                write!(f, "map 渲染的列表元素缺少 key")
+            }
+            TypeScriptIssueKind::AnyTypeCreep { added, removed } => write!(
+                f,
+                "新增 {} 处 any 类型（删除 {} 处），类型检查被削弱",
+                added, removed
+            ),
+            TypeScriptIssueKind::UnawaitedPromise { call } => {
+                write!(f, "异步调用 {} 的结果没有 await，错误会被忽略", call)
+            }
+            TypeScriptIssueKind::AsyncForEach => {
+                write!(
+                    f,
+                    "forEach 不会等待 async 回调完成，改用 for...of 或 Promise.all"
+                )
+            }
+        }
+    }
+}
+
+impl TypeScriptIssue {
+    fn new(kind: TypeScriptIssueKind, file: &str, line: u32) -> Self {
+        let severity = match kind {
+            TypeScriptIssueKind::AnyTypeCreep { .. } => Severity::Low,
+            _ => Severity::Medium,
+        };
+        Self {
+            kind,
+            severity,
+            file: file.to_string(),
+            line,
+        }
+    }
+
+    /// 转换为审查报告中的发现项
+    pub fn to_finding(&self) -> ReviewFinding {
+        let category = match self.kind {
+            TypeScriptIssueKind::HookWithoutDeps { .. } | TypeScriptIssueKind::MissingKey => {
+                "react"
+            }
+            TypeScriptIssueKind::AnyTypeCreep { .. } => "type-safety",
+            _ => "async",
+        };
+        ReviewFinding {
+            severity: self.severity,
+            category: category.to_string(),
+            message: self.to_string(),
+            file: Some(self.file.clone()),
+            line: Some(self.line),
+        }
+    }
+}
+
+/// 对 diff 中 TypeScript / JavaScript 文件的新增代码运行本地检查
+pub fn scan_diff(diff: &str) -> Vec<TypeScriptIssue> {
+    let mut issues = Vec::new();
+    let mut current: Option<FileScan> = None;
+
+    for line in diff.lines() {
+        if let Some(captures) = FILE_HEADER_REGEX.captures(line) {
+            if let Some(scan) = current.take() {
+                scan.finish(&mut issues);
+            }
+            let path = &captures[2];
+            current = Language::from_path(path)
+                .filter(|language| matches!(language, Language::TypeScript | Language::JavaScript))
+                .map(|_| FileScan::new(path));
+            continue;
+        }
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if let Some(scan) = current.as_mut() {
+            scan.push_line(line);
+        }
+    }
+    if let Some(scan) = current {
+        scan.finish(&mut issues);
+    }
+    issues
+}
+
+/// 新文件中的一行
+struct NewLine {
+    number: u32,
+    code: String,
+    added: bool,
+}
+
+/// 单个文件的扫描状态：保存新文件一侧的代码，文件结束时统一检查
+struct FileScan {
+    path: String,
+    lines: Vec<NewLine>,
+    next_line: u32,
+    removed_any: usize,
+}
+
+impl FileScan {
+    fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            lines: Vec::new(),
+            next_line: 1,
+            removed_any: 0,
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        if let Some(captures) = HUNK_HEADER_REGEX.captures(line) {
+            self.next_line = captures[1].parse().unwrap_or(1);
+            // hunk 之间不连续，插入空行避免跨 hunk 匹配括号
+            self.lines.push(NewLine {
+                number: 0,
+                code: String::new(),
+                added: false,
+            });
+            return;
+        }
+
+        let (code, added) = if let Some(code) = line.strip_prefix('+') {
+            (code, true)
+        } else if let Some(code) = line.strip_prefix(' ') {
+            (code, false)
+        } else {
+            if let Some(code) = line.strip_prefix('-') {
+                self.removed_any += ANY_TYPE_REGEX.find_iter(code).count();
+            }
+            return;
+        };
+
+        self.lines.push(NewLine {
+            number: self.next_line,
+            code: code.to_string(),
+            added,
+        });
+        self.next_line += 1;
+    }
+
+    fn finish(self, issues: &mut Vec<TypeScriptIssue>) {
+        let jsx = is_jsx_file(&self.path);
+        let typed = Language::from_path(&self.path) == Some(Language::TypeScript);
+        let async_functions = self.async_functions();
+
+        let mut added_any = 0;
+        let mut first_any_line = None;
+
+        for (i, line) in self.lines.iter().enumerate() {
+            if !line.added {
+                continue;
+            }
+            let code = line.code.as_str();
+            let trimmed = code.trim_start();
+            if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
+                continue;
+            }
+
+            for captures in HOOK_CALL_REGEX.captures_iter(code) {
+                let start = captures.get(0).map(|m| m.end()).unwrap_or_default();
+                if let Some(args) = self.call_arguments(i, start) {
+                    if args.top_level_commas == 0 {
+                        issues.push(TypeScriptIssue::new(
+                            TypeScriptIssueKind::HookWithoutDeps {
+                                hook: captures[1].to_string(),
+                            },
+                            &self.path,
+                            line.number,
+                        ));
+                    }
+                }
+            }
+
+            if jsx {
+                for m in MAP_CALL_REGEX.find_iter(code) {
+                    if let Some(args) = self.call_arguments(i, m.end()) {
+                        if JSX_OPEN_REGEX.is_match(&args.text) && !args.text.contains("key=") {
+                            issues.push(TypeScriptIssue::new(
+                                TypeScriptIssueKind::MissingKey,
+                                &self.path,
+                                line.number,
+                            ));
+                        }
+                    }
+                }
+            }
+
+            if typed {
+                let count = ANY_TYPE_REGEX.find_iter(code).count();
+                if count > 0 {
+                    added_any += count;
+                    first_any_line.get_or_insert(line.number);
+                }
+            }
+
+            if ASYNC_FOREACH_REGEX.is_match(code) {
+                issues.push(TypeScriptIssue::new(
+                    TypeScriptIssueKind::AsyncForEach,
+                    &self.path,
+                    line.number,
+                ));
+            } else if let Some(call) = unawaited_call(code, &async_functions) {
+                issues.push(TypeScriptIssue::new(
+                    TypeScriptIssueKind::UnawaitedPromise { call },
+                    &self.path,
+                    line.number,
+                ));
+            }
+        }
+
+        if added_any > self.removed_any {
+            issues.push(TypeScriptIssue::new(
+                TypeScriptIssueKind::AnyTypeCreep {
+                    added: added_any,
+                    removed: self.removed_any,
+                },
+                &self.path,
+                first_any_line.unwrap_or_default(),
+            ));
+        }
+    }
+
+    /// 文件中（新文件一侧）声明为 async 的函数名
+    fn async_functions(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for line in &self.lines {
+            for captures in ASYNC_DECLARATION_REGEX.captures_iter(&line.code) {
+                if let Some(name) = captures
+                    .get(1)
+                    .or_else(|| captures.get(2))
+                    .or_else(|| captures.get(3))
+                {
+                    if !names.iter().any(|existing| existing == name.as_str()) {
+                        names.push(name.as_str().to_string());
+                    }
+                }
+            }
+        }
+        names
+    }
+
+    /// 从第 `line` 行的 `start` 列（左括号之后）开始，收集到匹配的右括号为止的调用参数
+    fn call_arguments(&self, line: usize, start: usize) -> Option<CallArguments> {
+        let mut args = CallArguments::default();
+        let mut depth = 1usize;
+        let mut quote: Option<char> = None;
+
+        for (offset, new_line) in self.lines[line..]
+            .iter()
+            .take(MAX_LOOKAHEAD_LINES)
+            .enumerate()
+        {
+            if offset > 0 && new_line.number == 0 {
+                // 到达下一个 hunk，看不到完整的调用
+                return None;
+            }
+            let code = if offset == 0 {
+                new_line.code.get(start..)?
+            } else {
+                new_line.code.as_str()
+            };
+
+            let mut chars = code.chars().peekable();
+            while let Some(c) = chars.next() {
+                if let Some(q) = quote {
+                    if c == '\\' {
+                        chars.next();
+                    } else if c == q {
+                        quote = None;
+                    }
+                    args.text.push(c);
+                    continue;
+                }
+                match c {
+                    '\'' | '"' | '`' => quote = Some(c),
+                    '/' if chars.peek() == Some(&'/') => break,
+                    '(' | '[' | '{' => depth += 1,
+                    ')' | ']' | '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(args);
+                        }
+                    }
+                    ',' if depth == 1 => args.top_level_commas += 1,
+                    _ => {}
+                }
+                args.text.push(c);
+            }
+            args.text.push('\n');
+        }
+        None
+    }
+}
+
+/// 函数调用的参数文本
+#[derive(Debug, Default)]
+struct CallArguments {
+    text: String,
+    top_level_commas: usize,
+}
+
+/// 作为独立语句调用 async 函数或 `fetch` 且没有 await/return/void 时，返回被调用的函数
+fn unawaited_call(code: &str, async_functions: &[String]) -> Option<String> {
+    let captures = CALL_STATEMENT_REGEX.captures(code)?;
+    let callee = &captures[1];
+    let name = &captures[2];
+    if matches!(name, "await" | "return" | "void" | "yield") {
+        return None;
+    }
+
+    let is_async = name == "fetch"
+        || (name.ends_with("Async") && name.len() > "Async".len())
+        || async_functions.iter().any(|function| function == name);
+    // 链式调用已经处理了结果
+    let handled = code.contains(".then(") || code.contains(".catch(");
+    (is_async && !handled).then(|| callee.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::test_support::file_diff;
+
+    fn symbols(line: &str) -> Vec<Symbol> {
+        TypeScriptAnalyzer(Language::TypeScript).extract_symbols(line)
+    }
+
+    fn kinds(diff: &str) -> Vec<TypeScriptIssueKind> {
+        scan_diff(diff)
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_symbols() {
+        assert_eq!(
+            symbols("export default function UserList({ users }: Props) {"),
+            vec![Symbol::new(SymbolKind::Component, "UserList")]
+        );
+        assert_eq!(
+            symbols("export const useUsers = (id: string) => {"),
+            vec![Symbol::new(SymbolKind::Method, "useUsers")]
+        );
+        assert_eq!(
+            symbols("export interface UserProps {"),
+            vec![Symbol::new(SymbolKind::Interface, "UserProps")]
+        );
+        assert_eq!(
+            symbols("  async loadUsers(page: number): Promise<User[]> {"),
+            vec![Symbol::new(SymbolKind::Method, "loadUsers")]
+        );
+        assert!(symbols("  if (users.length === 0) {").is_empty());
+        assert!(symbols("  const total = users.length;").is_empty());
+    }
+
+    #[test]
+    fn test_hook_dependencies() {
+        let diff = file_diff(
+            "src/components/UserList.tsx",
+            "@@ -10,3 +10,9 @@ export function UserList() {\n\
+             +  useEffect(() => {\n\
+             +    loadUsers(page);\n\
+             +  });\n\
+             +  const sorted = useMemo(() => sort(users), [users]);\n\
+             +  const onClick = useCallback(\n\
+             +    () => select(id),\n\
+             +    [id, select],\n\
+             +  );\n",
+        );
+        let issues = scan_diff(&diff);
+        assert_eq!(
+            issues.iter().map(|i| &i.kind).collect::<Vec<_>>(),
+            vec![&TypeScriptIssueKind::HookWithoutDeps {
+                hook: "useEffect".to_string()
+            }]
+        );
+        assert_eq!(issues[0].line, 10);
+        assert_eq!(issues[0].to_finding().category, "react");
+    }
+
+    #[test]
+    fn test_missing_key() {
+        let diff = file_diff(
+            "src/components/UserList.tsx",
+            "@@ -1,2 +1,8 @@\n\
+             +  return <ul>{users.map((user) => (\n\
+             +    <li>{user.name}</li>\n\
+             +  ))}</ul>;\n\
+             +  const rows = users.map((user) => <Row key={user.id} user={user} />);\n\
+             +  const ids = users.map((user) => user.id);\n",
+        );
+        assert_eq!(kinds(&diff), vec![TypeScriptIssueKind::MissingKey]);
+
+        // 非 JSX 文件不检查 key
+        let ts = file_diff(
+            "src/utils.ts",
+            "@@ -1 +1 @@\n+const tags = items.map((i) => `<b>${i}</b>`);\n",
+        );
+        assert!(kinds(&ts).is_empty());
+    }
+
+    #[test]
+    fn test_any_type_creep() {
+        let diff = file_diff(
+            "src/api/client.ts",
+            "@@ -1,2 +1,3 @@\n\
+             -export function parse(data: unknown) {\n\
+             +export function parse(data: any): any {\n\
+             +  return data as any;\n",
+        );
+        assert_eq!(
+            kinds(&diff),
+            vec![TypeScriptIssueKind::AnyTypeCreep {
+                added: 3,
+                removed: 0
+            }]
+        );
+
+        let reduced = file_diff(
+            "src/api/client.ts",
+            "@@ -1 +1 @@\n-function parse(data: any) {\n+function parse(data: Payload) {\n",
+        );
+        assert!(kinds(&reduced).is_empty());
+    }
+
+    #[test]
+    fn test_unawaited_promises() {
+        let diff = file_diff(
+            "src/services/users.js",
+            "@@ -1,6 +1,12 @@\n\
+             \x20async function saveUser(user) {\n\
+             \x20  return api.post('/users', user);\n\
+             \x20}\n\
+             +export async function register(user) {\n\
+             +  saveUser(user);\n\
+             +  await saveUser(user);\n\
+             +  fetch('/audit');\n\
+             +  fetch('/ping').catch(noop);\n\
+             +  users.forEach(async (u) => await saveUser(u));\n\
+             +  logger.info('done');\n\
+             +}\n",
+        );
+        let issues = scan_diff(&diff);
+        assert_eq!(
+            issues.iter().map(|i| &i.kind).collect::<Vec<_>>(),
+            vec![
+                &TypeScriptIssueKind::UnawaitedPromise {
+                    call: "saveUser".to_string()
+                },
+                &TypeScriptIssueKind::UnawaitedPromise {
+                    call: "fetch".to_string()
+                },
+                &TypeScriptIssueKind::AsyncForEach,
+            ]
+        );
+        assert_eq!(issues[0].line, 5);
+    }
+}
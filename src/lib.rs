@@ -1,11 +1,18 @@
+pub mod analysis;
+pub mod cache;
 pub mod cli;
 pub mod commands;
 pub mod config;
 pub mod core;
 pub mod diff_viewer;
 pub mod git;
+pub mod integrations;
 pub mod internationalization;
+pub mod languages;
 pub mod mcp;
 pub mod query_history;
+pub mod recent_repos;
+pub mod report;
+pub mod storage;
 pub mod tui_unified;
 pub mod ui;
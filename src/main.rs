@@ -1,7 +1,8 @@
-use ai_commit::cli::args::Args;
+use ai_commit::cli::args::{Args, Command};
 use ai_commit::commands;
 use ai_commit::config::Config;
 use ai_commit::git;
+use ai_commit::recent_repos::{self, RecentRepos};
 use clap::Parser;
 
 async fn handle_worktree_operations(args: &Args, config: &Config) -> anyhow::Result<bool> {
@@ -136,9 +137,44 @@ async fn handle_worktree_operations(args: &Args, config: &Config) -> anyhow::Res
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+
+    // 切换到指定的仓库，之后的操作都在这个仓库中进行
+    if let Some(repo) = &args.repo {
+        let root = recent_repos::enter_repo(std::path::Path::new(repo))?;
+        // 最近仓库列表只用于 TUI 中快速切换，写入失败不影响命令
+        let _ = RecentRepos::remember(&root);
+    }
+
+    // 导出/导入配置不要求当前配置可用，在校验配置前处理
+    if let Some(Command::Config { action }) = &args.command {
+        return commands::handle_config_subcommand(action);
+    }
+
+    // 本地静态分析不调用 AI，不要求 provider 配置可用
+    if let Some(Command::Analyze { target }) = &args.command {
+        if !commands::handle_analyze_command(target, &args).await? {
+            // 超过阈值时以非零退出码结束，便于在 CI 中使用
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let mut config = Config::new();
 
     config.update_from_args(&args);
+
+    // 查看已保存的审查报告不调用 AI，不要求 provider 配置可用，`review run` 在运行前单独校验
+    if let Some(Command::Review { action }) = &args.command {
+        let action = action.clone().unwrap_or_default();
+        return commands::handle_review_subcommand(&action, &args, &config).await;
+    }
+    if let Some(Command::Report { action }) = &args.command {
+        return commands::handle_report_subcommand(action, &args, &config).await;
+    }
+    if let Some(Command::Storage { action }) = &args.command {
+        return commands::handle_storage_subcommand(action, &config).await;
+    }
+
     config.validate()?;
 
     // 处理 worktree 操作
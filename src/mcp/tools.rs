@@ -131,6 +131,9 @@ async fn tool_generate_commit(args: HashMap<String, Value>) -> ToolCallResult {
             if emoji {
                 message = crate::core::gitmoji::add_emoji(&message);
             }
+            let message = crate::commands::commit::apply_branch_ticket(&message, &config)
+                .await
+                .unwrap_or(message);
             ToolCallResult::text(message)
         }
         Err(e) => ToolCallResult::error(format!("Failed to generate commit message: {}", e)),
@@ -293,6 +296,7 @@ async fn generate_with_agent(diff: &str, config: &crate::config::Config) -> anyh
         env_vars.insert("API_KEY".to_string(), api_key);
     }
     env_vars.insert("API_URL".to_string(), config.get_url());
+    config.insert_conventions(&mut env_vars);
 
     // 注入项目记忆
     let working_dir = std::env::current_dir().unwrap_or_default();
@@ -1,16 +1,29 @@
+//! 查询历史
+//!
+//! 历史记录通过 `StorageManager` 保存在存储层的 `history/queries.json`，
+//! 配置 Redis 或 S3 后端时可以在多台机器间共享。同一查询再次执行时合并为一条记录并累计次数。
+//! 旧版本保存在数据目录 `query_history.json` 中的历史在首次打开时导入。
+
+use crate::config::Config;
+use crate::storage::StorageManager;
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
-use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::Path;
+
+/// 旧版本的历史文件名（位于数据目录下）
+const LEGACY_HISTORY_FILE: &str = "query_history.json";
+
+/// 统计中列出的常用查询数量
+const TOP_QUERIES: usize = 5;
 
 /// 查询历史记录条目
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryHistoryEntry {
     /// 查询内容
     pub query: String,
-    /// 查询时间
+    /// 最近一次执行的时间
     pub timestamp: DateTime<Local>,
     /// 查询类型（可选）
     pub query_type: Option<String>,
@@ -18,119 +31,117 @@ pub struct QueryHistoryEntry {
     pub result_count: Option<usize>,
     /// 是否成功
     pub success: bool,
+    /// 执行次数
+    #[serde(default = "default_run_count")]
+    pub run_count: u32,
+}
+
+fn default_run_count() -> u32 {
+    1
+}
+
+/// 收藏的查询
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FavoriteQuery {
+    /// 查询内容
+    pub query: String,
+    /// 自定义名称（可选）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
 }
 
 /// 查询历史管理器
 pub struct QueryHistory {
-    /// 历史记录列表
+    /// 历史记录列表，最近执行的在后
     entries: VecDeque<QueryHistoryEntry>,
     /// 最大历史记录数
     max_entries: usize,
-    /// 历史文件路径
-    history_file: PathBuf,
+    storage: StorageManager,
 }
 
 impl QueryHistory {
-    /// 创建新的查询历史管理器
-    pub fn new(max_entries: usize) -> anyhow::Result<Self> {
-        let history_file = Self::get_history_file_path()?;
-        let mut history = Self {
-            entries: VecDeque::new(),
-            max_entries,
-            history_file,
-        };
-
-        // 加载现有历史记录
-        history.load_history()?;
-
+    /// 按配置的存储后端打开查询历史
+    pub async fn new(max_entries: usize) -> anyhow::Result<Self> {
+        let storage = StorageManager::from_config(&Config::new())?;
+        let mut history = Self::open(storage, max_entries).await?;
+        if history.entries.is_empty() {
+            history
+                .import_legacy(&crate::config::data_dir()?.join(LEGACY_HISTORY_FILE))
+                .await?;
+        }
         Ok(history)
     }
 
-    /// 使用指定路径创建查询历史管理器（用于测试）
-    #[cfg(test)]
-    fn new_with_path(max_entries: usize, path: PathBuf) -> anyhow::Result<Self> {
-        let mut history = Self {
-            entries: VecDeque::new(),
+    /// 使用指定的存储打开查询历史
+    pub async fn open(storage: StorageManager, max_entries: usize) -> anyhow::Result<Self> {
+        let mut entries = VecDeque::from(storage.load_query_history().await?.unwrap_or_default());
+        // 确保不超过最大数量
+        while entries.len() > max_entries {
+            entries.pop_front();
+        }
+        Ok(Self {
+            entries,
             max_entries,
-            history_file: path,
-        };
-        history.load_history()?;
-        Ok(history)
+            storage,
+        })
     }
 
-    /// 获取历史文件路径
-    fn get_history_file_path() -> anyhow::Result<PathBuf> {
-        let home_dir =
-            dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot find home directory"))?;
-
-        let config_dir = home_dir.join(".ai-commit");
-
-        // 确保目录存在
-        if !config_dir.exists() {
-            fs::create_dir_all(&config_dir)?;
+    /// 导入旧版本的历史文件，导入后改名为 `.bak`，避免重复导入
+    async fn import_legacy(&mut self, path: &Path) -> anyhow::Result<()> {
+        let Ok(content) = tokio::fs::read_to_string(path).await else {
+            return Ok(());
+        };
+        if !content.trim().is_empty() {
+            let entries: Vec<QueryHistoryEntry> = serde_json::from_str(&content)?;
+            for entry in entries {
+                self.push(entry);
+            }
+            self.save_history().await?;
         }
-
-        Ok(config_dir.join("query_history.json"))
+        let _ = tokio::fs::rename(path, path.with_extension("json.bak")).await;
+        Ok(())
     }
 
-    /// 加载历史记录
-    fn load_history(&mut self) -> anyhow::Result<()> {
-        if !self.history_file.exists() {
-            return Ok(());
-        }
+    /// 保存历史记录到存储
+    pub async fn save_history(&self) -> anyhow::Result<()> {
+        let entries: Vec<_> = self.entries.iter().cloned().collect();
+        self.storage.save_query_history(&entries).await
+    }
 
-        let content = fs::read_to_string(&self.history_file)?;
-        if content.trim().is_empty() {
-            return Ok(());
+    /// 添加记录，已有相同查询时合并为一条并移到最后
+    fn push(&mut self, mut entry: QueryHistoryEntry) {
+        if let Some(index) = self.entries.iter().position(|e| e.query == entry.query) {
+            if let Some(previous) = self.entries.remove(index) {
+                entry.run_count = entry.run_count.saturating_add(previous.run_count);
+            }
         }
+        self.entries.push_back(entry);
 
-        let entries: Vec<QueryHistoryEntry> = serde_json::from_str(&content)?;
-        self.entries = VecDeque::from(entries);
-
-        // 确保不超过最大数量
+        // 保持历史记录在限制内
         while self.entries.len() > self.max_entries {
             self.entries.pop_front();
         }
-
-        Ok(())
-    }
-
-    /// 保存历史记录到文件
-    pub fn save_history(&self) -> anyhow::Result<()> {
-        let entries: Vec<_> = self.entries.iter().cloned().collect();
-        let json = serde_json::to_string_pretty(&entries)?;
-        fs::write(&self.history_file, json)?;
-        Ok(())
     }
 
     /// 添加新的查询记录
-    pub fn add_entry(
+    pub async fn add_entry(
         &mut self,
         query: String,
         query_type: Option<String>,
         result_count: Option<usize>,
         success: bool,
     ) -> anyhow::Result<()> {
-        let entry = QueryHistoryEntry {
+        self.push(QueryHistoryEntry {
             query,
             timestamp: Local::now(),
             query_type,
             result_count,
             success,
-        };
-
-        // 添加到历史记录
-        self.entries.push_back(entry);
-
-        // 保持历史记录在限制内
-        while self.entries.len() > self.max_entries {
-            self.entries.pop_front();
-        }
+            run_count: 1,
+        });
 
         // 自动保存
-        self.save_history()?;
-
-        Ok(())
+        self.save_history().await
     }
 
     /// 获取最近的历史记录
@@ -148,10 +159,9 @@ impl QueryHistory {
     }
 
     /// 清空历史记录
-    pub fn clear(&mut self) -> anyhow::Result<()> {
+    pub async fn clear(&mut self) -> anyhow::Result<()> {
         self.entries.clear();
-        self.save_history()?;
-        Ok(())
+        self.save_history().await
     }
 
     /// 获取历史记录统计信息
@@ -167,11 +177,24 @@ impl QueryHistory {
             }
         }
 
+        // 执行次数最多的查询，次数相同时最近执行的在前
+        let mut top_queries: Vec<(String, u32)> = self
+            .entries
+            .iter()
+            .rev()
+            .filter(|e| e.run_count > 1)
+            .map(|e| (e.query.clone(), e.run_count))
+            .collect();
+        top_queries.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        top_queries.truncate(TOP_QUERIES);
+
         QueryHistoryStats {
             total_queries,
+            total_runs: self.entries.iter().map(|e| e.run_count as usize).sum(),
             successful_queries,
             failed_queries,
             query_types,
+            top_queries,
         }
     }
 
@@ -204,6 +227,9 @@ impl QueryHistory {
             if let Some(count) = entry.result_count {
                 println!("   └─ Results: {}", count);
             }
+            if entry.run_count > 1 {
+                println!("   └─ Run {} times", entry.run_count);
+            }
 
             if i < recent.len() - 1 {
                 println!();
@@ -254,13 +280,18 @@ impl QueryHistory {
     }
 }
 
-/// 查询历史统计信息
+/// 查询历史统计信息，成功和失败按每个查询最近一次执行的结果统计
 #[derive(Debug)]
 pub struct QueryHistoryStats {
+    /// 不同查询的数量
     pub total_queries: usize,
+    /// 累计执行次数
+    pub total_runs: usize,
     pub successful_queries: usize,
     pub failed_queries: usize,
     pub query_types: std::collections::HashMap<String, usize>,
+    /// 执行多次的查询及次数，最多的在前
+    pub top_queries: Vec<(String, u32)>,
 }
 
 impl QueryHistoryStats {
@@ -269,6 +300,7 @@ impl QueryHistoryStats {
         println!("📊 Query History Statistics:");
         println!("{}", "─".repeat(40));
         println!("Total queries:      {}", self.total_queries);
+        println!("Total runs:         {}", self.total_runs);
         println!(
             "Successful queries: {} ({:.1}%)",
             self.successful_queries,
@@ -294,85 +326,118 @@ impl QueryHistoryStats {
                 println!("  {}: {}", query_type, count);
             }
         }
+
+        if !self.top_queries.is_empty() {
+            println!("\nMost frequent queries:");
+            for (query, count) in &self.top_queries {
+                println!("  {:>4}× {}", count, query);
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::FileBackend;
 
-    #[test]
-    fn test_query_history_creation() {
-        let history = QueryHistory::new(100);
-        assert!(history.is_ok());
+    async fn history(dir: &tempfile::TempDir, max_entries: usize) -> QueryHistory {
+        let storage = StorageManager::new(Box::new(FileBackend::new(dir.path().to_path_buf())));
+        QueryHistory::open(storage, max_entries).await.unwrap()
     }
 
-    #[test]
-    fn test_add_entry() {
-        let tmp = tempfile::NamedTempFile::new().unwrap();
-        let mut history = QueryHistory::new_with_path(100, tmp.path().to_path_buf()).unwrap();
-        let result = history.add_entry(
+    #[tokio::test]
+    async fn test_add_entry_persists_to_storage() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut h = history(&dir, 100).await;
+        h.add_entry(
             "author:john".to_string(),
             Some("filter".to_string()),
             Some(10),
             true,
-        );
-        assert!(result.is_ok());
-        assert_eq!(history.entries.len(), 1);
+        )
+        .await
+        .unwrap();
+        assert_eq!(h.entries.len(), 1);
+
+        // 重新打开后仍然存在
+        let reopened = history(&dir, 100).await;
+        assert_eq!(reopened.entries.len(), 1);
+        assert_eq!(reopened.entries[0].query, "author:john");
+        assert_eq!(reopened.entries[0].result_count, Some(10));
     }
 
-    #[test]
-    fn test_max_entries_limit() {
-        let tmp = tempfile::NamedTempFile::new().unwrap();
-        let mut history = QueryHistory::new_with_path(3, tmp.path().to_path_buf()).unwrap();
+    #[tokio::test]
+    async fn test_max_entries_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut h = history(&dir, 3).await;
 
         for i in 0..5 {
-            history
-                .add_entry(format!("query {}", i), None, None, true)
+            h.add_entry(format!("query {}", i), None, None, true)
+                .await
                 .unwrap();
         }
 
         // Should only keep last 3 entries
-        assert_eq!(history.entries.len(), 3);
-        assert_eq!(history.entries[0].query, "query 2");
-        assert_eq!(history.entries[2].query, "query 4");
+        assert_eq!(h.entries.len(), 3);
+        assert_eq!(h.entries[0].query, "query 2");
+        assert_eq!(h.entries[2].query, "query 4");
     }
 
-    #[test]
-    fn test_search_history() {
-        let tmp = tempfile::NamedTempFile::new().unwrap();
-        let mut history = QueryHistory::new_with_path(100, tmp.path().to_path_buf()).unwrap();
+    #[tokio::test]
+    async fn test_repeated_query_is_deduplicated() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut h = history(&dir, 100).await;
 
-        history
-            .add_entry("author:john".to_string(), None, None, true)
+        h.add_entry("author:john".to_string(), None, Some(3), true)
+            .await
             .unwrap();
-        history
-            .add_entry("message:feat".to_string(), None, None, true)
+        h.add_entry("message:feat".to_string(), None, None, true)
+            .await
             .unwrap();
-        history
-            .add_entry("author:jane".to_string(), None, None, true)
+        h.add_entry("author:john".to_string(), None, Some(5), false)
+            .await
             .unwrap();
 
-        let results = history.search("author");
+        assert_eq!(h.entries.len(), 2);
+        let latest = h.get_recent(1)[0];
+        assert_eq!(latest.query, "author:john");
+        assert_eq!(latest.run_count, 2);
+        assert_eq!(latest.result_count, Some(5));
+        assert!(!latest.success);
+    }
+
+    #[tokio::test]
+    async fn test_search_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut h = history(&dir, 100).await;
+
+        for query in ["author:john", "message:feat", "author:jane"] {
+            h.add_entry(query.to_string(), None, None, true)
+                .await
+                .unwrap();
+        }
+
+        let results = h.search("author");
         assert_eq!(results.len(), 2);
 
-        let results = history.search("john");
+        let results = h.search("john");
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].query, "author:john");
     }
 
-    #[test]
-    fn test_get_recent() {
-        let tmp = tempfile::NamedTempFile::new().unwrap();
-        let mut history = QueryHistory::new_with_path(100, tmp.path().to_path_buf()).unwrap();
+    #[tokio::test]
+    async fn test_get_recent() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut h = history(&dir, 100).await;
 
         for i in 0..5 {
-            history
-                .add_entry(format!("query {}", i), None, None, true)
+            h.add_entry(format!("query {}", i), None, None, true)
+                .await
                 .unwrap();
         }
 
-        let recent = history.get_recent(3);
+        let recent = h.get_recent(3);
         assert_eq!(recent.len(), 3);
         // Recent should be in reverse order (newest first)
         assert_eq!(recent[0].query, "query 4");
@@ -380,49 +445,75 @@ mod tests {
         assert_eq!(recent[2].query, "query 2");
     }
 
-    #[test]
-    fn test_stats() {
-        let tmp = tempfile::NamedTempFile::new().unwrap();
-        let mut history = QueryHistory::new_with_path(100, tmp.path().to_path_buf()).unwrap();
+    #[tokio::test]
+    async fn test_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut h = history(&dir, 100).await;
 
-        history
-            .add_entry("query1".to_string(), Some("filter".to_string()), None, true)
+        h.add_entry("query1".to_string(), Some("filter".to_string()), None, true)
+            .await
             .unwrap();
-        history
-            .add_entry("query2".to_string(), Some("filter".to_string()), None, true)
+        h.add_entry("query2".to_string(), Some("filter".to_string()), None, true)
+            .await
             .unwrap();
-        history
-            .add_entry(
-                "query3".to_string(),
-                Some("search".to_string()),
-                None,
-                false,
-            )
+        h.add_entry(
+            "query3".to_string(),
+            Some("search".to_string()),
+            None,
+            false,
+        )
+        .await
+        .unwrap();
+        h.add_entry("query1".to_string(), Some("filter".to_string()), None, true)
+            .await
             .unwrap();
 
-        let stats = history.get_stats();
+        let stats = h.get_stats();
         assert_eq!(stats.total_queries, 3);
+        assert_eq!(stats.total_runs, 4);
         assert_eq!(stats.successful_queries, 2);
         assert_eq!(stats.failed_queries, 1);
         assert_eq!(stats.query_types.get("filter"), Some(&2));
         assert_eq!(stats.query_types.get("search"), Some(&1));
+        assert_eq!(stats.top_queries, vec![("query1".to_string(), 2)]);
     }
 
-    #[test]
-    fn test_clear_history() {
-        let tmp = tempfile::NamedTempFile::new().unwrap();
-        let mut history = QueryHistory::new_with_path(100, tmp.path().to_path_buf()).unwrap();
+    #[tokio::test]
+    async fn test_clear_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut h = history(&dir, 100).await;
 
-        history
-            .add_entry("query1".to_string(), None, None, true)
+        h.add_entry("query1".to_string(), None, None, true)
+            .await
             .unwrap();
-        history
-            .add_entry("query2".to_string(), None, None, true)
+        h.add_entry("query2".to_string(), None, None, true)
+            .await
             .unwrap();
 
-        assert_eq!(history.entries.len(), 2);
+        assert_eq!(h.entries.len(), 2);
+
+        h.clear().await.unwrap();
+        assert_eq!(h.entries.len(), 0);
+        assert!(history(&dir, 100).await.entries.is_empty());
+    }
 
-        history.clear().unwrap();
-        assert_eq!(history.entries.len(), 0);
+    #[tokio::test]
+    async fn test_import_legacy_history_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = dir.path().join("query_history.json");
+        std::fs::write(
+            &legacy,
+            r#"[{"query":"author:john","timestamp":"2024-05-01T09:00:00+08:00","query_type":null,"result_count":2,"success":true}]"#,
+        )
+        .unwrap();
+
+        let mut h = history(&dir, 100).await;
+        h.import_legacy(&legacy).await.unwrap();
+        assert_eq!(h.entries.len(), 1);
+        assert_eq!(h.entries[0].run_count, 1);
+        assert!(!legacy.exists());
+        assert!(dir.path().join("query_history.json.bak").exists());
+
+        assert_eq!(history(&dir, 100).await.entries.len(), 1);
     }
 }
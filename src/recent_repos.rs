@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 最多记住的仓库数量
+pub const MAX_RECENT_REPOS: usize = 20;
+
+/// 最近打开的仓库，最近使用的排在最前面
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecentRepos {
+    repos: Vec<PathBuf>,
+}
+
+impl RecentRepos {
+    /// 列表文件路径 <数据目录>/recent_repos.json
+    fn file_path() -> anyhow::Result<PathBuf> {
+        Ok(crate::config::data_dir()?.join("recent_repos.json"))
+    }
+
+    /// 读取最近的仓库列表，文件不存在时返回空列表
+    pub fn load() -> anyhow::Result<Self> {
+        Self::load_from(&Self::file_path()?)
+    }
+
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        if content.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.save_to(&Self::file_path()?)
+    }
+
+    pub fn save_to(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn repos(&self) -> &[PathBuf] {
+        &self.repos
+    }
+
+    /// 把仓库移到最前面，超出上限的旧仓库被移除
+    pub fn record(&mut self, repo: PathBuf) {
+        self.repos.retain(|path| *path != repo);
+        self.repos.insert(0, repo);
+        self.repos.truncate(MAX_RECENT_REPOS);
+    }
+
+    pub fn remove(&mut self, repo: &Path) {
+        self.repos.retain(|path| path != repo);
+    }
+
+    /// 记录一次打开仓库并写回文件
+    pub fn remember(repo: &Path) -> anyhow::Result<()> {
+        let mut recent = Self::load()?;
+        recent.record(repo.to_path_buf());
+        recent.save()
+    }
+}
+
+/// 路径所在仓库的根目录，不是 Git 仓库时返回错误
+pub fn repo_root(path: &Path) -> anyhow::Result<PathBuf> {
+    if !path.is_dir() {
+        anyhow::bail!("{} is not a directory", path.display());
+    }
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["rev-parse", "--show-toplevel"])
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!("{} is not a git repository", path.display());
+    }
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim_end(),
+    ))
+}
+
+/// 切换工作目录到路径所在仓库的根目录，之后的 Git 操作都在这个仓库中进行
+pub fn enter_repo(path: &Path) -> anyhow::Result<PathBuf> {
+    let root = repo_root(path)?;
+    std::env::set_current_dir(&root)?;
+    Ok(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_moves_repo_to_front() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("recent_repos.json");
+
+        let mut recent = RecentRepos::load_from(&path).unwrap();
+        assert!(recent.repos().is_empty());
+
+        recent.record(PathBuf::from("/a"));
+        recent.record(PathBuf::from("/b"));
+        recent.record(PathBuf::from("/a"));
+        assert_eq!(recent.repos(), [PathBuf::from("/a"), PathBuf::from("/b")]);
+
+        for i in 0..MAX_RECENT_REPOS {
+            recent.record(PathBuf::from(format!("/repo{}", i)));
+        }
+        assert_eq!(recent.repos().len(), MAX_RECENT_REPOS);
+        assert!(!recent.repos().contains(&PathBuf::from("/b")));
+
+        recent.save_to(&path).unwrap();
+        assert_eq!(RecentRepos::load_from(&path).unwrap(), recent);
+    }
+
+    #[test]
+    fn test_repo_root_rejects_non_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(repo_root(&dir.path().join("missing")).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! 审查报告对比
+//!
+//! 比较同一代码在重构前后的两份审查报告：问题按类别、文件和归一化后的描述匹配（忽略行号），
+//! 只在新报告中出现的是新增问题，只在旧报告中出现的是已修复问题，
+//! 两份报告都有但严重程度升高的是回退问题。同时给出得分变化，作为重构效果的依据。
+
+use super::{normalize_message, CodeReviewReport, ReviewFinding, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// 严重程度升高的问题
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegressedFinding {
+    pub before: Severity,
+    pub finding: ReviewFinding,
+}
+
+/// 两份审查报告的差异
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReportDiff {
+    pub base_score: u32,
+    pub head_score: u32,
+    pub new: Vec<ReviewFinding>,
+    pub fixed: Vec<ReviewFinding>,
+    pub regressed: Vec<RegressedFinding>,
+    /// 两份报告都有且严重程度没有升高的问题数
+    pub unchanged: usize,
+}
+
+impl ReportDiff {
+    /// 比较 `base`（之前）和 `head`（之后）两份报告
+    pub fn compare(base: &CodeReviewReport, head: &CodeReviewReport) -> Self {
+        let before = index(&base.findings);
+        let after = index(&head.findings);
+
+        let mut diff = Self {
+            base_score: base.score(),
+            head_score: head.score(),
+            ..Default::default()
+        };
+        for (key, finding) in &after {
+            match before.get(key) {
+                None => diff.new.push((*finding).clone()),
+                Some(old) if finding.severity > old.severity => {
+                    diff.regressed.push(RegressedFinding {
+                        before: old.severity,
+                        finding: (*finding).clone(),
+                    })
+                }
+                Some(_) => diff.unchanged += 1,
+            }
+        }
+        diff.fixed = before
+            .iter()
+            .filter(|(key, _)| !after.contains_key(*key))
+            .map(|(_, finding)| (*finding).clone())
+            .collect();
+
+        for findings in [&mut diff.new, &mut diff.fixed] {
+            findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+        }
+        diff.regressed
+            .sort_by_key(|r| std::cmp::Reverse(r.finding.severity));
+        diff
+    }
+
+    pub fn score_delta(&self) -> i64 {
+        self.head_score as i64 - self.base_score as i64
+    }
+
+    /// 没有新增和回退的问题
+    pub fn is_improvement(&self) -> bool {
+        self.new.is_empty() && self.regressed.is_empty()
+    }
+}
+
+/// 按匹配键索引问题，同一键出现多次时保留最严重的
+fn index(findings: &[ReviewFinding]) -> BTreeMap<(String, String, String), &ReviewFinding> {
+    let mut index: BTreeMap<(String, String, String), &ReviewFinding> = BTreeMap::new();
+    for finding in findings {
+        let key = (
+            finding.category.clone(),
+            finding.file.clone().unwrap_or_default(),
+            normalize_message(&finding.message),
+        );
+        let entry = index.entry(key).or_insert(finding);
+        if finding.severity > entry.severity {
+            *entry = finding;
+        }
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(severity: Severity, message: &str, line: u32) -> ReviewFinding {
+        ReviewFinding {
+            severity,
+            category: "bug".to_string(),
+            message: message.to_string(),
+            file: Some("src/main.rs".to_string()),
+            line: Some(line),
+        }
+    }
+
+    fn report(findings: Vec<ReviewFinding>) -> CodeReviewReport {
+        CodeReviewReport {
+            findings,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_compare_reports() {
+        let base = report(vec![
+            finding(Severity::High, "src/main.rs:10 unwrap 可能导致崩溃", 10),
+            finding(Severity::Low, "函数过长", 30),
+            finding(Severity::Medium, "重复克隆字符串", 50),
+        ]);
+        let head = report(vec![
+            // 代码移动后行号变化，仍视为同一个问题
+            finding(Severity::High, "src/main.rs:14 unwrap 可能导致崩溃", 14),
+            finding(Severity::High, "函数过长", 34),
+            finding(Severity::Critical, "空指针解引用", 60),
+        ]);
+
+        let diff = ReportDiff::compare(&base, &head);
+        assert_eq!(diff.base_score, 83);
+        assert_eq!(diff.head_score, 60);
+        assert_eq!(diff.score_delta(), -23);
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(diff.new.len(), 1);
+        assert_eq!(diff.new[0].message, "空指针解引用");
+        assert_eq!(diff.fixed.len(), 1);
+        assert_eq!(diff.fixed[0].message, "重复克隆字符串");
+        assert_eq!(diff.regressed.len(), 1);
+        assert_eq!(diff.regressed[0].before, Severity::Low);
+        assert!(!diff.is_improvement());
+
+        let diff = ReportDiff::compare(&head, &base);
+        assert_eq!(diff.score_delta(), 23);
+        assert_eq!(diff.fixed[0].message, "空指针解引用");
+
+        let diff = ReportDiff::compare(&base, &report(Vec::new()));
+        assert!(diff.is_improvement());
+        assert_eq!(diff.fixed.len(), 3);
+    }
+}
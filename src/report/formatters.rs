@@ -0,0 +1,1426 @@
+use super::{
+    AnalysisReport, CodeReviewReport, ReportConfig, ReportFormat, ReportTable, Section, Severity,
+    ViewExport,
+};
+use std::io::Write;
+
+/// 报告格式化器
+pub trait ReportFormatter {
+    /// 将报告渲染为字符串
+    fn format(&self, report: &CodeReviewReport) -> anyhow::Result<String>;
+
+    /// 将导出的 TUI 视图渲染为字符串
+    fn format_view(&self, export: &ViewExport) -> anyhow::Result<String>;
+
+    /// 将本地静态分析报告渲染为字符串
+    fn format_analysis(&self, report: &AnalysisReport) -> anyhow::Result<String>;
+
+    /// 将本地静态分析报告逐节写入 `out`。整仓库分析的报告可能很大，
+    /// 逐行输出的格式直接写入，不再额外拼接整份渲染结果；`report` 本身仍在内存中。
+    /// 默认先渲染为字符串再写入
+    fn write_analysis(&self, report: &AnalysisReport, out: &mut dyn Write) -> anyhow::Result<()> {
+        out.write_all(self.format_analysis(report)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// 输出文件的默认扩展名
+    fn extension(&self) -> &'static str;
+}
+
+/// 根据输出格式获取使用默认设置的格式化器
+pub fn formatter_for(format: ReportFormat) -> Box<dyn ReportFormatter> {
+    formatter_with(format, &ReportConfig::default())
+}
+
+/// 根据输出格式和 `[report]` 设置获取格式化器
+pub fn formatter_with(format: ReportFormat, config: &ReportConfig) -> Box<dyn ReportFormatter> {
+    let omit = config.omitted_sections();
+    match format {
+        ReportFormat::Text => Box::new(TextFormatter::default().omitting(&omit)),
+        ReportFormat::Markdown => Box::new(MarkdownFormatter::default().omitting(&omit)),
+        ReportFormat::Json => Box::new(JsonFormatter::default().omitting(&omit)),
+        ReportFormat::Html => Box::new(HtmlFormatter::default().omitting(&omit)),
+        // SARIF 只包含问题列表，不区分部分
+        ReportFormat::Sarif => Box::new(SarifFormatter),
+        ReportFormat::Csv => Box::new(
+            CsvFormatter::csv()
+                .with_columns(&config.columns)
+                .omitting(&omit),
+        ),
+        ReportFormat::Tsv => Box::new(
+            CsvFormatter::tsv()
+                .with_columns(&config.columns)
+                .omitting(&omit),
+        ),
+    }
+}
+
+/// 把逐节写入的输出收集为字符串
+fn collect_output(
+    write: impl FnOnce(&mut dyn Write) -> anyhow::Result<()>,
+) -> anyhow::Result<String> {
+    let mut buffer = Vec::new();
+    write(&mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// 按严重程度从高到低排列的问题统计，忽略数量为 0 的级别
+fn severity_counts(report: &CodeReviewReport) -> Vec<(Severity, usize)> {
+    [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+        Severity::Info,
+    ]
+    .into_iter()
+    .map(|severity| (severity, report.count_by_severity(severity)))
+    .filter(|(_, count)| *count > 0)
+    .collect()
+}
+
+/// 文本表格各列宽度（按字符数计算）
+fn column_widths(table: &ReportTable) -> Vec<usize> {
+    table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            table
+                .rows
+                .iter()
+                .filter_map(|row| row.get(index))
+                .chain(std::iter::once(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+fn pad(cell: &str, width: usize) -> String {
+    let padding = width.saturating_sub(cell.chars().count());
+    format!("{}{}", cell, " ".repeat(padding))
+}
+
+/// 终端文本格式
+#[derive(Default)]
+pub struct TextFormatter {
+    omit: Vec<Section>,
+}
+
+impl TextFormatter {
+    /// 不输出指定的部分
+    pub fn omitting(mut self, sections: &[Section]) -> Self {
+        self.omit = sections.to_vec();
+        self
+    }
+
+    fn shows(&self, section: Section) -> bool {
+        !self.omit.contains(&section)
+    }
+}
+
+impl ReportFormatter for TextFormatter {
+    fn format(&self, report: &CodeReviewReport) -> anyhow::Result<String> {
+        let mut out = String::new();
+        out.push_str(&format!("📋 Code review: {}\n", report.target));
+        out.push_str(&format!("{}\n", "─".repeat(60)));
+        if self.shows(Section::Summary) {
+            out.push_str(&format!(
+                "Files: {}  (+{} / -{})  Type: {}",
+                report.summary.total_files,
+                report.summary.total_additions,
+                report.summary.total_deletions,
+                report.summary.primary_change_type
+            ));
+            if let Some(scope) = &report.summary.scope {
+                out.push_str(&format!("  Scope: {}", scope));
+            }
+            out.push('\n');
+        }
+
+        if self.shows(Section::Stats) {
+            for file in &report.files {
+                out.push_str(&format!(
+                    "  {:<9} {} (+{} / -{})\n",
+                    file.change_type, file.path, file.additions, file.deletions
+                ));
+            }
+        }
+
+        if !self.shows(Section::Issues) {
+            // 不输出问题列表
+        } else if report.findings.is_empty() {
+            out.push_str("\n✅ No issues found\n");
+        } else {
+            let counts: Vec<String> = severity_counts(report)
+                .into_iter()
+                .map(|(severity, count)| format!("{} {}", count, severity.as_str()))
+                .collect();
+            out.push_str(&format!(
+                "\nFindings: {} ({})\n",
+                report.findings.len(),
+                counts.join(", ")
+            ));
+            for finding in &report.findings {
+                out.push_str(&format!(
+                    "  {} [{}] {}: {}\n",
+                    finding.severity.icon(),
+                    finding.severity.as_str(),
+                    finding.category,
+                    finding.message
+                ));
+            }
+        }
+
+        if self.shows(Section::AiReview) && !report.ai_review.is_empty() {
+            out.push_str(&format!("\n{}\n", "─".repeat(60)));
+            out.push_str(&report.ai_review);
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+
+    fn format_view(&self, export: &ViewExport) -> anyhow::Result<String> {
+        let mut out = String::new();
+        out.push_str(&format!("{} ({})\n", export.title, export.generated_at));
+        out.push_str(&format!("{}\n", "─".repeat(60)));
+        for section in &export.sections {
+            out.push_str(&format!("\n{}\n", section.heading));
+            for line in &section.lines {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+        Ok(out)
+    }
+
+    fn format_analysis(&self, report: &AnalysisReport) -> anyhow::Result<String> {
+        collect_output(|out| self.write_analysis(report, out))
+    }
+
+    fn write_analysis(&self, report: &AnalysisReport, out: &mut dyn Write) -> anyhow::Result<()> {
+        writeln!(out, "📊 {} ({})", report.title, report.generated_at)?;
+        writeln!(out, "{}", "─".repeat(60))?;
+        if self.shows(Section::Summary) {
+            for metric in &report.metrics {
+                writeln!(out, "{}: {}", metric.name, metric.value)?;
+            }
+        }
+
+        for table in report.tables.iter().filter(|_| self.shows(Section::Issues)) {
+            writeln!(out, "\n{}", table.heading)?;
+            if table.rows.is_empty() {
+                writeln!(out, "  (none)")?;
+                continue;
+            }
+            let widths = column_widths(table);
+            let header: Vec<String> = table
+                .columns
+                .iter()
+                .zip(&widths)
+                .map(|(column, width)| pad(column, *width))
+                .collect();
+            writeln!(out, "  {}", header.join("  ").trim_end())?;
+            for row in &table.rows {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| pad(cell, *width))
+                    .collect();
+                writeln!(out, "  {}", cells.join("  ").trim_end())?;
+            }
+        }
+
+        if self.shows(Section::Suggestions) && !report.recommendations.is_empty() {
+            writeln!(out, "\n💡 Recommendations")?;
+            for recommendation in &report.recommendations {
+                writeln!(out, "  - {}", recommendation)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+}
+
+/// Markdown 格式
+#[derive(Default)]
+pub struct MarkdownFormatter {
+    omit: Vec<Section>,
+}
+
+impl MarkdownFormatter {
+    /// 不输出指定的部分
+    pub fn omitting(mut self, sections: &[Section]) -> Self {
+        self.omit = sections.to_vec();
+        self
+    }
+
+    fn shows(&self, section: Section) -> bool {
+        !self.omit.contains(&section)
+    }
+}
+
+impl ReportFormatter for MarkdownFormatter {
+    fn format(&self, report: &CodeReviewReport) -> anyhow::Result<String> {
+        let mut out = String::new();
+        out.push_str(&format!("# Code Review: {}\n\n", report.target));
+        out.push_str(&format!("_Generated at {}_\n\n", report.generated_at));
+
+        if self.shows(Section::Summary) {
+            out.push_str("## Summary\n\n");
+            out.push_str("| Files | Additions | Deletions | Type | Scope |\n");
+            out.push_str("|-------|-----------|-----------|------|-------|\n");
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n\n",
+                report.summary.total_files,
+                report.summary.total_additions,
+                report.summary.total_deletions,
+                report.summary.primary_change_type,
+                report.summary.scope.as_deref().unwrap_or("-")
+            ));
+        }
+
+        if self.shows(Section::Stats) && !report.files.is_empty() {
+            out.push_str("## Files\n\n");
+            out.push_str("| File | Change | + | - |\n");
+            out.push_str("|------|--------|---|---|\n");
+            for file in &report.files {
+                out.push_str(&format!(
+                    "| `{}` | {} | {} | {} |\n",
+                    file.path, file.change_type, file.additions, file.deletions
+                ));
+            }
+            out.push('\n');
+        }
+
+        if !self.shows(Section::Issues) {
+            // 不输出问题列表
+        } else if report.findings.is_empty() {
+            out.push_str("## Findings\n\nNo issues found.\n\n");
+        } else {
+            out.push_str("## Findings\n\n");
+            for finding in &report.findings {
+                out.push_str(&format!(
+                    "- {} **{}** ({}): {}\n",
+                    finding.severity.icon(),
+                    finding.severity.as_str(),
+                    finding.category,
+                    finding.message
+                ));
+            }
+            out.push('\n');
+        }
+
+        if self.shows(Section::AiReview) && !report.ai_review.is_empty() {
+            out.push_str("## AI Review\n\n");
+            out.push_str(&report.ai_review);
+            out.push('\n');
+        }
+
+        Ok(out)
+    }
+
+    fn format_view(&self, export: &ViewExport) -> anyhow::Result<String> {
+        let mut out = String::new();
+        out.push_str(&format!("# {}\n\n", export.title));
+        out.push_str(&format!("_Generated at {}_\n\n", export.generated_at));
+        for section in &export.sections {
+            out.push_str(&format!("## {}\n\n", section.heading));
+            match &section.code {
+                Some(language) => {
+                    out.push_str(&format!("```{}\n", language));
+                    for line in &section.lines {
+                        out.push_str(&format!("{}\n", line));
+                    }
+                    out.push_str("```\n\n");
+                }
+                None => {
+                    for line in &section.lines {
+                        out.push_str(&format!("- {}\n", line));
+                    }
+                    out.push('\n');
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn format_analysis(&self, report: &AnalysisReport) -> anyhow::Result<String> {
+        collect_output(|out| self.write_analysis(report, out))
+    }
+
+    fn write_analysis(&self, report: &AnalysisReport, out: &mut dyn Write) -> anyhow::Result<()> {
+        writeln!(out, "# {}\n", report.title)?;
+        writeln!(out, "_Generated at {}_\n", report.generated_at)?;
+
+        if self.shows(Section::Summary) && !report.metrics.is_empty() {
+            writeln!(out, "## Summary\n")?;
+            for metric in &report.metrics {
+                writeln!(out, "- **{}**: {}", metric.name, metric.value)?;
+            }
+            writeln!(out)?;
+        }
+
+        for table in report.tables.iter().filter(|_| self.shows(Section::Issues)) {
+            writeln!(out, "## {}\n", table.heading)?;
+            if table.rows.is_empty() {
+                writeln!(out, "None.\n")?;
+                continue;
+            }
+            writeln!(out, "| {} |", table.columns.join(" | "))?;
+            writeln!(out, "|{}", "---|".repeat(table.columns.len()))?;
+            for row in &table.rows {
+                let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+                writeln!(out, "| {} |", cells.join(" | "))?;
+            }
+            writeln!(out)?;
+        }
+
+        if self.shows(Section::Suggestions) && !report.recommendations.is_empty() {
+            writeln!(out, "## Recommendations\n")?;
+            for recommendation in &report.recommendations {
+                writeln!(out, "- {}", recommendation)?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    fn extension(&self) -> &'static str {
+        "md"
+    }
+}
+
+/// JSON 格式
+#[derive(Default)]
+pub struct JsonFormatter {
+    omit: Vec<Section>,
+}
+
+impl JsonFormatter {
+    /// 不输出指定的部分
+    pub fn omitting(mut self, sections: &[Section]) -> Self {
+        self.omit = sections.to_vec();
+        self
+    }
+
+    fn shows(&self, section: Section) -> bool {
+        !self.omit.contains(&section)
+    }
+}
+
+impl JsonFormatter {
+    /// 序列化报告，去掉不输出的部分对应的字段
+    fn render<T: serde::Serialize>(
+        &self,
+        report: &T,
+        fields: &[(Section, &str)],
+    ) -> anyhow::Result<String> {
+        if self.omit.is_empty() {
+            return Ok(serde_json::to_string_pretty(report)?);
+        }
+        let mut value = serde_json::to_value(report)?;
+        if let Some(object) = value.as_object_mut() {
+            for (section, field) in fields {
+                if !self.shows(*section) {
+                    object.remove(*field);
+                }
+            }
+        }
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+}
+
+impl ReportFormatter for JsonFormatter {
+    fn format(&self, report: &CodeReviewReport) -> anyhow::Result<String> {
+        self.render(
+            report,
+            &[
+                (Section::Summary, "summary"),
+                (Section::Stats, "files"),
+                (Section::Issues, "findings"),
+                (Section::AiReview, "ai_review"),
+            ],
+        )
+    }
+
+    fn format_view(&self, export: &ViewExport) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(export)?)
+    }
+
+    fn format_analysis(&self, report: &AnalysisReport) -> anyhow::Result<String> {
+        self.render(
+            report,
+            &[
+                (Section::Summary, "metrics"),
+                (Section::Issues, "tables"),
+                (Section::Suggestions, "recommendations"),
+            ],
+        )
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+}
+
+/// HTML 报告的内联样式，输出的页面不依赖外部资源
+const HTML_STYLE: &str = "\
+body{font-family:-apple-system,'Segoe UI',Roboto,'Noto Sans SC',sans-serif;margin:0 auto;max-width:1100px;padding:24px;color:#1f2328;background:#fff;line-height:1.5}\
+h1{font-size:1.6em;border-bottom:1px solid #d0d7de;padding-bottom:8px}\
+.meta{color:#656d76;font-size:.9em}\
+details{border:1px solid #d0d7de;border-radius:6px;margin:16px 0;padding:0 16px}\
+details[open]{padding-bottom:12px}\
+summary{cursor:pointer;font-weight:600;font-size:1.1em;padding:10px 0}\
+table{border-collapse:collapse;width:100%;font-size:.9em}\
+th,td{border:1px solid #d0d7de;padding:6px 10px;text-align:left;vertical-align:top}\
+th{background:#f6f8fa}\
+tr:nth-child(even) td{background:#fafbfc}\
+ul{padding-left:20px}\
+pre{background:#f6f8fa;border-radius:6px;padding:12px;overflow-x:auto;font-size:.85em}\
+code{font-family:SFMono-Regular,Consolas,monospace}\
+.badge{display:inline-block;border-radius:10px;padding:0 8px;font-size:.8em;font-weight:600;color:#fff}\
+.severity-critical{background:#8b0000}.severity-high{background:#cf222e}\
+.severity-medium{background:#bf8700}.severity-low{background:#0969da}.severity-info{background:#6e7781}\
+li.finding{margin:4px 0}\
+.empty{color:#656d76;font-style:italic}";
+
+/// 转义 HTML 特殊字符
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 严重程度标签，颜色由 `severity-*` 样式决定
+fn severity_badge(severity: Severity) -> String {
+    format!(
+        "<span class=\"badge severity-{0}\">{0}</span>",
+        severity.as_str()
+    )
+}
+
+/// 完整的 HTML 页面
+fn html_page(title: &str, generated_at: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>{style}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<p class=\"meta\">Generated at {generated_at}</p>\n{body}</body>\n</html>\n",
+        title = escape_html(title),
+        style = HTML_STYLE,
+        generated_at = escape_html(generated_at),
+        body = body
+    )
+}
+
+/// 可折叠的一节，默认展开
+fn html_section(heading: &str, content: &str) -> String {
+    format!(
+        "<details open>\n<summary>{}</summary>\n{}</details>\n",
+        escape_html(heading),
+        content
+    )
+}
+
+fn html_table(columns: &[String], rows: &[Vec<String>]) -> String {
+    let mut out = String::from("<table>\n<thead><tr>");
+    for column in columns {
+        out.push_str(&format!("<th>{}</th>", escape_html(column)));
+    }
+    out.push_str("</tr></thead>\n<tbody>\n");
+    for row in rows {
+        out.push_str("<tr>");
+        for cell in row {
+            // 严重程度或风险等级的单元格按颜色标注
+            match cell.parse::<Severity>() {
+                Ok(severity) => out.push_str(&format!("<td>{}</td>", severity_badge(severity))),
+                Err(_) => out.push_str(&format!("<td>{}</td>", escape_html(cell))),
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n");
+    out
+}
+
+fn html_list(items: &[String]) -> String {
+    let mut out = String::from("<ul>\n");
+    for item in items {
+        out.push_str(&format!("<li>{}</li>\n", escape_html(item)));
+    }
+    out.push_str("</ul>\n");
+    out
+}
+
+fn html_code(language: &str, lines: &[String]) -> String {
+    format!(
+        "<pre><code class=\"language-{}\">{}</code></pre>\n",
+        escape_html(language),
+        escape_html(&lines.join("\n"))
+    )
+}
+
+/// 自包含的 HTML 格式：内联样式，各节可折叠，严重程度按颜色区分
+#[derive(Default)]
+pub struct HtmlFormatter {
+    omit: Vec<Section>,
+}
+
+impl HtmlFormatter {
+    /// 不输出指定的部分
+    pub fn omitting(mut self, sections: &[Section]) -> Self {
+        self.omit = sections.to_vec();
+        self
+    }
+
+    fn shows(&self, section: Section) -> bool {
+        !self.omit.contains(&section)
+    }
+}
+
+impl ReportFormatter for HtmlFormatter {
+    fn format(&self, report: &CodeReviewReport) -> anyhow::Result<String> {
+        let mut body = String::new();
+
+        let summary = html_table(
+            &["Files", "Additions", "Deletions", "Type", "Scope"].map(String::from),
+            &[vec![
+                report.summary.total_files.to_string(),
+                report.summary.total_additions.to_string(),
+                report.summary.total_deletions.to_string(),
+                report.summary.primary_change_type.clone(),
+                report
+                    .summary
+                    .scope
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string()),
+            ]],
+        );
+        if self.shows(Section::Summary) {
+            body.push_str(&html_section("Summary", &summary));
+        }
+
+        if self.shows(Section::Stats) && !report.files.is_empty() {
+            let rows: Vec<Vec<String>> = report
+                .files
+                .iter()
+                .map(|file| {
+                    vec![
+                        file.path.clone(),
+                        file.change_type.clone(),
+                        file.additions.to_string(),
+                        file.deletions.to_string(),
+                    ]
+                })
+                .collect();
+            body.push_str(&html_section(
+                "Files",
+                &html_table(&["File", "Change", "+", "-"].map(String::from), &rows),
+            ));
+        }
+
+        let findings = if report.findings.is_empty() {
+            "<p class=\"empty\">No issues found.</p>\n".to_string()
+        } else {
+            let counts: Vec<String> = severity_counts(report)
+                .into_iter()
+                .map(|(severity, count)| format!("{} {}", severity_badge(severity), count))
+                .collect();
+            let mut out = format!("<p>{}</p>\n<ul>\n", counts.join(" "));
+            for finding in &report.findings {
+                let location = match (&finding.file, finding.line) {
+                    (Some(file), Some(line)) => {
+                        format!(" <code>{}:{}</code>", escape_html(file), line)
+                    }
+                    (Some(file), None) => format!(" <code>{}</code>", escape_html(file)),
+                    _ => String::new(),
+                };
+                out.push_str(&format!(
+                    "<li class=\"finding\">{} <strong>{}</strong>{}: {}</li>\n",
+                    severity_badge(finding.severity),
+                    escape_html(&finding.category),
+                    location,
+                    escape_html(&finding.message)
+                ));
+            }
+            out.push_str("</ul>\n");
+            out
+        };
+        if self.shows(Section::Issues) {
+            body.push_str(&html_section(
+                &format!("Findings ({})", report.findings.len()),
+                &findings,
+            ));
+        }
+
+        if self.shows(Section::AiReview) && !report.ai_review.is_empty() {
+            body.push_str(&html_section(
+                "AI Review",
+                &format!("<pre>{}</pre>\n", escape_html(&report.ai_review)),
+            ));
+        }
+
+        Ok(html_page(
+            &format!("Code Review: {}", report.target),
+            &report.generated_at,
+            &body,
+        ))
+    }
+
+    fn format_view(&self, export: &ViewExport) -> anyhow::Result<String> {
+        let body: String = export
+            .sections
+            .iter()
+            .map(|section| {
+                let content = match &section.code {
+                    Some(language) => html_code(language, &section.lines),
+                    None => html_list(&section.lines),
+                };
+                html_section(&section.heading, &content)
+            })
+            .collect();
+        Ok(html_page(&export.title, &export.generated_at, &body))
+    }
+
+    fn format_analysis(&self, report: &AnalysisReport) -> anyhow::Result<String> {
+        let mut body = String::new();
+
+        if self.shows(Section::Summary) && !report.metrics.is_empty() {
+            let rows: Vec<Vec<String>> = report
+                .metrics
+                .iter()
+                .map(|metric| vec![metric.name.clone(), metric.value.clone()])
+                .collect();
+            body.push_str(&html_section(
+                "Summary",
+                &html_table(&["Metric", "Value"].map(String::from), &rows),
+            ));
+        }
+
+        for table in report.tables.iter().filter(|_| self.shows(Section::Issues)) {
+            let content = if table.rows.is_empty() {
+                "<p class=\"empty\">None.</p>\n".to_string()
+            } else {
+                html_table(&table.columns, &table.rows)
+            };
+            body.push_str(&html_section(&table.heading, &content));
+        }
+
+        if self.shows(Section::Suggestions) && !report.recommendations.is_empty() {
+            body.push_str(&html_section(
+                "Recommendations",
+                &html_list(&report.recommendations),
+            ));
+        }
+
+        Ok(html_page(&report.title, &report.generated_at, &body))
+    }
+
+    fn extension(&self) -> &'static str {
+        "html"
+    }
+}
+
+/// SARIF 2.1.0 的 JSON Schema
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// 严重程度对应的 SARIF 结果级别
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low | Severity::Info => "note",
+    }
+}
+
+/// GitHub code scanning 用于排序和过滤的 `security-severity`（0–10）
+fn security_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "9.5",
+        Severity::High => "8.0",
+        Severity::Medium => "5.5",
+        Severity::Low => "3.0",
+        Severity::Info => "0.0",
+    }
+}
+
+/// SARIF 2.1.0 格式，可上传到 GitHub code scanning 等支持 SARIF 的平台
+///
+/// 每个问题类别对应一条规则，规则的默认级别取该类别中最高的严重程度。
+/// 只有审查报告能转换为 SARIF，TUI 视图和本地分析报告没有对应的结果结构。
+pub struct SarifFormatter;
+
+impl ReportFormatter for SarifFormatter {
+    fn format(&self, report: &CodeReviewReport) -> anyhow::Result<String> {
+        let mut categories: Vec<(&str, Severity)> = Vec::new();
+        for finding in &report.findings {
+            match categories
+                .iter_mut()
+                .find(|(category, _)| *category == finding.category)
+            {
+                Some((_, severity)) => *severity = (*severity).max(finding.severity),
+                None => categories.push((&finding.category, finding.severity)),
+            }
+        }
+
+        let rules: Vec<serde_json::Value> = categories
+            .iter()
+            .map(|(category, severity)| {
+                serde_json::json!({
+                    "id": category,
+                    "name": category,
+                    "shortDescription": { "text": format!("ai-commit review: {}", category) },
+                    "defaultConfiguration": { "level": sarif_level(*severity) },
+                    "properties": {
+                        "tags": [category],
+                        "security-severity": security_severity(*severity),
+                    },
+                })
+            })
+            .collect();
+
+        let results: Vec<serde_json::Value> = report
+            .findings
+            .iter()
+            .map(|finding| {
+                let mut result = serde_json::json!({
+                    "ruleId": finding.category,
+                    "ruleIndex": categories
+                        .iter()
+                        .position(|(category, _)| *category == finding.category),
+                    "level": sarif_level(finding.severity),
+                    "message": { "text": finding.message },
+                    "properties": { "severity": finding.severity.as_str() },
+                });
+                if let Some(file) = &finding.file {
+                    let mut location = serde_json::json!({
+                        "artifactLocation": { "uri": file, "uriBaseId": "%SRCROOT%" },
+                    });
+                    if let Some(line) = finding.line {
+                        location["region"] = serde_json::json!({ "startLine": line });
+                    }
+                    result["locations"] = serde_json::json!([{ "physicalLocation": location }]);
+                }
+                result
+            })
+            .collect();
+
+        let sarif = serde_json::json!({
+            "$schema": SARIF_SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "ai-commit",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "automationDetails": { "id": format!("ai-commit/{}", report.target) },
+                "results": results,
+            }],
+        });
+        Ok(serde_json::to_string_pretty(&sarif)?)
+    }
+
+    fn format_view(&self, _export: &ViewExport) -> anyhow::Result<String> {
+        anyhow::bail!("SARIF output is only supported for review reports")
+    }
+
+    fn format_analysis(&self, _report: &AnalysisReport) -> anyhow::Result<String> {
+        anyhow::bail!("SARIF output is only supported for review reports")
+    }
+
+    fn extension(&self) -> &'static str {
+        "sarif"
+    }
+}
+
+/// CSV/TSV 格式，用于导入电子表格
+///
+/// 所有表格合并为一张表，第一列 `Section` 为行所属的表格：审查报告包括问题和文件变更统计，
+/// 分析报告包括汇总指标和各个表格。同名的列合并为一列，行中没有的列留空。
+/// 设置了列名时只输出这些列（按设置的顺序），不包含任何设置列的表格不输出。
+pub struct CsvFormatter {
+    delimiter: char,
+    columns: Vec<String>,
+    omit: Vec<Section>,
+}
+
+impl CsvFormatter {
+    pub fn csv() -> Self {
+        Self {
+            delimiter: ',',
+            columns: Vec::new(),
+            omit: Vec::new(),
+        }
+    }
+
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            columns: Vec::new(),
+            omit: Vec::new(),
+        }
+    }
+
+    pub fn with_columns(mut self, columns: &[String]) -> Self {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    /// 不输出指定的部分
+    pub fn omitting(mut self, sections: &[Section]) -> Self {
+        self.omit = sections.to_vec();
+        self
+    }
+
+    /// 去掉不输出的部分对应的表格
+    fn visible<'a>(&self, tables: Vec<(Section, &'a ReportTable)>) -> Vec<&'a ReportTable> {
+        tables
+            .into_iter()
+            .filter(|(section, _)| !self.omit.contains(section))
+            .map(|(_, table)| table)
+            .collect()
+    }
+
+    /// CSV 中含分隔符、引号或换行的单元格加引号；TSV 没有转义规则，制表符和换行替换为空格。
+    /// 以 `=`、`+`、`-`、`@` 开头的文本会被电子表格当作公式执行，前面加 `'`，数值保持不变
+    fn escape(&self, cell: &str) -> String {
+        let is_number = cell.trim_end_matches('%').parse::<f64>().is_ok();
+        let cell = if !is_number && cell.starts_with(['=', '+', '-', '@']) {
+            std::borrow::Cow::Owned(format!("'{}", cell))
+        } else {
+            std::borrow::Cow::Borrowed(cell)
+        };
+        if self.delimiter == '\t' {
+            return cell.replace(['\t', '\n', '\r'], " ");
+        }
+        if cell.contains([self.delimiter, '"', '\n', '\r']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
+    }
+
+    fn render(&self, tables: &[&ReportTable]) -> anyhow::Result<String> {
+        collect_output(|out| self.write_tables(tables, out))
+    }
+
+    /// 逐行写出合并后的表格
+    fn write_tables(&self, tables: &[&ReportTable], out: &mut dyn Write) -> anyhow::Result<()> {
+        let delimiter = self.delimiter.to_string();
+        let same = |a: &str, b: &str| a.trim().eq_ignore_ascii_case(b.trim());
+
+        // 合并后的列：设置了列名时按设置的顺序，否则按出现的顺序取各表格的列
+        let mut columns: Vec<String> = Vec::new();
+        if self.columns.is_empty() {
+            for column in tables.iter().flat_map(|table| &table.columns) {
+                if !columns.iter().any(|c| same(c, column)) {
+                    columns.push(column.clone());
+                }
+            }
+        } else {
+            for name in &self.columns {
+                let found = tables
+                    .iter()
+                    .flat_map(|table| &table.columns)
+                    .find(|column| same(column, name));
+                if let Some(column) = found {
+                    if !columns.iter().any(|c| same(c, column)) {
+                        columns.push(column.clone());
+                    }
+                }
+            }
+        }
+        if columns.is_empty() {
+            return Ok(());
+        }
+
+        let line = |section: &str, cells: Vec<&str>| -> String {
+            std::iter::once(section)
+                .chain(cells)
+                .map(|cell| self.escape(cell))
+                .collect::<Vec<_>>()
+                .join(&delimiter)
+        };
+        writeln!(
+            out,
+            "{}",
+            line("Section", columns.iter().map(String::as_str).collect())
+        )?;
+        for table in tables {
+            // 合并后的每一列在该表格中的下标
+            let positions: Vec<Option<usize>> = columns
+                .iter()
+                .map(|column| table.columns.iter().position(|c| same(c, column)))
+                .collect();
+            if positions.iter().all(Option::is_none) {
+                continue;
+            }
+            for row in &table.rows {
+                let cells = positions
+                    .iter()
+                    .map(|position| {
+                        position
+                            .and_then(|index| row.get(index))
+                            .map_or("", String::as_str)
+                    })
+                    .collect();
+                writeln!(out, "{}", line(&table.heading, cells))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ReportFormatter for CsvFormatter {
+    fn format(&self, report: &CodeReviewReport) -> anyhow::Result<String> {
+        let mut findings = ReportTable::new(
+            "Findings",
+            &["Severity", "Category", "File", "Line", "Message"],
+        );
+        findings.rows = report
+            .findings
+            .iter()
+            .map(|finding| {
+                vec![
+                    finding.severity.as_str().to_string(),
+                    finding.category.clone(),
+                    finding.file.clone().unwrap_or_default(),
+                    finding
+                        .line
+                        .map(|line| line.to_string())
+                        .unwrap_or_default(),
+                    finding.message.clone(),
+                ]
+            })
+            .collect();
+
+        let mut files = ReportTable::new("Files", &["File", "Change", "Additions", "Deletions"]);
+        files.rows = report
+            .files
+            .iter()
+            .map(|file| {
+                vec![
+                    file.path.clone(),
+                    file.change_type.clone(),
+                    file.additions.to_string(),
+                    file.deletions.to_string(),
+                ]
+            })
+            .collect();
+
+        self.render(&self.visible(vec![(Section::Issues, &findings), (Section::Stats, &files)]))
+    }
+
+    fn format_view(&self, export: &ViewExport) -> anyhow::Result<String> {
+        let tables: Vec<ReportTable> = export
+            .sections
+            .iter()
+            .map(|section| {
+                let mut table = ReportTable::new(&section.heading, &["Line"]);
+                table.rows = section
+                    .lines
+                    .iter()
+                    .map(|line| vec![line.clone()])
+                    .collect();
+                table
+            })
+            .collect();
+        self.render(&tables.iter().collect::<Vec<_>>())
+    }
+
+    fn format_analysis(&self, report: &AnalysisReport) -> anyhow::Result<String> {
+        collect_output(|out| self.write_analysis(report, out))
+    }
+
+    fn write_analysis(&self, report: &AnalysisReport, out: &mut dyn Write) -> anyhow::Result<()> {
+        let mut metrics = ReportTable::new("Summary", &["Metric", "Value"]);
+        metrics.rows = report
+            .metrics
+            .iter()
+            .map(|metric| vec![metric.name.clone(), metric.value.clone()])
+            .collect();
+
+        let mut tables = vec![(Section::Summary, &metrics)];
+        tables.extend(report.tables.iter().map(|table| (Section::Issues, table)));
+        self.write_tables(&self.visible(tables), out)
+    }
+
+    fn extension(&self) -> &'static str {
+        if self.delimiter == '\t' {
+            "tsv"
+        } else {
+            "csv"
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::{ExportSection, FileReview, ReviewFinding, ReviewSummary};
+
+    fn sample_report() -> CodeReviewReport {
+        CodeReviewReport {
+            target: "HEAD".to_string(),
+            generated_at: "2024-01-01 10:00:00".to_string(),
+            summary: ReviewSummary {
+                total_files: 1,
+                total_additions: 3,
+                total_deletions: 1,
+                primary_change_type: "feat".to_string(),
+                scope: Some("cli".to_string()),
+            },
+            files: vec![FileReview {
+                path: "src/main.rs".to_string(),
+                change_type: "modified".to_string(),
+                additions: 3,
+                deletions: 1,
+            }],
+            findings: vec![ReviewFinding {
+                severity: Severity::Medium,
+                category: "performance".to_string(),
+                message: "循环中重复克隆字符串".to_string(),
+                file: None,
+                line: None,
+            }],
+            ai_review: "## 总结\n整体良好".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_text_formatter() {
+        let output = TextFormatter::default().format(&sample_report()).unwrap();
+        assert!(output.contains("Code review: HEAD"));
+        assert!(output.contains("Findings: 1 (1 medium)"));
+        assert!(output.contains("src/main.rs"));
+        assert!(output.contains("整体良好"));
+    }
+
+    #[test]
+    fn test_markdown_formatter() {
+        let output = MarkdownFormatter::default()
+            .format(&sample_report())
+            .unwrap();
+        assert!(output.starts_with("# Code Review: HEAD"));
+        assert!(output.contains("| `src/main.rs` | modified | 3 | 1 |"));
+        assert!(output.contains("**medium** (performance)"));
+    }
+
+    #[test]
+    fn test_json_formatter_roundtrip() {
+        let report = sample_report();
+        let output = JsonFormatter::default().format(&report).unwrap();
+        let restored: CodeReviewReport = serde_json::from_str(&output).unwrap();
+        assert_eq!(restored, report);
+        assert!(output.contains("\"severity\": \"medium\""));
+    }
+
+    #[test]
+    fn test_html_formatter() {
+        let mut report = sample_report();
+        report.findings[0].file = Some("src/main.rs".to_string());
+        report.findings[0].line = Some(7);
+        report.ai_review = "Vec<String> & <script>".to_string();
+
+        let output = HtmlFormatter::default().format(&report).unwrap();
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<title>Code Review: HEAD</title>"));
+        assert!(output.contains("<style>"));
+        assert!(output.contains("<details open>\n<summary>Findings (1)</summary>"));
+        assert!(output.contains(
+            "<span class=\"badge severity-medium\">medium</span> <strong>performance</strong> <code>src/main.rs:7</code>"
+        ));
+        assert!(output.contains("Vec&lt;String&gt; &amp; &lt;script&gt;"));
+        assert!(!output.contains("<script>"));
+    }
+
+    #[test]
+    fn test_html_analysis_and_view() {
+        let mut table = ReportTable::new("Hotspots", &["File", "Risk"]);
+        table
+            .rows
+            .push(vec!["src/a.rs".to_string(), "high".to_string()]);
+        let report = AnalysisReport::new("Hotspots")
+            .metric("Files", 1)
+            .table(table)
+            .table(ReportTable::new("Violations", &["Function"]));
+
+        let output = HtmlFormatter::default().format_analysis(&report).unwrap();
+        assert!(output.contains("<td>Files</td><td>1</td>"));
+        assert!(output
+            .contains("<td>src/a.rs</td><td><span class=\"badge severity-high\">high</span></td>"));
+        assert!(output.contains("<summary>Violations</summary>\n<p class=\"empty\">None.</p>"));
+
+        let export = ViewExport::new(
+            "Diff",
+            vec![ExportSection::code(
+                "a.rs",
+                "diff",
+                vec!["+<b>".to_string()],
+            )],
+        );
+        let output = HtmlFormatter::default().format_view(&export).unwrap();
+        assert!(output.contains("<pre><code class=\"language-diff\">+&lt;b&gt;</code></pre>"));
+    }
+
+    #[test]
+    fn test_sarif_formatter() {
+        let mut report = sample_report();
+        report.findings[0].file = Some("src/main.rs".to_string());
+        report.findings[0].line = Some(42);
+        report.findings.push(ReviewFinding {
+            severity: Severity::Critical,
+            category: "performance".to_string(),
+            message: "死循环".to_string(),
+            file: None,
+            line: None,
+        });
+        report.findings.push(ReviewFinding {
+            severity: Severity::Low,
+            category: "maintainability".to_string(),
+            message: "补充测试".to_string(),
+            file: None,
+            line: None,
+        });
+
+        let output = SarifFormatter.format(&report).unwrap();
+        let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "ai-commit");
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "performance");
+        assert_eq!(rules[0]["defaultConfiguration"]["level"], "error");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[0]["ruleIndex"], 0);
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(location["region"]["startLine"], 42);
+        assert!(results[1].get("locations").is_none());
+        assert_eq!(results[2]["ruleId"], "maintainability");
+        assert_eq!(results[2]["level"], "note");
+
+        assert!(SarifFormatter
+            .format_analysis(&AnalysisReport::new("Complexity"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_csv_formatter() {
+        let mut report = sample_report();
+        report.findings[0].file = Some("src/main.rs".to_string());
+        report.findings[0].line = Some(7);
+        report.findings[0].message = "clone, \"twice\"".to_string();
+
+        let output = CsvFormatter::csv().format(&report).unwrap();
+        assert_eq!(
+            output,
+            "Section,Severity,Category,File,Line,Message,Change,Additions,Deletions\n\
+             Findings,medium,performance,src/main.rs,7,\"clone, \"\"twice\"\"\",,,\n\
+             Files,,,src/main.rs,,,modified,3,1\n"
+        );
+        let output = CsvFormatter::tsv()
+            .with_columns(&["message".to_string(), "Severity".to_string()])
+            .format(&report)
+            .unwrap();
+        assert_eq!(
+            output,
+            "Section\tMessage\tSeverity\nFindings\tclone, \"twice\"\tmedium\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_escapes_formulas() {
+        let mut report = sample_report();
+        report.findings[0].file = Some("@SUM(A1:A9).rs".to_string());
+        report.findings[0].message = "=HYPERLINK(\"http://evil\",\"x\")".to_string();
+
+        let output = CsvFormatter::csv().format(&report).unwrap();
+        assert!(output.contains(",'@SUM(A1:A9).rs,"));
+        assert!(output.contains(",\"'=HYPERLINK(\"\"http://evil\"\",\"\"x\"\")\","));
+
+        let mut table = ReportTable::new("Trend", &["Name", "Delta", "Note"]);
+        table.rows.push(vec![
+            "Complexity".to_string(),
+            "-3.5".to_string(),
+            "+cmd|' /C calc'!A0".to_string(),
+        ]);
+        table.rows.push(vec![
+            "Coverage".to_string(),
+            "+12%".to_string(),
+            "-".to_string(),
+        ]);
+        let report = AnalysisReport::new("Trend").table(table);
+        let output = CsvFormatter::tsv().format_analysis(&report).unwrap();
+        assert_eq!(
+            output,
+            "Section\tMetric\tValue\tName\tDelta\tNote\n\
+             Trend\t\t\tComplexity\t-3.5\t'+cmd|' /C calc'!A0\n\
+             Trend\t\t\tCoverage\t+12%\t'-\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_analysis_report() {
+        let mut table = ReportTable::new("Hotspots", &["File", "Score"]);
+        table
+            .rows
+            .push(vec!["src/a.rs".to_string(), "80.0".to_string()]);
+        let report = AnalysisReport::new("Hotspots")
+            .metric("Files", 1)
+            .table(table);
+
+        let output = CsvFormatter::csv().format_analysis(&report).unwrap();
+        assert_eq!(
+            output,
+            "Section,Metric,Value,File,Score\nSummary,Files,1,,\nHotspots,,,src/a.rs,80.0\n"
+        );
+
+        let config = ReportConfig {
+            columns: vec!["file".to_string()],
+            ..Default::default()
+        };
+        let output = formatter_with(ReportFormat::Csv, &config)
+            .format_analysis(&report)
+            .unwrap();
+        assert_eq!(output, "Section,File\nHotspots,src/a.rs\n");
+    }
+
+    #[test]
+    fn test_report_sections() {
+        let config = ReportConfig {
+            sections: vec![Section::Summary, Section::Issues, Section::Suggestions],
+            exclude_sections: vec![Section::Suggestions],
+            ..Default::default()
+        };
+        let report = sample_report();
+
+        let text = formatter_with(ReportFormat::Text, &config)
+            .format(&report)
+            .unwrap();
+        assert!(text.contains("Files: 1"));
+        assert!(text.contains("Findings: 1 (1 medium)"));
+        assert!(!text.contains("src/main.rs"));
+        assert!(!text.contains("整体良好"));
+
+        let markdown = formatter_with(ReportFormat::Markdown, &config)
+            .format(&report)
+            .unwrap();
+        assert!(markdown.contains("## Summary"));
+        assert!(!markdown.contains("## Files"));
+        assert!(!markdown.contains("## AI Review"));
+
+        let json = formatter_with(ReportFormat::Json, &config)
+            .format(&report)
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(value.get("findings").is_some());
+        assert!(value.get("files").is_none());
+        assert!(value.get("ai_review").is_none());
+
+        let csv = formatter_with(ReportFormat::Csv, &config)
+            .format(&report)
+            .unwrap();
+        assert!(csv.starts_with("Section,Severity,Category"));
+        assert!(!csv.contains("Additions"));
+
+        let mut analysis = AnalysisReport::new("Complexity Analysis").metric("Functions", 3);
+        analysis.recommendations.push("拆分 parse_args".to_string());
+        let html = formatter_with(ReportFormat::Html, &config)
+            .format_analysis(&analysis)
+            .unwrap();
+        assert!(html.contains("<td>Functions</td><td>3</td>"));
+        assert!(!html.contains("拆分 parse_args"));
+    }
+
+    #[test]
+    fn test_view_export_formats() {
+        let export = ViewExport {
+            title: "Blame · src/main.rs".to_string(),
+            generated_at: "2024-01-01 10:00:00".to_string(),
+            sections: vec![
+                ExportSection::list("Files", vec!["src/main.rs (+3 / -1)".to_string()]),
+                ExportSection::code("src/main.rs", "diff", vec!["+fn main() {}".to_string()]),
+            ],
+        };
+
+        let text = TextFormatter::default().format_view(&export).unwrap();
+        assert!(text.starts_with("Blame · src/main.rs (2024-01-01 10:00:00)"));
+        assert!(text.contains("  +fn main() {}"));
+
+        let markdown = MarkdownFormatter::default().format_view(&export).unwrap();
+        assert!(markdown.contains("## Files\n\n- src/main.rs (+3 / -1)"));
+        assert!(markdown.contains("```diff\n+fn main() {}\n```"));
+
+        let json = JsonFormatter::default().format_view(&export).unwrap();
+        assert_eq!(serde_json::from_str::<ViewExport>(&json).unwrap(), export);
+    }
+
+    #[test]
+    fn test_analysis_report_formats() {
+        let mut table = ReportTable::new("Hotspots", &["Function", "Cyclomatic"]);
+        table
+            .rows
+            .push(vec!["parse_args".to_string(), "12".to_string()]);
+        let mut report = AnalysisReport::new("Complexity Analysis")
+            .metric("Functions", 3)
+            .table(table)
+            .table(ReportTable::new("Violations", &["Function"]));
+        report.generated_at = "2024-01-01 10:00:00".to_string();
+        report.recommendations.push("拆分 parse_args".to_string());
+
+        let text = TextFormatter::default().format_analysis(&report).unwrap();
+        assert!(text.starts_with("📊 Complexity Analysis (2024-01-01 10:00:00)"));
+        assert!(text.contains("Functions: 3"));
+        assert!(text.contains("  Function    Cyclomatic\n  parse_args  12\n"));
+        assert!(text.contains("Violations\n  (none)"));
+        assert!(text.contains("  - 拆分 parse_args"));
+
+        let markdown = MarkdownFormatter::default()
+            .format_analysis(&report)
+            .unwrap();
+        assert!(markdown.contains("- **Functions**: 3"));
+        assert!(markdown.contains("| Function | Cyclomatic |\n|---|---|\n| parse_args | 12 |"));
+        assert!(markdown.contains("## Recommendations\n\n- 拆分 parse_args"));
+
+        let json = JsonFormatter::default().format_analysis(&report).unwrap();
+        assert_eq!(
+            serde_json::from_str::<AnalysisReport>(&json).unwrap(),
+            report
+        );
+    }
+
+    #[test]
+    fn test_formatter_for() {
+        assert_eq!(formatter_for(ReportFormat::Text).extension(), "txt");
+        assert_eq!(formatter_for(ReportFormat::Markdown).extension(), "md");
+        assert_eq!(formatter_for(ReportFormat::Json).extension(), "json");
+        assert_eq!(formatter_for(ReportFormat::Html).extension(), "html");
+        assert_eq!(formatter_for(ReportFormat::Sarif).extension(), "sarif");
+        assert_eq!(formatter_for(ReportFormat::Csv).extension(), "csv");
+        assert_eq!(formatter_for(ReportFormat::Tsv).extension(), "tsv");
+    }
+}
@@ -0,0 +1,621 @@
+//! 代码审查报告
+//!
+//! 将 diff 的静态分析结果与 AI 审查意见汇总为结构化的 `CodeReviewReport`，
+//! 并通过 `formatters` 输出为不同格式。TUI 视图导出的 `ViewExport` 也使用同一套格式化器。
+
+pub mod diff;
+pub mod formatters;
+pub mod publish;
+pub mod trend;
+
+pub use formatters::{formatter_for, formatter_with, ReportFormatter};
+
+use crate::core::ai::diff_analyzer::{ChangeType, DiffAnalysis};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// 问题严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "💡",
+            Severity::Low => "🔹",
+            Severity::Medium => "⚠️",
+            Severity::High => "🔶",
+            Severity::Critical => "🚨",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "info" => Ok(Severity::Info),
+            "low" => Ok(Severity::Low),
+            "medium" => Ok(Severity::Medium),
+            "high" => Ok(Severity::High),
+            "critical" => Ok(Severity::Critical),
+            other => anyhow::bail!(
+                "Unknown severity '{}' (expected: info, low, medium, high, critical)",
+                other
+            ),
+        }
+    }
+}
+
+/// 审查发现的单个问题
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewFinding {
+    pub severity: Severity,
+    pub category: String,
+    pub message: String,
+    /// 问题涉及的文件（从审查意见中识别）
+    pub file: Option<String>,
+    /// 文件中的行号（审查意见写成 `path:行号` 时识别）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+}
+
+/// 单个文件的变更统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileReview {
+    pub path: String,
+    pub change_type: String,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// 变更概要
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewSummary {
+    pub total_files: usize,
+    pub total_additions: usize,
+    pub total_deletions: usize,
+    pub primary_change_type: String,
+    pub scope: Option<String>,
+}
+
+/// 代码审查报告
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CodeReviewReport {
+    /// 审查对象（已暂存的变更、提交或范围）
+    pub target: String,
+    pub generated_at: String,
+    pub summary: ReviewSummary,
+    pub files: Vec<FileReview>,
+    pub findings: Vec<ReviewFinding>,
+    /// AI 审查原文
+    pub ai_review: String,
+}
+
+impl CodeReviewReport {
+    /// 根据 diff 分析结果和 AI 审查意见构建报告
+    pub fn new(target: &str, analysis: &DiffAnalysis, ai_review: &str) -> Self {
+        let files: Vec<FileReview> = analysis
+            .file_changes
+            .iter()
+            .map(|change| FileReview {
+                path: change.file_path.clone(),
+                change_type: change_type_name(&change.change_type).to_string(),
+                additions: change.additions,
+                deletions: change.deletions,
+            })
+            .collect();
+
+        let known_files: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+
+        Self {
+            target: target.to_string(),
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            summary: ReviewSummary {
+                total_files: analysis.total_files,
+                total_additions: analysis.total_additions,
+                total_deletions: analysis.total_deletions,
+                primary_change_type: analysis.primary_change_type.clone(),
+                scope: analysis.dominant_scope.clone(),
+            },
+            findings: parse_findings(ai_review, &known_files),
+            files,
+            ai_review: ai_review.trim().to_string(),
+        }
+    }
+
+    /// 报告中最高的严重程度
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.findings.iter().map(|f| f.severity).max()
+    }
+
+    /// 严重程度不低于 `threshold` 的问题
+    pub fn findings_at_least(&self, threshold: Severity) -> Vec<&ReviewFinding> {
+        self.findings
+            .iter()
+            .filter(|f| f.severity >= threshold)
+            .collect()
+    }
+
+    /// 统计指定严重程度的问题数量
+    pub fn count_by_severity(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    /// 质量得分：从 100 分起按问题扣分（critical 20、high 10、medium 5、low 2），最低为 0
+    pub fn score(&self) -> u32 {
+        let penalty: u32 = self
+            .findings
+            .iter()
+            .map(|finding| match finding.severity {
+                Severity::Critical => 20,
+                Severity::High => 10,
+                Severity::Medium => 5,
+                Severity::Low => 2,
+                Severity::Info => 0,
+            })
+            .sum();
+        100u32.saturating_sub(penalty)
+    }
+}
+
+/// 从 TUI 导出的视图内容
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ViewExport {
+    /// 视图标题，如 `Git Log`
+    pub title: String,
+    pub generated_at: String,
+    pub sections: Vec<ExportSection>,
+}
+
+/// 导出内容中的一节
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportSection {
+    pub heading: String,
+    /// 代码块的语言（如 `diff`），为空时按列表输出
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub lines: Vec<String>,
+}
+
+impl ViewExport {
+    pub fn new(title: &str, sections: Vec<ExportSection>) -> Self {
+        Self {
+            title: title.to_string(),
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            sections,
+        }
+    }
+}
+
+impl ExportSection {
+    /// 按列表输出的一节
+    pub fn list(heading: &str, lines: Vec<String>) -> Self {
+        Self {
+            heading: heading.to_string(),
+            code: None,
+            lines,
+        }
+    }
+
+    /// 按代码块输出的一节
+    pub fn code(heading: &str, language: &str, lines: Vec<String>) -> Self {
+        Self {
+            heading: heading.to_string(),
+            code: Some(language.to_string()),
+            lines,
+        }
+    }
+}
+
+/// 本地静态分析（`ai-commit analyze ...`）的报告
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisReport {
+    /// 报告标题，如 `Complexity Analysis`
+    pub title: String,
+    pub generated_at: String,
+    /// 汇总指标，按插入顺序输出
+    pub metrics: Vec<AnalysisMetric>,
+    pub tables: Vec<ReportTable>,
+    pub recommendations: Vec<String>,
+    /// 完整的分析数据，只在 JSON 中输出
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+/// 汇总指标
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnalysisMetric {
+    pub name: String,
+    pub value: String,
+}
+
+/// 报告中的表格
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReportTable {
+    pub heading: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl AnalysisReport {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            generated_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn metric(mut self, name: &str, value: impl ToString) -> Self {
+        self.metrics.push(AnalysisMetric {
+            name: name.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    pub fn table(mut self, table: ReportTable) -> Self {
+        self.tables.push(table);
+        self
+    }
+}
+
+impl ReportTable {
+    pub fn new(heading: &str, columns: &[&str]) -> Self {
+        Self {
+            heading: heading.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+}
+
+/// 报告中可以单独选择输出的部分
+///
+/// 审查报告：`summary` 变更概要、`stats` 文件变更统计、`issues` 问题列表、`ai-review` AI 审查原文；
+/// 分析报告：`summary` 汇总指标、`issues` 各个表格、`suggestions` 建议。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Summary,
+    Stats,
+    Issues,
+    AiReview,
+    Suggestions,
+}
+
+impl Section {
+    pub const ALL: [Section; 5] = [
+        Section::Summary,
+        Section::Stats,
+        Section::Issues,
+        Section::AiReview,
+        Section::Suggestions,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Section::Summary => "summary",
+            Section::Stats => "stats",
+            Section::Issues => "issues",
+            Section::AiReview => "ai-review",
+            Section::Suggestions => "suggestions",
+        }
+    }
+}
+
+impl FromStr for Section {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().replace('_', "-").as_str() {
+            "summary" => Ok(Section::Summary),
+            "stats" | "files" => Ok(Section::Stats),
+            "issues" | "findings" => Ok(Section::Issues),
+            "ai-review" | "review" => Ok(Section::AiReview),
+            "suggestions" | "recommendations" => Ok(Section::Suggestions),
+            other => anyhow::bail!(
+                "Unknown report section '{}' (expected: summary, stats, issues, ai-review, suggestions)",
+                other
+            ),
+        }
+    }
+}
+
+/// 报告输出设置，来自配置文件的 `[report]` 和 `--report-sections`/`--exclude-section`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReportConfig {
+    /// CSV/TSV 输出的列名（不区分大小写），为空时输出全部列
+    pub columns: Vec<String>,
+    /// 只输出这些部分，为空时输出全部
+    pub sections: Vec<Section>,
+    /// 不输出的部分，优先于 `sections`
+    pub exclude_sections: Vec<Section>,
+}
+
+impl ReportConfig {
+    /// 是否输出报告的指定部分
+    pub fn includes(&self, section: Section) -> bool {
+        (self.sections.is_empty() || self.sections.contains(&section))
+            && !self.exclude_sections.contains(&section)
+    }
+
+    /// 不输出的部分
+    pub fn omitted_sections(&self) -> Vec<Section> {
+        Section::ALL
+            .into_iter()
+            .filter(|section| !self.includes(*section))
+            .collect()
+    }
+}
+
+/// 报告输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    Markdown,
+    Json,
+    Html,
+    Sarif,
+    Csv,
+    Tsv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "text" | "txt" => Ok(ReportFormat::Text),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "json" => Ok(ReportFormat::Json),
+            "html" | "htm" => Ok(ReportFormat::Html),
+            "sarif" => Ok(ReportFormat::Sarif),
+            "csv" => Ok(ReportFormat::Csv),
+            "tsv" => Ok(ReportFormat::Tsv),
+            other => anyhow::bail!(
+                "Unsupported report format '{}' (expected: text, markdown, json, html, sarif, csv, tsv)",
+                other
+            ),
+        }
+    }
+}
+
+/// 归一化问题描述：忽略大小写、多余空白和数字（行号、数量等）
+pub(crate) fn normalize_message(message: &str) -> String {
+    let mut normalized = String::with_capacity(message.len());
+    let mut last_digit = false;
+    for word in message.split_whitespace() {
+        if !normalized.is_empty() {
+            normalized.push(' ');
+        }
+        for c in word.chars() {
+            if c.is_ascii_digit() {
+                if !last_digit {
+                    normalized.push('#');
+                }
+                last_digit = true;
+            } else {
+                normalized.extend(c.to_lowercase());
+                last_digit = false;
+            }
+        }
+        last_digit = false;
+    }
+    normalized
+}
+
+fn change_type_name(change_type: &ChangeType) -> &'static str {
+    match change_type {
+        ChangeType::Added => "added",
+        ChangeType::Modified => "modified",
+        ChangeType::Deleted => "deleted",
+        ChangeType::Renamed => "renamed",
+        ChangeType::Copied => "copied",
+    }
+}
+
+/// 将 AI 审查意见中各章节的列表项解析为结构化问题
+///
+/// 章节与 ReviewAgent 的提示词一致：严重问题 / 安全问题 / 性能问题 / 改进建议。
+fn parse_findings(ai_review: &str, known_files: &[&str]) -> Vec<ReviewFinding> {
+    let mut findings = Vec::new();
+    let mut current: Option<(Severity, &str)> = None;
+
+    for line in ai_review.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with('#') {
+            let heading = trimmed.trim_start_matches('#').trim();
+            current = section_for_heading(heading);
+            continue;
+        }
+
+        let Some((severity, category)) = current else {
+            continue;
+        };
+
+        let item = strip_list_marker(trimmed);
+        if item.is_empty() || is_empty_marker(item) {
+            continue;
+        }
+
+        let file = known_files.iter().find(|file| item.contains(*file));
+        findings.push(ReviewFinding {
+            severity,
+            category: category.to_string(),
+            message: item.to_string(),
+            line: file.and_then(|file| line_after(item, file)),
+            file: file.map(|file| file.to_string()),
+        });
+    }
+
+    findings
+}
+
+/// 识别紧跟在文件路径后的 `:行号`
+fn line_after(item: &str, file: &str) -> Option<u32> {
+    let start = item.find(file)? + file.len();
+    let rest = item[start..].strip_prefix(':')?;
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+fn section_for_heading(heading: &str) -> Option<(Severity, &'static str)> {
+    if heading.contains("严重") || heading.to_lowercase().contains("critical") {
+        Some((Severity::Critical, "bug"))
+    } else if heading.contains("安全") || heading.to_lowercase().contains("security") {
+        Some((Severity::High, "security"))
+    } else if heading.contains("性能") || heading.to_lowercase().contains("performance") {
+        Some((Severity::Medium, "performance"))
+    } else if heading.contains("建议") || heading.to_lowercase().contains("suggestion") {
+        Some((Severity::Low, "maintainability"))
+    } else {
+        // 总结等章节不作为问题
+        None
+    }
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    let line = line.trim_start_matches(['-', '*', '•']).trim_start();
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && line[digits..].starts_with(['.', '、', ')']) {
+        line[digits..]
+            .trim_start_matches(['.', '、', ')'])
+            .trim_start()
+    } else {
+        line
+    }
+}
+
+fn is_empty_marker(item: &str) -> bool {
+    let item = item.trim_end_matches(['。', '.']);
+    matches!(item, "无" | "暂无" | "没有" | "None" | "none" | "N/A")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_REVIEW: &str = "## 严重问题\n\
+        - src/main.rs:42 中 unwrap 可能导致崩溃\n\n\
+        ## 安全问题\n\
+        无\n\n\
+        ## 性能问题\n\
+        1. 循环中重复克隆字符串\n\n\
+        ## 改进建议\n\
+        * 补充单元测试\n\n\
+        ## 总结\n\
+        整体质量良好。\n";
+
+    fn sample_analysis() -> DiffAnalysis {
+        DiffAnalysis::analyze_diff(
+            "diff --git a/src/main.rs b/src/main.rs\n+let x = 1;\n-let y = 2;\n",
+        )
+    }
+
+    #[test]
+    fn test_parse_findings() {
+        let findings = parse_findings(SAMPLE_REVIEW, &["src/main.rs"]);
+
+        assert_eq!(findings.len(), 3);
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(findings[0].category, "bug");
+        assert_eq!(findings[0].file.as_deref(), Some("src/main.rs"));
+        assert_eq!(findings[0].line, Some(42));
+        assert_eq!(findings[1].severity, Severity::Medium);
+        assert_eq!(findings[1].message, "循环中重复克隆字符串");
+        assert_eq!(findings[2].severity, Severity::Low);
+        assert_eq!(findings[2].file, None);
+        assert_eq!(findings[2].line, None);
+    }
+
+    #[test]
+    fn test_report_from_analysis() {
+        let report = CodeReviewReport::new("staged changes", &sample_analysis(), SAMPLE_REVIEW);
+
+        assert_eq!(report.target, "staged changes");
+        assert_eq!(report.summary.total_files, 1);
+        assert_eq!(report.files[0].path, "src/main.rs");
+        assert_eq!(report.max_severity(), Some(Severity::Critical));
+        assert_eq!(report.count_by_severity(Severity::Low), 1);
+        assert_eq!(report.findings_at_least(Severity::Medium).len(), 2);
+        assert_eq!(report.findings_at_least(Severity::Critical).len(), 1);
+        assert_eq!(report.score(), 73);
+    }
+
+    #[test]
+    fn test_severity_ordering_and_parse() {
+        assert!(Severity::Critical > Severity::High);
+        assert!(Severity::Low > Severity::Info);
+        assert_eq!("HIGH".parse::<Severity>().unwrap(), Severity::High);
+        assert!("urgent".parse::<Severity>().is_err());
+    }
+
+    #[test]
+    fn test_report_format_parse() {
+        assert_eq!(
+            "md".parse::<ReportFormat>().unwrap(),
+            ReportFormat::Markdown
+        );
+        assert_eq!("JSON".parse::<ReportFormat>().unwrap(), ReportFormat::Json);
+        assert_eq!("htm".parse::<ReportFormat>().unwrap(), ReportFormat::Html);
+        assert_eq!(
+            "SARIF".parse::<ReportFormat>().unwrap(),
+            ReportFormat::Sarif
+        );
+        assert_eq!("".parse::<ReportFormat>().unwrap(), ReportFormat::Text);
+        assert!("pdf".parse::<ReportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_report_sections() {
+        assert_eq!("AI_review".parse::<Section>().unwrap(), Section::AiReview);
+        assert_eq!("findings".parse::<Section>().unwrap(), Section::Issues);
+        assert!("charts".parse::<Section>().is_err());
+
+        let config = ReportConfig::default();
+        assert!(config.omitted_sections().is_empty());
+
+        let config = ReportConfig {
+            sections: vec![Section::Summary, Section::Issues, Section::Suggestions],
+            exclude_sections: vec![Section::Suggestions],
+            ..Default::default()
+        };
+        assert!(config.includes(Section::Issues));
+        assert!(!config.includes(Section::Suggestions));
+        assert_eq!(
+            config.omitted_sections(),
+            vec![Section::Stats, Section::AiReview, Section::Suggestions]
+        );
+    }
+
+    #[test]
+    fn test_strip_list_marker() {
+        assert_eq!(strip_list_marker("- item"), "item");
+        assert_eq!(strip_list_marker("12. item"), "item");
+        assert_eq!(strip_list_marker("3、item"), "item");
+        assert_eq!(strip_list_marker("plain"), "plain");
+    }
+}
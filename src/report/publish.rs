@@ -0,0 +1,282 @@
+//! 发布报告到对象存储
+//!
+//! `--report-publish s3://bucket/prefix` 或 `gs://bucket/prefix` 把生成的报告上传到
+//! `<prefix>/<repo>/<branch>/<sha>/<文件名>`，同一提交重复发布时覆盖。
+//! 上传使用本机已登录的 `aws` / `gcloud` 命令行工具，凭证和区域沿用它们的配置。
+//! S3 上的报告输出有效期 7 天的预签名 URL，GCS 输出需要登录 Google 账号访问的地址。
+
+use crate::git::GitCore;
+use std::path::Path;
+use std::str::FromStr;
+use tokio::process::Command;
+
+/// S3 预签名 URL 的有效期（秒），即 aws 允许的最大值 7 天
+const PRESIGN_EXPIRES_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// 对象存储服务
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    S3,
+    Gcs,
+}
+
+/// 发布位置：存储桶和键前缀
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishTarget {
+    pub provider: Provider,
+    pub bucket: String,
+    /// 不含首尾 `/`，可以为空
+    pub prefix: String,
+}
+
+impl FromStr for PublishTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+        let (provider, rest) = if let Some(rest) = s.strip_prefix("s3://") {
+            (Provider::S3, rest)
+        } else if let Some(rest) = s.strip_prefix("gs://") {
+            (Provider::Gcs, rest)
+        } else {
+            anyhow::bail!(
+                "Unsupported publish location '{}' (expected s3://bucket/prefix or gs://bucket/prefix)",
+                s
+            );
+        };
+        let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+        if bucket.is_empty() {
+            anyhow::bail!("Publish location '{}' has no bucket", s);
+        }
+        Ok(Self {
+            provider,
+            bucket: bucket.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+        })
+    }
+}
+
+impl PublishTarget {
+    /// 报告的对象键：`<prefix>/<repo>/<branch>/<sha>/<file_name>`
+    pub fn object_key(&self, repo: &str, branch: &str, sha: &str, file_name: &str) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if !self.prefix.is_empty() {
+            parts.push(self.prefix.clone());
+        }
+        parts.extend([repo, branch, sha, file_name].map(key_segment));
+        parts.join("/")
+    }
+
+    /// `s3://` / `gs://` 形式的对象地址
+    pub fn object_uri(&self, key: &str) -> String {
+        let scheme = match self.provider {
+            Provider::S3 => "s3",
+            Provider::Gcs => "gs",
+        };
+        format!("{}://{}/{}", scheme, self.bucket, key)
+    }
+
+    /// 上传文件，返回用于分享的 URL
+    pub async fn upload(&self, file: &Path, key: &str) -> anyhow::Result<String> {
+        let uri = self.object_uri(key);
+        let content_type = content_type(file);
+        let file = file.to_string_lossy();
+        match self.provider {
+            Provider::S3 => {
+                run(
+                    "aws",
+                    &["s3", "cp", &file, &uri, "--content-type", content_type],
+                )
+                .await?;
+                let url = run(
+                    "aws",
+                    &[
+                        "s3",
+                        "presign",
+                        &uri,
+                        "--expires-in",
+                        &PRESIGN_EXPIRES_SECS.to_string(),
+                    ],
+                )
+                .await?;
+                Ok(url.trim().to_string())
+            }
+            Provider::Gcs => {
+                run(
+                    "gcloud",
+                    &[
+                        "storage",
+                        "cp",
+                        &file,
+                        &uri,
+                        &format!("--content-type={}", content_type),
+                    ],
+                )
+                .await?;
+                Ok(format!(
+                    "https://storage.cloud.google.com/{}/{}",
+                    self.bucket, key
+                ))
+            }
+        }
+    }
+}
+
+/// 对象键中的一段：只保留字母、数字、`.`、`_` 和 `-`，其余字符（包括分支名中的 `/`）换成 `-`
+fn key_segment(segment: &str) -> String {
+    let segment: String = segment
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    if segment.is_empty() || segment.chars().all(|c| c == '.') {
+        "unknown".to_string()
+    } else {
+        segment
+    }
+}
+
+fn content_type(file: &Path) -> &'static str {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("json") => "application/json",
+        Some("sarif") => "application/sarif+json",
+        Some("md") => "text/markdown; charset=utf-8",
+        Some("csv") => "text/csv; charset=utf-8",
+        Some("tsv") => "text/tab-separated-values; charset=utf-8",
+        _ => "text/plain; charset=utf-8",
+    }
+}
+
+async fn run(program: &str, args: &[&str]) -> anyhow::Result<String> {
+    let output = match Command::new(program).args(args).output().await {
+        Ok(output) => output,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            anyhow::bail!("'{}' is not installed, cannot publish report", program)
+        }
+        Err(e) => anyhow::bail!("Failed to run {}: {}", program, e),
+    };
+    if !output.status.success() {
+        anyhow::bail!(
+            "{} {} failed: {}",
+            program,
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 发布报告内容：`file` 为已写出的报告文件，为空时先把内容写入临时目录的 `file_name`
+///
+/// `commit` 是报告对应的提交，未指定时使用 HEAD。返回用于分享的 URL。
+pub async fn publish(
+    location: &str,
+    content: &str,
+    file: Option<&Path>,
+    file_name: &str,
+    commit: Option<&str>,
+) -> anyhow::Result<String> {
+    let target: PublishTarget = location.parse()?;
+
+    let repo = GitCore::get_repo_root()
+        .await?
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let branch = GitCore::get_current_branch().await.unwrap_or_default();
+    let branch = if branch.is_empty() {
+        "detached".to_string()
+    } else {
+        branch
+    };
+    let sha = match commit {
+        Some(commit) => commit.to_string(),
+        None => GitCore::get_latest_commit_hash().await?,
+    };
+
+    match file {
+        Some(file) => {
+            let file_name = file
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| file_name.to_string());
+            let key = target.object_key(&repo, &branch, &sha, &file_name);
+            target.upload(file, &key).await
+        }
+        None => {
+            // 文件名前加进程号，避免多个进程互相覆盖
+            let temp = std::env::temp_dir().join(format!(
+                "ai-commit-{}-{}",
+                std::process::id(),
+                file_name
+            ));
+            tokio::fs::write(&temp, content).await?;
+            let key = target.object_key(&repo, &branch, &sha, file_name);
+            let result = target.upload(&temp, &key).await;
+            let _ = tokio::fs::remove_file(&temp).await;
+            result
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_publish_target() {
+        let target: PublishTarget = "s3://reports/ci/ai-commit/".parse().unwrap();
+        assert_eq!(target.provider, Provider::S3);
+        assert_eq!(target.bucket, "reports");
+        assert_eq!(target.prefix, "ci/ai-commit");
+
+        let target: PublishTarget = "gs://reports".parse().unwrap();
+        assert_eq!(target.provider, Provider::Gcs);
+        assert_eq!(target.prefix, "");
+
+        assert!("https://example.com/reports"
+            .parse::<PublishTarget>()
+            .is_err());
+        assert!("s3:///prefix".parse::<PublishTarget>().is_err());
+    }
+
+    #[test]
+    fn test_object_key_is_stable() {
+        let target: PublishTarget = "s3://reports/ci".parse().unwrap();
+        let key = target.object_key("ai-commit", "feature/login", "3f2a9c0", "review.html");
+        assert_eq!(key, "ci/ai-commit/feature-login/3f2a9c0/review.html");
+        assert_eq!(
+            target.object_uri(&key),
+            "s3://reports/ci/ai-commit/feature-login/3f2a9c0/review.html"
+        );
+
+        let target: PublishTarget = "gs://reports".parse().unwrap();
+        assert_eq!(
+            target.object_key("..", "", "abc", "a b.json"),
+            "unknown/unknown/abc/a-b.json"
+        );
+    }
+
+    #[test]
+    fn test_content_type() {
+        assert_eq!(
+            content_type(Path::new("review.html")),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(
+            content_type(Path::new("review.sarif")),
+            "application/sarif+json"
+        );
+        assert_eq!(
+            content_type(Path::new("report")),
+            "text/plain; charset=utf-8"
+        );
+    }
+}
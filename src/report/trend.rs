@@ -0,0 +1,248 @@
+//! 审查报告趋势
+//!
+//! 汇总按提交保存的多份审查报告：每份报告的质量得分和各严重程度的问题数按保存时间排列，
+//! 再统计在多份报告中反复出现的问题（按类别和归一化后的描述分组），
+//! 用于观察代码质量的变化和需要系统性解决的问题。
+
+use super::{normalize_message, Severity};
+use crate::storage::StoredReport;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 单份审查报告的统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewTrendPoint {
+    pub commit: String,
+    pub stored_at: String,
+    pub target: String,
+    pub score: u32,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub info: usize,
+}
+
+impl ReviewTrendPoint {
+    fn from_stored(stored: &StoredReport) -> Self {
+        let report = &stored.report;
+        Self {
+            commit: stored.commit.clone(),
+            stored_at: stored.stored_at.clone(),
+            target: report.target.clone(),
+            score: report.score(),
+            critical: report.count_by_severity(Severity::Critical),
+            high: report.count_by_severity(Severity::High),
+            medium: report.count_by_severity(Severity::Medium),
+            low: report.count_by_severity(Severity::Low),
+            info: report.count_by_severity(Severity::Info),
+        }
+    }
+
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(8)]
+    }
+
+    pub fn total(&self) -> usize {
+        self.critical + self.high + self.medium + self.low + self.info
+    }
+}
+
+/// 在多份报告中出现的问题
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecurringIssue {
+    pub category: String,
+    /// 最近一次出现时的描述
+    pub message: String,
+    pub severity: Severity,
+    /// 出现该问题的报告数
+    pub reports: usize,
+    /// 最近一次出现的提交
+    pub last_commit: String,
+}
+
+/// 审查报告趋势
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewTrend {
+    /// 按保存时间从早到晚排列
+    pub points: Vec<ReviewTrendPoint>,
+    /// 按出现的报告数从多到少排列，只包含出现在两份及以上报告中的问题
+    pub recurring: Vec<RecurringIssue>,
+}
+
+impl ReviewTrend {
+    /// 汇总报告，`top` 限制反复出现问题的数量
+    pub fn build(reports: &[StoredReport], top: usize) -> Self {
+        let mut reports: Vec<&StoredReport> = reports.iter().collect();
+        reports.sort_by(|a, b| a.stored_at.cmp(&b.stored_at).then(a.commit.cmp(&b.commit)));
+
+        let mut recurring: HashMap<(String, String), RecurringIssue> = HashMap::new();
+        for stored in &reports {
+            let mut seen = Vec::new();
+            for finding in &stored.report.findings {
+                let key = (
+                    finding.category.clone(),
+                    normalize_message(&finding.message),
+                );
+                // 同一份报告中的重复问题只计一次
+                if seen.contains(&key) {
+                    continue;
+                }
+                seen.push(key.clone());
+                let issue = recurring.entry(key).or_insert_with(|| RecurringIssue {
+                    category: finding.category.clone(),
+                    message: finding.message.clone(),
+                    severity: finding.severity,
+                    reports: 0,
+                    last_commit: String::new(),
+                });
+                issue.reports += 1;
+                issue.message = finding.message.clone();
+                issue.severity = issue.severity.max(finding.severity);
+                issue.last_commit = stored.commit.clone();
+            }
+        }
+
+        let mut recurring: Vec<RecurringIssue> = recurring
+            .into_values()
+            .filter(|issue| issue.reports > 1)
+            .collect();
+        recurring.sort_by(|a, b| {
+            b.reports
+                .cmp(&a.reports)
+                .then(b.severity.cmp(&a.severity))
+                .then(a.category.cmp(&b.category))
+                .then(a.message.cmp(&b.message))
+        });
+        recurring.truncate(top);
+
+        Self {
+            points: reports
+                .iter()
+                .map(|stored| ReviewTrendPoint::from_stored(stored))
+                .collect(),
+            recurring,
+        }
+    }
+
+    pub fn score_sparkline(&self) -> String {
+        crate::analysis::trend::sparkline(
+            &self
+                .points
+                .iter()
+                .map(|p| p.score as f64)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn findings_sparkline(&self) -> String {
+        crate::analysis::trend::sparkline(
+            &self
+                .points
+                .iter()
+                .map(|p| p.total() as f64)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// 最后一份报告相对第一份的得分变化
+    pub fn score_change(&self) -> i64 {
+        match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => last.score as i64 - first.score as i64,
+            _ => 0,
+        }
+    }
+
+    pub fn recommendations(&self) -> Vec<String> {
+        let mut recommendations = Vec::new();
+        if self.score_change() < 0 {
+            recommendations.push(format!(
+                "审查得分下降了 {} 分，建议检查最近提交中新增的问题",
+                -self.score_change()
+            ));
+        }
+        if let Some(issue) = self.recurring.first() {
+            recommendations.push(format!(
+                "「{}」在 {} 份报告中出现，建议在代码规范或自定义规则中统一处理",
+                issue.message, issue.reports
+            ));
+        }
+        recommendations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::ReviewFinding;
+
+    fn finding(severity: Severity, category: &str, message: &str) -> ReviewFinding {
+        ReviewFinding {
+            severity,
+            category: category.to_string(),
+            message: message.to_string(),
+            file: None,
+            line: None,
+        }
+    }
+
+    fn stored(commit: &str, stored_at: &str, findings: Vec<ReviewFinding>) -> StoredReport {
+        StoredReport {
+            commit: commit.to_string(),
+            stored_at: stored_at.to_string(),
+            report: crate::report::CodeReviewReport {
+                target: commit.to_string(),
+                findings,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_trend_orders_points_and_counts_recurring_issues() {
+        let reports = vec![
+            stored(
+                "cccc3333",
+                "2024-05-03 10:00:00",
+                vec![finding(
+                    Severity::Medium,
+                    "performance",
+                    "循环中重复克隆字符串（第 12 行）",
+                )],
+            ),
+            stored(
+                "aaaa1111",
+                "2024-05-01 10:00:00",
+                vec![
+                    finding(Severity::High, "security", "SQL 拼接"),
+                    finding(
+                        Severity::Low,
+                        "performance",
+                        "循环中重复克隆字符串（第 40 行）",
+                    ),
+                    finding(
+                        Severity::Low,
+                        "performance",
+                        "循环中重复克隆字符串（第 41 行）",
+                    ),
+                ],
+            ),
+            stored("bbbb2222", "2024-05-02 10:00:00", vec![]),
+        ];
+
+        let trend = ReviewTrend::build(&reports, 5);
+        let commits: Vec<&str> = trend.points.iter().map(|p| p.short_commit()).collect();
+        assert_eq!(commits, vec!["aaaa1111", "bbbb2222", "cccc3333"]);
+        assert_eq!(trend.points[0].score, 86);
+        assert_eq!(trend.points[0].total(), 3);
+        assert_eq!(trend.score_change(), 9);
+        assert_eq!(trend.score_sparkline().chars().count(), 3);
+
+        assert_eq!(trend.recurring.len(), 1);
+        let issue = &trend.recurring[0];
+        assert_eq!(issue.reports, 2);
+        assert_eq!(issue.severity, Severity::Medium);
+        assert_eq!(issue.last_commit, "cccc3333");
+        assert_eq!(trend.recommendations().len(), 1);
+    }
+}
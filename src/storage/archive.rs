@@ -0,0 +1,242 @@
+//! 审查报告归档
+//!
+//! `ai-commit report export` 把选中的报告打包为 tar 归档：`manifest.json` 记录格式版本、
+//! 导出时间和报告列表，每份报告保存为 `reports/<sha>.json`（与存储中的格式相同，已解密）。
+//! 默认使用 zstd 压缩，文件名以 `.tar.gz`/`.tgz` 结尾时用 gzip，以 `.tar` 结尾时不压缩。
+//! 导入时按文件头识别压缩格式，可以在不同机器和存储后端之间迁移历史报告。
+
+use super::StoredReport;
+use serde::{Deserialize, Serialize};
+use std::io::{BufReader, Read, Seek, Write};
+use std::path::Path;
+
+/// 归档格式版本，格式不兼容地变化时递增
+pub const ARCHIVE_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const REPORTS_DIR: &str = "reports/";
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// 归档的元数据
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub version: u32,
+    pub exported_at: String,
+    /// 导出时使用的 ai-commit 版本
+    pub tool_version: String,
+    /// 导出时的存储后端
+    pub source_backend: String,
+    /// 报告的提交 SHA，按保存时间排列
+    pub commits: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    fn for_path(path: &Path) -> Self {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Self::Gzip
+        } else if name.ends_with(".tar") {
+            Self::None
+        } else {
+            Self::Zstd
+        }
+    }
+}
+
+/// 把报告写入归档，返回写入的元数据
+pub fn write_archive(
+    path: &Path,
+    reports: &[StoredReport],
+    source_backend: &str,
+) -> anyhow::Result<ArchiveManifest> {
+    let manifest = ArchiveManifest {
+        version: ARCHIVE_VERSION,
+        exported_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        source_backend: source_backend.to_string(),
+        commits: reports.iter().map(|r| r.commit.clone()).collect(),
+    };
+
+    let file = std::fs::File::create(path)
+        .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", path.display(), e))?;
+    match Compression::for_path(path) {
+        Compression::None => {
+            build_tar(file, &manifest, reports)?;
+        }
+        Compression::Gzip => {
+            let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            build_tar(encoder, &manifest, reports)?.finish()?;
+        }
+        Compression::Zstd => {
+            let encoder = zstd::stream::write::Encoder::new(file, 0)?;
+            build_tar(encoder, &manifest, reports)?.finish()?;
+        }
+    }
+    Ok(manifest)
+}
+
+fn build_tar<W: Write>(
+    writer: W,
+    manifest: &ArchiveManifest,
+    reports: &[StoredReport],
+) -> anyhow::Result<W> {
+    let mut builder = tar::Builder::new(writer);
+    append(
+        &mut builder,
+        MANIFEST_NAME,
+        &serde_json::to_vec_pretty(manifest)?,
+    )?;
+    for report in reports {
+        append(
+            &mut builder,
+            &format!("{}{}.json", REPORTS_DIR, report.commit),
+            &serde_json::to_vec_pretty(report)?,
+        )?;
+    }
+    Ok(builder.into_inner()?)
+}
+
+fn append<W: Write>(builder: &mut tar::Builder<W>, name: &str, data: &[u8]) -> anyhow::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, name, data)?;
+    Ok(())
+}
+
+/// 读取归档中的元数据和报告
+pub fn read_archive(path: &Path) -> anyhow::Result<(ArchiveManifest, Vec<StoredReport>)> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path.display(), e))?;
+    let mut magic = [0u8; 4];
+    let read = file.read(&mut magic)?;
+    file.rewind()?;
+    let file = BufReader::new(file);
+
+    if read >= 4 && magic == ZSTD_MAGIC {
+        parse_tar(zstd::stream::read::Decoder::with_buffer(file)?)
+    } else if read >= 2 && magic[..2] == GZIP_MAGIC {
+        parse_tar(flate2::read::GzDecoder::new(file))
+    } else {
+        parse_tar(file)
+    }
+}
+
+fn parse_tar<R: Read>(reader: R) -> anyhow::Result<(ArchiveManifest, Vec<StoredReport>)> {
+    let mut archive = tar::Archive::new(reader);
+    let mut manifest: Option<ArchiveManifest> = None;
+    let mut reports = Vec::new();
+
+    for entry in archive
+        .entries()
+        .map_err(|e| anyhow::anyhow!("Not a valid report archive: {}", e))?
+    {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data)?;
+
+        if name == MANIFEST_NAME {
+            let parsed: ArchiveManifest = serde_json::from_slice(&data)
+                .map_err(|e| anyhow::anyhow!("Invalid archive manifest: {}", e))?;
+            if parsed.version > ARCHIVE_VERSION {
+                anyhow::bail!(
+                    "Archive format version {} is newer than supported version {}; upgrade ai-commit",
+                    parsed.version,
+                    ARCHIVE_VERSION
+                );
+            }
+            manifest = Some(parsed);
+        } else if name.starts_with(REPORTS_DIR) && name.ends_with(".json") {
+            let report: StoredReport = serde_json::from_slice(&data)
+                .map_err(|e| anyhow::anyhow!("Invalid report {} in archive: {}", name, e))?;
+            reports.push(report);
+        }
+    }
+
+    let manifest = manifest
+        .ok_or_else(|| anyhow::anyhow!("Not a report archive: {} missing", MANIFEST_NAME))?;
+    Ok((manifest, reports))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::CodeReviewReport;
+
+    fn stored(commit: &str, stored_at: &str) -> StoredReport {
+        StoredReport {
+            commit: commit.to_string(),
+            stored_at: stored_at.to_string(),
+            report: CodeReviewReport {
+                target: commit.to_string(),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_archive_roundtrip_all_compressions() {
+        let dir = tempfile::tempdir().unwrap();
+        let reports = vec![
+            stored("aaaa", "2024-05-01 09:00:00"),
+            stored("bbbb", "2024-05-02 09:00:00"),
+        ];
+
+        for name in ["reports.tar.zst", "reports.tgz", "reports.tar"] {
+            let path = dir.path().join(name);
+            let manifest = write_archive(&path, &reports, "file").unwrap();
+            assert_eq!(manifest.commits, vec!["aaaa", "bbbb"]);
+
+            let (read_manifest, read_reports) = read_archive(&path).unwrap();
+            assert_eq!(read_manifest, manifest);
+            assert_eq!(read_reports, reports);
+        }
+
+        let mut magic = [0u8; 4];
+        std::fs::File::open(dir.path().join("reports.tar.zst"))
+            .unwrap()
+            .read_exact(&mut magic)
+            .unwrap();
+        assert_eq!(magic, ZSTD_MAGIC);
+    }
+
+    #[test]
+    fn test_reject_invalid_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("not-an-archive.tar.zst");
+        std::fs::write(&path, b"hello").unwrap();
+        assert!(read_archive(&path).is_err());
+
+        // 更新版本导出的归档
+        let path = dir.path().join("future.tar");
+        build_tar(
+            std::fs::File::create(&path).unwrap(),
+            &ArchiveManifest {
+                version: ARCHIVE_VERSION + 1,
+                exported_at: String::new(),
+                tool_version: String::new(),
+                source_backend: String::new(),
+                commits: Vec::new(),
+            },
+            &[],
+        )
+        .unwrap();
+        let err = read_archive(&path).unwrap_err();
+        assert!(err.to_string().contains("newer than supported"));
+    }
+}
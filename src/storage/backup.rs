@@ -0,0 +1,289 @@
+//! 定时备份
+//!
+//! `BackupManager` 把已保存的审查报告打包为归档（格式与 `reports export` 相同），写入备份目录
+//! （默认数据目录下的 `backups/`，设置了命名空间时在 `backups/<namespace>/` 下）。
+//! 写入后重新读取归档，核对元数据与报告一致才保留，然后删除超过 `backup_keep` 份的旧备份。
+//! 配置 `[storage] backup_interval_hours` 后守护进程和 TUI 在到期时自动备份，
+//! `ai-commit storage restore` 从备份恢复。
+
+use super::archive::{self, ArchiveManifest};
+use super::{StorageManager, StoredReport};
+use crate::config::Config;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// 默认保留的备份数
+pub const DEFAULT_KEEP: usize = 7;
+
+const FILE_PREFIX: &str = "ai-commit-backup-";
+const FILE_SUFFIX: &str = ".tar.zst";
+
+/// 一份备份文件
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub modified: SystemTime,
+    pub size: u64,
+}
+
+/// 一次备份的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupSummary {
+    pub path: PathBuf,
+    pub reports: usize,
+    pub size: u64,
+    /// 轮换时删除的旧备份
+    pub removed: Vec<PathBuf>,
+}
+
+impl BackupSummary {
+    pub fn format(&self) -> String {
+        let mut text = format!(
+            "Backed up {} report(s) to {} ({} bytes, verified)",
+            self.reports,
+            self.path.display(),
+            self.size
+        );
+        if !self.removed.is_empty() {
+            text.push_str(&format!("; removed {} old backup(s)", self.removed.len()));
+        }
+        text
+    }
+}
+
+/// 备份的创建、校验、轮换和查找
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupManager {
+    dir: PathBuf,
+    keep: usize,
+    /// 定时备份的间隔，`None` 时只手动备份
+    interval: Option<Duration>,
+}
+
+impl BackupManager {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            keep: DEFAULT_KEEP,
+            interval: None,
+        }
+    }
+
+    /// 最多保留的备份数，至少 1 份
+    pub fn with_keep(mut self, keep: usize) -> Self {
+        self.keep = keep.max(1);
+        self
+    }
+
+    pub fn with_interval(mut self, interval: Option<Duration>) -> Self {
+        self.interval = interval.filter(|interval| !interval.is_zero());
+        self
+    }
+
+    /// 按 `[storage]` 的 `backup_dir`、`backup_keep`、`backup_interval_hours` 创建，
+    /// 命名空间的备份放在单独的子目录
+    pub fn from_config(config: &Config, namespace: Option<&str>) -> anyhow::Result<Self> {
+        let storage = &config.storage;
+        let mut dir = match &storage.backup_dir {
+            Some(dir) => crate::config::file::expand_home(dir),
+            None => {
+                if config.data_dir.as_os_str().is_empty() {
+                    anyhow::bail!("Cannot find home directory");
+                }
+                config.data_dir.join("backups")
+            }
+        };
+        if let Some(namespace) = namespace {
+            dir.push(namespace);
+        }
+        Ok(Self::new(dir)
+            .with_keep(storage.backup_keep.unwrap_or(DEFAULT_KEEP))
+            .with_interval(
+                storage
+                    .backup_interval_hours
+                    .map(|hours| Duration::from_secs(hours.saturating_mul(3600))),
+            ))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 已有的备份，最新的在前
+    pub fn list(&self) -> anyhow::Result<Vec<BackupInfo>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut backups = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with(FILE_PREFIX) || !name.ends_with(FILE_SUFFIX) {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            backups.push(BackupInfo {
+                path: entry.path(),
+                modified: metadata.modified()?,
+                size: metadata.len(),
+            });
+        }
+        // 修改时间相同时按文件名中的时间戳排序
+        backups.sort_by(|a, b| b.modified.cmp(&a.modified).then(b.path.cmp(&a.path)));
+        Ok(backups)
+    }
+
+    pub fn latest(&self) -> anyhow::Result<Option<BackupInfo>> {
+        Ok(self.list()?.into_iter().next())
+    }
+
+    /// 配置了定时备份且距上次备份已超过间隔
+    pub fn is_due(&self, now: SystemTime) -> anyhow::Result<bool> {
+        let Some(interval) = self.interval else {
+            return Ok(false);
+        };
+        Ok(match self.latest()? {
+            Some(latest) => now
+                .duration_since(latest.modified)
+                .is_ok_and(|age| age >= interval),
+            None => true,
+        })
+    }
+
+    /// 备份存储中的全部审查报告，校验后轮换旧备份
+    pub async fn create(&self, storage: &StorageManager) -> anyhow::Result<BackupSummary> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to create backup directory {}: {}",
+                self.dir.display(),
+                e
+            )
+        })?;
+        let reports = storage.load_reports(None).await?;
+        let name = format!(
+            "{}{}{}",
+            FILE_PREFIX,
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            FILE_SUFFIX
+        );
+        let path = self.dir.join(&name);
+        // 先写临时文件，校验通过后再改名，失败时不会留下无效的备份
+        let partial = self.dir.join(format!("{}.partial", name));
+        let written = archive::write_archive(&partial, &reports, storage.backend().name())
+            .and_then(|manifest| {
+                let (verified, _) = verify(&partial)?;
+                if verified.commits != manifest.commits {
+                    anyhow::bail!("Backup contents do not match the written manifest");
+                }
+                Ok(())
+            });
+        if let Err(e) = written {
+            let _ = std::fs::remove_file(&partial);
+            return Err(e.context("Backup verification failed"));
+        }
+        std::fs::rename(&partial, &path)?;
+
+        let removed = self.rotate()?;
+        Ok(BackupSummary {
+            size: std::fs::metadata(&path)?.len(),
+            path,
+            reports: reports.len(),
+            removed,
+        })
+    }
+
+    /// 删除超过保留数量的旧备份
+    fn rotate(&self) -> anyhow::Result<Vec<PathBuf>> {
+        let mut removed = Vec::new();
+        for backup in self.list()?.into_iter().skip(self.keep) {
+            std::fs::remove_file(&backup.path)?;
+            removed.push(backup.path);
+        }
+        Ok(removed)
+    }
+}
+
+/// 校验备份归档：能完整解压，报告与元数据列出的提交一一对应，返回其中的元数据和报告
+pub fn verify(path: &Path) -> anyhow::Result<(ArchiveManifest, Vec<StoredReport>)> {
+    let (manifest, reports) = archive::read_archive(path)?;
+    let mut listed = manifest.commits.clone();
+    let mut found: Vec<&str> = reports.iter().map(|r| r.commit.as_str()).collect();
+    listed.sort();
+    found.sort();
+    if listed != found {
+        anyhow::bail!(
+            "Backup {} is incomplete: manifest lists {} report(s), archive contains {}",
+            path.display(),
+            listed.len(),
+            found.len()
+        );
+    }
+    Ok((manifest, reports))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::CodeReviewReport;
+    use crate::storage::FileBackend;
+
+    async fn storage_with_reports(dir: &tempfile::TempDir) -> StorageManager {
+        let storage = StorageManager::new(Box::new(FileBackend::new(dir.path().join("storage"))));
+        for sha in ["aaaa1111", "bbbb2222"] {
+            storage
+                .save_report(sha, &CodeReviewReport::default())
+                .await
+                .unwrap();
+        }
+        storage
+    }
+
+    #[tokio::test]
+    async fn test_backup_verify_and_rotate() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage_with_reports(&dir).await;
+        let manager = BackupManager::new(dir.path().join("backups")).with_keep(2);
+
+        let summary = manager.create(&storage).await.unwrap();
+        assert_eq!(summary.reports, 2);
+        assert!(summary.removed.is_empty());
+        assert_eq!(verify(&summary.path).unwrap().1.len(), 2);
+
+        // 伪造两份更早的备份，轮换时删除最旧的
+        for (name, age) in [("20200101-000000", 200), ("20200102-000000", 100)] {
+            let path = manager
+                .dir()
+                .join(format!("{}{}{}", FILE_PREFIX, name, FILE_SUFFIX));
+            std::fs::copy(&summary.path, &path).unwrap();
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::now() - Duration::from_secs(age))
+                .unwrap();
+        }
+        let removed = manager.rotate().unwrap();
+        assert_eq!(removed.len(), 1);
+        assert!(removed[0].to_string_lossy().contains("20200101"));
+        assert_eq!(manager.list().unwrap()[0].path, summary.path);
+    }
+
+    #[tokio::test]
+    async fn test_schedule_and_corrupt_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage_with_reports(&dir).await;
+        let manual = BackupManager::new(dir.path().join("backups"));
+        assert!(!manual.is_due(SystemTime::now()).unwrap());
+
+        let scheduled = manual.with_interval(Some(Duration::from_secs(3600)));
+        assert!(scheduled.is_due(SystemTime::now()).unwrap());
+        let summary = scheduled.create(&storage).await.unwrap();
+        assert!(!scheduled.is_due(SystemTime::now()).unwrap());
+        assert!(scheduled
+            .is_due(SystemTime::now() + Duration::from_secs(7200))
+            .unwrap());
+
+        let bytes = std::fs::read(&summary.path).unwrap();
+        std::fs::write(&summary.path, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(verify(&summary.path).is_err());
+    }
+}
@@ -0,0 +1,411 @@
+//! 加密存储：在任意后端之上用 AES-256-GCM 加密保存的值
+//!
+//! `[storage] encryption_enabled = true` 时启用。每个值使用随机 nonce 加密，
+//! 键名作为附加认证数据，密文无法被挪到另一个键下使用。
+//! 没有加密标记的值不被信任，读取时报错；启用加密前保存的明文数据用
+//! `ai-commit storage encrypt`（`encrypt_plaintext`）加密后才能读取，
+//! 避免能写入后端的人注入未经认证的值。
+//! 密文无法再压缩，需要压缩时（S3 的 `s3_compress`）在加密前压缩明文。
+//!
+//! 密钥依次取 `AI_COMMIT_STORAGE_KEY`、`[storage] encryption_key`，都未设置时读取系统钥匙串
+//! （Linux 的 `secret-tool`、macOS 的 `security`），钥匙串中没有时生成新密钥并保存进去。
+
+use super::s3::{compress, decompress};
+use super::StorageBackend;
+use async_trait::async_trait;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// 加密值的开头标记
+const MAGIC: &[u8] = b"AICENC1\0";
+
+/// 明文先经 zlib 压缩的加密值的开头标记
+const MAGIC_COMPRESSED: &[u8] = b"AICENZ1\0";
+
+/// 密钥字节数
+pub const KEY_LEN: usize = 32;
+
+/// 系统钥匙串中的服务名和账户名
+const KEYRING_SERVICE: &str = "ai-commit";
+const KEYRING_ACCOUNT: &str = "storage-key";
+
+/// 加密包装的存储后端
+pub struct EncryptedBackend {
+    inner: Box<dyn StorageBackend>,
+    key: LessSafeKey,
+    rng: SystemRandom,
+    compress: bool,
+}
+
+impl EncryptedBackend {
+    pub fn new(inner: Box<dyn StorageBackend>, key: &[u8; KEY_LEN]) -> Self {
+        let key = UnboundKey::new(&AES_256_GCM, key).expect("AES-256 key length is fixed");
+        Self {
+            inner,
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+            compress: false,
+        }
+    }
+
+    /// 加密前用 zlib 压缩明文
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// 加密为 `MAGIC | nonce | 密文和认证标签`，压缩时使用 `MAGIC_COMPRESSED`
+    fn seal(&self, key: &str, plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| anyhow::anyhow!("Failed to generate encryption nonce"))?;
+        let (magic, mut data) = if self.compress {
+            (MAGIC_COMPRESSED, compress(plaintext)?)
+        } else {
+            (MAGIC, plaintext.to_vec())
+        };
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(key.as_bytes()),
+                &mut data,
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt '{}'", key))?;
+
+        let mut sealed = Vec::with_capacity(magic.len() + NONCE_LEN + data.len());
+        sealed.extend_from_slice(magic);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&data);
+        Ok(sealed)
+    }
+
+    fn open(&self, key: &str, value: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        let (compressed, rest) = match value.strip_prefix(MAGIC) {
+            Some(rest) => (false, rest),
+            None => match value.strip_prefix(MAGIC_COMPRESSED) {
+                Some(rest) => (true, rest),
+                None => anyhow::bail!(
+                    "Stored value '{}' is not encrypted; run `ai-commit storage encrypt` to encrypt data saved before encryption was enabled",
+                    key
+                ),
+            },
+        };
+        if rest.len() < NONCE_LEN {
+            anyhow::bail!("Encrypted value for '{}' is truncated", key);
+        }
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| anyhow::anyhow!("Invalid nonce for '{}'", key))?;
+        let mut data = ciphertext.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(key.as_bytes()), &mut data)
+            .map_err(|_| {
+                anyhow::anyhow!(
+                    "Failed to decrypt '{}': wrong encryption key or corrupted data",
+                    key
+                )
+            })?;
+        if compressed {
+            decompress(plaintext.to_vec())
+        } else {
+            Ok(plaintext.to_vec())
+        }
+    }
+
+    /// 加密内层后端中所有未加密的值，返回加密的数量
+    pub async fn encrypt_plaintext(&self) -> anyhow::Result<usize> {
+        let mut encrypted = 0;
+        for key in self.inner.list("").await? {
+            let Some(value) = self.inner.get(&key).await? else {
+                continue;
+            };
+            if !value.starts_with(MAGIC) && !value.starts_with(MAGIC_COMPRESSED) {
+                self.put(&key, &value).await?;
+                encrypted += 1;
+            }
+        }
+        Ok(encrypted)
+    }
+}
+
+#[async_trait]
+impl StorageBackend for EncryptedBackend {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = self.inner.details();
+        details.push(("Encryption".to_string(), "AES-256-GCM".to_string()));
+        if self.compress {
+            // 压缩移到加密之前，替换内层后端的设置
+            details.retain(|(name, _)| name != "Compression");
+            details.push((
+                "Compression".to_string(),
+                "zlib (before encryption)".to_string(),
+            ));
+        }
+        details
+    }
+
+    async fn put(&self, key: &str, value: &[u8]) -> anyhow::Result<()> {
+        let sealed = self.seal(key, value)?;
+        self.inner.put(key, &sealed).await
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match self.inner.get(key).await? {
+            Some(value) => self.open(key, value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<bool> {
+        self.inner.delete(key).await
+    }
+
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+        self.inner.list(prefix).await
+    }
+
+    async fn ping(&self) -> anyhow::Result<()> {
+        self.inner.ping().await
+    }
+}
+
+/// 解析 64 位十六进制的密钥
+pub fn parse_key(text: &str) -> anyhow::Result<[u8; KEY_LEN]> {
+    let text = text.trim();
+    if text.len() != KEY_LEN * 2 || !text.is_ascii() {
+        anyhow::bail!(
+            "Storage encryption key must be {} hex characters (e.g. from `openssl rand -hex 32`)",
+            KEY_LEN * 2
+        );
+    }
+    let mut key = [0u8; KEY_LEN];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16)
+            .map_err(|_| anyhow::anyhow!("Storage encryption key is not valid hex"))?;
+    }
+    Ok(key)
+}
+
+/// 加密密钥：优先使用配置的密钥，否则读取或生成系统钥匙串中的密钥
+pub fn load_key(configured: Option<&str>) -> anyhow::Result<[u8; KEY_LEN]> {
+    if let Some(key) = configured.filter(|key| !key.trim().is_empty()) {
+        return parse_key(key);
+    }
+    if let Some(key) = keyring_lookup()? {
+        return parse_key(&key);
+    }
+
+    let mut key = [0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| anyhow::anyhow!("Failed to generate storage encryption key"))?;
+    let hex: String = key.iter().map(|byte| format!("{:02x}", byte)).collect();
+    keyring_store(&hex)?;
+    Ok(key)
+}
+
+/// 从系统钥匙串读取密钥，不存在时返回 `None`
+fn keyring_lookup() -> anyhow::Result<Option<String>> {
+    let output = if cfg!(target_os = "macos") {
+        keyring_command(
+            "security",
+            &[
+                "find-generic-password",
+                "-s",
+                KEYRING_SERVICE,
+                "-a",
+                KEYRING_ACCOUNT,
+                "-w",
+            ],
+        )?
+        .output()
+    } else {
+        keyring_command(
+            "secret-tool",
+            &[
+                "lookup",
+                "service",
+                KEYRING_SERVICE,
+                "account",
+                KEYRING_ACCOUNT,
+            ],
+        )?
+        .output()
+    };
+    let output = output.map_err(keyring_error)?;
+    let key = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // 两个工具在找不到条目时都以非零状态退出
+    Ok((output.status.success() && !key.is_empty()).then_some(key))
+}
+
+/// 把密钥保存到系统钥匙串
+fn keyring_store(key: &str) -> anyhow::Result<()> {
+    let status = if cfg!(target_os = "macos") {
+        keyring_command(
+            "security",
+            &[
+                "add-generic-password",
+                "-s",
+                KEYRING_SERVICE,
+                "-a",
+                KEYRING_ACCOUNT,
+                "-w",
+                key,
+            ],
+        )?
+        .status()
+        .map_err(keyring_error)?
+    } else {
+        // secret-tool 从标准输入读取密钥
+        let mut child = keyring_command(
+            "secret-tool",
+            &[
+                "store",
+                "--label=ai-commit storage encryption key",
+                "service",
+                KEYRING_SERVICE,
+                "account",
+                KEYRING_ACCOUNT,
+            ],
+        )?
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(keyring_error)?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(key.as_bytes())?;
+        }
+        child.wait()?
+    };
+    if !status.success() {
+        anyhow::bail!(
+            "Failed to save the storage encryption key to the system keyring; set [storage] encryption_key or AI_COMMIT_STORAGE_KEY instead"
+        );
+    }
+    Ok(())
+}
+
+fn keyring_command(program: &str, args: &[&str]) -> anyhow::Result<Command> {
+    if cfg!(windows) {
+        anyhow::bail!(
+            "System keyring is not supported on this platform; set [storage] encryption_key or AI_COMMIT_STORAGE_KEY"
+        );
+    }
+    let mut command = Command::new(program);
+    command.args(args).stderr(Stdio::null());
+    Ok(command)
+}
+
+fn keyring_error(e: std::io::Error) -> anyhow::Error {
+    if e.kind() == std::io::ErrorKind::NotFound {
+        anyhow::anyhow!(
+            "No system keyring tool found (secret-tool/security); set [storage] encryption_key or AI_COMMIT_STORAGE_KEY"
+        )
+    } else {
+        anyhow::anyhow!("Failed to access the system keyring: {}", e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::FileBackend;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn backend(dir: &tempfile::TempDir, key: &str) -> EncryptedBackend {
+        EncryptedBackend::new(
+            Box::new(FileBackend::new(dir.path().to_path_buf())),
+            &parse_key(key).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_roundtrip_and_plaintext_migration() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = backend(&dir, KEY);
+
+        storage
+            .put("reports/abcd.json", b"{\"score\":90}")
+            .await
+            .unwrap();
+        let on_disk = std::fs::read(dir.path().join("reports/abcd.json")).unwrap();
+        assert!(on_disk.starts_with(MAGIC));
+        assert!(!on_disk.windows(5).any(|w| w == b"score"));
+        assert_eq!(
+            storage.get("reports/abcd.json").await.unwrap().unwrap(),
+            b"{\"score\":90}"
+        );
+
+        // 未加密的值（启用加密前保存或被直接写入后端）不被信任
+        std::fs::write(dir.path().join("reports/beef.json"), b"plain").unwrap();
+        let err = storage.get("reports/beef.json").await.unwrap_err();
+        assert!(err.to_string().contains("ai-commit storage encrypt"));
+
+        // 显式迁移后可以读取，已加密的值不会重复加密
+        assert_eq!(storage.encrypt_plaintext().await.unwrap(), 1);
+        assert_eq!(storage.encrypt_plaintext().await.unwrap(), 0);
+        assert_eq!(
+            storage.get("reports/beef.json").await.unwrap().unwrap(),
+            b"plain"
+        );
+        assert_eq!(storage.list("reports/").await.unwrap().len(), 2);
+        assert_eq!(storage.name(), "file");
+    }
+
+    #[tokio::test]
+    async fn test_wrong_key_and_moved_ciphertext_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        backend(&dir, KEY).put("a", b"secret").await.unwrap();
+
+        let other = backend(&dir, &"ff".repeat(KEY_LEN));
+        let err = other.get("a").await.unwrap_err();
+        assert!(err.to_string().contains("wrong encryption key"));
+
+        // 键名参与认证，密文换到其他键下无法解密
+        std::fs::copy(dir.path().join("a"), dir.path().join("b")).unwrap();
+        assert!(backend(&dir, KEY).get("b").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_compress_before_encrypt() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = backend(&dir, KEY).with_compression(true);
+        let report = br#"{"severity":"warning","message":"unused variable"},"#.repeat(50);
+
+        storage.put("reports/abcd.json", &report).await.unwrap();
+        let on_disk = std::fs::read(dir.path().join("reports/abcd.json")).unwrap();
+        assert!(on_disk.starts_with(MAGIC_COMPRESSED));
+        assert!(on_disk.len() < report.len());
+        assert_eq!(
+            storage.get("reports/abcd.json").await.unwrap().unwrap(),
+            report
+        );
+
+        // 关闭压缩后仍能读取压缩保存的值，反之亦然
+        let plain = backend(&dir, KEY);
+        assert_eq!(
+            plain.get("reports/abcd.json").await.unwrap().unwrap(),
+            report
+        );
+        plain.put("reports/beef.json", b"{}").await.unwrap();
+        assert_eq!(
+            storage.get("reports/beef.json").await.unwrap().unwrap(),
+            b"{}"
+        );
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key(KEY).unwrap()[31], 0x1f);
+        assert!(parse_key("abcd").is_err());
+        assert!(parse_key(&"zz".repeat(KEY_LEN)).is_err());
+    }
+}
@@ -0,0 +1,215 @@
+//! 本地文件存储后端：每个键对应根目录下的一个文件
+//!
+//! 默认后端，无需任何配置。新安装保存在系统的数据目录（Linux 为 `$XDG_DATA_HOME/ai-commit`，
+//! 默认 `~/.local/share/ai-commit`；macOS 为 `~/Library/Application Support/ai-commit`）；
+//! 已有 `~/.ai-commit/storage` 时继续使用，设置了 `AI_COMMIT_DATA_DIR` 或 `[storage] data_dir`
+//! 时（即使与默认的 `~/.ai-commit` 相同）保存在其中的 `storage/` 下。
+
+use super::StorageBackend;
+use crate::config::Config;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+
+/// 数据目录下存储子目录的名字
+const DATA_SUBDIR: &str = "storage";
+
+/// 按配置确定本地存储的根目录
+pub fn default_root(config: &Config) -> anyhow::Result<PathBuf> {
+    if config.data_dir.as_os_str().is_empty() {
+        anyhow::bail!("Cannot find home directory");
+    }
+    Ok(resolve_root(
+        &config.data_dir,
+        config.custom_data_dir.is_some(),
+        dirs::data_dir(),
+    ))
+}
+
+/// 显式配置的数据目录或已有数据的旧目录优先，其余情况使用系统数据目录
+fn resolve_root(data_dir: &Path, custom: bool, system_data_dir: Option<PathBuf>) -> PathBuf {
+    let in_data_dir = data_dir.join(DATA_SUBDIR);
+    if custom || in_data_dir.exists() {
+        return in_data_dir;
+    }
+    system_data_dir
+        .map(|dir| dir.join("ai-commit"))
+        .unwrap_or(in_data_dir)
+}
+
+/// 把键映射为 `<root>/<key>` 的文件存储
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    root: PathBuf,
+}
+
+impl FileBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 键对应的文件路径，拒绝空段、`..` 和绝对路径，避免写到根目录之外
+    fn path_for(&self, key: &str) -> anyhow::Result<PathBuf> {
+        let valid = !key.is_empty()
+            && key.split('/').all(|part| {
+                !part.is_empty() && part != "." && part != ".." && !part.contains('\\')
+            });
+        if !valid {
+            anyhow::bail!("Invalid storage key '{}'", key);
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl StorageBackend for FileBackend {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        vec![("Directory".to_string(), self.root.display().to_string())]
+    }
+
+    async fn put(&self, key: &str, value: &[u8]) -> anyhow::Result<()> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // 先写临时文件再改名，中途失败不会留下半个文件
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, value)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", tmp.display(), e))?;
+        tokio::fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        let path = self.path_for(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(anyhow::anyhow!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<bool> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(anyhow::anyhow!(
+                "Failed to delete {}: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut pending = vec![self.root.clone()];
+        while let Some(dir) = pending.pop() {
+            let mut entries = match tokio::fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                if path.extension().is_some_and(|ext| ext == "tmp") {
+                    continue;
+                }
+                let Ok(relative) = path.strip_prefix(&self.root) else {
+                    continue;
+                };
+                let key = relative
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                if key.starts_with(prefix) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_backend_crud() {
+        let dir = tempfile::tempdir().unwrap();
+        let backend = FileBackend::new(dir.path().join("storage"));
+
+        assert_eq!(backend.get("reports/a.json").await.unwrap(), None);
+        assert!(backend.list("").await.unwrap().is_empty());
+
+        backend.put("reports/a.json", b"{}").await.unwrap();
+        backend.put("reports/b.json", b"[]").await.unwrap();
+        backend.put("other/c.json", b"1").await.unwrap();
+
+        assert_eq!(
+            backend.get("reports/a.json").await.unwrap(),
+            Some(b"{}".to_vec())
+        );
+        assert_eq!(
+            backend.list("reports/").await.unwrap(),
+            vec!["reports/a.json", "reports/b.json"]
+        );
+        assert!(backend.delete("reports/a.json").await.unwrap());
+        assert!(!backend.delete("reports/a.json").await.unwrap());
+        assert_eq!(backend.list("").await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_resolve_root() {
+        let home = tempfile::tempdir().unwrap();
+        let default = home.path().join(".ai-commit");
+        let system = home.path().join(".local/share");
+
+        // 新安装使用系统数据目录
+        assert_eq!(
+            resolve_root(&default, false, Some(system.clone())),
+            system.join("ai-commit")
+        );
+        // 显式配置的数据目录，与默认目录相同时也使用
+        let custom = home.path().join("data");
+        assert_eq!(
+            resolve_root(&custom, true, Some(system.clone())),
+            custom.join("storage")
+        );
+        assert_eq!(
+            resolve_root(&default, true, Some(system.clone())),
+            default.join("storage")
+        );
+        // 已有旧目录时继续使用
+        std::fs::create_dir_all(default.join("storage")).unwrap();
+        assert_eq!(
+            resolve_root(&default, false, Some(system)),
+            default.join("storage")
+        );
+        assert_eq!(resolve_root(&default, false, None), default.join("storage"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_keys_outside_root() {
+        let backend = FileBackend::new(PathBuf::from("/tmp/ai-commit-storage"));
+        for key in ["", "../secret", "reports//a", "/etc/passwd", "a/./b"] {
+            assert!(backend.get(key).await.is_err(), "{}", key);
+        }
+    }
+}
@@ -0,0 +1,811 @@
+//! 持久化存储
+//!
+//! `StorageBackend` 是简单的键值接口，键是用 `/` 分隔的路径（如 `reports/<sha>.json`）。
+//! `StorageManager` 在后端之上提供类型化的读写，例如按提交 SHA 保存和查找审查报告、
+//! 复杂度趋势样本、查询历史和提交信息历史。
+//! 默认后端 `FileBackend` 无需配置，把每个键保存为本地存储目录下的一个文件
+//! （新安装在系统数据目录下，Linux 为 `~/.local/share/ai-commit`，见 `file::default_root`）；
+//! `[storage] backend = "redis"` 时使用 `RedisBackend`，`backend = "s3"` 时使用 `S3Backend`，
+//! 在团队间共享结果。
+//! `[storage] encryption_enabled = true` 时由 `EncryptedBackend` 加密所有后端保存的值。
+//! `BackupManager` 定时备份审查报告并校验、轮换备份文件。
+//! 远程后端由 `ResilientBackend` 包装：失败时重试，`fallback = true` 时降级到本地存储。
+//! `RetentionPolicy` 限制保存的审查报告，由 `StorageManager::purge` 清理；
+//! `StorageManager::stats` 汇总后端设置和已保存数据。
+//! `[storage] namespace` 把审查报告和趋势样本放在 `projects/<namespace>/` 下，
+//! 多个仓库共用一个后端时查询和清理只作用于当前项目。
+
+pub mod archive;
+pub mod backup;
+pub mod encrypted;
+pub mod file;
+pub mod namespace;
+pub mod redis;
+pub mod resilient;
+pub mod retention;
+pub mod s3;
+pub mod stats;
+
+pub use backup::{BackupManager, BackupSummary};
+pub use encrypted::EncryptedBackend;
+pub use file::FileBackend;
+use namespace::PROJECTS_PREFIX;
+pub use redis::RedisBackend;
+pub use resilient::ResilientBackend;
+pub use retention::{PurgeSummary, RetentionPolicy};
+pub use s3::S3Backend;
+pub use stats::StorageStats;
+
+use crate::analysis::trend::TrendPoint;
+use crate::config::Config;
+use crate::core::ai::message_history::MessageHistory;
+use crate::query_history::QueryHistoryEntry;
+use crate::report::CodeReviewReport;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// 审查报告的键前缀
+const REPORTS_PREFIX: &str = "reports/";
+
+/// 复杂度趋势样本的键前缀
+const TRENDS_PREFIX: &str = "trends/complexity/";
+
+/// 查询历史的键
+const QUERY_HISTORY_KEY: &str = "history/queries.json";
+
+/// 提交信息历史的键前缀，每个项目一个文件
+const MESSAGE_HISTORY_PREFIX: &str = "history/commits/";
+
+/// 健康检查读取的键，不会被写入
+const HEALTH_CHECK_KEY: &str = "health-check";
+
+/// 按前缀查找提交时要求的最短 SHA 长度
+const MIN_SHA_PREFIX: usize = 4;
+
+/// 键值存储后端
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// 后端名称，用于提示信息
+    fn name(&self) -> &'static str;
+
+    /// 后端的位置和设置，用于 `storage stats` 显示
+    fn details(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    async fn put(&self, key: &str, value: &[u8]) -> anyhow::Result<()>;
+
+    /// 读取键对应的值，不存在时返回 `None`
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>>;
+
+    /// 删除键，返回键是否存在
+    async fn delete(&self, key: &str) -> anyhow::Result<bool>;
+
+    /// 列出以 `prefix` 开头的键，按字典序排列
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>>;
+
+    /// 检查后端是否可用，默认读取一个不存在的键
+    async fn ping(&self) -> anyhow::Result<()> {
+        self.get(HEALTH_CHECK_KEY).await.map(|_| ())
+    }
+}
+
+/// 与提交关联的审查报告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredReport {
+    /// 完整的提交 SHA
+    pub commit: String,
+    pub stored_at: String,
+    pub report: CodeReviewReport,
+}
+
+/// 类型化的存储接口
+pub struct StorageManager {
+    backend: Box<dyn StorageBackend>,
+    retention: RetentionPolicy,
+    /// 审查报告和趋势样本所属的项目，`None` 时不分项目
+    namespace: Option<String>,
+}
+
+impl StorageManager {
+    pub fn new(backend: Box<dyn StorageBackend>) -> Self {
+        Self {
+            backend,
+            retention: RetentionPolicy::default(),
+            namespace: None,
+        }
+    }
+
+    /// 设置 `purge` 使用的保留策略
+    pub fn with_retention(mut self, retention: RetentionPolicy) -> Self {
+        self.retention = retention;
+        self
+    }
+
+    pub fn retention(&self) -> &RetentionPolicy {
+        &self.retention
+    }
+
+    /// 把审查报告和趋势样本限定在命名空间内，多个仓库共用后端时互不干扰
+    pub fn with_namespace(mut self, namespace: Option<String>) -> Self {
+        self.namespace = namespace;
+        self
+    }
+
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// 按配置创建：默认保存在本地存储目录，`[storage] backend` 可选择 Redis 或 S3
+    /// `encryption_enabled` 时在后端之上加密保存的值，`namespace` 按项目隔离报告
+    pub fn from_config(config: &Config) -> anyhow::Result<Self> {
+        let mut backend = Self::backend_from_config(config)?;
+        if config.storage.encryption_enabled.unwrap_or(false) {
+            let key = encrypted::load_key(config.storage.encryption_key.as_deref())?;
+            backend = Box::new(
+                EncryptedBackend::new(backend, &key).with_compression(Self::s3_compress(config)),
+            );
+        }
+        Ok(Self::new(backend)
+            .with_retention(RetentionPolicy::from_config(config))
+            .with_namespace(namespace::resolve(config.storage.namespace.as_deref())?))
+    }
+
+    /// 加密启用 `encryption_enabled` 之前以明文保存的数据，返回加密的数量
+    pub async fn encrypt_existing(config: &Config) -> anyhow::Result<usize> {
+        if !config.storage.encryption_enabled.unwrap_or(false) {
+            anyhow::bail!(
+                "Storage encryption is not enabled; set [storage] encryption_enabled = true first"
+            );
+        }
+        let key = encrypted::load_key(config.storage.encryption_key.as_deref())?;
+        EncryptedBackend::new(Self::backend_from_config(config)?, &key)
+            .with_compression(Self::s3_compress(config))
+            .encrypt_plaintext()
+            .await
+    }
+
+    fn backend_from_config(config: &Config) -> anyhow::Result<Box<dyn StorageBackend>> {
+        match config.storage.backend.as_deref().unwrap_or("file") {
+            "file" => Ok(Box::new(FileBackend::new(file::default_root(config)?))),
+            "redis" => {
+                let url = config.storage.redis_url.as_deref().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Redis storage requires [storage] redis_url or AI_COMMIT_REDIS_URL"
+                    )
+                })?;
+                let backend = RedisBackend::from_url(url)?
+                    .with_namespace(
+                        config
+                            .storage
+                            .redis_namespace
+                            .as_deref()
+                            .unwrap_or(redis::DEFAULT_NAMESPACE),
+                    )
+                    .with_ttl(config.storage.redis_ttl.unwrap_or(0))
+                    .with_ca_cert(config.network.ca_cert.as_deref());
+                Self::resilient(config, Box::new(backend))
+            }
+            "s3" => {
+                let storage = &config.storage;
+                let bucket = storage
+                    .s3_bucket
+                    .as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("S3 storage requires [storage] s3_bucket"))?;
+                let region = storage
+                    .s3_region
+                    .clone()
+                    .or_else(|| std::env::var("AWS_REGION").ok())
+                    .or_else(|| std::env::var("AWS_DEFAULT_REGION").ok())
+                    .unwrap_or_else(|| "us-east-1".to_string());
+                let backend = S3Backend::new(bucket, &region, s3::Credentials::from_env()?)
+                    .with_endpoint(storage.s3_endpoint.as_deref())
+                    .with_prefix(storage.s3_prefix.as_deref().unwrap_or_default())
+                    // 加密时密文无法压缩，改由 EncryptedBackend 在加密前压缩
+                    .with_compression(
+                        Self::s3_compress(config) && !storage.encryption_enabled.unwrap_or(false),
+                    )
+                    .with_server_side_encryption(storage.s3_sse.as_deref());
+                Self::resilient(config, Box::new(backend))
+            }
+            other => anyhow::bail!(
+                "Unknown storage backend '{}' (expected: file, redis, s3)",
+                other
+            ),
+        }
+    }
+
+    /// 是否按 `s3_compress` 压缩保存的值
+    fn s3_compress(config: &Config) -> bool {
+        config.storage.backend.as_deref() == Some("s3")
+            && config.storage.s3_compress.unwrap_or(false)
+    }
+
+    /// 远程后端失败时重试，`[storage] fallback` 时降级到数据目录下的本地存储
+    fn resilient(
+        config: &Config,
+        remote: Box<dyn StorageBackend>,
+    ) -> anyhow::Result<Box<dyn StorageBackend>> {
+        let mut backend = ResilientBackend::new(remote)
+            .with_retries(config.storage.retries.unwrap_or(resilient::DEFAULT_RETRIES));
+        if config.storage.fallback.unwrap_or(false) {
+            backend =
+                backend.with_fallback(Box::new(FileBackend::new(file::default_root(config)?)));
+        }
+        Ok(Box::new(backend))
+    }
+
+    /// 检查存储后端是否可用，返回耗时
+    pub async fn ping(&self) -> anyhow::Result<std::time::Duration> {
+        let start = std::time::Instant::now();
+        self.backend.ping().await?;
+        Ok(start.elapsed())
+    }
+
+    pub fn backend(&self) -> &dyn StorageBackend {
+        self.backend.as_ref()
+    }
+
+    /// 保存提交的审查报告，同一提交重复保存时覆盖
+    pub async fn save_report(&self, commit: &str, report: &CodeReviewReport) -> anyhow::Result<()> {
+        validate_sha(commit)?;
+        let commit = commit.to_lowercase();
+        let stored = StoredReport {
+            commit: commit.clone(),
+            stored_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            report: report.clone(),
+        };
+        let json = serde_json::to_vec_pretty(&stored)?;
+        self.backend.put(&self.report_key(&commit), &json).await
+    }
+
+    /// 查找提交的审查报告，支持唯一的 SHA 前缀
+    pub async fn load_report(&self, commit: &str) -> anyhow::Result<Option<StoredReport>> {
+        validate_sha(commit)?;
+        let commit = commit.to_lowercase();
+        if let Some(bytes) = self.backend.get(&self.report_key(&commit)).await? {
+            return Ok(Some(serde_json::from_slice(&bytes)?));
+        }
+        if commit.len() < MIN_SHA_PREFIX {
+            return Ok(None);
+        }
+
+        let matches: Vec<String> = self
+            .list_reports()
+            .await?
+            .into_iter()
+            .filter(|sha| sha.starts_with(&commit))
+            .collect();
+        match matches.as_slice() {
+            [] => Ok(None),
+            [sha] => match self.backend.get(&self.report_key(sha)).await? {
+                Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+                None => Ok(None),
+            },
+            _ => anyhow::bail!(
+                "Commit prefix '{}' is ambiguous ({} stored reports match)",
+                commit,
+                matches.len()
+            ),
+        }
+    }
+
+    /// 保存导入的审查报告，保留原来的保存时间；已存在且 `overwrite` 为 false 时跳过，
+    /// 返回是否写入
+    pub async fn import_report(
+        &self,
+        stored: &StoredReport,
+        overwrite: bool,
+    ) -> anyhow::Result<bool> {
+        validate_sha(&stored.commit)?;
+        let key = self.report_key(&stored.commit.to_lowercase());
+        if !overwrite && self.backend.get(&key).await?.is_some() {
+            return Ok(false);
+        }
+        let json = serde_json::to_vec_pretty(stored)?;
+        self.backend.put(&key, &json).await?;
+        Ok(true)
+    }
+
+    /// 已保存报告的提交 SHA
+    pub async fn list_reports(&self) -> anyhow::Result<Vec<String>> {
+        let prefix = self.scoped(REPORTS_PREFIX);
+        Ok(self
+            .backend
+            .list(&prefix)
+            .await?
+            .iter()
+            .filter_map(|key| {
+                key.strip_prefix(&prefix)
+                    .and_then(|name| name.strip_suffix(".json"))
+                    .map(str::to_string)
+            })
+            .collect())
+    }
+
+    /// 读取保存时间不早于 `since`（`YYYY-MM-DD` 或完整时间）的所有审查报告，按保存时间排列；
+    /// 无法解析的报告跳过
+    pub async fn load_reports(&self, since: Option<&str>) -> anyhow::Result<Vec<StoredReport>> {
+        let mut reports = Vec::new();
+        for sha in self.list_reports().await? {
+            let Some(bytes) = self.backend.get(&self.report_key(&sha)).await? else {
+                continue;
+            };
+            let Ok(stored) = serde_json::from_slice::<StoredReport>(&bytes) else {
+                continue;
+            };
+            if since.is_some_and(|since| stored.stored_at.as_str() < since) {
+                continue;
+            }
+            reports.push(stored);
+        }
+        reports.sort_by(|a, b| a.stored_at.cmp(&b.stored_at));
+        Ok(reports)
+    }
+
+    /// 最近保存的 `limit` 份审查报告，最新的在前
+    pub async fn recent_reports(&self, limit: usize) -> anyhow::Result<Vec<StoredReport>> {
+        let mut reports = self.load_reports(None).await?;
+        reports.reverse();
+        reports.truncate(limit);
+        Ok(reports)
+    }
+
+    /// 按保留策略删除较早的审查报告，`dry_run` 时只返回将删除的报告。
+    /// 无法解析的报告不参与清理
+    pub async fn purge(&self, dry_run: bool) -> anyhow::Result<PurgeSummary> {
+        let mut reports = Vec::new();
+        for sha in self.list_reports().await? {
+            let Some(bytes) = self.backend.get(&self.report_key(&sha)).await? else {
+                continue;
+            };
+            let Ok(stored) = serde_json::from_slice::<StoredReport>(&bytes) else {
+                continue;
+            };
+            reports.push(retention::RetainedReport {
+                commit: sha,
+                stored_at: stored.stored_at,
+                size: bytes.len() as u64,
+            });
+        }
+
+        let total = reports.len();
+        let expired = self
+            .retention
+            .select_expired(reports, chrono::Local::now().naive_local());
+        let mut summary = PurgeSummary {
+            kept: total - expired.len(),
+            ..Default::default()
+        };
+        for report in expired {
+            if !dry_run {
+                self.backend
+                    .delete(&self.report_key(&report.commit))
+                    .await?;
+            }
+            summary.freed_bytes += report.size;
+            summary.removed.push(report.commit);
+        }
+        Ok(summary)
+    }
+
+    /// 存储后端的设置和已保存数据的统计
+    pub async fn stats(&self) -> anyhow::Result<StorageStats> {
+        let mut stats = StorageStats {
+            backend: self.backend.name().to_string(),
+            ..Default::default()
+        };
+        let mut score_total = 0u64;
+        for sha in self.list_reports().await? {
+            let bytes = match self.backend.get(&self.report_key(&sha)).await {
+                Ok(Some(bytes)) => bytes,
+                Ok(None) => continue,
+                Err(_) => {
+                    stats.unreadable_reports += 1;
+                    continue;
+                }
+            };
+            stats.total_bytes += bytes.len() as u64;
+            let Ok(stored) = serde_json::from_slice::<StoredReport>(&bytes) else {
+                stats.unreadable_reports += 1;
+                continue;
+            };
+            stats.total_reports += 1;
+            score_total += stored.report.score() as u64;
+            if stats.oldest.as_ref().is_none_or(|t| &stored.stored_at < t) {
+                stats.oldest = Some(stored.stored_at.clone());
+            }
+            if stats.newest.as_ref().is_none_or(|t| &stored.stored_at > t) {
+                stats.newest = Some(stored.stored_at);
+            }
+        }
+        if stats.total_reports > 0 {
+            stats.average_score = Some(score_total as f64 / stats.total_reports as f64);
+        }
+        stats.trend_points = self.backend.list(&self.scoped(TRENDS_PREFIX)).await?.len();
+        stats.query_history = self
+            .load_query_history()
+            .await
+            .ok()
+            .flatten()
+            .map_or(0, |entries| entries.len());
+        for key in self.backend.list(MESSAGE_HISTORY_PREFIX).await? {
+            let project = key
+                .trim_start_matches(MESSAGE_HISTORY_PREFIX)
+                .trim_end_matches(".json");
+            if let Ok(Some(history)) = self.load_message_history(project).await {
+                stats.commit_messages += history.records.len();
+            }
+        }
+        // 最后读取后端设置，反映统计过程中远程后端的可用状态
+        stats.details = self.backend.details();
+        if let Some(namespace) = &self.namespace {
+            stats
+                .details
+                .push(("Namespace".to_string(), namespace.clone()));
+        }
+        Ok(stats)
+    }
+
+    /// 读取查询历史，从未保存过时返回 `None`
+    pub async fn load_query_history(&self) -> anyhow::Result<Option<Vec<QueryHistoryEntry>>> {
+        match self.backend.get(QUERY_HISTORY_KEY).await? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 保存查询历史，整体覆盖
+    pub async fn save_query_history(&self, entries: &[QueryHistoryEntry]) -> anyhow::Result<()> {
+        let json = serde_json::to_vec_pretty(entries)?;
+        self.backend.put(QUERY_HISTORY_KEY, &json).await
+    }
+
+    /// 设置了命名空间时加上 `projects/<namespace>/` 前缀
+    fn scoped(&self, key: &str) -> String {
+        match &self.namespace {
+            Some(namespace) => format!("{}{}/{}", PROJECTS_PREFIX, namespace, key),
+            None => key.to_string(),
+        }
+    }
+
+    fn report_key(&self, commit: &str) -> String {
+        self.scoped(&format!("{}{}.json", REPORTS_PREFIX, commit))
+    }
+
+    fn trend_key(&self, scope: &str, commit: &str) -> String {
+        self.scoped(&format!(
+            "{}{}/{}.json",
+            TRENDS_PREFIX,
+            scope.to_lowercase(),
+            commit.to_lowercase()
+        ))
+    }
+
+    /// 存储中所有命名空间及其审查报告数，按名称排列
+    pub async fn list_namespaces(&self) -> anyhow::Result<Vec<(String, usize)>> {
+        let mut namespaces = std::collections::BTreeMap::new();
+        for key in self.backend.list(PROJECTS_PREFIX).await? {
+            let Some((namespace, rest)) = key
+                .strip_prefix(PROJECTS_PREFIX)
+                .and_then(|key| key.split_once('/'))
+            else {
+                continue;
+            };
+            let count = namespaces.entry(namespace.to_string()).or_insert(0);
+            if rest.starts_with(REPORTS_PREFIX) {
+                *count += 1;
+            }
+        }
+        Ok(namespaces.into_iter().collect())
+    }
+
+    /// 读取项目的提交信息历史，`project` 是项目路径的哈希
+    pub async fn load_message_history(
+        &self,
+        project: &str,
+    ) -> anyhow::Result<Option<MessageHistory>> {
+        validate_sha(project)?;
+        match self.backend.get(&message_history_key(project)).await? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 保存项目的提交信息历史，整体覆盖
+    pub async fn save_message_history(
+        &self,
+        project: &str,
+        history: &MessageHistory,
+    ) -> anyhow::Result<()> {
+        validate_sha(project)?;
+        let json = serde_json::to_vec_pretty(history)?;
+        self.backend.put(&message_history_key(project), &json).await
+    }
+
+    /// 保存提交的复杂度趋势样本，`scope` 区分不同的分析路径和阈值
+    pub async fn save_trend_point(&self, scope: &str, point: &TrendPoint) -> anyhow::Result<()> {
+        validate_sha(scope)?;
+        validate_sha(&point.commit)?;
+        let json = serde_json::to_vec_pretty(point)?;
+        self.backend
+            .put(&self.trend_key(scope, &point.commit), &json)
+            .await
+    }
+
+    /// 读取已保存的复杂度趋势样本
+    pub async fn load_trend_point(
+        &self,
+        scope: &str,
+        commit: &str,
+    ) -> anyhow::Result<Option<TrendPoint>> {
+        validate_sha(scope)?;
+        validate_sha(commit)?;
+        match self.backend.get(&self.trend_key(scope, commit)).await? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+fn message_history_key(project: &str) -> String {
+    format!("{}{}.json", MESSAGE_HISTORY_PREFIX, project.to_lowercase())
+}
+
+/// SHA 只能包含十六进制字符，避免拼出任意的存储路径
+fn validate_sha(commit: &str) -> anyhow::Result<()> {
+    if commit.is_empty() || commit.len() > 64 || !commit.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("Invalid commit SHA '{}'", commit);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(dir: &tempfile::TempDir) -> StorageManager {
+        StorageManager::new(Box::new(FileBackend::new(dir.path().to_path_buf())))
+    }
+
+    fn report(target: &str) -> CodeReviewReport {
+        CodeReviewReport {
+            target: target.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_report_roundtrip_by_sha_and_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = manager(&dir);
+
+        let sha = "3f2a9c0d1e4b5a6f7081920a3b4c5d6e7f809112";
+        storage
+            .save_report(sha, &report("commit 3f2a9c0d"))
+            .await
+            .unwrap();
+        storage
+            .save_report("3f2b000000000000000000000000000000000000", &report("other"))
+            .await
+            .unwrap();
+
+        let stored = storage.load_report(sha).await.unwrap().unwrap();
+        assert_eq!(stored.commit, sha);
+        assert_eq!(stored.report.target, "commit 3f2a9c0d");
+
+        let by_prefix = storage.load_report("3f2a9c").await.unwrap().unwrap();
+        assert_eq!(by_prefix.commit, sha);
+        assert!(storage.load_report("3f2").await.unwrap().is_none());
+        assert!(storage.load_report("deadbeef").await.unwrap().is_none());
+        assert_eq!(storage.list_reports().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_load_reports_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = manager(&dir);
+        let write = |sha: &str, stored_at: &str| {
+            let stored = StoredReport {
+                commit: sha.to_string(),
+                stored_at: stored_at.to_string(),
+                report: report(sha),
+            };
+            std::fs::create_dir_all(dir.path().join("reports")).unwrap();
+            std::fs::write(
+                dir.path().join(format!("reports/{}.json", sha)),
+                serde_json::to_vec(&stored).unwrap(),
+            )
+            .unwrap();
+        };
+        write("bbbb", "2024-05-02 09:00:00");
+        write("aaaa", "2024-05-03 09:00:00");
+        write("cccc", "2024-04-30 09:00:00");
+        std::fs::write(dir.path().join("reports/dddd.json"), b"not json").unwrap();
+
+        let all = storage.load_reports(None).await.unwrap();
+        let commits: Vec<&str> = all.iter().map(|r| r.commit.as_str()).collect();
+        assert_eq!(commits, vec!["cccc", "bbbb", "aaaa"]);
+
+        let recent = storage.load_reports(Some("2024-05-01")).await.unwrap();
+        assert_eq!(recent.len(), 2);
+
+        let latest = storage.recent_reports(2).await.unwrap();
+        let commits: Vec<&str> = latest.iter().map(|r| r.commit.as_str()).collect();
+        assert_eq!(commits, vec!["aaaa", "bbbb"]);
+    }
+
+    #[tokio::test]
+    async fn test_purge_by_retention_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("reports")).unwrap();
+        for (sha, stored_at) in [
+            ("aaaa", "2024-05-01 09:00:00"),
+            ("bbbb", "2024-05-02 09:00:00"),
+            ("cccc", "2024-05-03 09:00:00"),
+        ] {
+            let stored = StoredReport {
+                commit: sha.to_string(),
+                stored_at: stored_at.to_string(),
+                report: report(sha),
+            };
+            std::fs::write(
+                dir.path().join(format!("reports/{}.json", sha)),
+                serde_json::to_vec(&stored).unwrap(),
+            )
+            .unwrap();
+        }
+
+        // 未配置保留策略时不删除
+        let summary = manager(&dir).purge(false).await.unwrap();
+        assert!(summary.removed.is_empty());
+        assert_eq!(summary.kept, 3);
+
+        let storage = manager(&dir).with_retention(RetentionPolicy {
+            max_count: Some(1),
+            ..Default::default()
+        });
+        let summary = storage.purge(true).await.unwrap();
+        assert_eq!(summary.removed, vec!["aaaa", "bbbb"]);
+        assert!(summary.freed_bytes > 0);
+        assert_eq!(storage.list_reports().await.unwrap().len(), 3);
+
+        storage.purge(false).await.unwrap();
+        assert_eq!(storage.list_reports().await.unwrap(), vec!["cccc"]);
+    }
+
+    #[tokio::test]
+    async fn test_namespaces_isolate_reports_and_purge() {
+        let dir = tempfile::tempdir().unwrap();
+        let web = manager(&dir).with_namespace(Some("team-web".to_string()));
+        let api = manager(&dir).with_namespace(Some("team-api".to_string()));
+        let shared = manager(&dir);
+
+        web.save_report("aaaa1111", &report("web")).await.unwrap();
+        web.save_report("bbbb2222", &report("web")).await.unwrap();
+        api.save_report("aaaa1111", &report("api")).await.unwrap();
+        shared
+            .save_report("cccc3333", &report("shared"))
+            .await
+            .unwrap();
+        assert!(dir
+            .path()
+            .join("projects/team-web/reports/aaaa1111.json")
+            .exists());
+
+        // 同一提交在不同项目中各自保存，查询互不可见
+        let stored = api.load_report("aaaa").await.unwrap().unwrap();
+        assert_eq!(stored.report.target, "api");
+        assert!(api.load_report("bbbb2222").await.unwrap().is_none());
+        assert_eq!(shared.list_reports().await.unwrap(), vec!["cccc3333"]);
+        assert_eq!(web.stats().await.unwrap().total_reports, 2);
+
+        let web = web.with_retention(RetentionPolicy {
+            max_count: Some(0),
+            ..Default::default()
+        });
+        assert_eq!(web.purge(false).await.unwrap().removed.len(), 2);
+        assert_eq!(api.list_reports().await.unwrap(), vec!["aaaa1111"]);
+        assert_eq!(shared.list_reports().await.unwrap().len(), 1);
+
+        assert_eq!(
+            shared.list_namespaces().await.unwrap(),
+            vec![("team-api".to_string(), 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_report_keeps_stored_at() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = manager(&dir);
+        let imported = StoredReport {
+            commit: "abcd1111".to_string(),
+            stored_at: "2024-05-01 09:00:00".to_string(),
+            report: report("imported"),
+        };
+
+        assert!(storage.import_report(&imported, false).await.unwrap());
+        let stored = storage.load_report("abcd1111").await.unwrap().unwrap();
+        assert_eq!(stored, imported);
+
+        storage
+            .save_report("abcd1111", &report("local"))
+            .await
+            .unwrap();
+        assert!(!storage.import_report(&imported, false).await.unwrap());
+        assert!(storage.import_report(&imported, true).await.unwrap());
+        assert_eq!(
+            storage
+                .load_report("abcd1111")
+                .await
+                .unwrap()
+                .unwrap()
+                .report
+                .target,
+            "imported"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_storage_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = manager(&dir);
+        let empty = storage.stats().await.unwrap();
+        assert_eq!(empty.backend, "file");
+        assert_eq!(empty.total_reports, 0);
+        assert_eq!(empty.average_score, None);
+
+        storage.save_report("abcd1111", &report("a")).await.unwrap();
+        storage.save_report("abcd2222", &report("b")).await.unwrap();
+        std::fs::write(dir.path().join("reports/beef.json"), b"not json").unwrap();
+
+        let stats = storage.stats().await.unwrap();
+        assert_eq!(stats.total_reports, 2);
+        assert_eq!(stats.unreadable_reports, 1);
+        assert!(stats.total_bytes > 0);
+        assert_eq!(stats.average_score, Some(100.0));
+        assert!(stats.oldest.is_some() && stats.oldest <= stats.newest);
+        assert_eq!(stats.details[0].0, "Directory");
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_prefix_and_invalid_sha() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = manager(&dir);
+        storage.save_report("abcd1111", &report("a")).await.unwrap();
+        storage.save_report("abcd2222", &report("b")).await.unwrap();
+
+        let err = storage.load_report("abcd").await.unwrap_err();
+        assert!(err.to_string().contains("ambiguous"));
+        assert!(storage.load_report("../config").await.is_err());
+        assert!(storage.save_report("HEAD", &report("c")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_trend_point_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = manager(&dir);
+        let point = TrendPoint {
+            commit: "abcd1111".to_string(),
+            date: "2024-05-01".to_string(),
+            files: 3,
+            functions: 12,
+            average_cyclomatic: 2.5,
+            average_cognitive: 3.0,
+            max_cyclomatic: 9,
+            violations: 0,
+        };
+
+        storage.save_trend_point("0f0f", &point).await.unwrap();
+        assert_eq!(
+            storage.load_trend_point("0f0f", "abcd1111").await.unwrap(),
+            Some(point)
+        );
+        assert_eq!(
+            storage.load_trend_point("1e1e", "abcd1111").await.unwrap(),
+            None
+        );
+        // 趋势样本不会被当成审查报告列出
+        assert!(storage.list_reports().await.unwrap().is_empty());
+    }
+}
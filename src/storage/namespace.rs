@@ -0,0 +1,140 @@
+//! 存储命名空间：多个仓库共用一个存储后端时按项目隔离审查报告和趋势样本
+//!
+//! 设置 `[storage] namespace`（或 `AI_COMMIT_STORAGE_NAMESPACE`）后，报告保存在
+//! `projects/<namespace>/reports/<sha>.json`，查询、导出和清理都只作用于该命名空间。
+//! `namespace = "auto"` 时从 `origin` 远程地址推导（如 `costa92-ai-commit`），
+//! 没有远程时使用仓库目录名。未设置时沿用不分项目的键。
+
+use std::path::Path;
+use std::process::Command;
+
+/// 命名空间数据的键前缀
+pub const PROJECTS_PREFIX: &str = "projects/";
+
+/// 从仓库推导命名空间的配置值
+pub const AUTO: &str = "auto";
+
+/// 解析配置的命名空间，`auto` 时从当前仓库推导
+pub fn resolve(configured: Option<&str>) -> anyhow::Result<Option<String>> {
+    let Some(configured) = configured.map(str::trim).filter(|ns| !ns.is_empty()) else {
+        return Ok(None);
+    };
+    if configured != AUTO {
+        validate(configured)?;
+        return Ok(Some(configured.to_string()));
+    }
+    detect(Path::new(".")).map(Some).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Cannot derive storage namespace: not inside a git repository; set [storage] namespace explicitly"
+        )
+    })
+}
+
+/// 命名空间只能包含小写字母、数字、`.`、`_` 和 `-`
+pub fn validate(namespace: &str) -> anyhow::Result<()> {
+    let valid = !namespace.is_empty()
+        && namespace.len() <= 128
+        && !namespace.starts_with('.')
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-'));
+    if !valid {
+        anyhow::bail!(
+            "Invalid storage namespace '{}': use lowercase letters, digits, '.', '_' and '-'",
+            namespace
+        );
+    }
+    Ok(())
+}
+
+/// 从 `origin` 远程地址或仓库目录名推导命名空间
+fn detect(dir: &Path) -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|text| !text.is_empty())
+    };
+    if let Some(namespace) = git(&["remote", "get-url", "origin"])
+        .as_deref()
+        .and_then(from_remote_url)
+    {
+        return Some(namespace);
+    }
+    let root = git(&["rev-parse", "--show-toplevel"])?;
+    let name = Path::new(&root).file_name()?.to_string_lossy().to_string();
+    Some(sanitize(&name)).filter(|ns| !ns.is_empty())
+}
+
+/// 从远程地址取 `owner/repo` 并转换为命名空间，如
+/// `git@github.com:costa92/ai-commit.git` → `costa92-ai-commit`
+pub fn from_remote_url(url: &str) -> Option<String> {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    // 去掉协议和主机：`https://host/owner/repo`、`git@host:owner/repo`
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.rsplit_once(':').map_or(url, |(_, path)| path),
+    };
+    let mut segments = path.rsplit('/').filter(|s| !s.is_empty());
+    let repo = segments.next()?;
+    let namespace = match segments.next() {
+        Some(owner) => sanitize(&format!("{}-{}", owner, repo)),
+        None => sanitize(repo),
+    };
+    Some(namespace).filter(|ns| !ns.is_empty())
+}
+
+fn sanitize(name: &str) -> String {
+    let mapped: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    mapped.trim_matches(|c| c == '-' || c == '.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_from_remote_url() {
+        for url in [
+            "git@github.com:costa92/ai-commit.git",
+            "https://github.com/costa92/ai-commit",
+            "ssh://git@gitlab.example.com:2222/Costa92/AI-Commit.git/",
+        ] {
+            assert_eq!(from_remote_url(url).as_deref(), Some("costa92-ai-commit"));
+        }
+        assert_eq!(
+            from_remote_url("/srv/git/tools.git").as_deref(),
+            Some("git-tools")
+        );
+        assert_eq!(from_remote_url("https://example.com"), None);
+    }
+
+    #[test]
+    fn test_resolve_and_validate() {
+        assert_eq!(resolve(None).unwrap(), None);
+        assert_eq!(resolve(Some("  ")).unwrap(), None);
+        assert_eq!(
+            resolve(Some("team-a.web")).unwrap().as_deref(),
+            Some("team-a.web")
+        );
+        assert!(resolve(Some("Team A")).is_err());
+        assert!(validate("../reports").is_err());
+        assert!(validate("a/b").is_err());
+    }
+}
@@ -0,0 +1,350 @@
+//! 远程存储的重试和降级
+//!
+//! Redis、S3 后端的操作失败时按指数退避重试（`[storage] retries`，默认 2 次），
+//! 重试后仍失败时标记为不可用。配置 `fallback = true` 时改用数据目录下的本地存储，
+//! 之后每隔 `PROBE_INTERVAL` 重新尝试远程后端，恢复后自动切回；
+//! 降级期间写入本地的数据在读取和列出时与远程的合并，不会自动同步回远程。
+
+use super::StorageBackend;
+use async_trait::async_trait;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 默认重试次数
+pub const DEFAULT_RETRIES: u32 = 2;
+
+/// 第一次重试前的等待时间，之后每次翻倍
+const BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// 降级后重新尝试远程后端的间隔
+const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 远程后端的状态
+#[derive(Debug, Default)]
+struct Health {
+    /// 不可用的开始时间和最近的错误
+    down_since: Option<String>,
+    last_error: Option<String>,
+    /// 降级期间下一次尝试远程后端的时间
+    next_probe: Option<Instant>,
+}
+
+/// 带重试和本地降级的远程存储后端
+pub struct ResilientBackend {
+    remote: Box<dyn StorageBackend>,
+    fallback: Option<Box<dyn StorageBackend>>,
+    retries: u32,
+    base_delay: Duration,
+    probe_interval: Duration,
+    health: Mutex<Health>,
+}
+
+impl ResilientBackend {
+    pub fn new(remote: Box<dyn StorageBackend>) -> Self {
+        Self {
+            remote,
+            fallback: None,
+            retries: DEFAULT_RETRIES,
+            base_delay: BASE_DELAY,
+            probe_interval: PROBE_INTERVAL,
+            health: Mutex::new(Health::default()),
+        }
+    }
+
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// 远程后端不可用时使用的本地存储
+    pub fn with_fallback(mut self, fallback: Box<dyn StorageBackend>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// 远程后端当前是否被标记为不可用
+    pub fn is_degraded(&self) -> bool {
+        self.health.lock().unwrap().down_since.is_some()
+    }
+
+    /// 是否应该尝试远程后端：正常时总是尝试，降级时到了探测时间才尝试
+    fn should_try_remote(&self) -> bool {
+        if self.fallback.is_none() {
+            return true;
+        }
+        let health = self.health.lock().unwrap();
+        health
+            .next_probe
+            .is_none_or(|next_probe| Instant::now() >= next_probe)
+    }
+
+    fn mark_up(&self) {
+        *self.health.lock().unwrap() = Health::default();
+    }
+
+    fn mark_down(&self, error: &anyhow::Error) {
+        let mut health = self.health.lock().unwrap();
+        if health.down_since.is_none() {
+            health.down_since = Some(chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
+        }
+        health.last_error = Some(error.to_string());
+        health.next_probe = Some(Instant::now() + self.probe_interval);
+    }
+
+    /// 在远程后端上执行操作，失败时按指数退避重试
+    async fn with_retry<T, F, Fut>(&self, op: F) -> anyhow::Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let mut delay = self.base_delay;
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Ok(value) => {
+                    self.mark_up();
+                    return Ok(value);
+                }
+                Err(_) if attempt < self.retries => {
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    self.mark_down(&e);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    /// 执行远程操作，不可用且配置了本地存储时返回 `None` 由调用方降级
+    async fn remote<T, F, Fut>(&self, op: F) -> anyhow::Result<Option<T>>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        if !self.should_try_remote() {
+            return Ok(None);
+        }
+        match self.with_retry(op).await {
+            Ok(value) => Ok(Some(value)),
+            Err(_) if self.fallback.is_some() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[async_trait]
+impl StorageBackend for ResilientBackend {
+    fn name(&self) -> &'static str {
+        self.remote.name()
+    }
+
+    fn details(&self) -> Vec<(String, String)> {
+        let mut details = self.remote.details();
+        details.push(("Retries".to_string(), self.retries.to_string()));
+        if let Some(fallback) = &self.fallback {
+            let location = fallback
+                .details()
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect::<Vec<_>>()
+                .join(", ");
+            details.push(("Fallback".to_string(), location));
+        }
+        let health = self.health.lock().unwrap();
+        let status = match (&health.down_since, &health.last_error) {
+            (Some(since), Some(error)) => format!("unavailable since {}: {}", since, error),
+            _ => "ok".to_string(),
+        };
+        details.push(("Status".to_string(), status));
+        details
+    }
+
+    async fn put(&self, key: &str, value: &[u8]) -> anyhow::Result<()> {
+        if self.remote(|| self.remote.put(key, value)).await?.is_some() {
+            return Ok(());
+        }
+        self.fallback
+            .as_ref()
+            .expect("remote operations only defer to a configured fallback")
+            .put(key, value)
+            .await
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        if let Some(Some(value)) = self.remote(|| self.remote.get(key)).await? {
+            return Ok(Some(value));
+        }
+        // 远程没有时再查降级期间写入本地的值
+        match &self.fallback {
+            Some(fallback) => fallback.get(key).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<bool> {
+        let removed = self
+            .remote(|| self.remote.delete(key))
+            .await?
+            .unwrap_or(false);
+        match &self.fallback {
+            Some(fallback) => Ok(fallback.delete(key).await? || removed),
+            None => Ok(removed),
+        }
+    }
+
+    async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+        let mut keys = self
+            .remote(|| self.remote.list(prefix))
+            .await?
+            .unwrap_or_default();
+        if let Some(fallback) = &self.fallback {
+            keys.extend(fallback.list(prefix).await?);
+            keys.sort();
+            keys.dedup();
+        }
+        Ok(keys)
+    }
+
+    /// 总是检查远程后端，不使用本地存储
+    async fn ping(&self) -> anyhow::Result<()> {
+        self.with_retry(|| self.remote.ping()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::FileBackend;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// 可以切换为不可用的后端，记录调用次数
+    struct FlakyBackend {
+        inner: FileBackend,
+        down: AtomicBool,
+        calls: AtomicUsize,
+        /// 开头失败的调用次数
+        fail_first: AtomicUsize,
+    }
+
+    impl FlakyBackend {
+        fn check(&self) -> anyhow::Result<()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let failing = self
+                .fail_first
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failing || self.down.load(Ordering::SeqCst) {
+                anyhow::bail!("connection refused");
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl StorageBackend for std::sync::Arc<FlakyBackend> {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn put(&self, key: &str, value: &[u8]) -> anyhow::Result<()> {
+            self.check()?;
+            self.inner.put(key, value).await
+        }
+
+        async fn get(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+            self.check()?;
+            self.inner.get(key).await
+        }
+
+        async fn delete(&self, key: &str) -> anyhow::Result<bool> {
+            self.check()?;
+            self.inner.delete(key).await
+        }
+
+        async fn list(&self, prefix: &str) -> anyhow::Result<Vec<String>> {
+            self.check()?;
+            self.inner.list(prefix).await
+        }
+    }
+
+    fn flaky(dir: &tempfile::TempDir, fail_first: usize) -> std::sync::Arc<FlakyBackend> {
+        std::sync::Arc::new(FlakyBackend {
+            inner: FileBackend::new(dir.path().join("remote")),
+            down: AtomicBool::new(false),
+            calls: AtomicUsize::new(0),
+            fail_first: AtomicUsize::new(fail_first),
+        })
+    }
+
+    fn resilient(remote: &std::sync::Arc<FlakyBackend>) -> ResilientBackend {
+        let mut backend = ResilientBackend::new(Box::new(remote.clone()));
+        backend.base_delay = Duration::from_millis(1);
+        backend
+    }
+
+    #[tokio::test]
+    async fn test_retries_with_backoff() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = flaky(&dir, 2);
+        let backend = resilient(&remote);
+
+        backend.put("a", b"1").await.unwrap();
+        assert_eq!(remote.calls.load(Ordering::SeqCst), 3);
+        assert!(!backend.is_degraded());
+
+        remote.down.store(true, Ordering::SeqCst);
+        let err = backend.get("a").await.unwrap_err();
+        assert!(err.to_string().contains("connection refused"));
+        assert!(backend.is_degraded());
+        assert!(backend.ping().await.is_err());
+        assert!(backend
+            .details()
+            .iter()
+            .any(|(name, value)| name == "Status" && value.starts_with("unavailable since")));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_and_reconnect() {
+        let dir = tempfile::tempdir().unwrap();
+        let remote = flaky(&dir, 0);
+        let mut backend = resilient(&remote)
+            .with_retries(0)
+            .with_fallback(Box::new(FileBackend::new(dir.path().join("local"))));
+        backend.put("reports/a.json", b"remote").await.unwrap();
+
+        // 远程不可用时写入本地，探测间隔内不再访问远程
+        remote.down.store(true, Ordering::SeqCst);
+        backend.put("reports/b.json", b"local").await.unwrap();
+        assert!(backend.is_degraded());
+        let calls = remote.calls.load(Ordering::SeqCst);
+        assert_eq!(
+            backend.get("reports/b.json").await.unwrap().unwrap(),
+            b"local"
+        );
+        assert_eq!(remote.calls.load(Ordering::SeqCst), calls);
+        assert!(dir.path().join("local/reports/b.json").exists());
+
+        // 远程恢复后到了探测时间自动切回，列出时合并本地的键
+        remote.down.store(false, Ordering::SeqCst);
+        backend.probe_interval = Duration::ZERO;
+        backend.mark_down(&anyhow::anyhow!("still marked down"));
+        assert_eq!(
+            backend.list("reports/").await.unwrap(),
+            vec!["reports/a.json", "reports/b.json"]
+        );
+        assert!(!backend.is_degraded());
+        assert_eq!(
+            backend.get("reports/b.json").await.unwrap().unwrap(),
+            b"local"
+        );
+        assert!(backend.delete("reports/b.json").await.unwrap());
+        assert_eq!(
+            backend.list("reports/").await.unwrap(),
+            vec!["reports/a.json"]
+        );
+    }
+}
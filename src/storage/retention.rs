@@ -0,0 +1,195 @@
+//! 审查报告的保留策略
+//!
+//! `[storage]` 的 `max_age_days`、`max_reports`、`max_size_mb` 限制保存的审查报告：
+//! 从最新的报告开始保留，超过天数、数量或总大小上限的较早报告在清理时删除。
+//! 清理由 `ai-commit storage purge` 手动执行，守护进程模式下定期执行。
+
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+
+/// 保留策略，各项为 `None` 时不限制
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    pub max_age_days: Option<u64>,
+    pub max_count: Option<usize>,
+    pub max_size_bytes: Option<u64>,
+}
+
+/// 参与清理的报告
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetainedReport {
+    pub commit: String,
+    pub stored_at: String,
+    /// 保存的字节数
+    pub size: u64,
+}
+
+/// 清理结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PurgeSummary {
+    /// 删除（或试运行时将删除）的报告的提交 SHA，最早的在前
+    pub removed: Vec<String>,
+    pub kept: usize,
+    pub freed_bytes: u64,
+}
+
+impl RetentionPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_age_days: config.storage.max_age_days,
+            max_count: config.storage.max_reports,
+            max_size_bytes: config
+                .storage
+                .max_size_mb
+                .map(|mb| mb.saturating_mul(1024 * 1024)),
+        }
+    }
+
+    /// 没有任何限制
+    pub fn is_unlimited(&self) -> bool {
+        self.max_age_days.is_none() && self.max_count.is_none() && self.max_size_bytes.is_none()
+    }
+
+    /// 按策略挑出要删除的报告。`now` 与 `stored_at` 格式相同（`YYYY-MM-DD HH:MM:SS`）
+    pub fn select_expired(
+        &self,
+        mut reports: Vec<RetainedReport>,
+        now: chrono::NaiveDateTime,
+    ) -> Vec<RetainedReport> {
+        let cutoff = self.max_age_days.map(|days| {
+            (now - chrono::Duration::days(days.min(365 * 1000) as i64))
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        });
+
+        // 最新的在前，依次累计数量和大小
+        reports.sort_by_key(|r| std::cmp::Reverse(r.stored_at.clone()));
+        let mut kept = 0usize;
+        let mut total = 0u64;
+        // 超过大小上限后，更早的报告即使较小也不再保留
+        let mut full = false;
+        let mut expired = Vec::new();
+        for report in reports {
+            let too_old = cutoff
+                .as_deref()
+                .is_some_and(|cutoff| report.stored_at.as_str() < cutoff);
+            let too_many = self.max_count.is_some_and(|max| kept >= max);
+            full = full
+                || self
+                    .max_size_bytes
+                    .is_some_and(|max| total + report.size > max);
+            if too_old || too_many || full {
+                expired.push(report);
+            } else {
+                kept += 1;
+                total += report.size;
+            }
+        }
+        expired.reverse();
+        expired
+    }
+}
+
+impl PurgeSummary {
+    pub fn format(&self, dry_run: bool) -> String {
+        if self.removed.is_empty() {
+            return format!("Nothing to purge ({} report(s) kept)", self.kept);
+        }
+        let verb = if dry_run {
+            "Would remove"
+        } else {
+            "✓ Removed"
+        };
+        let mut out = format!(
+            "{} {} report(s), freeing {:.1} KB ({} kept)",
+            verb,
+            self.removed.len(),
+            self.freed_bytes as f64 / 1024.0,
+            self.kept
+        );
+        for commit in &self.removed {
+            out.push_str(&format!("\n  {}", &commit[..commit.len().min(12)]));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(commit: &str, stored_at: &str, size: u64) -> RetainedReport {
+        RetainedReport {
+            commit: commit.to_string(),
+            stored_at: stored_at.to_string(),
+            size,
+        }
+    }
+
+    fn commits(reports: &[RetainedReport]) -> Vec<&str> {
+        reports.iter().map(|r| r.commit.as_str()).collect()
+    }
+
+    #[test]
+    fn test_select_expired() {
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 5, 10)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let reports = vec![
+            report("aaaa", "2024-05-09 08:00:00", 400),
+            report("bbbb", "2024-04-01 08:00:00", 100),
+            report("cccc", "2024-05-01 08:00:00", 300),
+            report("dddd", "2024-05-10 08:00:00", 200),
+        ];
+
+        assert!(RetentionPolicy::default()
+            .select_expired(reports.clone(), now)
+            .is_empty());
+
+        let by_age = RetentionPolicy {
+            max_age_days: Some(30),
+            ..Default::default()
+        };
+        assert_eq!(
+            commits(&by_age.select_expired(reports.clone(), now)),
+            vec!["bbbb"]
+        );
+
+        let by_count = RetentionPolicy {
+            max_count: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            commits(&by_count.select_expired(reports.clone(), now)),
+            vec!["bbbb", "cccc"]
+        );
+
+        // 最新的两份共 600 字节，第三份会超过上限；更早但更小的报告也一并删除
+        let by_size = RetentionPolicy {
+            max_size_bytes: Some(700),
+            ..Default::default()
+        };
+        assert_eq!(
+            commits(&by_size.select_expired(reports, now)),
+            vec!["bbbb", "cccc"]
+        );
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let summary = PurgeSummary {
+            removed: vec!["3f2a9c0d1e4b5a6f7081".to_string()],
+            kept: 4,
+            freed_bytes: 2048,
+        };
+        assert_eq!(
+            summary.format(true),
+            "Would remove 1 report(s), freeing 2.0 KB (4 kept)\n  3f2a9c0d1e4b"
+        );
+        assert_eq!(
+            PurgeSummary::default().format(false),
+            "Nothing to purge (0 report(s) kept)"
+        );
+    }
+}
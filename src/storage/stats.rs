@@ -0,0 +1,122 @@
+//! 存储概况：`ai-commit storage stats` 和 TUI 存储面板显示的内容
+
+use serde::{Deserialize, Serialize};
+
+/// 存储后端和已保存数据的统计
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StorageStats {
+    pub backend: String,
+    /// 后端的位置和设置
+    pub details: Vec<(String, String)>,
+    pub total_reports: usize,
+    /// 无法解析（如密钥不匹配）的报告数
+    pub unreadable_reports: usize,
+    /// 审查报告的字节数
+    pub total_bytes: u64,
+    /// 最早和最近的保存时间
+    pub oldest: Option<String>,
+    pub newest: Option<String>,
+    pub average_score: Option<f64>,
+    pub trend_points: usize,
+    pub query_history: usize,
+    /// 所有项目记录的提交信息数
+    pub commit_messages: usize,
+}
+
+impl StorageStats {
+    /// 以 `(名称, 值)` 列出，CLI 和 TUI 共用
+    pub fn rows(&self) -> Vec<(String, String)> {
+        let mut rows = vec![("Backend".to_string(), self.backend.clone())];
+        rows.extend(self.details.iter().cloned());
+
+        let mut reports = self.total_reports.to_string();
+        if self.unreadable_reports > 0 {
+            reports.push_str(&format!(" ({} unreadable)", self.unreadable_reports));
+        }
+        rows.push(("Reports".to_string(), reports));
+        rows.push(("Size".to_string(), format_bytes(self.total_bytes)));
+        let range = match (&self.oldest, &self.newest) {
+            (Some(oldest), Some(newest)) => format!("{} → {}", oldest, newest),
+            _ => "-".to_string(),
+        };
+        rows.push(("Date range".to_string(), range));
+        rows.push((
+            "Average score".to_string(),
+            self.average_score
+                .map_or("-".to_string(), |score| format!("{:.1}", score)),
+        ));
+        rows.push((
+            "Complexity trend points".to_string(),
+            self.trend_points.to_string(),
+        ));
+        rows.push((
+            "Query history entries".to_string(),
+            self.query_history.to_string(),
+        ));
+        rows.push((
+            "Commit message history".to_string(),
+            self.commit_messages.to_string(),
+        ));
+        rows
+    }
+
+    pub fn format(&self) -> String {
+        let rows = self.rows();
+        let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        let mut out = String::from("📦 Storage");
+        for (name, value) in rows {
+            out.push_str(&format!("\n  {:<width$}  {}", name, value, width = width));
+        }
+        out
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_stats() {
+        let stats = StorageStats {
+            backend: "file".to_string(),
+            details: vec![("Directory".to_string(), "/data/storage".to_string())],
+            total_reports: 3,
+            unreadable_reports: 1,
+            total_bytes: 3 * 1024 + 512,
+            oldest: Some("2024-05-01 09:00:00".to_string()),
+            newest: Some("2024-05-03 09:00:00".to_string()),
+            average_score: Some(87.5),
+            trend_points: 2,
+            query_history: 0,
+            commit_messages: 4,
+        };
+        let text = stats.format();
+        assert!(text.contains("Directory"));
+        assert!(text.contains("3 (1 unreadable)"));
+        assert!(text.contains("3.5 KB"));
+        assert!(text.contains("2024-05-01 09:00:00 → 2024-05-03 09:00:00"));
+        assert!(text.contains("87.5"));
+        assert!(text
+            .lines()
+            .any(|line| line.contains("Commit message history") && line.ends_with(" 4")));
+
+        let empty = StorageStats::default().format();
+        assert!(empty.contains("Date range"));
+        assert_eq!(format_bytes(512), "512 B");
+    }
+}
@@ -124,14 +124,14 @@ impl super::app::TuiUnifiedApp {
     }
 
     /// 确保 AgentManager 已初始化
-    fn ensure_agent_manager(&mut self) {
+    pub(crate) fn ensure_agent_manager(&mut self) {
         if self.agent_manager.is_none() {
             self.agent_manager = Some(AgentManager::with_default_context());
         }
     }
 
     /// 构建 Agent 上下文
-    fn build_agent_context() -> anyhow::Result<AgentContext> {
+    pub(crate) fn build_agent_context() -> anyhow::Result<AgentContext> {
         let config = Config::new();
         let mut env_vars: HashMap<String, String> = std::env::vars().collect();
 
@@ -139,6 +139,7 @@ impl super::app::TuiUnifiedApp {
             env_vars.insert("API_KEY".to_string(), api_key);
         }
         env_vars.insert("API_URL".to_string(), config.get_url());
+        config.insert_conventions(&mut env_vars);
 
         let agent_config = AgentConfig {
             provider: config.provider.clone(),
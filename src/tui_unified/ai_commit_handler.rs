@@ -1,35 +1,48 @@
 use std::collections::HashMap;
+use std::time::Instant;
+
+use tokio::{sync::mpsc, task::JoinHandle};
 
 use crate::config::Config;
 use crate::core::ai::agents::manager::AgentManager;
 use crate::core::ai::agents::{AgentConfig, AgentContext, AgentTask, TaskType};
-use crate::tui_unified::Result;
+use crate::tui_unified::{
+    components::widgets::commit_editor::{recent_scopes, validate_message},
+    Result,
+};
 
 use super::app::AppMode;
 
+/// 生成进度指示器的帧
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// 后台进行中的 commit message 生成任务
+pub(crate) struct AiCommitGeneration {
+    chunks: mpsc::UnboundedReceiver<String>,
+    task: JoinHandle<anyhow::Result<String>>,
+    started: Instant,
+    /// 已收到的流式输出
+    streamed: String,
+    /// AI 填充：完成后只补全编辑器中的空字段
+    fill_only: bool,
+}
+
+impl AiCommitGeneration {
+    fn progress(&self) -> String {
+        let elapsed = self.started.elapsed();
+        let frame = SPINNER_FRAMES[(elapsed.as_millis() / 100) as usize % SPINNER_FRAMES.len()];
+        format!(
+            "{} Generating commit message... {}s",
+            frame,
+            elapsed.as_secs()
+        )
+    }
+}
+
 impl super::app::TuiUnifiedApp {
     pub(crate) async fn enter_ai_commit_mode(&mut self) -> Result<()> {
-        // 使用新的函数获取所有变更（包括未暂存的）
-        let diff = match crate::git::get_all_changes_diff().await {
-            Ok(diff) => {
-                if diff.trim().is_empty() {
-                    let mut state = self.state.write().await;
-                    state.add_notification(
-                        "No changes to commit".to_string(),
-                        crate::tui_unified::state::app_state::NotificationLevel::Warning,
-                    );
-                    return Ok(());
-                }
-                diff
-            }
-            Err(e) => {
-                let mut state = self.state.write().await;
-                state.add_notification(
-                    format!("Failed to get changes: {}", e),
-                    crate::tui_unified::state::app_state::NotificationLevel::Error,
-                );
-                return Ok(());
-            }
+        let Some(diff) = self.commit_diff().await else {
+            return Ok(());
         };
 
         // 初始化 Agent Manager（如果还没有）
@@ -43,18 +56,108 @@ impl super::app::TuiUnifiedApp {
         self.ai_commit_status = Some("Generating commit message...".to_string());
         self.current_mode = AppMode::AICommit;
 
-        // 显示 AI commit 模态框
+        // 显示 AI commit 模态框，并用最近提交的 scope 作为补全候选
         {
             let mut state = self.state.write().await;
+            let scopes = recent_scopes(
+                state
+                    .repo_state
+                    .commits
+                    .iter()
+                    .map(|commit| commit.subject.as_str()),
+            );
+            self.commit_editor.set_recent_scopes(scopes);
             state.show_ai_commit_modal("".to_string(), "Generating commit message...".to_string());
         }
 
         // 生成 commit message
-        self.generate_commit_message(diff).await
+        self.generate_commit_message(diff, false).await
+    }
+
+    /// 编辑时按 Ctrl+G 重新生成，只补全仍为空的字段
+    pub(crate) async fn ai_fill_commit_message(&mut self) -> Result<()> {
+        if self.ai_commit_generation.is_some() {
+            return Ok(());
+        }
+        let Some(diff) = self.commit_diff().await else {
+            return Ok(());
+        };
+        self.generate_commit_message(diff, true).await
+    }
+
+    /// 读取要提交的变更，没有变更或读取失败时发出通知并返回 None
+    async fn commit_diff(&mut self) -> Option<String> {
+        // 暂存视图中只根据已暂存的变更生成，其他视图包含所有变更（包括未暂存的）
+        let staged_only = self.state.read().await.current_view
+            == crate::tui_unified::state::app_state::ViewType::Staging;
+        let diff = if staged_only {
+            crate::git::get_git_diff().await
+        } else {
+            crate::git::get_all_changes_diff().await
+        };
+        self.checked_commit_diff(diff, staged_only).await
     }
 
-    /// 生成 AI commit message
-    async fn generate_commit_message(&mut self, diff: String) -> Result<()> {
+    /// 检查读取到的变更：为空、读取失败或包含疑似敏感信息时发出通知并返回 None，
+    /// 敏感信息不会发送给 AI
+    async fn checked_commit_diff(
+        &mut self,
+        diff: anyhow::Result<String>,
+        staged_only: bool,
+    ) -> Option<String> {
+        match diff {
+            Ok(diff) => {
+                if diff.trim().is_empty() {
+                    let mut state = self.state.write().await;
+                    let message = if staged_only {
+                        "No staged changes to commit"
+                    } else {
+                        "No changes to commit"
+                    };
+                    state.add_notification(
+                        message.to_string(),
+                        crate::tui_unified::state::app_state::NotificationLevel::Warning,
+                    );
+                    None
+                } else if self.block_sensitive_diff(&diff).await {
+                    None
+                } else {
+                    Some(diff)
+                }
+            }
+            Err(e) => {
+                let mut state = self.state.write().await;
+                state.add_notification(
+                    format!("Failed to get changes: {}", e),
+                    crate::tui_unified::state::app_state::NotificationLevel::Error,
+                );
+                None
+            }
+        }
+    }
+
+    /// 变更中有疑似敏感信息时发出错误通知并返回 true；TUI 中不提供跳过选项
+    async fn block_sensitive_diff(&mut self, diff: &str) -> bool {
+        let findings = crate::analysis::sensitive::scan_diff(diff);
+        let Some(first) = findings.first() else {
+            return false;
+        };
+        let message = format!(
+            "Sensitive data detected in {} place(s), e.g. {} — remove it before committing, or use `ai-commit --allow-sensitive`",
+            findings.len(),
+            first
+        );
+        self.ai_commit_status = Some(message.clone());
+        let mut state = self.state.write().await;
+        state.add_notification(
+            message,
+            crate::tui_unified::state::app_state::NotificationLevel::Error,
+        );
+        true
+    }
+
+    /// 在后台生成 AI commit message，生成过程由 `poll_ai_commit_generation` 更新到界面
+    async fn generate_commit_message(&mut self, diff: String, fill_only: bool) -> Result<()> {
         if let Some(ref mut agent_manager) = self.agent_manager {
             // 创建配置
             let config = Config::new();
@@ -70,6 +173,7 @@ impl super::app::TuiUnifiedApp {
             // 设置 API URL
             let api_url = config.get_url();
             env_vars.insert("API_URL".to_string(), api_url);
+            config.insert_conventions(&mut env_vars);
 
             let agent_config = AgentConfig {
                 provider: config.provider.clone(),
@@ -92,52 +196,8 @@ impl super::app::TuiUnifiedApp {
             agent_manager.update_context(context);
 
             // 获取或创建 Commit Agent
-            match agent_manager.get_or_create_agent("commit").await {
-                Ok(commit_agent) => {
-                    // 创建任务
-                    let task = AgentTask::new(TaskType::GenerateCommit, diff);
-
-                    // 执行任务
-                    match commit_agent.execute(task, agent_manager.context()).await {
-                        Ok(result) => {
-                            if result.success {
-                                // 应用 gitmoji（如果启用）
-                                let content = if config.emoji {
-                                    crate::core::gitmoji::add_emoji(&result.content)
-                                } else {
-                                    result.content.clone()
-                                };
-                                self.ai_commit_message = Some(content.clone());
-                                self.ai_commit_status =
-                                    Some("Commit message generated successfully".to_string());
-
-                                // 更新模态框内容
-                                let mut state = self.state.write().await;
-                                state.show_ai_commit_modal(
-                                    content,
-                                    "Commit message generated successfully".to_string(),
-                                );
-                            } else {
-                                self.ai_commit_status =
-                                    Some("Failed to generate commit message".to_string());
-
-                                // 更新模态框内容
-                                let mut state = self.state.write().await;
-                                state.show_ai_commit_modal(
-                                    "".to_string(),
-                                    "Failed to generate commit message".to_string(),
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            self.ai_commit_status = Some(format!("Error: {}", e));
-
-                            // 更新模态框内容
-                            let mut state = self.state.write().await;
-                            state.show_ai_commit_modal("".to_string(), format!("Error: {}", e));
-                        }
-                    }
-                }
+            let commit_agent = match agent_manager.get_or_create_agent("commit").await {
+                Ok(commit_agent) => commit_agent,
                 Err(e) => {
                     self.ai_commit_status = Some(format!("Failed to create agent: {}", e));
 
@@ -147,7 +207,105 @@ impl super::app::TuiUnifiedApp {
                         "".to_string(),
                         format!("Failed to create agent: {}", e),
                     );
+                    return Ok(());
+                }
+            };
+
+            let context = agent_manager.context().clone();
+            let (chunk_sender, chunks) = mpsc::unbounded_channel();
+            let task = tokio::spawn(async move {
+                let task = AgentTask::new(TaskType::GenerateCommit, diff);
+                let result = commit_agent
+                    .execute_streaming(task, &context, chunk_sender)
+                    .await?;
+                if !result.success {
+                    anyhow::bail!("Failed to generate commit message");
                 }
+
+                // 应用 gitmoji（如果启用）
+                let content = if config.emoji {
+                    crate::core::gitmoji::add_emoji(&result.content)
+                } else {
+                    result.content
+                };
+                // 附加分支名中的工单号（如果配置）
+                Ok(
+                    crate::commands::commit::apply_branch_ticket(&content, &config)
+                        .await
+                        .unwrap_or(content),
+                )
+            });
+
+            if !fill_only {
+                self.commit_editor.set_content("");
+            }
+            self.ai_commit_generation = Some(AiCommitGeneration {
+                chunks,
+                task,
+                started: Instant::now(),
+                streamed: String::new(),
+                fill_only,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// 将后台生成的流式输出更新到编辑器，完成后进入编辑模式
+    pub(crate) async fn poll_ai_commit_generation(&mut self) -> Result<()> {
+        let Some(generation) = self.ai_commit_generation.as_mut() else {
+            return Ok(());
+        };
+
+        while let Ok(chunk) = generation.chunks.try_recv() {
+            generation.streamed.push_str(&chunk);
+        }
+
+        if !generation.task.is_finished() {
+            let progress = generation.progress();
+            if !generation.fill_only {
+                self.commit_editor.set_content(&generation.streamed);
+            }
+            self.commit_editor.set_title(&progress);
+            self.ai_commit_status = Some(progress);
+            return Ok(());
+        }
+
+        let Some(generation) = self.ai_commit_generation.take() else {
+            return Ok(());
+        };
+        self.commit_editor
+            .set_title(crate::tui_unified::components::widgets::commit_editor::DEFAULT_TITLE);
+
+        let result = match generation.task.await {
+            Ok(result) => result,
+            Err(e) => Err(anyhow::anyhow!("Generation task failed: {}", e)),
+        };
+
+        let mut state = self.state.write().await;
+        match result {
+            Ok(content) => {
+                let status =
+                    "Commit message generated — Tab to finish editing, then Enter to commit";
+                self.ai_commit_status = Some(status.to_string());
+
+                // 将生成的消息放入编辑器，直接进入编辑模式
+                if generation.fill_only {
+                    self.commit_editor.fill_missing(&content);
+                } else {
+                    self.commit_editor.set_content(&content);
+                }
+                let content = self.commit_editor.get_content();
+                self.ai_commit_message = Some(content.clone());
+                self.commit_editor.set_focused(true);
+                self.ai_commit_editing = true;
+
+                state.show_ai_commit_modal(content, status.to_string());
+            }
+            Err(e) => {
+                self.ai_commit_status = Some(format!("Error: {}", e));
+                self.commit_editor.set_content("");
+                state.show_ai_commit_modal("".to_string(), format!("Error: {}", e));
             }
         }
 
@@ -156,7 +314,19 @@ impl super::app::TuiUnifiedApp {
 
     /// 确认并提交 AI 生成的 commit message
     pub(crate) async fn confirm_ai_commit(&mut self) -> Result<()> {
-        if let Some(ref message) = self.ai_commit_message {
+        if let Some(message) = self.ai_commit_message.clone() {
+            // 不符合 Conventional Commits 格式时不允许提交
+            if let Err(e) = validate_message(&message) {
+                self.ai_commit_status =
+                    Some(format!("Invalid commit message: {} — press e to edit", e));
+                let mut state = self.state.write().await;
+                state.add_notification(
+                    format!("Invalid commit message: {}", e),
+                    crate::tui_unified::state::app_state::NotificationLevel::Error,
+                );
+                return Ok(());
+            }
+
             // 首先检查是否有暂存的变更
             let staged_diff = match crate::git::get_git_diff().await {
                 Ok(diff) => diff,
@@ -192,8 +362,25 @@ impl super::app::TuiUnifiedApp {
                 }
             }
 
+            // 提交前扫描最终暂存的变更，生成后暂存区可能已经变化
+            match crate::git::get_git_diff().await {
+                Ok(diff) => {
+                    if self.block_sensitive_diff(&diff).await {
+                        return Ok(());
+                    }
+                }
+                Err(e) => {
+                    let mut state = self.state.write().await;
+                    state.add_notification(
+                        format!("Failed to check staged changes: {}", e),
+                        crate::tui_unified::state::app_state::NotificationLevel::Error,
+                    );
+                    return Ok(());
+                }
+            }
+
             // 现在执行提交
-            match crate::git::git_commit(message).await {
+            match crate::git::git_commit(&message).await {
                 Ok(_) => {
                     let mut state = self.state.write().await;
                     state.add_notification(
@@ -212,6 +399,12 @@ impl super::app::TuiUnifiedApp {
                         drop(state);
                     }
 
+                    // 从暂存视图提交后刷新文件列表
+                    let current_view = self.state.read().await.current_view;
+                    if current_view == crate::tui_unified::state::app_state::ViewType::Staging {
+                        let _ = self.refresh_current_view(current_view).await;
+                    }
+
                     // 显示推送提示而不是立即退出
                     self.ai_commit_push_prompt = true;
                     self.ai_commit_status = Some("Commit successful! Push to remote?".to_string());
@@ -235,6 +428,10 @@ impl super::app::TuiUnifiedApp {
 
     /// 退出 AI commit 模式
     pub(crate) fn exit_ai_commit_mode(&mut self) {
+        // 取消仍在进行的生成任务
+        if let Some(generation) = self.ai_commit_generation.take() {
+            generation.task.abort();
+        }
         self.ai_commit_mode = false;
         self.ai_commit_editing = false;
         self.ai_commit_message = None;
@@ -245,6 +442,8 @@ impl super::app::TuiUnifiedApp {
         // 重置编辑器状态
         self.commit_editor.set_focused(false);
         self.commit_editor.set_content("");
+        self.commit_editor
+            .set_title(crate::tui_unified::components::widgets::commit_editor::DEFAULT_TITLE);
     }
 
     /// 确认推送到远程
@@ -279,64 +478,10 @@ impl super::app::TuiUnifiedApp {
     }
 
     pub(crate) async fn confirm_git_pull(&mut self) -> Result<()> {
-        // 隐藏模态框
-        {
-            let mut state = self.state.write().await;
-            state.hide_modal();
-        }
-
-        // 执行 git pull
-        let result = tokio::process::Command::new("git")
-            .arg("pull")
-            .output()
-            .await;
-
-        match result {
-            Ok(output) => {
-                let mut state = self.state.write().await;
-                if output.status.success() {
-                    let pull_output = String::from_utf8_lossy(&output.stdout);
-                    let notification_msg = if pull_output.contains("Already up to date") {
-                        "Already up to date".to_string()
-                    } else {
-                        "Pull completed successfully!".to_string()
-                    };
-
-                    state.add_notification(
-                        notification_msg,
-                        crate::tui_unified::state::app_state::NotificationLevel::Success,
-                    );
-                    drop(state);
-
-                    // 拉取成功后刷新git log
-                    if let Err(e) = self
-                        .refresh_current_view(
-                            crate::tui_unified::state::app_state::ViewType::GitLog,
-                        )
-                        .await
-                    {
-                        let mut state = self.state.write().await;
-                        state.add_notification(
-                            format!("Failed to refresh git log: {}", e),
-                            crate::tui_unified::state::app_state::NotificationLevel::Warning,
-                        );
-                    }
-                } else {
-                    let error_output = String::from_utf8_lossy(&output.stderr);
-                    state.add_notification(
-                        format!("Pull failed: {}", error_output),
-                        crate::tui_unified::state::app_state::NotificationLevel::Error,
-                    );
-                }
-            }
-            Err(e) => {
-                let mut state = self.state.write().await;
-                state.add_notification(
-                    format!("Failed to execute git pull: {}", e),
-                    crate::tui_unified::state::app_state::NotificationLevel::Error,
-                );
-            }
-        }
+        // 隐藏模态框，拉取在后台执行并显示进度
+        let mut state = self.state.write().await;
+        state.hide_modal();
+        state.request_remote_operation(crate::tui_unified::state::app_state::RemoteOperation::Pull);
 
         Ok(())
     }
@@ -448,3 +593,31 @@ impl super::app::TuiUnifiedApp {
         self.exit_ai_commit_mode();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::tui_unified::app::TuiUnifiedApp;
+    use crate::tui_unified::state::app_state::NotificationLevel;
+
+    #[tokio::test]
+    async fn test_sensitive_diff_is_not_sent_to_ai() {
+        let mut app = TuiUnifiedApp::new().await.unwrap();
+        let leaked = format!(
+            "diff --git a/.env b/.env\n+AWS_KEY=AKIA{}\n",
+            "IOSFODNN7REALKEY"
+        );
+
+        assert_eq!(app.checked_commit_diff(Ok(leaked), true).await, None);
+        let state = app.state.read().await;
+        let notification = state.notifications.last().unwrap();
+        assert_eq!(notification.level, NotificationLevel::Error);
+        assert!(notification.message.contains("Sensitive data detected"));
+        drop(state);
+
+        let clean = "diff --git a/src/lib.rs b/src/lib.rs\n+pub mod analysis;\n".to_string();
+        assert_eq!(
+            app.checked_commit_diff(Ok(clean.clone()), true).await,
+            Some(clean)
+        );
+    }
+}
@@ -0,0 +1,174 @@
+// 提交图布局算法 - 为每个提交分配泳道并生成 Unicode 连线
+
+/// 提交图中的一行（对应一个提交）
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphRow {
+    /// 提交所在的泳道
+    pub column: usize,
+    /// 图形字符，每条泳道占两个字符宽度
+    pub glyphs: String,
+}
+
+/// 增量构建的提交图
+///
+/// 提交需要按拓扑顺序（子提交在前）依次加入，分页加载时可以继续追加。
+#[derive(Debug, Clone, Default)]
+pub struct CommitGraph {
+    /// 每条泳道正在等待的下一个提交
+    lanes: Vec<Option<String>>,
+}
+
+impl CommitGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 当前占用的泳道数
+    pub fn width(&self) -> usize {
+        self.lanes.len()
+    }
+
+    /// 加入一个提交并返回它的图形行
+    pub fn push(&mut self, hash: &str, parents: &[String]) -> GraphRow {
+        let column = match self.lanes.iter().position(|l| l.as_deref() == Some(hash)) {
+            Some(column) => column,
+            None => self.allocate_lane(),
+        };
+
+        // 其他等待同一提交的泳道在此汇合
+        let merging: Vec<usize> = (0..self.lanes.len())
+            .filter(|&j| j != column && self.lanes[j].as_deref() == Some(hash))
+            .collect();
+        for &j in &merging {
+            self.lanes[j] = None;
+        }
+
+        let passing: Vec<bool> = self
+            .lanes
+            .iter()
+            .enumerate()
+            .map(|(j, lane)| j != column && lane.is_some())
+            .collect();
+
+        self.lanes[column] = parents.first().cloned();
+
+        // 合并提交的其他父提交：复用已有泳道或新开泳道
+        let mut forks = Vec::new();
+        for parent in parents.iter().skip(1) {
+            match self
+                .lanes
+                .iter()
+                .position(|l| l.as_deref() == Some(parent.as_str()))
+            {
+                Some(j) => forks.push((j, true)),
+                None => {
+                    let j = self.allocate_lane();
+                    self.lanes[j] = Some(parent.clone());
+                    forks.push((j, merging.contains(&j)));
+                }
+            }
+        }
+
+        let width = self.lanes.len();
+        let mut cells = vec![' '; width * 2];
+        for (j, _) in passing.iter().enumerate().filter(|(_, active)| **active) {
+            cells[j * 2] = '│';
+        }
+        cells[column * 2] = '●';
+
+        for &j in &merging {
+            if !forks.iter().any(|&(f, _)| f == j) {
+                Self::connect(&mut cells, column, j, if j > column { '╯' } else { '╰' });
+            }
+        }
+        for &(j, existing) in &forks {
+            let glyph = match (existing, j > column) {
+                (true, true) => '┤',
+                (true, false) => '├',
+                (false, true) => '╮',
+                (false, false) => '╭',
+            };
+            Self::connect(&mut cells, column, j, glyph);
+        }
+
+        while matches!(self.lanes.last(), Some(None)) {
+            self.lanes.pop();
+        }
+
+        GraphRow {
+            column,
+            glyphs: cells.into_iter().collect::<String>().trim_end().to_string(),
+        }
+    }
+
+    fn allocate_lane(&mut self) -> usize {
+        match self.lanes.iter().position(|l| l.is_none()) {
+            Some(j) => j,
+            None => {
+                self.lanes.push(None);
+                self.lanes.len() - 1
+            }
+        }
+    }
+
+    /// 从提交列画横线连接到目标泳道
+    fn connect(cells: &mut [char], column: usize, target: usize, glyph: char) {
+        let (from, to) = if target > column {
+            (column * 2 + 1, target * 2)
+        } else {
+            (target * 2 + 1, column * 2)
+        };
+        for cell in cells.iter_mut().take(to).skip(from) {
+            *cell = match *cell {
+                '│' => '┼',
+                ' ' => '─',
+                other => other,
+            };
+        }
+        cells[target * 2] = glyph;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parents(list: &[&str]) -> Vec<String> {
+        list.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn test_linear_history() {
+        let mut graph = CommitGraph::new();
+        assert_eq!(graph.push("c", &parents(&["b"])).glyphs, "●");
+        assert_eq!(graph.push("b", &parents(&["a"])).glyphs, "●");
+        assert_eq!(graph.push("a", &[]).glyphs, "●");
+        assert_eq!(graph.width(), 0);
+    }
+
+    #[test]
+    fn test_branch_and_merge() {
+        let mut graph = CommitGraph::new();
+        assert_eq!(graph.push("m", &parents(&["a", "b"])).glyphs, "●─╮");
+
+        let row = graph.push("b", &parents(&["a"]));
+        assert_eq!(row.column, 1);
+        assert_eq!(row.glyphs, "│ ●");
+
+        assert_eq!(graph.push("a", &[]).glyphs, "●─╯");
+        assert_eq!(graph.width(), 0);
+    }
+
+    #[test]
+    fn test_crossing_lane() {
+        let mut graph = CommitGraph::new();
+        // 三个分支头，c 的第二个父提交已在最左侧泳道中，连线穿过中间泳道
+        graph.push("x", &parents(&["a"]));
+        graph.push("y", &parents(&["b"]));
+        graph.push("z", &parents(&["c"]));
+
+        let row = graph.push("c", &parents(&["d", "a"]));
+        assert_eq!(row.column, 2);
+        assert_eq!(row.glyphs, "├─┼─●");
+    }
+}
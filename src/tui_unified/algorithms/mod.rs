@@ -1,12 +1,22 @@
 // Algorithms - placeholder implementations
 
+pub mod graph;
+
 use std::marker::PhantomData;
 
+/// 虚拟滚动管理器：只保留完整列表中的一段窗口，渲染时只取视口内的条目
+///
+/// `offset` 是窗口第一条在完整列表中的位置。按页追加或前插后超过 `max_items`
+/// 时从另一端丢弃，内存占用与列表总长度无关。
 pub struct VirtualScrollManager<T> {
     _marker: PhantomData<T>,
     pub items: Vec<T>,
     pub viewport_start: usize,
     pub viewport_size: usize,
+    /// 窗口第一条在完整列表中的位置
+    pub offset: usize,
+    /// 窗口最多保留的条目数，0 表示不限制
+    pub max_items: usize,
 }
 
 impl<T> VirtualScrollManager<T> {
@@ -16,12 +26,66 @@ impl<T> VirtualScrollManager<T> {
             items: Vec::new(),
             viewport_start: 0,
             viewport_size,
+            offset: 0,
+            max_items: 0,
         }
     }
 
+    /// 限制窗口最多保留的条目数
+    pub fn with_max_items(mut self, max_items: usize) -> Self {
+        self.max_items = max_items;
+        self
+    }
+
     pub fn set_items(&mut self, items: Vec<T>) {
         self.items = items;
         self.viewport_start = 0;
+        self.offset = 0;
+    }
+
+    /// 窗口末尾之后一条在完整列表中的位置
+    pub fn end(&self) -> usize {
+        self.offset + self.items.len()
+    }
+
+    /// 在窗口末尾追加一页，返回从开头丢弃的条目数
+    pub fn append_page(&mut self, page: Vec<T>) -> usize {
+        self.items.extend(page);
+        let dropped = self.overflow();
+        if dropped > 0 {
+            self.items.drain(..dropped);
+            self.offset += dropped;
+            self.viewport_start = self.viewport_start.saturating_sub(dropped);
+        }
+        dropped
+    }
+
+    /// 在窗口开头插入一页（紧挨在 `offset` 之前），返回从末尾丢弃的条目数
+    pub fn prepend_page(&mut self, page: Vec<T>) -> usize {
+        let added = page.len().min(self.offset);
+        self.offset -= added;
+        self.viewport_start += added;
+        self.items.splice(0..0, page.into_iter().take(added));
+        let dropped = self.overflow();
+        self.items.truncate(self.items.len() - dropped);
+        dropped
+    }
+
+    fn overflow(&self) -> usize {
+        if self.max_items == 0 {
+            0
+        } else {
+            self.items.len().saturating_sub(self.max_items)
+        }
+    }
+
+    /// 调整视口，使窗口内的第 `index` 条可见
+    pub fn ensure_visible(&mut self, index: usize) {
+        if index < self.viewport_start {
+            self.viewport_start = index;
+        } else if self.viewport_size > 0 && index >= self.viewport_start + self.viewport_size {
+            self.viewport_start = index + 1 - self.viewport_size;
+        }
     }
 
     pub fn scroll_up(&mut self) {
@@ -43,8 +107,9 @@ impl<T> VirtualScrollManager<T> {
     }
 
     pub fn get_visible_items(&self) -> &[T] {
-        let end = std::cmp::min(self.viewport_start + self.viewport_size, self.items.len());
-        &self.items[self.viewport_start..end]
+        let start = self.viewport_start.min(self.items.len());
+        let end = std::cmp::min(start + self.viewport_size, self.items.len());
+        &self.items[start..end]
     }
 
     pub fn get_selected_index(&self) -> usize {
@@ -52,10 +117,118 @@ impl<T> VirtualScrollManager<T> {
     }
 }
 
+/// 模糊匹配结果：分数越高越相关，`positions` 是命中字符的下标（按字符计）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 15;
+const BONUS_BOUNDARY: i64 = 10;
+const PENALTY_GAP: i64 = 1;
+const MAX_LEADING_PENALTY: i64 = 10;
+
+fn is_boundary(prev: Option<char>, current: char) -> bool {
+    match prev {
+        None => true,
+        Some(prev) => {
+            matches!(prev, '/' | '_' | '-' | '.' | ' ' | ':' | '@')
+                || (prev.is_lowercase() && current.is_uppercase())
+        }
+    }
+}
+
+/// 模糊匹配：查询中的字符按顺序出现在文本中即为命中，空白字符被忽略
+///
+/// 先正向找到最早能完成匹配的结尾，再反向收缩起点，得到最短的匹配窗口，
+/// 连续命中和单词边界命中会获得加分，中间跳过的字符会扣分。
+pub fn fuzzy_match(query: &str, text: &str, case_sensitive: bool) -> Option<FuzzyMatch> {
+    let normalize = |c: char| {
+        if case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    };
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(normalize)
+        .collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    let original: Vec<char> = text.chars().collect();
+    let chars: Vec<char> = original.iter().map(|&c| normalize(c)).collect();
+
+    // 正向：找到匹配的结尾
+    let mut qi = 0;
+    let mut end = None;
+    for (i, &c) in chars.iter().enumerate() {
+        if c == query[qi] {
+            qi += 1;
+            if qi == query.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    // 反向：从结尾往回找到最晚的起点
+    let mut qi = query.len();
+    let mut start = end;
+    for i in (0..=end).rev() {
+        if chars[i] == query[qi - 1] {
+            qi -= 1;
+            if qi == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    let mut positions = Vec::with_capacity(query.len());
+    let mut qi = 0;
+    for (i, &c) in chars.iter().enumerate().take(end + 1).skip(start) {
+        if qi < query.len() && c == query[qi] {
+            positions.push(i);
+            qi += 1;
+        }
+    }
+
+    let mut score = -(start as i64).min(MAX_LEADING_PENALTY);
+    let mut previous: Option<usize> = None;
+    for &pos in &positions {
+        score += SCORE_MATCH;
+        if is_boundary(pos.checked_sub(1).map(|p| original[p]), original[pos]) {
+            score += BONUS_BOUNDARY;
+        }
+        if let Some(prev) = previous {
+            if pos == prev + 1 {
+                score += BONUS_CONSECUTIVE;
+            } else {
+                score -= (pos - prev - 1) as i64 * PENALTY_GAP;
+            }
+        }
+        previous = Some(pos);
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
 pub struct SmartSearchEngine {
     pub query: String,
     pub case_sensitive: bool,
     pub regex_enabled: bool,
+    /// 启用后按模糊匹配筛选并按相关度排序
+    pub fuzzy_enabled: bool,
 }
 
 impl Default for SmartSearchEngine {
@@ -70,6 +243,15 @@ impl SmartSearchEngine {
             query: String::new(),
             case_sensitive: false,
             regex_enabled: false,
+            fuzzy_enabled: false,
+        }
+    }
+
+    /// 创建启用模糊匹配的搜索引擎
+    pub fn fuzzy() -> Self {
+        Self {
+            fuzzy_enabled: true,
+            ..Self::new()
         }
     }
 
@@ -78,6 +260,14 @@ impl SmartSearchEngine {
     }
 
     pub fn search<T>(&self, items: &[T], extract_text: fn(&T) -> &str) -> Vec<usize> {
+        if self.fuzzy_enabled {
+            return self
+                .search_ranked(items, extract_text)
+                .into_iter()
+                .map(|(index, _)| index)
+                .collect();
+        }
+
         if self.query.is_empty() {
             return (0..items.len()).collect();
         }
@@ -97,4 +287,92 @@ impl SmartSearchEngine {
         }
         matches
     }
+
+    /// 模糊搜索并按分数从高到低排序，分数相同时较短的文本优先，其次保持原有顺序
+    pub fn search_ranked<T>(
+        &self,
+        items: &[T],
+        extract_text: fn(&T) -> &str,
+    ) -> Vec<(usize, FuzzyMatch)> {
+        let mut results: Vec<(usize, FuzzyMatch)> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                fuzzy_match(&self.query, extract_text(item), self.case_sensitive)
+                    .map(|m| (index, m))
+            })
+            .collect();
+
+        results.sort_by(|(a_index, a), (b_index, b)| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| {
+                    extract_text(&items[*a_index])
+                        .len()
+                        .cmp(&extract_text(&items[*b_index]).len())
+                })
+                .then_with(|| a_index.cmp(b_index))
+        });
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_virtual_scroll_window_stays_bounded() {
+        let mut scroll = VirtualScrollManager::new(3).with_max_items(4);
+        scroll.set_items(vec![0, 1, 2]);
+        scroll.ensure_visible(2);
+
+        // 追加超过上限时丢弃开头，视口跟随同一条目
+        assert_eq!(scroll.append_page(vec![3, 4, 5]), 2);
+        assert_eq!((scroll.offset, scroll.end()), (2, 6));
+        assert_eq!(scroll.items, vec![2, 3, 4, 5]);
+        assert_eq!(scroll.get_visible_items(), &[2, 3, 4]);
+
+        // 向前补回一页时丢弃末尾
+        assert_eq!(scroll.prepend_page(vec![0, 1]), 2);
+        assert_eq!(scroll.offset, 0);
+        assert_eq!(scroll.items, vec![0, 1, 2, 3]);
+        assert_eq!(scroll.get_visible_items(), &[2, 3]);
+    }
+
+    #[test]
+    fn test_fuzzy_match_subsequence() {
+        let m = fuzzy_match("gtl", "src/git_log.rs", false).unwrap();
+        assert_eq!(m.positions, vec![4, 6, 8]);
+        assert!(fuzzy_match("xyz", "src/git_log.rs", false).is_none());
+        assert!(fuzzy_match("GIT", "src/git_log.rs", true).is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_match_prefers_shortest_window() {
+        // 反向收缩后应命中相邻的 "ab"，而不是开头的 "a"
+        let m = fuzzy_match("ab", "a__ab", false).unwrap();
+        assert_eq!(m.positions, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_search_ranked_orders_by_relevance() {
+        let items = vec![
+            "feature/remaining-tasks".to_string(),
+            "main".to_string(),
+            "maintenance".to_string(),
+        ];
+        let engine = SmartSearchEngine {
+            query: "main".to_string(),
+            ..SmartSearchEngine::fuzzy()
+        };
+
+        let ranked: Vec<usize> = engine
+            .search_ranked(&items, |s| s.as_str())
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(ranked, vec![1, 2, 0]);
+        assert_eq!(engine.search(&items, |s| s.as_str()), ranked);
+    }
 }
@@ -1,5 +1,5 @@
 use crossterm::{
-    event::{self, Event},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -10,30 +10,47 @@ use tokio::sync::RwLock;
 use crate::core::ai::agents::manager::AgentManager;
 use crate::diff_viewer::DiffViewer;
 use crate::tui_unified::{
+    async_manager::AsyncTaskManager,
+    clipboard::SystemClipboard,
     components::{
-        panels::sidebar::SidebarPanel,
+        panels::{detail::DetailPanel, sidebar::SidebarPanel},
         views::{
-            branches::BranchesView, git_log::GitLogView, query_history::QueryHistoryView,
-            remotes::RemotesView, staging::StagingView, stash::StashView, tags::TagsView,
+            blame::BlameView, branches::BranchesView, git_log::GitLogView, graph::GraphView,
+            hotspots::HotspotsView, query_history::QueryHistoryView, reflog::ReflogView,
+            remotes::RemotesView, review::ReviewView, staging::StagingView, stash::StashView,
+            tags::TagsView, worktrees::WorktreesView,
+        },
+        widgets::{
+            command_palette::CommandPalette, commit_editor::CommitEditor,
+            fuzzy_finder::FuzzyFinder, help_panel::HelpPanel,
+            notification_center::NotificationCenter, repo_switcher::RepoSwitcher,
+            settings_panel::SettingsPanel, storage_panel::StoragePanel, tag_dialog::TagDialog,
         },
-        widgets::{commit_editor::CommitEditor, search_box::SearchBox},
     },
-    config::AppConfig,
+    config::{AppConfig, Theme, ThemePalette},
     diff_rendering::DiffRenderCache,
     focus::{FocusManager, FocusPanel},
     layout::LayoutManager,
-    state::AppState,
+    state::{
+        app_state::{NotificationLevel, RemoteOperation},
+        AppState, SimpleStatePersistence,
+    },
     Result,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppMode {
-    Normal,   // 正常浏览模式
-    Search,   // 搜索模式
-    Command,  // 命令模式
-    Help,     // 帮助模式
-    Diff,     // 全屏diff模式
-    AICommit, // AI提交模式
+    Normal,        // 正常浏览模式
+    Search,        // 搜索模式
+    Command,       // 命令面板
+    Help,          // 帮助模式
+    Diff,          // 全屏diff模式
+    AICommit,      // AI提交模式
+    Settings,      // 设置界面
+    CreateTag,     // 新建标签
+    Notifications, // 通知中心
+    Repos,         // 仓库切换
+    Storage,       // 存储概况
 }
 
 pub struct TuiUnifiedApp {
@@ -46,6 +63,7 @@ pub struct TuiUnifiedApp {
 
     // 组件
     pub(crate) sidebar_panel: SidebarPanel,
+    pub(crate) detail_panel: DetailPanel,
     pub(crate) git_log_view: GitLogView,
     pub(crate) branches_view: BranchesView,
     pub(crate) tags_view: TagsView,
@@ -53,17 +71,53 @@ pub struct TuiUnifiedApp {
     pub(crate) stash_view: StashView,
     pub(crate) query_history_view: QueryHistoryView,
     pub(crate) staging_view: StagingView,
-    pub(crate) search_box: SearchBox,
+    pub(crate) graph_view: GraphView,
+    pub(crate) reflog_view: ReflogView,
+    pub(crate) blame_view: BlameView,
+    pub(crate) worktrees_view: WorktreesView,
+    pub(crate) review_view: ReviewView,
+    pub(crate) hotspots_view: HotspotsView,
+    pub(crate) fuzzy_finder: FuzzyFinder,
+    pub(crate) settings_panel: SettingsPanel,
+    pub(crate) tag_dialog: TagDialog,
+    pub(crate) notification_center: NotificationCenter,
+    pub(crate) help_panel: HelpPanel,
+    pub(crate) command_palette: CommandPalette,
+    pub(crate) repo_switcher: RepoSwitcher,
+    pub(crate) storage_panel: StoragePanel,
     pub(crate) diff_viewer: Option<DiffViewer>,
     pub(crate) diff_render_cache: DiffRenderCache,
     pub(crate) commit_editor: CommitEditor,
+    pub(crate) clipboard: SystemClipboard,
 
     // 配置
-    pub(crate) _config: AppConfig,
+    pub(crate) config: AppConfig,
+    /// 当前主题的调色板，每帧渲染完成后应用
+    pub(crate) theme: ThemePalette,
 
     // 运行状态
     pub(crate) should_quit: bool,
     pub(crate) current_mode: AppMode,
+    pub(crate) last_auto_refresh: std::time::Instant,
+    /// 上次检查定时备份是否到期的时间
+    pub(crate) last_backup_check: Option<std::time::Instant>,
+    /// 下一轮循环中要打开 shell 的目录
+    pub(crate) pending_shell: Option<std::path::PathBuf>,
+    /// 等待输入文件名的视图导出
+    pub(crate) pending_export: Option<super::export_handler::PendingExport>,
+    /// 文件变化时自动刷新，关闭时为 None
+    pub(crate) repo_watcher: Option<super::watch_handler::RepoWatcher>,
+    /// 配置文件变化时重新加载
+    pub(crate) config_watcher: crate::config::ConfigWatcher,
+    /// 配置文件中上一次的 [tui]，只应用有变化的项
+    pub(crate) tui_defaults: crate::config::TuiSection,
+    /// 后台的 fetch/pull/push 任务
+    pub(crate) task_manager: AsyncTaskManager,
+    pub(crate) remote_operation: Option<RemoteOperation>,
+
+    // 鼠标命中测试：上一帧的布局和模态框中可点击的按键提示
+    pub(crate) last_layout: LayoutResult,
+    pub(crate) modal_click_targets: Vec<(ratatui::layout::Rect, KeyEvent)>,
 
     // AI commit 功能
     pub(crate) agent_manager: Option<AgentManager>,
@@ -72,6 +126,9 @@ pub struct TuiUnifiedApp {
     pub(crate) ai_commit_editing: bool,
     pub(crate) ai_commit_status: Option<String>,
     pub(crate) ai_commit_push_prompt: bool,
+    pub(crate) ai_commit_generation: Option<super::ai_commit_handler::AiCommitGeneration>,
+    pub(crate) tag_note: Option<super::tag_handler::TagNoteGeneration>,
+    pub(crate) revert_message: Option<super::commit_action_handler::RevertMessageGeneration>,
 
     // 分支提交缓存（避免每帧重新加载）
     pub(crate) cached_branch_name: Option<String>,
@@ -80,17 +137,64 @@ pub struct TuiUnifiedApp {
 
 impl TuiUnifiedApp {
     pub async fn new() -> Result<Self> {
-        let config = AppConfig::load().unwrap_or_default();
+        // 配置文件损坏时使用默认配置，创建状态后再提示；
+        // 还没有保存过 TUI 配置时以 config.toml 的 [tui] 为准
+        let settings = crate::config::Config::new();
+        let (mut config, config_error) = match AppConfig::load() {
+            Ok(Some(config)) => (config, None),
+            Ok(None) => (AppConfig::from_section(&settings.tui), None),
+            Err(e) => (AppConfig::from_section(&settings.tui), Some(e)),
+        };
+        config.apply_notifications(&settings.notifications);
+
+        // 恢复上次选择的主题和布局
+        let mut saved_layout = None;
+        let mut favorite_queries = Vec::new();
+        if let Ok(persistence) = SimpleStatePersistence::new() {
+            if let Ok(Some(saved)) = persistence.load_state().await {
+                config.theme_name = saved.theme_name;
+                saved_layout = Some((saved.layout.unwrap_or_default(), saved.layout_presets));
+                favorite_queries = saved.favorite_queries;
+            }
+        }
+        let theme = Theme::load(&config.theme_name).and_then(|theme| theme.colors.palette());
+
         let state = Arc::new(RwLock::new(AppState::new(&config).await?));
+        if let Some(e) = config_error {
+            state.write().await.add_notification(
+                format!("Failed to load TUI config, using defaults: {:#}", e),
+                NotificationLevel::Warning,
+            );
+        }
+        let theme = match theme {
+            Ok(palette) => palette,
+            Err(e) => {
+                state.write().await.add_notification(
+                    format!("Failed to load theme, using dark: {}", e),
+                    NotificationLevel::Warning,
+                );
+                ThemePalette::default()
+            }
+        };
+
+        let mut layout_manager = LayoutManager::new(&config);
+        state.write().await.search_state.favorite_queries = favorite_queries;
+        if let Some((arrangement, presets)) = saved_layout {
+            layout_manager.apply_preset(&arrangement);
+            let mut state = state.write().await;
+            state.layout.arrangement = arrangement;
+            state.layout.presets = presets;
+        }
 
         let mut focus_manager = FocusManager::new();
         focus_manager.set_focus(FocusPanel::Content);
 
         Ok(Self {
             state: Arc::clone(&state),
-            layout_manager: LayoutManager::new(&config),
+            layout_manager,
             focus_manager,
             sidebar_panel: SidebarPanel::new(),
+            detail_panel: DetailPanel::new(),
             git_log_view: GitLogView::new(),
             branches_view: BranchesView::new(),
             tags_view: TagsView::new(),
@@ -98,13 +202,40 @@ impl TuiUnifiedApp {
             stash_view: StashView::new(),
             query_history_view: QueryHistoryView::new(),
             staging_view: StagingView::new(),
-            search_box: SearchBox::new().with_placeholder("Search...".to_string()),
+            graph_view: GraphView::new(),
+            reflog_view: ReflogView::new(),
+            blame_view: BlameView::new(),
+            worktrees_view: WorktreesView::new(),
+            review_view: ReviewView::new(),
+            hotspots_view: HotspotsView::new(),
+            fuzzy_finder: FuzzyFinder::new(),
+            settings_panel: SettingsPanel::new(),
+            tag_dialog: TagDialog::new(),
+            notification_center: NotificationCenter::new(),
+            help_panel: HelpPanel::new(),
+            command_palette: CommandPalette::new(),
+            repo_switcher: RepoSwitcher::new(),
+            storage_panel: StoragePanel::new(),
             diff_viewer: None,
             diff_render_cache: DiffRenderCache::new(),
             commit_editor: CommitEditor::new(),
-            _config: config,
+            clipboard: SystemClipboard::new(),
+            config,
+            theme,
             should_quit: false,
             current_mode: AppMode::Normal,
+            last_auto_refresh: std::time::Instant::now(),
+            last_backup_check: None,
+            pending_shell: None,
+            pending_export: None,
+            repo_watcher: None,
+            config_watcher: crate::config::ConfigWatcher::new(),
+            tui_defaults: settings.tui.clone(),
+            task_manager: AsyncTaskManager::new(),
+            remote_operation: None,
+
+            last_layout: LayoutResult::default(),
+            modal_click_targets: Vec::new(),
 
             agent_manager: None,
             ai_commit_message: None,
@@ -112,6 +243,9 @@ impl TuiUnifiedApp {
             ai_commit_editing: false,
             ai_commit_status: None,
             ai_commit_push_prompt: false,
+            ai_commit_generation: None,
+            tag_note: None,
+            revert_message: None,
 
             cached_branch_name: None,
             cached_branch_commits: Vec::new(),
@@ -121,7 +255,7 @@ impl TuiUnifiedApp {
     pub async fn run() -> Result<()> {
         enable_raw_mode()?;
         let mut stdout = std::io::stdout();
-        execute!(stdout, EnterAlternateScreen)?;
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
 
@@ -130,7 +264,11 @@ impl TuiUnifiedApp {
         let result = app.run_loop(&mut terminal).await;
 
         disable_raw_mode()?;
-        execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+        execute!(
+            terminal.backend_mut(),
+            LeaveAlternateScreen,
+            DisableMouseCapture
+        )?;
         terminal.show_cursor()?;
 
         result
@@ -140,20 +278,47 @@ impl TuiUnifiedApp {
     where
         B: ratatui::backend::Backend,
     {
-        self.load_initial_git_data().await?;
+        self.start_initial_load().await?;
 
         loop {
             terminal.draw(|f| self.render(f))?;
 
             if event::poll(std::time::Duration::from_millis(100))? {
-                if let Event::Key(key) = event::read()? {
-                    self.handle_key_event(key).await?;
+                match event::read()? {
+                    Event::Key(key) => self.handle_key_event(key).await?,
+                    Event::Mouse(mouse) => self.handle_mouse_event(mouse).await?,
+                    _ => {}
                 }
             }
 
             self.handle_pending_diff_request().await?;
+            self.sync_commit_detail().await?;
             self.handle_direct_branch_switch_request().await?;
-            self.handle_pending_hunk_stage().await?;
+            self.handle_pending_staging_operation().await?;
+            self.handle_pending_staging_diff().await?;
+            self.handle_pending_graph_page().await?;
+            self.handle_pending_git_log_page().await?;
+            self.handle_pending_blame_request().await?;
+            self.handle_pending_copy_request().await?;
+            self.handle_pending_worktree_action().await?;
+            self.handle_pending_branch_action().await?;
+            if let Some(dir) = self.pending_shell.take() {
+                self.run_shell(terminal, &dir).await?;
+            }
+            self.handle_pending_remote_operation().await?;
+            self.handle_pending_review_request().await?;
+            self.handle_pending_query_action().await?;
+            self.poll_background_tasks().await?;
+            self.state.write().await.expire_notifications();
+            self.handle_pending_tag_creation().await?;
+            self.poll_tag_note_generation().await?;
+            self.handle_pending_revert_request().await?;
+            self.poll_revert_message_generation().await?;
+            self.poll_ai_commit_generation().await?;
+            self.handle_auto_refresh().await?;
+            self.handle_file_changes().await?;
+            self.handle_config_reload().await?;
+            self.handle_scheduled_backup();
 
             if self.should_quit {
                 break;
@@ -164,11 +329,21 @@ impl TuiUnifiedApp {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default)]
 pub struct LayoutResult {
     pub sidebar: ratatui::layout::Rect,
     pub content: ratatui::layout::Rect,
     pub detail: ratatui::layout::Rect,
     pub status_bar: ratatui::layout::Rect,
+    /// 窄终端下切换面板的标签栏，其他布局中为空
+    pub tab_bar: ratatui::layout::Rect,
+}
+
+impl LayoutResult {
+    /// 是否为一次只显示一个面板的窄终端布局
+    pub fn is_compact(&self) -> bool {
+        self.tab_bar.height > 0
+    }
 }
 
 #[cfg(test)]
@@ -1,12 +1,51 @@
-// Async manager - placeholder implementations
+// Async manager - 后台任务及其进度
 
 use std::collections::HashMap;
 use tokio::sync::mpsc;
 
+/// 后台任务上报给界面的事件
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskEvent {
+    Progress {
+        name: String,
+        /// 0.0 到 1.0，无法估计时为 None
+        ratio: Option<f64>,
+        message: String,
+    },
+    Finished {
+        name: String,
+        result: Result<String, String>,
+    },
+}
+
+/// 任务内部用来上报进度和结果
+#[derive(Debug, Clone)]
+pub struct TaskReporter {
+    name: String,
+    sender: mpsc::UnboundedSender<TaskEvent>,
+}
+
+impl TaskReporter {
+    pub fn progress(&self, ratio: Option<f64>, message: impl Into<String>) {
+        let _ = self.sender.send(TaskEvent::Progress {
+            name: self.name.clone(),
+            ratio,
+            message: message.into(),
+        });
+    }
+
+    pub fn finish(&self, result: Result<String, String>) {
+        let _ = self.sender.send(TaskEvent::Finished {
+            name: self.name.clone(),
+            result,
+        });
+    }
+}
+
 pub struct AsyncTaskManager {
     tasks: HashMap<String, tokio::task::JoinHandle<()>>,
-    _task_sender: mpsc::UnboundedSender<String>, // 保留用于未来功能
-    _task_receiver: Option<mpsc::UnboundedReceiver<String>>, // 保留用于未来功能
+    event_sender: mpsc::UnboundedSender<TaskEvent>,
+    event_receiver: mpsc::UnboundedReceiver<TaskEvent>,
 }
 
 impl Default for AsyncTaskManager {
@@ -17,11 +56,11 @@ impl Default for AsyncTaskManager {
 
 impl AsyncTaskManager {
     pub fn new() -> Self {
-        let (sender, receiver) = mpsc::unbounded_channel();
+        let (event_sender, event_receiver) = mpsc::unbounded_channel();
         Self {
             tasks: HashMap::new(),
-            _task_sender: sender,
-            _task_receiver: Some(receiver),
+            event_sender,
+            event_receiver,
         }
     }
 
@@ -33,6 +72,39 @@ impl AsyncTaskManager {
         self.tasks.insert(name, handle);
     }
 
+    /// 启动会上报进度的任务，任务返回值作为 `TaskEvent::Finished` 的结果
+    pub fn spawn_reported<F, Fut>(&mut self, name: String, task: F)
+    where
+        F: FnOnce(TaskReporter) -> Fut,
+        Fut: std::future::Future<Output = Result<String, String>> + Send + 'static,
+    {
+        let reporter = TaskReporter {
+            name: name.clone(),
+            sender: self.event_sender.clone(),
+        };
+        let future = task(reporter.clone());
+        self.spawn_task(name, async move {
+            let result = future.await;
+            reporter.finish(result);
+        });
+    }
+
+    pub fn is_running(&self, name: &str) -> bool {
+        self.tasks
+            .get(name)
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// 取出目前收到的所有事件，并清理已结束的任务
+    pub fn poll_events(&mut self) -> Vec<TaskEvent> {
+        let mut events = Vec::new();
+        while let Ok(event) = self.event_receiver.try_recv() {
+            events.push(event);
+        }
+        self.tasks.retain(|_, handle| !handle.is_finished());
+        events
+    }
+
     pub async fn wait_for_task(&mut self, name: &str) -> Result<(), tokio::task::JoinError> {
         if let Some(handle) = self.tasks.remove(name) {
             handle.await
@@ -108,3 +180,34 @@ impl EventBus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reported_task_events() {
+        let mut manager = AsyncTaskManager::new();
+        manager.spawn_reported("fetch".to_string(), |reporter| async move {
+            reporter.progress(Some(0.5), "Receiving objects");
+            Ok("done".to_string())
+        });
+        manager.wait_for_task("fetch").await.unwrap();
+
+        assert_eq!(
+            manager.poll_events(),
+            vec![
+                TaskEvent::Progress {
+                    name: "fetch".to_string(),
+                    ratio: Some(0.5),
+                    message: "Receiving objects".to_string(),
+                },
+                TaskEvent::Finished {
+                    name: "fetch".to_string(),
+                    result: Ok("done".to_string()),
+                },
+            ]
+        );
+        assert!(!manager.is_running("fetch"));
+    }
+}
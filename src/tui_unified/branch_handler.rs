@@ -0,0 +1,148 @@
+use crate::tui_unified::{
+    git::interface::{AsyncGitImpl, GitRepositoryAPI},
+    state::app_state::{BranchAction, NotificationLevel, ViewType},
+    Result,
+};
+
+/// 解析新建分支的输入 `名称 [基准]`，分支名不能包含空白
+pub(crate) fn parse_new_branch(input: &str) -> Option<BranchAction> {
+    let mut parts = input.split_whitespace();
+    let name = parts.next()?.to_string();
+    let base = parts.next().map(str::to_string);
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(BranchAction::Create { name, base })
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 执行不需要确认的分支操作，确认框打开时等待
+    pub(crate) async fn handle_pending_branch_action(&mut self) -> Result<()> {
+        let action = {
+            let state = self.state.read().await;
+            if state.is_modal_active() {
+                return Ok(());
+            }
+            state.take_branch_action(false)
+        };
+
+        match action {
+            Some(action) => self.execute_branch_action(action).await,
+            None => Ok(()),
+        }
+    }
+
+    /// 执行确认后的分支操作
+    pub(crate) async fn confirm_branch_action(&mut self) -> Result<()> {
+        let action = {
+            let mut state = self.state.write().await;
+            state.hide_modal();
+            state.take_branch_action(true)
+        };
+
+        match action {
+            Some(action) => self.execute_branch_action(action).await,
+            None => Ok(()),
+        }
+    }
+
+    /// 提交新建分支输入框的内容
+    pub(crate) async fn create_branch_from_input(&mut self, input: &str) -> Result<()> {
+        match parse_new_branch(input) {
+            Some(action) => self.execute_branch_action(action).await,
+            None => {
+                self.state.write().await.add_notification(
+                    "Expected a branch name, optionally followed by a base".to_string(),
+                    NotificationLevel::Warning,
+                );
+                Ok(())
+            }
+        }
+    }
+
+    async fn execute_branch_action(&mut self, action: BranchAction) -> Result<()> {
+        let git = AsyncGitImpl::new(std::env::current_dir()?);
+        let result = match &action {
+            BranchAction::Create { name, base } => git.create_branch(name, base.as_deref()).await,
+            BranchAction::Rename { from, to } => git.rename_branch(from, to).await,
+            BranchAction::Delete { name, force } => git.delete_branch(name, *force).await,
+        };
+
+        if let Err(e) = result {
+            let error = e.to_string();
+            let mut state = self.state.write().await;
+            match action {
+                // 未合并的分支需要再次确认后强制删除
+                BranchAction::Delete { name, force: false }
+                    if error.contains("not fully merged") =>
+                {
+                    state.request_branch_action(BranchAction::Delete { name, force: true });
+                }
+                _ => {
+                    state.add_notification(error, NotificationLevel::Error);
+                }
+            }
+            return Ok(());
+        }
+
+        let (message, select) = match &action {
+            BranchAction::Create { name, base } => (
+                match base {
+                    Some(base) => format!("Created branch '{}' from '{}'", name, base),
+                    None => format!("Created branch '{}'", name),
+                },
+                Some(name.clone()),
+            ),
+            BranchAction::Rename { from, to } => (
+                format!("Renamed branch '{}' to '{}'", from, to),
+                Some(to.clone()),
+            ),
+            BranchAction::Delete { name, .. } => (format!("Deleted branch '{}'", name), None),
+        };
+
+        // 分支列表变化后，右侧的分支提交缓存可能指向已不存在的分支
+        self.cached_branch_name = None;
+        self.cached_branch_commits.clear();
+        let _ = self.refresh_current_view(ViewType::Branches).await;
+        if matches!(action, BranchAction::Create { .. }) {
+            // 新建分支后已切换过去
+            let _ = self.refresh_current_view(ViewType::GitLog).await;
+        }
+
+        let mut state = self.state.write().await;
+        self.branches_view.refresh_branches(&state);
+        if let Some(name) = select {
+            if self.branches_view.select_branch(&name, &state) {
+                self.branches_view
+                    .update_selected_branch_in_state(&mut state);
+            }
+        }
+        state.add_notification(message, NotificationLevel::Success);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_new_branch() {
+        assert_eq!(
+            parse_new_branch("  feature/x  "),
+            Some(BranchAction::Create {
+                name: "feature/x".to_string(),
+                base: None,
+            })
+        );
+        assert_eq!(
+            parse_new_branch("hotfix v1.2.0"),
+            Some(BranchAction::Create {
+                name: "hotfix".to_string(),
+                base: Some("v1.2.0".to_string()),
+            })
+        );
+        assert_eq!(parse_new_branch("a b c"), None);
+        assert_eq!(parse_new_branch("   "), None);
+    }
+}
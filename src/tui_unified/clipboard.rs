@@ -0,0 +1,99 @@
+use anyhow::Context;
+
+use crate::tui_unified::{
+    state::{app_state::NotificationLevel, CopyRequest},
+    Result,
+};
+
+/// 提示信息中预览的最大字符数
+const PREVIEW_CHARS: usize = 40;
+
+/// 系统剪贴板
+///
+/// 在 Linux 上剪贴板内容由持有者进程提供，所以在应用运行期间保留同一个实例。
+#[derive(Default)]
+pub struct SystemClipboard {
+    inner: Option<arboard::Clipboard>,
+}
+
+impl SystemClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn copy(&mut self, text: &str) -> anyhow::Result<()> {
+        let clipboard = match &mut self.inner {
+            Some(clipboard) => clipboard,
+            None => self
+                .inner
+                .insert(arboard::Clipboard::new().context("Clipboard is not available")?),
+        };
+        clipboard
+            .set_text(text.to_string())
+            .context("Failed to write to clipboard")
+    }
+}
+
+/// 复制成功提示中显示的内容预览：第一行，过长时截断
+fn preview(text: &str) -> String {
+    let first_line = text.lines().next().unwrap_or_default();
+    let mut preview: String = first_line.chars().take(PREVIEW_CHARS).collect();
+    if first_line.chars().count() > PREVIEW_CHARS || text.lines().nth(1).is_some() {
+        preview.push('…');
+    }
+    preview
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 处理视图发出的复制请求
+    pub(crate) async fn handle_pending_copy_request(&mut self) -> Result<()> {
+        let request = self.state.read().await.take_copy_request();
+        let (label, text) = match request {
+            Some(CopyRequest::Text { label, text }) => (label, text),
+            Some(CopyRequest::CommitMessage(hash)) => {
+                let repo_path = std::env::current_dir()?;
+                let git = crate::tui_unified::git::interface::AsyncGitImpl::new(repo_path);
+                match git.get_commit_message(&hash).await {
+                    Ok(message) => ("commit message".to_string(), message),
+                    Err(e) => {
+                        let mut state = self.state.write().await;
+                        state.add_notification(
+                            format!("Failed to read commit message: {}", e),
+                            NotificationLevel::Error,
+                        );
+                        return Ok(());
+                    }
+                }
+            }
+            None => return Ok(()),
+        };
+
+        self.copy_to_clipboard(&label, &text).await;
+        Ok(())
+    }
+
+    /// 复制文本并显示结果提示
+    pub(crate) async fn copy_to_clipboard(&mut self, label: &str, text: &str) {
+        let result = self.clipboard.copy(text);
+        let mut state = self.state.write().await;
+        match result {
+            Ok(()) => state.add_notification(
+                format!("Copied {}: {}", label, preview(text)),
+                NotificationLevel::Success,
+            ),
+            Err(e) => state.add_notification(format!("{:#}", e), NotificationLevel::Error),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview() {
+        assert_eq!(preview("abc1234"), "abc1234");
+        assert_eq!(preview("feat: x\n\nbody"), "feat: x…");
+        assert_eq!(preview(&"a".repeat(50)), format!("{}…", "a".repeat(40)));
+    }
+}
@@ -0,0 +1,401 @@
+use tokio::task::JoinHandle;
+
+use crate::core::ai::agents::{AgentTask, TaskType};
+use crate::tui_unified::{
+    focus::FocusPanel,
+    state::app_state::{CommitAction, ModalType, NotificationLevel, ViewType},
+    Result,
+};
+
+/// 预计影响中列出的文件数上限
+const MAX_IMPACT_FILES: usize = 5;
+
+/// 撤销确认框打开期间在后台生成的 AI 撤销说明
+pub(crate) struct RevertMessageGeneration {
+    hash: String,
+    task: JoinHandle<anyhow::Result<String>>,
+}
+
+/// 执行 git 命令并返回标准输出，失败时返回标准错误
+async fn git_output(args: &[&str]) -> anyhow::Result<String> {
+    let output = tokio::process::Command::new("git")
+        .args(args)
+        .output()
+        .await?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// 提交的父提交数，合并提交大于 1
+async fn parent_count(hash: &str) -> anyhow::Result<usize> {
+    let output = git_output(&["rev-list", "--parents", "-n", "1", hash]).await?;
+    Ok(output.split_whitespace().count().saturating_sub(1))
+}
+
+/// 与 `git revert --no-edit` 相同的默认撤销说明
+fn default_revert_message(subject: &str, hash: &str) -> String {
+    format!("Revert \"{}\"\n\nThis reverts commit {}.", subject, hash)
+}
+
+/// 确认框中展示的撤销影响
+fn format_revert_impact(
+    shortstat: &str,
+    files: &[&str],
+    later_commits: usize,
+    on_current_branch: bool,
+    is_merge: bool,
+) -> String {
+    let mut lines = vec![shortstat.trim().to_string()];
+
+    let mut listed = files
+        .iter()
+        .take(MAX_IMPACT_FILES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if files.len() > MAX_IMPACT_FILES {
+        listed.push_str(&format!(" (+{} more)", files.len() - MAX_IMPACT_FILES));
+    }
+    lines.push(format!("Files: {}", listed));
+
+    if later_commits > 0 {
+        lines.push(format!(
+            "{} later commit(s) touched these files, conflicts are possible",
+            later_commits
+        ));
+    } else {
+        lines.push("No later commits touched these files".to_string());
+    }
+    if is_merge {
+        lines.push("Merge commit: changes are reverted against its first parent".to_string());
+    }
+    if !on_current_branch {
+        lines.push("WARNING: this commit is not on the current branch".to_string());
+    }
+    lines.join("\n")
+}
+
+/// 预测撤销提交的影响：改动范围、之后改过同一文件的提交，以及提交是否在当前分支上
+async fn revert_impact(hash: &str, is_merge: bool) -> anyhow::Result<String> {
+    let mut show = vec!["show", "--format="];
+    if is_merge {
+        show.push("--first-parent");
+    }
+
+    let shortstat = git_output(&[show.as_slice(), &["--shortstat", hash]].concat()).await?;
+    let names = git_output(&[show.as_slice(), &["--name-only", hash]].concat()).await?;
+    let files: Vec<&str> = names.lines().filter(|line| !line.is_empty()).collect();
+
+    let range = format!("{}..HEAD", hash);
+    let mut rev_list = vec!["rev-list", "--count", range.as_str(), "--"];
+    rev_list.extend(files.iter().copied());
+    let later_commits = git_output(&rev_list)
+        .await
+        .ok()
+        .and_then(|count| count.trim().parse().ok())
+        .unwrap_or(0);
+
+    let on_current_branch = tokio::process::Command::new("git")
+        .args(["merge-base", "--is-ancestor", hash, "HEAD"])
+        .status()
+        .await
+        .is_ok_and(|status| status.success());
+
+    Ok(format_revert_impact(
+        &shortstat,
+        &files,
+        later_commits,
+        on_current_branch,
+        is_merge,
+    ))
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 执行确认后的提交操作
+    pub(crate) async fn confirm_commit_action(&mut self) -> Result<()> {
+        let action = {
+            let mut state = self.state.write().await;
+            state.hide_modal();
+            state.take_commit_action()
+        };
+        if let Some(generation) = self.revert_message.take() {
+            generation.task.abort();
+        }
+
+        match action {
+            Some(action) => self.execute_commit_action(action).await,
+            None => Ok(()),
+        }
+    }
+
+    /// 打开撤销确认框，并在后台生成 AI 撤销说明
+    pub(crate) async fn handle_pending_revert_request(&mut self) -> Result<()> {
+        let Some(hash) = self.state.read().await.take_revert_request() else {
+            return Ok(());
+        };
+
+        let prepared = async {
+            let parents = parent_count(&hash).await?;
+            if parents == 0 {
+                anyhow::bail!("Cannot revert the root commit");
+            }
+            let subject = git_output(&["log", "-1", "--format=%s", &hash]).await?;
+            let impact = revert_impact(&hash, parents > 1).await?;
+            // 撤销的改动即提交的反向 diff
+            let parent = format!("{}^", hash);
+            let diff = git_output(&["diff", &hash, &parent]).await?;
+            Ok((subject.trim().to_string(), impact, diff))
+        };
+        let (subject, impact, diff) = match prepared.await {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                self.state
+                    .write()
+                    .await
+                    .add_notification(format!("{:#}", e), NotificationLevel::Error);
+                return Ok(());
+            }
+        };
+
+        {
+            let mut state = self.state.write().await;
+            state.request_commit_action(CommitAction::Revert {
+                message: default_revert_message(&subject, &hash),
+                hash: hash.clone(),
+                impact,
+            });
+            if let Some(modal) = state.modal.as_mut() {
+                modal
+                    .content
+                    .push_str("\n\nGenerating AI revert message...");
+            }
+        }
+
+        self.start_revert_message_generation(hash, diff).await;
+        Ok(())
+    }
+
+    /// 在后台用 CommitAgent 根据反向 diff 生成撤销说明
+    async fn start_revert_message_generation(&mut self, hash: String, diff: String) {
+        if let Some(generation) = self.revert_message.take() {
+            generation.task.abort();
+        }
+        self.ensure_agent_manager();
+        let Some(manager) = self.agent_manager.as_mut() else {
+            return;
+        };
+
+        let agent = match Self::build_agent_context() {
+            Ok(context) => {
+                manager.update_context(context);
+                manager.get_or_create_agent("commit").await
+            }
+            Err(e) => Err(e),
+        };
+        let agent = match agent {
+            Ok(agent) => agent,
+            Err(e) => {
+                self.finish_revert_message(&hash, Err(e.to_string())).await;
+                return;
+            }
+        };
+
+        let context = manager.context().clone();
+        let reverted = hash.clone();
+        let task = tokio::spawn(async move {
+            let task = AgentTask::new(TaskType::GenerateCommit, diff);
+            let result = agent.execute(task, &context).await?;
+            if !result.success || result.content.trim().is_empty() {
+                anyhow::bail!("No message returned");
+            }
+            Ok(format!(
+                "{}\n\nThis reverts commit {}.",
+                result.content.trim(),
+                reverted
+            ))
+        });
+        self.revert_message = Some(RevertMessageGeneration { hash, task });
+    }
+
+    /// 生成完成后把撤销说明填入确认框
+    pub(crate) async fn poll_revert_message_generation(&mut self) -> Result<()> {
+        if !self
+            .revert_message
+            .as_ref()
+            .is_some_and(|generation| generation.task.is_finished())
+        {
+            return Ok(());
+        }
+
+        if let Some(generation) = self.revert_message.take() {
+            let result = match generation.task.await {
+                Ok(result) => result.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            self.finish_revert_message(&generation.hash, result).await;
+        }
+        Ok(())
+    }
+
+    /// 确认框仍在等待同一提交时更新说明，生成失败时保留默认说明
+    async fn finish_revert_message(
+        &mut self,
+        hash: &str,
+        result: std::result::Result<String, String>,
+    ) {
+        let mut state = self.state.write().await;
+        if !state
+            .modal
+            .as_ref()
+            .is_some_and(|modal| modal.modal_type == ModalType::CommitAction)
+        {
+            return;
+        }
+        let Some(CommitAction::Revert {
+            hash: pending,
+            message,
+            impact,
+        }) = state.take_commit_action()
+        else {
+            return;
+        };
+        if pending != hash {
+            return;
+        }
+
+        let (message, note) = match result {
+            Ok(generated) => (generated, None),
+            Err(e) => (message, Some(format!("AI message unavailable: {}", e))),
+        };
+        state.request_commit_action(CommitAction::Revert {
+            hash: pending,
+            message,
+            impact,
+        });
+        if let (Some(note), Some(modal)) = (note, state.modal.as_mut()) {
+            modal.content.push_str(&format!("\n\n{}", note));
+        }
+    }
+
+    async fn execute_commit_action(&mut self, action: CommitAction) -> Result<()> {
+        let (args, name) = match &action {
+            CommitAction::CherryPick(hashes) => {
+                let mut args = vec!["cherry-pick".to_string()];
+                args.extend(hashes.iter().cloned());
+                (args, "Cherry-pick")
+            }
+            CommitAction::Revert { hash, .. } => {
+                let mut args = vec!["revert".to_string(), "--no-edit".to_string()];
+                if parent_count(hash).await.unwrap_or(1) > 1 {
+                    args.extend(["-m".to_string(), "1".to_string()]);
+                }
+                args.push(hash.clone());
+                (args, "Revert")
+            }
+        };
+
+        let output = tokio::process::Command::new("git")
+            .args(&args)
+            .output()
+            .await?;
+
+        if output.status.success() {
+            let message = match &action {
+                CommitAction::CherryPick(hashes) => {
+                    format!("Cherry-picked {} commit(s)", hashes.len())
+                }
+                CommitAction::Revert { hash, message, .. } => {
+                    // 用生成的说明替换 git 的默认说明
+                    if let Err(e) = git_output(&["commit", "--amend", "-m", message]).await {
+                        self.state.write().await.add_notification(
+                            format!("Reverted, but failed to set the message: {}", e),
+                            NotificationLevel::Warning,
+                        );
+                    }
+                    format!("Reverted {}", hash.chars().take(8).collect::<String>())
+                }
+            };
+            self.state.write().await.clear_multi_selection();
+            self.refresh_after_commit_action().await;
+            self.state
+                .write()
+                .await
+                .add_notification(message, NotificationLevel::Success);
+            return Ok(());
+        }
+
+        // 停在冲突上时交给暂存视图处理，解决并暂存后再继续
+        let _ = self.refresh_staging().await;
+        let mut state = self.state.write().await;
+        if state.repo_state.has_conflicts() {
+            if let CommitAction::Revert { message, .. } = &action {
+                // 让 `git revert --continue` 使用生成的说明
+                if let Ok(path) = git_output(&["rev-parse", "--git-path", "MERGE_MSG"]).await {
+                    let _ = tokio::fs::write(path.trim(), message).await;
+                }
+            }
+            let conflicts = state.repo_state.status.conflicts.len();
+            state.clear_multi_selection();
+            state.set_current_view(ViewType::Staging);
+            self.focus_manager.set_focus(FocusPanel::Content);
+            state.add_notification(
+                format!(
+                    "{} stopped on conflicts in {} file(s). Resolve and stage them, then run `git {} --continue`",
+                    name, conflicts, args[0]
+                ),
+                NotificationLevel::Warning,
+            );
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            state.add_notification(
+                format!("git {} failed: {}", args[0], error.trim()),
+                NotificationLevel::Error,
+            );
+        }
+        Ok(())
+    }
+
+    /// 提交操作改变了当前分支，刷新分支和日志；日志按分支过滤时保留原来的列表
+    async fn refresh_after_commit_action(&mut self) {
+        let _ = self.refresh_current_view(ViewType::Branches).await;
+        let _ = self.refresh_staging().await;
+        if self.git_log_view.branch_filter().is_none() {
+            let _ = self.refresh_current_view(ViewType::GitLog).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_revert_impact() {
+        let files = ["a.rs", "b.rs", "c.rs", "d.rs", "e.rs", "f.rs", "g.rs"];
+        let impact = format_revert_impact(
+            " 7 files changed, 10 insertions(+), 2 deletions(-)\n",
+            &files,
+            3,
+            false,
+            false,
+        );
+        assert_eq!(
+            impact,
+            "7 files changed, 10 insertions(+), 2 deletions(-)\n\
+             Files: a.rs, b.rs, c.rs, d.rs, e.rs (+2 more)\n\
+             3 later commit(s) touched these files, conflicts are possible\n\
+             WARNING: this commit is not on the current branch"
+        );
+
+        let impact = format_revert_impact("1 file changed", &["a.rs"], 0, true, true);
+        assert_eq!(
+            impact,
+            "1 file changed\n\
+             Files: a.rs\n\
+             No later commits touched these files\n\
+             Merge commit: changes are reverted against its first parent"
+        );
+    }
+}
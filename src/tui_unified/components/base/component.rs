@@ -1,9 +1,19 @@
 // 基础组件trait
 use super::events::{EventResult, StateChange};
+use crate::report::ExportSection;
 use crate::tui_unified::state::AppState;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{layout::Rect, Frame};
 
+/// 计算鼠标位置落在带边框区域内的第几行（从 0 开始），不在内部区域时返回 None
+pub fn bordered_row_at(area: Rect, column: u16, row: u16) -> Option<usize> {
+    let inside = column > area.x
+        && column < area.x + area.width.saturating_sub(1)
+        && row > area.y
+        && row < area.y + area.height.saturating_sub(1);
+    inside.then(|| (row - area.y - 1) as usize)
+}
+
 /// 基础组件trait，所有TUI组件都应该实现这个trait
 pub trait Component {
     /// 组件名称，用于调试和识别
@@ -18,6 +28,12 @@ pub trait Component {
         EventResult::NotHandled
     }
 
+    /// 处理鼠标事件，坐标为终端绝对坐标，不在组件区域内时应返回 NotHandled
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        _ = (mouse, state);
+        EventResult::NotHandled
+    }
+
     /// 组件是否获得焦点
     fn is_focused(&self) -> bool {
         false
@@ -133,6 +149,11 @@ pub trait ViewComponent: Component {
         _ = state;
         EventResult::NotHandled
     }
+
+    /// 导出视图当前显示的内容，没有可导出的内容时为空
+    fn export(&self) -> Vec<ExportSection> {
+        Vec::new()
+    }
 }
 
 /// 面板类型枚举
@@ -157,6 +178,12 @@ pub enum ViewType {
     QueryHistory,
     DiffViewer,
     Staging,
+    Graph,
+    Reflog,
+    Blame,
+    Worktrees,
+    Review,
+    Hotspots,
 }
 
 /// 组件工厂，用于创建各种组件实例
@@ -233,3 +260,21 @@ impl Default for ComponentRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bordered_row_at() {
+        let area = Rect::new(10, 5, 20, 6);
+
+        assert_eq!(bordered_row_at(area, 11, 6), Some(0));
+        assert_eq!(bordered_row_at(area, 28, 9), Some(3));
+        // 边框和区域外不算
+        assert_eq!(bordered_row_at(area, 10, 6), None);
+        assert_eq!(bordered_row_at(area, 11, 5), None);
+        assert_eq!(bordered_row_at(area, 11, 10), None);
+        assert_eq!(bordered_row_at(area, 40, 6), None);
+    }
+}
@@ -1,2 +1,574 @@
-// 占位符实现 - TODO: 实现具体功能
-pub struct DetailPanel;
+// 提交详情面板 - 显示选中提交的元信息和可折叠的变更文件树
+use std::collections::{BTreeMap, HashSet};
+
+use crate::tui_unified::{
+    components::base::{
+        component::{Component, PanelComponent, PanelType},
+        events::EventResult,
+    },
+    git::models::{CommitDetail, FileChangeStat},
+    state::AppState,
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+/// 文件树中的一行
+#[derive(Debug, Clone, PartialEq)]
+enum TreeRow {
+    /// 目录，只有一个子目录的目录链会合并显示，如 `src/tui`
+    Dir {
+        path: String,
+        name: String,
+        depth: usize,
+        additions: usize,
+        deletions: usize,
+        collapsed: bool,
+    },
+    /// 文件，`index` 指向 [`CommitDetail::files`]
+    File {
+        index: usize,
+        name: String,
+        depth: usize,
+    },
+}
+
+#[derive(Default)]
+struct DirNode {
+    dirs: BTreeMap<String, DirNode>,
+    files: Vec<(String, usize)>,
+}
+
+impl DirNode {
+    fn insert(&mut self, components: &[&str], index: usize) {
+        match components {
+            [name] => self.files.push((name.to_string(), index)),
+            [dir, rest @ ..] => self
+                .dirs
+                .entry(dir.to_string())
+                .or_default()
+                .insert(rest, index),
+            [] => {}
+        }
+    }
+
+    /// 目录下所有文件的新增和删除行数之和
+    fn stats(&self, files: &[FileChangeStat]) -> (usize, usize) {
+        let own = self.files.iter().fold((0, 0), |(a, d), (_, index)| {
+            let file = &files[*index];
+            (
+                a + file.additions.unwrap_or(0),
+                d + file.deletions.unwrap_or(0),
+            )
+        });
+        self.dirs.values().fold(own, |(a, d), dir| {
+            let (da, dd) = dir.stats(files);
+            (a + da, d + dd)
+        })
+    }
+
+    fn flatten(
+        &self,
+        prefix: &str,
+        depth: usize,
+        files: &[FileChangeStat],
+        collapsed: &HashSet<String>,
+        rows: &mut Vec<TreeRow>,
+    ) {
+        for (name, dir) in &self.dirs {
+            let mut name = name.clone();
+            let mut dir = dir;
+            while dir.files.is_empty() && dir.dirs.len() == 1 {
+                let Some((sub, next)) = dir.dirs.iter().next() else {
+                    break;
+                };
+                name = format!("{}/{}", name, sub);
+                dir = next;
+            }
+
+            let path = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", prefix, name)
+            };
+            let (additions, deletions) = dir.stats(files);
+            let is_collapsed = collapsed.contains(&path);
+            rows.push(TreeRow::Dir {
+                path: path.clone(),
+                name,
+                depth,
+                additions,
+                deletions,
+                collapsed: is_collapsed,
+            });
+            if !is_collapsed {
+                dir.flatten(&path, depth + 1, files, collapsed, rows);
+            }
+        }
+
+        for (name, index) in &self.files {
+            rows.push(TreeRow::File {
+                index: *index,
+                name: name.clone(),
+                depth,
+            });
+        }
+    }
+}
+
+/// 按路径构建文件树并展开为可显示的行
+fn build_rows(files: &[FileChangeStat], collapsed: &HashSet<String>) -> Vec<TreeRow> {
+    let mut root = DirNode::default();
+    for (index, file) in files.iter().enumerate() {
+        let components: Vec<&str> = file.path.split('/').collect();
+        root.insert(&components, index);
+    }
+
+    let mut rows = Vec::new();
+    root.flatten("", 0, files, collapsed, &mut rows);
+    rows
+}
+
+fn stat_spans(additions: Option<usize>, deletions: Option<usize>) -> Vec<Span<'static>> {
+    match (additions, deletions) {
+        (Some(additions), Some(deletions)) => vec![
+            Span::styled(
+                format!(" +{}", additions),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(format!(" −{}", deletions), Style::default().fg(Color::Red)),
+        ],
+        _ => vec![Span::styled(" bin", Style::default().fg(Color::DarkGray))],
+    }
+}
+
+/// 提交详情面板 - Enter 展开/折叠目录或打开文件的 diff
+pub struct DetailPanel {
+    /// 当前显示或正在加载的提交
+    hash: Option<String>,
+    detail: Option<CommitDetail>,
+    error: Option<String>,
+    collapsed: HashSet<String>,
+    rows: Vec<TreeRow>,
+    cursor: usize,
+    offset: usize,
+    /// 上次渲染的文件树区域，用于鼠标命中测试和翻页
+    tree_area: Rect,
+    focused: bool,
+}
+
+impl Default for DetailPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DetailPanel {
+    pub fn new() -> Self {
+        Self {
+            hash: None,
+            detail: None,
+            error: None,
+            collapsed: HashSet::new(),
+            rows: Vec::new(),
+            cursor: 0,
+            offset: 0,
+            tree_area: Rect::default(),
+            focused: false,
+        }
+    }
+
+    pub fn commit_hash(&self) -> Option<&str> {
+        self.hash.as_deref()
+    }
+
+    /// 切换到新的提交，详情加载完成前显示加载提示
+    pub fn set_loading(&mut self, hash: String) {
+        self.hash = Some(hash);
+        self.detail = None;
+        self.error = None;
+        self.collapsed.clear();
+        self.rows.clear();
+        self.cursor = 0;
+        self.offset = 0;
+    }
+
+    pub fn set_detail(&mut self, detail: CommitDetail) {
+        if self.hash.as_deref() != Some(detail.hash.as_str()) {
+            self.set_loading(detail.hash.clone());
+        }
+        self.rows = build_rows(&detail.files, &self.collapsed);
+        self.detail = Some(detail);
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    fn rebuild_rows(&mut self) {
+        if let Some(detail) = &self.detail {
+            self.rows = build_rows(&detail.files, &self.collapsed);
+            self.cursor = self.cursor.min(self.rows.len().saturating_sub(1));
+        }
+    }
+
+    fn move_to(&mut self, index: usize) {
+        self.cursor = index.min(self.rows.len().saturating_sub(1));
+    }
+
+    fn set_collapsed(&mut self, path: &str, collapsed: bool) {
+        if collapsed {
+            self.collapsed.insert(path.to_string());
+        } else {
+            self.collapsed.remove(path);
+        }
+        self.rebuild_rows();
+    }
+
+    /// Enter：目录展开/折叠，文件打开该提交中此文件的 diff
+    fn activate(&mut self, state: &mut AppState) {
+        match self.rows.get(self.cursor).cloned() {
+            Some(TreeRow::Dir {
+                path, collapsed, ..
+            }) => self.set_collapsed(&path, !collapsed),
+            Some(TreeRow::File { index, .. }) => {
+                if let Some(detail) = &self.detail {
+                    state.request_file_diff(detail.hash.clone(), detail.files[index].path.clone());
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn header_lines(detail: &CommitDetail) -> Vec<Line<'static>> {
+        let label = Style::default().fg(Color::DarkGray);
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("commit ", label),
+                Span::styled(
+                    detail.hash.chars().take(12).collect::<String>(),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Author ", label),
+                Span::raw(format!("{} <{}>", detail.author, detail.author_email)),
+            ]),
+            Line::from(vec![
+                Span::styled("Date   ", label),
+                Span::raw(detail.date.clone()),
+            ]),
+        ];
+        if !detail.refs.is_empty() {
+            lines.push(Line::from(vec![
+                Span::styled("Refs   ", label),
+                Span::styled(detail.refs.join(", "), Style::default().fg(Color::Cyan)),
+            ]));
+        }
+
+        lines.push(Line::raw(""));
+        lines.extend(
+            detail
+                .message
+                .lines()
+                .map(|line| Line::styled(line.to_string(), Style::default().fg(Color::White))),
+        );
+        lines.push(Line::raw(""));
+
+        let additions = detail.files.iter().filter_map(|f| f.additions).sum();
+        let deletions = detail.files.iter().filter_map(|f| f.deletions).sum();
+        let mut summary = vec![Span::styled(
+            format!("{} files changed", detail.files.len()),
+            label,
+        )];
+        summary.extend(stat_spans(Some(additions), Some(deletions)));
+        lines.push(Line::from(summary));
+        lines
+    }
+
+    fn row_item(&self, row: &TreeRow, files: &[FileChangeStat]) -> ListItem<'static> {
+        let line = match row {
+            TreeRow::Dir {
+                name,
+                depth,
+                additions,
+                deletions,
+                collapsed,
+                ..
+            } => {
+                let mut spans = vec![
+                    Span::raw("  ".repeat(*depth)),
+                    Span::styled(
+                        format!("{} {}/", if *collapsed { "▸" } else { "▾" }, name),
+                        Style::default()
+                            .fg(Color::Blue)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ];
+                spans.extend(stat_spans(Some(*additions), Some(*deletions)));
+                Line::from(spans)
+            }
+            TreeRow::File { index, name, depth } => {
+                let file = &files[*index];
+                let mut spans = vec![
+                    Span::raw("  ".repeat(*depth)),
+                    Span::raw(format!("  {}", name)),
+                ];
+                spans.extend(stat_spans(file.additions, file.deletions));
+                Line::from(spans)
+            }
+        };
+        ListItem::new(line)
+    }
+
+    fn render_tree(&mut self, frame: &mut Frame, area: Rect) {
+        self.tree_area = area;
+        let Some(detail) = &self.detail else {
+            return;
+        };
+
+        let height = area.height.saturating_sub(2).max(1) as usize;
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+
+        let end = (self.offset + height).min(self.rows.len());
+        let items: Vec<ListItem> = self.rows[self.offset..end]
+            .iter()
+            .map(|row| self.row_item(row, &detail.files))
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default().title("Files").borders(Borders::TOP))
+            .highlight_style(if self.focused {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            });
+
+        let mut list_state = ListState::default();
+        if end > self.offset {
+            list_state.select(Some(self.cursor - self.offset));
+        }
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+}
+
+impl Component for DetailPanel {
+    fn name(&self) -> &str {
+        "DetailPanel"
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, _state: &AppState) {
+        let border_style = if self.focused {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let block = Block::default()
+            .title("📋 Commit Details")
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let message = match (&self.hash, &self.detail, &self.error) {
+            (_, _, Some(error)) => Some(Line::styled(
+                format!("Failed to load commit: {}", error),
+                Style::default().fg(Color::Red),
+            )),
+            (None, _, _) => Some(Line::styled(
+                "Select a commit to see its details",
+                Style::default().fg(Color::DarkGray),
+            )),
+            (Some(_), None, _) => Some(Line::styled(
+                "Loading...",
+                Style::default().fg(Color::DarkGray),
+            )),
+            _ => None,
+        };
+        if let Some(message) = message {
+            self.tree_area = Rect::default();
+            frame.render_widget(Paragraph::new(message).wrap(Wrap { trim: true }), inner);
+            return;
+        }
+
+        let Some(detail) = &self.detail else {
+            return;
+        };
+        let header = Self::header_lines(detail);
+        // 提交信息最多占一半高度，剩余空间留给文件树
+        let header_height = (header.len() as u16).min(inner.height / 2);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(header_height), Constraint::Min(1)])
+            .split(inner);
+
+        frame.render_widget(Paragraph::new(header).wrap(Wrap { trim: false }), chunks[0]);
+        self.render_tree(frame, chunks[1]);
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
+        if self.detail.is_none() {
+            return EventResult::NotHandled;
+        }
+
+        let page = self.tree_area.height.saturating_sub(2).max(1) as usize;
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.move_to(self.cursor.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.move_to(self.cursor + 1),
+            KeyCode::PageUp => self.move_to(self.cursor.saturating_sub(page)),
+            KeyCode::PageDown => self.move_to(self.cursor + page),
+            KeyCode::Home | KeyCode::Char('g') => self.move_to(0),
+            KeyCode::End | KeyCode::Char('G') => self.move_to(self.rows.len()),
+            KeyCode::Enter | KeyCode::Char(' ') => self.activate(state),
+            KeyCode::Left | KeyCode::Char('h') => {
+                if let Some(TreeRow::Dir { path, .. }) = self.rows.get(self.cursor).cloned() {
+                    self.set_collapsed(&path, true);
+                }
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                if let Some(TreeRow::Dir { path, .. }) = self.rows.get(self.cursor).cloned() {
+                    self.set_collapsed(&path, false);
+                }
+            }
+            _ => return EventResult::NotHandled,
+        }
+
+        EventResult::Handled
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        if !self.tree_area.contains((mouse.column, mouse.row).into()) {
+            return EventResult::NotHandled;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // 文件树只有顶部边框，第一行是标题
+                if mouse.row > self.tree_area.y {
+                    let index = self.offset + (mouse.row - self.tree_area.y - 1) as usize;
+                    // 点击已选中的行等同于 Enter
+                    if index == self.cursor {
+                        self.activate(state);
+                    } else if index < self.rows.len() {
+                        self.move_to(index);
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp => self.move_to(self.cursor.saturating_sub(1)),
+            MouseEventKind::ScrollDown => self.move_to(self.cursor + 1),
+            _ => return EventResult::NotHandled,
+        }
+
+        EventResult::Handled
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn min_size(&self) -> (u16, u16) {
+        (30, 10)
+    }
+}
+
+impl PanelComponent for DetailPanel {
+    fn panel_type(&self) -> PanelType {
+        PanelType::Detail
+    }
+
+    fn supports_scroll(&self) -> bool {
+        true
+    }
+
+    fn scroll_position(&self) -> usize {
+        self.cursor
+    }
+
+    fn set_scroll_position(&mut self, position: usize) {
+        self.move_to(position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, additions: usize, deletions: usize) -> FileChangeStat {
+        FileChangeStat {
+            path: path.to_string(),
+            additions: Some(additions),
+            deletions: Some(deletions),
+        }
+    }
+
+    fn detail() -> CommitDetail {
+        CommitDetail {
+            hash: "abc123".to_string(),
+            author: "Alice".to_string(),
+            author_email: "alice@example.com".to_string(),
+            date: "2024-05-01 10:00".to_string(),
+            refs: vec![],
+            message: "feat: add x".to_string(),
+            files: vec![
+                file("src/tui/app.rs", 3, 1),
+                file("src/tui/view.rs", 2, 0),
+                file("README.md", 1, 1),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_build_rows_merges_single_child_dirs() {
+        let rows = build_rows(&detail().files, &HashSet::new());
+
+        assert_eq!(rows.len(), 4);
+        assert!(matches!(
+            &rows[0],
+            TreeRow::Dir { name, additions: 5, deletions: 1, .. } if name == "src/tui"
+        ));
+        assert!(matches!(&rows[1], TreeRow::File { name, depth: 1, .. } if name == "app.rs"));
+        assert!(matches!(&rows[3], TreeRow::File { name, depth: 0, .. } if name == "README.md"));
+    }
+
+    #[tokio::test]
+    async fn test_collapse_and_open_file_diff() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut panel = DetailPanel::new();
+        panel.set_detail(detail());
+
+        // Enter 折叠目录
+        panel.handle_key_event(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert_eq!(panel.rows.len(), 2);
+
+        // 在文件上按 Enter 请求该文件的 diff
+        panel.handle_key_event(KeyEvent::from(KeyCode::Char('j')), &mut state);
+        panel.handle_key_event(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert_eq!(state.get_pending_diff_commit(), Some("abc123".to_string()));
+        assert_eq!(
+            state.take_pending_diff_file(),
+            Some("README.md".to_string())
+        );
+    }
+}
@@ -1,12 +1,12 @@
 // 侧边栏面板组件实现
 use crate::tui_unified::{
     components::base::{
-        component::{Component, PanelComponent, PanelType},
+        component::{bordered_row_at, Component, PanelComponent, PanelType},
         events::EventResult,
     },
     state::AppState,
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
@@ -24,6 +24,8 @@ pub struct SidebarPanel {
     branches_focused: bool,
     selected_branch_index: usize,
     show_branches: bool,
+    /// 上次渲染的导航菜单区域，菜单未显示时为空
+    menu_area: Rect,
 }
 
 struct MenuItem {
@@ -67,6 +69,36 @@ impl SidebarPanel {
                 key: '5',
                 description: "Query history".to_string(),
             },
+            MenuItem {
+                label: "📝 Staging".to_string(),
+                key: '7',
+                description: "Stage and unstage changes".to_string(),
+            },
+            MenuItem {
+                label: "🌳 Graph".to_string(),
+                key: '8',
+                description: "Commit graph of all branches".to_string(),
+            },
+            MenuItem {
+                label: "⏪ Reflog".to_string(),
+                key: '9',
+                description: "Recover from mistakes via reflog".to_string(),
+            },
+            MenuItem {
+                label: "🗂️ Worktrees".to_string(),
+                key: '0',
+                description: "Manage git worktrees".to_string(),
+            },
+            MenuItem {
+                label: "🔍 AI Review".to_string(),
+                key: 'V',
+                description: "Review changes and jump to issues".to_string(),
+            },
+            MenuItem {
+                label: "🔥 Hotspots".to_string(),
+                key: 'H',
+                description: "Frequently changed complex files".to_string(),
+            },
         ];
 
         Self {
@@ -76,6 +108,7 @@ impl SidebarPanel {
             branches_focused: false,
             selected_branch_index: 0,
             show_branches: true, // 默认显示分支列表
+            menu_area: Rect::default(),
         }
     }
 
@@ -89,6 +122,12 @@ impl SidebarPanel {
             crate::tui_unified::state::app_state::ViewType::Stash => 3,
             crate::tui_unified::state::app_state::ViewType::QueryHistory => 4,
             crate::tui_unified::state::app_state::ViewType::Staging => 5,
+            crate::tui_unified::state::app_state::ViewType::Graph => 6,
+            crate::tui_unified::state::app_state::ViewType::Reflog => 7,
+            crate::tui_unified::state::app_state::ViewType::Blame => 5, // Blame 从 Staging 视图进入
+            crate::tui_unified::state::app_state::ViewType::Worktrees => 8,
+            crate::tui_unified::state::app_state::ViewType::Review => 9,
+            crate::tui_unified::state::app_state::ViewType::Hotspots => 10,
         };
 
         if new_index < self.menu_items.len() {
@@ -96,6 +135,26 @@ impl SidebarPanel {
         }
     }
 
+    /// 切换到菜单项对应的视图
+    fn activate_menu_item(index: usize, state: &mut AppState) {
+        use crate::tui_unified::state::app_state::ViewType;
+
+        match index {
+            0 => state.set_current_view(ViewType::GitLog),
+            1 => state.set_current_view(ViewType::Tags),
+            2 => state.set_current_view(ViewType::Remotes),
+            3 => state.set_current_view(ViewType::Stash),
+            4 => state.set_current_view(ViewType::QueryHistory),
+            5 => state.set_current_view(ViewType::Staging),
+            6 => state.set_current_view(ViewType::Graph),
+            7 => state.set_current_view(ViewType::Reflog),
+            8 => state.set_current_view(ViewType::Worktrees),
+            9 => state.set_current_view(ViewType::Review),
+            10 => state.set_current_view(ViewType::Hotspots),
+            _ => {}
+        }
+    }
+
     /// 渲染分支列表
     fn render_branches_list(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
         use ratatui::{
@@ -322,6 +381,12 @@ impl Component for SidebarPanel {
             self.render_branches_list(frame, branches_area, state);
         }
 
+        self.menu_area = if should_show_menu {
+            menu_area
+        } else {
+            Rect::default()
+        };
+
         // 渲染列表（菜单或分支列表）
         frame.render_widget(
             List::new(list_items).block(
@@ -334,6 +399,23 @@ impl Component for SidebarPanel {
         );
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return EventResult::NotHandled;
+        }
+
+        // 点击菜单项直接切换视图
+        match bordered_row_at(self.menu_area, mouse.column, mouse.row) {
+            Some(index) if index < self.menu_items.len() => {
+                self.branches_focused = false;
+                self.selected_index = index;
+                Self::activate_menu_item(index, state);
+                EventResult::Handled
+            }
+            _ => EventResult::NotHandled,
+        }
+    }
+
     fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
         use crossterm::event::KeyCode;
 
@@ -396,23 +478,7 @@ impl Component for SidebarPanel {
                     }
                 } else {
                     // 根据选中的菜单项切换视图
-                    match self.selected_index {
-                        0 => state.set_current_view(
-                            crate::tui_unified::state::app_state::ViewType::GitLog,
-                        ),
-                        1 => state
-                            .set_current_view(crate::tui_unified::state::app_state::ViewType::Tags),
-                        2 => state.set_current_view(
-                            crate::tui_unified::state::app_state::ViewType::Remotes,
-                        ),
-                        3 => state.set_current_view(
-                            crate::tui_unified::state::app_state::ViewType::Stash,
-                        ),
-                        4 => state.set_current_view(
-                            crate::tui_unified::state::app_state::ViewType::QueryHistory,
-                        ),
-                        _ => {}
-                    }
+                    Self::activate_menu_item(self.selected_index, state);
                 }
                 EventResult::Handled
             }
@@ -0,0 +1,435 @@
+// Git blame视图组件 - 逐行显示提交、作者和修改时间
+use crate::report::ExportSection;
+use crate::tui_unified::{
+    components::base::{
+        component::{bordered_row_at, Component, ViewComponent, ViewType},
+        events::EventResult,
+    },
+    git::models::BlameLine,
+    state::{AppState, CopyRequest},
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+/// 作者列宽度
+const AUTHOR_WIDTH: usize = 14;
+
+/// 按修改时间在文件中的相对新旧程度选择热度颜色，越新越暖
+fn heat_color(timestamp: i64, oldest: i64, newest: i64) -> Color {
+    if newest <= oldest {
+        return Color::Yellow;
+    }
+    let ratio = (timestamp - oldest) as f64 / (newest - oldest) as f64;
+    match ratio {
+        r if r >= 0.9 => Color::Red,
+        r if r >= 0.7 => Color::LightRed,
+        r if r >= 0.5 => Color::Yellow,
+        r if r >= 0.3 => Color::Green,
+        r if r >= 0.1 => Color::Cyan,
+        _ => Color::Blue,
+    }
+}
+
+/// 将时间戳格式化为简短的相对时间，如 `3d`
+fn format_age(timestamp: i64, now: i64) -> String {
+    let seconds = (now - timestamp).max(0);
+    match seconds {
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s if s < 86400 * 30 => format!("{}d", s / 86400),
+        s if s < 86400 * 365 => format!("{}mo", s / (86400 * 30)),
+        s => format!("{}y", s / (86400 * 365)),
+    }
+}
+
+/// Git blame视图 - 显示文件每一行最后一次修改的提交，Enter查看该提交的diff
+pub struct BlameView {
+    file_path: Option<String>,
+    lines: Vec<BlameLine>,
+    /// 文件中最早和最新的提交时间，用于热度着色
+    time_range: (i64, i64),
+    cursor: usize,
+    offset: usize,
+    /// 上次渲染时的窗口高度，用于翻页
+    page_height: usize,
+    /// 上次渲染的区域，用于鼠标命中测试
+    area: Rect,
+    focused: bool,
+}
+
+impl Default for BlameView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlameView {
+    pub fn new() -> Self {
+        Self {
+            file_path: None,
+            lines: Vec::new(),
+            time_range: (0, 0),
+            cursor: 0,
+            offset: 0,
+            page_height: 1,
+            area: Rect::default(),
+            focused: false,
+        }
+    }
+
+    pub fn file_path(&self) -> Option<&str> {
+        self.file_path.as_deref()
+    }
+
+    /// 设置 blame 内容；同一文件重新加载时保留光标位置
+    pub fn set_blame(&mut self, file_path: String, lines: Vec<BlameLine>) {
+        let committed = lines.iter().filter(|l| !l.is_uncommitted());
+        let oldest = committed.clone().map(|l| l.timestamp).min().unwrap_or(0);
+        let newest = committed.map(|l| l.timestamp).max().unwrap_or(0);
+        self.time_range = (oldest, newest);
+
+        if self.file_path.as_deref() != Some(file_path.as_str()) {
+            self.cursor = 0;
+            self.offset = 0;
+        }
+        self.file_path = Some(file_path);
+        self.lines = lines;
+        self.cursor = self.cursor.min(self.lines.len().saturating_sub(1));
+    }
+
+    pub fn selected_line(&self) -> Option<&BlameLine> {
+        self.lines.get(self.cursor)
+    }
+
+    fn move_to(&mut self, index: usize) {
+        self.cursor = index.min(self.lines.len().saturating_sub(1));
+    }
+
+    fn create_line_item(&self, index: usize, now: i64) -> ListItem<'static> {
+        let line = &self.lines[index];
+        // 同一提交的连续行只在第一行显示注解
+        let starts_group = index == 0 || self.lines[index - 1].hash != line.hash;
+
+        let annotation = if !starts_group {
+            Span::raw(" ".repeat(8 + 1 + AUTHOR_WIDTH + 1 + 4))
+        } else if line.is_uncommitted() {
+            Span::styled(
+                format!(
+                    "{:<8} {:<width$} {:>4}",
+                    "-------",
+                    "Uncommitted",
+                    "",
+                    width = AUTHOR_WIDTH
+                ),
+                Style::default().fg(Color::Magenta),
+            )
+        } else {
+            let short_hash: String = line.hash.chars().take(8).collect();
+            let author: String = line.author.chars().take(AUTHOR_WIDTH).collect();
+            Span::styled(
+                format!(
+                    "{:<8} {:<width$} {:>4}",
+                    short_hash,
+                    author,
+                    format_age(line.timestamp, now),
+                    width = AUTHOR_WIDTH
+                ),
+                Style::default().fg(heat_color(
+                    line.timestamp,
+                    self.time_range.0,
+                    self.time_range.1,
+                )),
+            )
+        };
+
+        ListItem::new(Line::from(vec![
+            annotation,
+            Span::styled(
+                format!(" {:>5} │ ", line.line_number),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(line.content.replace('\t', "    ")),
+        ]))
+    }
+}
+
+impl Component for BlameView {
+    fn name(&self) -> &str {
+        "BlameView"
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, _state: &AppState) {
+        let height = area.height.saturating_sub(2).max(1) as usize;
+        self.page_height = height;
+        self.area = area;
+
+        // 保证选中行在可见窗口内
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let end = (self.offset + height).min(self.lines.len());
+        let items: Vec<ListItem> = (self.offset..end)
+            .map(|index| self.create_line_item(index, now))
+            .collect();
+
+        let title = match (&self.file_path, self.selected_line()) {
+            (Some(path), Some(line)) if !line.is_uncommitted() => {
+                format!("🔍 Blame: {} — {}", path, line.summary)
+            }
+            (Some(path), _) => format!("🔍 Blame: {}", path),
+            (None, _) => "🔍 Blame (press 'b' on a file in Staging)".to_string(),
+        };
+
+        let border_style = if self.focused {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .highlight_style(if self.focused {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().bg(Color::DarkGray)
+            });
+
+        let mut list_state = ListState::default();
+        if end > self.offset {
+            list_state.select(Some(self.cursor - self.offset));
+        }
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.move_to(self.cursor.saturating_sub(1));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.move_to(self.cursor + 1);
+            }
+            KeyCode::PageUp => {
+                self.move_to(self.cursor.saturating_sub(self.page_height));
+            }
+            KeyCode::PageDown => {
+                self.move_to(self.cursor + self.page_height);
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.move_to(0);
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.move_to(self.lines.len());
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                // y 复制该行所属提交的哈希，Y 复制完整提交信息
+                return match self.selected_line() {
+                    Some(line) if line.is_uncommitted() => EventResult::NotHandled,
+                    Some(line) if key.code == KeyCode::Char('y') => {
+                        state.request_copy("commit hash", line.hash.clone());
+                        EventResult::Handled
+                    }
+                    Some(line) => {
+                        state.request_copy_of(CopyRequest::CommitMessage(line.hash.clone()));
+                        EventResult::Handled
+                    }
+                    None => EventResult::NotHandled,
+                };
+            }
+            KeyCode::Enter => {
+                // 请求显示该行所属提交的diff，未提交的行没有对应提交
+                return match self.selected_line() {
+                    Some(line) if !line.is_uncommitted() => {
+                        state.request_diff(line.hash.clone());
+                        EventResult::Handled
+                    }
+                    _ => EventResult::NotHandled,
+                };
+            }
+            _ => return EventResult::NotHandled,
+        }
+
+        EventResult::Handled
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, _state: &mut AppState) -> EventResult {
+        if !self.area.contains((mouse.column, mouse.row).into()) {
+            return EventResult::NotHandled;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(row) = bordered_row_at(self.area, mouse.column, mouse.row) {
+                    if self.offset + row < self.lines.len() {
+                        self.move_to(self.offset + row);
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp => self.move_to(self.cursor.saturating_sub(1)),
+            MouseEventKind::ScrollDown => self.move_to(self.cursor + 1),
+            _ => return EventResult::NotHandled,
+        }
+
+        EventResult::Handled
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn min_size(&self) -> (u16, u16) {
+        (60, 10)
+    }
+}
+
+impl ViewComponent for BlameView {
+    fn view_type(&self) -> ViewType {
+        ViewType::Blame
+    }
+
+    fn title(&self) -> String {
+        "Git Blame".to_string()
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    /// 跳转到光标之后第一行内容匹配的行，找不到时从头查找
+    fn search(&mut self, query: &str) -> EventResult {
+        let query = query.to_lowercase();
+        let matches = |line: &BlameLine| line.content.to_lowercase().contains(&query);
+        let found = (self.cursor + 1..self.lines.len())
+            .chain(0..=self.cursor.min(self.lines.len().saturating_sub(1)))
+            .find(|&index| matches(&self.lines[index]));
+
+        match found {
+            Some(index) => {
+                self.cursor = index;
+                EventResult::Handled
+            }
+            None => EventResult::NotHandled,
+        }
+    }
+
+    fn clear_search(&mut self) -> EventResult {
+        EventResult::Handled
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        (!self.lines.is_empty()).then_some(self.cursor)
+    }
+
+    fn set_selected_index(&mut self, index: Option<usize>) {
+        if let Some(index) = index {
+            self.move_to(index);
+        }
+    }
+
+    /// 导出整个文件的 blame，每行带提交、作者和日期
+    fn export(&self) -> Vec<ExportSection> {
+        let Some(file_path) = &self.file_path else {
+            return Vec::new();
+        };
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| {
+                let annotation = if line.is_uncommitted() {
+                    format!("{:<8} {:<14} {:<10}", "-", "Not committed", "")
+                } else {
+                    let date = chrono::DateTime::from_timestamp(line.timestamp, 0)
+                        .map(|date| date.format("%Y-%m-%d").to_string())
+                        .unwrap_or_default();
+                    format!(
+                        "{:<8} {:<14} {:<10}",
+                        &line.hash[..8.min(line.hash.len())],
+                        line.author.chars().take(AUTHOR_WIDTH).collect::<String>(),
+                        date
+                    )
+                };
+                format!("{} {:>5} │ {}", annotation, line.line_number, line.content)
+            })
+            .collect();
+        vec![ExportSection::code(file_path, "text", lines)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blame_line(hash: &str, timestamp: i64, line_number: usize, content: &str) -> BlameLine {
+        BlameLine {
+            hash: hash.to_string(),
+            author: "Alice".to_string(),
+            timestamp,
+            summary: "feat: x".to_string(),
+            line_number,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_heat_color() {
+        assert_eq!(heat_color(200, 100, 200), Color::Red);
+        assert_eq!(heat_color(100, 100, 200), Color::Blue);
+        assert_eq!(heat_color(155, 100, 200), Color::Yellow);
+        assert_eq!(heat_color(100, 100, 100), Color::Yellow);
+    }
+
+    #[tokio::test]
+    async fn test_enter_requests_diff_for_committed_lines_only() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = BlameView::new();
+        view.set_blame(
+            "src/lib.rs".to_string(),
+            vec![
+                blame_line("aaaa1111", 100, 1, "fn main() {"),
+                blame_line(&"0".repeat(40), 300, 2, "    todo!()"),
+                blame_line("bbbb2222", 200, 3, "}"),
+            ],
+        );
+        assert_eq!(view.time_range, (100, 200));
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert_eq!(
+            state.get_pending_diff_commit(),
+            Some("aaaa1111".to_string())
+        );
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('j')), &mut state);
+        let result = view.handle_key_event(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert!(matches!(result, EventResult::NotHandled));
+        assert_eq!(state.get_pending_diff_commit(), None);
+
+        view.search("}");
+        assert_eq!(view.selected_line().unwrap().line_number, 3);
+    }
+}
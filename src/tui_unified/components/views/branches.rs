@@ -1,4 +1,5 @@
 // 分支视图组件
+use crate::report::ExportSection;
 use crate::tui_unified::{
     components::{
         base::{
@@ -7,16 +8,20 @@ use crate::tui_unified::{
         },
         widgets::list::ListWidget,
     },
-    state::{git_state::Branch, AppState},
+    state::{
+        app_state::{BranchAction, InputPurpose, NotificationLevel},
+        git_state::Branch,
+        AppState,
+    },
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
     Frame,
 };
 
-/// 分支视图 - 显示所有分支
+/// 分支视图 - 显示所有分支，支持检出、新建、重命名和删除
 pub struct BranchesView {
     list_widget: ListWidget<Branch>,
     show_remotes: bool,
@@ -37,7 +42,14 @@ impl BranchesView {
             } else {
                 String::new()
             };
-            format!("{}{}{}", indicator, branch.name, upstream_info)
+            let mut tracking = String::new();
+            if branch.ahead_count > 0 {
+                tracking.push_str(&format!(" ↑{}", branch.ahead_count));
+            }
+            if branch.behind_count > 0 {
+                tracking.push_str(&format!(" ↓{}", branch.behind_count));
+            }
+            format!("{}{}{}{}", indicator, branch.name, upstream_info, tracking)
         });
 
         let style_fn = Box::new(
@@ -102,6 +114,14 @@ impl BranchesView {
         self.list_widget.set_items(branches);
     }
 
+    /// 按名称选中分支
+    pub fn select_branch(&mut self, name: &str, state: &AppState) -> bool {
+        if self.list_widget.len() != state.repo_state.branches.len() {
+            self.refresh_branches(state);
+        }
+        self.list_widget.select_where(|branch| branch.name == name)
+    }
+
     /// 通知应用状态当前选中的分支
     pub fn update_selected_branch_in_state(&self, state: &mut AppState) {
         if let Some(selected_branch) = self.selected_branch() {
@@ -159,13 +179,79 @@ impl Component for BranchesView {
                 }
                 EventResult::Handled
             }
+            KeyCode::Char('F') => {
+                state.request_remote_operation(
+                    crate::tui_unified::state::app_state::RemoteOperation::Fetch(None),
+                );
+                EventResult::Handled
+            }
+            KeyCode::Char('p') => {
+                // git pull 只更新当前分支
+                if self.selected_branch().is_some_and(|b| !b.is_current) {
+                    state.add_notification(
+                        "Pull only updates the current branch, switch to it first".to_string(),
+                        crate::tui_unified::state::app_state::NotificationLevel::Warning,
+                    );
+                } else {
+                    state.request_remote_operation(
+                        crate::tui_unified::state::app_state::RemoteOperation::Pull,
+                    );
+                }
+                EventResult::Handled
+            }
+            KeyCode::Char('U') => {
+                let branch = self.selected_branch().map(|b| b.name.clone());
+                state.request_push(branch.as_deref());
+                EventResult::Handled
+            }
+            KeyCode::Char('n') => {
+                state.request_input(
+                    InputPurpose::CreateBranch,
+                    "New Branch",
+                    "Branch name, optionally followed by a base branch, tag or commit (default: HEAD):",
+                );
+                EventResult::Handled
+            }
             KeyCode::Char('r') => {
-                // 刷新分支列表
-                self.refresh_branches(state);
+                match self.selected_branch() {
+                    Some(branch) if branch.is_remote => {
+                        state.add_notification(
+                            "Remote branches cannot be renamed here".to_string(),
+                            NotificationLevel::Warning,
+                        );
+                    }
+                    Some(branch) => {
+                        let name = branch.name.clone();
+                        state.request_input(
+                            InputPurpose::RenameBranch(name.clone()),
+                            "Rename Branch",
+                            &format!("New name for '{}':", name),
+                        );
+                        state.edit_input(|value| *value = name);
+                    }
+                    None => {}
+                }
                 EventResult::Handled
             }
-            // 移除了 'c' 键，因为现在它用于分支切换
             KeyCode::Char('d') => {
+                match self.selected_branch() {
+                    Some(branch) if branch.is_current || branch.is_remote => {
+                        state.add_notification(
+                            "Cannot delete the current branch or a remote branch".to_string(),
+                            NotificationLevel::Warning,
+                        );
+                    }
+                    Some(branch) => {
+                        state.request_branch_action(BranchAction::Delete {
+                            name: branch.name.clone(),
+                            force: false,
+                        });
+                    }
+                    None => {}
+                }
+                EventResult::Handled
+            }
+            KeyCode::Char('o') => {
                 // 显示选中分支的git diff
                 if let Some(selected_branch) = self.selected_branch() {
                     if let Some(commit_hash) = &selected_branch.last_commit {
@@ -202,6 +288,15 @@ impl Component for BranchesView {
         }
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        let old_selection = self.list_widget.selected_index();
+        let result = self.list_widget.handle_mouse_event(mouse, state);
+        if old_selection != self.list_widget.selected_index() {
+            self.update_selected_branch_in_state(state);
+        }
+        result
+    }
+
     fn is_focused(&self) -> bool {
         self.list_widget.is_focused()
     }
@@ -257,4 +352,66 @@ impl ViewComponent for BranchesView {
             }
         }
     }
+
+    fn export(&self) -> Vec<ExportSection> {
+        vec![ExportSection::list(
+            &self.title(),
+            self.list_widget.formatted_lines(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+
+    fn branch(name: &str, is_current: bool) -> Branch {
+        Branch {
+            name: name.to_string(),
+            full_name: format!("refs/heads/{}", name),
+            is_current,
+            is_remote: false,
+            upstream: None,
+            last_commit: None,
+            ahead_count: 0,
+            behind_count: 0,
+            last_updated: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_rename_requests() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        state.repo_state.branches = vec![branch("main", true), branch("feature", false)];
+        let mut view = BranchesView::new();
+        view.refresh_branches(&state);
+        view.set_focus(true);
+
+        // 当前分支不能删除
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('d')), &mut state);
+        assert!(state.take_branch_action(true).is_none());
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Down), &mut state);
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('d')), &mut state);
+        assert!(state.is_modal_active());
+        assert!(state.take_branch_action(false).is_none());
+        assert_eq!(
+            state.take_branch_action(true),
+            Some(BranchAction::Delete {
+                name: "feature".to_string(),
+                force: false,
+            })
+        );
+        state.hide_modal();
+
+        // 重命名时输入框预填当前名称
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('r')), &mut state);
+        assert_eq!(state.input_value().as_deref(), Some("feature"));
+        assert_eq!(
+            state.take_input().map(|prompt| prompt.purpose),
+            Some(InputPurpose::RenameBranch("feature".to_string()))
+        );
+    }
 }
@@ -1,15 +1,17 @@
 // Git 日志视图组件
+use crate::report::ExportSection;
 use crate::tui_unified::{
+    algorithms::VirtualScrollManager,
     components::{
         base::{
-            component::{Component, ViewComponent, ViewType},
+            component::{bordered_row_at, Component, ViewComponent, ViewType},
             events::EventResult,
         },
         widgets::list::ListWidget,
     },
-    state::{git_state::Commit, AppState},
+    state::{git_state::Commit, AppState, CommitAction, CopyRequest},
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -18,16 +20,35 @@ use ratatui::{
     Frame,
 };
 
-/// Git 日志视图 - 显示提交历史
+/// 每次加载的提交数
+pub const GIT_LOG_PAGE_SIZE: usize = 500;
+
+/// 最多同时保留的提交数，超过后丢弃离选中位置较远的一端
+const MAX_LOADED_COMMITS: usize = GIT_LOG_PAGE_SIZE * 10;
+
+/// 距离已加载窗口边缘多少行时预加载相邻页
+const PRELOAD_THRESHOLD: usize = 50;
+
+/// Git 日志视图 - 显示提交历史，支持标记多个提交后 cherry-pick，以及撤销选中的提交
+///
+/// 提交按 `git log --skip/--max-count` 分页加载，只保留选中位置附近的一段窗口，
+/// 渲染时只构建可见的行，适合有大量提交的仓库。
 pub struct GitLogView {
     list_widget: ListWidget<Commit>,
     show_details: bool,
-    commits: Vec<Commit>,
+    /// 已加载的提交窗口，`selected_index` 是窗口内的位置
+    commits: VirtualScrollManager<Commit>,
     list_state: ListState,
     focused: bool,
     selected_index: Option<usize>,
+    /// 窗口之后是否还有提交
+    has_more: bool,
+    /// 是否有分页请求尚未完成
+    loading: bool,
     // 新增：当前过滤的分支
     current_branch_filter: Option<String>,
+    /// 上次渲染的区域，用于鼠标命中测试
+    area: Rect,
 }
 
 impl Default for GitLogView {
@@ -74,16 +95,40 @@ impl GitLogView {
         Self {
             list_widget,
             show_details: false,
-            commits: Vec::new(),
+            commits: VirtualScrollManager::new(0).with_max_items(MAX_LOADED_COMMITS),
             list_state,
             focused: false,
             selected_index: None,
+            has_more: false,
+            loading: false,
             current_branch_filter: None,
+            area: Rect::default(),
+        }
+    }
+
+    /// 选中指定提交，超出范围时停在最后一条
+    fn select_commit_at(&mut self, index: usize) {
+        if !self.commits.items.is_empty() {
+            let index = index.min(self.commits.items.len() - 1);
+            self.selected_index = Some(index);
+            self.commits.ensure_visible(index);
         }
     }
 
     pub fn selected_commit(&self) -> Option<&Commit> {
-        self.selected_index.and_then(|idx| self.commits.get(idx))
+        self.selected_index
+            .and_then(|idx| self.commits.items.get(idx))
+    }
+
+    /// 按哈希在已加载的提交中选中，找不到时返回 false
+    pub fn select_commit(&mut self, hash: &str) -> bool {
+        match self.commits.items.iter().position(|c| c.hash == hash) {
+            Some(index) => {
+                self.select_commit_at(index);
+                true
+            }
+            None => false,
+        }
     }
 
     pub fn set_selected_index(&mut self, index: Option<usize>) {
@@ -96,6 +141,31 @@ impl GitLogView {
         self.update_title();
     }
 
+    /// 当前过滤的分支
+    pub fn branch_filter(&self) -> Option<&str> {
+        self.current_branch_filter.as_deref()
+    }
+
+    /// 要 cherry-pick 的提交：有标记时取所有标记的提交，否则取选中的提交
+    ///
+    /// 日志按从新到旧排列，返回时反转为从旧到新，保证按原始顺序应用。
+    fn cherry_pick_targets(&self, state: &AppState) -> Vec<String> {
+        let marked = &state.selected_items.multi_selection;
+        if marked.is_empty() {
+            return self
+                .selected_commit()
+                .map(|commit| vec![commit.hash.clone()])
+                .unwrap_or_default();
+        }
+        self.commits
+            .items
+            .iter()
+            .rev()
+            .filter(|commit| marked.contains(&commit.hash))
+            .map(|commit| commit.hash.clone())
+            .collect()
+    }
+
     /// 更新标题以反映当前分支过滤状态
     fn update_title(&mut self) {
         let title = if let Some(ref branch_name) = self.current_branch_filter {
@@ -106,11 +176,14 @@ impl GitLogView {
         self.list_widget.set_title(title);
     }
 
-    /// 更新commit列表数据
+    /// 更新commit列表数据（作为从头开始的第一页）
     pub fn update_commits(&mut self, commits: Vec<Commit>) {
         let has_commits = !commits.is_empty();
-        self.commits = commits;
-        self.list_widget.set_items(self.commits.clone());
+        self.list_widget.set_items(commits.clone());
+        self.commits.set_items(commits);
+        // 第一页的大小由调用方决定，是否还有更多提交由下一页的结果确定
+        self.has_more = has_commits;
+        self.loading = false;
 
         // 确保第一个项目被选中
         if has_commits {
@@ -124,6 +197,83 @@ impl GitLogView {
         }
     }
 
+    /// 已加载窗口第一条在完整历史中的位置
+    pub fn loaded_offset(&self) -> usize {
+        self.commits.offset
+    }
+
+    /// 已加载的提交数
+    pub fn loaded_len(&self) -> usize {
+        self.commits.items.len()
+    }
+
+    /// 加载从 `skip` 开始的一页：接在窗口末尾或补到窗口开头，选中的提交保持不变
+    pub fn load_page(&mut self, skip: usize, mut page: Vec<Commit>) {
+        self.loading = false;
+        let selected = self.selected_index.unwrap_or(0);
+
+        if skip < self.commits.offset {
+            page.truncate(self.commits.offset - skip);
+            let added = page.len();
+            if self.commits.prepend_page(page) > 0 {
+                self.has_more = true;
+            }
+            self.select_commit_at(selected + added);
+        } else if skip == self.commits.end() {
+            self.has_more = page.len() >= GIT_LOG_PAGE_SIZE;
+            let dropped = self.commits.append_page(page);
+            self.select_commit_at(selected.saturating_sub(dropped));
+        }
+    }
+
+    /// 页面加载失败时允许重新请求
+    pub fn mark_load_failed(&mut self) {
+        self.loading = false;
+    }
+
+    /// 选中位置接近已加载窗口的边缘时请求相邻的一页
+    fn request_more_if_needed(&mut self, state: &mut AppState) {
+        let Some(selected) = self.selected_index else {
+            return;
+        };
+        if self.loading {
+            return;
+        }
+        if self.has_more && selected + PRELOAD_THRESHOLD >= self.commits.items.len() {
+            self.loading = true;
+            state.request_git_log_page(self.commits.end());
+        } else if self.commits.offset > 0 && selected < PRELOAD_THRESHOLD {
+            self.loading = true;
+            state.request_git_log_page(self.commits.offset.saturating_sub(GIT_LOG_PAGE_SIZE));
+        }
+    }
+
+    /// 移动选中位置，还有未加载的提交时停在窗口边缘等待加载，否则首尾循环
+    fn move_selection(&mut self, delta: isize, state: &mut AppState) {
+        let Some(current) = self.selected_index else {
+            return;
+        };
+        let last = self.commits.items.len().saturating_sub(1);
+        let target = current as isize + delta;
+        let index = if target < 0 {
+            if self.commits.offset > 0 || delta < -1 {
+                0
+            } else {
+                last
+            }
+        } else if target as usize > last {
+            if self.has_more || delta > 1 {
+                last
+            } else {
+                0
+            }
+        } else {
+            target as usize
+        };
+        self.select_commit_at(index);
+        self.request_more_if_needed(state);
+    }
+
     pub fn toggle_details(&mut self) {
         self.show_details = !self.show_details;
         self.update_title();
@@ -141,8 +291,30 @@ impl GitLogView {
         }
     }
 
+    /// 签名列：✓ 有效，✗ 无效（红色）或未签名（灰色），? 无法校验
+    fn signature_span(commit: &Commit) -> Span<'static> {
+        use crate::git::SignatureStatus;
+
+        match commit.signature {
+            Some(status) => {
+                let color = match status {
+                    SignatureStatus::Good | SignatureStatus::UnknownValidity => Color::Green,
+                    SignatureStatus::CannotCheck => Color::Yellow,
+                    SignatureStatus::Unsigned => Color::DarkGray,
+                    _ => Color::Red,
+                };
+                Span::styled(format!("{} ", status.symbol()), Style::default().fg(color))
+            }
+            None => Span::raw("  "),
+        }
+    }
+
     /// 创建彩色的提交项显示（静态版本）
-    fn create_colored_commit_item_static(commit: &Commit, is_selected: bool) -> ListItem<'_> {
+    fn create_colored_commit_item_static(
+        commit: &Commit,
+        is_selected: bool,
+        is_marked: bool,
+    ) -> ListItem<'_> {
         // 获取短哈希
         let short_hash = &commit.hash[..8.min(commit.hash.len())];
 
@@ -172,6 +344,12 @@ impl GitLogView {
 
         // 使用多个 Span 创建彩色显示
         let content = Line::from(vec![
+            if is_marked {
+                Span::styled("● ", Style::default().fg(Color::Magenta))
+            } else {
+                Span::raw("  ")
+            },
+            Self::signature_span(commit),
             Span::styled(
                 format!("{} ", short_hash),
                 Style::default().fg(hash_color).add_modifier(Modifier::BOLD),
@@ -194,23 +372,40 @@ impl Component for GitLogView {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        self.area = area;
+
         // 确保提交列表是最新的
         if self.list_widget.len() != state.repo_state.commits.len() {
             self.refresh_commits(state);
         }
 
+        // 只构建视口内的行
+        self.commits.viewport_size = area.height.saturating_sub(2).max(1) as usize;
+        if let Some(selected) = self.selected_index {
+            self.commits.ensure_visible(selected);
+        }
+        let viewport_start = self.commits.viewport_start;
+        self.list_state.select(
+            self.selected_index
+                .map(|i| i.saturating_sub(viewport_start)),
+        );
+        *self.list_state.offset_mut() = 0;
+
         // 获取需要的值避免借用冲突
-        let commits = &self.commits;
         let selected_index = self.selected_index;
         let focused = self.focused;
+        let marked = &state.selected_items.multi_selection;
 
         // 创建彩色的列表项
-        let list_items: Vec<ListItem> = commits
+        let list_items: Vec<ListItem> = self
+            .commits
+            .get_visible_items()
             .iter()
             .enumerate()
             .map(|(i, commit)| {
-                let is_selected = Some(i) == selected_index;
-                Self::create_colored_commit_item_static(commit, is_selected)
+                let is_selected = Some(viewport_start + i) == selected_index;
+                let is_marked = marked.contains(&commit.hash);
+                Self::create_colored_commit_item_static(commit, is_selected, is_marked)
             })
             .collect();
 
@@ -221,8 +416,18 @@ impl Component for GitLogView {
             Style::default().fg(Color::White)
         };
 
-        // 标题
-        let title = format!("📊 Git Log ({} commits)", commits.len());
+        // 标题：已知的提交数，后面还有未加载的提交时加 "+"
+        let more = if self.has_more { "+" } else { "" };
+        let title = if marked.is_empty() {
+            format!("📊 Git Log ({}{} commits)", self.commits.end(), more)
+        } else {
+            format!(
+                "📊 Git Log ({}{} commits, {} marked)",
+                self.commits.end(),
+                more,
+                marked.len()
+            )
+        };
 
         // 创建列表
         let list = List::new(list_items)
@@ -269,35 +474,107 @@ impl Component for GitLogView {
                 state.request_git_pull();
                 EventResult::Handled
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if let Some(current) = self.selected_index {
-                    if current > 0 {
-                        self.selected_index = Some(current - 1);
-                        self.list_state.select(Some(current - 1));
-                    } else {
-                        let last = self.commits.len().saturating_sub(1);
-                        self.selected_index = Some(last);
-                        self.list_state.select(Some(last));
+            KeyCode::Char('y') => {
+                // 复制选中提交的哈希
+                match self.selected_commit() {
+                    Some(commit) => {
+                        state.request_copy("commit hash", commit.hash.clone());
+                        EventResult::Handled
                     }
+                    None => EventResult::NotHandled,
                 }
-                EventResult::Handled
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if let Some(current) = self.selected_index {
-                    if current < self.commits.len().saturating_sub(1) {
-                        self.selected_index = Some(current + 1);
-                        self.list_state.select(Some(current + 1));
-                    } else {
-                        self.selected_index = Some(0);
-                        self.list_state.select(Some(0));
+            KeyCode::Char(' ') => {
+                // 标记或取消标记选中的提交，然后移到下一条
+                match self.selected_commit().map(|commit| commit.hash.clone()) {
+                    Some(hash) => {
+                        state.toggle_multi_selection(hash);
+                        if let Some(current) = self.selected_index {
+                            self.select_commit_at(current + 1);
+                        }
+                        self.request_more_if_needed(state);
+                        EventResult::Handled
+                    }
+                    None => EventResult::NotHandled,
+                }
+            }
+            KeyCode::Char('C') => {
+                // cherry-pick 标记的提交（没有标记时为选中的提交）到当前分支
+                let hashes = self.cherry_pick_targets(state);
+                if hashes.is_empty() {
+                    EventResult::NotHandled
+                } else {
+                    state.request_commit_action(CommitAction::CherryPick(hashes));
+                    EventResult::Handled
+                }
+            }
+            KeyCode::Char('v') => {
+                // 撤销选中的提交，生成说明后确认
+                match self.selected_commit() {
+                    Some(commit) => {
+                        state.request_revert(commit.hash.clone());
+                        EventResult::Handled
                     }
+                    None => EventResult::NotHandled,
                 }
+            }
+            KeyCode::Char('Y') => {
+                // 复制选中提交的完整提交信息
+                match self.selected_commit() {
+                    Some(commit) => {
+                        state.request_copy_of(CopyRequest::CommitMessage(commit.hash.clone()));
+                        EventResult::Handled
+                    }
+                    None => EventResult::NotHandled,
+                }
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.move_selection(-1, state);
+                EventResult::Handled
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.move_selection(1, state);
+                EventResult::Handled
+            }
+            KeyCode::PageUp => {
+                self.move_selection(-(self.commits.viewport_size.max(1) as isize), state);
+                EventResult::Handled
+            }
+            KeyCode::PageDown => {
+                self.move_selection(self.commits.viewport_size.max(1) as isize, state);
                 EventResult::Handled
             }
             _ => EventResult::NotHandled,
         }
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        if !self.area.contains((mouse.column, mouse.row).into()) {
+            return EventResult::NotHandled;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(row) = bordered_row_at(self.area, mouse.column, mouse.row) {
+                    let index = self.commits.viewport_start + row;
+                    if index < self.commits.items.len() {
+                        self.select_commit_at(index);
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                self.select_commit_at(self.selected_index.unwrap_or(0).saturating_sub(1));
+                self.request_more_if_needed(state);
+            }
+            MouseEventKind::ScrollDown => {
+                self.select_commit_at(self.selected_index.map_or(0, |index| index + 1));
+                self.request_more_if_needed(state);
+            }
+            _ => return EventResult::NotHandled,
+        }
+        EventResult::Handled
+    }
+
     fn is_focused(&self) -> bool {
         self.focused
     }
@@ -354,6 +631,28 @@ impl ViewComponent for GitLogView {
             }
         }
     }
+
+    /// 导出当前显示的提交，附带改动统计
+    fn export(&self) -> Vec<ExportSection> {
+        let lines = self
+            .list_widget
+            .visible_items()
+            .into_iter()
+            .map(|commit| {
+                format!(
+                    "{} {} {} - {} ({} files, +{} / -{})",
+                    &commit.hash[..8.min(commit.hash.len())],
+                    commit.date.format("%Y-%m-%d %H:%M"),
+                    commit.message.lines().next().unwrap_or_default(),
+                    commit.author,
+                    commit.files_changed,
+                    commit.insertions,
+                    commit.deletions
+                )
+            })
+            .collect();
+        vec![ExportSection::list(&self.title(), lines)]
+    }
 }
 
 pub struct BranchesView;
@@ -361,3 +660,115 @@ pub struct TagsView;
 pub struct RemotesView;
 pub struct StashView;
 pub struct QueryHistoryView;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui_unified::state::SelectionMode;
+    use crossterm::event::KeyCode;
+
+    fn commit(hash: &str) -> Commit {
+        Commit {
+            hash: hash.to_string(),
+            short_hash: hash.to_string(),
+            author: "dev".to_string(),
+            author_email: "dev@example.com".to_string(),
+            committer: "dev".to_string(),
+            committer_email: "dev@example.com".to_string(),
+            date: chrono::Utc::now(),
+            message: format!("commit {}", hash),
+            subject: format!("commit {}", hash),
+            body: None,
+            parents: Vec::new(),
+            refs: Vec::new(),
+            files_changed: 0,
+            insertions: 0,
+            deletions: 0,
+            signature: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cherry_pick_marked_commits_oldest_first() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = GitLogView::new();
+        view.update_commits(vec![commit("ccc"), commit("bbb"), commit("aaa")]);
+
+        // 没有标记时只 cherry-pick 选中的提交
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('C')), &mut state);
+        assert_eq!(
+            state.take_commit_action(),
+            Some(CommitAction::CherryPick(vec!["ccc".to_string()]))
+        );
+        state.hide_modal();
+
+        // 标记 ccc 和 aaa，按从旧到新的顺序应用
+        view.handle_key_event(KeyEvent::from(KeyCode::Char(' ')), &mut state);
+        view.handle_key_event(KeyEvent::from(KeyCode::Down), &mut state);
+        view.handle_key_event(KeyEvent::from(KeyCode::Char(' ')), &mut state);
+        assert_eq!(state.selected_items.selection_mode, SelectionMode::Multiple);
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('C')), &mut state);
+        assert!(state.is_modal_active());
+        assert_eq!(
+            state.take_commit_action(),
+            Some(CommitAction::CherryPick(vec![
+                "aaa".to_string(),
+                "ccc".to_string()
+            ]))
+        );
+        state.hide_modal();
+
+        // v 请求撤销选中的提交
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('v')), &mut state);
+        assert_eq!(state.take_revert_request().as_deref(), Some("aaa"));
+    }
+
+    fn page(start: usize, len: usize) -> Vec<Commit> {
+        (start..start + len)
+            .map(|i| commit(&format!("{:08}", i)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_pages_load_near_window_edges() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = GitLogView::new();
+        view.update_commits(page(0, 100));
+
+        // 接近末尾时请求下一页，加载中不重复请求
+        view.select_commit_at(60);
+        view.handle_key_event(KeyEvent::from(KeyCode::Down), &mut state);
+        assert_eq!(state.take_git_log_page(), Some(100));
+        view.handle_key_event(KeyEvent::from(KeyCode::Down), &mut state);
+        assert_eq!(state.take_git_log_page(), None);
+
+        // 窗口超过上限后丢弃开头，选中的提交不变
+        let mut skip = 100;
+        while skip < MAX_LOADED_COMMITS + GIT_LOG_PAGE_SIZE {
+            view.select_commit_at(view.loaded_len() - 1);
+            let hash = view.selected_commit().unwrap().hash.clone();
+            view.load_page(skip, page(skip, GIT_LOG_PAGE_SIZE));
+            assert_eq!(view.selected_commit().unwrap().hash, hash);
+            skip += GIT_LOG_PAGE_SIZE;
+        }
+        assert_eq!(view.loaded_len(), MAX_LOADED_COMMITS);
+        assert!(view.loaded_offset() > 0);
+
+        // 回到窗口开头时向前补一页
+        let offset = view.loaded_offset();
+        view.select_commit_at(1);
+        view.handle_key_event(KeyEvent::from(KeyCode::Up), &mut state);
+        assert_eq!(state.take_git_log_page(), Some(offset - GIT_LOG_PAGE_SIZE));
+        view.load_page(
+            offset - GIT_LOG_PAGE_SIZE,
+            page(offset - GIT_LOG_PAGE_SIZE, GIT_LOG_PAGE_SIZE),
+        );
+        assert_eq!(view.loaded_offset(), offset - GIT_LOG_PAGE_SIZE);
+        assert_eq!(
+            view.selected_commit().unwrap().hash,
+            format!("{:08}", offset)
+        );
+    }
+}
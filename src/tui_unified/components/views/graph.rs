@@ -0,0 +1,511 @@
+// 提交图视图组件 - 以 Unicode DAG 形式展示分支、合并和引用
+use crate::report::ExportSection;
+use crate::tui_unified::{
+    algorithms::graph::{CommitGraph, GraphRow},
+    components::base::{
+        component::{bordered_row_at, Component, ViewComponent, ViewType},
+        events::EventResult,
+    },
+    git::models::GraphCommit,
+    state::{AppState, CopyRequest},
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+
+/// 每次加载的提交数
+pub const GRAPH_PAGE_SIZE: usize = 300;
+
+/// 距离末尾多少行时预加载下一页
+const PRELOAD_THRESHOLD: usize = 20;
+
+/// 提交图视图
+///
+/// 图形布局随分页增量计算，渲染时只构建可见窗口内的行，适合大型仓库。
+pub struct GraphView {
+    commits: Vec<GraphCommit>,
+    rows: Vec<GraphRow>,
+    graph: CommitGraph,
+    /// 过滤后可见的提交索引
+    visible: Vec<usize>,
+    filter: Option<String>,
+    /// 在 visible 中的选中位置
+    cursor: usize,
+    /// 可见窗口的起始位置
+    offset: usize,
+    /// 上次渲染时的窗口高度，用于翻页
+    page_height: usize,
+    /// 上次渲染的区域，用于鼠标命中测试
+    area: Rect,
+    focused: bool,
+    loaded: bool,
+    has_more: bool,
+    loading: bool,
+}
+
+impl Default for GraphView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphView {
+    pub fn new() -> Self {
+        Self {
+            commits: Vec::new(),
+            rows: Vec::new(),
+            graph: CommitGraph::new(),
+            visible: Vec::new(),
+            filter: None,
+            cursor: 0,
+            offset: 0,
+            page_height: 10,
+            area: Rect::default(),
+            focused: false,
+            loaded: false,
+            has_more: false,
+            loading: false,
+        }
+    }
+
+    /// 是否已加载过第一页
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    /// 清空已加载的提交，重新开始布局
+    pub fn reset(&mut self) {
+        self.commits.clear();
+        self.rows.clear();
+        self.graph = CommitGraph::new();
+        self.visible.clear();
+        self.cursor = 0;
+        self.offset = 0;
+        self.loaded = false;
+        self.has_more = false;
+        self.loading = false;
+    }
+
+    /// 追加一页提交（需按拓扑顺序）
+    pub fn append_commits(&mut self, page: Vec<GraphCommit>) {
+        self.has_more = page.len() >= GRAPH_PAGE_SIZE;
+        self.loaded = true;
+        self.loading = false;
+
+        for commit in page {
+            let row = self.graph.push(&commit.hash, &commit.parents);
+            if self.matches_filter(&commit) {
+                self.visible.push(self.commits.len());
+            }
+            self.rows.push(row);
+            self.commits.push(commit);
+        }
+    }
+
+    /// 页面加载失败时允许重新请求
+    pub fn mark_load_failed(&mut self) {
+        self.loading = false;
+    }
+
+    pub fn selected_commit(&self) -> Option<&GraphCommit> {
+        self.visible
+            .get(self.cursor)
+            .and_then(|&idx| self.commits.get(idx))
+    }
+
+    fn matches_filter(&self, commit: &GraphCommit) -> bool {
+        let Some(filter) = &self.filter else {
+            return true;
+        };
+        commit.subject.to_lowercase().contains(filter)
+            || commit.author.to_lowercase().contains(filter)
+            || commit.hash.starts_with(filter.as_str())
+            || commit
+                .refs
+                .iter()
+                .any(|r| r.to_lowercase().contains(filter))
+    }
+
+    fn apply_filter(&mut self, filter: Option<String>) {
+        self.filter = filter.map(|f| f.to_lowercase());
+        self.visible = (0..self.commits.len())
+            .filter(|&idx| self.matches_filter(&self.commits[idx]))
+            .collect();
+        self.cursor = 0;
+        self.offset = 0;
+    }
+
+    fn move_to(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.visible.len().saturating_sub(1));
+    }
+
+    /// 接近末尾时请求下一页
+    fn request_more_if_needed(&mut self, state: &mut AppState) {
+        if self.has_more && !self.loading && self.cursor + PRELOAD_THRESHOLD >= self.visible.len() {
+            self.loading = true;
+            state.request_graph_page(self.commits.len());
+        }
+    }
+
+    /// 将 `%D`（`--decorate=full`）引用转换为彩色标签
+    fn ref_badges(refs: &[String]) -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        for r in refs {
+            let names: Vec<&str> = match r.split_once(" -> ") {
+                Some((head, branch)) => vec![head, branch],
+                None => vec![r.strip_prefix("tag: ").unwrap_or(r)],
+            };
+
+            for name in names {
+                let (label, color) = if let Some(tag) = name.strip_prefix("refs/tags/") {
+                    (format!("[tag:{}]", tag), Color::Yellow)
+                } else if let Some(branch) = name.strip_prefix("refs/heads/") {
+                    (format!("[{}]", branch), Color::Green)
+                } else if let Some(remote) = name.strip_prefix("refs/remotes/") {
+                    (format!("[{}]", remote), Color::Red)
+                } else {
+                    (format!("[{}]", name), Color::Cyan)
+                };
+                spans.push(Span::styled(
+                    label,
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::raw(" "));
+            }
+        }
+        spans
+    }
+
+    fn create_row_item(
+        commit: &GraphCommit,
+        row: &GraphRow,
+        graph_width: usize,
+    ) -> ListItem<'static> {
+        let short_hash: String = commit.hash.chars().take(8).collect();
+
+        let mut spans = vec![
+            Span::styled(
+                format!("{:<width$} ", row.glyphs, width = graph_width),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::styled(
+                format!("{} ", short_hash),
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ];
+        spans.extend(Self::ref_badges(&commit.refs));
+        spans.push(Span::styled(
+            commit.subject.clone(),
+            Style::default().fg(Color::White),
+        ));
+        spans.push(Span::styled(
+            format!(" - {} {}", commit.author, commit.date),
+            Style::default().fg(Color::Gray),
+        ));
+
+        ListItem::new(Line::from(spans))
+    }
+}
+
+impl Component for GraphView {
+    fn name(&self) -> &str {
+        "GraphView"
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, _state: &AppState) {
+        let height = area.height.saturating_sub(2).max(1) as usize;
+        self.page_height = height;
+        self.area = area;
+
+        // 保证选中行在可见窗口内
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+
+        let window: Vec<usize> = self
+            .visible
+            .iter()
+            .skip(self.offset)
+            .take(height)
+            .copied()
+            .collect();
+        let graph_width = window
+            .iter()
+            .map(|&idx| self.rows[idx].glyphs.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let items: Vec<ListItem> = window
+            .iter()
+            .map(|&idx| Self::create_row_item(&self.commits[idx], &self.rows[idx], graph_width))
+            .collect();
+
+        let more = if self.has_more { "+" } else { "" };
+        let title = match &self.filter {
+            Some(filter) => format!(
+                "🌳 Commit Graph ({}/{}{} commits, filter: {})",
+                self.visible.len(),
+                self.commits.len(),
+                more,
+                filter
+            ),
+            None => format!("🌳 Commit Graph ({}{} commits)", self.commits.len(), more),
+        };
+
+        let border_style = if self.focused {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .highlight_style(if self.focused {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().bg(Color::DarkGray)
+            });
+
+        let mut list_state = ListState::default();
+        if !window.is_empty() {
+            list_state.select(Some(self.cursor - self.offset));
+        }
+        frame.render_stateful_widget(list, area, &mut list_state);
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.move_to(self.cursor.saturating_sub(1));
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.move_to(self.cursor + 1);
+            }
+            KeyCode::PageUp => {
+                self.move_to(self.cursor.saturating_sub(self.page_height));
+            }
+            KeyCode::PageDown => {
+                self.move_to(self.cursor + self.page_height);
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.move_to(0);
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.move_to(self.visible.len());
+            }
+            KeyCode::Enter => {
+                // 请求显示选中提交的diff
+                return match self.selected_commit() {
+                    Some(commit) => {
+                        state.request_diff(commit.hash.clone());
+                        EventResult::Handled
+                    }
+                    None => EventResult::NotHandled,
+                };
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                // y 复制提交哈希，Y 复制完整提交信息
+                return match self.selected_commit() {
+                    Some(commit) if key.code == KeyCode::Char('y') => {
+                        state.request_copy("commit hash", commit.hash.clone());
+                        EventResult::Handled
+                    }
+                    Some(commit) => {
+                        state.request_copy_of(CopyRequest::CommitMessage(commit.hash.clone()));
+                        EventResult::Handled
+                    }
+                    None => EventResult::NotHandled,
+                };
+            }
+            _ => return EventResult::NotHandled,
+        }
+
+        self.request_more_if_needed(state);
+        EventResult::Handled
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        if !self.area.contains((mouse.column, mouse.row).into()) {
+            return EventResult::NotHandled;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(row) = bordered_row_at(self.area, mouse.column, mouse.row) {
+                    if self.offset + row < self.visible.len() {
+                        self.move_to(self.offset + row);
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp => self.move_to(self.cursor.saturating_sub(1)),
+            MouseEventKind::ScrollDown => self.move_to(self.cursor + 1),
+            _ => return EventResult::NotHandled,
+        }
+
+        self.request_more_if_needed(state);
+        EventResult::Handled
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn min_size(&self) -> (u16, u16) {
+        (60, 20)
+    }
+}
+
+impl ViewComponent for GraphView {
+    fn view_type(&self) -> ViewType {
+        ViewType::Graph
+    }
+
+    fn title(&self) -> String {
+        "Commit Graph".to_string()
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    fn search(&mut self, query: &str) -> EventResult {
+        let query = query.trim();
+        self.apply_filter((!query.is_empty()).then(|| query.to_string()));
+        EventResult::Handled
+    }
+
+    fn clear_search(&mut self) -> EventResult {
+        self.apply_filter(None);
+        EventResult::Handled
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.visible.get(self.cursor).copied()
+    }
+
+    fn set_selected_index(&mut self, index: Option<usize>) {
+        if let Some(pos) = index.and_then(|idx| self.visible.iter().position(|&v| v == idx)) {
+            self.cursor = pos;
+        }
+    }
+
+    /// 导出当前显示的提交及其图形
+    fn export(&self) -> Vec<ExportSection> {
+        let graph_width = self
+            .visible
+            .iter()
+            .map(|&index| self.rows[index].glyphs.chars().count())
+            .max()
+            .unwrap_or(0);
+        let lines = self
+            .visible
+            .iter()
+            .map(|&index| {
+                let commit = &self.commits[index];
+                format!(
+                    "{:<width$} {} {} - {} {}",
+                    self.rows[index].glyphs,
+                    commit.hash.chars().take(8).collect::<String>(),
+                    commit.subject,
+                    commit.author,
+                    commit.date,
+                    width = graph_width
+                )
+            })
+            .collect();
+        vec![ExportSection::code(&self.title(), "text", lines)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, parents: &[&str], subject: &str) -> GraphCommit {
+        GraphCommit {
+            hash: hash.to_string(),
+            parents: parents.iter().map(|p| p.to_string()).collect(),
+            refs: Vec::new(),
+            author: "Alice".to_string(),
+            date: "2024-05-01".to_string(),
+            subject: subject.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_append_and_filter() {
+        let mut view = GraphView::new();
+        view.append_commits(vec![
+            commit("m", &["a", "b"], "Merge branch 'feature'"),
+            commit("b", &["a"], "feat: add login"),
+            commit("a", &[], "initial commit"),
+        ]);
+
+        assert!(view.is_loaded());
+        assert!(!view.has_more);
+        assert_eq!(view.rows[0].glyphs, "●─╮");
+        assert_eq!(view.selected_commit().unwrap().hash, "m");
+
+        view.search("LOGIN");
+        assert_eq!(view.visible, vec![1]);
+        assert_eq!(view.selected_commit().unwrap().hash, "b");
+
+        view.clear_search();
+        assert_eq!(view.visible.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_requests_next_page_near_end() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = GraphView::new();
+
+        let page: Vec<GraphCommit> = (0..GRAPH_PAGE_SIZE)
+            .map(|i| commit(&format!("c{}", i), &[&format!("c{}", i + 1)], "change"))
+            .collect();
+        view.append_commits(page);
+        assert!(view.has_more);
+
+        view.handle_key_event(KeyEvent::from(KeyCode::End), &mut state);
+        assert_eq!(state.take_graph_page(), Some(GRAPH_PAGE_SIZE));
+
+        // 加载中不重复请求
+        view.handle_key_event(KeyEvent::from(KeyCode::Up), &mut state);
+        assert_eq!(state.take_graph_page(), None);
+    }
+
+    #[test]
+    fn test_ref_badges() {
+        let spans = GraphView::ref_badges(&[
+            "HEAD -> refs/heads/main".to_string(),
+            "tag: refs/tags/v1.0".to_string(),
+            "refs/remotes/origin/main".to_string(),
+        ]);
+        let text: String = spans.iter().map(|s| s.content.to_string()).collect();
+        assert_eq!(text, "[HEAD] [main] [tag:v1.0] [origin/main] ");
+    }
+}
@@ -0,0 +1,217 @@
+// 变更热点视图组件
+use crate::analysis::hotspots::{Hotspot, HotspotReport};
+use crate::report::ExportSection;
+use crate::tui_unified::{
+    components::base::{
+        component::{Component, ViewComponent, ViewType},
+        events::EventResult,
+    },
+    components::widgets::list::ListWidget,
+    state::AppState,
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    Frame,
+};
+
+/// 变更热点视图 - 按修改频率和复杂度列出高风险文件，Enter 查看文件的 blame
+pub struct HotspotsView {
+    list_widget: ListWidget<Hotspot>,
+    loaded: bool,
+}
+
+impl Default for HotspotsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HotspotsView {
+    pub fn new() -> Self {
+        // 格式化函数：得分、风险、修改次数、圈复杂度合计和文件路径
+        let format_fn = Box::new(|hotspot: &Hotspot| -> String {
+            format!(
+                "{:>5.1} {:<6} {:>4} commits {:>5} lines  cc {:>4} (max {:>3})  {}",
+                hotspot.score,
+                hotspot.risk(),
+                hotspot.commits,
+                hotspot.lines_changed,
+                hotspot.total_cyclomatic,
+                hotspot.max_cyclomatic,
+                hotspot.file
+            )
+        });
+
+        // 样式函数：选中时高亮，否则按风险着色
+        let style_fn = Box::new(
+            |hotspot: &Hotspot, is_selected: bool, is_focused: bool| -> Style {
+                if is_selected {
+                    super::shared::default_selection_style(hotspot, is_selected, is_focused)
+                } else {
+                    match hotspot.risk() {
+                        "high" => Style::default().fg(Color::Red),
+                        "medium" => Style::default().fg(Color::Yellow),
+                        _ => Style::default().fg(Color::White),
+                    }
+                }
+            },
+        );
+
+        // 搜索函数：按文件路径搜索
+        let search_fn = Box::new(|hotspot: &Hotspot, query: &str| -> bool {
+            hotspot.file.to_lowercase().contains(&query.to_lowercase())
+        });
+
+        let list_widget =
+            ListWidget::new("Hotspots".to_string(), format_fn, style_fn).with_search_fn(search_fn);
+
+        Self {
+            list_widget,
+            loaded: false,
+        }
+    }
+
+    pub fn set_report(&mut self, report: HotspotReport) {
+        self.list_widget.set_title(format!(
+            "Hotspots since {} ({} high risk / {} files)",
+            report.since,
+            report.high_risk(),
+            report.hotspots.len()
+        ));
+        self.list_widget.set_items(report.hotspots);
+        self.loaded = true;
+    }
+
+    /// 是否已经分析过，首次进入视图时才自动分析
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    pub fn selected_hotspot(&self) -> Option<&Hotspot> {
+        self.list_widget.selected_item()
+    }
+}
+
+impl Component for HotspotsView {
+    fn name(&self) -> &str {
+        "HotspotsView"
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        self.list_widget.render(frame, area, state);
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
+        let Some(file) = self.selected_hotspot().map(|h| h.file.clone()) else {
+            return self.list_widget.handle_key_event(key, state);
+        };
+
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('b') => {
+                state.request_blame(file);
+                EventResult::Handled
+            }
+            KeyCode::Char('y') => {
+                state.request_copy("file path", file);
+                EventResult::Handled
+            }
+            _ => self.list_widget.handle_key_event(key, state),
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        self.list_widget.handle_mouse_event(mouse, state)
+    }
+
+    fn is_focused(&self) -> bool {
+        self.list_widget.is_focused()
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.list_widget.set_focus(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        self.list_widget.can_focus()
+    }
+
+    fn min_size(&self) -> (u16, u16) {
+        self.list_widget.min_size()
+    }
+}
+
+impl ViewComponent for HotspotsView {
+    fn view_type(&self) -> ViewType {
+        ViewType::Hotspots
+    }
+
+    fn title(&self) -> String {
+        "Hotspots".to_string()
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    fn search(&mut self, query: &str) -> EventResult {
+        self.list_widget.search(query)
+    }
+
+    fn clear_search(&mut self) -> EventResult {
+        self.list_widget.clear_search()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.list_widget.selected_index()
+    }
+
+    fn set_selected_index(&mut self, index: Option<usize>) {
+        self.list_widget.set_selected_index(index)
+    }
+
+    fn export(&self) -> Vec<ExportSection> {
+        vec![ExportSection::list(
+            &self.title(),
+            self.list_widget.formatted_lines(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hotspot(file: &str, score: f64) -> Hotspot {
+        Hotspot {
+            file: file.to_string(),
+            commits: 3,
+            lines_changed: 40,
+            functions: 2,
+            total_cyclomatic: 9,
+            max_cyclomatic: 6,
+            score,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enter_opens_blame_for_selected_file() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = HotspotsView::new();
+        assert!(!view.is_loaded());
+
+        view.set_report(HotspotReport {
+            since: "12 months ago".to_string(),
+            files_changed: 5,
+            hotspots: vec![hotspot("src/a.rs", 80.0), hotspot("src/b.rs", 10.0)],
+        });
+        view.set_focus(true);
+        assert!(view.is_loaded());
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Down), &mut state);
+        view.handle_key_event(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert_eq!(state.take_blame_request(), Some("src/b.rs".to_string()));
+    }
+}
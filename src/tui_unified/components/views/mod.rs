@@ -1,16 +1,28 @@
+pub mod blame;
 pub mod branches;
 pub mod git_log;
+pub mod graph;
+pub mod hotspots;
 pub mod query_history;
+pub mod reflog;
 pub mod remotes;
+pub mod review;
 pub mod shared;
 pub mod staging;
 pub mod stash;
 pub mod tags;
+pub mod worktrees;
 
+pub use blame::BlameView;
 pub use branches::BranchesView;
 pub use git_log::GitLogView;
+pub use graph::GraphView;
+pub use hotspots::HotspotsView;
 pub use query_history::QueryHistoryView;
+pub use reflog::ReflogView;
 pub use remotes::RemotesView;
+pub use review::ReviewView;
 pub use staging::StagingView;
 pub use stash::StashView;
 pub use tags::TagsView;
+pub use worktrees::WorktreesView;
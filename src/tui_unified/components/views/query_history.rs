@@ -1,5 +1,6 @@
 // 查询历史视图组件
-use crate::query_history::QueryHistory;
+use crate::query_history::{FavoriteQuery, QueryHistory};
+use crate::report::ExportSection;
 use crate::tui_unified::{
     components::base::{
         component::{Component, ViewComponent, ViewType},
@@ -7,14 +8,49 @@ use crate::tui_unified::{
     },
     components::widgets::list::ListWidget,
     git::models::QueryHistoryEntry,
-    state::AppState,
+    state::{
+        app_state::{InputPurpose, QueryAction},
+        AppState,
+    },
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{layout::Rect, Frame};
 
-/// 查询历史视图组件 - 显示查询历史列表
+/// 查询历史中的一行：收藏的查询或一条历史记录
+#[derive(Debug, Clone)]
+pub struct QueryRow {
+    pub query: String,
+    pub favorite: Option<FavoriteQuery>,
+    /// 最近一次执行的记录，从未执行过的收藏为空
+    pub last_run: Option<QueryHistoryEntry>,
+}
+
+/// 收藏的查询在前，其余历史记录按时间从新到旧排在后面
+fn build_rows(favorites: &[FavoriteQuery], history: &[QueryHistoryEntry]) -> Vec<QueryRow> {
+    let pinned = favorites.iter().map(|favorite| QueryRow {
+        query: favorite.query.clone(),
+        favorite: Some(favorite.clone()),
+        last_run: history
+            .iter()
+            .find(|entry| entry.query == favorite.query)
+            .cloned(),
+    });
+    let recent = history
+        .iter()
+        .filter(|entry| !favorites.iter().any(|f| f.query == entry.query))
+        .map(|entry| QueryRow {
+            query: entry.query.clone(),
+            favorite: None,
+            last_run: Some(entry.clone()),
+        });
+    pinned.chain(recent).collect()
+}
+
+/// 查询历史视图组件 - 显示查询历史列表，收藏的查询置顶
 pub struct QueryHistoryView {
-    list_widget: ListWidget<QueryHistoryEntry>,
+    list_widget: ListWidget<QueryRow>,
+    history: Vec<QueryHistoryEntry>,
+    favorites: Vec<FavoriteQuery>,
 }
 
 impl Default for QueryHistoryView {
@@ -25,8 +61,18 @@ impl Default for QueryHistoryView {
 
 impl QueryHistoryView {
     pub fn new() -> Self {
-        // 格式化函数：显示查询内容、时间和结果
-        let format_fn = Box::new(|entry: &QueryHistoryEntry| -> String {
+        // 格式化函数：显示收藏标记、名称、查询内容、时间和结果
+        let format_fn = Box::new(|row: &QueryRow| -> String {
+            let (icon, label) = match &row.favorite {
+                Some(FavoriteQuery {
+                    name: Some(name), ..
+                }) => ("★", format!("{} ({})", name, row.query)),
+                Some(_) => ("★", row.query.clone()),
+                None => ("📜", row.query.clone()),
+            };
+            let Some(entry) = &row.last_run else {
+                return format!("{} {} - never run", icon, label);
+            };
             let status_icon = if entry.success { "✅" } else { "❌" };
             let result_info = if let Some(count) = entry.result_count {
                 format!(" ({} results)", count)
@@ -34,23 +80,25 @@ impl QueryHistoryView {
                 String::new()
             };
             let time_str = entry.timestamp.format("%m-%d %H:%M").to_string();
+            let runs = if entry.run_count > 1 {
+                format!(" ×{}", entry.run_count)
+            } else {
+                String::new()
+            };
             format!(
-                "📜 {} {} - {}{}",
-                status_icon, entry.query, time_str, result_info
+                "{} {} {} - {}{}{}",
+                icon, status_icon, label, time_str, result_info, runs
             )
         });
 
-        // 样式函数：选中时高亮显示，成功和失败用不同颜色
+        // 样式函数：选中时高亮显示，收藏为黄色，成功和失败用不同颜色
         let style_fn = Box::new(
-            |entry: &QueryHistoryEntry,
-             is_selected: bool,
-             is_focused: bool|
-             -> ratatui::style::Style {
+            |row: &QueryRow, is_selected: bool, is_focused: bool| -> ratatui::style::Style {
                 use ratatui::style::{Color, Style};
-                let base_color = if entry.success {
-                    Color::Green
-                } else {
-                    Color::Red
+                let base_color = match (&row.favorite, &row.last_run) {
+                    (Some(_), _) => Color::LightYellow,
+                    (None, Some(entry)) if !entry.success => Color::Red,
+                    (None, _) => Color::Green,
                 };
 
                 if is_selected && is_focused {
@@ -63,39 +111,59 @@ impl QueryHistoryView {
             },
         );
 
-        // 搜索函数：支持按查询内容和类型搜索
-        let search_fn = Box::new(|entry: &QueryHistoryEntry, query: &str| -> bool {
+        // 搜索函数：支持按查询内容、收藏名称和类型搜索
+        let search_fn = Box::new(|row: &QueryRow, query: &str| -> bool {
             let query = query.to_lowercase();
-            entry.query.to_lowercase().contains(&query)
-                || entry
-                    .query_type
+            row.query.to_lowercase().contains(&query)
+                || row
+                    .favorite
+                    .as_ref()
+                    .and_then(|f| f.name.as_ref())
+                    .is_some_and(|name| name.to_lowercase().contains(&query))
+                || row
+                    .last_run
                     .as_ref()
+                    .and_then(|entry| entry.query_type.as_ref())
                     .is_some_and(|t| t.to_lowercase().contains(&query))
         });
 
         let list_widget = ListWidget::new("Query History".to_string(), format_fn, style_fn)
             .with_search_fn(search_fn);
 
-        Self { list_widget }
+        Self {
+            list_widget,
+            history: Vec::new(),
+            favorites: Vec::new(),
+        }
     }
 
-    pub async fn load_history(&mut self) {
-        match QueryHistory::new(1000) {
-            Ok(history) => {
-                let entries = history.get_recent(100);
-                let entries_owned: Vec<QueryHistoryEntry> = entries.into_iter().cloned().collect();
-                self.list_widget.set_items(entries_owned);
-            }
-            Err(_) => {
-                // 如果加载失败，设置空列表
-                self.list_widget.set_items(vec![]);
-            }
+    pub async fn load_history(&mut self, favorites: &[FavoriteQuery]) {
+        // 如果加载失败，只显示收藏的查询
+        self.history = match QueryHistory::new(1000).await {
+            Ok(history) => history.get_recent(100).into_iter().cloned().collect(),
+            Err(_) => Vec::new(),
+        };
+        self.set_favorites(favorites.to_vec());
+    }
+
+    /// 更新收藏的查询，保持当前选中的查询不变
+    pub fn set_favorites(&mut self, favorites: Vec<FavoriteQuery>) {
+        let selected = self.selected_query().map(str::to_string);
+        self.favorites = favorites;
+        self.list_widget
+            .set_items(build_rows(&self.favorites, &self.history));
+        if let Some(query) = selected {
+            self.list_widget.select_where(|row| row.query == query);
         }
     }
 
-    pub fn selected_query(&self) -> Option<&QueryHistoryEntry> {
+    pub fn selected_row(&self) -> Option<&QueryRow> {
         self.list_widget.selected_item()
     }
+
+    pub fn selected_query(&self) -> Option<&str> {
+        self.selected_row().map(|row| row.query.as_str())
+    }
 }
 
 impl Component for QueryHistoryView {
@@ -108,7 +176,35 @@ impl Component for QueryHistoryView {
     }
 
     fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
-        self.list_widget.handle_key_event(key, state)
+        let Some(row) = self.selected_row().cloned() else {
+            return self.list_widget.handle_key_event(key, state);
+        };
+        match key.code {
+            KeyCode::Enter => {
+                state.request_query_action(QueryAction::Run(row.query));
+                EventResult::Handled
+            }
+            KeyCode::Char('s') => {
+                state.request_query_action(QueryAction::TogglePin(row.query));
+                EventResult::Handled
+            }
+            KeyCode::Char('r') => {
+                // 重命名同时会收藏该查询
+                let name = row.favorite.and_then(|f| f.name).unwrap_or_default();
+                state.request_input(
+                    InputPurpose::RenameQuery(row.query.clone()),
+                    "Rename Query",
+                    &format!("Name for \"{}\":", row.query),
+                );
+                state.edit_input(|value| *value = name);
+                EventResult::Handled
+            }
+            _ => self.list_widget.handle_key_event(key, state),
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        self.list_widget.handle_mouse_event(mouse, state)
     }
 
     fn is_focused(&self) -> bool {
@@ -156,4 +252,67 @@ impl ViewComponent for QueryHistoryView {
     fn set_selected_index(&mut self, index: Option<usize>) {
         self.list_widget.set_selected_index(index)
     }
+
+    fn export(&self) -> Vec<ExportSection> {
+        vec![ExportSection::list(
+            &self.title(),
+            self.list_widget.formatted_lines(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+
+    fn entry(query: &str) -> QueryHistoryEntry {
+        QueryHistoryEntry {
+            query: query.to_string(),
+            timestamp: Local::now(),
+            query_type: None,
+            result_count: Some(3),
+            success: true,
+            run_count: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_favorites_on_top_and_actions_requested() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = QueryHistoryView::new();
+        view.history = vec![entry("author:jane"), entry("message:feat")];
+        view.set_favorites(vec![FavoriteQuery {
+            query: "message:feat".to_string(),
+            name: Some("features".to_string()),
+        }]);
+        view.set_focus(true);
+
+        // 收藏的查询置顶，且不在历史部分重复出现
+        let queries: Vec<&str> = view
+            .list_widget
+            .items()
+            .iter()
+            .map(|row| row.query.as_str())
+            .collect();
+        assert_eq!(queries, ["message:feat", "author:jane"]);
+        assert!(view.list_widget.items()[0].last_run.is_some());
+
+        view.set_selected_index(Some(1));
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('s')), &mut state);
+        assert_eq!(
+            state.take_query_action(),
+            Some(QueryAction::TogglePin("author:jane".to_string()))
+        );
+
+        // 收藏变化后仍选中同一个查询
+        view.set_favorites(Vec::new());
+        assert_eq!(view.selected_query(), Some("author:jane"));
+        view.handle_key_event(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert_eq!(
+            state.take_query_action(),
+            Some(QueryAction::Run("author:jane".to_string()))
+        );
+    }
 }
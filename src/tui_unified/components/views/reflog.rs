@@ -0,0 +1,277 @@
+// Git reflog视图组件
+use crate::report::ExportSection;
+use crate::tui_unified::{
+    components::base::{
+        component::{Component, ViewComponent, ViewType},
+        events::EventResult,
+    },
+    components::widgets::list::ListWidget,
+    git::models::ReflogEntry,
+    state::{
+        app_state::{ReflogAction, ResetMode},
+        AppState, CopyRequest,
+    },
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    Frame,
+};
+
+/// 加载的 reflog 条目上限
+pub const REFLOG_LIMIT: usize = 500;
+
+/// 将时间戳格式化为相对时间，如 `5m ago`
+fn format_age(timestamp: i64, now: i64) -> String {
+    let seconds = (now - timestamp).max(0);
+    match seconds {
+        s if s < 60 => format!("{}s ago", s),
+        s if s < 3600 => format!("{}m ago", s / 60),
+        s if s < 86400 => format!("{}h ago", s / 3600),
+        s if s < 86400 * 30 => format!("{}d ago", s / 86400),
+        s if s < 86400 * 365 => format!("{}mo ago", s / (86400 * 30)),
+        s => format!("{}y ago", s / (86400 * 365)),
+    }
+}
+
+/// 根据操作类型选择颜色
+fn operation_color(operation: &str) -> Color {
+    let kind = operation.split([' ', '(']).next().unwrap_or_default();
+    match kind {
+        "commit" => Color::Green,
+        "checkout" | "switch" => Color::Cyan,
+        "reset" => Color::Red,
+        "rebase" => Color::Magenta,
+        "merge" | "pull" | "cherry-pick" | "revert" => Color::Yellow,
+        _ => Color::White,
+    }
+}
+
+/// Git reflog视图组件 - 显示 HEAD 的移动记录，支持检出或重置到某个条目
+pub struct ReflogView {
+    list_widget: ListWidget<ReflogEntry>,
+}
+
+impl Default for ReflogView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReflogView {
+    pub fn new() -> Self {
+        // 格式化函数：选择器、短哈希、相对时间、操作和说明
+        let format_fn = Box::new(|entry: &ReflogEntry| -> String {
+            let short_hash: String = entry.hash.chars().take(8).collect();
+            let age = format_age(entry.timestamp, chrono::Utc::now().timestamp());
+            format!(
+                "{:<10} {} {:>8}  {}: {}",
+                entry.selector, short_hash, age, entry.operation, entry.message
+            )
+        });
+
+        // 样式函数：选中时高亮，否则按操作类型着色
+        let style_fn = Box::new(
+            |entry: &ReflogEntry, is_selected: bool, is_focused: bool| -> Style {
+                if is_selected {
+                    super::shared::default_selection_style(entry, is_selected, is_focused)
+                } else {
+                    Style::default().fg(operation_color(&entry.operation))
+                }
+            },
+        );
+
+        // 搜索函数：支持按操作、说明和哈希搜索
+        let search_fn = Box::new(|entry: &ReflogEntry, query: &str| -> bool {
+            let query = query.to_lowercase();
+            entry.operation.to_lowercase().contains(&query)
+                || entry.message.to_lowercase().contains(&query)
+                || entry.hash.starts_with(&query)
+        });
+
+        let list_widget = ListWidget::new("Git Reflog".to_string(), format_fn, style_fn)
+            .with_search_fn(search_fn);
+
+        Self { list_widget }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<ReflogEntry>) {
+        self.list_widget.set_items(entries);
+    }
+
+    pub fn selected_entry(&self) -> Option<&ReflogEntry> {
+        self.list_widget.selected_item()
+    }
+
+    fn reset_action(entry: &ReflogEntry, mode: ResetMode) -> ReflogAction {
+        ReflogAction::Reset {
+            selector: entry.selector.clone(),
+            hash: entry.hash.clone(),
+            mode,
+        }
+    }
+}
+
+impl Component for ReflogView {
+    fn name(&self) -> &str {
+        "ReflogView"
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        self.list_widget.render(frame, area, state);
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
+        let Some(entry) = self.selected_entry().cloned() else {
+            return self.list_widget.handle_key_event(key, state);
+        };
+
+        match key.code {
+            KeyCode::Enter => {
+                // 请求显示条目对应提交的diff
+                state.request_diff(entry.hash);
+                EventResult::Handled
+            }
+            KeyCode::Char('y') => {
+                state.request_copy("commit hash", entry.hash);
+                EventResult::Handled
+            }
+            KeyCode::Char('Y') => {
+                state.request_copy_of(CopyRequest::CommitMessage(entry.hash));
+                EventResult::Handled
+            }
+            KeyCode::Char('o') => {
+                state.request_reflog_action(ReflogAction::Checkout {
+                    selector: entry.selector,
+                    hash: entry.hash,
+                });
+                EventResult::Handled
+            }
+            KeyCode::Char('S') => {
+                state.request_reflog_action(Self::reset_action(&entry, ResetMode::Soft));
+                EventResult::Handled
+            }
+            KeyCode::Char('M') => {
+                state.request_reflog_action(Self::reset_action(&entry, ResetMode::Mixed));
+                EventResult::Handled
+            }
+            KeyCode::Char('H') => {
+                state.request_reflog_action(Self::reset_action(&entry, ResetMode::Hard));
+                EventResult::Handled
+            }
+            _ => self.list_widget.handle_key_event(key, state),
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        self.list_widget.handle_mouse_event(mouse, state)
+    }
+
+    fn is_focused(&self) -> bool {
+        self.list_widget.is_focused()
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.list_widget.set_focus(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        self.list_widget.can_focus()
+    }
+
+    fn min_size(&self) -> (u16, u16) {
+        self.list_widget.min_size()
+    }
+}
+
+impl ViewComponent for ReflogView {
+    fn view_type(&self) -> ViewType {
+        ViewType::Reflog
+    }
+
+    fn title(&self) -> String {
+        "Git Reflog".to_string()
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    fn search(&mut self, query: &str) -> EventResult {
+        self.list_widget.search(query)
+    }
+
+    fn clear_search(&mut self) -> EventResult {
+        self.list_widget.clear_search()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.list_widget.selected_index()
+    }
+
+    fn set_selected_index(&mut self, index: Option<usize>) {
+        self.list_widget.set_selected_index(index)
+    }
+
+    fn export(&self) -> Vec<ExportSection> {
+        vec![ExportSection::list(
+            &self.title(),
+            self.list_widget.formatted_lines(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(1000, 1030), "30s ago");
+        assert_eq!(format_age(1000, 1000 + 5 * 60), "5m ago");
+        assert_eq!(format_age(1000, 1000 + 3 * 3600), "3h ago");
+        assert_eq!(format_age(1000, 1000 + 2 * 86400), "2d ago");
+        assert_eq!(format_age(2000, 1000), "0s ago");
+    }
+
+    #[test]
+    fn test_operation_color() {
+        assert_eq!(operation_color("commit (amend)"), Color::Green);
+        assert_eq!(operation_color("reset"), Color::Red);
+        assert_eq!(operation_color("rebase (finish)"), Color::Magenta);
+        assert_eq!(operation_color("unknown"), Color::White);
+    }
+
+    #[tokio::test]
+    async fn test_hard_reset_requires_confirmation() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = ReflogView::new();
+        view.set_entries(vec![ReflogEntry {
+            hash: "0123456789abcdef".to_string(),
+            selector: "HEAD@{0}".to_string(),
+            timestamp: 0,
+            operation: "commit".to_string(),
+            message: "feat: x".to_string(),
+        }]);
+        view.set_focus(true);
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('H')), &mut state);
+        assert!(state.is_modal_active());
+        assert!(state
+            .modal
+            .as_ref()
+            .unwrap()
+            .content
+            .contains("uncommitted changes will be lost"));
+        assert_eq!(
+            state.take_reflog_action(),
+            Some(ReflogAction::Reset {
+                selector: "HEAD@{0}".to_string(),
+                hash: "0123456789abcdef".to_string(),
+                mode: ResetMode::Hard,
+            })
+        );
+    }
+}
@@ -1,4 +1,5 @@
 // Git 远程仓库视图组件
+use crate::report::ExportSection;
 use crate::tui_unified::{
     components::base::{
         component::{Component, ViewComponent, ViewType},
@@ -6,12 +7,12 @@ use crate::tui_unified::{
     },
     components::widgets::list::ListWidget,
     git::models::Remote,
-    state::AppState,
+    state::{app_state::RemoteOperation, AppState},
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{layout::Rect, Frame};
 
-/// Git 远程仓库视图组件 - 显示远程仓库列表
+/// Git 远程仓库视图组件 - 显示远程仓库列表，支持 fetch/pull/push
 pub struct RemotesView {
     list_widget: ListWidget<Remote>,
 }
@@ -75,7 +76,30 @@ impl Component for RemotesView {
     }
 
     fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
-        self.list_widget.handle_key_event(key, state)
+        match key.code {
+            KeyCode::Char('F') => {
+                let remote = self.selected_remote().map(|r| r.name.clone());
+                state.request_remote_operation(RemoteOperation::Fetch(remote));
+                EventResult::Handled
+            }
+            KeyCode::Char('A') => {
+                state.request_remote_operation(RemoteOperation::Fetch(None));
+                EventResult::Handled
+            }
+            KeyCode::Char('p') => {
+                state.request_remote_operation(RemoteOperation::Pull);
+                EventResult::Handled
+            }
+            KeyCode::Char('U') => {
+                state.request_push(None);
+                EventResult::Handled
+            }
+            _ => self.list_widget.handle_key_event(key, state),
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        self.list_widget.handle_mouse_event(mouse, state)
     }
 
     fn is_focused(&self) -> bool {
@@ -123,4 +147,11 @@ impl ViewComponent for RemotesView {
     fn set_selected_index(&mut self, index: Option<usize>) {
         self.list_widget.set_selected_index(index)
     }
+
+    fn export(&self) -> Vec<ExportSection> {
+        vec![ExportSection::list(
+            &self.title(),
+            self.list_widget.formatted_lines(),
+        )]
+    }
 }
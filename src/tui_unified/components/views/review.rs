@@ -0,0 +1,524 @@
+// AI 审查结果视图组件
+use crate::report::{CodeReviewReport, ReviewFinding, Severity};
+use crate::tui_unified::{
+    components::base::{
+        component::{Component, ViewComponent, ViewType},
+        events::EventResult,
+    },
+    components::widgets::list::ListWidget,
+    state::{
+        app_state::{NotificationLevel, ReviewRequest},
+        AppState,
+    },
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+/// 审查对象
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewTarget {
+    /// 工作区的变更，有已暂存的变更时只审查已暂存部分
+    WorkingTree,
+    Commit(String),
+}
+
+impl ReviewTarget {
+    pub fn label(&self) -> String {
+        match self {
+            ReviewTarget::WorkingTree => "working tree".to_string(),
+            ReviewTarget::Commit(hash) => format!("commit {}", &hash[..8.min(hash.len())]),
+        }
+    }
+}
+
+/// 详情区域显示的内容
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DetailTab {
+    /// 选中的问题
+    #[default]
+    Issue,
+    /// 完整的 AI 审查意见
+    Report,
+}
+
+/// 严重程度对应的颜色
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Critical => Color::Red,
+        Severity::High => Color::LightRed,
+        Severity::Medium => Color::Yellow,
+        Severity::Low => Color::Cyan,
+        Severity::Info => Color::Gray,
+    }
+}
+
+/// 问题所在位置，如 `src/main.rs:42`
+fn location(finding: &ReviewFinding) -> String {
+    match (&finding.file, finding.line) {
+        (Some(file), Some(line)) => format!("{}:{}", file, line),
+        (Some(file), None) => file.clone(),
+        (None, _) => "-".to_string(),
+    }
+}
+
+/// 各严重程度的问题数量，从高到低，如 `critical 1 · medium 2`
+fn severity_summary(report: &CodeReviewReport) -> String {
+    let counts: Vec<String> = [
+        Severity::Critical,
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+        Severity::Info,
+    ]
+    .into_iter()
+    .filter_map(|severity| match report.count_by_severity(severity) {
+        0 => None,
+        count => Some(format!("{} {}", severity.as_str(), count)),
+    })
+    .collect();
+    if counts.is_empty() {
+        "no issues".to_string()
+    } else {
+        counts.join(" · ")
+    }
+}
+
+/// AI 审查结果视图组件 - 审查工作区或选中的提交，按严重程度分组列出问题并跳转到 diff
+pub struct ReviewView {
+    list_widget: ListWidget<ReviewFinding>,
+    target: Option<ReviewTarget>,
+    report: Option<CodeReviewReport>,
+    running: bool,
+    error: Option<String>,
+    /// 从存储中加载的报告的保存时间
+    stored_at: Option<String>,
+    detail_tab: DetailTab,
+}
+
+impl Default for ReviewView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReviewView {
+    pub fn new() -> Self {
+        // 格式化函数：严重程度、类别、位置和问题说明
+        let format_fn = Box::new(|finding: &ReviewFinding| -> String {
+            format!(
+                "{:<8} {:<15} {:<32} {}",
+                finding.severity.as_str(),
+                finding.category,
+                location(finding),
+                finding.message.lines().next().unwrap_or_default()
+            )
+        });
+
+        // 样式函数：选中时高亮，否则按严重程度着色
+        let style_fn = Box::new(
+            |finding: &ReviewFinding, is_selected: bool, is_focused: bool| -> Style {
+                if is_selected {
+                    super::shared::default_selection_style(finding, is_selected, is_focused)
+                } else {
+                    Style::default().fg(severity_color(finding.severity))
+                }
+            },
+        );
+
+        // 搜索函数：支持按说明、类别和文件搜索
+        let search_fn = Box::new(|finding: &ReviewFinding, query: &str| -> bool {
+            let query = query.to_lowercase();
+            finding.message.to_lowercase().contains(&query)
+                || finding.category.to_lowercase().contains(&query)
+                || finding
+                    .file
+                    .as_ref()
+                    .is_some_and(|file| file.to_lowercase().contains(&query))
+        });
+
+        let list_widget =
+            ListWidget::new("AI Review".to_string(), format_fn, style_fn).with_search_fn(search_fn);
+
+        Self {
+            list_widget,
+            target: None,
+            report: None,
+            running: false,
+            error: None,
+            stored_at: None,
+            detail_tab: DetailTab::default(),
+        }
+    }
+
+    /// 上一次（或正在进行的）审查对象
+    pub fn target(&self) -> Option<&ReviewTarget> {
+        self.target.as_ref()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    /// 开始审查，旧的报告在新结果出来之前继续显示
+    pub fn start(&mut self, target: ReviewTarget) {
+        self.target = Some(target);
+        self.running = true;
+        self.error = None;
+        self.update_title();
+    }
+
+    /// 显示审查报告，问题按严重程度从高到低分组
+    pub fn set_report(&mut self, report: CodeReviewReport) {
+        let mut findings = report.findings.clone();
+        findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+        self.list_widget.set_items(findings);
+        // 新报告从最严重的问题开始
+        self.list_widget.set_selected_index(Some(0));
+        self.report = Some(report);
+        self.running = false;
+        self.stored_at = None;
+        self.update_title();
+    }
+
+    /// 显示之前保存的提交审查报告
+    pub fn set_stored_report(&mut self, commit: &str, report: CodeReviewReport, stored_at: &str) {
+        self.target = Some(ReviewTarget::Commit(commit.to_string()));
+        self.set_report(report);
+        self.stored_at = Some(stored_at.to_string());
+        self.update_title();
+    }
+
+    pub fn detail_tab(&self) -> DetailTab {
+        self.detail_tab
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.running = false;
+        self.error = Some(error);
+        self.update_title();
+    }
+
+    pub fn report(&self) -> Option<&CodeReviewReport> {
+        self.report.as_ref()
+    }
+
+    pub fn selected_finding(&self) -> Option<&ReviewFinding> {
+        self.list_widget.selected_item()
+    }
+
+    fn update_title(&mut self) {
+        let mut title = match (&self.report, &self.target) {
+            (Some(report), _) => format!(
+                "AI Review · {} · {}",
+                report.target,
+                severity_summary(report)
+            ),
+            (None, Some(target)) => format!("AI Review · {}", target.label()),
+            (None, None) => "AI Review".to_string(),
+        };
+        if let Some(stored_at) = &self.stored_at {
+            title.push_str(&format!(" · stored {}", stored_at));
+        }
+        if self.running {
+            title.push_str(" · reviewing…");
+        }
+        self.list_widget.set_title(title);
+    }
+
+    fn render_placeholder(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = Vec::new();
+        if let (true, Some(target)) = (self.running, &self.target) {
+            lines.push(Line::raw(format!("Reviewing {}…", target.label())));
+        } else if let Some(error) = &self.error {
+            lines.push(Line::styled(
+                format!("Review failed: {}", error),
+                Style::default().fg(Color::Red),
+            ));
+        } else {
+            lines.push(Line::raw("No review yet."));
+        }
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            "w - review working tree   C - review the commit selected in Git log   s - stored review of that commit",
+            Style::default().fg(Color::DarkGray),
+        ));
+
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("AI Review")
+                    .border_style(if self.is_focused() {
+                        Style::default().fg(Color::Yellow)
+                    } else {
+                        Style::default()
+                    }),
+            ),
+            area,
+        );
+    }
+
+    fn render_detail(&self, frame: &mut Frame, area: Rect) {
+        if self.detail_tab == DetailTab::Report {
+            let text = self
+                .report
+                .as_ref()
+                .map(|report| report.ai_review.clone())
+                .filter(|review| !review.is_empty())
+                .unwrap_or_else(|| "No AI review text".to_string());
+            frame.render_widget(
+                Paragraph::new(text).wrap(Wrap { trim: false }).block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Report · Tab-issue, r-rerun, s-stored review"),
+                ),
+                area,
+            );
+            return;
+        }
+
+        let lines = match self.selected_finding() {
+            Some(finding) => vec![
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} {} ", finding.severity.icon(), finding.severity.as_str()),
+                        Style::default().fg(severity_color(finding.severity)),
+                    ),
+                    Span::styled(
+                        format!("[{}] {}", finding.category, location(finding)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]),
+                Line::raw(finding.message.clone()),
+            ],
+            None => vec![Line::styled(
+                "No issues found",
+                Style::default().fg(Color::Green),
+            )],
+        };
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .block(Block::default().borders(Borders::ALL).title(
+                "Issue · Enter-jump to diff, y-copy, Tab-report, r-rerun, w-working tree, C-selected commit, s-stored review",
+            )),
+            area,
+        );
+    }
+}
+
+impl Component for ReviewView {
+    fn name(&self) -> &str {
+        "ReviewView"
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        if self.report.is_none() {
+            self.render_placeholder(frame, area);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(6)])
+            .split(area);
+        self.list_widget.render(frame, chunks[0], state);
+        self.render_detail(frame, chunks[1]);
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
+        match key.code {
+            KeyCode::Char('w') => {
+                state.request_review(ReviewRequest::WorkingTree);
+                EventResult::Handled
+            }
+            KeyCode::Char('C') => {
+                state.request_review(ReviewRequest::SelectedCommit);
+                EventResult::Handled
+            }
+            KeyCode::Char('s') => {
+                state.request_review(ReviewRequest::StoredForSelectedCommit);
+                EventResult::Handled
+            }
+            KeyCode::Tab if self.report.is_some() => {
+                self.detail_tab = match self.detail_tab {
+                    DetailTab::Issue => DetailTab::Report,
+                    DetailTab::Report => DetailTab::Issue,
+                };
+                EventResult::Handled
+            }
+            KeyCode::Char('r') if self.target.is_some() => {
+                state.request_review(ReviewRequest::Rerun);
+                EventResult::Handled
+            }
+            KeyCode::Enter => {
+                match self.selected_finding() {
+                    Some(ReviewFinding {
+                        file: Some(file),
+                        line,
+                        ..
+                    }) => state.request_review(ReviewRequest::Jump {
+                        file: file.clone(),
+                        line: *line,
+                    }),
+                    Some(_) => {
+                        state.add_notification(
+                            "This issue does not point to a file".to_string(),
+                            NotificationLevel::Warning,
+                        );
+                    }
+                    None => {}
+                }
+                EventResult::Handled
+            }
+            KeyCode::Char('y') => {
+                if let Some(finding) = self.selected_finding() {
+                    let text = finding.message.clone();
+                    state.request_copy("review issue", text);
+                }
+                EventResult::Handled
+            }
+            _ => self.list_widget.handle_key_event(key, state),
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        self.list_widget.handle_mouse_event(mouse, state)
+    }
+
+    fn is_focused(&self) -> bool {
+        self.list_widget.is_focused()
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.list_widget.set_focus(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        self.list_widget.can_focus()
+    }
+
+    fn min_size(&self) -> (u16, u16) {
+        self.list_widget.min_size()
+    }
+}
+
+impl ViewComponent for ReviewView {
+    fn view_type(&self) -> ViewType {
+        ViewType::Review
+    }
+
+    fn title(&self) -> String {
+        "AI Review".to_string()
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    fn search(&mut self, query: &str) -> EventResult {
+        self.list_widget.search(query)
+    }
+
+    fn clear_search(&mut self) -> EventResult {
+        self.list_widget.clear_search()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.list_widget.selected_index()
+    }
+
+    fn set_selected_index(&mut self, index: Option<usize>) {
+        self.list_widget.set_selected_index(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(severity: Severity, file: Option<&str>, line: Option<u32>) -> ReviewFinding {
+        ReviewFinding {
+            severity,
+            category: "bug".to_string(),
+            message: "issue".to_string(),
+            file: file.map(str::to_string),
+            line,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_findings_sorted_and_jump_requested() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = ReviewView::new();
+        view.start(ReviewTarget::WorkingTree);
+        assert!(view.is_running());
+
+        view.set_report(CodeReviewReport {
+            target: "working tree".to_string(),
+            findings: vec![
+                finding(Severity::Low, None, None),
+                finding(Severity::Critical, Some("src/main.rs"), Some(42)),
+            ],
+            ..Default::default()
+        });
+        view.set_focus(true);
+        assert!(!view.is_running());
+        assert_eq!(
+            severity_summary(view.report().unwrap()),
+            "critical 1 · low 1"
+        );
+
+        // 最严重的问题排在最前面
+        view.handle_key_event(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert_eq!(
+            state.take_review_request(),
+            Some(ReviewRequest::Jump {
+                file: "src/main.rs".to_string(),
+                line: Some(42),
+            })
+        );
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('r')), &mut state);
+        assert_eq!(state.take_review_request(), Some(ReviewRequest::Rerun));
+    }
+
+    #[tokio::test]
+    async fn test_stored_report_and_detail_tab() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = ReviewView::new();
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('s')), &mut state);
+        assert_eq!(
+            state.take_review_request(),
+            Some(ReviewRequest::StoredForSelectedCommit)
+        );
+
+        view.set_stored_report(
+            "3f2a9c0d1e4b",
+            CodeReviewReport {
+                target: "commit 3f2a9c0d".to_string(),
+                ai_review: "## 总结\n整体质量良好。".to_string(),
+                ..Default::default()
+            },
+            "2026-10-01 12:00:00",
+        );
+        assert_eq!(
+            view.target(),
+            Some(&ReviewTarget::Commit("3f2a9c0d1e4b".to_string()))
+        );
+
+        assert_eq!(view.detail_tab(), DetailTab::Issue);
+        view.handle_key_event(KeyEvent::from(KeyCode::Tab), &mut state);
+        assert_eq!(view.detail_tab(), DetailTab::Report);
+    }
+}
@@ -1,12 +1,17 @@
 // 交互式暂存视图组件 - 支持文件级和 hunk 级暂存
+use crate::report::ExportSection;
 use crate::tui_unified::{
     components::base::{
-        component::{Component, ViewComponent, ViewType},
+        component::{bordered_row_at, Component, ViewComponent, ViewType},
         events::EventResult,
     },
-    state::{app_state::NotificationLevel, git_state::ChangeType, AppState},
+    state::{
+        app_state::{NotificationLevel, StagingOperation},
+        git_state::ChangeType,
+        AppState,
+    },
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -113,6 +118,8 @@ struct StagingEntry {
     path: PathBuf,
     change_type: ChangeType,
     is_staged: bool,
+    /// 未追踪文件（不支持 hunk 级暂存）
+    untracked: bool,
     #[allow(dead_code)]
     additions: usize,
     #[allow(dead_code)]
@@ -171,6 +178,11 @@ pub struct StagingView {
     visible_rows: Vec<ListRow>,
     /// 当前选中的可见行索引
     cursor: usize,
+    /// 已请求 diff 预览的文件 (path, is_staged)
+    previewed: Option<(PathBuf, bool)>,
+    /// 上次渲染的文件列表和 diff 预览区域，用于鼠标命中测试
+    list_area: Rect,
+    preview_area: Rect,
 }
 
 impl Default for StagingView {
@@ -191,6 +203,9 @@ impl StagingView {
             list_state,
             visible_rows: Vec::new(),
             cursor: 0,
+            previewed: None,
+            list_area: Rect::default(),
+            preview_area: Rect::default(),
         }
     }
 
@@ -215,8 +230,25 @@ impl StagingView {
     }
 
     /// 从 AppState 刷新文件列表
+    ///
+    /// 保留同一文件的展开状态和已加载的 hunks，避免暂存后列表跳动。
     pub fn refresh_file_list(&mut self, state: &AppState) {
-        self.files.clear();
+        let previous = std::mem::take(&mut self.files);
+        self.previewed = None;
+
+        // 冲突文件放在最前面，解决后暂存即标记为已解决
+        for path in &state.repo_state.status.conflicts {
+            self.files.push(StagingEntry {
+                path: path.clone(),
+                change_type: ChangeType::Unmerged,
+                is_staged: false,
+                untracked: false,
+                additions: 0,
+                deletions: 0,
+                expanded: false,
+                hunks: Vec::new(),
+            });
+        }
 
         // 添加已暂存文件
         for file in &state.repo_state.status.staged_files {
@@ -224,6 +256,7 @@ impl StagingView {
                 path: file.path.clone(),
                 change_type: file.status.clone(),
                 is_staged: true,
+                untracked: false,
                 additions: file.additions,
                 deletions: file.deletions,
                 expanded: false,
@@ -237,6 +270,7 @@ impl StagingView {
                 path: file.path.clone(),
                 change_type: file.status.clone(),
                 is_staged: false,
+                untracked: false,
                 additions: file.additions,
                 deletions: file.deletions,
                 expanded: false,
@@ -250,6 +284,7 @@ impl StagingView {
                 path: path.clone(),
                 change_type: ChangeType::Added,
                 is_staged: false,
+                untracked: true,
                 additions: 0,
                 deletions: 0,
                 expanded: false,
@@ -257,6 +292,16 @@ impl StagingView {
             });
         }
 
+        for entry in &mut self.files {
+            if let Some(old) = previous
+                .iter()
+                .find(|old| old.path == entry.path && old.is_staged == entry.is_staged)
+            {
+                entry.expanded = old.expanded;
+                entry.hunks = old.hunks.clone();
+            }
+        }
+
         self.rebuild_visible_rows();
     }
 
@@ -278,35 +323,47 @@ impl StagingView {
     /// 请求切换当前选中文件的暂存状态
     fn request_toggle_staging(&self, state: &mut AppState) {
         if let Some(ListRow::File(file_idx)) = self.visible_rows.get(self.cursor) {
-            *state
-                .selected_items
-                .pending_staging_toggle
-                .lock()
-                .unwrap_or_else(|e| e.into_inner()) = Some(*file_idx);
+            let entry = &self.files[*file_idx];
+            let file_path = entry.path.to_string_lossy().to_string();
+            state.request_staging_operation(if entry.is_staged {
+                StagingOperation::UnstageFile(file_path)
+            } else {
+                StagingOperation::StageFile(file_path)
+            });
         }
     }
 
-    /// 请求暂存单个 hunk
+    /// 请求暂存单个 hunk，已暂存文件的 hunk 则取消暂存
     fn request_hunk_stage(&self, state: &mut AppState, file_idx: usize, hunk_idx: usize) {
         let entry = &self.files[file_idx];
         let hunk = &entry.hunks[hunk_idx];
         let file_path = entry.path.to_string_lossy().to_string();
         let patch = hunk.to_patch(&file_path);
 
-        *state
-            .selected_items
-            .pending_hunk_stage
-            .lock()
-            .unwrap_or_else(|e| e.into_inner()) = Some((file_path, patch));
+        state.request_staging_operation(StagingOperation::ApplyHunk {
+            file_path,
+            patch,
+            reverse: entry.is_staged,
+        });
     }
 
-    /// 请求暂存全部文件
-    fn request_stage_all(&self, state: &mut AppState) {
-        *state
-            .selected_items
-            .pending_stage_all
-            .lock()
-            .unwrap_or_else(|e| e.into_inner()) = true;
+    /// 当前选中文件变化时请求加载其 diff 预览
+    pub fn request_preview(&mut self, state: &mut AppState) {
+        let selected = match self.visible_rows.get(self.cursor) {
+            Some(ListRow::File(idx)) | Some(ListRow::Hunk(idx, _)) => self.files.get(*idx),
+            None => None,
+        };
+        let Some(entry) = selected else {
+            self.previewed = None;
+            self.diff_preview.clear();
+            return;
+        };
+
+        let key = (entry.path.clone(), entry.is_staged);
+        if self.previewed.as_ref() != Some(&key) {
+            state.request_staging_diff(entry.path.to_string_lossy().to_string(), entry.is_staged);
+            self.previewed = Some(key);
+        }
     }
 
     /// 获取当前选中文件的路径
@@ -318,14 +375,34 @@ impl StagingView {
         }
     }
 
-    /// 设置 diff 预览内容，同时解析 hunks
-    pub fn set_diff_preview(&mut self, content: String) {
-        // 如果当前选中的是文件，解析 hunks
-        if let Some(ListRow::File(file_idx)) = self.visible_rows.get(self.cursor) {
-            let hunks = parse_hunks(&content);
-            if let Some(entry) = self.files.get_mut(*file_idx) {
-                entry.hunks = hunks;
+    /// 选中指定路径的文件，找不到时返回 false
+    pub fn select_path(&mut self, path: &str) -> bool {
+        let Some(row) = self.visible_rows.iter().position(|row| {
+            matches!(row, ListRow::File(idx) if self.files[*idx].path == std::path::Path::new(path))
+        }) else {
+            return false;
+        };
+        self.cursor = row;
+        self.list_state.select(Some(row));
+        true
+    }
+
+    /// 设置文件的 diff 预览内容，同时解析 hunks
+    pub fn set_diff_preview(&mut self, file_path: &str, staged: bool, content: String) {
+        let entry = self
+            .files
+            .iter_mut()
+            .find(|f| f.is_staged == staged && f.path == std::path::Path::new(file_path));
+        if let Some(entry) = entry {
+            entry.hunks = if entry.untracked {
+                Vec::new()
+            } else {
+                parse_hunks(&content)
+            };
+            if entry.hunks.is_empty() {
+                entry.expanded = false;
             }
+            self.rebuild_visible_rows();
         }
         self.diff_preview = content;
         self.diff_scroll_offset = 0;
@@ -560,7 +637,7 @@ impl StagingView {
     fn render_help_bar(&self, frame: &mut Frame, area: Rect) {
         let help_text = Line::from(vec![
             Span::styled(" Space", Style::default().fg(Color::Yellow)),
-            Span::raw(":toggle  "),
+            Span::raw(":stage/unstage  "),
             Span::styled("Enter", Style::default().fg(Color::Yellow)),
             Span::raw(":expand  "),
             Span::styled("a", Style::default().fg(Color::Yellow)),
@@ -569,6 +646,10 @@ impl StagingView {
             Span::raw(":unstage all  "),
             Span::styled("c", Style::default().fg(Color::Yellow)),
             Span::raw(":commit  "),
+            Span::styled("b", Style::default().fg(Color::Yellow)),
+            Span::raw(":blame  "),
+            Span::styled("y", Style::default().fg(Color::Yellow)),
+            Span::raw(":copy path  "),
             Span::styled("r", Style::default().fg(Color::Yellow)),
             Span::raw(":refresh  "),
             Span::styled("q", Style::default().fg(Color::Yellow)),
@@ -596,6 +677,8 @@ impl Component for StagingView {
             ])
             .split(area);
 
+        self.list_area = chunks[0];
+        self.preview_area = chunks[1];
         self.render_file_list(frame, chunks[0]);
         self.render_diff_preview(frame, chunks[1]);
         self.render_help_bar(frame, chunks[2]);
@@ -606,16 +689,12 @@ impl Component for StagingView {
             KeyCode::Up | KeyCode::Char('k') => {
                 self.move_up();
                 // 请求更新 diff 预览
-                if let Some(path) = self.selected_file_path() {
-                    state.request_diff(path.to_string_lossy().to_string());
-                }
+                self.request_preview(state);
                 EventResult::Handled
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 self.move_down();
-                if let Some(path) = self.selected_file_path() {
-                    state.request_diff(path.to_string_lossy().to_string());
-                }
+                self.request_preview(state);
                 EventResult::Handled
             }
             KeyCode::Enter => {
@@ -633,10 +712,6 @@ impl Component for StagingView {
                         let file_idx = *file_idx;
                         let hunk_idx = *hunk_idx;
                         self.request_hunk_stage(state, file_idx, hunk_idx);
-                        state.add_notification(
-                            "Staging hunk...".to_string(),
-                            NotificationLevel::Info,
-                        );
                     }
                     None => {}
                 }
@@ -644,16 +719,12 @@ impl Component for StagingView {
             }
             KeyCode::Char('a') => {
                 // 暂存全部
-                self.request_stage_all(state);
-                state.add_notification("Staging all files...".to_string(), NotificationLevel::Info);
+                state.request_staging_operation(StagingOperation::StageAll);
                 EventResult::Handled
             }
             KeyCode::Char('u') => {
                 // 取消暂存全部
-                state.add_notification(
-                    "Unstaging all files...".to_string(),
-                    NotificationLevel::Info,
-                );
+                state.request_staging_operation(StagingOperation::UnstageAll);
                 EventResult::Handled
             }
             KeyCode::Char('c') => {
@@ -672,13 +743,31 @@ impl Component for StagingView {
                     EventResult::Handled
                 }
             }
-            KeyCode::Char('r') => {
-                // 刷新文件列表
-                self.refresh_file_list(state);
-                state.add_notification(
-                    "Staging view refreshed".to_string(),
-                    NotificationLevel::Info,
-                );
+            KeyCode::Char('b') => {
+                // 查看选中文件的 blame，未追踪文件没有历史
+                let file_idx = match self.visible_rows.get(self.cursor) {
+                    Some(ListRow::File(idx)) | Some(ListRow::Hunk(idx, _)) => *idx,
+                    None => return EventResult::Handled,
+                };
+                match self.files.get(file_idx) {
+                    Some(entry) if entry.untracked => {
+                        state.add_notification(
+                            "Untracked files have no blame history".to_string(),
+                            NotificationLevel::Warning,
+                        );
+                    }
+                    Some(entry) => {
+                        state.request_blame(entry.path.to_string_lossy().to_string());
+                    }
+                    None => {}
+                }
+                EventResult::Handled
+            }
+            KeyCode::Char('y') => {
+                // 复制选中文件的路径
+                if let Some(path) = self.selected_file_path() {
+                    state.request_copy("file path", path.to_string_lossy().to_string());
+                }
                 EventResult::Handled
             }
             KeyCode::PageUp | KeyCode::Char('U') => {
@@ -696,6 +785,47 @@ impl Component for StagingView {
         }
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        let position = (mouse.column, mouse.row).into();
+
+        if self.preview_area.contains(position) {
+            // diff 预览区域只响应滚轮
+            let max_offset = self.diff_preview.lines().count().saturating_sub(5);
+            match mouse.kind {
+                MouseEventKind::ScrollUp => {
+                    self.diff_scroll_offset = self.diff_scroll_offset.saturating_sub(3);
+                }
+                MouseEventKind::ScrollDown => {
+                    self.diff_scroll_offset = (self.diff_scroll_offset + 3).min(max_offset);
+                }
+                _ => return EventResult::NotHandled,
+            }
+            return EventResult::Handled;
+        }
+
+        if !self.list_area.contains(position) {
+            return EventResult::NotHandled;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(row) = bordered_row_at(self.list_area, mouse.column, mouse.row) {
+                    let index = self.list_state.offset() + row;
+                    if index < self.visible_rows.len() && index != self.cursor {
+                        self.cursor = index;
+                        self.list_state.select(Some(index));
+                        self.diff_scroll_offset = 0;
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp => self.move_up(),
+            MouseEventKind::ScrollDown => self.move_down(),
+            _ => return EventResult::NotHandled,
+        }
+        self.request_preview(state);
+        EventResult::Handled
+    }
+
     fn is_focused(&self) -> bool {
         self.focused
     }
@@ -746,6 +876,34 @@ impl ViewComponent for StagingView {
             }
         }
     }
+
+    /// 导出变更文件列表和当前的 diff 预览
+    fn export(&self) -> Vec<ExportSection> {
+        let files = self
+            .files
+            .iter()
+            .map(|file| {
+                let stage = if file.is_staged { "staged" } else { "unstaged" };
+                let mut line =
+                    format!("{} {} [{}]", file.status_char(), file.path.display(), stage);
+                if !file.hunks.is_empty() {
+                    let additions: usize = file.hunks.iter().map(|hunk| hunk.additions).sum();
+                    let deletions: usize = file.hunks.iter().map(|hunk| hunk.deletions).sum();
+                    line.push_str(&format!(" (+{} / -{})", additions, deletions));
+                }
+                line
+            })
+            .collect();
+        let mut sections = vec![ExportSection::list(&self.title(), files)];
+        if !self.diff_preview.is_empty() {
+            sections.push(ExportSection::code(
+                "Diff preview",
+                "diff",
+                self.diff_preview.lines().map(str::to_string).collect(),
+            ));
+        }
+        sections
+    }
 }
 
 #[cfg(test)]
@@ -838,6 +996,7 @@ index abc..def 100644
             path: PathBuf::from("foo.rs"),
             change_type: ChangeType::Modified,
             is_staged: false,
+            untracked: false,
             additions: 0,
             deletions: 0,
             expanded: false,
@@ -852,6 +1011,7 @@ index abc..def 100644
             path: PathBuf::from("bar.rs"),
             change_type: ChangeType::Added,
             is_staged: true,
+            untracked: false,
             additions: 0,
             deletions: 0,
             expanded: false,
@@ -874,6 +1034,7 @@ index abc..def 100644
             path: PathBuf::from("a.rs"),
             change_type: ChangeType::Modified,
             is_staged: true,
+            untracked: false,
             additions: 0,
             deletions: 0,
             expanded: false,
@@ -883,6 +1044,7 @@ index abc..def 100644
             path: PathBuf::from("b.rs"),
             change_type: ChangeType::Modified,
             is_staged: false,
+            untracked: false,
             additions: 0,
             deletions: 0,
             expanded: false,
@@ -893,4 +1055,103 @@ index abc..def 100644
         assert_eq!(view.total_count(), 2);
         assert!(view.has_staged_files());
     }
+
+    fn entry(path: &str, is_staged: bool) -> StagingEntry {
+        StagingEntry {
+            path: PathBuf::from(path),
+            change_type: ChangeType::Modified,
+            is_staged,
+            untracked: false,
+            additions: 0,
+            deletions: 0,
+            expanded: false,
+            hunks: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_toggle_requests_stage_or_unstage() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = StagingView::new();
+        view.files.push(entry("a.rs", true));
+        view.files.push(entry("b.rs", false));
+        view.rebuild_visible_rows();
+
+        view.request_toggle_staging(&mut state);
+        assert_eq!(
+            state.take_staging_operation(),
+            Some(StagingOperation::UnstageFile("a.rs".to_string()))
+        );
+
+        view.move_down();
+        view.request_toggle_staging(&mut state);
+        assert_eq!(
+            state.take_staging_operation(),
+            Some(StagingOperation::StageFile("b.rs".to_string()))
+        );
+        assert_eq!(state.take_staging_operation(), None);
+    }
+
+    #[tokio::test]
+    async fn test_hunk_request_reverses_for_staged_file() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = StagingView::new();
+        view.files.push(entry("a.rs", true));
+        view.rebuild_visible_rows();
+        view.set_diff_preview("a.rs", true, "@@ -1 +1 @@\n-old\n+new".to_string());
+
+        view.request_hunk_stage(&mut state, 0, 0);
+        match state.take_staging_operation() {
+            Some(StagingOperation::ApplyHunk {
+                file_path,
+                patch,
+                reverse,
+            }) => {
+                assert_eq!(file_path, "a.rs");
+                assert!(patch.contains("+new"));
+                assert!(reverse);
+            }
+            other => panic!("unexpected operation: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preview_requested_once_per_file() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = StagingView::new();
+        view.files.push(entry("a.rs", false));
+        view.rebuild_visible_rows();
+
+        view.request_preview(&mut state);
+        assert_eq!(state.take_staging_diff(), Some(("a.rs".to_string(), false)));
+        view.request_preview(&mut state);
+        assert_eq!(state.take_staging_diff(), None);
+    }
+
+    #[test]
+    fn test_set_diff_preview_keeps_expansion_on_refresh() {
+        let mut view = StagingView::new();
+        view.files.push(entry("a.rs", false));
+        view.rebuild_visible_rows();
+        view.set_diff_preview(
+            "a.rs",
+            false,
+            "@@ -1 +1 @@\n-a\n+b\n@@ -9 +9 @@\n-c\n+d".to_string(),
+        );
+        assert_eq!(view.files[0].hunks.len(), 2);
+
+        view.toggle_expand();
+        assert_eq!(view.visible_rows.len(), 3);
+
+        // 只剩一个 hunk 时重新加载 diff，展开的行随之更新
+        view.set_diff_preview("a.rs", false, "@@ -9 +9 @@\n-c\n+d".to_string());
+        assert_eq!(view.visible_rows.len(), 2);
+
+        // 另一侧（已暂存）的同名文件不受影响
+        view.set_diff_preview("a.rs", true, String::new());
+        assert_eq!(view.files[0].hunks.len(), 1);
+    }
 }
@@ -1,4 +1,5 @@
 // Git stash视图组件
+use crate::report::ExportSection;
 use crate::tui_unified::{
     components::base::{
         component::{Component, ViewComponent, ViewType},
@@ -8,7 +9,7 @@ use crate::tui_unified::{
     git::models::Stash,
     state::AppState,
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{layout::Rect, Frame};
 
 /// Git stash视图组件 - 显示stash列表
@@ -65,6 +66,11 @@ impl StashView {
         self.list_widget.set_items(stashes);
     }
 
+    /// 按序号选中 stash
+    pub fn select_stash(&mut self, index: u32) -> bool {
+        self.list_widget.select_where(|stash| stash.index == index)
+    }
+
     pub fn selected_stash(&self) -> Option<&Stash> {
         self.list_widget.selected_item()
     }
@@ -83,6 +89,10 @@ impl Component for StashView {
         self.list_widget.handle_key_event(key, state)
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        self.list_widget.handle_mouse_event(mouse, state)
+    }
+
     fn is_focused(&self) -> bool {
         self.list_widget.is_focused()
     }
@@ -128,4 +138,11 @@ impl ViewComponent for StashView {
     fn set_selected_index(&mut self, index: Option<usize>) {
         self.list_widget.set_selected_index(index)
     }
+
+    fn export(&self) -> Vec<ExportSection> {
+        vec![ExportSection::list(
+            &self.title(),
+            self.list_widget.formatted_lines(),
+        )]
+    }
 }
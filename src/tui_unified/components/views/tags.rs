@@ -1,4 +1,5 @@
 // Tags视图组件
+use crate::report::ExportSection;
 use crate::tui_unified::{
     components::{
         base::{
@@ -9,7 +10,7 @@ use crate::tui_unified::{
     },
     state::{git_state::Tag, AppState},
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
 use ratatui::{layout::Rect, Frame};
 
 /// Tags视图 - 显示所有标签
@@ -64,6 +65,14 @@ impl TagsView {
         self.list_widget.selected_item()
     }
 
+    /// 按名称选中标签
+    pub fn select_tag(&mut self, name: &str, state: &AppState) -> bool {
+        if self.list_widget.len() != state.repo_state.tags.len() {
+            self.refresh_tags(state);
+        }
+        self.list_widget.select_where(|tag| tag.name == name)
+    }
+
     pub fn refresh_tags(&mut self, state: &AppState) {
         let tags = state.repo_state.tags.clone();
         self.list_widget.set_items(tags);
@@ -98,6 +107,11 @@ impl Component for TagsView {
                     EventResult::NotHandled
                 }
             }
+            KeyCode::Char('n') => {
+                // 新建标签，由应用打开浮层
+                state.request_tag_creation();
+                EventResult::Handled
+            }
             KeyCode::Char('r') => {
                 // 刷新标签列表
                 self.refresh_tags(state);
@@ -114,6 +128,10 @@ impl Component for TagsView {
         }
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        self.list_widget.handle_mouse_event(mouse, state)
+    }
+
     fn is_focused(&self) -> bool {
         self.list_widget.is_focused()
     }
@@ -164,4 +182,11 @@ impl ViewComponent for TagsView {
             }
         }
     }
+
+    fn export(&self) -> Vec<ExportSection> {
+        vec![ExportSection::list(
+            &self.title(),
+            self.list_widget.formatted_lines(),
+        )]
+    }
 }
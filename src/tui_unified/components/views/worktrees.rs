@@ -0,0 +1,329 @@
+// Git worktree视图组件
+use crate::git::worktree::WorktreeInfo;
+use crate::report::ExportSection;
+use crate::tui_unified::{
+    components::base::{
+        component::{Component, ViewComponent, ViewType},
+        events::EventResult,
+    },
+    components::widgets::list::ListWidget,
+    state::{
+        app_state::{InputPurpose, NotificationLevel, WorktreeAction},
+        AppState,
+    },
+};
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    Frame,
+};
+
+/// 视图中的一个 worktree
+#[derive(Debug, Clone)]
+pub struct WorktreeEntry {
+    pub info: WorktreeInfo,
+    /// 是否是应用当前所在的 worktree
+    pub is_current: bool,
+    /// 是否有未提交的修改，目录不存在时为 None
+    pub dirty: Option<bool>,
+}
+
+impl WorktreeEntry {
+    /// 显示用的分支名：去掉 refs/heads/ 前缀，分离 HEAD 和裸仓库单独标注
+    pub fn branch_label(&self) -> String {
+        if self.info.is_bare {
+            "(bare)".to_string()
+        } else if self.info.is_detached || self.info.branch.is_empty() {
+            "(detached)".to_string()
+        } else {
+            self.info
+                .branch
+                .strip_prefix("refs/heads/")
+                .unwrap_or(&self.info.branch)
+                .to_string()
+        }
+    }
+
+    fn status_label(&self) -> String {
+        let mut status = match self.dirty {
+            _ if self.info.is_bare => "-",
+            Some(true) => "dirty",
+            Some(false) => "clean",
+            None => "missing",
+        }
+        .to_string();
+        if self.info.is_locked {
+            status.push_str(",locked");
+        }
+        status
+    }
+}
+
+/// Git worktree视图组件 - 列出所有 worktree，支持新建、切换、删除、清理和打开 shell
+pub struct WorktreesView {
+    list_widget: ListWidget<WorktreeEntry>,
+}
+
+impl Default for WorktreesView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorktreesView {
+    pub fn new() -> Self {
+        // 格式化函数：当前标记、分支、短哈希、状态和路径
+        let format_fn = Box::new(|entry: &WorktreeEntry| -> String {
+            let marker = if entry.is_current { "*" } else { " " };
+            let short_hash: String = entry.info.commit.chars().take(8).collect();
+            format!(
+                "{} {:<28} {:<8} {:<14} {}",
+                marker,
+                entry.branch_label(),
+                short_hash,
+                entry.status_label(),
+                entry.info.path.display()
+            )
+        });
+
+        // 样式函数：选中时高亮，否则按状态着色
+        let style_fn = Box::new(
+            |entry: &WorktreeEntry, is_selected: bool, is_focused: bool| -> Style {
+                if is_selected {
+                    super::shared::default_selection_style(entry, is_selected, is_focused)
+                } else if entry.dirty.is_none() && !entry.info.is_bare {
+                    Style::default().fg(Color::DarkGray)
+                } else if entry.is_current {
+                    Style::default().fg(Color::Green)
+                } else if entry.dirty == Some(true) {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            },
+        );
+
+        // 搜索函数：支持按分支和路径搜索
+        let search_fn = Box::new(|entry: &WorktreeEntry, query: &str| -> bool {
+            let query = query.to_lowercase();
+            entry.branch_label().to_lowercase().contains(&query)
+                || entry
+                    .info
+                    .path
+                    .to_string_lossy()
+                    .to_lowercase()
+                    .contains(&query)
+        });
+
+        let list_widget = ListWidget::new("Git Worktrees".to_string(), format_fn, style_fn)
+            .with_search_fn(search_fn);
+
+        Self { list_widget }
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<WorktreeEntry>) {
+        self.list_widget.set_items(entries);
+    }
+
+    pub fn selected_entry(&self) -> Option<&WorktreeEntry> {
+        self.list_widget.selected_item()
+    }
+}
+
+impl Component for WorktreesView {
+    fn name(&self) -> &str {
+        "WorktreesView"
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        self.list_widget.render(frame, area, state);
+    }
+
+    fn handle_key_event(&mut self, key: KeyEvent, state: &mut AppState) -> EventResult {
+        // 不依赖选中项的操作
+        match key.code {
+            KeyCode::Char('n') => {
+                state.request_input(
+                    InputPurpose::CreateWorktree,
+                    "New Worktree",
+                    "Branch to check out (a new branch is created if it does not exist):",
+                );
+                return EventResult::Handled;
+            }
+            KeyCode::Char('P') => {
+                state.request_worktree_action(WorktreeAction::Prune);
+                return EventResult::Handled;
+            }
+            _ => {}
+        }
+
+        let Some(entry) = self.selected_entry().cloned() else {
+            return self.list_widget.handle_key_event(key, state);
+        };
+        let path = entry.info.path.clone();
+
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('s') => {
+                if entry.is_current {
+                    state.add_notification(
+                        "Already in this worktree".to_string(),
+                        NotificationLevel::Info,
+                    );
+                } else {
+                    state.request_worktree_action(WorktreeAction::Switch(path));
+                }
+                EventResult::Handled
+            }
+            KeyCode::Char('d') => {
+                if entry.is_current || entry.info.is_bare {
+                    state.add_notification(
+                        "Cannot remove the current or main worktree".to_string(),
+                        NotificationLevel::Warning,
+                    );
+                } else {
+                    state.request_worktree_action(WorktreeAction::Remove(path));
+                }
+                EventResult::Handled
+            }
+            KeyCode::Char('o') => {
+                if entry.dirty.is_none() && !entry.info.is_bare {
+                    state.add_notification(
+                        format!("{} does not exist", path.display()),
+                        NotificationLevel::Warning,
+                    );
+                } else {
+                    state.request_worktree_action(WorktreeAction::Shell(path));
+                }
+                EventResult::Handled
+            }
+            KeyCode::Char('y') => {
+                state.request_copy("worktree path", path.display().to_string());
+                EventResult::Handled
+            }
+            _ => self.list_widget.handle_key_event(key, state),
+        }
+    }
+
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, state: &mut AppState) -> EventResult {
+        self.list_widget.handle_mouse_event(mouse, state)
+    }
+
+    fn is_focused(&self) -> bool {
+        self.list_widget.is_focused()
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.list_widget.set_focus(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        self.list_widget.can_focus()
+    }
+
+    fn min_size(&self) -> (u16, u16) {
+        self.list_widget.min_size()
+    }
+}
+
+impl ViewComponent for WorktreesView {
+    fn view_type(&self) -> ViewType {
+        ViewType::Worktrees
+    }
+
+    fn title(&self) -> String {
+        "Git Worktrees".to_string()
+    }
+
+    fn supports_search(&self) -> bool {
+        true
+    }
+
+    fn search(&mut self, query: &str) -> EventResult {
+        self.list_widget.search(query)
+    }
+
+    fn clear_search(&mut self) -> EventResult {
+        self.list_widget.clear_search()
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        self.list_widget.selected_index()
+    }
+
+    fn set_selected_index(&mut self, index: Option<usize>) {
+        self.list_widget.set_selected_index(index)
+    }
+
+    fn export(&self) -> Vec<ExportSection> {
+        vec![ExportSection::list(
+            &self.title(),
+            self.list_widget.formatted_lines(),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(path: &str, branch: &str, is_current: bool) -> WorktreeEntry {
+        WorktreeEntry {
+            info: WorktreeInfo::new(
+                PathBuf::from(path),
+                branch.to_string(),
+                "0123456789abcdef".to_string(),
+                false,
+                false,
+            ),
+            is_current,
+            dirty: Some(false),
+        }
+    }
+
+    #[test]
+    fn test_labels() {
+        let mut feature = entry("/repo-feature", "refs/heads/feature/x", false);
+        assert_eq!(feature.branch_label(), "feature/x");
+        assert_eq!(feature.status_label(), "clean");
+
+        feature.dirty = None;
+        feature.info.is_locked = true;
+        assert_eq!(feature.status_label(), "missing,locked");
+
+        feature.info.is_detached = true;
+        assert_eq!(feature.branch_label(), "(detached)");
+    }
+
+    #[tokio::test]
+    async fn test_remove_requires_confirmation() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut view = WorktreesView::new();
+        view.set_entries(vec![
+            entry("/repo", "refs/heads/main", true),
+            entry("/repo-feature", "refs/heads/feature", false),
+        ]);
+        view.set_focus(true);
+
+        // 当前 worktree 不能删除
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('d')), &mut state);
+        assert!(state.take_worktree_action(true).is_none());
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Down), &mut state);
+        view.handle_key_event(KeyEvent::from(KeyCode::Char('d')), &mut state);
+        assert!(state.is_modal_active());
+        assert!(state.take_worktree_action(false).is_none());
+        assert_eq!(
+            state.take_worktree_action(true),
+            Some(WorktreeAction::Remove(PathBuf::from("/repo-feature")))
+        );
+
+        view.handle_key_event(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert_eq!(
+            state.take_worktree_action(false),
+            Some(WorktreeAction::Switch(PathBuf::from("/repo-feature")))
+        );
+    }
+}
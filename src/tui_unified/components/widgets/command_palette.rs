@@ -0,0 +1,401 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::tui_unified::{
+    algorithms::{FuzzyMatch, SmartSearchEngine},
+    config::KeyBindings,
+    state::app_state::ViewType,
+};
+
+/// 面板中可以执行的操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteCommand {
+    SwitchView(ViewType),
+    /// 切换到视图后交给视图处理的按键
+    ViewKey(ViewType, char),
+    Find,
+    Refresh,
+    AiCommit,
+    Review,
+    Refactor,
+    CycleTheme,
+    ToggleZen,
+    NextLayout,
+    Settings,
+    Notifications,
+    Export,
+    ToggleWatch,
+    SwitchRepo,
+    StorageStats,
+    Help,
+    Quit,
+}
+
+/// 面板中的一条命令
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteEntry {
+    pub command: PaletteCommand,
+    pub title: String,
+    /// 对应的快捷键，帮助用户记住按键
+    pub hint: String,
+}
+
+/// 按键处理结果，由应用负责执行
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteAction {
+    None,
+    Close,
+    Run(PaletteCommand),
+}
+
+const VIEWS: &[(ViewType, &str, &str)] = &[
+    (ViewType::GitLog, "Git log", "1"),
+    (ViewType::Branches, "Branches", "2"),
+    (ViewType::Tags, "Tags", "3"),
+    (ViewType::Remotes, "Remotes", "4"),
+    (ViewType::Stash, "Stash", "5"),
+    (ViewType::QueryHistory, "Query history", "6"),
+    (ViewType::Staging, "Staging", "7"),
+    (ViewType::Graph, "Commit graph", "8"),
+    (ViewType::Reflog, "Reflog", "9"),
+    (ViewType::Worktrees, "Worktrees", "0"),
+    (ViewType::Review, "AI review", "V"),
+    (ViewType::Hotspots, "Churn hotspots", "H"),
+];
+
+const VIEW_COMMANDS: &[(ViewType, char, &str)] = &[
+    (ViewType::Branches, 'n', "Create branch"),
+    (ViewType::Branches, 'F', "Fetch"),
+    (ViewType::Branches, 'p', "Pull current branch"),
+    (ViewType::Remotes, 'A', "Fetch all remotes"),
+    (ViewType::Remotes, 'U', "Push current branch"),
+    (ViewType::Tags, 'n', "Create tag"),
+    (ViewType::Staging, 'a', "Stage all changes"),
+    (ViewType::Staging, 'u', "Unstage all changes"),
+    (ViewType::GitLog, 'C', "Cherry-pick marked commits"),
+    (ViewType::Worktrees, 'n', "Create worktree"),
+    (ViewType::Worktrees, 'P', "Prune worktrees"),
+    (ViewType::Review, 'w', "Review working tree"),
+    (ViewType::Review, 'C', "Review selected commit"),
+];
+
+/// 按当前按键配置生成所有命令
+pub fn palette_entries(bindings: &KeyBindings) -> Vec<PaletteEntry> {
+    let key = |action: &str| {
+        bindings
+            .entries()
+            .into_iter()
+            .find(|(name, _, _)| *name == action)
+            .map(|(_, key, _)| key.to_string())
+            .unwrap_or_default()
+    };
+    let entry = |command, title: &str, hint: String| PaletteEntry {
+        command,
+        title: title.to_string(),
+        hint,
+    };
+
+    let mut entries = vec![
+        entry(
+            PaletteCommand::AiCommit,
+            "Generate AI commit",
+            key("ai_commit"),
+        ),
+        entry(PaletteCommand::Review, "AI code review", key("review")),
+        entry(
+            PaletteCommand::Refactor,
+            "AI refactor suggestions",
+            key("refactor"),
+        ),
+        entry(
+            PaletteCommand::Find,
+            "Find commits, branches, files…",
+            key("find"),
+        ),
+        entry(
+            PaletteCommand::Refresh,
+            "Refresh current view",
+            key("refresh"),
+        ),
+    ];
+    entries.extend(VIEWS.iter().map(|(view, name, hint)| {
+        entry(
+            PaletteCommand::SwitchView(*view),
+            &format!("Go to {}", name),
+            hint.to_string(),
+        )
+    }));
+    entries.extend(VIEW_COMMANDS.iter().map(|(view, key, title)| {
+        let view_name = VIEWS
+            .iter()
+            .find(|(v, _, _)| v == view)
+            .map_or("", |(_, name, _)| name);
+        entry(
+            PaletteCommand::ViewKey(*view, *key),
+            &format!("{}: {}", view_name, title),
+            key.to_string(),
+        )
+    }));
+    entries.extend([
+        entry(
+            PaletteCommand::CycleTheme,
+            "Cycle color theme",
+            key("theme"),
+        ),
+        entry(PaletteCommand::ToggleZen, "Toggle zen mode", key("zen")),
+        entry(
+            PaletteCommand::NextLayout,
+            "Next layout preset",
+            "L".to_string(),
+        ),
+        entry(PaletteCommand::Settings, "Open settings", key("settings")),
+        entry(
+            PaletteCommand::Notifications,
+            "Show notifications",
+            "N".to_string(),
+        ),
+        entry(
+            PaletteCommand::Export,
+            "Export current view to file",
+            "E".to_string(),
+        ),
+        entry(
+            PaletteCommand::ToggleWatch,
+            "Toggle auto-refresh on file changes",
+            "W".to_string(),
+        ),
+        entry(
+            PaletteCommand::SwitchRepo,
+            "Switch repository",
+            "O".to_string(),
+        ),
+        entry(
+            PaletteCommand::StorageStats,
+            "Show storage stats",
+            String::new(),
+        ),
+        entry(PaletteCommand::Help, "Keyboard shortcuts", "?".to_string()),
+        entry(PaletteCommand::Quit, "Quit", key("quit")),
+    ]);
+    entries
+}
+
+/// 命令面板浮层 - 模糊搜索并执行所有操作，无需记住快捷键
+pub struct CommandPalette {
+    open: bool,
+    query: String,
+    engine: SmartSearchEngine,
+    entries: Vec<PaletteEntry>,
+    results: Vec<(usize, FuzzyMatch)>,
+    selected: usize,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandPalette {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            query: String::new(),
+            engine: SmartSearchEngine::fuzzy(),
+            entries: Vec::new(),
+            results: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    pub fn open(&mut self, entries: Vec<PaletteEntry>) {
+        self.open = true;
+        self.query.clear();
+        self.entries = entries;
+        self.update_results();
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.entries.clear();
+        self.results.clear();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn selected_entry(&self) -> Option<&PaletteEntry> {
+        self.results
+            .get(self.selected)
+            .map(|(index, _)| &self.entries[*index])
+    }
+
+    fn update_results(&mut self) {
+        self.engine.set_query(self.query.clone());
+        self.results = self
+            .engine
+            .search_ranked(&self.entries, |entry| entry.title.as_str());
+        self.selected = 0;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if !self.results.is_empty() {
+            let last = self.results.len() as isize - 1;
+            self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PaletteAction {
+        if !self.open {
+            return PaletteAction::Close;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return PaletteAction::Close,
+            KeyCode::Enter => {
+                return match self.selected_entry() {
+                    Some(entry) => PaletteAction::Run(entry.command),
+                    None => PaletteAction::Close,
+                }
+            }
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('p') | KeyCode::Char('k') if ctrl => self.move_selection(-1),
+            KeyCode::Char('n') | KeyCode::Char('j') if ctrl => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Backspace if !self.query.is_empty() => {
+                self.query.pop();
+                self.update_results();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.update_results();
+            }
+            _ => {}
+        }
+        PaletteAction::None
+    }
+
+    fn result_line(entry: &PaletteEntry, m: &FuzzyMatch, width: usize) -> Line<'static> {
+        let highlight = Style::default()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD);
+        let mut matched = m.positions.iter().peekable();
+        let mut spans: Vec<Span> = entry
+            .title
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if matched.next_if_eq(&&i).is_some() {
+                    Span::styled(c.to_string(), highlight)
+                } else {
+                    Span::raw(c.to_string())
+                }
+            })
+            .collect();
+
+        // 快捷键右对齐
+        let padding = width.saturating_sub(entry.title.chars().count() + entry.hint.len() + 1);
+        spans.push(Span::raw(" ".repeat(padding)));
+        spans.push(Span::styled(
+            entry.hint.clone(),
+            Style::default().fg(Color::DarkGray),
+        ));
+        Line::from(spans)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.open {
+            return;
+        }
+
+        let width = 64.min(area.width);
+        let height = 20.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + area.height / 6,
+            width,
+            height: height.min(area.height - area.height / 6),
+        };
+        frame.render_widget(Clear, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(1)])
+            .split(popup);
+
+        frame.render_widget(
+            Paragraph::new(format!("> {}▏", self.query)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Command Palette")
+                    .border_style(Style::default().fg(Color::Yellow)),
+            ),
+            chunks[0],
+        );
+
+        let inner_width = chunks[1].width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .map(|(index, m)| {
+                ListItem::new(Self::result_line(&self.entries[*index], m, inner_width))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(
+                        "{}/{} · Enter-run, Esc-close",
+                        self.results.len(),
+                        self.entries.len()
+                    ))
+                    .border_style(Style::default().fg(Color::Yellow)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let mut list_state = ListState::default();
+        if !self.results.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_and_run_command() {
+        let mut bindings = KeyBindings::default();
+        bindings.set("ai_commit", 'g').unwrap();
+        let entries = palette_entries(&bindings);
+        let ai_commit = entries
+            .iter()
+            .find(|entry| entry.command == PaletteCommand::AiCommit)
+            .unwrap();
+        assert_eq!(ai_commit.hint, "g");
+
+        let mut palette = CommandPalette::new();
+        palette.open(entries);
+        for c in "crbranch".chars() {
+            palette.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        assert_eq!(
+            palette.handle_key(KeyEvent::from(KeyCode::Enter)),
+            PaletteAction::Run(PaletteCommand::ViewKey(ViewType::Branches, 'n'))
+        );
+        assert_eq!(
+            palette.handle_key(KeyEvent::from(KeyCode::Esc)),
+            PaletteAction::Close
+        );
+    }
+}
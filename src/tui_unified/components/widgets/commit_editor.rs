@@ -1,17 +1,149 @@
+use crate::core::ai::validation::{COMMIT_FORMAT_REGEX, COMMIT_TYPES};
 use crate::tui_unified::{
     components::base::{component::Component, events::EventResult},
     state::AppState,
 };
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use once_cell::sync::Lazy;
 use ratatui::{prelude::*, widgets::*};
+use regex::Regex;
 
-/// Commit 消息编辑器组件 - 支持多行编辑
+/// 编辑器默认标题
+pub const DEFAULT_TITLE: &str = "Edit Commit Message";
+
+/// 首行的最大长度，与 `validate_commit_message` 一致
+pub const MAX_HEADER_LEN: usize = 100;
+/// 超过该长度时计数器变黄提醒
+const WARN_HEADER_LEN: usize = 72;
+/// 最多记住的最近使用的 scope 数量
+const MAX_RECENT_SCOPES: usize = 20;
+
+/// 首行：可选的前缀（gitmoji、工单号）、类型、scope 和主题
+static HEADER_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^(?:(?P<prefix>.*?\s))?(?P<type>{})(?:\((?P<scope>[^)]*)\))?:\s*(?P<subject>.*)$",
+        COMMIT_TYPES.join("|")
+    ))
+    .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EditorField {
+    Type,
+    Scope,
+    Subject,
+    Body,
+}
+
+/// 字符串中第 `col` 个字符的字节位置
+fn byte_index(s: &str, col: usize) -> usize {
+    s.char_indices().nth(col).map_or(s.len(), |(i, _)| i)
+}
+
+/// 单行输入框，光标按字符计数
+#[derive(Debug, Clone, Default)]
+struct LineInput {
+    text: String,
+    cursor: usize,
+}
+
+impl LineInput {
+    fn set(&mut self, text: &str) {
+        self.text = text.to_string();
+        self.cursor = text.chars().count();
+    }
+
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn insert(&mut self, c: char) {
+        self.text.insert(byte_index(&self.text, self.cursor), c);
+        self.cursor += 1;
+    }
+
+    fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.text.remove(byte_index(&self.text, self.cursor));
+        }
+    }
+
+    /// 处理光标移动和编辑，返回是否处理了按键
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char(c) => self.insert(c),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.len()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.len(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// 从提交主题中提取最近使用的 scope，按出现顺序去重
+pub fn recent_scopes<'a>(subjects: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut scopes: Vec<String> = Vec::new();
+    for subject in subjects {
+        let Some(scope) = HEADER_REGEX
+            .captures(subject)
+            .and_then(|caps| caps.name("scope"))
+            .map(|m| m.as_str().trim())
+        else {
+            continue;
+        };
+        if !scope.is_empty() && !scopes.iter().any(|s| s == scope) {
+            scopes.push(scope.to_string());
+            if scopes.len() == MAX_RECENT_SCOPES {
+                break;
+            }
+        }
+    }
+    scopes
+}
+
+/// 校验提交信息是否符合 Conventional Commits 格式，忽略 gitmoji 等前缀
+pub fn validate_message(message: &str) -> std::result::Result<(), String> {
+    let header = message.lines().next().unwrap_or("");
+    let Some(caps) = HEADER_REGEX.captures(header) else {
+        return Err("Header must look like <type>(<scope>): <subject>".to_string());
+    };
+    if caps["subject"].trim().is_empty() {
+        return Err("Subject cannot be empty".to_string());
+    }
+    let len = header.chars().count();
+    if len > MAX_HEADER_LEN {
+        return Err(format!(
+            "Header is too long ({} > {} characters)",
+            len, MAX_HEADER_LEN
+        ));
+    }
+    let prefix_len = caps.name("prefix").map_or(0, |m| m.end());
+    if !COMMIT_FORMAT_REGEX.is_match(&header[prefix_len..]) {
+        return Err("Header must look like <type>(<scope>): <subject>".to_string());
+    }
+    Ok(())
+}
+
+/// Commit 消息编辑器组件 - 按 Conventional Commits 结构分别编辑类型、scope、主题和正文
 pub struct CommitEditor {
-    lines: Vec<String>,
+    /// gitmoji、工单号等首行前缀，原样保留
+    prefix: String,
+    /// 选中的类型在 `COMMIT_TYPES` 中的位置，首行无法解析时为空
+    commit_type: Option<usize>,
+    scope: LineInput,
+    subject: LineInput,
+    body: Vec<String>,
+    field: EditorField,
+    title: String,
     cursor_line: usize,
     cursor_col: usize,
     focused: bool,
     scroll_offset: usize,
+    recent_scopes: Vec<String>,
 }
 
 impl Default for CommitEditor {
@@ -23,103 +155,288 @@ impl Default for CommitEditor {
 impl CommitEditor {
     pub fn new() -> Self {
         Self {
-            lines: vec![String::new()],
+            prefix: String::new(),
+            commit_type: None,
+            scope: LineInput::default(),
+            subject: LineInput::default(),
+            body: vec![String::new()],
+            field: EditorField::Subject,
+            title: DEFAULT_TITLE.to_string(),
             cursor_line: 0,
             cursor_col: 0,
             focused: false,
             scroll_offset: 0,
+            recent_scopes: Vec::new(),
         }
     }
 
+    /// 把完整的提交信息拆分到各个字段
     pub fn set_content(&mut self, content: &str) {
-        self.lines = content.lines().map(|s| s.to_string()).collect();
-        if self.lines.is_empty() {
-            self.lines.push(String::new());
+        let mut lines = content.lines();
+        let header = lines.next().unwrap_or("");
+        match HEADER_REGEX.captures(header) {
+            Some(caps) => {
+                self.prefix = caps
+                    .name("prefix")
+                    .map_or(String::new(), |m| m.as_str().to_string());
+                self.commit_type = COMMIT_TYPES.iter().position(|t| *t == &caps["type"]);
+                self.scope
+                    .set(caps.name("scope").map_or("", |m| m.as_str()));
+                self.subject.set(&caps["subject"]);
+            }
+            None => {
+                self.prefix.clear();
+                self.commit_type = None;
+                self.scope.set("");
+                self.subject.set(header);
+            }
         }
+
+        self.body = lines
+            .skip_while(|line| line.trim().is_empty())
+            .map(str::to_string)
+            .collect();
+        if self.body.is_empty() {
+            self.body.push(String::new());
+        }
+        self.field = EditorField::Subject;
         self.cursor_line = 0;
         self.cursor_col = 0;
         self.scroll_offset = 0;
     }
 
+    /// 按字段拼出完整的提交信息
     pub fn get_content(&self) -> String {
-        self.lines.join("\n")
+        let header = self.header();
+        let body = self.body.join("\n");
+        if body.trim().is_empty() {
+            header
+        } else {
+            format!("{}\n\n{}", header, body.trim_end())
+        }
+    }
+
+    fn header(&self) -> String {
+        match self.commit_type {
+            Some(index) => {
+                let scope = self.scope.text.trim();
+                let scope = if scope.is_empty() {
+                    String::new()
+                } else {
+                    format!("({})", scope)
+                };
+                format!(
+                    "{}{}{}: {}",
+                    self.prefix, COMMIT_TYPES[index], scope, self.subject.text
+                )
+            }
+            None => format!("{}{}", self.prefix, self.subject.text),
+        }
+    }
+
+    /// 只填入仍为空的字段，保留用户已经输入的内容；主题为空时使用生成的整个首行
+    pub fn fill_missing(&mut self, content: &str) {
+        let mut generated = Self::new();
+        generated.set_content(content);
+
+        if self.subject.text.trim().is_empty() {
+            self.prefix = generated.prefix;
+            self.commit_type = generated.commit_type.or(self.commit_type);
+            self.subject = generated.subject;
+        }
+        if self.scope.text.trim().is_empty() {
+            self.scope = generated.scope;
+        }
+        if self.body.iter().all(|line| line.trim().is_empty()) {
+            self.body = generated.body;
+            self.cursor_line = 0;
+            self.cursor_col = 0;
+        }
+    }
+
+    /// 设置 scope 自动补全的候选，越靠前越优先
+    pub fn set_recent_scopes(&mut self, scopes: Vec<String>) {
+        self.recent_scopes = scopes;
+    }
+
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.commit_type.is_none() {
+            return Err("Pick a commit type".to_string());
+        }
+        validate_message(&self.get_content())
+    }
+
+    /// 设置边框标题，如生成进度
+    pub fn set_title(&mut self, title: &str) {
+        self.title = title.to_string();
     }
 
     pub fn set_focused(&mut self, focused: bool) {
         self.focused = focused;
     }
 
+    /// 以当前输入为前缀的第一个最近 scope
+    fn scope_suggestion(&self) -> Option<&str> {
+        let typed = self.scope.text.to_lowercase();
+        self.recent_scopes
+            .iter()
+            .find(|scope| scope.to_lowercase().starts_with(&typed) && scope.len() > typed.len())
+            .map(String::as_str)
+    }
+
+    fn move_field(&mut self, forward: bool) {
+        let fields = [
+            EditorField::Type,
+            EditorField::Scope,
+            EditorField::Subject,
+            EditorField::Body,
+        ];
+        let index = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        self.field = if forward {
+            fields[(index + 1).min(fields.len() - 1)]
+        } else {
+            fields[index.saturating_sub(1)]
+        };
+    }
+
+    /// 切换类型，第一次切换时从 feat 开始
+    fn cycle_type(&mut self, forward: bool) {
+        let count = COMMIT_TYPES.len();
+        self.commit_type = Some(match self.commit_type {
+            None => 0,
+            Some(index) if forward => (index + 1) % count,
+            Some(index) => (index + count - 1) % count,
+        });
+    }
+
+    /// 跳到当前类型之后第一个以该字母开头的类型
+    fn jump_to_type(&mut self, c: char) {
+        let count = COMMIT_TYPES.len();
+        let start = self.commit_type.map_or(0, |index| index + 1);
+        if let Some(index) = (0..count)
+            .map(|offset| (start + offset) % count)
+            .find(|index| COMMIT_TYPES[*index].starts_with(c))
+        {
+            self.commit_type = Some(index);
+        }
+    }
+
+    fn handle_type_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Left => self.cycle_type(false),
+            KeyCode::Right | KeyCode::Char(' ') => self.cycle_type(true),
+            KeyCode::Char(c) => self.jump_to_type(c.to_ascii_lowercase()),
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_scope_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            // 光标在末尾时右方向键接受补全
+            KeyCode::Right | KeyCode::End if self.scope.cursor == self.scope.len() => {
+                if let Some(scope) = self.scope_suggestion().map(str::to_string) {
+                    self.scope.set(&scope);
+                }
+                true
+            }
+            KeyCode::Char('(') | KeyCode::Char(')') | KeyCode::Char(':') => true,
+            code => self.scope.handle_key(code),
+        }
+    }
+
     fn handle_input_char(&mut self, c: char) {
-        if self.cursor_line < self.lines.len() {
-            self.lines[self.cursor_line].insert(self.cursor_col, c);
+        if self.cursor_line < self.body.len() {
+            let line = &mut self.body[self.cursor_line];
+            line.insert(byte_index(line, self.cursor_col), c);
             self.cursor_col += 1;
         }
     }
 
     fn handle_backspace(&mut self) {
         if self.cursor_col > 0 {
-            if self.cursor_line < self.lines.len() {
+            if self.cursor_line < self.body.len() {
                 self.cursor_col -= 1;
-                self.lines[self.cursor_line].remove(self.cursor_col);
+                let line = &mut self.body[self.cursor_line];
+                line.remove(byte_index(line, self.cursor_col));
             }
         } else if self.cursor_line > 0 {
             // 合并到上一行
-            let current_line = self.lines.remove(self.cursor_line);
+            let current_line = self.body.remove(self.cursor_line);
             self.cursor_line -= 1;
-            self.cursor_col = self.lines[self.cursor_line].len();
-            self.lines[self.cursor_line].push_str(&current_line);
+            self.cursor_col = self.body[self.cursor_line].chars().count();
+            self.body[self.cursor_line].push_str(&current_line);
         }
     }
 
     fn handle_enter(&mut self) {
-        if self.cursor_line < self.lines.len() {
-            let current_line = &self.lines[self.cursor_line];
-            let right_part = current_line[self.cursor_col..].to_string();
-            self.lines[self.cursor_line].truncate(self.cursor_col);
-            self.lines.insert(self.cursor_line + 1, right_part);
+        if self.cursor_line < self.body.len() {
+            let line = &mut self.body[self.cursor_line];
+            let right_part = line.split_off(byte_index(line, self.cursor_col));
+            self.body.insert(self.cursor_line + 1, right_part);
             self.cursor_line += 1;
             self.cursor_col = 0;
         }
     }
 
+    fn line_len(&self, line: usize) -> usize {
+        self.body.get(line).map_or(0, |line| line.chars().count())
+    }
+
     fn move_cursor_left(&mut self) {
         if self.cursor_col > 0 {
             self.cursor_col -= 1;
         } else if self.cursor_line > 0 {
             self.cursor_line -= 1;
-            self.cursor_col = if self.cursor_line < self.lines.len() {
-                self.lines[self.cursor_line].len()
-            } else {
-                0
-            };
+            self.cursor_col = self.line_len(self.cursor_line);
         }
     }
 
     fn move_cursor_right(&mut self) {
-        if self.cursor_line < self.lines.len() {
-            if self.cursor_col < self.lines[self.cursor_line].len() {
-                self.cursor_col += 1;
-            } else if self.cursor_line + 1 < self.lines.len() {
-                self.cursor_line += 1;
-                self.cursor_col = 0;
-            }
+        if self.cursor_col < self.line_len(self.cursor_line) {
+            self.cursor_col += 1;
+        } else if self.cursor_line + 1 < self.body.len() {
+            self.cursor_line += 1;
+            self.cursor_col = 0;
         }
     }
 
-    fn move_cursor_up(&mut self) {
-        if self.cursor_line > 0 {
-            self.cursor_line -= 1;
-            if self.cursor_line < self.lines.len() {
-                self.cursor_col = self.cursor_col.min(self.lines[self.cursor_line].len());
+    fn handle_body_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char(c) => self.handle_input_char(c),
+            KeyCode::Backspace => self.handle_backspace(),
+            KeyCode::Enter => self.handle_enter(),
+            KeyCode::Left => self.move_cursor_left(),
+            KeyCode::Right => self.move_cursor_right(),
+            // 第一行按上方向键回到主题
+            KeyCode::Up if self.cursor_line == 0 => self.move_field(false),
+            KeyCode::Up => {
+                self.cursor_line -= 1;
+                self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_line));
+            }
+            KeyCode::Down => {
+                if self.cursor_line + 1 < self.body.len() {
+                    self.cursor_line += 1;
+                    self.cursor_col = self.cursor_col.min(self.line_len(self.cursor_line));
+                }
             }
+            KeyCode::Home => self.cursor_col = 0,
+            KeyCode::End => self.cursor_col = self.line_len(self.cursor_line),
+            _ => return false,
         }
+        true
     }
 
-    fn move_cursor_down(&mut self) {
-        if self.cursor_line + 1 < self.lines.len() {
-            self.cursor_line += 1;
-            self.cursor_col = self.cursor_col.min(self.lines[self.cursor_line].len());
-        }
+    fn field_block(&self, title: String, field: EditorField) -> Block<'static> {
+        let style = if self.focused && self.field == field {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(style)
     }
 }
 
@@ -134,104 +451,156 @@ impl Component for CommitEditor {
         } else {
             Style::default().fg(Color::Gray)
         };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(self.title.as_str())
+            .border_style(border_style);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
 
-        let text_style = if self.focused {
-            Style::default().fg(Color::White).bg(Color::DarkGray)
-        } else {
-            Style::default().fg(Color::Gray)
-        };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(16), Constraint::Min(10)])
+            .split(rows[0]);
 
-        // 计算可见区域
-        let inner_height = area.height.saturating_sub(2) as usize;
-        let visible_lines = &self.lines
-            [self.scroll_offset..(self.scroll_offset + inner_height).min(self.lines.len())];
+        let commit_type = self
+            .commit_type
+            .map_or("(none)", |index| COMMIT_TYPES[index]);
+        frame.render_widget(
+            Paragraph::new(format!("◀ {} ▶", commit_type))
+                .block(self.field_block("Type".to_string(), EditorField::Type)),
+            top[0],
+        );
 
-        // 构建显示文本
-        let mut text_lines = Vec::new();
-        for line in visible_lines {
-            text_lines.push(Line::from(Span::styled(line.clone(), text_style)));
+        let mut scope_spans = vec![Span::raw(self.scope.text.clone())];
+        if let Some(suggestion) = self.scope_suggestion() {
+            let typed = self.scope.len();
+            scope_spans.push(Span::styled(
+                suggestion.chars().skip(typed).collect::<String>(),
+                Style::default().fg(Color::DarkGray),
+            ));
         }
+        frame.render_widget(
+            Paragraph::new(Line::from(scope_spans))
+                .block(self.field_block("Scope (→ complete)".to_string(), EditorField::Scope)),
+            top[1],
+        );
 
-        let paragraph = Paragraph::new(text_lines)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Edit Commit Message")
-                    .border_style(border_style),
-            )
-            .wrap(Wrap { trim: false });
+        // 计数器统计整个首行，包括类型和前缀
+        let header_len = self.header().chars().count();
+        let counter_color = if header_len > MAX_HEADER_LEN {
+            Color::Red
+        } else if header_len > WARN_HEADER_LEN {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        let subject_block =
+            self.field_block(String::new(), EditorField::Subject)
+                .title(Line::from(vec![
+                    Span::raw("Subject "),
+                    Span::styled(
+                        format!("{}/{}", header_len, MAX_HEADER_LEN),
+                        Style::default().fg(counter_color),
+                    ),
+                ]));
+        frame.render_widget(
+            Paragraph::new(self.subject.text.clone()).block(subject_block),
+            rows[1],
+        );
+
+        // 保证光标所在行可见
+        let body_height = rows[2].height.saturating_sub(2) as usize;
+        if self.cursor_line < self.scroll_offset {
+            self.scroll_offset = self.cursor_line;
+        } else if body_height > 0 && self.cursor_line >= self.scroll_offset + body_height {
+            self.scroll_offset = self.cursor_line + 1 - body_height;
+        }
+        let body: Vec<Line> = self
+            .body
+            .iter()
+            .skip(self.scroll_offset)
+            .take(body_height)
+            .map(|line| Line::raw(line.clone()))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(body).block(self.field_block("Body".to_string(), EditorField::Body)),
+            rows[2],
+        );
 
-        frame.render_widget(paragraph, area);
+        let footer = match self.validate() {
+            Ok(()) => Line::from(vec![
+                Span::styled("✓ ", Style::default().fg(Color::Green)),
+                Span::styled(
+                    "↑/↓ field, ←/→ type, Ctrl+G AI fill",
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]),
+            Err(e) => Line::styled(format!("✗ {}", e), Style::default().fg(Color::Red)),
+        };
+        frame.render_widget(Paragraph::new(footer), rows[3]);
 
         // 渲染光标
-        if self.focused
-            && self.cursor_line >= self.scroll_offset
-            && self.cursor_line < self.scroll_offset + inner_height
+        if !self.focused {
+            return;
+        }
+        let (field_area, col, line) = match self.field {
+            EditorField::Type => return,
+            EditorField::Scope => (top[1], self.scope.cursor, 0),
+            EditorField::Subject => (rows[1], self.subject.cursor, 0),
+            EditorField::Body => (
+                rows[2],
+                self.cursor_col,
+                self.cursor_line - self.scroll_offset,
+            ),
+        };
+        let cursor_x = field_area.x + 1 + col as u16;
+        let cursor_y = field_area.y + 1 + line as u16;
+        if cursor_x < field_area.x + field_area.width.saturating_sub(1)
+            && cursor_y < field_area.y + field_area.height.saturating_sub(1)
         {
-            let cursor_x = area.x + 1 + self.cursor_col as u16;
-            let cursor_y = area.y + 1 + (self.cursor_line - self.scroll_offset) as u16;
-
-            if cursor_x < area.x + area.width.saturating_sub(1)
-                && cursor_y < area.y + area.height.saturating_sub(1)
-            {
-                frame.set_cursor(cursor_x, cursor_y);
-            }
+            frame.set_cursor(cursor_x, cursor_y);
         }
     }
 
     fn handle_key_event(&mut self, key: KeyEvent, _state: &mut AppState) -> EventResult {
-        if !self.focused {
+        if !self.focused || key.modifiers.contains(KeyModifiers::CONTROL) {
             return EventResult::NotHandled;
         }
 
-        match key.code {
-            KeyCode::Char(c) => {
-                self.handle_input_char(c);
-                EventResult::Handled
-            }
-            KeyCode::Backspace => {
-                self.handle_backspace();
-                EventResult::Handled
-            }
-            KeyCode::Enter => {
-                self.handle_enter();
-                EventResult::Handled
-            }
-            KeyCode::Left => {
-                self.move_cursor_left();
-                EventResult::Handled
-            }
-            KeyCode::Right => {
-                self.move_cursor_right();
-                EventResult::Handled
-            }
-            KeyCode::Up => {
-                self.move_cursor_up();
-                EventResult::Handled
-            }
-            KeyCode::Down => {
-                self.move_cursor_down();
-                EventResult::Handled
-            }
-            KeyCode::Home => {
-                self.cursor_col = 0;
-                EventResult::Handled
-            }
-            KeyCode::End => {
-                if self.cursor_line < self.lines.len() {
-                    self.cursor_col = self.lines[self.cursor_line].len();
-                }
-                EventResult::Handled
-            }
-            KeyCode::Tab => {
-                // 退出编辑模式，由父组件处理
-                EventResult::NotHandled
+        // Tab 退出编辑、Esc 取消编辑，由父组件处理
+        if matches!(key.code, KeyCode::Tab | KeyCode::Esc) {
+            return EventResult::NotHandled;
+        }
+
+        let handled = match (self.field, key.code) {
+            (EditorField::Body, code) => self.handle_body_key(code),
+            (_, KeyCode::Up) => {
+                self.move_field(false);
+                true
             }
-            KeyCode::Esc => {
-                // 取消编辑，由父组件处理
-                EventResult::NotHandled
+            (_, KeyCode::Down | KeyCode::Enter) => {
+                self.move_field(true);
+                true
             }
-            _ => EventResult::NotHandled,
+            (EditorField::Type, code) => self.handle_type_key(code),
+            (EditorField::Scope, code) => self.handle_scope_key(code),
+            (EditorField::Subject, code) => self.subject.handle_key(code),
+        };
+
+        if handled {
+            EventResult::Handled
+        } else {
+            EventResult::NotHandled
         }
     }
 
@@ -243,3 +612,70 @@ impl Component for CommitEditor {
         self.focused = focused;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(editor: &mut CommitEditor, state: &mut AppState, codes: &[KeyCode]) {
+        for code in codes {
+            editor.handle_key_event(KeyEvent::from(*code), state);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_structured_editing_round_trip() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut editor = CommitEditor::new();
+        editor.set_focused(true);
+
+        // gitmoji 前缀和正文原样保留
+        editor.set_content("✨ feat(api): add login\n\nLonger body");
+        assert_eq!(
+            editor.get_content(),
+            "✨ feat(api): add login\n\nLonger body"
+        );
+        assert!(editor.validate().is_ok());
+
+        // 类型按首字母跳转，scope 按最近使用补全
+        editor.set_recent_scopes(recent_scopes(["fix(tui): a", "docs(readme): b"]));
+        editor.scope.set("");
+        press(&mut editor, &mut state, &[KeyCode::Up, KeyCode::Up]);
+        press(&mut editor, &mut state, &[KeyCode::Char('f')]);
+        press(
+            &mut editor,
+            &mut state,
+            &[KeyCode::Down, KeyCode::Char('r'), KeyCode::Right],
+        );
+        assert_eq!(
+            editor.get_content(),
+            "✨ fix(readme): add login\n\nLonger body"
+        );
+
+        // 主题为空时不允许提交
+        press(&mut editor, &mut state, &[KeyCode::Down]);
+        for _ in 0.."add login".len() {
+            press(&mut editor, &mut state, &[KeyCode::Backspace]);
+        }
+        assert_eq!(
+            editor.validate(),
+            Err("Subject cannot be empty".to_string())
+        );
+
+        // AI 填充只补全空字段
+        editor.fill_missing("feat(core): generated\n\nAI body");
+        assert_eq!(
+            editor.get_content(),
+            "feat(readme): generated\n\nLonger body"
+        );
+    }
+
+    #[test]
+    fn test_validate_message() {
+        assert!(validate_message("🐛 fix: crash on start").is_ok());
+        assert!(validate_message("[ABC-1] feat(ui): new panel").is_ok());
+        assert!(validate_message("update stuff").is_err());
+        assert!(validate_message(&format!("feat: {}", "x".repeat(100))).is_err());
+    }
+}
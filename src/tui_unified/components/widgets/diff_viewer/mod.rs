@@ -7,7 +7,7 @@ mod three_column;
 mod types;
 mod word_diff;
 
-pub use types::{DiffDisplayMode, DiffFile, DiffLine, DiffLineType};
+pub use types::{DiffDisplayMode, DiffFile, DiffLine, DiffLineType, WordDiffGranularity};
 
 use crate::tui_unified::{
     components::base::{
@@ -34,6 +34,9 @@ pub struct DiffViewerComponent {
     pub(super) wrap_lines: bool,
     pub(super) syntax_highlight: bool,
     pub(super) word_level_diff: bool,
+    pub(super) word_granularity: WordDiffGranularity,
+    /// 行内差异：diff 行下标 -> 需要强调的字节范围（不含 `+`/`-`）
+    pub(super) word_highlights: std::collections::HashMap<usize, word_diff::LineRanges>,
 
     // 状态信息
     pub(super) current_file: Option<String>,
@@ -59,6 +62,8 @@ impl DiffViewerComponent {
             wrap_lines: false,
             syntax_highlight: true,
             word_level_diff: false,
+            word_granularity: WordDiffGranularity::default(),
+            word_highlights: std::collections::HashMap::new(),
 
             // 状态信息
             current_file: None,
@@ -73,6 +78,7 @@ impl DiffViewerComponent {
         let (files, lines) = self.parse_enhanced_diff(diff_content);
         self.diff_files = files;
         self.diff_lines = lines;
+        self.refresh_word_highlights();
 
         // 计算总的添加和删除行数
         self.total_additions = self.diff_files.iter().map(|f| f.additions).sum();
@@ -139,6 +145,11 @@ impl Component for DiffViewerComponent {
                 self.toggle_word_level_diff();
                 EventResult::Handled
             }
+            // W 键在单词和字符粒度之间切换
+            (KeyCode::Char('W'), _) => {
+                self.toggle_word_granularity();
+                EventResult::Handled
+            }
             // 基本导航
             (KeyCode::Up, _) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
                 self.navigate_up();
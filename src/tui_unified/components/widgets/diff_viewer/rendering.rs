@@ -1,5 +1,6 @@
 use super::types::{
     get_file_icon, DiffDisplayMode, DiffFile, DiffLine, DiffLineType, FileTreeNode,
+    WordDiffGranularity,
 };
 use super::DiffViewerComponent;
 use ratatui::{
@@ -91,9 +92,15 @@ impl DiffViewerComponent {
                 let is_selected = self.selected_line == Some(self.scroll_position + i);
 
                 if self.word_level_diff {
-                    // 使用单词级高亮
+                    // 使用单词级高亮，行内容从行号前缀和 +/- 标记之后开始
                     let line_content = self.format_line(line);
-                    let spans = self.apply_word_level_highlighting(&line_content, &line.line_type);
+                    let body_offset = line_content.len().saturating_sub(line.content.len()) + 1;
+                    let spans = self.apply_word_level_highlighting(
+                        self.scroll_position + i,
+                        &line_content,
+                        body_offset,
+                        &line.line_type,
+                    );
 
                     // 为选中行添加背景色
                     let final_spans = if is_selected && self.focused {
@@ -210,8 +217,12 @@ impl DiffViewerComponent {
                     );
 
                     if self.word_level_diff {
-                        let right_spans =
-                            self.apply_word_level_highlighting(&right_content, &line.line_type);
+                        let right_spans = self.apply_word_level_highlighting(
+                            self.scroll_position + i,
+                            &right_content,
+                            self.side_prefix_len(),
+                            &line.line_type,
+                        );
                         let mut spans = vec![
                             Span::styled(left_content, Style::default()),
                             Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
@@ -242,8 +253,12 @@ impl DiffViewerComponent {
                     let right_content = " ".repeat(half_width as usize);
 
                     if self.word_level_diff {
-                        let left_spans =
-                            self.apply_word_level_highlighting(&left_content, &line.line_type);
+                        let left_spans = self.apply_word_level_highlighting(
+                            self.scroll_position + i,
+                            &left_content,
+                            self.side_prefix_len(),
+                            &line.line_type,
+                        );
                         let mut spans = vec![];
                         spans.extend(left_spans);
                         spans.extend(vec![
@@ -581,7 +596,10 @@ impl DiffViewerComponent {
 
         let mut features = vec![];
         if self.word_level_diff {
-            features.push("🔍Word");
+            features.push(match self.word_granularity {
+                WordDiffGranularity::Word => "🔍Word",
+                WordDiffGranularity::Char => "🔍Char",
+            });
         }
         if self.show_line_numbers {
             features.push("📊Line#");
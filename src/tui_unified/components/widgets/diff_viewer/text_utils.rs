@@ -88,6 +88,16 @@ impl DiffViewerComponent {
         }
     }
 
+    /// 并排显示时行号前缀的长度
+    #[allow(dead_code)]
+    pub(super) fn side_prefix_len(&self) -> usize {
+        if self.show_line_numbers {
+            5
+        } else {
+            0
+        }
+    }
+
     /// 格式化并排显示内容
     #[allow(dead_code)]
     pub(super) fn format_side_content(
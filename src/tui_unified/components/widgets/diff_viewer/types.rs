@@ -18,6 +18,14 @@ pub enum DiffDisplayMode {
     FileTree,   // 文件树形diff
 }
 
+/// 行内差异的比较粒度
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum WordDiffGranularity {
+    #[default]
+    Word, // 按单词比较
+    Char, // 按字符比较
+}
+
 /// 文件信息
 #[derive(Debug, Clone)]
 pub struct DiffFile {
@@ -1,159 +1,395 @@
-use super::types::DiffLineType;
+use super::types::{DiffLine, DiffLineType, WordDiffGranularity};
 use super::DiffViewerComponent;
 use ratatui::{
     style::{Color, Modifier, Style},
     text::Span,
 };
+use std::collections::HashMap;
+use std::ops::Range;
 
-impl DiffViewerComponent {
-    /// 应用单词级差异高亮
-    pub(super) fn apply_word_level_highlighting(
-        &self,
-        content: &str,
-        line_type: &DiffLineType,
-    ) -> Vec<Span<'static>> {
-        if !self.word_level_diff {
-            return vec![Span::raw(content.to_string())];
+/// 超过这个 token 数的行对不做行内比较，避免长行拖慢渲染
+const MAX_TOKENS: usize = 1000;
+
+/// 改动内容占比超过这个比例时认为两行无关，不做行内高亮
+const MAX_CHANGED_RATIO: f64 = 0.6;
+
+/// 一行中需要强调的字节范围
+pub(super) type LineRanges = Vec<Range<usize>>;
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn is_whitespace(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(char::is_whitespace)
+}
+
+/// 将一行切分为 token，返回每个 token 的字节范围
+///
+/// 按单词切分时，标识符和空白各自成段，其余字符（标点、运算符）单独成段；
+/// 按字符切分时每个字符一段。
+pub(super) fn tokenize(text: &str, granularity: WordDiffGranularity) -> Vec<Range<usize>> {
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let mut previous: Option<char> = None;
+
+    for (i, ch) in text.char_indices() {
+        let extends = granularity == WordDiffGranularity::Word
+            && previous.is_some_and(|prev| {
+                (is_word_char(prev) && is_word_char(ch))
+                    || (prev.is_whitespace() && ch.is_whitespace())
+            });
+        match tokens.last_mut() {
+            Some(last) if extends => last.end = i + ch.len_utf8(),
+            _ => tokens.push(i..i + ch.len_utf8()),
         }
+        previous = Some(ch);
+    }
+    tokens
+}
+
+/// 比较 token 时忽略空白的具体内容，只改缩进或空格数量不算改动
+fn tokens_equal(a: &str, b: &str) -> bool {
+    a == b || (is_whitespace(a) && is_whitespace(b))
+}
+
+/// Myers 差分，返回旧、新两侧每个 token 是否被改动
+fn myers_changes(old: &[&str], new: &[&str]) -> (Vec<bool>, Vec<bool>) {
+    let (n, m) = (old.len(), new.len());
+    let max = n + m;
+    let offset = max as isize;
+    let mut v = vec![0usize; 2 * max + 2];
+    let mut trace = Vec::new();
 
-        match line_type {
-            DiffLineType::Added | DiffLineType::Removed => {
-                self.highlight_word_differences(content, line_type)
+    'search: for d in 0..=max as isize {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let idx = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = (x as isize - k) as usize;
+            while x < n && y < m && tokens_equal(old[x], new[y]) {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                break 'search;
             }
-            _ => vec![Span::raw(content.to_string())],
         }
     }
 
-    /// 高亮单词差异
-    pub(super) fn highlight_word_differences(
-        &self,
-        content: &str,
-        line_type: &DiffLineType,
-    ) -> Vec<Span<'static>> {
-        let mut spans = Vec::new();
+    // 从终点沿每一步的来路回溯，记录删除和插入的 token
+    let mut old_changed = vec![false; n];
+    let mut new_changed = vec![false; m];
+    let (mut x, mut y) = (n as isize, m as isize);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let idx = (k + offset) as usize;
+        let prev_k = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[(prev_k + offset) as usize] as isize;
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == prev_x {
+                new_changed[prev_y as usize] = true;
+            } else {
+                old_changed[prev_x as usize] = true;
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+
+    (old_changed, new_changed)
+}
 
-        let clean_content = if let Some(s) = content
-            .strip_prefix('+')
-            .or_else(|| content.strip_prefix('-'))
-        {
-            s
+/// 把改动标记转换成需要强调的字节范围
+///
+/// 夹在两处改动之间的空白并入改动，让高亮连成一片；只改了空白的 token 不强调。
+fn emphasized_ranges(text: &str, tokens: &[Range<usize>], changed: &[bool]) -> LineRanges {
+    let mut ranges = LineRanges::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let is_space = is_whitespace(&text[token.clone()]);
+        let emphasize = if is_space {
+            let before = i > 0 && changed[i - 1];
+            let after = changed.get(i + 1).copied().unwrap_or(false);
+            before && after
         } else {
-            content
+            changed[i]
         };
+        if !emphasize {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.end == token.start => last.end = token.end,
+            _ => ranges.push(token.clone()),
+        }
+    }
+    ranges
+}
+
+/// 改动的非空白字节占比
+fn changed_ratio(text: &str, tokens: &[Range<usize>], changed: &[bool]) -> f64 {
+    let mut total = 0;
+    let mut changed_len = 0;
+    for (token, &is_changed) in tokens.iter().zip(changed) {
+        if is_whitespace(&text[token.clone()]) {
+            continue;
+        }
+        total += token.len();
+        if is_changed {
+            changed_len += token.len();
+        }
+    }
+    if total == 0 {
+        0.0
+    } else {
+        changed_len as f64 / total as f64
+    }
+}
 
-        let words = self.split_into_tokens(clean_content);
+/// 比较一对删除行和添加行，返回两侧需要强调的字节范围
+///
+/// 两行差别太大或太长时返回 None，此时只按行着色。
+pub(super) fn diff_line_pair(
+    old: &str,
+    new: &str,
+    granularity: WordDiffGranularity,
+) -> Option<(LineRanges, LineRanges)> {
+    let old_tokens = tokenize(old, granularity);
+    let new_tokens = tokenize(new, granularity);
+    if old_tokens.len() + new_tokens.len() > MAX_TOKENS {
+        return None;
+    }
 
-        for word in words.iter() {
-            let base_style = match line_type {
-                DiffLineType::Added => Style::default().fg(Color::Green),
-                DiffLineType::Removed => Style::default().fg(Color::Red),
-                _ => Style::default().fg(Color::White),
-            };
+    let old_words: Vec<&str> = old_tokens.iter().map(|r| &old[r.clone()]).collect();
+    let new_words: Vec<&str> = new_tokens.iter().map(|r| &new[r.clone()]).collect();
+    let (old_changed, new_changed) = myers_changes(&old_words, &new_words);
 
-            let word_style = if self.is_significant_change(word) {
-                match line_type {
-                    DiffLineType::Added => base_style.bg(Color::Green).add_modifier(Modifier::BOLD),
-                    DiffLineType::Removed => base_style.bg(Color::Red).add_modifier(Modifier::BOLD),
-                    _ => base_style,
-                }
-            } else {
-                base_style
-            };
+    if changed_ratio(old, &old_tokens, &old_changed) > MAX_CHANGED_RATIO
+        && changed_ratio(new, &new_tokens, &new_changed) > MAX_CHANGED_RATIO
+    {
+        return None;
+    }
 
-            spans.push(Span::styled(word.clone(), word_style));
+    Some((
+        emphasized_ranges(old, &old_tokens, &old_changed),
+        emphasized_ranges(new, &new_tokens, &new_changed),
+    ))
+}
+
+/// 去掉 diff 行开头的 `+`/`-` 标记
+fn line_body(line: &DiffLine) -> &str {
+    line.content
+        .strip_prefix('+')
+        .or_else(|| line.content.strip_prefix('-'))
+        .unwrap_or(&line.content)
+}
+
+/// 把连续的删除行和紧随其后的添加行按顺序配对，返回各行需要强调的范围
+pub(super) fn compute_word_highlights(
+    lines: &[DiffLine],
+    granularity: WordDiffGranularity,
+) -> HashMap<usize, LineRanges> {
+    let mut highlights = HashMap::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if lines[i].line_type != DiffLineType::Removed {
+            i += 1;
+            continue;
+        }
+        let removed_start = i;
+        while i < lines.len() && lines[i].line_type == DiffLineType::Removed {
+            i += 1;
+        }
+        let added_start = i;
+        while i < lines.len() && lines[i].line_type == DiffLineType::Added {
+            i += 1;
         }
 
-        if spans.is_empty() {
-            let style = match line_type {
-                DiffLineType::Added => Style::default().fg(Color::Green),
-                DiffLineType::Removed => Style::default().fg(Color::Red),
-                _ => Style::default().fg(Color::White),
-            };
-            spans.push(Span::styled(content.to_string(), style));
+        let pairs = (added_start - removed_start).min(i - added_start);
+        for offset in 0..pairs {
+            let (old, new) = (removed_start + offset, added_start + offset);
+            if let Some((old_ranges, new_ranges)) =
+                diff_line_pair(line_body(&lines[old]), line_body(&lines[new]), granularity)
+            {
+                highlights.insert(old, old_ranges);
+                highlights.insert(new, new_ranges);
+            }
         }
+    }
+    highlights
+}
 
-        spans
+impl DiffViewerComponent {
+    /// 重新计算行内差异，diff 内容或比较粒度变化后调用
+    pub(super) fn refresh_word_highlights(&mut self) {
+        self.word_highlights = compute_word_highlights(&self.diff_lines, self.word_granularity);
     }
 
-    /// 将内容分割为token（单词、空格、标点符号）
-    pub(super) fn split_into_tokens(&self, content: &str) -> Vec<String> {
-        let mut tokens = Vec::new();
-        let mut current_token = String::new();
-
-        for ch in content.chars() {
-            match ch {
-                ' ' | '\t' => {
-                    if !current_token.is_empty() {
-                        tokens.push(current_token.clone());
-                        current_token.clear();
-                    }
-                    tokens.push(ch.to_string());
-                }
-                '(' | ')' | '[' | ']' | '{' | '}' | '"' | '\'' | ',' | ';' | ':' | '.' => {
-                    if !current_token.is_empty() {
-                        tokens.push(current_token.clone());
-                        current_token.clear();
-                    }
-                    tokens.push(ch.to_string());
-                }
-                _ => {
-                    current_token.push(ch);
-                }
+    /// 在单词和字符粒度之间切换
+    pub fn toggle_word_granularity(&mut self) {
+        self.word_granularity = match self.word_granularity {
+            WordDiffGranularity::Word => WordDiffGranularity::Char,
+            WordDiffGranularity::Char => WordDiffGranularity::Word,
+        };
+        self.refresh_word_highlights();
+    }
+
+    /// 应用行内差异高亮
+    ///
+    /// `text` 是显示的内容，行内容（去掉 `+`/`-`）从 `body_offset` 字节处开始；
+    /// 内容被截断时超出部分的强调范围会被丢弃。
+    pub(super) fn apply_word_level_highlighting(
+        &self,
+        line_index: usize,
+        text: &str,
+        body_offset: usize,
+        line_type: &DiffLineType,
+    ) -> Vec<Span<'static>> {
+        let (base_style, emphasis_style) = match line_type {
+            DiffLineType::Added => (
+                Style::default().fg(Color::Green),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            DiffLineType::Removed => (
+                Style::default().fg(Color::Red),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            _ => return vec![Span::raw(text.to_string())],
+        };
+
+        let Some(ranges) = self.word_highlights.get(&line_index) else {
+            return vec![Span::styled(text.to_string(), base_style)];
+        };
+
+        let clamp = |mut pos: usize| {
+            pos = pos.min(text.len());
+            while !text.is_char_boundary(pos) {
+                pos -= 1;
             }
-        }
+            pos
+        };
 
-        if !current_token.is_empty() {
-            tokens.push(current_token);
+        let mut spans = Vec::new();
+        let mut cursor = 0;
+        for range in ranges {
+            let start = clamp(body_offset + range.start);
+            let end = clamp(body_offset + range.end);
+            if start >= end {
+                continue;
+            }
+            if cursor < start {
+                spans.push(Span::styled(text[cursor..start].to_string(), base_style));
+            }
+            spans.push(Span::styled(text[start..end].to_string(), emphasis_style));
+            cursor = end;
+        }
+        if cursor < text.len() {
+            spans.push(Span::styled(text[cursor..].to_string(), base_style));
         }
+        spans
+    }
+}
 
-        tokens
-    }
-
-    /// 判断是否为显著变更（用于强化高亮）
-    pub(super) fn is_significant_change(&self, token: &str) -> bool {
-        let keywords = [
-            "function",
-            "fn",
-            "def",
-            "class",
-            "struct",
-            "enum",
-            "impl",
-            "trait",
-            "let",
-            "var",
-            "const",
-            "mut",
-            "pub",
-            "private",
-            "public",
-            "static",
-            "if",
-            "else",
-            "match",
-            "for",
-            "while",
-            "loop",
-            "return",
-            "break",
-            "continue",
-            "true",
-            "false",
-            "null",
-            "undefined",
-            "None",
-            "Some",
-        ];
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        if token.parse::<f64>().is_ok()
-            || (token.starts_with('"') && token.ends_with('"'))
-            || (token.starts_with('\'') && token.ends_with('\''))
-        {
-            return true;
-        }
+    fn words(text: &str, granularity: WordDiffGranularity) -> Vec<&str> {
+        tokenize(text, granularity)
+            .into_iter()
+            .map(|r| &text[r])
+            .collect()
+    }
+
+    fn emphasized<'a>(text: &'a str, ranges: &[Range<usize>]) -> Vec<&'a str> {
+        ranges.iter().map(|r| &text[r.clone()]).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            words("let foo_bar = baz(1);", WordDiffGranularity::Word),
+            vec!["let", " ", "foo_bar", " ", "=", " ", "baz", "(", "1", ")", ";"]
+        );
+        assert_eq!(
+            words("a  b", WordDiffGranularity::Char),
+            vec!["a", " ", " ", "b"]
+        );
+    }
+
+    #[test]
+    fn test_diff_line_pair_highlights_changed_words() {
+        let old = "    let total = compute(a, b);";
+        let new = "    let sum = compute(a, c);";
+        let (old_ranges, new_ranges) = diff_line_pair(old, new, WordDiffGranularity::Word).unwrap();
+        assert_eq!(emphasized(old, &old_ranges), vec!["total", "b"]);
+        assert_eq!(emphasized(new, &new_ranges), vec!["sum", "c"]);
+
+        let (old_ranges, new_ranges) =
+            diff_line_pair("count += 1", "count += 12", WordDiffGranularity::Char).unwrap();
+        assert!(old_ranges.is_empty());
+        assert_eq!(emphasized("count += 12", &new_ranges), vec!["2"]);
+    }
 
-        keywords
-            .iter()
-            .any(|&keyword| keyword == token.to_lowercase())
+    #[test]
+    fn test_diff_line_pair_ignores_whitespace_changes() {
+        let (old_ranges, new_ranges) =
+            diff_line_pair("  if ready {", "\tif  ready {", WordDiffGranularity::Word).unwrap();
+        assert!(old_ranges.is_empty());
+        assert!(new_ranges.is_empty());
+    }
+
+    #[test]
+    fn test_diff_line_pair_skips_unrelated_lines() {
+        assert!(diff_line_pair(
+            "fn parse(input: &str) -> Result<Ast>",
+            "// TODO: remove this module entirely",
+            WordDiffGranularity::Word,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_compute_word_highlights_pairs_runs() {
+        let line = |line_type, content: &str| DiffLine {
+            line_type,
+            content: content.to_string(),
+            old_line_no: None,
+            new_line_no: None,
+        };
+        let lines = vec![
+            line(DiffLineType::Context, " fn main() {"),
+            line(DiffLineType::Removed, "-    let x = 1;"),
+            line(DiffLineType::Removed, "-    let y = 2;"),
+            line(DiffLineType::Added, "+    let x = 10;"),
+            line(DiffLineType::Context, " }"),
+            line(DiffLineType::Added, "+// unpaired"),
+        ];
+        let highlights = compute_word_highlights(&lines, WordDiffGranularity::Word);
+        assert_eq!(highlights.len(), 2);
+        assert_eq!(emphasized(line_body(&lines[1]), &highlights[&1]), vec!["1"]);
+        assert_eq!(
+            emphasized(line_body(&lines[3]), &highlights[&3]),
+            vec!["10"]
+        );
     }
 }
@@ -0,0 +1,301 @@
+use crate::tui_unified::{
+    algorithms::{FuzzyMatch, SmartSearchEngine},
+    components::base::component::Component,
+    components::widgets::search_box::SearchBox,
+    state::AppState,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+
+/// 模糊查找的条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinderKind {
+    Commit,
+    Branch,
+    Tag,
+    File,
+    Stash,
+}
+
+impl FinderKind {
+    fn label(&self) -> &'static str {
+        match self {
+            FinderKind::Commit => "commit",
+            FinderKind::Branch => "branch",
+            FinderKind::Tag => "tag",
+            FinderKind::File => "file",
+            FinderKind::Stash => "stash",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            FinderKind::Commit => Color::Yellow,
+            FinderKind::Branch => Color::Green,
+            FinderKind::Tag => Color::Magenta,
+            FinderKind::File => Color::Cyan,
+            FinderKind::Stash => Color::Blue,
+        }
+    }
+}
+
+/// 模糊查找的条目
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinderItem {
+    pub kind: FinderKind,
+    /// 参与匹配并显示的文本
+    pub text: String,
+    /// 附加信息，只显示不参与匹配
+    pub detail: String,
+    /// 跳转目标：提交哈希、分支名、标签名、文件路径或 stash 序号
+    pub target: String,
+}
+
+/// 按键处理结果，由应用负责执行
+#[derive(Debug, Clone, PartialEq)]
+pub enum FinderAction {
+    None,
+    Close,
+    Jump(FinderItem),
+    /// Ctrl+F：把查询作为当前视图的过滤条件
+    Filter(String),
+}
+
+/// 最多显示的结果数量
+const MAX_RESULTS: usize = 200;
+
+/// 全局模糊查找浮层 - 同时搜索提交、分支、标签、文件和 stash
+pub struct FuzzyFinder {
+    input: SearchBox,
+    engine: SmartSearchEngine,
+    items: Vec<FinderItem>,
+    results: Vec<(usize, FuzzyMatch)>,
+    selected: usize,
+}
+
+impl Default for FuzzyFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FuzzyFinder {
+    pub fn new() -> Self {
+        Self {
+            input: SearchBox::new()
+                .with_placeholder("Search commits, branches, tags, files, stashes".to_string()),
+            engine: SmartSearchEngine::fuzzy(),
+            items: Vec::new(),
+            results: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// 打开浮层并替换候选条目
+    pub fn open(&mut self, items: Vec<FinderItem>) {
+        self.items = items;
+        self.input.set_search_active(true);
+        self.input.set_focus(true);
+        self.update_results();
+    }
+
+    pub fn close(&mut self) {
+        self.input.set_search_active(false);
+        self.input.set_focus(false);
+        self.items.clear();
+        self.results.clear();
+    }
+
+    pub fn query(&self) -> &str {
+        self.input.get_input()
+    }
+
+    pub fn selected_item(&self) -> Option<&FinderItem> {
+        self.results
+            .get(self.selected)
+            .map(|(index, _)| &self.items[*index])
+    }
+
+    fn update_results(&mut self) {
+        self.engine.set_query(self.input.get_input().to_string());
+        self.results = self
+            .engine
+            .search_ranked(&self.items, |item| item.text.as_str());
+        self.results.truncate(MAX_RESULTS);
+        self.selected = 0;
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if !self.results.is_empty() {
+            let last = self.results.len() as isize - 1;
+            self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent, state: &mut AppState) -> FinderAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return FinderAction::Close,
+            KeyCode::Enter => {
+                return match self.selected_item() {
+                    Some(item) => FinderAction::Jump(item.clone()),
+                    None => FinderAction::Close,
+                }
+            }
+            KeyCode::Char('f') if ctrl => {
+                return FinderAction::Filter(self.query().to_string());
+            }
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::Char('p') | KeyCode::Char('k') if ctrl => self.move_selection(-1),
+            KeyCode::Char('n') | KeyCode::Char('j') if ctrl => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            _ => {
+                let before = self.query().to_string();
+                self.input.handle_key_event(key, state);
+                if self.query() != before {
+                    self.update_results();
+                }
+            }
+        }
+        FinderAction::None
+    }
+
+    fn result_line(&self, item: &FinderItem, m: &FuzzyMatch) -> Line<'static> {
+        let mut spans = vec![Span::styled(
+            format!("{:<7}", item.kind.label()),
+            Style::default().fg(item.kind.color()),
+        )];
+
+        let highlight = Style::default()
+            .fg(Color::LightYellow)
+            .add_modifier(Modifier::BOLD);
+        let mut matched = m.positions.iter().peekable();
+        for (i, c) in item.text.chars().enumerate() {
+            if matched.next_if_eq(&&i).is_some() {
+                spans.push(Span::styled(c.to_string(), highlight));
+            } else {
+                spans.push(Span::raw(c.to_string()));
+            }
+        }
+
+        if !item.detail.is_empty() {
+            spans.push(Span::styled(
+                format!("  {}", item.detail),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Component for FuzzyFinder {
+    fn name(&self) -> &str {
+        "FuzzyFinder"
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, state: &AppState) {
+        let width = (area.width * 7 / 10).max(40).min(area.width);
+        let height = (area.height * 7 / 10).max(10).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(1)])
+            .split(popup);
+        self.input.render(frame, chunks[0], state);
+
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .map(|(index, m)| ListItem::new(self.result_line(&self.items[*index], m)))
+            .collect();
+        let title = format!(
+            "{}/{} · Enter-jump, Ctrl+F-filter view, Esc-close",
+            self.results.len(),
+            self.items.len()
+        );
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(Color::Yellow)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let mut list_state = ListState::default();
+        if !self.results.is_empty() {
+            list_state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+
+    fn is_focused(&self) -> bool {
+        self.input.is_focused()
+    }
+
+    fn set_focus(&mut self, focused: bool) {
+        self.input.set_focus(focused);
+    }
+
+    fn can_focus(&self) -> bool {
+        true
+    }
+
+    fn min_size(&self) -> (u16, u16) {
+        (40, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: FinderKind, text: &str) -> FinderItem {
+        FinderItem {
+            kind,
+            text: text.to_string(),
+            detail: String::new(),
+            target: text.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_typing_ranks_and_enter_jumps() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut finder = FuzzyFinder::new();
+        finder.open(vec![
+            item(FinderKind::File, "src/main.rs"),
+            item(FinderKind::Branch, "main"),
+            item(FinderKind::Tag, "v1.0.0"),
+        ]);
+        assert_eq!(finder.results.len(), 3);
+
+        for c in "main".chars() {
+            finder.handle_key(KeyEvent::from(KeyCode::Char(c)), &mut state);
+        }
+        assert_eq!(finder.results.len(), 2);
+
+        let action = finder.handle_key(KeyEvent::from(KeyCode::Enter), &mut state);
+        assert_eq!(action, FinderAction::Jump(item(FinderKind::Branch, "main")));
+
+        let ctrl_f = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert_eq!(
+            finder.handle_key(ctrl_f, &mut state),
+            FinderAction::Filter("main".to_string())
+        );
+    }
+}
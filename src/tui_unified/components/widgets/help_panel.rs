@@ -1,8 +1,177 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{prelude::*, widgets::*};
 
+use crate::tui_unified::{config::KeyBindings, state::app_state::ViewType};
+
+/// 帮助中的一行：按键和说明
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyHelp {
+    pub key: String,
+    pub description: &'static str,
+}
+
+/// 一组相关的按键
+#[derive(Debug, Clone, PartialEq)]
+pub struct HelpSection {
+    pub title: String,
+    pub keys: Vec<KeyHelp>,
+}
+
+/// 按键处理结果，由应用负责执行
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HelpAction {
+    None,
+    Close,
+}
+
+const LAYOUT_KEYS: &[(&str, &str)] = &[
+    ("[ / ]", "Shrink / grow sidebar"),
+    ("{ / }", "Shrink / grow detail panel"),
+    ("\\", "Cycle panel arrangement"),
+    ("|", "Mirror layout"),
+    ("=", "Reset layout"),
+    ("L", "Next layout preset"),
+    ("P", "Save layout as preset"),
+];
+
+const DIFF_KEYS: &[(&str, &str)] = &[
+    ("j / k", "Next / previous file"),
+    ("J / K", "Scroll"),
+    ("] / [", "Next / previous hunk"),
+    ("} / {", "Next / previous file"),
+    ("1 / 2 / 3", "Unified / side-by-side / file list"),
+    ("s", "Toggle hunk list"),
+    ("h", "Toggle syntax highlighting"),
+    ("o / O", "Expand next / all folded lines"),
+    ("+ / -", "More / less context"),
+    ("y / Y", "Copy hunk / file path"),
+    ("E", "Export diff to file"),
+    ("Esc / q", "Close diff"),
+];
+
+/// 各视图在内容区获得焦点时的按键
+fn view_keys(view: ViewType) -> &'static [(&'static str, &'static str)] {
+    match view {
+        ViewType::GitLog => &[
+            ("j / k", "Select commit"),
+            ("PgUp / PgDn", "Page through history"),
+            ("Enter", "View diff"),
+            ("Space", "Mark commit"),
+            ("C", "Cherry-pick marked commits"),
+            ("v", "Revert commit"),
+            ("y / Y", "Copy hash / message"),
+            ("p", "Pull"),
+            ("r", "Refresh"),
+            ("Tab", "Browse files"),
+        ],
+        ViewType::Branches => &[
+            ("j / k", "Select branch"),
+            ("Enter / c", "Checkout"),
+            ("n", "New branch"),
+            ("r", "Rename branch"),
+            ("d", "Delete branch"),
+            ("o", "Diff against current branch"),
+            ("F", "Fetch"),
+            ("p", "Pull"),
+            ("U", "Push"),
+            ("Tab", "Toggle remote branches"),
+        ],
+        ViewType::Tags => &[
+            ("Enter", "View tag details"),
+            ("n", "New tag"),
+            ("r", "Refresh"),
+        ],
+        ViewType::Remotes => &[
+            ("F", "Fetch remote"),
+            ("A", "Fetch all remotes"),
+            ("p", "Pull"),
+            ("U", "Push current branch"),
+        ],
+        ViewType::Stash => &[("Enter", "View stash details")],
+        ViewType::QueryHistory => &[
+            ("Enter", "Run query again"),
+            ("s", "Pin / unpin query"),
+            ("r", "Rename and pin query"),
+        ],
+        ViewType::Staging => &[
+            ("j / k", "Select file"),
+            ("Space", "Stage / unstage file or hunk"),
+            ("a", "Stage all"),
+            ("u", "Unstage all"),
+            ("c", "Commit"),
+            ("b", "Blame file"),
+            ("y", "Copy path"),
+            ("U / D", "Scroll diff preview"),
+        ],
+        ViewType::Graph => &[
+            ("j / k", "Select commit"),
+            ("g / G", "First / last commit"),
+            ("Enter", "View diff"),
+            ("y / Y", "Copy hash / message"),
+        ],
+        ViewType::Reflog => &[
+            ("Enter", "View diff"),
+            ("y", "Copy hash"),
+            ("o", "Checkout entry"),
+            ("S / M / H", "Reset soft / mixed / hard"),
+        ],
+        ViewType::Blame => &[
+            ("j / k", "Select line"),
+            ("g / G", "First / last line"),
+            ("Enter", "View commit diff"),
+            ("y / Y", "Copy hash / message"),
+        ],
+        ViewType::Worktrees => &[
+            ("Enter / s", "Switch to worktree"),
+            ("n", "New worktree"),
+            ("d", "Remove worktree"),
+            ("P", "Prune stale worktrees"),
+            ("o", "Open shell"),
+            ("y", "Copy path"),
+        ],
+        ViewType::Review => &[
+            ("w", "Review working tree"),
+            ("C", "Review commit selected in Git log"),
+            ("r", "Review again"),
+            ("j / k", "Select issue"),
+            ("Enter", "Jump to diff"),
+            ("y", "Copy issue"),
+        ],
+        ViewType::Hotspots => &[
+            ("j / k", "Select file"),
+            ("Enter / b", "Blame file"),
+            ("y", "Copy path"),
+            ("r", "Analyze again"),
+        ],
+    }
+}
+
+fn key_label(key: char) -> String {
+    match key {
+        ' ' => "Space".to_string(),
+        key => key.to_string(),
+    }
+}
+
+fn section(title: impl Into<String>, keys: &[(&str, &'static str)]) -> HelpSection {
+    HelpSection {
+        title: title.into(),
+        keys: keys
+            .iter()
+            .map(|(key, description)| KeyHelp {
+                key: key.to_string(),
+                description,
+            })
+            .collect(),
+    }
+}
+
+/// 帮助浮层 - 根据当前按键配置生成的按键速查表，输入文字即可过滤
 pub struct HelpPanel {
-    pub shortcuts: Vec<(String, String)>,
-    pub visible: bool,
+    open: bool,
+    sections: Vec<HelpSection>,
+    query: String,
+    scroll: u16,
 }
 
 impl Default for HelpPanel {
@@ -13,47 +182,250 @@ impl Default for HelpPanel {
 
 impl HelpPanel {
     pub fn new() -> Self {
-        let shortcuts = vec![
-            ("Tab".to_string(), "Switch panel".to_string()),
-            ("q".to_string(), "Quit".to_string()),
-            ("?".to_string(), "Toggle help".to_string()),
-            ("Enter".to_string(), "Select item".to_string()),
-        ];
-
         Self {
-            shortcuts,
-            visible: false,
+            open: false,
+            sections: Vec::new(),
+            query: String::new(),
+            scroll: 0,
         }
     }
 
-    pub fn toggle(&mut self) {
-        self.visible = !self.visible;
+    /// 打开浮层，当前视图的按键排在最前面
+    pub fn open(&mut self, bindings: &KeyBindings, view: ViewType) {
+        *self = Self {
+            open: true,
+            sections: Self::build_sections(bindings, view),
+            ..Self::new()
+        };
     }
 
-    pub fn render(&self, area: Rect, buf: &mut Buffer) {
-        if !self.visible {
-            return;
-        }
+    pub fn close(&mut self) {
+        self.open = false;
+    }
 
-        let text: Vec<Line> = self
-            .shortcuts
-            .iter()
-            .map(|(key, desc)| {
-                Line::from(vec![
-                    Span::styled(format!("{:<10}", key), Style::default().fg(Color::Yellow)),
-                    Span::raw(" - "),
-                    Span::raw(desc.clone()),
-                ])
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// 全局按键来自按键配置，显示的是用户实际使用的按键
+    pub fn build_sections(bindings: &KeyBindings, view: ViewType) -> Vec<HelpSection> {
+        let mut global: Vec<KeyHelp> = bindings
+            .entries()
+            .into_iter()
+            .map(|(action, key, _)| KeyHelp {
+                key: key_label(key),
+                description: KeyBindings::describe(action),
             })
             .collect();
-
-        let help = Paragraph::new(text)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("Help (Press ? to toggle)"),
+        global.extend(
+            section(
+                "",
+                &[
+                    ("Tab / Shift+Tab", "Switch panel focus"),
+                    ("Ctrl+P", "Fuzzy find"),
+                    (": / Ctrl+Shift+P", "Command palette"),
+                    ("N", "Notification center"),
+                    ("V", "AI review view"),
+                    ("H", "Churn hotspots view"),
+                    ("E", "Export current view to file"),
+                    ("W", "Toggle auto-refresh on file changes"),
+                    ("O", "Switch repository"),
+                    ("Esc", "Back to normal mode, dismiss toasts"),
+                    ("?", "Toggle this help"),
+                ],
             )
-            .wrap(Wrap { trim: true });
-        help.render(area, buf);
+            .keys,
+        );
+
+        vec![
+            section(format!("{:?} view", view), view_keys(view)),
+            HelpSection {
+                title: "Global".to_string(),
+                keys: global,
+            },
+            section("Layout", LAYOUT_KEYS),
+            section("Diff viewer", DIFF_KEYS),
+        ]
+    }
+
+    /// 按查询过滤，标题匹配时保留整组
+    pub fn filtered_sections(&self) -> Vec<HelpSection> {
+        let query = self.query.to_lowercase();
+        if query.is_empty() {
+            return self.sections.clone();
+        }
+        self.sections
+            .iter()
+            .filter_map(|section| {
+                if section.title.to_lowercase().contains(&query) {
+                    return Some(section.clone());
+                }
+                let keys: Vec<KeyHelp> = section
+                    .keys
+                    .iter()
+                    .filter(|help| {
+                        help.key.to_lowercase().contains(&query)
+                            || help.description.to_lowercase().contains(&query)
+                    })
+                    .cloned()
+                    .collect();
+                (!keys.is_empty()).then(|| HelpSection {
+                    title: section.title.clone(),
+                    keys,
+                })
+            })
+            .collect()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> HelpAction {
+        if !self.open {
+            return HelpAction::Close;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return HelpAction::None;
+        }
+
+        match key.code {
+            KeyCode::Char('?') => return HelpAction::Close,
+            // 有查询时 Esc 先清空查询
+            KeyCode::Esc if self.query.is_empty() => return HelpAction::Close,
+            KeyCode::Esc => self.query.clear(),
+            KeyCode::Backspace => {
+                self.query.pop();
+            }
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+        HelpAction::None
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.open {
+            return;
+        }
+
+        let width = 72.min(area.width);
+        let height = 30.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Keyboard Shortcuts")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("Search: ", Style::default().fg(Color::DarkGray)),
+                Span::raw(format!("{}▏", self.query)),
+            ]))
+            .block(Block::default().borders(Borders::BOTTOM)),
+            chunks[0],
+        );
+
+        let sections = self.filtered_sections();
+        let mut lines = Vec::new();
+        for section in &sections {
+            if !lines.is_empty() {
+                lines.push(Line::raw(""));
+            }
+            lines.push(Line::styled(
+                section.title.clone(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            for help in &section.keys {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {:<16}", help.key),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(help.description),
+                ]));
+            }
+        }
+        if lines.is_empty() {
+            lines.push(Line::styled(
+                "No matching keys",
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+
+        let max_scroll = (lines.len() as u16).saturating_sub(chunks[1].height);
+        frame.render_widget(
+            Paragraph::new(lines).scroll((self.scroll.min(max_scroll), 0)),
+            chunks[1],
+        );
+
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "Type to filter, ↑/↓ scroll, Esc clear/close, ? close",
+                Style::default().fg(Color::DarkGray),
+            )),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_follow_keymap_and_filter() {
+        let mut bindings = KeyBindings::default();
+        bindings.set("quit", 'x').unwrap();
+
+        let mut panel = HelpPanel::new();
+        panel.open(&bindings, ViewType::Branches);
+        assert_eq!(panel.filtered_sections()[0].title, "Branches view");
+
+        for c in "quit".chars() {
+            panel.handle_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        let sections = panel.filtered_sections();
+        assert_eq!(sections.len(), 1);
+        assert_eq!(
+            sections[0].keys,
+            vec![KeyHelp {
+                key: "x".to_string(),
+                description: "Quit",
+            }]
+        );
+
+        // Esc 先清空查询，再关闭
+        assert_eq!(
+            panel.handle_key(KeyEvent::from(KeyCode::Esc)),
+            HelpAction::None
+        );
+        assert_eq!(panel.filtered_sections().len(), 4);
+        assert_eq!(
+            panel.handle_key(KeyEvent::from(KeyCode::Esc)),
+            HelpAction::Close
+        );
     }
 }
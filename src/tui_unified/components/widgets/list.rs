@@ -1,12 +1,12 @@
 // 通用列表组件
 use crate::tui_unified::{
     components::base::{
-        component::{Component, ViewComponent},
+        component::{bordered_row_at, Component, ViewComponent},
         events::EventResult,
     },
     state::AppState,
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::Rect,
     style::{Color, Style},
@@ -33,6 +33,8 @@ pub struct ListWidget<T> {
     search_fn: SearchFn<T>, // 搜索函数
     current_search: Option<String>,
     show_search_results: bool,
+    /// 上次渲染的区域，用于鼠标命中测试
+    area: Rect,
 }
 
 impl<T> ListWidget<T>
@@ -65,6 +67,7 @@ where
             search_fn,
             current_search: None,
             show_search_results: false,
+            area: Rect::default(),
         }
     }
 
@@ -127,10 +130,38 @@ where
         self.selected_index
     }
 
+    /// 清除搜索过滤并选中第一个满足条件的条目
+    pub fn select_where(&mut self, predicate: impl Fn(&T) -> bool) -> bool {
+        let Some(index) = self.items.iter().position(predicate) else {
+            return false;
+        };
+        self.current_search = None;
+        self.show_search_results = false;
+        self.filtered_items = (0..self.items.len()).collect();
+        self.selected_index = Some(index);
+        self.list_state.select(Some(index));
+        true
+    }
+
     pub fn items(&self) -> &[T] {
         &self.items
     }
 
+    /// 当前显示的条目，搜索时只包含匹配的条目
+    pub fn visible_items(&self) -> Vec<&T> {
+        (0..self.effective_len())
+            .filter_map(|index| self.get_effective_item(index))
+            .collect()
+    }
+
+    /// 当前显示的条目按列表中的格式转换成文本，用于导出
+    pub fn formatted_lines(&self) -> Vec<String> {
+        self.visible_items()
+            .into_iter()
+            .map(|item| (self.format_fn)(item))
+            .collect()
+    }
+
     pub fn len(&self) -> usize {
         self.items.len()
     }
@@ -204,6 +235,15 @@ where
         }
     }
 
+    fn select(&mut self, index: usize) {
+        let effective_len = self.effective_len();
+        if effective_len > 0 {
+            let index = index.min(effective_len - 1);
+            self.selected_index = Some(index);
+            self.list_state.select(Some(index));
+        }
+    }
+
     fn go_to_end(&mut self) {
         let effective_len = self.effective_len();
         if effective_len > 0 {
@@ -223,6 +263,7 @@ where
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, _state: &AppState) {
+        self.area = area;
         let border_style = if self.focused {
             Style::default().fg(Color::Yellow)
         } else {
@@ -288,6 +329,32 @@ where
         }
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent, _state: &mut AppState) -> EventResult {
+        if !self.area.contains((mouse.column, mouse.row).into()) {
+            return EventResult::NotHandled;
+        }
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                // 点击选中对应行
+                if let Some(row) = bordered_row_at(self.area, mouse.column, mouse.row) {
+                    let index = self.list_state.offset() + row;
+                    if index < self.effective_len() {
+                        self.select(index);
+                    }
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                self.select(self.selected_index.unwrap_or(0).saturating_sub(1));
+            }
+            MouseEventKind::ScrollDown => {
+                self.select(self.selected_index.map_or(0, |index| index + 1));
+            }
+            _ => return EventResult::NotHandled,
+        }
+        EventResult::Handled
+    }
+
     fn is_focused(&self) -> bool {
         self.focused
     }
@@ -367,3 +434,53 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mouse_click_and_scroll() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        let mut list = ListWidget::new(
+            "Items".to_string(),
+            Box::new(|item: &String| item.clone()),
+            Box::new(crate::tui_unified::components::views::shared::default_selection_style),
+        );
+        list.set_items(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+
+        let mut terminal = Terminal::new(TestBackend::new(20, 10)).unwrap();
+        terminal
+            .draw(|frame| list.render(frame, Rect::new(0, 0, 20, 10), &state))
+            .unwrap();
+
+        // 第一行内容在边框下方
+        let click = MouseEventKind::Down(MouseButton::Left);
+        list.handle_mouse_event(mouse(click, 2, 3), &mut state);
+        assert_eq!(list.selected_index(), Some(2));
+
+        // 点击空白行不改变选择，区域外不处理
+        list.handle_mouse_event(mouse(click, 2, 6), &mut state);
+        assert_eq!(list.selected_index(), Some(2));
+        let result = list.handle_mouse_event(mouse(click, 30, 3), &mut state);
+        assert!(matches!(result, EventResult::NotHandled));
+
+        // 滚轮在两端停止而不是循环
+        list.handle_mouse_event(mouse(MouseEventKind::ScrollDown, 2, 3), &mut state);
+        assert_eq!(list.selected_index(), Some(2));
+        list.handle_mouse_event(mouse(MouseEventKind::ScrollUp, 2, 3), &mut state);
+        assert_eq!(list.selected_index(), Some(1));
+    }
+}
@@ -1,15 +1,30 @@
+pub mod command_palette;
 pub mod commit_editor;
 pub mod diff_viewer;
+pub mod fuzzy_finder;
 pub mod help_panel;
 pub mod list;
+pub mod notification_center;
 pub mod progress_bar;
+pub mod repo_switcher;
 pub mod search_box;
+pub mod settings_panel;
 pub mod status_bar;
+pub mod storage_panel;
+pub mod tab_bar;
+pub mod tag_dialog;
 
+pub use command_palette::CommandPalette;
 pub use commit_editor::CommitEditor;
 pub use diff_viewer::DiffViewerComponent;
+pub use fuzzy_finder::FuzzyFinder;
 pub use help_panel::HelpPanel;
 pub use list::ListWidget;
+pub use notification_center::NotificationCenter;
 pub use progress_bar::ProgressBar;
+pub use repo_switcher::RepoSwitcher;
 pub use search_box::SearchBox;
+pub use settings_panel::SettingsPanel;
 pub use status_bar::StatusBar;
+pub use storage_panel::StoragePanel;
+pub use tag_dialog::TagDialog;
@@ -0,0 +1,256 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use uuid::Uuid;
+
+use crate::tui_unified::state::app_state::{Notification, NotificationLevel};
+
+/// 同时显示的提示数量上限
+const MAX_TOASTS: usize = 3;
+const TOAST_WIDTH: u16 = 48;
+
+/// 按键处理结果，由应用负责执行
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotificationAction {
+    None,
+    Close,
+    /// 从历史中删除一条通知
+    Remove(Uuid),
+    /// 清空通知历史
+    Clear,
+}
+
+/// 通知级别对应的图标和颜色
+pub fn level_style(level: NotificationLevel) -> (&'static str, Color) {
+    match level {
+        NotificationLevel::Info => ("ℹ", Color::Cyan),
+        NotificationLevel::Success => ("✓", Color::Green),
+        NotificationLevel::Warning => ("⚠", Color::Yellow),
+        NotificationLevel::Error => ("✗", Color::Red),
+    }
+}
+
+/// 在右上角叠放最近的提示，最新的在最上面
+pub fn render_toasts(frame: &mut Frame, area: Rect, notifications: &[&Notification]) {
+    let width = TOAST_WIDTH.min(area.width);
+    let mut y = area.y;
+    for notification in notifications.iter().rev().take(MAX_TOASTS) {
+        if y + 3 > area.y + area.height {
+            break;
+        }
+        let toast = Rect {
+            x: area.x + area.width - width,
+            y,
+            width,
+            height: 3,
+        };
+        y += 3;
+
+        let (icon, color) = level_style(notification.level);
+        let inner_width = width.saturating_sub(4) as usize;
+        let message: String = notification
+            .message
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .take(inner_width)
+            .collect();
+        frame.render_widget(Clear, toast);
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(format!("{} ", icon), Style::default().fg(color)),
+                Span::raw(message),
+            ]))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(color)),
+            ),
+            toast,
+        );
+    }
+}
+
+/// 通知中心浮层 - 浏览所有通知的历史，最新的在最上面
+pub struct NotificationCenter {
+    open: bool,
+    selected: usize,
+}
+
+impl Default for NotificationCenter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            selected: 0,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.selected = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// `notifications` 为按时间顺序排列的完整历史
+    pub fn handle_key(
+        &mut self,
+        key: KeyEvent,
+        notifications: &[Notification],
+    ) -> NotificationAction {
+        if !self.open {
+            return NotificationAction::Close;
+        }
+        let count = notifications.len();
+        self.selected = self.selected.min(count.saturating_sub(1));
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('N') => NotificationAction::Close,
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < count {
+                    self.selected += 1;
+                }
+                NotificationAction::None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                NotificationAction::None
+            }
+            KeyCode::Char('d') | KeyCode::Delete if count > 0 => {
+                let id = notifications[count - 1 - self.selected].id;
+                // 删除最后一条时选中上一条
+                if self.selected + 1 == count {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                NotificationAction::Remove(id)
+            }
+            KeyCode::Char('D') if count > 0 => {
+                self.selected = 0;
+                NotificationAction::Clear
+            }
+            _ => NotificationAction::None,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, notifications: &[Notification]) {
+        if !self.open {
+            return;
+        }
+
+        let width = 90.min(area.width);
+        let height = 24.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Notifications ({})", notifications.len()))
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+
+        if notifications.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No notifications yet").style(Style::default().fg(Color::DarkGray)),
+                chunks[0],
+            );
+        } else {
+            let items: Vec<ListItem> = notifications
+                .iter()
+                .rev()
+                .map(|notification| {
+                    let (icon, color) = level_style(notification.level);
+                    let time = notification
+                        .timestamp
+                        .with_timezone(&chrono::Local)
+                        .format("%H:%M:%S");
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{} ", time), Style::default().fg(Color::DarkGray)),
+                        Span::styled(format!("{} ", icon), Style::default().fg(color)),
+                        Span::raw(notification.message.replace('\n', " ")),
+                    ]))
+                })
+                .collect();
+            let mut list_state = ListState::default();
+            list_state.select(Some(self.selected.min(notifications.len() - 1)));
+            frame.render_stateful_widget(
+                List::new(items).highlight_style(Style::default().bg(Color::DarkGray)),
+                chunks[0],
+                &mut list_state,
+            );
+        }
+
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "j/k move, d delete, D clear all, Esc close",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .block(Block::default().borders(Borders::TOP)),
+            chunks[1],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(message: &str) -> Notification {
+        Notification {
+            id: Uuid::new_v4(),
+            message: message.to_string(),
+            level: NotificationLevel::Info,
+            timestamp: chrono::Utc::now(),
+            auto_dismiss: None,
+            dismissed: true,
+        }
+    }
+
+    #[test]
+    fn test_select_and_remove_newest_first() {
+        let history = vec![notification("old"), notification("new")];
+        let mut center = NotificationCenter::new();
+        center.open();
+
+        let press = |center: &mut NotificationCenter, code| {
+            center.handle_key(KeyEvent::from(code), &history)
+        };
+        assert_eq!(
+            press(&mut center, KeyCode::Char('d')),
+            NotificationAction::Remove(history[1].id)
+        );
+        press(&mut center, KeyCode::Char('j'));
+        press(&mut center, KeyCode::Char('j'));
+        assert_eq!(
+            press(&mut center, KeyCode::Char('d')),
+            NotificationAction::Remove(history[0].id)
+        );
+        assert_eq!(
+            press(&mut center, KeyCode::Char('D')),
+            NotificationAction::Clear
+        );
+        assert_eq!(press(&mut center, KeyCode::Esc), NotificationAction::Close);
+    }
+}
@@ -0,0 +1,419 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use std::path::{Path, PathBuf};
+
+/// 切换器的两个页签
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepoSwitcherTab {
+    Recent,
+    Browse,
+}
+
+/// 按键处理结果，由应用负责执行
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepoSwitcherAction {
+    None,
+    Close,
+    Switch(PathBuf),
+    /// 从最近列表中移除
+    Forget(PathBuf),
+}
+
+/// 目录浏览中的一项
+#[derive(Debug, Clone, PartialEq)]
+struct DirEntry {
+    name: String,
+    path: PathBuf,
+    is_repo: bool,
+}
+
+fn is_repo(path: &Path) -> bool {
+    path.join(".git").exists()
+}
+
+/// 目录下的子目录，隐藏目录除外，第一项为上级目录
+fn list_dirs(dir: &Path) -> std::io::Result<Vec<DirEntry>> {
+    let mut entries: Vec<DirEntry> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            (!name.starts_with('.')).then(|| DirEntry {
+                is_repo: is_repo(&entry.path()),
+                path: entry.path(),
+                name,
+            })
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.name.to_lowercase());
+    if let Some(parent) = dir.parent() {
+        entries.insert(
+            0,
+            DirEntry {
+                name: "..".to_string(),
+                path: parent.to_path_buf(),
+                is_repo: false,
+            },
+        );
+    }
+    Ok(entries)
+}
+
+/// 仓库切换浮层 - 从最近打开的仓库中选择，或浏览目录找到仓库
+pub struct RepoSwitcher {
+    open: bool,
+    tab: RepoSwitcherTab,
+    recent: Vec<PathBuf>,
+    current: Option<PathBuf>,
+    dir: PathBuf,
+    entries: Vec<DirEntry>,
+    error: Option<String>,
+    selected: usize,
+}
+
+impl Default for RepoSwitcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RepoSwitcher {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            tab: RepoSwitcherTab::Recent,
+            recent: Vec::new(),
+            current: None,
+            dir: PathBuf::new(),
+            entries: Vec::new(),
+            error: None,
+            selected: 0,
+        }
+    }
+
+    /// 打开切换器，目录浏览从当前仓库的上级目录开始
+    pub fn open(&mut self, recent: Vec<PathBuf>, current: Option<PathBuf>) {
+        self.open = true;
+        self.recent = recent;
+        let start = current
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .or_else(dirs::home_dir)
+            .unwrap_or_default();
+        self.current = current;
+        self.browse(start);
+        let others = self
+            .recent
+            .iter()
+            .any(|repo| Some(repo) != self.current.as_ref());
+        self.tab = if others {
+            RepoSwitcherTab::Recent
+        } else {
+            RepoSwitcherTab::Browse
+        };
+        self.selected = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.recent.clear();
+        self.entries.clear();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn tab(&self) -> RepoSwitcherTab {
+        self.tab
+    }
+
+    pub fn set_recent(&mut self, recent: Vec<PathBuf>) {
+        self.recent = recent;
+        self.clamp_selection();
+    }
+
+    fn browse(&mut self, dir: PathBuf) {
+        match list_dirs(&dir) {
+            Ok(entries) => {
+                self.entries = entries;
+                self.error = None;
+            }
+            Err(e) => {
+                self.entries.clear();
+                self.error = Some(format!("Cannot read {}: {}", dir.display(), e));
+            }
+        }
+        self.dir = dir;
+        self.selected = 0;
+    }
+
+    fn len(&self) -> usize {
+        match self.tab {
+            RepoSwitcherTab::Recent => self.recent.len(),
+            RepoSwitcherTab::Browse => self.entries.len(),
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        self.selected = self.selected.min(self.len().saturating_sub(1));
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.len() > 0 {
+            let last = self.len() as isize - 1;
+            self.selected = (self.selected as isize + delta).clamp(0, last) as usize;
+        }
+    }
+
+    fn go_up(&mut self) {
+        if let Some(parent) = self.dir.parent().map(Path::to_path_buf) {
+            self.browse(parent);
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> RepoSwitcherAction {
+        if !self.open {
+            return RepoSwitcherAction::Close;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return RepoSwitcherAction::Close,
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.tab = match self.tab {
+                    RepoSwitcherTab::Recent => RepoSwitcherTab::Browse,
+                    RepoSwitcherTab::Browse => RepoSwitcherTab::Recent,
+                };
+                self.selected = 0;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::Enter => match self.tab {
+                RepoSwitcherTab::Recent => {
+                    if let Some(repo) = self.recent.get(self.selected) {
+                        return RepoSwitcherAction::Switch(repo.clone());
+                    }
+                }
+                RepoSwitcherTab::Browse => {
+                    if let Some(entry) = self.entries.get(self.selected).cloned() {
+                        if entry.is_repo {
+                            return RepoSwitcherAction::Switch(entry.path);
+                        }
+                        self.browse(entry.path);
+                    }
+                }
+            },
+            // 打开浏览中的当前目录，可用于选择子目录所在的仓库
+            KeyCode::Char('o') if self.tab == RepoSwitcherTab::Browse => {
+                return RepoSwitcherAction::Switch(self.dir.clone());
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.tab == RepoSwitcherTab::Browse => {
+                if let Some(entry) = self.entries.get(self.selected).cloned() {
+                    self.browse(entry.path);
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Backspace
+                if self.tab == RepoSwitcherTab::Browse =>
+            {
+                self.go_up();
+            }
+            KeyCode::Char('d') if self.tab == RepoSwitcherTab::Recent => {
+                if let Some(repo) = self.recent.get(self.selected) {
+                    return RepoSwitcherAction::Forget(repo.clone());
+                }
+            }
+            _ => {}
+        }
+        RepoSwitcherAction::None
+    }
+
+    fn recent_items(&self) -> Vec<ListItem<'static>> {
+        self.recent
+            .iter()
+            .map(|repo| {
+                let name = repo
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| repo.display().to_string());
+                let mut spans = vec![
+                    Span::styled(
+                        format!("{:<24} ", name),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        repo.display().to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ];
+                if Some(repo) == self.current.as_ref() {
+                    spans.push(Span::styled(
+                        " (current)",
+                        Style::default().fg(Color::Green),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    }
+
+    fn browse_items(&self) -> Vec<ListItem<'static>> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                if entry.is_repo {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("● {}/", entry.name),
+                            Style::default().fg(Color::Green),
+                        ),
+                        Span::styled("  git", Style::default().fg(Color::DarkGray)),
+                    ]))
+                } else {
+                    ListItem::new(format!("  {}/", entry.name))
+                }
+            })
+            .collect()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.open {
+            return;
+        }
+
+        let width = 80.min(area.width);
+        let height = 22.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(1)])
+            .split(popup);
+
+        let tab_index = match self.tab {
+            RepoSwitcherTab::Recent => 0,
+            RepoSwitcherTab::Browse => 1,
+        };
+        frame.render_widget(
+            Tabs::new(vec!["Recent", "Browse"])
+                .select(tab_index)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Switch Repository")
+                        .border_style(Style::default().fg(Color::Yellow)),
+                )
+                .highlight_style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            chunks[0],
+        );
+
+        let (items, title) = match self.tab {
+            RepoSwitcherTab::Recent => (
+                self.recent_items(),
+                "Enter-open, d-forget, Tab-browse, Esc-close".to_string(),
+            ),
+            RepoSwitcherTab::Browse => (
+                self.browse_items(),
+                format!(
+                    "{} · Enter-open/descend, ←-up, o-open this dir, Tab-recent",
+                    self.dir.display()
+                ),
+            ),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow));
+
+        let empty = match (&self.error, self.tab) {
+            (Some(error), RepoSwitcherTab::Browse) => Some(error.clone()),
+            _ if items.is_empty() => Some(match self.tab {
+                RepoSwitcherTab::Recent => "No recent repositories".to_string(),
+                RepoSwitcherTab::Browse => "No subdirectories".to_string(),
+            }),
+            _ => None,
+        };
+        if let Some(message) = empty {
+            frame.render_widget(
+                Paragraph::new(message)
+                    .style(Style::default().fg(Color::DarkGray))
+                    .block(block),
+                chunks[1],
+            );
+            return;
+        }
+
+        let list = List::new(items).block(block).highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::from(code)
+    }
+
+    #[test]
+    fn test_browse_and_switch() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(root.path().join("app/.git")).unwrap();
+        std::fs::create_dir_all(root.path().join("docs/guide")).unwrap();
+        std::fs::create_dir_all(root.path().join(".cache")).unwrap();
+        let current = root.path().join("app");
+
+        let mut switcher = RepoSwitcher::new();
+        // 最近列表只有当前仓库时直接浏览目录
+        switcher.open(vec![current.clone()], Some(current.clone()));
+        assert_eq!(switcher.tab(), RepoSwitcherTab::Browse);
+
+        // ".." 之后是按名称排序的目录，隐藏目录被跳过
+        let names: Vec<&str> = switcher.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["..", "app", "docs"]);
+
+        // 普通目录进入下一级，仓库直接切换
+        switcher.handle_key(key(KeyCode::Down));
+        switcher.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            switcher.handle_key(key(KeyCode::Enter)),
+            RepoSwitcherAction::None
+        );
+        assert_eq!(switcher.dir, root.path().join("docs"));
+        switcher.handle_key(key(KeyCode::Backspace));
+        switcher.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            switcher.handle_key(key(KeyCode::Enter)),
+            RepoSwitcherAction::Switch(current.clone())
+        );
+
+        switcher.handle_key(key(KeyCode::Tab));
+        assert_eq!(switcher.tab(), RepoSwitcherTab::Recent);
+        assert_eq!(
+            switcher.handle_key(key(KeyCode::Char('d'))),
+            RepoSwitcherAction::Forget(current)
+        );
+        assert_eq!(
+            switcher.handle_key(key(KeyCode::Esc)),
+            RepoSwitcherAction::Close
+        );
+    }
+}
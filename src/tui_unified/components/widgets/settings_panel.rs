@@ -0,0 +1,444 @@
+use crate::diff_viewer::{DiffViewMode, MAX_CONTEXT_LINES};
+use crate::tui_unified::config::AppConfig;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+/// 自动刷新间隔的可选值（秒），0 表示关闭
+const REFRESH_STEPS: [u64; 6] = [0, 5, 10, 30, 60, 300];
+/// 折叠未修改行阈值的可选值，0 表示不折叠
+const FOLD_STEPS: [usize; 5] = [0, 8, 12, 20, 50];
+
+/// 设置界面编辑中的草稿，保存前不影响应用
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettingsDraft {
+    pub provider: String,
+    pub model: String,
+    pub config: AppConfig,
+}
+
+/// 按键处理结果，由应用负责执行
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingsAction {
+    None,
+    Close,
+    Save(SettingsDraft),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SettingsRow {
+    Provider,
+    Model,
+    Theme,
+    RefreshInterval,
+    DiffView,
+    DiffContext,
+    FoldThreshold,
+    IgnoreWhitespace,
+    /// 按键绑定，序号对应 `KeyBindings::entries`
+    Key(usize),
+}
+
+/// 正在输入的内容
+#[derive(Debug, Clone, PartialEq)]
+enum EditState {
+    Idle,
+    /// 编辑模型名称
+    Text(String),
+    /// 等待新的按键
+    Capture,
+}
+
+/// 在前一项和后一项之间循环
+fn cycle<T: PartialEq + Clone>(options: &[T], current: &T, forward: bool) -> Option<T> {
+    if options.is_empty() {
+        return None;
+    }
+    let len = options.len();
+    let index = match options.iter().position(|option| option == current) {
+        Some(i) if forward => (i + 1) % len,
+        Some(i) => (i + len - 1) % len,
+        None => 0,
+    };
+    Some(options[index].clone())
+}
+
+/// 设置浮层 - 修改 AI 提供商、模型、主题、刷新间隔、diff 选项和按键绑定
+pub struct SettingsPanel {
+    draft: Option<SettingsDraft>,
+    providers: Vec<String>,
+    themes: Vec<String>,
+    rows: Vec<SettingsRow>,
+    selected: usize,
+    edit: EditState,
+    message: Option<String>,
+}
+
+impl Default for SettingsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SettingsPanel {
+    pub fn new() -> Self {
+        let mut rows = vec![
+            SettingsRow::Provider,
+            SettingsRow::Model,
+            SettingsRow::Theme,
+            SettingsRow::RefreshInterval,
+            SettingsRow::DiffView,
+            SettingsRow::DiffContext,
+            SettingsRow::FoldThreshold,
+            SettingsRow::IgnoreWhitespace,
+        ];
+        let key_count = AppConfig::default().key_bindings.entries().len();
+        rows.extend((0..key_count).map(SettingsRow::Key));
+
+        Self {
+            draft: None,
+            providers: Vec::new(),
+            themes: Vec::new(),
+            rows,
+            selected: 0,
+            edit: EditState::Idle,
+            message: None,
+        }
+    }
+
+    /// 打开浮层，providers 和 themes 是可选值
+    pub fn open(&mut self, draft: SettingsDraft, mut providers: Vec<String>, themes: Vec<String>) {
+        providers.sort();
+        self.providers = providers;
+        self.themes = themes;
+        self.draft = Some(draft);
+        self.selected = 0;
+        self.edit = EditState::Idle;
+        self.message = None;
+    }
+
+    pub fn close(&mut self) {
+        self.draft = None;
+        self.edit = EditState::Idle;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.draft.is_some()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> SettingsAction {
+        let Some(draft) = self.draft.as_mut() else {
+            return SettingsAction::Close;
+        };
+        let row = self.rows[self.selected];
+
+        match &mut self.edit {
+            EditState::Text(text) => {
+                match key.code {
+                    KeyCode::Enter => {
+                        let model = text.trim().to_string();
+                        if !model.is_empty() {
+                            draft.model = model;
+                        }
+                        self.edit = EditState::Idle;
+                    }
+                    KeyCode::Esc => self.edit = EditState::Idle,
+                    KeyCode::Backspace => {
+                        text.pop();
+                    }
+                    KeyCode::Char(c) => text.push(c),
+                    _ => {}
+                }
+                return SettingsAction::None;
+            }
+            EditState::Capture => {
+                if let (SettingsRow::Key(index), KeyCode::Char(c)) = (row, key.code) {
+                    let action = draft.config.key_bindings.entries()[index].0;
+                    self.message = draft
+                        .config
+                        .key_bindings
+                        .set(action, c)
+                        .err()
+                        .map(|e| e.to_string());
+                }
+                self.edit = EditState::Idle;
+                return SettingsAction::None;
+            }
+            EditState::Idle => {}
+        }
+
+        self.message = None;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => return SettingsAction::Close,
+            KeyCode::Char('s') => return SettingsAction::Save(draft.clone()),
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.rows.len() - 1)
+            }
+            KeyCode::Left | KeyCode::Char('h') => self.change(row, false),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char(' ') => self.change(row, true),
+            KeyCode::Enter => match row {
+                SettingsRow::Model => self.edit = EditState::Text(draft.model.clone()),
+                SettingsRow::Key(_) => self.edit = EditState::Capture,
+                _ => self.change(row, true),
+            },
+            KeyCode::Char('d') => {
+                // 恢复按键的默认值
+                if let SettingsRow::Key(index) = row {
+                    let (action, _, default) = draft.config.key_bindings.entries()[index];
+                    self.message = draft
+                        .config
+                        .key_bindings
+                        .set(action, default)
+                        .err()
+                        .map(|e| e.to_string());
+                }
+            }
+            _ => {}
+        }
+        SettingsAction::None
+    }
+
+    /// 切换选中行的值
+    fn change(&mut self, row: SettingsRow, forward: bool) {
+        let Some(draft) = self.draft.as_mut() else {
+            return;
+        };
+        match row {
+            SettingsRow::Provider => {
+                if let Some(provider) = cycle(&self.providers, &draft.provider, forward) {
+                    // 切换提供商时改用它的默认模型
+                    if let Some(info) = crate::config::ProviderRegistry::get_provider(&provider) {
+                        draft.model = info.default_model.clone();
+                    }
+                    draft.provider = provider;
+                }
+            }
+            SettingsRow::Model => {
+                let models = crate::config::ProviderRegistry::get_provider(&draft.provider)
+                    .map(|info| info.supported_models.clone())
+                    .unwrap_or_default();
+                if let Some(model) = cycle(&models, &draft.model, forward) {
+                    draft.model = model;
+                }
+            }
+            SettingsRow::Theme => {
+                if let Some(theme) = cycle(&self.themes, &draft.config.theme_name, forward) {
+                    draft.config.theme_name = theme;
+                }
+            }
+            SettingsRow::RefreshInterval => {
+                if let Some(interval) =
+                    cycle(&REFRESH_STEPS, &draft.config.refresh_interval, forward)
+                {
+                    draft.config.refresh_interval = interval;
+                }
+            }
+            SettingsRow::DiffView => {
+                let modes = [
+                    DiffViewMode::SideBySide,
+                    DiffViewMode::Unified,
+                    DiffViewMode::Split,
+                ];
+                if let Some(mode) = cycle(&modes, &draft.config.diff.view_mode, forward) {
+                    draft.config.diff.view_mode = mode;
+                }
+            }
+            SettingsRow::DiffContext => {
+                let lines = &mut draft.config.diff.context_lines;
+                *lines = if forward {
+                    (*lines + 1).min(MAX_CONTEXT_LINES)
+                } else {
+                    lines.saturating_sub(1)
+                };
+            }
+            SettingsRow::FoldThreshold => {
+                if let Some(threshold) =
+                    cycle(&FOLD_STEPS, &draft.config.diff.fold_threshold, forward)
+                {
+                    draft.config.diff.fold_threshold = threshold;
+                }
+            }
+            SettingsRow::IgnoreWhitespace => {
+                draft.config.diff.ignore_whitespace = !draft.config.diff.ignore_whitespace;
+            }
+            SettingsRow::Key(_) => self.edit = EditState::Capture,
+        }
+    }
+
+    fn row_text(&self, draft: &SettingsDraft, row: SettingsRow) -> (String, String) {
+        let config = &draft.config;
+        match row {
+            SettingsRow::Provider => ("AI provider".to_string(), draft.provider.clone()),
+            SettingsRow::Model => {
+                let value = match (&self.edit, self.rows[self.selected]) {
+                    (EditState::Text(text), SettingsRow::Model) => format!("{}▏", text),
+                    _ => draft.model.clone(),
+                };
+                ("AI model".to_string(), value)
+            }
+            SettingsRow::Theme => ("Theme".to_string(), config.theme_name.clone()),
+            SettingsRow::RefreshInterval => {
+                let value = match config.refresh_interval {
+                    0 => "off".to_string(),
+                    seconds => format!("{}s", seconds),
+                };
+                ("Auto refresh".to_string(), value)
+            }
+            SettingsRow::DiffView => (
+                "Diff view".to_string(),
+                format!("{:?}", config.diff.view_mode),
+            ),
+            SettingsRow::DiffContext => (
+                "Diff context lines".to_string(),
+                config.diff.context_lines.to_string(),
+            ),
+            SettingsRow::FoldThreshold => {
+                let value = match config.diff.fold_threshold {
+                    0 => "off".to_string(),
+                    lines => format!("{} lines", lines),
+                };
+                ("Fold unchanged".to_string(), value)
+            }
+            SettingsRow::IgnoreWhitespace => (
+                "Ignore whitespace".to_string(),
+                if config.diff.ignore_whitespace {
+                    "yes"
+                } else {
+                    "no"
+                }
+                .to_string(),
+            ),
+            SettingsRow::Key(index) => {
+                let (action, current, default) = config.key_bindings.entries()[index];
+                let capturing = self.edit == EditState::Capture && self.rows[self.selected] == row;
+                let value = if capturing {
+                    "press a key…".to_string()
+                } else if current == default {
+                    current.to_string()
+                } else {
+                    format!("{} (default {})", current, default)
+                };
+                (format!("Key: {}", action), value)
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let Some(draft) = self.draft.as_ref() else {
+            return;
+        };
+
+        let width = 64.min(area.width);
+        let height = (self.rows.len() as u16 + 5).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Settings · h/l change, Enter edit, s save, Esc cancel")
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+
+        let items: Vec<ListItem> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let (label, value) = self.row_text(draft, *row);
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<22}", label), Style::default().fg(Color::Cyan)),
+                    Span::raw(value),
+                ]))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        );
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.selected));
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        let footer = match &self.message {
+            Some(message) => Span::styled(message.clone(), Style::default().fg(Color::Red)),
+            None => Span::styled(
+                "Provider/model are saved to ~/.ai-commit/.env, the rest to ~/.ai-commit/tui/config.toml",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(footer))
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::TOP)),
+            chunks[1],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(panel: &mut SettingsPanel, code: KeyCode) -> SettingsAction {
+        panel.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_edit_and_save_draft() {
+        let mut panel = SettingsPanel::new();
+        panel.open(
+            SettingsDraft {
+                provider: "ollama".to_string(),
+                model: "mistral".to_string(),
+                config: AppConfig::default(),
+            },
+            vec!["ollama".to_string()],
+            vec!["dark".to_string(), "light".to_string()],
+        );
+
+        // 模型名称：Enter 开始编辑，Enter 确认
+        press(&mut panel, KeyCode::Down);
+        press(&mut panel, KeyCode::Enter);
+        for _ in 0.."mistral".len() {
+            press(&mut panel, KeyCode::Backspace);
+        }
+        for c in "qwen2".chars() {
+            press(&mut panel, KeyCode::Char(c));
+        }
+        press(&mut panel, KeyCode::Enter);
+
+        // 刷新间隔
+        press(&mut panel, KeyCode::Down);
+        press(&mut panel, KeyCode::Down);
+        press(&mut panel, KeyCode::Char('l'));
+
+        // 第一个按键绑定（quit）：冲突时保持原值
+        for _ in 0..5 {
+            press(&mut panel, KeyCode::Down);
+        }
+        press(&mut panel, KeyCode::Enter);
+        press(&mut panel, KeyCode::Char('/'));
+        assert!(panel.message.is_some());
+        press(&mut panel, KeyCode::Enter);
+        press(&mut panel, KeyCode::Char('x'));
+
+        let SettingsAction::Save(draft) = press(&mut panel, KeyCode::Char('s')) else {
+            panic!("expected save");
+        };
+        assert_eq!(draft.model, "qwen2");
+        assert_eq!(draft.config.refresh_interval, 5);
+        assert_eq!(draft.config.key_bindings.quit, 'x');
+        assert_eq!(press(&mut panel, KeyCode::Esc), SettingsAction::Close);
+    }
+}
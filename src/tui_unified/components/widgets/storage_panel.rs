@@ -0,0 +1,143 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+
+use crate::storage::StorageStats;
+
+/// 面板的内容
+#[derive(Debug, Clone, PartialEq)]
+enum StorageContent {
+    Loading,
+    Loaded(StorageStats),
+    Failed(String),
+}
+
+/// 存储概况浮层 - 显示存储后端的设置和已保存报告的统计
+pub struct StoragePanel {
+    open: bool,
+    content: StorageContent,
+}
+
+impl Default for StoragePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StoragePanel {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            content: StorageContent::Loading,
+        }
+    }
+
+    /// 打开面板，统计完成前显示加载中
+    pub fn open(&mut self) {
+        self.open = true;
+        self.content = StorageContent::Loading;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn set_stats(&mut self, result: anyhow::Result<StorageStats>) {
+        self.content = match result {
+            Ok(stats) => StorageContent::Loaded(stats),
+            Err(e) => StorageContent::Failed(e.to_string()),
+        };
+    }
+
+    /// 返回 true 时关闭面板
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.open {
+            return;
+        }
+
+        let width = 80.min(area.width);
+        let height = 18.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Storage")
+            .border_style(Style::default().fg(Color::Cyan));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let lines: Vec<Line> = match &self.content {
+            StorageContent::Loading => vec![Line::styled(
+                "Collecting storage statistics…",
+                Style::default().fg(Color::DarkGray),
+            )],
+            StorageContent::Failed(error) => vec![Line::styled(
+                format!("Failed to read storage: {}", error),
+                Style::default().fg(Color::Red),
+            )],
+            StorageContent::Loaded(stats) => {
+                let rows = stats.rows();
+                let label_width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+                rows.into_iter()
+                    .map(|(name, value)| {
+                        Line::from(vec![
+                            Span::styled(
+                                format!("{:<width$}  ", name, width = label_width),
+                                Style::default().fg(Color::Cyan),
+                            ),
+                            Span::raw(value),
+                        ])
+                    })
+                    .collect()
+            }
+        };
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+        frame.render_widget(
+            Paragraph::new("Esc close").style(Style::default().fg(Color::DarkGray)),
+            chunks[1],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_panel_lifecycle() {
+        let mut panel = StoragePanel::new();
+        panel.open();
+        assert!(panel.is_open());
+        assert_eq!(panel.content, StorageContent::Loading);
+
+        panel.set_stats(Ok(StorageStats {
+            backend: "file".to_string(),
+            total_reports: 2,
+            ..Default::default()
+        }));
+        assert!(matches!(panel.content, StorageContent::Loaded(_)));
+        panel.set_stats(Err(anyhow::anyhow!("boom")));
+        assert_eq!(panel.content, StorageContent::Failed("boom".to_string()));
+
+        assert!(!panel.handle_key(KeyEvent::from(KeyCode::Char('j'))));
+        assert!(panel.handle_key(KeyEvent::from(KeyCode::Esc)));
+    }
+}
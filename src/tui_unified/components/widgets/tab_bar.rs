@@ -0,0 +1,81 @@
+// 标签栏组件
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// 标签之间的分隔符
+const SEPARATOR: &str = " │ ";
+
+/// 每个标签左侧的起始列和宽度
+fn tab_spans(area: Rect, labels: &[&str]) -> Vec<(u16, u16)> {
+    let mut x = area.x + 1;
+    labels
+        .iter()
+        .map(|label| {
+            let width = Span::raw(*label).width() as u16 + 2;
+            let span = (x, width);
+            x += width + SEPARATOR.chars().count() as u16;
+            span
+        })
+        .collect()
+}
+
+/// 在一行中渲染标签，高亮选中的标签，右侧显示切换提示
+pub fn render_tab_bar(frame: &mut Frame, area: Rect, labels: &[&str], selected: usize) {
+    let mut spans = vec![Span::raw(" ")];
+    for (i, label) in labels.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(
+                SEPARATOR,
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        let style = if i == selected {
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        spans.push(Span::styled(format!(" {} ", label), style));
+    }
+    spans.push(Span::styled(
+        "  Tab to switch",
+        Style::default().fg(Color::DarkGray),
+    ));
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// 鼠标点击位置对应的标签
+pub fn tab_at(area: Rect, labels: &[&str], column: u16, row: u16) -> Option<usize> {
+    if row < area.y || row >= area.y + area.height {
+        return None;
+    }
+    tab_spans(area, labels)
+        .into_iter()
+        .position(|(x, width)| column >= x && column < x + width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tab_at() {
+        let area = Rect::new(0, 0, 60, 1);
+        let labels = ["Sidebar", "Content"];
+
+        // " Sidebar " 从第 1 列开始，宽 9；分隔符占 3 列
+        assert_eq!(tab_at(area, &labels, 1, 0), Some(0));
+        assert_eq!(tab_at(area, &labels, 9, 0), Some(0));
+        assert_eq!(tab_at(area, &labels, 11, 0), None);
+        assert_eq!(tab_at(area, &labels, 13, 0), Some(1));
+        assert_eq!(tab_at(area, &labels, 13, 1), None);
+        assert_eq!(tab_at(area, &labels, 40, 0), None);
+    }
+}
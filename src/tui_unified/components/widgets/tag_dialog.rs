@@ -0,0 +1,321 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{prelude::*, widgets::*};
+
+/// 确认创建时提交给应用的内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagRequest {
+    pub name: String,
+    /// 标签说明，为空时使用标签名
+    pub note: String,
+    pub push: bool,
+}
+
+/// 按键处理结果，由应用负责执行
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagDialogAction {
+    None,
+    Close,
+    /// 重新生成 AI 标签说明
+    Regenerate,
+    Create(TagRequest),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TagField {
+    Name,
+    Note,
+    Push,
+}
+
+/// 新建标签浮层 - 编辑建议的版本号和 AI 生成的标签说明，可选推送到远程
+pub struct TagDialog {
+    open: bool,
+    name: String,
+    note: String,
+    push: bool,
+    field: TagField,
+    /// 上一个标签，用于标题提示
+    previous: Option<String>,
+    /// 正在生成标签说明
+    generating: bool,
+    /// 用户已修改过说明，AI 结果不再覆盖
+    note_edited: bool,
+    message: Option<String>,
+}
+
+impl Default for TagDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TagDialog {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            name: String::new(),
+            note: String::new(),
+            push: false,
+            field: TagField::Name,
+            previous: None,
+            generating: false,
+            note_edited: false,
+            message: None,
+        }
+    }
+
+    /// 以建议的版本号打开浮层，标签说明随后由 `set_note` 填入
+    pub fn open(&mut self, suggested: String, previous: Option<String>) {
+        *self = Self {
+            open: true,
+            name: suggested,
+            previous,
+            generating: true,
+            ..Self::new()
+        };
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+        self.generating = false;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn set_generating(&mut self) {
+        self.generating = true;
+        self.note_edited = false;
+        self.message = None;
+    }
+
+    /// 填入生成结果，用户已编辑过说明时保留用户的内容
+    pub fn set_note(&mut self, result: Result<String, String>) {
+        self.generating = false;
+        match result {
+            Ok(note) if !self.note_edited => self.note = note.trim().to_string(),
+            Ok(_) => {}
+            Err(e) => self.message = Some(format!("AI note failed: {}", e)),
+        }
+    }
+
+    /// 在底部显示错误，如标签已存在
+    pub fn set_message(&mut self, message: String) {
+        self.message = Some(message);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> TagDialogAction {
+        if !self.open {
+            return TagDialogAction::Close;
+        }
+        self.message = None;
+
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return match key.code {
+                KeyCode::Char('s') => self.submit(),
+                KeyCode::Char('g') if !self.generating => TagDialogAction::Regenerate,
+                _ => TagDialogAction::None,
+            };
+        }
+
+        match key.code {
+            KeyCode::Esc => return TagDialogAction::Close,
+            KeyCode::Tab => self.move_field(true),
+            KeyCode::BackTab => self.move_field(false),
+            // 说明是多行文本，上下键不离开说明框
+            KeyCode::Down if self.field != TagField::Note => self.move_field(true),
+            KeyCode::Up if self.field != TagField::Note => self.move_field(false),
+            _ => match self.field {
+                TagField::Name => match key.code {
+                    KeyCode::Enter => return self.submit(),
+                    KeyCode::Backspace => {
+                        self.name.pop();
+                    }
+                    KeyCode::Char(c) if !c.is_whitespace() => self.name.push(c),
+                    _ => {}
+                },
+                TagField::Note => {
+                    match key.code {
+                        KeyCode::Enter => self.note.push('\n'),
+                        KeyCode::Backspace => {
+                            self.note.pop();
+                        }
+                        KeyCode::Char(c) => self.note.push(c),
+                        _ => return TagDialogAction::None,
+                    }
+                    self.note_edited = true;
+                }
+                TagField::Push => match key.code {
+                    KeyCode::Char(' ') | KeyCode::Enter => self.push = !self.push,
+                    _ => {}
+                },
+            },
+        }
+        TagDialogAction::None
+    }
+
+    fn move_field(&mut self, forward: bool) {
+        let fields = [TagField::Name, TagField::Note, TagField::Push];
+        let index = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        let next = if forward {
+            index + 1
+        } else {
+            index + fields.len() - 1
+        };
+        self.field = fields[next % fields.len()];
+    }
+
+    fn submit(&mut self) -> TagDialogAction {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            self.message = Some("Tag name cannot be empty".to_string());
+            return TagDialogAction::None;
+        }
+        let note = match self.note.trim() {
+            "" => name.clone(),
+            note => note.to_string(),
+        };
+        TagDialogAction::Create(TagRequest {
+            name,
+            note,
+            push: self.push,
+        })
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        if !self.open {
+            return;
+        }
+
+        let width = 72.min(area.width);
+        let height = 22.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        frame.render_widget(Clear, popup);
+
+        let title = match &self.previous {
+            Some(previous) => format!("New Tag (previous: {})", previous),
+            None => "New Tag".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner = block.inner(popup);
+        frame.render_widget(block, popup);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(2),
+            ])
+            .split(inner);
+
+        let field_style = |field: TagField| {
+            if self.field == field {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Gray)
+            }
+        };
+        let cursor = |field: TagField| if self.field == field { "▏" } else { "" };
+
+        frame.render_widget(
+            Paragraph::new(format!("{}{}", self.name, cursor(TagField::Name))).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Tag")
+                    .border_style(field_style(TagField::Name)),
+            ),
+            chunks[0],
+        );
+
+        let note_title = if self.generating {
+            "Annotation (generating with AI…)"
+        } else {
+            "Annotation (Ctrl+G regenerate)"
+        };
+        // 内容超出时只显示末尾，保证光标所在行可见
+        let note = format!("{}{}", self.note, cursor(TagField::Note));
+        let visible = chunks[1].height.saturating_sub(2) as usize;
+        let skip = note.lines().count().saturating_sub(visible);
+        let note: Vec<Line> = note.lines().skip(skip).map(Line::raw).collect();
+        frame.render_widget(
+            Paragraph::new(note).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(note_title)
+                    .border_style(field_style(TagField::Note)),
+            ),
+            chunks[1],
+        );
+
+        let checkbox = if self.push { "[x]" } else { "[ ]" };
+        frame.render_widget(
+            Paragraph::new(format!(" {} Push to origin after creating", checkbox))
+                .style(field_style(TagField::Push)),
+            chunks[2],
+        );
+
+        let footer = match &self.message {
+            Some(message) => Span::styled(message.clone(), Style::default().fg(Color::Red)),
+            None => Span::styled(
+                "Tab next field, Space toggle push, Ctrl+S or Enter on tag to create, Esc cancel",
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(footer))
+                .wrap(Wrap { trim: true })
+                .block(Block::default().borders(Borders::TOP)),
+            chunks[3],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(dialog: &mut TagDialog, code: KeyCode) -> TagDialogAction {
+        dialog.handle_key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_create_tag_request() {
+        let mut dialog = TagDialog::new();
+        dialog.open("v1.3.0".to_string(), Some("v1.2.4".to_string()));
+
+        // 版本号可以修改，空白字符被忽略
+        press(&mut dialog, KeyCode::Backspace);
+        press(&mut dialog, KeyCode::Char(' '));
+        press(&mut dialog, KeyCode::Char('1'));
+
+        // 用户编辑过说明后，AI 结果不再覆盖
+        press(&mut dialog, KeyCode::Tab);
+        press(&mut dialog, KeyCode::Char('x'));
+        dialog.set_note(Ok("AI notes".to_string()));
+        press(&mut dialog, KeyCode::Backspace);
+
+        press(&mut dialog, KeyCode::Tab);
+        press(&mut dialog, KeyCode::Char(' '));
+
+        assert_eq!(
+            dialog.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)),
+            TagDialogAction::Create(TagRequest {
+                name: "v1.3.1".to_string(),
+                note: "v1.3.1".to_string(),
+                push: true,
+            })
+        );
+        assert_eq!(press(&mut dialog, KeyCode::Esc), TagDialogAction::Close);
+    }
+}
@@ -1,16 +1,87 @@
 // 配置管理模块
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone)]
+use super::KeyBindings;
+use crate::config::{NotificationsSection, TuiSection};
+use crate::diff_viewer::DiffOptions;
+
+/// TUI 配置，保存在 <数据目录>/tui/config.toml，默认 ~/.ai-commit/tui/config.toml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub cache_size: usize,
     pub theme_name: String,
-    // TODO: 添加更多配置项
+    /// 自动刷新当前视图的间隔（秒），0 表示关闭
+    pub refresh_interval: u64,
+    /// 工作区文件或引用变化时自动刷新状态和 diff
+    pub watch_files: bool,
+    pub diff: DiffOptions,
+    pub key_bindings: KeyBindings,
+    /// 普通提示显示的秒数，来自 config.toml 的 `[notifications]`，不写入 TUI 配置
+    #[serde(skip)]
+    pub toast_seconds: u64,
+    /// 警告显示的秒数
+    #[serde(skip)]
+    pub warning_seconds: u64,
 }
 
 impl AppConfig {
-    pub fn load() -> Option<Self> {
-        // TODO: 从配置文件加载
-        None
+    pub fn path() -> Option<PathBuf> {
+        crate::config::data_dir()
+            .ok()
+            .map(|dir| dir.join("tui").join("config.toml"))
+    }
+
+    /// 以 config.toml 的 `[tui]` 为默认值，用于还没有保存过 TUI 配置时
+    pub fn from_section(tui: &TuiSection) -> Self {
+        let defaults = Self::default();
+        Self {
+            theme_name: tui.theme.clone().unwrap_or(defaults.theme_name.clone()),
+            refresh_interval: tui.refresh_interval.unwrap_or(defaults.refresh_interval),
+            watch_files: tui.watch_files.unwrap_or(defaults.watch_files),
+            ..defaults
+        }
+    }
+
+    /// 应用 config.toml 的 `[notifications]`
+    pub fn apply_notifications(&mut self, notifications: &NotificationsSection) {
+        if let Some(seconds) = notifications.toast_seconds {
+            self.toast_seconds = seconds;
+        }
+        if let Some(seconds) = notifications.warning_seconds {
+            self.warning_seconds = seconds;
+        }
+    }
+
+    /// 读取配置文件，文件不存在时返回 None
+    pub fn load() -> Result<Option<Self>> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load_from(&path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// 写回配置文件，返回文件路径
+    pub fn save(&self) -> Result<PathBuf> {
+        let path = Self::path().ok_or_else(|| anyhow::anyhow!("Cannot locate home directory"))?;
+        self.save_to(&path)?;
+        Ok(path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
     }
 }
 
@@ -19,6 +90,59 @@ impl Default for AppConfig {
         Self {
             cache_size: 1000,
             theme_name: "default".to_string(),
+            refresh_interval: 0,
+            watch_files: true,
+            diff: DiffOptions::default(),
+            key_bindings: KeyBindings::default(),
+            toast_seconds: 3,
+            warning_seconds: 5,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff_viewer::DiffViewMode;
+
+    #[test]
+    fn test_config_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tui").join("config.toml");
+
+        let mut config = AppConfig {
+            refresh_interval: 30,
+            ..AppConfig::default()
+        };
+        config.diff.view_mode = DiffViewMode::Unified;
+        config.key_bindings.set("quit", 'x').unwrap();
+        config.save_to(&path).unwrap();
+
+        assert_eq!(AppConfig::load_from(&path).unwrap(), config);
+
+        // 缺少的字段使用默认值
+        std::fs::write(&path, "refresh_interval = 5\n").unwrap();
+        let partial = AppConfig::load_from(&path).unwrap();
+        assert_eq!(partial.refresh_interval, 5);
+        assert_eq!(partial.key_bindings, KeyBindings::default());
+        assert_eq!(partial.toast_seconds, 3);
+    }
+
+    #[test]
+    fn test_defaults_from_config_file() {
+        let mut config = AppConfig::from_section(&TuiSection {
+            refresh_interval: Some(10),
+            watch_files: Some(false),
+            ..TuiSection::default()
+        });
+        assert_eq!(config.refresh_interval, 10);
+        assert!(!config.watch_files);
+        assert_eq!(config.theme_name, "default");
+
+        config.apply_notifications(&NotificationsSection {
+            toast_seconds: Some(1),
+            warning_seconds: None,
+        });
+        assert_eq!((config.toast_seconds, config.warning_seconds), (1, 5));
+    }
+}
@@ -1,5 +1,132 @@
-// 按键绑定配置占位符
-#[derive(Default)]
+use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
+/// 可自定义的全局按键
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct KeyBindings {
-    // TODO: 实现按键绑定配置
+    pub quit: char,
+    pub find: char,
+    pub refresh: char,
+    pub ai_commit: char,
+    pub review: char,
+    pub refactor: char,
+    pub theme: char,
+    pub zen: char,
+    pub settings: char,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            quit: 'q',
+            find: '/',
+            refresh: 'r',
+            ai_commit: 'c',
+            review: 'v',
+            refactor: 'f',
+            theme: 'T',
+            zen: 'z',
+            settings: ',',
+        }
+    }
+}
+
+impl KeyBindings {
+    /// 所有操作：(名称, 当前按键, 默认按键)
+    pub fn entries(&self) -> [(&'static str, char, char); 9] {
+        let default = Self::default();
+        [
+            ("quit", self.quit, default.quit),
+            ("find", self.find, default.find),
+            ("refresh", self.refresh, default.refresh),
+            ("ai_commit", self.ai_commit, default.ai_commit),
+            ("review", self.review, default.review),
+            ("refactor", self.refactor, default.refactor),
+            ("theme", self.theme, default.theme),
+            ("zen", self.zen, default.zen),
+            ("settings", self.settings, default.settings),
+        ]
+    }
+
+    /// 操作的说明，用于帮助面板
+    pub fn describe(action: &str) -> &'static str {
+        match action {
+            "quit" => "Quit",
+            "find" => "Fuzzy find",
+            "refresh" => "Refresh current view",
+            "ai_commit" => "AI commit",
+            "review" => "AI code review",
+            "refactor" => "AI refactor suggestions",
+            "theme" => "Cycle color theme",
+            "zen" => "Toggle zen mode",
+            "settings" => "Open settings",
+            _ => "",
+        }
+    }
+
+    /// 修改操作的按键，按键已被其他操作占用时返回错误
+    pub fn set(&mut self, action: &str, key: char) -> anyhow::Result<()> {
+        if let Some((other, _, _)) = self
+            .entries()
+            .into_iter()
+            .find(|(name, current, _)| *name != action && *current == key)
+        {
+            anyhow::bail!("'{}' is already bound to {}", key, other);
+        }
+
+        let slot = match action {
+            "quit" => &mut self.quit,
+            "find" => &mut self.find,
+            "refresh" => &mut self.refresh,
+            "ai_commit" => &mut self.ai_commit,
+            "review" => &mut self.review,
+            "refactor" => &mut self.refactor,
+            "theme" => &mut self.theme,
+            "zen" => &mut self.zen,
+            "settings" => &mut self.settings,
+            _ => anyhow::bail!("Unknown action: {}", action),
+        };
+        *slot = key;
+        Ok(())
+    }
+
+    /// 把自定义按键转换成默认按键，后续按键处理只需要识别默认按键；
+    /// 被改绑操作原来的默认按键不再生效
+    pub fn translate(&self, code: KeyCode) -> KeyCode {
+        let KeyCode::Char(c) = code else {
+            return code;
+        };
+        let entries = self.entries();
+        if let Some((_, _, default)) = entries
+            .iter()
+            .find(|(_, current, default)| *current == c && current != default)
+        {
+            return KeyCode::Char(*default);
+        }
+        if entries
+            .iter()
+            .any(|(_, current, default)| *default == c && *current != c)
+        {
+            return KeyCode::Null;
+        }
+        code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebind_and_translate() {
+        let mut bindings = KeyBindings::default();
+        bindings.set("quit", 'x').unwrap();
+
+        assert_eq!(bindings.translate(KeyCode::Char('x')), KeyCode::Char('q'));
+        assert_eq!(bindings.translate(KeyCode::Char('q')), KeyCode::Null);
+        assert_eq!(bindings.translate(KeyCode::Char('j')), KeyCode::Char('j'));
+        assert!(bindings.set("find", 'x').is_err());
+        assert!(bindings.set("missing", 'y').is_err());
+    }
 }
@@ -1,16 +1,45 @@
+use anyhow::{Context, Result};
+use ratatui::{buffer::Buffer, style::Color};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// 内置主题，按 `T` 键切换时的顺序
+pub const BUILTIN_THEMES: [&str; 3] = ["dark", "light", "solarized"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Theme {
+    #[serde(default)]
     pub name: String,
+    #[serde(default)]
     pub colors: ColorScheme,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 主题调色板
+///
+/// `background`/`foreground` 替换终端默认的背景色和前景色，其余字段替换界面中使用的同名颜色。
+/// 颜色可以是颜色名、`#rrggbb` 或 0-255 的索引色，未设置的颜色保持不变。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ColorScheme {
-    pub background: String,
-    pub foreground: String,
-    pub primary: String,
+    pub background: Option<String>,
+    pub foreground: Option<String>,
+    pub black: Option<String>,
+    pub red: Option<String>,
+    pub green: Option<String>,
+    pub yellow: Option<String>,
+    pub blue: Option<String>,
+    pub magenta: Option<String>,
+    pub cyan: Option<String>,
+    pub gray: Option<String>,
+    pub dark_gray: Option<String>,
+    pub light_red: Option<String>,
+    pub light_green: Option<String>,
+    pub light_yellow: Option<String>,
+    pub light_blue: Option<String>,
+    pub light_magenta: Option<String>,
+    pub light_cyan: Option<String>,
+    pub white: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,12 +47,309 @@ pub struct StyleScheme {
     // TODO: 实现样式方案
 }
 
-impl Default for ColorScheme {
-    fn default() -> Self {
+/// 解析后的调色板，渲染完成后对整个缓冲区做颜色替换
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThemePalette {
+    background: Option<Color>,
+    foreground: Option<Color>,
+    named: Vec<(Color, Color)>,
+}
+
+impl ThemePalette {
+    fn map(&self, color: Color, default: Option<Color>) -> Color {
+        if color == Color::Reset {
+            return default.unwrap_or(color);
+        }
+        self.named
+            .iter()
+            .find(|(from, _)| *from == color)
+            .map_or(color, |(_, to)| *to)
+    }
+
+    /// 将主题颜色应用到已渲染的缓冲区
+    pub fn apply(&self, buffer: &mut Buffer) {
+        if *self == Self::default() {
+            return;
+        }
+        for cell in buffer.content.iter_mut() {
+            cell.fg = self.map(cell.fg, self.foreground);
+            cell.bg = self.map(cell.bg, self.background);
+        }
+    }
+}
+
+impl ColorScheme {
+    fn entries(&self) -> [(&'static str, Option<Color>, &Option<String>); 18] {
+        [
+            ("background", None, &self.background),
+            ("foreground", None, &self.foreground),
+            ("black", Some(Color::Black), &self.black),
+            ("red", Some(Color::Red), &self.red),
+            ("green", Some(Color::Green), &self.green),
+            ("yellow", Some(Color::Yellow), &self.yellow),
+            ("blue", Some(Color::Blue), &self.blue),
+            ("magenta", Some(Color::Magenta), &self.magenta),
+            ("cyan", Some(Color::Cyan), &self.cyan),
+            ("gray", Some(Color::Gray), &self.gray),
+            ("dark_gray", Some(Color::DarkGray), &self.dark_gray),
+            ("light_red", Some(Color::LightRed), &self.light_red),
+            ("light_green", Some(Color::LightGreen), &self.light_green),
+            ("light_yellow", Some(Color::LightYellow), &self.light_yellow),
+            ("light_blue", Some(Color::LightBlue), &self.light_blue),
+            (
+                "light_magenta",
+                Some(Color::LightMagenta),
+                &self.light_magenta,
+            ),
+            ("light_cyan", Some(Color::LightCyan), &self.light_cyan),
+            ("white", Some(Color::White), &self.white),
+        ]
+    }
+
+    /// 解析所有颜色字符串
+    pub fn palette(&self) -> Result<ThemePalette> {
+        let mut palette = ThemePalette::default();
+        for (key, from, value) in self.entries() {
+            let Some(value) = value else {
+                continue;
+            };
+            let color = Color::from_str(value)
+                .map_err(|_| anyhow::anyhow!("Invalid color '{}' for '{}'", value, key))?;
+            match (key, from) {
+                ("background", _) => palette.background = Some(color),
+                ("foreground", _) => palette.foreground = Some(color),
+                (_, Some(from)) => palette.named.push((from, color)),
+                _ => {}
+            }
+        }
+        Ok(palette)
+    }
+}
+
+fn colors(pairs: &[(&str, &str)]) -> ColorScheme {
+    let mut scheme = ColorScheme::default();
+    for (key, value) in pairs {
+        let value = Some(value.to_string());
+        match *key {
+            "background" => scheme.background = value,
+            "foreground" => scheme.foreground = value,
+            "black" => scheme.black = value,
+            "red" => scheme.red = value,
+            "green" => scheme.green = value,
+            "yellow" => scheme.yellow = value,
+            "blue" => scheme.blue = value,
+            "magenta" => scheme.magenta = value,
+            "cyan" => scheme.cyan = value,
+            "gray" => scheme.gray = value,
+            "dark_gray" => scheme.dark_gray = value,
+            "light_red" => scheme.light_red = value,
+            "light_green" => scheme.light_green = value,
+            "light_yellow" => scheme.light_yellow = value,
+            "light_blue" => scheme.light_blue = value,
+            "light_magenta" => scheme.light_magenta = value,
+            "light_cyan" => scheme.light_cyan = value,
+            "white" => scheme.white = value,
+            _ => {}
+        }
+    }
+    scheme
+}
+
+impl Theme {
+    /// 深色主题：使用终端自身的配色
+    pub fn dark() -> Self {
+        Self {
+            name: "dark".to_string(),
+            colors: ColorScheme::default(),
+        }
+    }
+
+    /// 浅色主题：白底深色文字
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            colors: colors(&[
+                ("background", "#ffffff"),
+                ("foreground", "#1f2328"),
+                ("black", "#ffffff"),
+                ("white", "#1f2328"),
+                ("gray", "#57606a"),
+                ("dark_gray", "#afb8c1"),
+                ("red", "#cf222e"),
+                ("light_red", "#a40e26"),
+                ("green", "#1a7f37"),
+                ("light_green", "#116329"),
+                ("yellow", "#9a6700"),
+                ("light_yellow", "#7d4e00"),
+                ("blue", "#0969da"),
+                ("light_blue", "#0550ae"),
+                ("magenta", "#8250df"),
+                ("light_magenta", "#6639ba"),
+                ("cyan", "#1b7c83"),
+                ("light_cyan", "#136061"),
+            ]),
+        }
+    }
+
+    /// Solarized 深色主题
+    pub fn solarized() -> Self {
         Self {
-            background: "black".to_string(),
-            foreground: "white".to_string(),
-            primary: "blue".to_string(),
+            name: "solarized".to_string(),
+            colors: colors(&[
+                ("background", "#002b36"),
+                ("foreground", "#839496"),
+                ("black", "#002b36"),
+                ("dark_gray", "#586e75"),
+                ("gray", "#93a1a1"),
+                ("white", "#eee8d5"),
+                ("red", "#dc322f"),
+                ("light_red", "#cb4b16"),
+                ("green", "#859900"),
+                ("light_green", "#859900"),
+                ("yellow", "#b58900"),
+                ("light_yellow", "#b58900"),
+                ("blue", "#268bd2"),
+                ("light_blue", "#268bd2"),
+                ("magenta", "#d33682"),
+                ("light_magenta", "#6c71c4"),
+                ("cyan", "#2aa198"),
+                ("light_cyan", "#2aa198"),
+            ]),
         }
     }
+
+    /// 按名称获取内置主题，`default` 等同于 `dark`
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" | "default" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "solarized" => Some(Self::solarized()),
+            _ => None,
+        }
+    }
+
+    /// 从 TOML 调色板解析主题
+    pub fn from_toml(name: &str, content: &str) -> Result<Self> {
+        let mut theme: Theme =
+            toml::from_str(content).with_context(|| format!("Failed to parse theme '{}'", name))?;
+        if theme.name.is_empty() {
+            theme.name = name.to_string();
+        }
+        // 提前校验颜色，避免切换时才发现错误
+        theme.colors.palette()?;
+        Ok(theme)
+    }
+
+    /// 自定义主题目录：<数据目录>/tui/themes/<name>.toml
+    pub fn custom_dir() -> Option<PathBuf> {
+        crate::config::data_dir()
+            .ok()
+            .map(|dir| dir.join("tui").join("themes"))
+    }
+
+    /// 加载内置主题或自定义主题
+    pub fn load(name: &str) -> Result<Self> {
+        if let Some(theme) = Self::builtin(name) {
+            return Ok(theme);
+        }
+        let path = Self::custom_dir()
+            .map(|dir| dir.join(format!("{}.toml", name)))
+            .ok_or_else(|| anyhow::anyhow!("Cannot locate home directory"))?;
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Unknown theme '{}' ({} not found)", name, path.display()))?;
+        Self::from_toml(name, &content)
+    }
+
+    /// 所有可选主题：内置主题加上自定义目录中的 TOML 文件
+    pub fn available() -> Vec<String> {
+        let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|s| s.to_string()).collect();
+        let mut custom: Vec<String> = Self::custom_dir()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                (path.extension()? == "toml")
+                    .then(|| path.file_stem()?.to_str().map(str::to_string))
+                    .flatten()
+            })
+            .filter(|name| !names.contains(name))
+            .collect();
+        custom.sort();
+        names.extend(custom);
+        names
+    }
+
+    /// 按顺序返回下一个主题名称
+    pub fn next_name(current: &str, available: &[String]) -> String {
+        let current = if current == "default" {
+            "dark"
+        } else {
+            current
+        };
+        let index = available.iter().position(|name| name == current);
+        match index {
+            Some(index) => available[(index + 1) % available.len()].clone(),
+            None => available
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "dark".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_builtin_palettes_are_valid() {
+        for name in BUILTIN_THEMES {
+            assert!(Theme::builtin(name).unwrap().colors.palette().is_ok());
+        }
+        assert_eq!(
+            Theme::dark().colors.palette().unwrap(),
+            ThemePalette::default()
+        );
+    }
+
+    #[test]
+    fn test_apply_remaps_buffer_colors() {
+        let palette = Theme::from_toml(
+            "custom",
+            "[colors]\nbackground = \"#000011\"\nyellow = \"blue\"\n",
+        )
+        .unwrap()
+        .colors
+        .palette()
+        .unwrap();
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.get_mut(0, 0).set_fg(Color::Yellow);
+        buffer.get_mut(1, 0).set_fg(Color::Green).set_bg(Color::Red);
+        palette.apply(&mut buffer);
+
+        assert_eq!(buffer.get(0, 0).fg, Color::Blue);
+        assert_eq!(buffer.get(0, 0).bg, Color::Rgb(0, 0, 0x11));
+        assert_eq!(buffer.get(1, 0).fg, Color::Green);
+        assert_eq!(buffer.get(1, 0).bg, Color::Red);
+    }
+
+    #[test]
+    fn test_custom_theme_validation() {
+        let theme = Theme::from_toml("ocean", "[colors]\nforeground = \"white\"\n").unwrap();
+        assert_eq!(theme.name, "ocean");
+
+        let err = Theme::from_toml("bad", "[colors]\nred = \"not-a-color\"\n").unwrap_err();
+        assert!(err.to_string().contains("red"));
+    }
+
+    #[test]
+    fn test_next_name_cycles() {
+        let available: Vec<String> = BUILTIN_THEMES.iter().map(|s| s.to_string()).collect();
+        assert_eq!(Theme::next_name("default", &available), "light");
+        assert_eq!(Theme::next_name("solarized", &available), "dark");
+        assert_eq!(Theme::next_name("missing", &available), "dark");
+    }
 }
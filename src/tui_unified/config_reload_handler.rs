@@ -0,0 +1,87 @@
+// 配置热重载：配置文件修改后应用模型、主题、刷新间隔等设置，无需重启
+use crate::config::{Config, ConfigReload};
+use crate::tui_unified::{config::Theme, state::app_state::NotificationLevel, Result};
+
+impl super::app::TuiUnifiedApp {
+    /// 配置文件变化时重新加载并提示，新配置无效时保留原来的设置
+    pub(crate) async fn handle_config_reload(&mut self) -> Result<()> {
+        let Some(reload) = self.config_watcher.poll() else {
+            return Ok(());
+        };
+        let path = self
+            .config_watcher
+            .path()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+
+        let issues = match reload {
+            ConfigReload::Reloaded(issues) => issues,
+            ConfigReload::Invalid(e) => {
+                self.state.write().await.add_notification(
+                    format!("Invalid configuration, keeping previous settings: {:#}", e),
+                    NotificationLevel::Error,
+                );
+                return Ok(());
+            }
+        };
+
+        let mut problems: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        let settings = Config::new();
+        // 已创建的 Agent 保留了旧的提供商和模型，下次使用时重新创建
+        self.agent_manager = None;
+
+        // 设置面板保存的 TUI 配置优先，只应用配置文件中改动过的项
+        let tui = settings.tui;
+        if tui.theme != self.tui_defaults.theme {
+            if let Some(theme_name) = &tui.theme {
+                match Theme::load(theme_name).and_then(|theme| theme.colors.palette()) {
+                    Ok(palette) => {
+                        self.theme = palette;
+                        self.config.theme_name = theme_name.clone();
+                    }
+                    Err(e) => problems.push(format!("Failed to load theme: {}", e)),
+                }
+            }
+        }
+        if tui.refresh_interval != self.tui_defaults.refresh_interval {
+            if let Some(interval) = tui.refresh_interval {
+                self.config.refresh_interval = interval;
+            }
+        }
+        if tui.watch_files != self.tui_defaults.watch_files {
+            if let Some(watch_files) = tui.watch_files {
+                self.config.watch_files = watch_files;
+                if !watch_files {
+                    self.repo_watcher = None;
+                } else if self.repo_watcher.is_none() {
+                    if let Err(e) = self.start_file_watch() {
+                        problems.push(format!("Failed to watch repository: {}", e));
+                    }
+                }
+            }
+        }
+        self.tui_defaults = tui;
+        self.config.apply_notifications(&settings.notifications);
+
+        let mut state = self.state.write().await;
+        state.config.theme_name = self.config.theme_name.clone();
+        state.config.refresh_interval = self.config.refresh_interval;
+        state.config.watch_files = self.config.watch_files;
+        state.config.apply_notifications(&settings.notifications);
+        if problems.is_empty() {
+            state.add_notification(
+                format!("Configuration reloaded from {}", path),
+                NotificationLevel::Info,
+            );
+        } else {
+            state.add_notification(
+                format!(
+                    "Configuration reloaded with problems: {}",
+                    problems.join("; ")
+                ),
+                NotificationLevel::Warning,
+            );
+        }
+        Ok(())
+    }
+}
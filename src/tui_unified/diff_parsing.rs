@@ -73,6 +73,20 @@ impl super::app::TuiUnifiedApp {
                 continue;
             }
 
+            if let Some(hidden) = crate::diff_viewer::parse_fold_marker(line) {
+                // 折叠的未修改行：两边显示同一标记并跳过对应行号
+                let fold_line = Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                left_lines.push(fold_line.clone());
+                right_lines.push(fold_line);
+                old_line_num += hidden as u32;
+                new_line_num += hidden as u32;
+                i += 1;
+                continue;
+            }
+
             if line.starts_with('-') {
                 // 收集连续的删除行
                 let mut removed_lines = Vec::new();
@@ -242,6 +256,19 @@ impl super::app::TuiUnifiedApp {
                 continue;
             }
 
+            if let Some(hidden) = crate::diff_viewer::parse_fold_marker(line) {
+                // 折叠的未修改行：两边显示同一标记并跳过对应行号
+                let fold_line = Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                removed_lines.push(fold_line.clone());
+                added_lines.push(fold_line);
+                old_line_num += hidden as u32;
+                new_line_num += hidden as u32;
+                continue;
+            }
+
             if let Some(line_content) = line.strip_prefix('-') {
                 // 删除的行
                 let formatted_line = format!("{:4} │ {}", old_line_num, line_content);
@@ -345,6 +372,17 @@ impl super::app::TuiUnifiedApp {
                 continue;
             }
 
+            if let Some(hidden) = crate::diff_viewer::parse_fold_marker(line) {
+                // 折叠的未修改行
+                lines.push(Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(Color::DarkGray),
+                )));
+                old_line_num += hidden as u32;
+                new_line_num += hidden as u32;
+                continue;
+            }
+
             if let Some(line_content) = line.strip_prefix('-') {
                 // 删除的行
                 let formatted_line = format!("{:4}   │ -{}", old_line_num, line_content);
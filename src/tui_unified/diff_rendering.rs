@@ -40,7 +40,8 @@ impl super::app::TuiUnifiedApp {
     pub(super) fn ensure_diff_cache(&mut self) {
         let (content_hash, view_mode) = match &self.diff_viewer {
             Some(v) if !v.current_diff.is_empty() => {
-                let hash = DiffRenderCache::hash_content(&v.current_diff);
+                // 按折叠后的显示内容计算哈希，展开/收起时自动失效
+                let hash = DiffRenderCache::hash_content(v.display_diff());
                 let mode = v.view_mode.clone();
                 (hash, mode)
             }
@@ -69,7 +70,12 @@ impl super::app::TuiUnifiedApp {
         }
 
         // Clone diff content for parsing (only on cache miss)
-        let diff_content = self.diff_viewer.as_ref().unwrap().current_diff.clone();
+        let diff_content = self
+            .diff_viewer
+            .as_ref()
+            .unwrap()
+            .display_diff()
+            .to_string();
 
         match view_mode {
             DiffViewMode::Unified => {
@@ -151,15 +157,30 @@ impl super::app::TuiUnifiedApp {
             .style(Style::default().fg(Color::White).bg(Color::Black));
         frame.render_widget(info_paragraph, main_chunks[0]);
 
-        // 内容区：根据视图模式渲染不同的diff显示
-        self.render_diff_content_by_mode(frame, viewer, main_chunks[1]);
+        // 内容区：统一视图可在左侧显示修改块跳转列表
+        let content_area = if viewer.view_mode == crate::diff_viewer::DiffViewMode::Unified
+            && viewer.show_hunk_list
+            && !viewer.hunks.is_empty()
+        {
+            let chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(28), Constraint::Min(0)])
+                .split(main_chunks[1]);
+            self.render_hunk_list(frame, viewer, chunks[0]);
+            chunks[1]
+        } else {
+            main_chunks[1]
+        };
+        self.render_diff_content_by_mode(frame, viewer, content_area);
 
         // 状态栏 - 添加视图切换说明
         let status_text = format!(
-            "File {}/{} | Scroll: {} | View Mode: {} | Keys: 1-Unified 2-Side-by-Side 3-Split q-Close",
+            "File {}/{} | Scroll: {} | Context: {} | Folded: {} | View Mode: {} | Keys: 1-Unified 2-Side-by-Side 3-Split ]/[ Hunk }}/{{ File s-Hunk list o/O-Expand +/- Context q-Close",
             viewer.selected_file + 1,
             viewer.files.len().max(1),
             viewer.diff_scroll,
+            viewer.options.context_lines,
+            viewer.collapsed_fold_count(),
             match viewer.view_mode {
                 crate::diff_viewer::DiffViewMode::Unified => "Unified",
                 crate::diff_viewer::DiffViewMode::SideBySide => "Side-by-Side",
@@ -172,6 +193,42 @@ impl super::app::TuiUnifiedApp {
         frame.render_widget(status_paragraph, main_chunks[2]);
     }
 
+    /// 渲染当前文件的修改块跳转列表
+    fn render_hunk_list(
+        &self,
+        frame: &mut ratatui::Frame,
+        viewer: &DiffViewer,
+        area: ratatui::layout::Rect,
+    ) {
+        use ratatui::{
+            style::{Color, Modifier, Style},
+            widgets::{Block, Borders, List, ListItem, ListState},
+        };
+
+        let items: Vec<ListItem> = viewer
+            .hunk_labels()
+            .into_iter()
+            .enumerate()
+            .map(|(i, label)| ListItem::new(format!("{:>2}. {}", i + 1, label)))
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Hunks ({})", viewer.hunks.len())),
+            )
+            .style(Style::default().fg(Color::White).bg(Color::Black))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let mut state = ListState::default();
+        state.select(Some(viewer.current_hunk));
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
     pub(super) fn render_diff_content_by_mode(
         &self,
         frame: &mut ratatui::Frame,
@@ -205,7 +262,7 @@ impl super::app::TuiUnifiedApp {
                     cached.clone()
                 } else {
                     let diff_content = if !viewer.current_diff.is_empty() {
-                        viewer.current_diff.clone()
+                        viewer.display_diff().to_string()
                     } else {
                         "No diff content available".to_string()
                     };
@@ -235,7 +292,7 @@ impl super::app::TuiUnifiedApp {
                         cached.clone()
                     } else {
                         let diff_content = if !viewer.current_diff.is_empty() {
-                            viewer.current_diff.clone()
+                            viewer.display_diff().to_string()
                         } else {
                             "No diff content available".to_string()
                         };
@@ -276,7 +333,7 @@ impl super::app::TuiUnifiedApp {
                         cached.clone()
                     } else {
                         let diff_content = if !viewer.current_diff.is_empty() {
-                            viewer.current_diff.clone()
+                            viewer.display_diff().to_string()
                         } else {
                             "No diff content available".to_string()
                         };
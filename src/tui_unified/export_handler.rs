@@ -0,0 +1,228 @@
+// 导出当前视图：把视图内容写入 text、Markdown 或 JSON 文件
+use crate::diff_viewer::DiffViewer;
+use crate::report::{formatter_for, CodeReviewReport, ExportSection, ReportFormat, ViewExport};
+use crate::tui_unified::{
+    components::base::component::ViewComponent,
+    state::app_state::{InputPurpose, ModalType, NotificationLevel, ViewType},
+    Result,
+};
+use std::path::PathBuf;
+
+/// 要导出的内容，审查视图直接导出完整报告
+#[derive(Debug, Clone)]
+pub(crate) enum ExportContent {
+    Report(CodeReviewReport),
+    View(ViewExport),
+}
+
+/// 等待输入文件名的导出
+#[derive(Debug, Clone)]
+pub(crate) struct PendingExport {
+    content: ExportContent,
+    /// 从 diff 查看器导出时记录提交，结束后回到 diff
+    diff_commit: Option<String>,
+}
+
+impl ExportContent {
+    fn title(&self) -> &str {
+        match self {
+            ExportContent::Report(report) => &report.target,
+            ExportContent::View(export) => &export.title,
+        }
+    }
+}
+
+/// 按扩展名选择导出格式，没有扩展名时使用 Markdown
+fn export_target(input: &str) -> anyhow::Result<(PathBuf, ReportFormat)> {
+    let path = PathBuf::from(input);
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => Ok((path.clone(), ext.parse()?)),
+        None => Ok((path.with_extension("md"), ReportFormat::Markdown)),
+    }
+}
+
+/// 默认文件名，如 `ai-commit-git-log-20240101-100000.md`
+fn default_file_name(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    format!(
+        "ai-commit-{}-{}.md",
+        slug,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// diff 查看器中的提交信息、文件统计和当前文件的 diff
+fn diff_export(viewer: &DiffViewer) -> ViewExport {
+    let info = &viewer.commit_info;
+    let commit = vec![
+        format!("Commit: {}", info.hash),
+        format!("Author: {}", info.author),
+        format!("Date: {}", info.date.format("%Y-%m-%d %H:%M:%S")),
+        format!(
+            "Message: {}",
+            info.message.lines().next().unwrap_or_default()
+        ),
+    ];
+    let files = viewer
+        .files
+        .iter()
+        .map(|file| format!("{} (+{} / -{})", file.path, file.additions, file.deletions))
+        .collect();
+    let mut sections = vec![
+        ExportSection::list("Commit", commit),
+        ExportSection::list("Files", files),
+    ];
+    if let Some(path) = viewer.selected_file_path() {
+        sections.push(ExportSection::code(
+            path,
+            "diff",
+            viewer.current_diff.lines().map(str::to_string).collect(),
+        ));
+    }
+    ViewExport::new(
+        &format!("Diff · {}", &info.hash[..8.min(info.hash.len())]),
+        sections,
+    )
+}
+
+impl super::app::TuiUnifiedApp {
+    fn current_view_component(&self, view: ViewType) -> &dyn ViewComponent {
+        match view {
+            ViewType::GitLog => &self.git_log_view,
+            ViewType::Branches => &self.branches_view,
+            ViewType::Tags => &self.tags_view,
+            ViewType::Remotes => &self.remotes_view,
+            ViewType::Stash => &self.stash_view,
+            ViewType::QueryHistory => &self.query_history_view,
+            ViewType::Staging => &self.staging_view,
+            ViewType::Graph => &self.graph_view,
+            ViewType::Reflog => &self.reflog_view,
+            ViewType::Blame => &self.blame_view,
+            ViewType::Worktrees => &self.worktrees_view,
+            ViewType::Review => &self.review_view,
+            ViewType::Hotspots => &self.hotspots_view,
+        }
+    }
+
+    /// 打开 diff 时导出 diff，否则导出当前视图，先询问文件名
+    pub(crate) async fn prompt_export(&mut self) -> Result<()> {
+        let mut state = self.state.write().await;
+        let in_diff = state
+            .modal
+            .as_ref()
+            .is_some_and(|modal| modal.modal_type == ModalType::DiffViewer);
+
+        let pending = match (&self.diff_viewer, state.current_view) {
+            (Some(viewer), _) if in_diff => PendingExport {
+                content: ExportContent::View(diff_export(viewer)),
+                diff_commit: Some(viewer.commit_hash.clone()),
+            },
+            (_, ViewType::Review) if self.review_view.report().is_some() => PendingExport {
+                content: ExportContent::Report(
+                    self.review_view.report().cloned().unwrap_or_default(),
+                ),
+                diff_commit: None,
+            },
+            (_, view) => {
+                let component = self.current_view_component(view);
+                let sections = component.export();
+                if sections.iter().all(|section| section.lines.is_empty()) {
+                    state.add_notification(
+                        format!("Nothing to export in {}", component.title()),
+                        NotificationLevel::Warning,
+                    );
+                    return Ok(());
+                }
+                PendingExport {
+                    content: ExportContent::View(ViewExport::new(&component.title(), sections)),
+                    diff_commit: None,
+                }
+            }
+        };
+
+        let file_name = default_file_name(pending.content.title());
+        state.request_input(
+            InputPurpose::ExportView,
+            "Export View",
+            &format!(
+                "Export {} to file (.txt, .md, .json, .html or .csv):",
+                pending.content.title()
+            ),
+        );
+        state.edit_input(|value| *value = file_name);
+        self.pending_export = Some(pending);
+        Ok(())
+    }
+
+    /// 按输入的文件名写出导出内容
+    pub(crate) async fn export_to_file(&mut self, input: &str) -> Result<()> {
+        let Some(pending) = self.pending_export.take() else {
+            return Ok(());
+        };
+
+        let written = export_target(input).and_then(|(path, format)| {
+            let formatter = formatter_for(format);
+            let output = match &pending.content {
+                ExportContent::Report(report) => formatter.format(report)?,
+                ExportContent::View(export) => formatter.format_view(export)?,
+            };
+            std::fs::write(&path, output)?;
+            Ok(path)
+        });
+
+        let mut state = self.state.write().await;
+        match written {
+            Ok(path) => {
+                state.add_notification(
+                    format!("Exported {} to {}", pending.content.title(), path.display()),
+                    NotificationLevel::Success,
+                );
+            }
+            Err(e) => {
+                state.add_notification(format!("Export failed: {}", e), NotificationLevel::Error);
+            }
+        }
+        if let Some(hash) = pending.diff_commit {
+            state.show_diff_modal(hash, String::new());
+        }
+        Ok(())
+    }
+
+    /// 取消导出，从 diff 导出时回到 diff
+    pub(crate) async fn cancel_export(&mut self) {
+        if let Some(hash) = self
+            .pending_export
+            .take()
+            .and_then(|pending| pending.diff_commit)
+        {
+            self.state
+                .write()
+                .await
+                .show_diff_modal(hash, String::new());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_target_format() {
+        let (path, format) = export_target("log.json").unwrap();
+        assert_eq!(path, PathBuf::from("log.json"));
+        assert_eq!(format, ReportFormat::Json);
+
+        let (path, format) = export_target("notes/log").unwrap();
+        assert_eq!(path, PathBuf::from("notes/log.md"));
+        assert_eq!(format, ReportFormat::Markdown);
+
+        assert!(export_target("log.pdf").is_err());
+        assert!(default_file_name("Git Log (Details)").starts_with("ai-commit-git-log-details-"));
+    }
+}
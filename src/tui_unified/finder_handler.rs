@@ -0,0 +1,173 @@
+use crossterm::event::KeyEvent;
+
+use super::app::AppMode;
+use crate::tui_unified::{
+    components::widgets::fuzzy_finder::{FinderAction, FinderItem, FinderKind},
+    focus::FocusPanel,
+    state::{
+        app_state::{NotificationLevel, ViewType},
+        AppState,
+    },
+    Result,
+};
+
+/// 汇总模糊查找的候选条目，分数相同时按这里的顺序排列
+fn collect_finder_items(state: &AppState, files: Vec<String>) -> Vec<FinderItem> {
+    let repo = &state.repo_state;
+    let mut items = Vec::new();
+
+    items.extend(repo.branches.iter().map(|branch| FinderItem {
+        kind: FinderKind::Branch,
+        text: branch.name.clone(),
+        detail: branch.upstream.clone().unwrap_or_default(),
+        target: branch.name.clone(),
+    }));
+    items.extend(repo.tags.iter().map(|tag| FinderItem {
+        kind: FinderKind::Tag,
+        text: tag.name.clone(),
+        detail: tag.message.clone().unwrap_or_default(),
+        target: tag.name.clone(),
+    }));
+    items.extend(repo.stashes.iter().map(|stash| FinderItem {
+        kind: FinderKind::Stash,
+        text: format!("stash@{{{}}}: {}", stash.index, stash.message),
+        detail: stash.branch.clone(),
+        target: stash.index.to_string(),
+    }));
+    items.extend(repo.commits.iter().map(|commit| FinderItem {
+        kind: FinderKind::Commit,
+        text: format!("{} {}", commit.short_hash, commit.subject),
+        detail: format!("{} · {}", commit.author, commit.date.format("%Y-%m-%d")),
+        target: commit.hash.clone(),
+    }));
+    items.extend(files.into_iter().map(|path| FinderItem {
+        kind: FinderKind::File,
+        text: path.clone(),
+        detail: String::new(),
+        target: path,
+    }));
+
+    items
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 打开全局模糊查找浮层
+    pub(crate) async fn open_fuzzy_finder(&mut self) -> Result<()> {
+        let repo_path = std::env::current_dir()?;
+        let git = crate::tui_unified::git::interface::AsyncGitImpl::new(repo_path);
+        // 文件列表读取失败时仍然可以搜索其他条目
+        let files = git.list_files().await.unwrap_or_default();
+
+        let state = self.state.read().await;
+        self.fuzzy_finder.open(collect_finder_items(&state, files));
+        self.current_mode = AppMode::Search;
+        Ok(())
+    }
+
+    fn close_fuzzy_finder(&mut self) {
+        self.fuzzy_finder.close();
+        self.current_mode = AppMode::Normal;
+    }
+
+    pub(crate) async fn handle_finder_key(&mut self, key: KeyEvent) -> Result<()> {
+        let action = {
+            let mut state = self.state.write().await;
+            self.fuzzy_finder.handle_key(key, &mut state)
+        };
+
+        match action {
+            FinderAction::None => {}
+            FinderAction::Close => self.close_fuzzy_finder(),
+            FinderAction::Jump(item) => {
+                self.close_fuzzy_finder();
+                self.jump_to_finder_item(item).await?;
+            }
+            FinderAction::Filter(query) => {
+                self.close_fuzzy_finder();
+                self.execute_search(&query).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 切换到条目所在的视图并选中它，文件在 Blame 视图中打开
+    async fn jump_to_finder_item(&mut self, item: FinderItem) -> Result<()> {
+        let mut state = self.state.write().await;
+        let previous_view = state.current_view;
+
+        let found = match item.kind {
+            FinderKind::Commit => {
+                state.set_current_view(ViewType::GitLog);
+                // 分支视图可能只留下了某个分支的提交，找不到时恢复完整历史
+                self.git_log_view.select_commit(&item.target) || {
+                    self.git_log_view.set_branch_filter(None);
+                    self.git_log_view
+                        .update_commits(state.repo_state.commits.clone());
+                    self.git_log_view.select_commit(&item.target)
+                }
+            }
+            FinderKind::Branch => {
+                state.set_current_view(ViewType::Branches);
+                self.branches_view.select_branch(&item.target, &state)
+            }
+            FinderKind::Tag => {
+                state.set_current_view(ViewType::Tags);
+                self.tags_view.select_tag(&item.target, &state)
+            }
+            FinderKind::Stash => {
+                state.set_current_view(ViewType::Stash);
+                item.target
+                    .parse()
+                    .is_ok_and(|index| self.stash_view.select_stash(index))
+            }
+            FinderKind::File => {
+                state.request_blame(item.target.clone());
+                true
+            }
+        };
+        self.focus_manager.set_focus(FocusPanel::Content);
+
+        if !found {
+            state.add_notification(
+                format!("'{}' is no longer in the list", item.text),
+                NotificationLevel::Warning,
+            );
+        }
+        drop(state);
+
+        self.handle_view_entry(previous_view).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui_unified::state::git_state::Branch;
+
+    #[tokio::test]
+    async fn test_collect_finder_items() {
+        let config = crate::tui_unified::config::AppConfig::default();
+        let mut state = AppState::new(&config).await.unwrap();
+        state.repo_state.tags.clear();
+        state.repo_state.stashes.clear();
+        state.repo_state.commits.clear();
+        state.repo_state.branches = vec![Branch {
+            name: "main".to_string(),
+            full_name: "refs/heads/main".to_string(),
+            is_current: true,
+            is_remote: false,
+            upstream: Some("origin/main".to_string()),
+            last_commit: None,
+            ahead_count: 0,
+            behind_count: 0,
+            last_updated: chrono::Utc::now(),
+        }];
+
+        let items = collect_finder_items(&state, vec!["src/main.rs".to_string()]);
+        let kinds: Vec<FinderKind> = items.iter().map(|item| item.kind).collect();
+        assert_eq!(kinds, vec![FinderKind::Branch, FinderKind::File]);
+        assert_eq!(items[0].detail, "origin/main");
+        assert_eq!(items[1].target, "src/main.rs");
+    }
+}
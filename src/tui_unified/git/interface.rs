@@ -101,6 +101,227 @@ impl AsyncGitImpl {
         Ok(file_count as u32)
     }
 
+    // Helper method to get signature status codes (%G?) keyed by commit hash
+    async fn get_signature_statuses(
+        &self,
+        limit_arg: &str,
+    ) -> Result<std::collections::HashMap<String, char>, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args(["log", "--format=%H %G?", "-n", limit_arg])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            return Ok(std::collections::HashMap::new());
+        }
+
+        let output_str = String::from_utf8_lossy(&output.stdout);
+        Ok(output_str
+            .lines()
+            .filter_map(|line| {
+                let (hash, code) = line.split_once(' ')?;
+                Some((hash.to_string(), code.chars().next()?))
+            })
+            .collect())
+    }
+
+    /// 分页获取提交历史，`rev` 为空时从 HEAD 开始
+    pub async fn get_commit_page(
+        &self,
+        rev: Option<&str>,
+        skip: usize,
+        limit: usize,
+    ) -> Result<Vec<Commit>, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args([
+                "log",
+                rev.unwrap_or("HEAD"),
+                &format!("--format={}", Commit::PAGE_FORMAT),
+                &format!("--skip={}", skip),
+                &format!("--max-count={}", limit),
+                "--",
+            ])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Git log command failed: {}", stderr).into());
+        }
+
+        Ok(Commit::parse_page(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// 分页获取提交图数据（所有本地分支、远程分支和标签，按拓扑顺序）
+    pub async fn get_graph_commits(
+        &self,
+        skip: usize,
+        limit: usize,
+    ) -> Result<Vec<GraphCommit>, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args([
+                "log",
+                "--branches",
+                "--tags",
+                "--remotes",
+                "HEAD",
+                "--topo-order",
+                "--date=short",
+                "--decorate=full",
+                &format!("--format={}", GraphCommit::LOG_FORMAT),
+                &format!("--skip={}", skip),
+                &format!("--max-count={}", limit),
+            ])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Git log command failed: {}", stderr).into());
+        }
+
+        Ok(GraphCommit::parse_log(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// 获取 HEAD 的 reflog 条目
+    pub async fn get_reflog(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<ReflogEntry>, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args([
+                "reflog",
+                "--date=unix",
+                &format!("--format={}", ReflogEntry::LOG_FORMAT),
+                &format!("--max-count={}", limit),
+            ])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Git reflog command failed: {}", stderr).into());
+        }
+
+        Ok(ReflogEntry::parse_log(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// 获取文件的逐行 blame 信息
+    pub async fn get_blame(
+        &self,
+        file_path: &str,
+    ) -> Result<Vec<BlameLine>, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args(["blame", "--line-porcelain", "--", file_path])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Git blame command failed: {}", stderr).into());
+        }
+
+        Ok(BlameLine::parse_porcelain(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// 获取提交详情和每个文件的变更行数
+    pub async fn get_commit_detail(
+        &self,
+        hash: &str,
+    ) -> Result<CommitDetail, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args([
+                "show",
+                "--numstat",
+                "--no-renames",
+                "--date=format:%Y-%m-%d %H:%M",
+                &format!("--format={}", CommitDetail::SHOW_FORMAT),
+                hash,
+            ])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Git show command failed: {}", stderr).into());
+        }
+
+        CommitDetail::parse_show(&String::from_utf8_lossy(&output.stdout))
+            .ok_or_else(|| format!("Unexpected git show output for {}", hash).into())
+    }
+
+    /// 列出仓库中所有被追踪的文件
+    pub async fn list_files(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args(["ls-files"])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Git ls-files command failed: {}", stderr).into());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+
+    /// 获取提交的完整提交信息（标题和正文）
+    pub async fn get_commit_message(
+        &self,
+        hash: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args(["log", "-1", "--format=%B", hash])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Git log command failed: {}", stderr).into());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim_end()
+            .to_string())
+    }
+
+    /// 重命名本地分支
+    pub async fn rename_branch(
+        &self,
+        from: &str,
+        to: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let output = Command::new("git")
+            .args(["branch", "-m", from, to])
+            .current_dir(&self.repo_path)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Git rename branch command failed: {}", stderr).into());
+        }
+
+        Ok(())
+    }
+
     // Helper method to get detailed commit statistics (files changed, insertions, deletions)
     async fn get_commit_stats(
         &self,
@@ -216,10 +437,20 @@ impl GitRepositoryAPI for AsyncGitImpl {
                     author,
                     date,
                     files_changed: files_changed as u32,
+                    signature_status: None,
                 });
             }
         }
 
+        // 附加签名状态（单独查询，避免 %G? 影响主格式的字段解析）
+        let signatures = self
+            .get_signature_statuses(&limit_arg)
+            .await
+            .unwrap_or_default();
+        for commit in &mut commits {
+            commit.signature_status = signatures.get(&commit.hash).copied();
+        }
+
         Ok(commits)
     }
 
@@ -253,8 +484,8 @@ impl GitRepositoryAPI for AsyncGitImpl {
                 let _commit_hash = parts[1].trim().to_string(); // For future use
 
                 let mut upstream = None;
-                let mut _ahead_count = 0; // For future use
-                let mut _behind_count = 0; // For future use
+                let mut ahead_count = 0;
+                let mut behind_count = 0;
 
                 // Extract upstream info if present
                 if let Some(bracket_start) = line.find('[') {
@@ -276,7 +507,7 @@ impl GitRepositoryAPI for AsyncGitImpl {
                                     if let Ok(count) =
                                         ahead_str[..next_comma_or_end].trim().parse::<usize>()
                                     {
-                                        _ahead_count = count;
+                                        ahead_count = count;
                                     }
                                 }
                             }
@@ -289,7 +520,7 @@ impl GitRepositoryAPI for AsyncGitImpl {
                                     if let Ok(count) =
                                         behind_str[..next_comma_or_end].trim().parse::<usize>()
                                     {
-                                        _behind_count = count;
+                                        behind_count = count;
                                     }
                                 }
                             }
@@ -303,6 +534,8 @@ impl GitRepositoryAPI for AsyncGitImpl {
                     name,
                     is_current,
                     upstream,
+                    ahead_count,
+                    behind_count,
                 });
             }
         }
@@ -883,6 +1116,7 @@ impl AsyncGitImpl {
                     author,
                     date,
                     files_changed: files_changed as u32,
+                    signature_status: None,
                 });
             }
         }
@@ -7,6 +7,8 @@ pub struct Commit {
     pub author: String,
     pub date: String,
     pub files_changed: u32,
+    /// `%G?` 签名状态码，None 表示未加载
+    pub signature_status: Option<char>,
 }
 
 impl Commit {
@@ -17,8 +19,35 @@ impl Commit {
             author,
             date,
             files_changed: 0,
+            signature_status: None,
         }
     }
+
+    /// 分页加载 Git Log 时的 `git log --format`：哈希、作者、日期、签名状态、标题
+    pub const PAGE_FORMAT: &'static str = "%H%x1f%an%x1f%aI%x1f%G?%x1f%s%x1e";
+
+    /// 解析 [`Self::PAGE_FORMAT`] 格式的 `git log` 输出
+    pub fn parse_page(output: &str) -> Vec<Commit> {
+        output
+            .split('\x1e')
+            .filter_map(|record| {
+                let mut fields = record.trim_start_matches('\n').split('\x1f');
+                let hash = fields.next().filter(|h| !h.is_empty())?.to_string();
+                let author = fields.next()?.to_string();
+                let date = fields.next()?.to_string();
+                let signature_status = fields.next()?.chars().next();
+                let message = fields.next().unwrap_or_default().to_string();
+                Some(Commit {
+                    hash,
+                    message,
+                    author,
+                    date,
+                    files_changed: 0,
+                    signature_status,
+                })
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +55,9 @@ pub struct Branch {
     pub name: String,
     pub is_current: bool,
     pub upstream: Option<String>,
+    /// 相对上游领先和落后的提交数
+    pub ahead_count: usize,
+    pub behind_count: usize,
 }
 
 impl Branch {
@@ -34,6 +66,8 @@ impl Branch {
             name,
             is_current,
             upstream: None,
+            ahead_count: 0,
+            behind_count: 0,
         }
     }
 }
@@ -101,5 +135,333 @@ impl FileStatus {
     }
 }
 
+/// 提交图使用的提交信息（包含父提交和引用）
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphCommit {
+    pub hash: String,
+    pub parents: Vec<String>,
+    /// `%D` 输出的引用，如 `HEAD -> refs/heads/main`、`tag: refs/tags/v1.0`
+    pub refs: Vec<String>,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+}
+
+impl GraphCommit {
+    /// `git log` 使用的格式，字段以 0x1f 分隔、记录以 0x1e 结尾
+    pub const LOG_FORMAT: &'static str = "%H%x1f%P%x1f%D%x1f%an%x1f%ad%x1f%s%x1e";
+
+    /// 解析 [`Self::LOG_FORMAT`] 格式的 `git log` 输出
+    pub fn parse_log(output: &str) -> Vec<GraphCommit> {
+        output
+            .split('\x1e')
+            .filter_map(|record| {
+                let mut fields = record.trim_start_matches('\n').split('\x1f');
+                let hash = fields.next().filter(|h| !h.is_empty())?.to_string();
+                let parents = fields
+                    .next()?
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect();
+                let refs = fields
+                    .next()?
+                    .split(", ")
+                    .filter(|r| !r.is_empty())
+                    .map(str::to_string)
+                    .collect();
+                Some(GraphCommit {
+                    hash,
+                    parents,
+                    refs,
+                    author: fields.next()?.to_string(),
+                    date: fields.next()?.to_string(),
+                    subject: fields.next().unwrap_or_default().to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// reflog 条目
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReflogEntry {
+    pub hash: String,
+    /// 选择器，如 `HEAD@{3}`
+    pub selector: String,
+    /// 条目记录时间（Unix 时间戳）
+    pub timestamp: i64,
+    /// 操作类型，如 `commit`、`checkout`、`reset`、`rebase (finish)`
+    pub operation: String,
+    pub message: String,
+}
+
+impl ReflogEntry {
+    /// `git reflog --date=unix` 使用的格式
+    pub const LOG_FORMAT: &'static str = "%H%x1f%gd%x1f%gs%x1e";
+
+    /// 解析 [`Self::LOG_FORMAT`] 格式的输出，选择器按条目顺序生成
+    pub fn parse_log(output: &str) -> Vec<ReflogEntry> {
+        output
+            .split('\x1e')
+            .filter_map(|record| {
+                let mut fields = record.trim_start_matches('\n').split('\x1f');
+                let hash = fields.next().filter(|h| !h.is_empty())?.to_string();
+                // --date=unix 时 %gd 形如 HEAD@{1700000000}
+                let timestamp = fields
+                    .next()?
+                    .rsplit_once("@{")
+                    .and_then(|(_, ts)| ts.trim_end_matches('}').parse().ok())
+                    .unwrap_or_default();
+                let subject = fields.next().unwrap_or_default();
+                let (operation, message) = subject.split_once(": ").unwrap_or((subject, ""));
+                Some((hash, timestamp, operation.to_string(), message.to_string()))
+            })
+            .enumerate()
+            .map(
+                |(index, (hash, timestamp, operation, message))| ReflogEntry {
+                    hash,
+                    selector: format!("HEAD@{{{}}}", index),
+                    timestamp,
+                    operation,
+                    message,
+                },
+            )
+            .collect()
+    }
+}
+
+/// `git blame` 的单行注解
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameLine {
+    pub hash: String,
+    pub author: String,
+    /// 作者提交时间（Unix 时间戳）
+    pub timestamp: i64,
+    pub summary: String,
+    /// 文件中的行号，从 1 开始
+    pub line_number: usize,
+    pub content: String,
+}
+
+impl BlameLine {
+    /// 未提交修改在 blame 中使用的全零哈希
+    pub fn is_uncommitted(&self) -> bool {
+        self.hash.chars().all(|c| c == '0')
+    }
+
+    /// 解析 `git blame --line-porcelain` 的输出
+    pub fn parse_porcelain(output: &str) -> Vec<BlameLine> {
+        let mut lines = Vec::new();
+        let mut current: Option<BlameLine> = None;
+
+        for line in output.lines() {
+            if let Some(content) = line.strip_prefix('\t') {
+                if let Some(mut entry) = current.take() {
+                    entry.content = content.to_string();
+                    lines.push(entry);
+                }
+                continue;
+            }
+
+            if let Some(entry) = current.as_mut() {
+                if let Some(author) = line.strip_prefix("author ") {
+                    entry.author = author.to_string();
+                } else if let Some(time) = line.strip_prefix("author-time ") {
+                    entry.timestamp = time.parse().unwrap_or_default();
+                } else if let Some(summary) = line.strip_prefix("summary ") {
+                    entry.summary = summary.to_string();
+                }
+                continue;
+            }
+
+            // 头部行：<hash> <原行号> <最终行号> [<组内行数>]
+            let mut fields = line.split(' ');
+            let (Some(hash), Some(_), Some(final_line)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            current = Some(BlameLine {
+                hash: hash.to_string(),
+                author: String::new(),
+                timestamp: 0,
+                summary: String::new(),
+                line_number: final_line.parse().unwrap_or_default(),
+                content: String::new(),
+            });
+        }
+
+        lines
+    }
+}
+
+/// 提交中单个文件的变更行数，二进制文件没有行数统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChangeStat {
+    pub path: String,
+    pub additions: Option<usize>,
+    pub deletions: Option<usize>,
+}
+
+/// 提交详情：元信息和变更文件统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommitDetail {
+    pub hash: String,
+    pub author: String,
+    pub author_email: String,
+    pub date: String,
+    /// `%D` 输出的引用，如 `HEAD -> main`、`tag: v1.0`
+    pub refs: Vec<String>,
+    /// 完整提交信息（标题和正文）
+    pub message: String,
+    pub files: Vec<FileChangeStat>,
+}
+
+impl CommitDetail {
+    /// `git show --numstat` 使用的格式，头部字段以 0x1f 分隔、以 0x1e 结尾，其后是 numstat 行
+    pub const SHOW_FORMAT: &'static str = "%H%x1f%an%x1f%ae%x1f%ad%x1f%D%x1f%B%x1e";
+
+    /// 解析 [`Self::SHOW_FORMAT`] 格式的 `git show --numstat` 输出
+    pub fn parse_show(output: &str) -> Option<CommitDetail> {
+        let (header, numstat) = output.split_once('\x1e')?;
+        let mut fields = header.split('\x1f');
+        let hash = fields.next().filter(|h| !h.is_empty())?.to_string();
+        let author = fields.next()?.to_string();
+        let author_email = fields.next()?.to_string();
+        let date = fields.next()?.to_string();
+        let refs = fields
+            .next()?
+            .split(", ")
+            .filter(|r| !r.is_empty())
+            .map(str::to_string)
+            .collect();
+        let message = fields.next().unwrap_or_default().trim_end().to_string();
+
+        // numstat 行：<新增>\t<删除>\t<路径>，二进制文件的行数为 `-`
+        let files = numstat
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(3, '\t');
+                let additions = parts.next()?;
+                let deletions = parts.next()?;
+                let path = parts.next()?;
+                Some(FileChangeStat {
+                    path: path.to_string(),
+                    additions: additions.parse().ok(),
+                    deletions: deletions.parse().ok(),
+                })
+            })
+            .collect();
+
+        Some(CommitDetail {
+            hash,
+            author,
+            author_email,
+            date,
+            refs,
+            message,
+            files,
+        })
+    }
+}
+
 // Re-export QueryHistoryEntry from the main query_history module
 pub use crate::query_history::QueryHistoryEntry;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commit_page() {
+        let output = "aaa\x1fAlice\x1f2024-05-01T10:00:00+08:00\x1fG\x1ffeat: a | b\x1e\nbbb\x1fBob\x1f2024-04-30T09:00:00+00:00\x1fN\x1f\x1e\n";
+        let commits = Commit::parse_page(output);
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].message, "feat: a | b");
+        assert_eq!(commits[0].date, "2024-05-01T10:00:00+08:00");
+        assert_eq!(commits[0].signature_status, Some('G'));
+        assert_eq!(commits[1].author, "Bob");
+        assert_eq!(commits[1].message, "");
+    }
+
+    #[test]
+    fn test_parse_graph_log() {
+        let output = "m1\x1fa1 b1\x1fHEAD -> main, tag: v1.0\x1fAlice\x1f2024-05-01\x1fMerge branch 'feature'\x1e\nb1\x1fa1\x1f\x1fBob\x1f2024-04-30\x1ffeat: add x\x1e\n";
+        let commits = GraphCommit::parse_log(output);
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].parents, vec!["a1", "b1"]);
+        assert_eq!(commits[0].refs, vec!["HEAD -> main", "tag: v1.0"]);
+        assert_eq!(commits[0].subject, "Merge branch 'feature'");
+        assert!(commits[1].refs.is_empty());
+        assert_eq!(commits[1].author, "Bob");
+    }
+
+    #[test]
+    fn test_parse_commit_detail() {
+        let output = "abc123\x1fAlice\x1falice@example.com\x1f2024-05-01 10:00\x1fHEAD -> main, tag: v1.0\x1ffeat: add x\n\nLonger body\n\x1e\n\n10\t2\tsrc/lib.rs\n-\t-\tassets/logo.png\n";
+        let detail = CommitDetail::parse_show(output).unwrap();
+
+        assert_eq!(detail.hash, "abc123");
+        assert_eq!(detail.author_email, "alice@example.com");
+        assert_eq!(detail.refs, vec!["HEAD -> main", "tag: v1.0"]);
+        assert_eq!(detail.message, "feat: add x\n\nLonger body");
+        assert_eq!(detail.files.len(), 2);
+        assert_eq!(detail.files[0].additions, Some(10));
+        assert_eq!(detail.files[0].deletions, Some(2));
+        assert_eq!(detail.files[1].path, "assets/logo.png");
+        assert_eq!(detail.files[1].additions, None);
+    }
+
+    #[test]
+    fn test_parse_reflog() {
+        let output = "abc\x1fHEAD@{1700000100}\x1fcommit (amend): fix typo\x1e\ndef\x1fHEAD@{1700000000}\x1fcheckout: moving from main to feature\x1e\n";
+        let entries = ReflogEntry::parse_log(output);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].selector, "HEAD@{0}");
+        assert_eq!(entries[0].timestamp, 1700000100);
+        assert_eq!(entries[0].operation, "commit (amend)");
+        assert_eq!(entries[0].message, "fix typo");
+        assert_eq!(entries[1].selector, "HEAD@{1}");
+        assert_eq!(entries[1].operation, "checkout");
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        let output = "\
+aaaa1111 1 1 2
+author Alice
+author-mail <alice@example.com>
+author-time 1700000000
+summary feat: init
+filename src/lib.rs
+\tfn main() {
+aaaa1111 2 2
+author Alice
+author-time 1700000000
+summary feat: init
+filename src/lib.rs
+\t}
+0000000000000000000000000000000000000000 3 3 1
+author Not Committed Yet
+author-time 1700000500
+summary Version of src/lib.rs from src/lib.rs
+filename src/lib.rs
+\t// todo
+";
+        let lines = BlameLine::parse_porcelain(output);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].hash, "aaaa1111");
+        assert_eq!(lines[0].author, "Alice");
+        assert_eq!(lines[0].timestamp, 1700000000);
+        assert_eq!(lines[0].content, "fn main() {");
+        assert_eq!(lines[1].line_number, 2);
+        assert_eq!(lines[1].content, "}");
+        assert!(!lines[1].is_uncommitted());
+        assert!(lines[2].is_uncommitted());
+        assert_eq!(lines[2].content, "// todo");
+    }
+}
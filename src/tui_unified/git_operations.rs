@@ -1,8 +1,28 @@
 use crate::diff_viewer::DiffViewer;
 use crate::tui_unified::components::base::component::Component;
+use crate::tui_unified::components::views::git_log::GIT_LOG_PAGE_SIZE;
+use crate::tui_unified::components::views::graph::GRAPH_PAGE_SIZE;
+use crate::tui_unified::components::views::reflog::REFLOG_LIMIT;
 use crate::tui_unified::git::interface::GitRepositoryAPI;
+use crate::tui_unified::state::{DataSection, ReflogAction, RepoStatus, StagingOperation};
 use crate::tui_unified::Result;
 
+/// 读取工作区状态（包含未追踪目录中的文件）
+async fn load_repo_status(repo_path: &std::path::Path) -> Option<RepoStatus> {
+    let output = tokio::process::Command::new("git")
+        .args(["status", "--porcelain", "-z", "--untracked-files=all"])
+        .current_dir(repo_path)
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(RepoStatus::from_porcelain(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
 /// 将 git interface 的 Commit 转换为 TUI state 的 Commit
 fn convert_commits(
     commits_data: Vec<crate::tui_unified::git::models::Commit>,
@@ -16,12 +36,15 @@ fn convert_commits(
             author_email: format!("{}@example.com", c.author),
             committer: c.author.clone(),
             committer_email: format!("{}@example.com", c.author),
-            date: chrono::DateTime::parse_from_str(
-                &format!("{} 00:00:00 +0000", c.date),
-                "%Y-%m-%d %H:%M:%S %z",
-            )
-            .unwrap_or_else(|_| chrono::Utc::now().into())
-            .with_timezone(&chrono::Utc),
+            date: chrono::DateTime::parse_from_rfc3339(&c.date)
+                .or_else(|_| {
+                    chrono::DateTime::parse_from_str(
+                        &format!("{} 00:00:00 +0000", c.date),
+                        "%Y-%m-%d %H:%M:%S %z",
+                    )
+                })
+                .unwrap_or_else(|_| chrono::Utc::now().into())
+                .with_timezone(&chrono::Utc),
             message: c.message.clone(),
             subject: c.message,
             body: None,
@@ -30,6 +53,9 @@ fn convert_commits(
             files_changed: c.files_changed as usize,
             insertions: 0,
             deletions: 0,
+            signature: c
+                .signature_status
+                .map(crate::git::SignatureStatus::from_code),
         })
         .collect()
 }
@@ -47,8 +73,8 @@ fn convert_branches(
             is_remote: false,
             upstream: b.upstream,
             last_commit: None,
-            ahead_count: 0,
-            behind_count: 0,
+            ahead_count: b.ahead_count,
+            behind_count: b.behind_count,
             last_updated: chrono::Utc::now(),
         })
         .collect()
@@ -104,6 +130,92 @@ fn convert_stashes(
         .collect()
 }
 
+/// 后台加载得到的一类仓库数据
+enum SectionData {
+    Commits(Vec<crate::tui_unified::state::git_state::Commit>),
+    Branches(
+        Option<String>,
+        Vec<crate::tui_unified::state::git_state::Branch>,
+    ),
+    Tags(Vec<crate::tui_unified::state::git_state::Tag>),
+    Status(RepoStatus),
+    Remotes(Vec<crate::tui_unified::state::git_state::Remote>),
+    Stashes(Vec<crate::tui_unified::state::git_state::Stash>),
+}
+
+impl SectionData {
+    async fn load(
+        repo_path: &std::path::Path,
+        section: DataSection,
+    ) -> std::result::Result<Self, String> {
+        let git = crate::tui_unified::git::interface::AsyncGitImpl::new(repo_path.to_path_buf());
+        let data = match section {
+            DataSection::Commits => git
+                .get_commits(Some(100))
+                .await
+                .map(|commits| Self::Commits(convert_commits(commits))),
+            DataSection::Branches => {
+                let current_branch = git.get_current_branch().await.ok();
+                git.get_branches()
+                    .await
+                    .map(|branches| Self::Branches(current_branch, convert_branches(branches)))
+            }
+            DataSection::Tags => git
+                .get_tags()
+                .await
+                .map(|tags| Self::Tags(convert_tags(tags))),
+            DataSection::Status => {
+                return load_repo_status(repo_path)
+                    .await
+                    .map(Self::Status)
+                    .ok_or_else(|| "git status failed".to_string());
+            }
+            DataSection::Remotes => git
+                .get_remotes()
+                .await
+                .map(|remotes| Self::Remotes(convert_remotes(remotes))),
+            DataSection::Stashes => git
+                .get_stashes()
+                .await
+                .map(|stashes| Self::Stashes(convert_stashes(stashes))),
+        };
+        data.map_err(|e| e.to_string())
+    }
+
+    fn apply(self, state: &mut crate::tui_unified::state::AppState) {
+        match self {
+            Self::Commits(commits) => state.repo_state.update_commits(commits),
+            Self::Branches(current_branch, branches) => {
+                if let Some(branch) = current_branch {
+                    state.repo_state.update_current_branch(branch);
+                }
+                state.repo_state.update_branches(branches);
+            }
+            Self::Tags(tags) => state.repo_state.update_tags(tags),
+            Self::Status(status) => state.repo_state.update_status(status),
+            Self::Remotes(remotes) => state.repo_state.update_remotes(remotes),
+            Self::Stashes(stashes) => state.repo_state.update_stashes(stashes),
+        }
+    }
+}
+
+/// 在后台加载一类仓库数据并写入状态，完成后（无论成败）结束对应面板的加载占位
+pub(super) async fn load_data_section(
+    repo_path: std::path::PathBuf,
+    section: DataSection,
+    state: std::sync::Arc<tokio::sync::RwLock<crate::tui_unified::state::AppState>>,
+) -> std::result::Result<String, String> {
+    // 加载期间不持有锁，界面可以继续渲染
+    let data = SectionData::load(&repo_path, section).await;
+
+    let mut state = state.write().await;
+    state.finish_loading_section(section);
+    data.map(|data| {
+        data.apply(&mut state);
+        format!("Loaded {}", section.label())
+    })
+}
+
 impl super::app::TuiUnifiedApp {
     /// 加载初始Git数据
     ///
@@ -119,7 +231,7 @@ impl super::app::TuiUnifiedApp {
         let current_branch = git.get_current_branch().await.ok();
         let commits = git.get_commits(Some(100)).await.ok().map(convert_commits);
         let branches = git.get_branches().await.ok().map(convert_branches);
-        let status = git.get_status().await.ok();
+        let status = load_repo_status(&repo_path).await;
         let tags = git.get_tags().await.ok().map(convert_tags);
         let remotes = git.get_remotes().await.ok().map(convert_remotes);
         let stashes = git.get_stashes().await.ok().map(convert_stashes);
@@ -137,20 +249,8 @@ impl super::app::TuiUnifiedApp {
             if let Some(branches) = branches {
                 state.repo_state.update_branches(branches);
             }
-            if let Some(status_text) = status {
-                let is_clean = status_text.trim() == "Working tree clean";
-                state
-                    .repo_state
-                    .update_status(crate::tui_unified::state::git_state::RepoStatus {
-                        staged_files: Vec::new(),
-                        unstaged_files: Vec::new(),
-                        untracked_files: Vec::new(),
-                        conflicts: Vec::new(),
-                        ahead_count: 0,
-                        behind_count: 0,
-                        is_clean,
-                        is_detached: false,
-                    });
+            if let Some(status) = status {
+                state.repo_state.update_status(status);
             }
             if let Some(tags) = tags {
                 state.repo_state.update_tags(tags);
@@ -168,7 +268,9 @@ impl super::app::TuiUnifiedApp {
         let state_ref = &*self.state.read().await;
         self.remotes_view.load_remotes(state_ref).await;
         self.stash_view.load_stashes(state_ref).await;
-        self.query_history_view.load_history().await;
+        self.query_history_view
+            .load_history(&state_ref.search_state.favorite_queries)
+            .await;
 
         // 更新GitLogView的commit数据
         if let Some(commits) = commits {
@@ -191,9 +293,16 @@ impl super::app::TuiUnifiedApp {
         };
 
         if let Some(hash) = commit_hash {
+            let file_path = self.state.read().await.take_pending_diff_file();
+
             // 创建DiffViewer实例
-            match DiffViewer::new(&hash).await {
-                Ok(diff_viewer) => {
+            match DiffViewer::with_options(&hash, self.config.diff.clone()).await {
+                Ok(mut diff_viewer) => {
+                    // 从提交详情面板进入时直接定位到对应文件
+                    if let Some(path) = file_path {
+                        diff_viewer.select_file(&path).await;
+                    }
+
                     // 保存diff_viewer实例
                     self.diff_viewer = Some(diff_viewer);
 
@@ -228,61 +337,290 @@ impl super::app::TuiUnifiedApp {
         Ok(())
     }
 
-    /// 处理 hunk 级暂存请求
-    pub(crate) async fn handle_pending_hunk_stage(&mut self) -> Result<()> {
-        let hunk_request = {
+    /// 处理暂存视图的 stage/unstage 请求
+    pub(crate) async fn handle_pending_staging_operation(&mut self) -> Result<()> {
+        let operation = {
             let state = self.state.read().await;
-            let value = state
-                .selected_items
-                .pending_hunk_stage
-                .lock()
-                .unwrap_or_else(|e| e.into_inner())
-                .take();
-            value
+            state.take_staging_operation()
+        };
+
+        let Some(operation) = operation else {
+            return Ok(());
+        };
+
+        let (args, patch, success_message): (Vec<String>, Option<String>, String) = match operation
+        {
+            StagingOperation::StageFile(path) => (
+                vec!["add".into(), "--".into(), path.clone()],
+                None,
+                format!("Staged {}", path),
+            ),
+            StagingOperation::UnstageFile(path) => (
+                vec![
+                    "reset".into(),
+                    "-q".into(),
+                    "HEAD".into(),
+                    "--".into(),
+                    path.clone(),
+                ],
+                None,
+                format!("Unstaged {}", path),
+            ),
+            StagingOperation::StageAll => (
+                vec!["add".into(), "-A".into()],
+                None,
+                "Staged all changes".to_string(),
+            ),
+            StagingOperation::UnstageAll => (
+                vec!["reset".into(), "-q".into()],
+                None,
+                "Unstaged all changes".to_string(),
+            ),
+            StagingOperation::ApplyHunk {
+                file_path,
+                patch,
+                reverse,
+            } => {
+                // 使用 git apply --cached 暂存单个 hunk，--reverse 则从暂存区移除
+                let mut args: Vec<String> = vec!["apply".into(), "--cached".into()];
+                if reverse {
+                    args.push("--reverse".into());
+                }
+                let action = if reverse { "Unstaged" } else { "Staged" };
+                (
+                    args,
+                    Some(patch),
+                    format!("{} hunk in {}", action, file_path),
+                )
+            }
         };
 
-        if let Some((_file_path, patch)) = hunk_request {
-            // 使用 git apply --cached 暂存单个 hunk
-            let mut child = tokio::process::Command::new("git")
-                .args(["apply", "--cached", "--allow-empty"])
-                .stdin(std::process::Stdio::piped())
-                .stdout(std::process::Stdio::piped())
-                .stderr(std::process::Stdio::piped())
-                .spawn()
-                .map_err(|e| anyhow::anyhow!("Failed to spawn git apply: {}", e))?;
-
-            if let Some(mut stdin) = child.stdin.take() {
+        let mut child = tokio::process::Command::new("git")
+            .args(&args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow::anyhow!("Failed to spawn git {}: {}", args[0], e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            if let Some(patch) = patch {
                 use tokio::io::AsyncWriteExt;
                 stdin
                     .write_all(patch.as_bytes())
                     .await
                     .map_err(|e| anyhow::anyhow!("Failed to write patch to stdin: {}", e))?;
-                drop(stdin);
             }
+            drop(stdin);
+        }
 
-            let output = child
-                .wait_with_output()
-                .await
-                .map_err(|e| anyhow::anyhow!("Failed to wait for git apply: {}", e))?;
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to wait for git {}: {}", args[0], e))?;
 
+        {
             let mut state = self.state.write().await;
             if output.status.success() {
                 state.add_notification(
-                    "Hunk staged successfully".to_string(),
-                    crate::tui_unified::state::app_state::NotificationLevel::Info,
+                    success_message,
+                    crate::tui_unified::state::app_state::NotificationLevel::Success,
                 );
             } else {
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 state.add_notification(
-                    format!("Failed to stage hunk: {}", stderr.trim()),
+                    format!("git {} failed: {}", args[0], stderr.trim()),
                     crate::tui_unified::state::app_state::NotificationLevel::Error,
                 );
             }
         }
 
+        if let Err(e) = self.refresh_staging().await {
+            let mut state = self.state.write().await;
+            state.add_notification(
+                format!("Refresh failed: {}", e),
+                crate::tui_unified::state::app_state::NotificationLevel::Error,
+            );
+        }
+
         Ok(())
     }
 
+    /// 处理暂存视图的文件 diff 预览请求
+    pub(crate) async fn handle_pending_staging_diff(&mut self) -> Result<()> {
+        let (request, untracked_files) = {
+            let state = self.state.read().await;
+            (
+                state.take_staging_diff(),
+                state.repo_state.status.untracked_files.clone(),
+            )
+        };
+
+        if let Some((file_path, staged)) = request {
+            let untracked = untracked_files
+                .iter()
+                .any(|path| path == std::path::Path::new(&file_path));
+
+            let args: Vec<&str> = if staged {
+                vec!["diff", "--cached", "--", &file_path]
+            } else if untracked {
+                // 未追踪文件与空文件对比，显示完整内容
+                vec!["diff", "--no-index", "--", "/dev/null", &file_path]
+            } else {
+                vec!["diff", "--", &file_path]
+            };
+
+            let content = match tokio::process::Command::new("git")
+                .args(&args)
+                .output()
+                .await
+            {
+                Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+                Err(e) => format!("Failed to load diff: {}", e),
+            };
+            self.staging_view
+                .set_diff_preview(&file_path, staged, content);
+        }
+
+        Ok(())
+    }
+
+    /// 处理提交图的分页加载请求
+    pub(crate) async fn handle_pending_graph_page(&mut self) -> Result<()> {
+        let skip = {
+            let state = self.state.read().await;
+            state.take_graph_page()
+        };
+
+        if let Some(skip) = skip {
+            if let Err(e) = self.load_graph_page(skip).await {
+                self.graph_view.mark_load_failed();
+                let mut state = self.state.write().await;
+                state.add_notification(
+                    format!("Failed to load commit graph: {}", e),
+                    crate::tui_unified::state::app_state::NotificationLevel::Error,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 处理 Git Log 的分页加载请求，按当前的分支过滤加载
+    pub(crate) async fn handle_pending_git_log_page(&mut self) -> Result<()> {
+        let skip = {
+            let state = self.state.read().await;
+            state.take_git_log_page()
+        };
+
+        if let Some(skip) = skip {
+            let repo_path = std::env::current_dir()?;
+            let git = crate::tui_unified::git::interface::AsyncGitImpl::new(repo_path);
+            let branch = self.git_log_view.branch_filter().map(str::to_string);
+
+            match git
+                .get_commit_page(branch.as_deref(), skip, GIT_LOG_PAGE_SIZE)
+                .await
+            {
+                Ok(page) => self.git_log_view.load_page(skip, convert_commits(page)),
+                Err(e) => {
+                    self.git_log_view.mark_load_failed();
+                    let mut state = self.state.write().await;
+                    state.add_notification(
+                        format!("Failed to load commits: {}", e),
+                        crate::tui_unified::state::app_state::NotificationLevel::Error,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Git Log 视图中选中的提交变化时加载提交详情面板
+    pub(crate) async fn sync_commit_detail(&mut self) -> Result<()> {
+        let current_view = self.state.read().await.current_view;
+        if current_view != crate::tui_unified::state::app_state::ViewType::GitLog {
+            return Ok(());
+        }
+
+        let Some(hash) = self.git_log_view.selected_commit().map(|c| c.hash.clone()) else {
+            return Ok(());
+        };
+        if self.detail_panel.commit_hash() == Some(hash.as_str()) {
+            return Ok(());
+        }
+
+        self.detail_panel.set_loading(hash.clone());
+        let repo_path = std::env::current_dir()?;
+        let git = crate::tui_unified::git::interface::AsyncGitImpl::new(repo_path);
+        match git.get_commit_detail(&hash).await {
+            Ok(detail) => self.detail_panel.set_detail(detail),
+            Err(e) => self.detail_panel.set_error(e.to_string()),
+        }
+
+        Ok(())
+    }
+
+    /// 处理 blame 请求，加载成功后切换到 Blame 视图
+    pub(crate) async fn handle_pending_blame_request(&mut self) -> Result<()> {
+        let file_path = {
+            let state = self.state.read().await;
+            state.take_blame_request()
+        };
+
+        if let Some(file_path) = file_path {
+            match self.load_blame(file_path.clone()).await {
+                Ok(()) => {
+                    let mut state = self.state.write().await;
+                    state.set_current_view(crate::tui_unified::state::app_state::ViewType::Blame);
+                    self.focus_manager
+                        .set_focus(crate::tui_unified::focus::FocusPanel::Content);
+                }
+                Err(e) => {
+                    let mut state = self.state.write().await;
+                    state.add_notification(
+                        format!("Failed to blame {}: {}", file_path, e),
+                        crate::tui_unified::state::app_state::NotificationLevel::Error,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 加载文件的 blame 信息
+    async fn load_blame(&mut self, file_path: String) -> Result<()> {
+        let repo_path = std::env::current_dir()?;
+        let git = crate::tui_unified::git::interface::AsyncGitImpl::new(repo_path);
+
+        match git.get_blame(&file_path).await {
+            Ok(lines) => {
+                self.blame_view.set_blame(file_path, lines);
+                Ok(())
+            }
+            Err(e) => Err(anyhow::anyhow!("Git operation failed: {}", e).into()),
+        }
+    }
+
+    /// 加载一页提交图数据
+    async fn load_graph_page(&mut self, skip: usize) -> Result<()> {
+        let repo_path = std::env::current_dir()?;
+        let git = crate::tui_unified::git::interface::AsyncGitImpl::new(repo_path);
+
+        match git.get_graph_commits(skip, GRAPH_PAGE_SIZE).await {
+            Ok(commits) => {
+                if skip == 0 {
+                    self.graph_view.reset();
+                }
+                self.graph_view.append_commits(commits);
+                Ok(())
+            }
+            Err(e) => Err(anyhow::anyhow!("Git operation failed: {}", e).into()),
+        }
+    }
+
     /// 同步获取特定分支的提交历史
     pub(crate) fn get_branch_commits_sync(
         &self,
@@ -347,6 +685,7 @@ impl super::app::TuiUnifiedApp {
                         files_changed: 0,
                         insertions: 0,
                         deletions: 0,
+                        signature: None,
                     });
                 }
             }
@@ -392,7 +731,57 @@ impl super::app::TuiUnifiedApp {
                 self.refresh_query_history().await
             }
             crate::tui_unified::state::app_state::ViewType::Staging => self.refresh_staging().await,
+            crate::tui_unified::state::app_state::ViewType::Graph => self.load_graph_page(0).await,
+            crate::tui_unified::state::app_state::ViewType::Reflog => self.refresh_reflog().await,
+            crate::tui_unified::state::app_state::ViewType::Worktrees => {
+                self.refresh_worktrees().await
+            }
+            // 审查报告是一次性的结果，用 r 重新审查
+            crate::tui_unified::state::app_state::ViewType::Review => Ok(()),
+            crate::tui_unified::state::app_state::ViewType::Hotspots => {
+                self.refresh_hotspots().await
+            }
+            crate::tui_unified::state::app_state::ViewType::Blame => {
+                match self.blame_view.file_path() {
+                    Some(path) => self.load_blame(path.to_string()).await,
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// 按设置的间隔自动刷新当前视图，浮层或模态框打开时跳过
+    pub(crate) async fn handle_auto_refresh(&mut self) -> Result<()> {
+        let interval = self.config.refresh_interval;
+        if interval == 0
+            || self.current_mode != super::app::AppMode::Normal
+            || self.last_auto_refresh.elapsed() < std::time::Duration::from_secs(interval)
+        {
+            return Ok(());
         }
+        self.last_auto_refresh = std::time::Instant::now();
+
+        let current_view = {
+            let state = self.state.read().await;
+            if state.is_modal_active() {
+                return Ok(());
+            }
+            state.current_view
+        };
+        // 成功的刷新只记入通知历史，不弹出提示
+        match self.refresh_current_view(current_view).await {
+            Ok(()) => self.state.write().await.record_notification(
+                format!("Auto refreshed {:?}", current_view),
+                crate::tui_unified::state::app_state::NotificationLevel::Info,
+            ),
+            Err(e) => {
+                self.state.write().await.add_notification(
+                    format!("Auto refresh failed: {}", e),
+                    crate::tui_unified::state::app_state::NotificationLevel::Warning,
+                );
+            }
+        }
+        Ok(())
     }
 
     /// 刷新Git Log视图
@@ -408,7 +797,26 @@ impl super::app::TuiUnifiedApp {
                 state.repo_state.update_commits(commits.clone());
                 drop(state);
 
-                self.git_log_view.update_commits(commits);
+                // 已翻到较早历史的窗口保持不动，避免打断浏览
+                if self.git_log_view.loaded_offset() > 0 {
+                    return Ok(());
+                }
+
+                // 重新加载已浏览过的范围，刷新后保持原来选中的提交
+                let branch = self.git_log_view.branch_filter().map(str::to_string);
+                let limit = self.git_log_view.loaded_len().max(commits.len());
+                let window = match git.get_commit_page(branch.as_deref(), 0, limit).await {
+                    Ok(page) => convert_commits(page),
+                    Err(_) => commits,
+                };
+                let selected = self
+                    .git_log_view
+                    .selected_commit()
+                    .map(|commit| commit.hash.clone());
+                self.git_log_view.update_commits(window);
+                if let Some(hash) = selected {
+                    self.git_log_view.select_commit(&hash);
+                }
                 Ok(())
             }
             Err(e) => Err(anyhow::anyhow!("Git operation failed: {}", e).into()),
@@ -493,14 +901,115 @@ impl super::app::TuiUnifiedApp {
 
     /// 刷新Query History视图
     async fn refresh_query_history(&mut self) -> Result<()> {
-        self.query_history_view.load_history().await;
+        let favorites = self
+            .state
+            .read()
+            .await
+            .search_state
+            .favorite_queries
+            .clone();
+        self.query_history_view.load_history(&favorites).await;
+        Ok(())
+    }
+
+    /// 统计默认时间范围内的修改次数，重新分析热点文件
+    async fn refresh_hotspots(&mut self) -> Result<()> {
+        match crate::analysis::hotspots::analyze(crate::analysis::hotspots::DEFAULT_SINCE, &[])
+            .await
+        {
+            Ok(report) => {
+                self.hotspots_view.set_report(report);
+                Ok(())
+            }
+            Err(e) => Err(anyhow::anyhow!("Hotspot analysis failed: {}", e).into()),
+        }
+    }
+
+    /// 刷新Reflog视图
+    async fn refresh_reflog(&mut self) -> Result<()> {
+        let repo_path = std::env::current_dir()?;
+        let git = crate::tui_unified::git::interface::AsyncGitImpl::new(repo_path);
+
+        match git.get_reflog(REFLOG_LIMIT).await {
+            Ok(entries) => {
+                self.reflog_view.set_entries(entries);
+                Ok(())
+            }
+            Err(e) => Err(anyhow::anyhow!("Git operation failed: {}", e).into()),
+        }
+    }
+
+    /// 执行确认后的 reflog 检出或重置操作
+    pub(crate) async fn confirm_reflog_action(&mut self) -> Result<()> {
+        let action = {
+            let mut state = self.state.write().await;
+            state.hide_modal();
+            state.take_reflog_action()
+        };
+
+        let Some(action) = action else {
+            return Ok(());
+        };
+
+        let (args, success_message) = match &action {
+            ReflogAction::Checkout { selector, hash } => (
+                vec!["checkout".to_string(), hash.clone()],
+                format!("Checked out {} (detached HEAD)", selector),
+            ),
+            ReflogAction::Reset {
+                selector,
+                hash,
+                mode,
+            } => (
+                vec![
+                    "reset".to_string(),
+                    mode.as_flag().to_string(),
+                    hash.clone(),
+                ],
+                format!("Reset to {} ({})", selector, mode.as_flag()),
+            ),
+        };
+
+        let output = tokio::process::Command::new("git")
+            .args(&args)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            let mut state = self.state.write().await;
+            state.add_notification(
+                format!("git {} failed: {}", args[0], error.trim()),
+                crate::tui_unified::state::app_state::NotificationLevel::Error,
+            );
+            return Ok(());
+        }
+
+        {
+            let mut state = self.state.write().await;
+            state.add_notification(
+                success_message,
+                crate::tui_unified::state::app_state::NotificationLevel::Success,
+            );
+        }
+
+        // 重新加载提交、分支和 reflog
+        let _ = self.reload_git_data().await;
+        let _ = self.refresh_reflog().await;
         Ok(())
     }
 
     /// 刷新Staging视图
-    async fn refresh_staging(&mut self) -> Result<()> {
-        let state = self.state.read().await;
+    pub(crate) async fn refresh_staging(&mut self) -> Result<()> {
+        let repo_path = std::env::current_dir()?;
+        let status = load_repo_status(&repo_path)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Failed to read git status"))?;
+
+        let mut state = self.state.write().await;
+        state.repo_state.update_status(status);
         self.staging_view.refresh_file_list(&state);
+        self.staging_view.request_preview(&mut state);
         Ok(())
     }
 }
@@ -0,0 +1,22 @@
+use crossterm::event::KeyEvent;
+
+use super::app::AppMode;
+use crate::tui_unified::{components::widgets::help_panel::HelpAction, Result};
+
+impl super::app::TuiUnifiedApp {
+    /// 打开按键速查表，内容按当前视图和按键配置生成
+    pub(crate) async fn open_help(&mut self) -> Result<()> {
+        let view = self.state.read().await.current_view;
+        self.help_panel.open(&self.config.key_bindings, view);
+        self.current_mode = AppMode::Help;
+        Ok(())
+    }
+
+    pub(crate) fn handle_help_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.help_panel.handle_key(key) == HelpAction::Close {
+            self.help_panel.close();
+            self.current_mode = AppMode::Normal;
+        }
+        Ok(())
+    }
+}
@@ -1,14 +1,15 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::app::AppMode;
 use crate::tui_unified::{
     components::base::{component::Component, events::EventResult},
     focus::FocusPanel,
+    layout_handler::LayoutCommand,
     Result,
 };
 
 impl super::app::TuiUnifiedApp {
-    pub(crate) async fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
+    pub(crate) async fn handle_key_event(&mut self, mut key: KeyEvent) -> Result<()> {
         // 优先检查模态框
         {
             let state = self.state.read().await;
@@ -18,6 +19,70 @@ impl super::app::TuiUnifiedApp {
             }
         }
 
+        // 模糊查找浮层打开时接管所有按键
+        if self.current_mode == AppMode::Search {
+            return self.handle_finder_key(key).await;
+        }
+
+        // 设置浮层打开时接管所有按键
+        if self.current_mode == AppMode::Settings {
+            return self.handle_settings_key(key).await;
+        }
+
+        // 新建标签浮层打开时接管所有按键
+        if self.current_mode == AppMode::CreateTag {
+            return self.handle_tag_dialog_key(key).await;
+        }
+
+        // 帮助浮层打开时接管所有按键，输入用于过滤
+        if self.current_mode == AppMode::Help {
+            return self.handle_help_key(key);
+        }
+
+        // 通知中心打开时接管所有按键
+        if self.current_mode == AppMode::Notifications {
+            return self.handle_notification_key(key).await;
+        }
+
+        // 仓库切换器打开时接管所有按键
+        if self.current_mode == AppMode::Repos {
+            return self.handle_repo_switcher_key(key).await;
+        }
+
+        // 存储概况面板打开时接管所有按键
+        if self.current_mode == AppMode::Storage {
+            return self.handle_storage_panel_key(key);
+        }
+
+        // 命令面板打开时接管所有按键
+        if self.current_mode == AppMode::Command {
+            return self.handle_palette_key(key).await;
+        }
+
+        // Ctrl+Shift+P 或 : 打开命令面板，先于布局快捷键 P 处理
+        if self.current_mode == AppMode::Normal
+            && (key.code == KeyCode::Char(':')
+                || matches!(key.code, KeyCode::Char('P') | KeyCode::Char('p'))
+                    && key
+                        .modifiers
+                        .contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT))
+        {
+            self.open_command_palette();
+            return Ok(());
+        }
+
+        // 自定义按键转换成默认按键
+        if self.current_mode == AppMode::Normal && !key.modifiers.contains(KeyModifiers::CONTROL) {
+            key.code = self.config.key_bindings.translate(key.code);
+        }
+
+        // 布局快捷键
+        if self.current_mode == AppMode::Normal {
+            if let Some(command) = LayoutCommand::from_key(key.code) {
+                return self.apply_layout_command(command).await;
+            }
+        }
+
         // 全局按键处理
         match key.code {
             KeyCode::Char('q') => {
@@ -25,97 +90,84 @@ impl super::app::TuiUnifiedApp {
                 return Ok(());
             }
             KeyCode::Char('/') => {
-                self.current_mode = AppMode::Search;
-                return Ok(());
+                return self.open_fuzzy_finder().await;
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return self.open_fuzzy_finder().await;
             }
             KeyCode::Esc => {
-                if self.current_mode == AppMode::Search {
-                    self.current_mode = AppMode::Normal;
-                    self.search_box.clear();
-                } else {
-                    self.current_mode = AppMode::Normal;
+                // 正常模式下 Esc 关闭所有提示
+                if self.current_mode == AppMode::Normal {
+                    self.state.write().await.dismiss_all_notifications();
                 }
+                self.current_mode = AppMode::Normal;
                 return Ok(());
             }
-            KeyCode::Char('?') => {
-                self.current_mode = if self.current_mode == AppMode::Help {
-                    AppMode::Normal
-                } else {
-                    AppMode::Help
-                };
-                return Ok(());
+            KeyCode::Char('?') if self.current_mode == AppMode::Normal => {
+                return self.open_help().await;
             }
-            KeyCode::Char('c') => {
+            KeyCode::Char('c') if !self.ai_commit_mode => {
                 // AI Commit 功能
-                if !self.ai_commit_mode {
-                    return self.enter_ai_commit_mode().await;
-                }
+                return self.enter_ai_commit_mode().await;
             }
             KeyCode::Char('v') => {
-                // AI Code Review
-                return self.enter_review_mode().await;
+                // 日志视图内容区中 v 用于撤销提交，交给视图处理
+                let in_log = self.focus_manager.current_panel == FocusPanel::Content
+                    && self.state.read().await.current_view
+                        == crate::tui_unified::state::app_state::ViewType::GitLog;
+                if !in_log {
+                    // AI Code Review
+                    return self.enter_review_mode().await;
+                }
             }
             KeyCode::Char('f') => {
                 // AI Refactor Suggestions
                 return self.enter_refactor_mode().await;
             }
-            KeyCode::Tab => {
-                if self.current_mode == AppMode::Normal {
-                    self.focus_manager.next_focus();
-                    return Ok(());
-                }
+            KeyCode::Char('T') if self.current_mode == AppMode::Normal => {
+                return self.cycle_theme().await;
             }
-            KeyCode::BackTab => {
-                if self.current_mode == AppMode::Normal {
-                    self.focus_manager.prev_focus();
-                    return Ok(());
-                }
+            KeyCode::Char(',') if self.current_mode == AppMode::Normal => {
+                self.open_settings();
+                return Ok(());
             }
-            _ => {}
-        }
-
-        // 模式特定的按键处理
-        match self.current_mode {
-            AppMode::Search => {
-                self.handle_search_mode_key(key).await?;
+            KeyCode::Char('N') if self.current_mode == AppMode::Normal => {
+                return self.open_notification_center().await;
+            }
+            KeyCode::Char('E') if self.current_mode == AppMode::Normal => {
+                return self.prompt_export().await;
             }
-            AppMode::Help => {
-                // Help模式下只处理退出键
+            KeyCode::Char('W') if self.current_mode == AppMode::Normal => {
+                return self.toggle_file_watch().await;
+            }
+            KeyCode::Char('O') if self.current_mode == AppMode::Normal => {
+                return self.open_repo_switcher().await;
+            }
+            KeyCode::Tab if self.current_mode == AppMode::Normal => {
+                self.focus_manager.next_focus();
+                self.sync_zen_with_focus();
                 return Ok(());
             }
-            AppMode::Normal => {
-                self.handle_normal_mode_key(key).await?;
+            KeyCode::BackTab if self.current_mode == AppMode::Normal => {
+                self.focus_manager.prev_focus();
+                self.sync_zen_with_focus();
+                return Ok(());
             }
             _ => {}
         }
 
-        Ok(())
-    }
-
-    async fn handle_search_mode_key(&mut self, key: KeyEvent) -> Result<()> {
-        // 处理搜索框特定事件
-        match key.code {
-            KeyCode::Enter => {
-                let query = self.search_box.get_input().to_string();
-                if !query.is_empty() {
-                    // 执行搜索
-                    self.execute_search(&query).await?;
-                }
-                self.current_mode = AppMode::Normal;
-            }
-            _ => {
-                // 让搜索框处理其他输入
-                let mut state = self.state.write().await;
-                let _result = self.search_box.handle_key_event(key, &mut state);
-            }
+        // 模式特定的按键处理
+        if self.current_mode == AppMode::Normal {
+            self.handle_normal_mode_key(key).await?;
         }
 
         Ok(())
     }
 
-    async fn handle_normal_mode_key(&mut self, key: KeyEvent) -> Result<()> {
+    pub(crate) async fn handle_normal_mode_key(&mut self, key: KeyEvent) -> Result<()> {
         let current_panel = self.focus_manager.current_panel;
         let mut state = self.state.write().await;
+        let previous_view = state.current_view;
 
         // 首先尝试让获得焦点的组件处理事件
         let handled = match current_panel {
@@ -142,7 +194,30 @@ impl super::app::TuiUnifiedApp {
                 crate::tui_unified::state::app_state::ViewType::Staging => {
                     self.staging_view.handle_key_event(key, &mut state)
                 }
+                crate::tui_unified::state::app_state::ViewType::Graph => {
+                    self.graph_view.handle_key_event(key, &mut state)
+                }
+                crate::tui_unified::state::app_state::ViewType::Reflog => {
+                    self.reflog_view.handle_key_event(key, &mut state)
+                }
+                crate::tui_unified::state::app_state::ViewType::Blame => {
+                    self.blame_view.handle_key_event(key, &mut state)
+                }
+                crate::tui_unified::state::app_state::ViewType::Worktrees => {
+                    self.worktrees_view.handle_key_event(key, &mut state)
+                }
+                crate::tui_unified::state::app_state::ViewType::Review => {
+                    self.review_view.handle_key_event(key, &mut state)
+                }
+                crate::tui_unified::state::app_state::ViewType::Hotspots => {
+                    self.hotspots_view.handle_key_event(key, &mut state)
+                }
             },
+            FocusPanel::Detail
+                if state.current_view == crate::tui_unified::state::app_state::ViewType::GitLog =>
+            {
+                self.detail_panel.handle_key_event(key, &mut state)
+            }
             _ => EventResult::NotHandled,
         };
 
@@ -211,7 +286,29 @@ impl super::app::TuiUnifiedApp {
                 }
                 KeyCode::Char('7') => {
                     state.set_current_view(crate::tui_unified::state::app_state::ViewType::Staging);
-                    self.staging_view.refresh_file_list(&state);
+                    self.focus_manager.set_focus(FocusPanel::Content);
+                }
+                KeyCode::Char('8') => {
+                    state.set_current_view(crate::tui_unified::state::app_state::ViewType::Graph);
+                    self.focus_manager.set_focus(FocusPanel::Content);
+                }
+                KeyCode::Char('9') => {
+                    state.set_current_view(crate::tui_unified::state::app_state::ViewType::Reflog);
+                    self.focus_manager.set_focus(FocusPanel::Content);
+                }
+                KeyCode::Char('0') => {
+                    state.set_current_view(
+                        crate::tui_unified::state::app_state::ViewType::Worktrees,
+                    );
+                    self.focus_manager.set_focus(FocusPanel::Content);
+                }
+                KeyCode::Char('V') => {
+                    state.set_current_view(crate::tui_unified::state::app_state::ViewType::Review);
+                    self.focus_manager.set_focus(FocusPanel::Content);
+                }
+                KeyCode::Char('H') => {
+                    state
+                        .set_current_view(crate::tui_unified::state::app_state::ViewType::Hotspots);
                     self.focus_manager.set_focus(FocusPanel::Content);
                 }
                 KeyCode::Tab => {
@@ -252,10 +349,95 @@ impl super::app::TuiUnifiedApp {
             }
         }
 
+        drop(state);
+        self.handle_view_entry(previous_view).await
+    }
+
+    /// 视图切换后加载新视图需要的数据
+    pub(crate) async fn handle_view_entry(
+        &mut self,
+        previous_view: crate::tui_unified::state::app_state::ViewType,
+    ) -> Result<()> {
+        let mut state = self.state.write().await;
+        let current_view = state.current_view;
+        if current_view == previous_view {
+            return Ok(());
+        }
+
+        // 首次进入提交图视图时加载第一页
+        if current_view == crate::tui_unified::state::app_state::ViewType::Graph
+            && !self.graph_view.is_loaded()
+        {
+            state.request_graph_page(0);
+        }
+
+        // 进入暂存、reflog 和 worktree 视图时重新读取数据，热点视图只在首次进入时分析
+        let first_hotspots_entry = current_view
+            == crate::tui_unified::state::app_state::ViewType::Hotspots
+            && !self.hotspots_view.is_loaded();
+        if first_hotspots_entry
+            || matches!(
+                current_view,
+                crate::tui_unified::state::app_state::ViewType::Staging
+                    | crate::tui_unified::state::app_state::ViewType::Reflog
+                    | crate::tui_unified::state::app_state::ViewType::Worktrees
+            )
+        {
+            drop(state);
+            if let Err(e) = self.refresh_current_view(current_view).await {
+                let mut state = self.state.write().await;
+                state.add_notification(
+                    format!("Refresh failed: {}", e),
+                    crate::tui_unified::state::app_state::NotificationLevel::Error,
+                );
+            }
+        }
+
         Ok(())
     }
 
-    async fn execute_search(&mut self, query: &str) -> Result<()> {
+    /// 切换到下一个颜色主题并保存选择
+    pub(crate) async fn cycle_theme(&mut self) -> Result<()> {
+        use crate::tui_unified::{
+            config::Theme,
+            state::{app_state::NotificationLevel, SimpleStatePersistence},
+        };
+
+        let mut state = self.state.write().await;
+        let name = Theme::next_name(&state.config.theme_name, &Theme::available());
+        // 无法加载的主题也记为当前主题，下次按键会跳过它
+        state.config.theme_name = name.clone();
+        self.config.theme_name = name.clone();
+
+        match Theme::load(&name).and_then(|theme| theme.colors.palette()) {
+            Ok(palette) => {
+                self.theme = palette;
+                let saved = match SimpleStatePersistence::new() {
+                    Ok(persistence) => persistence.save_state(&state).await,
+                    Err(e) => Err(e),
+                };
+                match saved {
+                    Ok(()) => {
+                        state.add_notification(format!("Theme: {}", name), NotificationLevel::Info)
+                    }
+                    Err(e) => state.add_notification(
+                        format!("Theme: {} (not saved: {})", name, e),
+                        NotificationLevel::Warning,
+                    ),
+                };
+            }
+            Err(e) => {
+                state.add_notification(
+                    format!("Failed to load theme: {}", e),
+                    NotificationLevel::Error,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn execute_search(&mut self, query: &str) -> Result<()> {
         use crate::tui_unified::components::base::component::ViewComponent;
 
         let state = self.state.read().await;
@@ -284,6 +466,24 @@ impl super::app::TuiUnifiedApp {
             crate::tui_unified::state::app_state::ViewType::Staging => {
                 // Staging view does not support search
             }
+            crate::tui_unified::state::app_state::ViewType::Graph => {
+                self.graph_view.search(query);
+            }
+            crate::tui_unified::state::app_state::ViewType::Reflog => {
+                self.reflog_view.search(query);
+            }
+            crate::tui_unified::state::app_state::ViewType::Blame => {
+                self.blame_view.search(query);
+            }
+            crate::tui_unified::state::app_state::ViewType::Worktrees => {
+                self.worktrees_view.search(query);
+            }
+            crate::tui_unified::state::app_state::ViewType::Review => {
+                self.review_view.search(query);
+            }
+            crate::tui_unified::state::app_state::ViewType::Hotspots => {
+                self.hotspots_view.search(query);
+            }
         }
 
         Ok(())
@@ -1,4 +1,4 @@
-use super::LayoutMode;
+use super::{Breakpoint, LayoutMode, LayoutPreset, PanelType};
 use crate::tui_unified::{app::LayoutResult, config::AppConfig};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
@@ -6,9 +6,17 @@ use ratatui::layout::{Constraint, Direction, Layout, Rect};
 pub const MIN_TERMINAL_WIDTH: u16 = 80;
 pub const MIN_TERMINAL_HEIGHT: u16 = 24;
 pub const STATUS_BAR_HEIGHT: u16 = 3;
+/// 窄终端下面板标签栏的高度
+pub const TAB_BAR_HEIGHT: u16 = 1;
+/// 低于该宽度时面板按最小宽度分配
+pub const NARROW_TERMINAL_WIDTH: u16 = 100;
+/// 达到该宽度时主内容区占更多空间
+pub const WIDE_TERMINAL_WIDTH: u16 = 120;
 pub const MIN_SIDEBAR_WIDTH: u16 = 15;
 pub const MIN_CONTENT_WIDTH: u16 = 30;
 pub const MIN_DETAIL_WIDTH: u16 = 20;
+/// 手动调整宽度时主内容区至少保留的百分比
+pub const MIN_CONTENT_PERCENT: u16 = 30;
 
 pub struct LayoutManager {
     pub mode: LayoutMode,
@@ -20,6 +28,14 @@ pub struct LayoutManager {
     pub min_content_width: u16,
     pub min_detail_width: u16,
     pub adaptive_resize: bool,
+    /// 侧边栏放在右侧
+    pub sidebar_right: bool,
+    /// 禅模式：只显示这一个面板
+    pub zoomed: Option<PanelType>,
+    /// 最近应用的预设名称
+    pub preset_name: String,
+    /// 焦点所在的面板，窄终端下只显示这个面板
+    pub focused: PanelType,
 }
 
 impl LayoutManager {
@@ -33,13 +49,32 @@ impl LayoutManager {
             min_content_width: MIN_CONTENT_WIDTH,
             min_detail_width: MIN_DETAIL_WIDTH,
             adaptive_resize: true,
+            sidebar_right: false,
+            zoomed: None,
+            preset_name: "default".to_string(),
+            focused: PanelType::Content,
+        }
+    }
+
+    /// 终端尺寸对应的断点
+    pub fn breakpoint(area: Rect) -> Breakpoint {
+        match area.width {
+            _ if area.height < MIN_TERMINAL_HEIGHT => Breakpoint::Compact,
+            w if w < MIN_TERMINAL_WIDTH => Breakpoint::Compact,
+            w if w < NARROW_TERMINAL_WIDTH => Breakpoint::Narrow,
+            w if w < WIDE_TERMINAL_WIDTH => Breakpoint::Medium,
+            _ => Breakpoint::Wide,
         }
     }
 
     pub fn calculate_layout(&self, area: Rect) -> LayoutResult {
-        // 检查终端最小尺寸
-        if area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT {
-            return self.calculate_minimal_layout(area);
+        // 终端太小时面板会被压到最小尺寸以下，改为单面板堆叠显示
+        if Self::breakpoint(area) == Breakpoint::Compact {
+            return self.calculate_compact_layout(area);
+        }
+
+        if let Some(panel) = self.zoomed {
+            return self.calculate_zoomed_layout(area, panel);
         }
 
         match self.mode {
@@ -63,23 +98,56 @@ impl LayoutManager {
         let (sidebar_constraint, content_constraint, detail_constraint) =
             self.calculate_responsive_constraints(area.width);
 
+        let constraints = if self.sidebar_right {
+            [detail_constraint, content_constraint, sidebar_constraint]
+        } else {
+            [sidebar_constraint, content_constraint, detail_constraint]
+        };
         let content_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                sidebar_constraint, // 侧边栏
-                content_constraint, // 主内容
-                detail_constraint,  // 详情面板
-            ])
+            .constraints(constraints)
             .split(main_chunks[0]);
+        let (sidebar, detail) = self.order_outer(content_chunks[0], content_chunks[2]);
 
         LayoutResult {
-            sidebar: content_chunks[0],
+            sidebar,
             content: content_chunks[1],
-            detail: content_chunks[2],
+            detail,
             status_bar: main_chunks[1],
+            ..LayoutResult::default()
+        }
+    }
+
+    /// 按侧边栏位置区分左右两侧的区域，返回 (侧边栏, 另一侧)
+    fn order_outer(&self, left: Rect, right: Rect) -> (Rect, Rect) {
+        if self.sidebar_right {
+            (right, left)
+        } else {
+            (left, right)
         }
     }
 
+    fn calculate_zoomed_layout(&self, area: Rect, panel: PanelType) -> LayoutResult {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(area.height.saturating_sub(STATUS_BAR_HEIGHT)),
+                Constraint::Length(STATUS_BAR_HEIGHT),
+            ])
+            .split(area);
+
+        let mut layout = LayoutResult {
+            status_bar: chunks[1],
+            ..LayoutResult::default()
+        };
+        match panel {
+            PanelType::Sidebar => layout.sidebar = chunks[0],
+            PanelType::Content => layout.content = chunks[0],
+            PanelType::Detail => layout.detail = chunks[0],
+        }
+        layout
+    }
+
     fn calculate_fullscreen_layout(&self, area: Rect) -> LayoutResult {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -94,6 +162,7 @@ impl LayoutManager {
             content: chunks[0],       // 全屏
             detail: Rect::default(),  // 隐藏
             status_bar: chunks[1],
+            ..LayoutResult::default()
         }
     }
 
@@ -107,27 +176,47 @@ impl LayoutManager {
             .split(area);
 
         // 水平分屏：侧边栏 | 上下分屏(主内容/详情)
+        let (sidebar_constraint, split_constraint) = if self.adaptive_resize {
+            (
+                Constraint::Length(self.min_sidebar_width),
+                Constraint::Min(40),
+            )
+        } else {
+            (
+                Constraint::Percentage(self.sidebar_width),
+                Constraint::Percentage(100 - self.sidebar_width),
+            )
+        };
+        let constraints = if self.sidebar_right {
+            [split_constraint, sidebar_constraint]
+        } else {
+            [sidebar_constraint, split_constraint]
+        };
         let horizontal_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Length(self.min_sidebar_width), // 侧边栏
-                Constraint::Min(40),                        // 分屏区域
-            ])
+            .constraints(constraints)
             .split(main_chunks[0]);
+        let (sidebar, split_area) = self.order_outer(horizontal_chunks[0], horizontal_chunks[1]);
 
+        let detail_percent = if self.adaptive_resize {
+            40
+        } else {
+            self.detail_width
+        };
         let split_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(60), // 主内容 (上)
-                Constraint::Percentage(40), // 详情 (下)
+                Constraint::Percentage(100 - detail_percent), // 主内容 (上)
+                Constraint::Percentage(detail_percent),       // 详情 (下)
             ])
-            .split(horizontal_chunks[1]);
+            .split(split_area);
 
         LayoutResult {
-            sidebar: horizontal_chunks[0],
+            sidebar,
             content: split_chunks[0],
             detail: split_chunks[1],
             status_bar: main_chunks[1],
+            ..LayoutResult::default()
         }
     }
 
@@ -141,46 +230,64 @@ impl LayoutManager {
             .split(area);
 
         // 垂直分屏：左右分屏 + 底部详情面板
+        let (sidebar_percent, detail_percent) = if self.adaptive_resize {
+            (25, 30)
+        } else {
+            (self.sidebar_width, self.detail_width)
+        };
         let vertical_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(70), // 主分屏区域
-                Constraint::Percentage(30), // 详情面板
+                Constraint::Percentage(100 - detail_percent), // 主分屏区域
+                Constraint::Percentage(detail_percent),       // 详情面板
             ])
             .split(main_chunks[0]);
 
+        let constraints = [
+            Constraint::Percentage(sidebar_percent),       // 侧边栏
+            Constraint::Percentage(100 - sidebar_percent), // 主内容
+        ];
         let split_chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage(25), // 侧边栏
-                Constraint::Percentage(75), // 主内容
-            ])
+            .constraints(if self.sidebar_right {
+                [constraints[1], constraints[0]]
+            } else {
+                constraints
+            })
             .split(vertical_chunks[0]);
+        let (sidebar, content) = self.order_outer(split_chunks[0], split_chunks[1]);
 
         LayoutResult {
-            sidebar: split_chunks[0],
-            content: split_chunks[1],
+            sidebar,
+            content,
             detail: vertical_chunks[1],
             status_bar: main_chunks[1],
+            ..LayoutResult::default()
         }
     }
 
-    fn calculate_minimal_layout(&self, area: Rect) -> LayoutResult {
-        // 最小化布局：只显示主内容和状态栏
+    /// 窄终端布局：顶部标签栏，下面只显示焦点所在的面板
+    fn calculate_compact_layout(&self, area: Rect) -> LayoutResult {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(area.height.saturating_sub(STATUS_BAR_HEIGHT)),
+                Constraint::Length(TAB_BAR_HEIGHT),
+                Constraint::Min(0),
                 Constraint::Length(STATUS_BAR_HEIGHT),
             ])
             .split(area);
 
-        LayoutResult {
-            sidebar: Rect::default(), // 隐藏
-            content: chunks[0],       // 全屏主内容
-            detail: Rect::default(),  // 隐藏
-            status_bar: chunks[1],
+        let mut layout = LayoutResult {
+            tab_bar: chunks[0],
+            status_bar: chunks[2],
+            ..LayoutResult::default()
+        };
+        match self.zoomed.unwrap_or(self.focused) {
+            PanelType::Sidebar => layout.sidebar = chunks[1],
+            PanelType::Content => layout.content = chunks[1],
+            PanelType::Detail => layout.detail = chunks[1],
         }
+        layout
     }
 
     // 响应式约束计算
@@ -188,18 +295,24 @@ impl LayoutManager {
         &self,
         terminal_width: u16,
     ) -> (Constraint, Constraint, Constraint) {
-        if !self.adaptive_resize {
-            // 固定比例模式
+        // 固定比例模式：使用手动调整的百分比，但不能把面板压到最小宽度以下
+        let fits =
+            |percent: u16, min: u16| terminal_width as u32 * percent as u32 / 100 >= min as u32;
+        if !self.adaptive_resize
+            && fits(self.sidebar_width, self.min_sidebar_width)
+            && fits(self.content_width, self.min_content_width)
+            && fits(self.detail_width, self.min_detail_width)
+        {
             return (
-                Constraint::Percentage(20),
-                Constraint::Percentage(50),
-                Constraint::Percentage(30),
+                Constraint::Percentage(self.sidebar_width),
+                Constraint::Percentage(self.content_width),
+                Constraint::Percentage(self.detail_width),
             );
         }
 
         // 根据终端宽度自适应调整
-        match terminal_width {
-            w if w < 100 => {
+        match Self::breakpoint(Rect::new(0, 0, terminal_width, MIN_TERMINAL_HEIGHT)) {
+            Breakpoint::Compact | Breakpoint::Narrow => {
                 // 小屏幕：缩小侧边栏，保持主内容
                 (
                     Constraint::Length(self.min_sidebar_width),
@@ -207,7 +320,7 @@ impl LayoutManager {
                     Constraint::Length(self.min_detail_width),
                 )
             }
-            w if w < 120 => {
+            Breakpoint::Medium => {
                 // 中等屏幕：平衡分配
                 (
                     Constraint::Percentage(18),
@@ -215,7 +328,7 @@ impl LayoutManager {
                     Constraint::Percentage(30),
                 )
             }
-            _ => {
+            Breakpoint::Wide => {
                 // 大屏幕：给主内容更多空间
                 (
                     Constraint::Percentage(15),
@@ -251,23 +364,74 @@ impl LayoutManager {
         };
     }
 
-    // 面板尺寸调整
+    /// 排列方式在标准三栏和两种分屏之间循环，全屏由禅模式代替
+    pub fn cycle_arrangement(&mut self) {
+        self.mode = match self.mode {
+            LayoutMode::Normal => LayoutMode::SplitHorizontal,
+            LayoutMode::SplitHorizontal => LayoutMode::SplitVertical,
+            LayoutMode::SplitVertical | LayoutMode::FullScreen => LayoutMode::Normal,
+        };
+    }
+
+    /// 把侧边栏移到另一侧
+    pub fn mirror(&mut self) {
+        self.sidebar_right = !self.sidebar_right;
+    }
+
+    /// 切换禅模式，最大化指定面板
+    pub fn toggle_zoom(&mut self, panel: PanelType) {
+        self.zoomed = match self.zoomed {
+            Some(_) => None,
+            None => Some(panel),
+        };
+    }
+
+    // 面板尺寸调整（百分比），调整后改为固定比例模式
     pub fn adjust_sidebar_width(&mut self, delta: i16) {
+        let max = 40.min(100 - MIN_CONTENT_PERCENT - self.detail_width);
         let new_width =
             (self.sidebar_width as i16 + delta).max(self.min_sidebar_width as i16) as u16;
-        self.sidebar_width = new_width.min(40); // 最大40个字符宽度
+        self.sidebar_width = new_width.min(max); // 最大40%
+        self.content_width = 100 - self.sidebar_width - self.detail_width;
+        self.adaptive_resize = false;
     }
 
     pub fn adjust_detail_width(&mut self, delta: i16) {
+        let max = 50.min(100 - MIN_CONTENT_PERCENT - self.sidebar_width);
         let new_width = (self.detail_width as i16 + delta).max(self.min_detail_width as i16) as u16;
-        self.detail_width = new_width.min(50); // 最大50个字符宽度
+        self.detail_width = new_width.min(max); // 最大50%
+        self.content_width = 100 - self.sidebar_width - self.detail_width;
+        self.adaptive_resize = false;
     }
 
     pub fn reset_layout(&mut self) {
-        self.sidebar_width = 20;
-        self.content_width = 50;
-        self.detail_width = 30;
-        self.mode = LayoutMode::Normal;
+        self.apply_preset(&LayoutPreset::default());
+        self.zoomed = None;
+    }
+
+    /// 以当前布局生成预设
+    pub fn preset(&self, name: &str) -> LayoutPreset {
+        LayoutPreset {
+            name: name.to_string(),
+            mode: self.mode,
+            sidebar_width: self.sidebar_width,
+            detail_width: self.detail_width,
+            sidebar_right: self.sidebar_right,
+            adaptive: self.adaptive_resize,
+        }
+    }
+
+    pub fn apply_preset(&mut self, preset: &LayoutPreset) {
+        self.mode = preset.mode;
+        self.sidebar_width = preset.sidebar_width.clamp(self.min_sidebar_width, 40);
+        self.detail_width = preset.detail_width.clamp(
+            self.min_detail_width,
+            100 - MIN_CONTENT_PERCENT - self.sidebar_width,
+        );
+        self.content_width = 100 - self.sidebar_width - self.detail_width;
+        self.sidebar_right = preset.sidebar_right;
+        self.adaptive_resize = preset.adaptive;
+        self.preset_name = preset.name.clone();
     }
 
     // 布局验证
@@ -5,4 +5,4 @@ pub mod modes;
 mod tests;
 
 pub use manager::LayoutManager;
-pub use modes::{LayoutMode, PanelType};
+pub use modes::{Breakpoint, LayoutMode, LayoutPreset, PanelType};
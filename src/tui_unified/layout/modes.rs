@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LayoutMode {
     Normal,          // 标准三栏布局
     SplitHorizontal, // 水平分屏
@@ -6,9 +8,78 @@ pub enum LayoutMode {
     FullScreen,      // 全屏diff模式
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// 按终端尺寸划分的布局断点
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// 放不下多栏：一次只显示一个面板，顶部的标签栏显示其他面板
+    Compact,
+    /// 面板按最小宽度分配，剩余空间给主内容
+    Narrow,
+    Medium,
+    Wide,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PanelType {
     Sidebar,
     Content,
     Detail,
 }
+
+/// 布局预设：排列方式和面板宽度（百分比）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutPreset {
+    pub name: String,
+    pub mode: LayoutMode,
+    pub sidebar_width: u16,
+    pub detail_width: u16,
+    /// 侧边栏放在右侧
+    #[serde(default)]
+    pub sidebar_right: bool,
+    /// 按终端宽度自动分配宽度，忽略上面的百分比
+    #[serde(default)]
+    pub adaptive: bool,
+}
+
+impl Default for LayoutPreset {
+    fn default() -> Self {
+        Self {
+            name: "default".to_string(),
+            mode: LayoutMode::Normal,
+            sidebar_width: 20,
+            detail_width: 30,
+            sidebar_right: false,
+            adaptive: true,
+        }
+    }
+}
+
+impl LayoutPreset {
+    /// 内置预设，按 `L` 键切换时排在用户保存的预设之前
+    pub fn builtin() -> Vec<Self> {
+        vec![
+            Self::default(),
+            Self {
+                name: "wide".to_string(),
+                sidebar_width: 15,
+                detail_width: 20,
+                adaptive: false,
+                ..Self::default()
+            },
+            Self {
+                name: "review".to_string(),
+                mode: LayoutMode::SplitVertical,
+                detail_width: 40,
+                adaptive: false,
+                ..Self::default()
+            },
+            Self {
+                name: "stacked".to_string(),
+                mode: LayoutMode::SplitHorizontal,
+                detail_width: 40,
+                adaptive: false,
+                ..Self::default()
+            },
+        ]
+    }
+}
@@ -3,8 +3,8 @@ mod layout_tests {
     use crate::tui_unified::{
         config::AppConfig,
         layout::{
-            manager::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH, STATUS_BAR_HEIGHT},
-            LayoutManager, LayoutMode,
+            manager::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH, STATUS_BAR_HEIGHT, TAB_BAR_HEIGHT},
+            Breakpoint, LayoutManager, LayoutMode, PanelType,
         },
     };
     use ratatui::layout::Rect;
@@ -95,18 +95,52 @@ mod layout_tests {
     }
 
     #[test]
-    fn test_minimal_layout_for_small_terminal() {
+    fn test_compact_layout_for_small_terminal() {
         let config = AppConfig::default();
-        let manager = LayoutManager::new(&config);
+        let mut manager = LayoutManager::new(&config);
 
         // 小于最小尺寸的终端
         let small_area = Rect::new(0, 0, 60, 20);
+        assert_eq!(LayoutManager::breakpoint(small_area), Breakpoint::Compact);
         let layout = manager.calculate_layout(small_area);
 
-        // 小终端应该使用最小化布局（隐藏侧边栏和详情）
+        // 小终端只显示焦点所在的面板，顶部显示标签栏
+        assert!(layout.is_compact());
+        assert_eq!(layout.tab_bar.height, TAB_BAR_HEIGHT);
         assert_eq!(layout.sidebar.width, 0);
         assert_eq!(layout.detail.width, 0);
         assert_eq!(layout.content.width, small_area.width);
+        assert_eq!(
+            layout.content.height,
+            small_area.height - TAB_BAR_HEIGHT - STATUS_BAR_HEIGHT
+        );
+
+        // 焦点切到侧边栏时侧边栏占满整个宽度
+        manager.focused = PanelType::Sidebar;
+        let layout = manager.calculate_layout(small_area);
+        assert_eq!(layout.sidebar.width, small_area.width);
+        assert_eq!(layout.content.width, 0);
+
+        // 足够大的终端不显示标签栏
+        let layout = manager.calculate_layout(Rect::new(0, 0, 120, 40));
+        assert!(!layout.is_compact());
+    }
+
+    #[test]
+    fn test_fixed_widths_respect_minimum_on_narrow_terminal() {
+        let config = AppConfig::default();
+        let mut manager = LayoutManager::new(&config);
+        manager.adjust_sidebar_width(-5);
+
+        // 85 列时 15% 的侧边栏不足最小宽度，改按最小宽度分配
+        let layout = manager.calculate_layout(Rect::new(0, 0, 85, 30));
+        assert_eq!(layout.sidebar.width, manager.min_sidebar_width);
+        assert!(layout.content.width >= manager.min_content_width);
+        assert!(layout.detail.width >= manager.min_detail_width);
+
+        // 宽终端仍按手动调整的比例分配
+        let layout = manager.calculate_layout(Rect::new(0, 0, 200, 40));
+        assert_eq!(layout.sidebar.width, 30);
     }
 
     #[test]
@@ -225,4 +259,44 @@ mod layout_tests {
         assert_eq!(manager.content_width, 50);
         assert_eq!(manager.detail_width, 30);
     }
+
+    #[test]
+    fn test_mirror_and_zoom() {
+        let config = AppConfig::default();
+        let mut manager = LayoutManager::new(&config);
+        let area = Rect::new(0, 0, 120, 40);
+
+        manager.mirror();
+        let layout = manager.calculate_layout(area);
+        assert!(layout.detail.x < layout.content.x);
+        assert!(layout.content.x < layout.sidebar.x);
+
+        manager.toggle_zoom(PanelType::Detail);
+        let layout = manager.calculate_layout(area);
+        assert_eq!(layout.detail.width, area.width);
+        assert_eq!(layout.sidebar.width, 0);
+        assert_eq!(layout.content.width, 0);
+
+        manager.toggle_zoom(PanelType::Detail);
+        assert_eq!(manager.zoomed, None);
+    }
+
+    #[test]
+    fn test_preset_round_trip_uses_fixed_percentages() {
+        let config = AppConfig::default();
+        let mut manager = LayoutManager::new(&config);
+        manager.adjust_sidebar_width(5);
+        manager.adjust_detail_width(-10);
+        assert!(!manager.adaptive_resize);
+
+        let preset = manager.preset("mine");
+        let mut restored = LayoutManager::new(&config);
+        restored.apply_preset(&preset);
+        assert_eq!(restored.preset("mine"), preset);
+
+        let layout = restored.calculate_layout(Rect::new(0, 0, 200, 40));
+        assert_eq!(layout.sidebar.width, 50);
+        assert_eq!(layout.detail.width, 40);
+        assert_eq!(restored.content_width, 55);
+    }
 }
@@ -0,0 +1,181 @@
+use crossterm::event::KeyCode;
+
+use crate::tui_unified::{
+    focus::FocusPanel,
+    layout::{LayoutPreset, PanelType},
+    state::{
+        app_state::{NotificationLevel, ViewType},
+        SimpleStatePersistence,
+    },
+    Result,
+};
+
+/// 每次按键调整的宽度百分比
+const RESIZE_STEP: i16 = 5;
+
+/// 布局快捷键对应的操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum LayoutCommand {
+    ToggleZen,
+    ResizeSidebar(i16),
+    ResizeDetail(i16),
+    CycleArrangement,
+    Mirror,
+    Reset,
+    NextPreset,
+    SavePreset,
+}
+
+impl LayoutCommand {
+    pub(crate) fn from_key(code: KeyCode) -> Option<Self> {
+        match code {
+            KeyCode::Char('z') => Some(Self::ToggleZen),
+            KeyCode::Char('[') => Some(Self::ResizeSidebar(-RESIZE_STEP)),
+            KeyCode::Char(']') => Some(Self::ResizeSidebar(RESIZE_STEP)),
+            KeyCode::Char('{') => Some(Self::ResizeDetail(-RESIZE_STEP)),
+            KeyCode::Char('}') => Some(Self::ResizeDetail(RESIZE_STEP)),
+            KeyCode::Char('\\') => Some(Self::CycleArrangement),
+            KeyCode::Char('|') => Some(Self::Mirror),
+            KeyCode::Char('=') => Some(Self::Reset),
+            KeyCode::Char('L') => Some(Self::NextPreset),
+            KeyCode::Char('P') => Some(Self::SavePreset),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) fn panel_type(panel: FocusPanel) -> PanelType {
+    match panel {
+        FocusPanel::Sidebar => PanelType::Sidebar,
+        FocusPanel::Content => PanelType::Content,
+        FocusPanel::Detail => PanelType::Detail,
+    }
+}
+
+/// 窄终端标签栏中的面板，只有 Git log 视图有详情面板
+pub(crate) fn compact_tabs(view: ViewType) -> &'static [(FocusPanel, &'static str)] {
+    match view {
+        ViewType::GitLog => &[
+            (FocusPanel::Sidebar, "Sidebar"),
+            (FocusPanel::Content, "Content"),
+            (FocusPanel::Detail, "Detail"),
+        ],
+        _ => &[
+            (FocusPanel::Sidebar, "Sidebar"),
+            (FocusPanel::Content, "Content"),
+        ],
+    }
+}
+
+/// 内置预设之后接用户保存的预设，返回当前预设的下一个
+fn next_preset(current: &str, saved: &[LayoutPreset]) -> LayoutPreset {
+    let all: Vec<LayoutPreset> = LayoutPreset::builtin()
+        .into_iter()
+        .chain(saved.iter().cloned())
+        .collect();
+    let index = all.iter().position(|preset| preset.name == current);
+    all[index.map_or(0, |i| (i + 1) % all.len())].clone()
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 禅模式下焦点切换时，最大化的面板跟随焦点
+    pub(crate) fn sync_zen_with_focus(&mut self) {
+        if self.layout_manager.zoomed.is_some() {
+            self.layout_manager.zoomed = Some(panel_type(self.focus_manager.current_panel));
+        }
+    }
+
+    pub(crate) async fn apply_layout_command(&mut self, command: LayoutCommand) -> Result<()> {
+        let mut state = self.state.write().await;
+        let message = match command {
+            LayoutCommand::ToggleZen => {
+                self.layout_manager
+                    .toggle_zoom(panel_type(self.focus_manager.current_panel));
+                // 禅模式不保存，重启后恢复正常布局
+                let message = if self.layout_manager.zoomed.is_some() {
+                    "Zen mode on (z to exit)"
+                } else {
+                    "Zen mode off"
+                };
+                state.add_notification(message.to_string(), NotificationLevel::Info);
+                return Ok(());
+            }
+            LayoutCommand::ResizeSidebar(delta) => {
+                self.layout_manager.adjust_sidebar_width(delta);
+                None
+            }
+            LayoutCommand::ResizeDetail(delta) => {
+                self.layout_manager.adjust_detail_width(delta);
+                None
+            }
+            LayoutCommand::CycleArrangement => {
+                self.layout_manager.cycle_arrangement();
+                Some(format!("Layout: {:?}", self.layout_manager.mode))
+            }
+            LayoutCommand::Mirror => {
+                self.layout_manager.mirror();
+                None
+            }
+            LayoutCommand::Reset => {
+                self.layout_manager.reset_layout();
+                Some("Layout reset".to_string())
+            }
+            LayoutCommand::NextPreset => {
+                let preset = next_preset(&self.layout_manager.preset_name, &state.layout.presets);
+                self.layout_manager.apply_preset(&preset);
+                Some(format!("Layout preset: {}", preset.name))
+            }
+            LayoutCommand::SavePreset => {
+                let name = format!("custom-{}", state.layout.presets.len() + 1);
+                let preset = self.layout_manager.preset(&name);
+                self.layout_manager.preset_name = name.clone();
+                state.layout.presets.push(preset);
+                Some(format!("Saved layout preset: {}", name))
+            }
+        };
+
+        // 同步到应用状态并保存
+        let arrangement = self.layout_manager.preset(&self.layout_manager.preset_name);
+        state.layout.sidebar_width = arrangement.sidebar_width;
+        state.layout.detail_width = arrangement.detail_width;
+        state.layout.content_width = self.layout_manager.content_width;
+        state.layout.arrangement = arrangement;
+
+        let saved = match SimpleStatePersistence::new() {
+            Ok(persistence) => persistence.save_state(&state).await,
+            Err(e) => Err(e),
+        };
+        match (saved, message) {
+            (Err(e), _) => {
+                state.add_notification(
+                    format!("Failed to save layout: {}", e),
+                    NotificationLevel::Warning,
+                );
+            }
+            (Ok(()), Some(message)) => {
+                state.add_notification(message, NotificationLevel::Info);
+            }
+            (Ok(()), None) => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_preset_cycles_through_saved() {
+        let saved = vec![LayoutPreset {
+            name: "custom-1".to_string(),
+            ..LayoutPreset::default()
+        }];
+
+        assert_eq!(next_preset("default", &saved).name, "wide");
+        assert_eq!(next_preset("stacked", &saved).name, "custom-1");
+        assert_eq!(next_preset("custom-1", &saved).name, "default");
+        assert_eq!(next_preset("deleted", &saved).name, "default");
+    }
+}
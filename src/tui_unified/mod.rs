@@ -7,21 +7,42 @@ mod ai_commit_handler;
 pub mod algorithms;
 pub mod app;
 pub mod async_manager;
+mod branch_handler;
 pub mod cache;
+mod clipboard;
+mod commit_action_handler;
 pub mod components;
 pub mod config;
+mod config_reload_handler;
 mod diff_parsing;
 mod diff_rendering;
 pub mod events;
+mod export_handler;
+mod finder_handler;
 pub mod focus;
 pub mod git;
 mod git_operations;
+mod help_handler;
 mod input_handler;
 pub mod layout;
+mod layout_handler;
 mod modal_rendering;
+mod mouse_handler;
+mod notification_handler;
+mod palette_handler;
+mod query_history_handler;
+mod remote_handler;
 mod rendering;
+mod repo_handler;
+mod review_handler;
+mod settings_handler;
+mod startup_handler;
 pub mod state;
+mod storage_handler;
+mod tag_handler;
 pub mod utils;
+mod watch_handler;
+mod worktree_handler;
 
 pub use app::TuiUnifiedApp;
 
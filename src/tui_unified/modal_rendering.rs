@@ -1,10 +1,108 @@
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::collections::HashMap;
 
 use crate::tui_unified::components::base::component::Component;
 use crate::tui_unified::Result;
 
+/// 模态框底部的按键提示，带按键的提示可以用鼠标点击
+type KeyHint = (String, Option<KeyCode>);
+
+/// 根据模态框按钮生成按键提示，确认类按钮对应 Enter，取消类按钮对应 Esc
+fn button_hints(buttons: &[crate::tui_unified::state::app_state::ModalButton]) -> Vec<KeyHint> {
+    use crate::tui_unified::state::app_state::ModalAction;
+
+    if buttons.is_empty() {
+        return vec![
+            ("[Enter] OK".to_string(), Some(KeyCode::Enter)),
+            ("[Esc] Cancel".to_string(), Some(KeyCode::Esc)),
+        ];
+    }
+    buttons
+        .iter()
+        .map(|button| match button.action {
+            ModalAction::Ok | ModalAction::Yes => {
+                (format!("[Enter] {}", button.label), Some(KeyCode::Enter))
+            }
+            ModalAction::Cancel | ModalAction::No => {
+                (format!("[Esc] {}", button.label), Some(KeyCode::Esc))
+            }
+            _ => (button.label.clone(), None),
+        })
+        .collect()
+}
+
 impl super::app::TuiUnifiedApp {
+    /// 按用途执行输入框提交的内容
+    async fn submit_input(
+        &mut self,
+        prompt: crate::tui_unified::state::app_state::InputPrompt,
+    ) -> Result<()> {
+        use crate::tui_unified::state::app_state::InputPurpose;
+
+        let value = prompt.value.trim();
+        if value.is_empty() {
+            return Ok(());
+        }
+        match prompt.purpose {
+            InputPurpose::CreateWorktree => self.create_worktree(value).await,
+            InputPurpose::CreateBranch => self.create_branch_from_input(value).await,
+            InputPurpose::RenameBranch(from) if from == value => Ok(()),
+            InputPurpose::RenameBranch(from) => {
+                let action = crate::tui_unified::state::app_state::BranchAction::Rename {
+                    from,
+                    to: value.to_string(),
+                };
+                self.state.write().await.request_branch_action(action);
+                Ok(())
+            }
+            InputPurpose::RenameQuery(query) => self.rename_query(query, value).await,
+            InputPurpose::ExportView => self.export_to_file(value).await,
+        }
+    }
+
+    /// 居中渲染一行按键提示，并登记可点击区域
+    fn render_key_hints(
+        &mut self,
+        frame: &mut ratatui::Frame,
+        area: ratatui::layout::Rect,
+        hints: &[KeyHint],
+        style: ratatui::style::Style,
+    ) {
+        use ratatui::{
+            layout::{Alignment, Rect},
+            text::{Line, Span},
+            widgets::Paragraph,
+        };
+
+        let mut spans = Vec::new();
+        for (i, (label, _)) in hints.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" | "));
+            }
+            spans.push(Span::raw(label.clone()));
+        }
+        let line = Line::from(spans);
+
+        // 与 Alignment::Center 相同的方式计算起点
+        let mut x = area.x + area.width.saturating_sub(line.width() as u16) / 2;
+        for (i, (label, key)) in hints.iter().enumerate() {
+            if i > 0 {
+                x += 3;
+            }
+            let width = Span::raw(label.as_str()).width() as u16;
+            if let Some(key) = key {
+                self.modal_click_targets
+                    .push((Rect::new(x, area.y, width, 1), KeyEvent::from(*key)));
+            }
+            x += width;
+        }
+
+        let paragraph = Paragraph::new(line)
+            .style(style)
+            .alignment(Alignment::Center);
+        frame.render_widget(paragraph, area);
+    }
+
     /// 渲染模态框
     pub(crate) fn render_modal(
         &mut self,
@@ -13,7 +111,7 @@ impl super::app::TuiUnifiedApp {
         area: ratatui::layout::Rect,
     ) {
         use ratatui::{
-            layout::{Alignment, Constraint, Direction, Layout},
+            layout::{Constraint, Direction, Layout},
             style::{Color, Style},
             text::Text,
             widgets::Paragraph,
@@ -75,11 +173,28 @@ impl super::app::TuiUnifiedApp {
                     height: 1,
                 };
 
-                let help_text = "Press [Esc] or [q] to close | [↑↓/jk] scroll | [PgUp/PgDn/ud] page | [g/G] start/end | [←→] files (side-by-side) | [1] unified | [2] side-by-side | [3/t] file list | [w] word-level | [n] line numbers | [h] syntax";
-                let help = Paragraph::new(Text::from(help_text))
-                    .style(Style::default().fg(Color::Gray).bg(Color::Black))
-                    .alignment(Alignment::Center);
-                frame.render_widget(help, help_area);
+                let hints: Vec<KeyHint> = vec![
+                    ("[Esc/q] close".to_string(), Some(KeyCode::Esc)),
+                    ("[↑↓/jk] files".to_string(), None),
+                    ("[J/K/PgUp/PgDn] scroll".to_string(), None),
+                    ("[←→/[]] hunks".to_string(), None),
+                    ("[{}] files".to_string(), None),
+                    ("[s] hunk list".to_string(), Some(KeyCode::Char('s'))),
+                    ("[1] unified".to_string(), Some(KeyCode::Char('1'))),
+                    ("[2] side-by-side".to_string(), Some(KeyCode::Char('2'))),
+                    ("[3/t] file list".to_string(), Some(KeyCode::Char('3'))),
+                    ("[h] syntax".to_string(), Some(KeyCode::Char('h'))),
+                    ("[o/O] expand".to_string(), Some(KeyCode::Char('o'))),
+                    ("[+/-] context".to_string(), None),
+                    ("[y] copy hunk".to_string(), Some(KeyCode::Char('y'))),
+                    ("[Y] copy path".to_string(), Some(KeyCode::Char('Y'))),
+                ];
+                self.render_key_hints(
+                    frame,
+                    help_area,
+                    &hints,
+                    Style::default().fg(Color::Gray).bg(Color::Black),
+                );
             }
             crate::tui_unified::state::app_state::ModalType::AICommit => {
                 // AI Commit 模态框
@@ -109,8 +224,8 @@ impl super::app::TuiUnifiedApp {
                 // AI Commit 对话框
                 use ratatui::widgets::{Block, Borders};
 
-                if self.ai_commit_editing {
-                    // 编辑模式：显示编辑器
+                if self.ai_commit_editing || self.ai_commit_generation.is_some() {
+                    // 编辑模式和生成过程中：显示编辑器
                     match self.state.try_read() {
                         Ok(state) => {
                             self.commit_editor.render(frame, popup_area, &state);
@@ -130,6 +245,7 @@ impl super::app::TuiUnifiedApp {
                                 search_state: Default::default(),
                                 config: crate::tui_unified::config::AppConfig::default(),
                                 loading_tasks: HashMap::new(),
+                                loading_sections: std::collections::HashSet::new(),
                                 notifications: Vec::new(),
                                 new_layout: Default::default(),
                             });
@@ -176,23 +292,35 @@ impl super::app::TuiUnifiedApp {
                     height: 1,
                 };
 
-                let help_text = if self.ai_commit_editing {
-                    "[Tab] Save & Exit Edit | [Esc] Cancel Edit"
+                let hints: Vec<KeyHint> = if self.ai_commit_editing {
+                    vec![
+                        ("[Tab] Save & Exit Edit".to_string(), Some(KeyCode::Tab)),
+                        ("[Ctrl+G] AI fill".to_string(), None),
+                        ("[Esc] Cancel Edit".to_string(), Some(KeyCode::Esc)),
+                    ]
                 } else if self.ai_commit_push_prompt {
-                    "[y/Enter] Push | [n/Esc] Skip Push"
+                    vec![
+                        ("[y/Enter] Push".to_string(), Some(KeyCode::Enter)),
+                        ("[n/Esc] Skip Push".to_string(), Some(KeyCode::Esc)),
+                    ]
                 } else if self.ai_commit_message.is_some() {
-                    "[Enter] Commit | [e] Edit | [Esc] Cancel"
+                    vec![
+                        ("[Enter] Commit".to_string(), Some(KeyCode::Enter)),
+                        ("[e] Edit".to_string(), Some(KeyCode::Char('e'))),
+                        ("[Esc] Cancel".to_string(), Some(KeyCode::Esc)),
+                    ]
                 } else {
-                    "🤖 Generating commit message... | [Esc] Cancel"
+                    vec![
+                        ("🤖 Generating commit message...".to_string(), None),
+                        ("[Esc] Cancel".to_string(), Some(KeyCode::Esc)),
+                    ]
                 };
-                let help = Paragraph::new(Text::from(help_text))
-                    .style(Style::default().fg(Color::Gray))
-                    .alignment(Alignment::Center);
-                frame.render_widget(help, help_area);
+                self.render_key_hints(frame, help_area, &hints, Style::default().fg(Color::Gray));
             }
             crate::tui_unified::state::app_state::ModalType::AIReview
-            | crate::tui_unified::state::app_state::ModalType::AIRefactor => {
-                // AI Review / Refactor 结果模态框（大面积，可滚动）
+            | crate::tui_unified::state::app_state::ModalType::AIRefactor
+            | crate::tui_unified::state::app_state::ModalType::QueryResult => {
+                // AI Review / Refactor 和查询结果模态框（大面积，可滚动）
                 let popup_area = {
                     let vertical = Layout::default()
                         .direction(Direction::Vertical)
@@ -225,6 +353,9 @@ impl super::app::TuiUnifiedApp {
                     crate::tui_unified::state::app_state::ModalType::AIRefactor => {
                         ("AI Refactor Suggestions", Color::Magenta)
                     }
+                    crate::tui_unified::state::app_state::ModalType::QueryResult => {
+                        (modal.title.as_str(), Color::Green)
+                    }
                     _ => unreachable!(),
                 };
 
@@ -248,11 +379,9 @@ impl super::app::TuiUnifiedApp {
                     height: 1,
                 };
 
-                let help_text = "[Esc] or [q] Close";
-                let help = Paragraph::new(Text::from(help_text))
-                    .style(Style::default().fg(Color::Gray))
-                    .alignment(Alignment::Center);
-                frame.render_widget(help, help_area);
+                let hints: Vec<KeyHint> =
+                    vec![("[Esc] or [q] Close".to_string(), Some(KeyCode::Esc))];
+                self.render_key_hints(frame, help_area, &hints, Style::default().fg(Color::Gray));
             }
             _ => {
                 // 对于其他类型的模态框，使用简单的消息框
@@ -279,9 +408,19 @@ impl super::app::TuiUnifiedApp {
                 // 使用专门的背景清除方法
                 self.clear_modal_background(frame, area);
 
-                // 渲染通用模态框
+                // 渲染通用模态框，输入框在说明下方显示正在编辑的内容
                 use ratatui::widgets::{Block, Borders};
-                let modal_block = Paragraph::new(Text::from(modal.content.clone()))
+                let mut content = modal.content.clone();
+                if modal.modal_type == crate::tui_unified::state::app_state::ModalType::Input {
+                    let value = self
+                        .state
+                        .try_read()
+                        .ok()
+                        .and_then(|state| state.input_value())
+                        .unwrap_or_default();
+                    content.push_str(&format!("\n\n> {}▏", value));
+                }
+                let modal_block = Paragraph::new(Text::from(content))
                     .block(
                         Block::default()
                             .borders(Borders::ALL)
@@ -301,19 +440,14 @@ impl super::app::TuiUnifiedApp {
                     height: 1,
                 };
 
-                let help_text = "[Enter] OK | [Esc] Cancel";
-                let help = Paragraph::new(Text::from(help_text))
-                    .style(Style::default().fg(Color::Gray))
-                    .alignment(Alignment::Center);
-                frame.render_widget(help, help_area);
+                let hints = button_hints(&modal.buttons);
+                self.render_key_hints(frame, help_area, &hints, Style::default().fg(Color::Gray));
             }
         }
     }
 
     /// 处理模态框按键事件
     pub(crate) async fn handle_modal_key(&mut self, key: KeyEvent) -> Result<()> {
-        use crossterm::event::KeyCode;
-
         // 先检查是否为DiffViewer模态框，如果是就转发键盘事件
         let state = self.state.read().await;
         if let Some(modal) = &state.modal {
@@ -333,6 +467,9 @@ impl super::app::TuiUnifiedApp {
 
                     // 其他键转发到DiffViewer，使用和--query-tui-pro相同的逻辑
                     drop(state);
+                    if key.code == KeyCode::Char('E') {
+                        return self.prompt_export().await;
+                    }
                     if let Some(viewer) = &mut self.diff_viewer {
                         match key.code {
                             KeyCode::Char('j') | KeyCode::Tab | KeyCode::Down => {
@@ -372,19 +509,101 @@ impl super::app::TuiUnifiedApp {
                             KeyCode::Char('h') => {
                                 viewer.syntax_highlight = !viewer.syntax_highlight;
                             }
+                            KeyCode::Char('o') => {
+                                viewer.expand_fold();
+                            }
+                            KeyCode::Char('O') => {
+                                viewer.toggle_all_folds();
+                            }
+                            KeyCode::Char('+') | KeyCode::Char('=') => {
+                                let lines = viewer.options.context_lines + 1;
+                                viewer.set_context_lines(lines).await;
+                            }
+                            KeyCode::Char('-') => {
+                                let lines = viewer.options.context_lines.saturating_sub(1);
+                                viewer.set_context_lines(lines).await;
+                            }
+                            KeyCode::Char(']') => {
+                                viewer.next_hunk();
+                            }
+                            KeyCode::Char('[') => {
+                                viewer.prev_hunk();
+                            }
+                            KeyCode::Char('}') => {
+                                viewer.next_file();
+                                viewer.load_current_file_diff().await;
+                            }
+                            KeyCode::Char('{') => {
+                                viewer.prev_file();
+                                viewer.load_current_file_diff().await;
+                            }
+                            KeyCode::Char('s') => {
+                                viewer.show_hunk_list = !viewer.show_hunk_list;
+                            }
                             KeyCode::Left | KeyCode::Char('H') => {
                                 viewer.prev_hunk();
                             }
                             KeyCode::Right | KeyCode::Char('L') => {
                                 viewer.next_hunk();
                             }
+                            KeyCode::Char('y') => {
+                                // 复制当前修改块
+                                if let Some(text) = viewer.current_hunk_text() {
+                                    self.copy_to_clipboard("hunk", &text).await;
+                                }
+                            }
+                            KeyCode::Char('Y') => {
+                                // 复制当前文件路径
+                                if let Some(path) = viewer.selected_file_path().map(str::to_string)
+                                {
+                                    self.copy_to_clipboard("file path", &path).await;
+                                }
+                            }
                             _ => {}
                         }
                     }
                 }
+                crate::tui_unified::state::app_state::ModalType::Input => {
+                    match key.code {
+                        KeyCode::Esc => {
+                            drop(state);
+                            let prompt = {
+                                let mut state = self.state.write().await;
+                                state.hide_modal();
+                                state.take_input()
+                            };
+                            if prompt.is_some_and(|prompt| {
+                                prompt.purpose
+                                    == crate::tui_unified::state::app_state::InputPurpose::ExportView
+                            }) {
+                                self.cancel_export().await;
+                            }
+                        }
+                        KeyCode::Enter => {
+                            drop(state);
+                            let prompt = {
+                                let mut state = self.state.write().await;
+                                state.hide_modal();
+                                state.take_input()
+                            };
+                            if let Some(prompt) = prompt {
+                                return self.submit_input(prompt).await;
+                            }
+                        }
+                        KeyCode::Backspace => state.edit_input(|value| {
+                            value.pop();
+                        }),
+                        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            state.edit_input(|value| value.push(c))
+                        }
+                        _ => {}
+                    }
+                    return Ok(());
+                }
                 crate::tui_unified::state::app_state::ModalType::AIReview
-                | crate::tui_unified::state::app_state::ModalType::AIRefactor => {
-                    // AI Review/Refactor 模态框：只处理关闭键
+                | crate::tui_unified::state::app_state::ModalType::AIRefactor
+                | crate::tui_unified::state::app_state::ModalType::QueryResult => {
+                    // AI Review/Refactor 和查询结果模态框：只处理关闭键
                     match key.code {
                         KeyCode::Esc | KeyCode::Char('q') => {
                             drop(state);
@@ -396,6 +615,27 @@ impl super::app::TuiUnifiedApp {
                     }
                 }
                 _ => {
+                    // AI commit编辑模式下，Ctrl+G 用 AI 补全空字段
+                    if self.ai_commit_mode
+                        && self.ai_commit_editing
+                        && key.code == KeyCode::Char('g')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        drop(state); // 显式释放读锁
+                        return self.ai_fill_commit_message().await;
+                    }
+
+                    // AI commit编辑模式下，除 Esc/Tab 外的按键都交给编辑器，避免快捷键吞掉输入
+                    if self.ai_commit_mode
+                        && self.ai_commit_editing
+                        && !matches!(key.code, KeyCode::Esc | KeyCode::Tab)
+                    {
+                        drop(state); // 显式释放读锁
+                        let mut state = self.state.write().await;
+                        let _result = self.commit_editor.handle_key_event(key, &mut state);
+                        return Ok(());
+                    }
+
                     // 对于其他模态框类型，只处理关闭快捷键
                     match key.code {
                         KeyCode::Esc | KeyCode::Char('q') => {
@@ -434,6 +674,34 @@ impl super::app::TuiUnifiedApp {
                                 drop(state); // 显式释放读锁
                                 return self.confirm_git_pull().await;
                             }
+                            // 在 reflog 操作确认框中，Enter执行检出或重置
+                            else if modal.modal_type
+                                == crate::tui_unified::state::app_state::ModalType::ReflogAction
+                            {
+                                drop(state); // 显式释放读锁
+                                return self.confirm_reflog_action().await;
+                            }
+                            // 在提交操作确认框中，Enter执行 cherry-pick
+                            else if modal.modal_type
+                                == crate::tui_unified::state::app_state::ModalType::CommitAction
+                            {
+                                drop(state); // 显式释放读锁
+                                return self.confirm_commit_action().await;
+                            }
+                            // 在 worktree 删除确认框中，Enter执行删除
+                            else if modal.modal_type
+                                == crate::tui_unified::state::app_state::ModalType::WorktreeAction
+                            {
+                                drop(state); // 显式释放读锁
+                                return self.confirm_worktree_action().await;
+                            }
+                            // 在分支删除确认框中，Enter执行删除
+                            else if modal.modal_type
+                                == crate::tui_unified::state::app_state::ModalType::BranchAction
+                            {
+                                drop(state); // 显式释放读锁
+                                return self.confirm_branch_action().await;
+                            }
                             // 在分支切换模式下，Enter确认切换
                             else if modal.modal_type
                                 == crate::tui_unified::state::app_state::ModalType::BranchSwitch
@@ -457,7 +725,10 @@ impl super::app::TuiUnifiedApp {
                         }
                         KeyCode::Char('e') => {
                             // 在AI commit模式下按e编辑commit message
-                            if self.ai_commit_mode && !self.ai_commit_editing {
+                            if self.ai_commit_mode
+                                && !self.ai_commit_editing
+                                && self.ai_commit_generation.is_none()
+                            {
                                 self.ai_commit_editing = true;
                                 // 将当前消息加载到编辑器中
                                 if let Some(ref message) = self.ai_commit_message {
@@ -515,6 +786,7 @@ impl super::app::TuiUnifiedApp {
                                         search_state: Default::default(),
                                         config: crate::tui_unified::config::AppConfig::default(),
                                         loading_tasks: HashMap::new(),
+                                        loading_sections: std::collections::HashSet::new(),
                                         notifications: Vec::new(),
                                         new_layout: Default::default(),
                                     }
@@ -0,0 +1,118 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+
+use super::app::AppMode;
+use super::layout_handler::compact_tabs;
+use crate::tui_unified::{
+    components::{base::component::Component, widgets::tab_bar::tab_at},
+    focus::FocusPanel,
+    state::app_state::ViewType,
+    Result,
+};
+
+/// 滚轮每格在 diff 弹窗中滚动的行数
+const DIFF_SCROLL_LINES: u16 = 3;
+
+impl super::app::TuiUnifiedApp {
+    pub(crate) async fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<()> {
+        // 模态框打开时只响应模态框内的点击和滚轮
+        if self.state.read().await.is_modal_active() {
+            return self.handle_modal_mouse(mouse).await;
+        }
+
+        if self.current_mode != AppMode::Normal {
+            return Ok(());
+        }
+
+        let position = (mouse.column, mouse.row).into();
+        let is_click = mouse.kind == MouseEventKind::Down(MouseButton::Left);
+
+        // 窄终端下点击标签切换显示的面板
+        if is_click && self.last_layout.tab_bar.contains(position) {
+            let tabs = compact_tabs(self.state.read().await.current_view);
+            let labels: Vec<&str> = tabs.iter().map(|(_, label)| *label).collect();
+            if let Some(index) = tab_at(self.last_layout.tab_bar, &labels, mouse.column, mouse.row)
+            {
+                self.focus_manager.set_focus(tabs[index].0);
+            }
+            return Ok(());
+        }
+
+        if self.last_layout.sidebar.contains(position) {
+            if is_click {
+                self.focus_manager.set_focus(FocusPanel::Sidebar);
+            }
+            let mut state = self.state.write().await;
+            let previous_view = state.current_view;
+            self.sidebar_panel.handle_mouse_event(mouse, &mut state);
+            drop(state);
+            return self.handle_view_entry(previous_view).await;
+        }
+
+        if self.last_layout.content.contains(position) {
+            if is_click {
+                self.focus_manager.set_focus(FocusPanel::Content);
+            }
+            let mut state = self.state.write().await;
+            match state.current_view {
+                ViewType::GitLog => self.git_log_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Branches => self.branches_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Tags => self.tags_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Remotes => self.remotes_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Stash => self.stash_view.handle_mouse_event(mouse, &mut state),
+                ViewType::QueryHistory => self
+                    .query_history_view
+                    .handle_mouse_event(mouse, &mut state),
+                ViewType::Staging => self.staging_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Graph => self.graph_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Reflog => self.reflog_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Blame => self.blame_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Worktrees => self.worktrees_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Review => self.review_view.handle_mouse_event(mouse, &mut state),
+                ViewType::Hotspots => self.hotspots_view.handle_mouse_event(mouse, &mut state),
+            };
+        }
+
+        if self.last_layout.detail.contains(position) {
+            let mut state = self.state.write().await;
+            if state.current_view == ViewType::GitLog {
+                if is_click {
+                    self.focus_manager.set_focus(FocusPanel::Detail);
+                }
+                self.detail_panel.handle_mouse_event(mouse, &mut state);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 模态框中：点击按键提示等同于按下对应按键，滚轮滚动 diff
+    async fn handle_modal_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let position = (mouse.column, mouse.row).into();
+                let key = self
+                    .modal_click_targets
+                    .iter()
+                    .find(|(area, _)| area.contains(position))
+                    .map(|(_, key)| *key);
+                if let Some(key) = key {
+                    return self.handle_modal_key(key).await;
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                if let Some(viewer) = &mut self.diff_viewer {
+                    viewer.diff_scroll = viewer.diff_scroll.saturating_sub(DIFF_SCROLL_LINES);
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                if let Some(viewer) = &mut self.diff_viewer {
+                    viewer.diff_scroll = viewer.diff_scroll.saturating_add(DIFF_SCROLL_LINES);
+                    viewer.clamp_scroll();
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
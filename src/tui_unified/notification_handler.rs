@@ -0,0 +1,31 @@
+use crossterm::event::KeyEvent;
+
+use super::app::AppMode;
+use crate::tui_unified::{components::widgets::notification_center::NotificationAction, Result};
+
+impl super::app::TuiUnifiedApp {
+    /// 打开通知中心，当前的提示视为已读
+    pub(crate) async fn open_notification_center(&mut self) -> Result<()> {
+        self.state.write().await.dismiss_all_notifications();
+        self.notification_center.open();
+        self.current_mode = AppMode::Notifications;
+        Ok(())
+    }
+
+    pub(crate) async fn handle_notification_key(&mut self, key: KeyEvent) -> Result<()> {
+        let mut state = self.state.write().await;
+        match self
+            .notification_center
+            .handle_key(key, &state.notifications)
+        {
+            NotificationAction::None => {}
+            NotificationAction::Close => {
+                self.notification_center.close();
+                self.current_mode = AppMode::Normal;
+            }
+            NotificationAction::Remove(id) => state.remove_notification(id),
+            NotificationAction::Clear => state.notifications.clear(),
+        }
+        Ok(())
+    }
+}
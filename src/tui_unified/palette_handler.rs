@@ -0,0 +1,96 @@
+use crossterm::event::{KeyCode, KeyEvent};
+
+use super::app::AppMode;
+use crate::tui_unified::{
+    components::widgets::command_palette::{palette_entries, PaletteAction, PaletteCommand},
+    focus::FocusPanel,
+    layout_handler::LayoutCommand,
+    state::app_state::{NotificationLevel, ViewType},
+    Result,
+};
+
+impl super::app::TuiUnifiedApp {
+    /// 打开命令面板，快捷键提示跟随当前按键配置
+    pub(crate) fn open_command_palette(&mut self) {
+        self.command_palette
+            .open(palette_entries(&self.config.key_bindings));
+        self.current_mode = AppMode::Command;
+    }
+
+    pub(crate) async fn handle_palette_key(&mut self, key: KeyEvent) -> Result<()> {
+        match self.command_palette.handle_key(key) {
+            PaletteAction::None => Ok(()),
+            PaletteAction::Close => {
+                self.close_command_palette();
+                Ok(())
+            }
+            PaletteAction::Run(command) => {
+                self.close_command_palette();
+                self.run_palette_command(command).await
+            }
+        }
+    }
+
+    fn close_command_palette(&mut self) {
+        self.command_palette.close();
+        self.current_mode = AppMode::Normal;
+    }
+
+    async fn run_palette_command(&mut self, command: PaletteCommand) -> Result<()> {
+        match command {
+            PaletteCommand::SwitchView(view) => self.switch_to_view(view).await,
+            PaletteCommand::ViewKey(view, key) => {
+                self.switch_to_view(view).await?;
+                self.handle_normal_mode_key(KeyEvent::from(KeyCode::Char(key)))
+                    .await
+            }
+            PaletteCommand::Find => self.open_fuzzy_finder().await,
+            PaletteCommand::Refresh => {
+                let view = self.state.read().await.current_view;
+                let (message, level) = match self.refresh_current_view(view).await {
+                    Ok(()) => (
+                        "Refreshed successfully".to_string(),
+                        NotificationLevel::Success,
+                    ),
+                    Err(e) => (format!("Refresh failed: {}", e), NotificationLevel::Error),
+                };
+                self.state.write().await.add_notification(message, level);
+                Ok(())
+            }
+            PaletteCommand::AiCommit => self.enter_ai_commit_mode().await,
+            PaletteCommand::Review => self.enter_review_mode().await,
+            PaletteCommand::Refactor => self.enter_refactor_mode().await,
+            PaletteCommand::CycleTheme => self.cycle_theme().await,
+            PaletteCommand::ToggleZen => self.apply_layout_command(LayoutCommand::ToggleZen).await,
+            PaletteCommand::NextLayout => {
+                self.apply_layout_command(LayoutCommand::NextPreset).await
+            }
+            PaletteCommand::Settings => {
+                self.open_settings();
+                Ok(())
+            }
+            PaletteCommand::Notifications => self.open_notification_center().await,
+            PaletteCommand::Export => self.prompt_export().await,
+            PaletteCommand::ToggleWatch => self.toggle_file_watch().await,
+            PaletteCommand::SwitchRepo => self.open_repo_switcher().await,
+            PaletteCommand::StorageStats => self.open_storage_panel().await,
+            PaletteCommand::Help => self.open_help().await,
+            PaletteCommand::Quit => {
+                self.should_quit = true;
+                Ok(())
+            }
+        }
+    }
+
+    /// 切换视图并把焦点交给内容区
+    pub(crate) async fn switch_to_view(&mut self, view: ViewType) -> Result<()> {
+        let previous_view = {
+            let mut state = self.state.write().await;
+            let previous_view = state.current_view;
+            state.set_current_view(view);
+            previous_view
+        };
+        self.focus_manager.set_focus(FocusPanel::Content);
+        self.handle_view_entry(previous_view).await
+    }
+}
@@ -0,0 +1,158 @@
+// 查询历史视图：重新执行查询、收藏和命名查询
+use crate::git::GitQuery;
+use crate::query_history::{FavoriteQuery, QueryHistory};
+use crate::tui_unified::{
+    state::{
+        app_state::{NotificationLevel, QueryAction},
+        AppState, SimpleStatePersistence,
+    },
+    Result,
+};
+
+/// 查询任务名前缀，后面跟查询内容
+pub(crate) const QUERY_TASK_PREFIX: &str = "query: ";
+
+async fn run_query(query: String) -> std::result::Result<String, String> {
+    let filters = GitQuery::parse_query(&query).map_err(|e| e.to_string())?;
+    GitQuery::execute_query(&filters)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 收藏状态变化后保存，失败时提示
+async fn save_favorites(state: &mut AppState, message: String) {
+    let saved = match SimpleStatePersistence::new() {
+        Ok(persistence) => persistence.save_state(state).await,
+        Err(e) => Err(e),
+    };
+    match saved {
+        Ok(()) => {
+            state.add_notification(message, NotificationLevel::Info);
+        }
+        Err(e) => {
+            state.add_notification(
+                format!("Failed to save favorite queries: {}", e),
+                NotificationLevel::Warning,
+            );
+        }
+    }
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 处理查询历史视图的请求
+    pub(crate) async fn handle_pending_query_action(&mut self) -> Result<()> {
+        let Some(action) = self.state.read().await.take_query_action() else {
+            return Ok(());
+        };
+
+        match action {
+            QueryAction::Run(query) => self.start_query(query).await,
+            QueryAction::TogglePin(query) => {
+                let mut state = self.state.write().await;
+                let favorites = &mut state.search_state.favorite_queries;
+                let message = match favorites.iter().position(|f| f.query == query) {
+                    Some(index) => {
+                        favorites.remove(index);
+                        format!("Unpinned query: {}", query)
+                    }
+                    None => {
+                        favorites.push(FavoriteQuery {
+                            query: query.clone(),
+                            name: None,
+                        });
+                        format!("Pinned query: {}", query)
+                    }
+                };
+                self.query_history_view
+                    .set_favorites(state.search_state.favorite_queries.clone());
+                save_favorites(&mut state, message).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// 给查询命名，未收藏的查询同时被收藏
+    pub(crate) async fn rename_query(&mut self, query: String, name: &str) -> Result<()> {
+        let mut state = self.state.write().await;
+        let favorites = &mut state.search_state.favorite_queries;
+        match favorites.iter_mut().find(|f| f.query == query) {
+            Some(favorite) => favorite.name = Some(name.to_string()),
+            None => favorites.push(FavoriteQuery {
+                query: query.clone(),
+                name: Some(name.to_string()),
+            }),
+        }
+        self.query_history_view
+            .set_favorites(state.search_state.favorite_queries.clone());
+        save_favorites(&mut state, format!("Named query \"{}\": {}", name, query)).await;
+        Ok(())
+    }
+
+    async fn start_query(&mut self, query: String) {
+        let name = format!("{}{}", QUERY_TASK_PREFIX, query);
+        let mut state = self.state.write().await;
+        if self.task_manager.is_running(&name) {
+            state.add_notification(
+                format!("Query is already running: {}", query),
+                NotificationLevel::Warning,
+            );
+            return;
+        }
+
+        state.add_loading_task(name.clone(), format!("Running query {}", query));
+        self.task_manager
+            .spawn_reported(name, move |_| run_query(query));
+    }
+
+    /// 查询结束后显示结果，并记录到查询历史
+    pub(crate) async fn finish_query(
+        &mut self,
+        query: &str,
+        result: std::result::Result<String, String>,
+    ) {
+        let (result_count, success) = match &result {
+            Ok(output) => (Some(output.lines().count()), true),
+            Err(_) => (None, false),
+        };
+        // 在获取状态锁之前写入历史，远程存储较慢时不阻塞界面
+        let recorded = match QueryHistory::new(1000).await {
+            Ok(mut history) => {
+                history
+                    .add_entry(
+                        query.to_string(),
+                        Some("execute".to_string()),
+                        result_count,
+                        success,
+                    )
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        let mut state = self.state.write().await;
+        state.remove_loading_task(&format!("{}{}", QUERY_TASK_PREFIX, query));
+        if let Err(e) = recorded {
+            state.add_notification(
+                format!("Failed to record query history: {}", e),
+                NotificationLevel::Warning,
+            );
+        }
+
+        match result {
+            Ok(output) if output.trim().is_empty() => {
+                state.add_notification(
+                    format!("No results for query: {}", query),
+                    NotificationLevel::Info,
+                );
+            }
+            Ok(output) => state.show_query_result_modal(query, output),
+            Err(e) => {
+                state.add_notification(format!("Query failed: {}", e), NotificationLevel::Error);
+            }
+        }
+
+        let favorites = state.search_state.favorite_queries.clone();
+        drop(state);
+        self.query_history_view.load_history(&favorites).await;
+    }
+}
@@ -0,0 +1,273 @@
+use std::process::Stdio;
+use tokio::io::AsyncReadExt;
+
+use crate::tui_unified::{
+    async_manager::{TaskEvent, TaskReporter},
+    state::app_state::{NotificationLevel, RemoteOperation, ViewType},
+    Result,
+};
+
+/// 远程操作在任务管理器和加载任务中使用的名称，同一时间只运行一个
+const REMOTE_TASK: &str = "remote";
+/// 失败时在通知中保留的 stderr 行数
+const ERROR_TAIL_LINES: usize = 3;
+
+/// 解析 git `--progress` 输出的一行，例如 `Receiving objects:  45% (9/20)`，
+/// 返回阶段名称和 0.0 到 1.0 的进度
+pub(crate) fn parse_git_progress(line: &str) -> Option<(String, f64)> {
+    let line = line.trim();
+    let line = line.strip_prefix("remote:").unwrap_or(line).trim_start();
+    let (phase, rest) = line.split_once(':')?;
+    let percent = rest.trim_start().split('%').next()?.trim();
+    let percent: f64 = percent.parse().ok()?;
+    Some((phase.trim().to_string(), (percent / 100.0).clamp(0.0, 1.0)))
+}
+
+/// 运行 git 命令并把 stderr 中的进度转发给 reporter，
+/// 成功时返回 stdout 的最后一行，失败时返回 stderr 的末尾几行
+async fn run_git_with_progress(
+    args: Vec<String>,
+    reporter: TaskReporter,
+) -> std::result::Result<String, String> {
+    let mut child = tokio::process::Command::new("git")
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run git: {}", e))?;
+
+    // git 用 \r 刷新同一行进度，按 \r 和 \n 切分
+    let mut messages = Vec::new();
+    if let Some(mut stderr) = child.stderr.take() {
+        let mut buf = [0u8; 1024];
+        let mut pending = String::new();
+        loop {
+            let n = match stderr.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            pending.push_str(&String::from_utf8_lossy(&buf[..n]));
+            while let Some(pos) = pending.find(['\r', '\n']) {
+                let line: String = pending.drain(..=pos).collect();
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match parse_git_progress(line) {
+                    Some((phase, ratio)) => reporter.progress(Some(ratio), phase),
+                    None => messages.push(line.to_string()),
+                }
+            }
+        }
+        if !pending.trim().is_empty() {
+            messages.push(pending.trim().to_string());
+        }
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to wait for git: {}", e))?;
+    if output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or_default()
+            .trim()
+            .to_string())
+    } else {
+        let start = messages.len().saturating_sub(ERROR_TAIL_LINES);
+        Err(match messages[start..].join(" ") {
+            tail if tail.is_empty() => format!("git exited with {}", output.status),
+            tail => tail,
+        })
+    }
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 在后台启动视图请求的远程操作
+    pub(crate) async fn handle_pending_remote_operation(&mut self) -> Result<()> {
+        let Some(operation) = self.state.read().await.take_remote_operation() else {
+            return Ok(());
+        };
+
+        let mut state = self.state.write().await;
+        if self.task_manager.is_running(REMOTE_TASK) {
+            state.add_notification(
+                "Another fetch/pull/push is still running".to_string(),
+                NotificationLevel::Warning,
+            );
+            return Ok(());
+        }
+
+        let label = operation.label();
+        state.add_loading_task(REMOTE_TASK.to_string(), label);
+        let args = operation.git_args();
+        self.task_manager
+            .spawn_reported(REMOTE_TASK.to_string(), |reporter| {
+                run_git_with_progress(args, reporter)
+            });
+        self.remote_operation = Some(operation);
+        Ok(())
+    }
+
+    /// 处理后台任务的进度和结果
+    pub(crate) async fn poll_background_tasks(&mut self) -> Result<()> {
+        for event in self.task_manager.poll_events() {
+            match event {
+                TaskEvent::Progress {
+                    name,
+                    ratio,
+                    message,
+                } => {
+                    let mut state = self.state.write().await;
+                    let label = self
+                        .remote_operation
+                        .as_ref()
+                        .map(RemoteOperation::label)
+                        .unwrap_or_default();
+                    let message = format!("{}: {}", label, message);
+                    match ratio {
+                        Some(ratio) => state.update_loading_progress(&name, ratio, message),
+                        None => {
+                            if let Some(task) = state.loading_tasks.get_mut(&name) {
+                                task.message = message;
+                            }
+                        }
+                    }
+                }
+                TaskEvent::Finished { name, result } if name == REMOTE_TASK => {
+                    let operation = self.remote_operation.take();
+                    self.finish_remote_operation(operation, result).await;
+                }
+                TaskEvent::Finished { name, result }
+                    if name == super::review_handler::REVIEW_TASK =>
+                {
+                    self.finish_review(result).await;
+                }
+                TaskEvent::Finished { name, result }
+                    if name.starts_with(super::query_history_handler::QUERY_TASK_PREFIX) =>
+                {
+                    let query = &name[super::query_history_handler::QUERY_TASK_PREFIX.len()..];
+                    self.finish_query(query, result).await;
+                }
+                TaskEvent::Finished { name, result }
+                    if name.starts_with(super::startup_handler::LOAD_TASK_PREFIX) =>
+                {
+                    let label = &name[super::startup_handler::LOAD_TASK_PREFIX.len()..];
+                    self.finish_initial_load(label, result).await;
+                }
+                TaskEvent::Finished { name, result } => {
+                    let mut state = self.state.write().await;
+                    state.remove_loading_task(&name);
+                    match result {
+                        Ok(message) if message.is_empty() => state.add_notification(
+                            format!("{} finished", name),
+                            NotificationLevel::Success,
+                        ),
+                        Ok(message) => state.add_notification(message, NotificationLevel::Success),
+                        Err(e) => state.add_notification(
+                            format!("{} failed: {}", name, e),
+                            NotificationLevel::Error,
+                        ),
+                    };
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn finish_remote_operation(
+        &mut self,
+        operation: Option<RemoteOperation>,
+        result: std::result::Result<String, String>,
+    ) {
+        let label = operation
+            .as_ref()
+            .map(RemoteOperation::label)
+            .unwrap_or_else(|| "Remote operation".to_string());
+        {
+            let mut state = self.state.write().await;
+            state.remove_loading_task(REMOTE_TASK);
+            match &result {
+                Ok(summary) if !summary.is_empty() => {
+                    state.add_notification(summary.clone(), NotificationLevel::Success)
+                }
+                Ok(_) => state
+                    .add_notification(format!("{} completed", label), NotificationLevel::Success),
+                Err(e) => state
+                    .add_notification(format!("{} failed: {}", label, e), NotificationLevel::Error),
+            };
+        }
+        if result.is_err() {
+            return;
+        }
+
+        // 刷新领先/落后计数和远程列表，拉取后还要刷新提交历史
+        let mut views = vec![ViewType::Branches, ViewType::Remotes];
+        if operation == Some(RemoteOperation::Pull) {
+            views.push(ViewType::GitLog);
+        }
+        for view in views {
+            if let Err(e) = self.refresh_current_view(view).await {
+                self.state.write().await.add_notification(
+                    format!("Failed to refresh {:?}: {}", view, e),
+                    NotificationLevel::Warning,
+                );
+            }
+        }
+        // 分支数不变时分支视图不会自动重建列表
+        let state = self.state.read().await;
+        self.branches_view.refresh_branches(&state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_git_progress() {
+        assert_eq!(
+            parse_git_progress("Receiving objects:  45% (9/20), 1.20 MiB | 2.00 MiB/s"),
+            Some(("Receiving objects".to_string(), 0.45))
+        );
+        assert_eq!(
+            parse_git_progress("remote: Counting objects: 100% (5/5), done."),
+            Some(("Counting objects".to_string(), 1.0))
+        );
+        assert_eq!(parse_git_progress("From github.com:owner/repo"), None);
+        assert_eq!(
+            parse_git_progress("   abc123..def456  main -> origin/main"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remote_operation_args() {
+        let push = RemoteOperation::Push {
+            branch: "feature".to_string(),
+            upstream: Some("upstream/feature-x".to_string()),
+        };
+        assert_eq!(
+            push.git_args(),
+            vec!["push", "--progress", "upstream", "feature:feature-x"]
+        );
+
+        let first_push = RemoteOperation::Push {
+            branch: "feature".to_string(),
+            upstream: None,
+        };
+        assert_eq!(
+            first_push.git_args(),
+            vec!["push", "--progress", "--set-upstream", "origin", "feature"]
+        );
+        assert_eq!(
+            RemoteOperation::Fetch(None).git_args(),
+            vec!["fetch", "--progress", "--all"]
+        );
+    }
+}
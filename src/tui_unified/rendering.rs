@@ -1,12 +1,26 @@
 use super::app::{AppMode, LayoutResult, TuiUnifiedApp};
+use super::layout_handler::{compact_tabs, panel_type};
 use crate::tui_unified::components::base::component::Component;
+use crate::tui_unified::components::widgets::notification_center::render_toasts;
+use crate::tui_unified::components::widgets::tab_bar::render_tab_bar;
 use crate::tui_unified::focus::FocusPanel;
-use crate::tui_unified::state::AppState;
+use crate::tui_unified::state::{app_state::ViewType, AppState, DataSection};
 
 impl TuiUnifiedApp {
     pub(crate) fn render(&mut self, frame: &mut ratatui::Frame) {
-        // 计算布局
+        // 计算布局，窄终端下只显示焦点所在的面板
+        let tabs = match self.state.try_read() {
+            Ok(state) => compact_tabs(state.current_view),
+            Err(_) => compact_tabs(ViewType::GitLog),
+        };
+        let selected_tab = tabs
+            .iter()
+            .position(|(panel, _)| *panel == self.focus_manager.current_panel)
+            .unwrap_or(1);
+        self.layout_manager.focused = panel_type(tabs[selected_tab].0);
         let layout = self.layout_manager.calculate_layout(frame.size());
+        self.last_layout = layout;
+        self.modal_click_targets.clear();
 
         // 检查是否能获取状态读锁
         let modal_info = match self.state.try_read() {
@@ -28,18 +42,24 @@ impl TuiUnifiedApp {
                 // 渲染侧边栏
                 self.sidebar_panel.render(frame, layout.sidebar, &state);
 
+                if layout.is_compact() {
+                    let labels: Vec<&str> = tabs.iter().map(|(_, label)| *label).collect();
+                    render_tab_bar(frame, layout.tab_bar, &labels, selected_tab);
+                }
+
                 // 根据当前视图渲染主内容区
                 match current_view {
                     crate::tui_unified::state::app_state::ViewType::GitLog => {
                         // Git Log 视图：左侧显示git log，右侧显示分支列表
                         use ratatui::layout::{Constraint, Direction, Layout};
 
-                        // 分割区域：左侧60%显示git log，右侧40%显示分支列表
+                        // 分割区域：左侧60%显示git log，右侧40%显示分支列表，窄终端下只显示git log
+                        let split = if layout.is_compact() { 100 } else { 60 };
                         let chunks = Layout::default()
                             .direction(Direction::Horizontal)
                             .constraints([
-                                Constraint::Percentage(60), // Git log
-                                Constraint::Percentage(40), // 分支列表
+                                Constraint::Percentage(split),       // Git log
+                                Constraint::Percentage(100 - split), // 分支列表
                             ])
                             .split(layout.content);
 
@@ -49,21 +69,43 @@ impl TuiUnifiedApp {
                         // 渲染git log
                         self.git_log_view.set_focus(content_focused);
                         self.git_log_view.render(frame, chunks[0], &state);
+                        Self::render_section_loading(
+                            frame,
+                            chunks[0],
+                            &state,
+                            DataSection::Commits,
+                        );
 
                         // 渲染分支列表
                         self.branches_view.set_focus(false); // 分支列表在git log视图中不获得焦点
-                        self.branches_view.render(frame, chunks[1], &state);
+                        if chunks[1].width > 0 {
+                            self.branches_view.render(frame, chunks[1], &state);
+                            Self::render_section_loading(
+                                frame,
+                                chunks[1],
+                                &state,
+                                DataSection::Branches,
+                            );
+                        }
+
+                        // 渲染提交详情面板
+                        if layout.detail.width > 0 {
+                            self.detail_panel
+                                .set_focus(self.focus_manager.current_panel == FocusPanel::Detail);
+                            self.detail_panel.render(frame, layout.detail, &state);
+                        }
                     }
                     crate::tui_unified::state::app_state::ViewType::Branches => {
                         // 分支视图：左侧显示分支列表，右侧显示该分支的git log
                         use ratatui::layout::{Constraint, Direction, Layout};
 
-                        // 分割区域：左侧40%显示分支列表，右侧60%显示git log
+                        // 分割区域：左侧40%显示分支列表，右侧60%显示git log，窄终端下只显示分支列表
+                        let split = if layout.is_compact() { 100 } else { 40 };
                         let chunks = Layout::default()
                             .direction(Direction::Horizontal)
                             .constraints([
-                                Constraint::Percentage(40), // 分支列表
-                                Constraint::Percentage(60), // Git log
+                                Constraint::Percentage(split),       // 分支列表
+                                Constraint::Percentage(100 - split), // Git log
                             ])
                             .split(layout.content);
 
@@ -77,6 +119,12 @@ impl TuiUnifiedApp {
                         // 渲染分支列表
                         self.branches_view.set_focus(content_focused);
                         self.branches_view.render(frame, chunks[0], &state);
+                        Self::render_section_loading(
+                            frame,
+                            chunks[0],
+                            &state,
+                            DataSection::Branches,
+                        );
 
                         // 根据选中的分支更新Git Log并渲染
                         let selected_branch = state.selected_items.selected_branch.clone();
@@ -96,22 +144,48 @@ impl TuiUnifiedApp {
                             self.git_log_view.update_commits(commits_to_show);
                         }
                         self.git_log_view.set_focus(false); // git log在分支视图中不获得焦点
-                        self.git_log_view.render(frame, chunks[1], &state);
+                        if chunks[1].width > 0 {
+                            self.git_log_view.render(frame, chunks[1], &state);
+                            Self::render_section_loading(
+                                frame,
+                                chunks[1],
+                                &state,
+                                DataSection::Commits,
+                            );
+                        }
                     }
                     crate::tui_unified::state::app_state::ViewType::Tags => {
                         self.tags_view
                             .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
                         self.tags_view.render(frame, layout.content, &state);
+                        Self::render_section_loading(
+                            frame,
+                            layout.content,
+                            &state,
+                            DataSection::Tags,
+                        );
                     }
                     crate::tui_unified::state::app_state::ViewType::Remotes => {
                         self.remotes_view
                             .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
                         self.remotes_view.render(frame, layout.content, &state);
+                        Self::render_section_loading(
+                            frame,
+                            layout.content,
+                            &state,
+                            DataSection::Remotes,
+                        );
                     }
                     crate::tui_unified::state::app_state::ViewType::Stash => {
                         self.stash_view
                             .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
                         self.stash_view.render(frame, layout.content, &state);
+                        Self::render_section_loading(
+                            frame,
+                            layout.content,
+                            &state,
+                            DataSection::Stashes,
+                        );
                     }
                     crate::tui_unified::state::app_state::ViewType::QueryHistory => {
                         self.query_history_view
@@ -123,19 +197,92 @@ impl TuiUnifiedApp {
                         self.staging_view
                             .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
                         self.staging_view.render(frame, layout.content, &state);
+                        Self::render_section_loading(
+                            frame,
+                            layout.content,
+                            &state,
+                            DataSection::Status,
+                        );
+                    }
+                    crate::tui_unified::state::app_state::ViewType::Graph => {
+                        self.graph_view
+                            .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
+                        self.graph_view.render(frame, layout.content, &state);
+                    }
+                    crate::tui_unified::state::app_state::ViewType::Reflog => {
+                        self.reflog_view
+                            .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
+                        self.reflog_view.render(frame, layout.content, &state);
+                    }
+                    crate::tui_unified::state::app_state::ViewType::Blame => {
+                        self.blame_view
+                            .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
+                        self.blame_view.render(frame, layout.content, &state);
+                    }
+                    crate::tui_unified::state::app_state::ViewType::Worktrees => {
+                        self.worktrees_view
+                            .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
+                        self.worktrees_view.render(frame, layout.content, &state);
+                    }
+                    crate::tui_unified::state::app_state::ViewType::Review => {
+                        self.review_view
+                            .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
+                        self.review_view.render(frame, layout.content, &state);
+                    }
+                    crate::tui_unified::state::app_state::ViewType::Hotspots => {
+                        self.hotspots_view
+                            .set_focus(self.focus_manager.current_panel == FocusPanel::Content);
+                        self.hotspots_view.render(frame, layout.content, &state);
                     }
                 }
 
-                // 渲染搜索框（如果在搜索模式）
+                // 后台任务的进度条覆盖在内容区底部
+                Self::render_loading_tasks(frame, layout.content, &state);
+
+                // 渲染状态栏
+                self.render_status_bar(frame, layout.status_bar, &state);
+
+                // 渲染模糊查找浮层
                 if self.current_mode == AppMode::Search {
-                    self.search_box.set_focus(true);
-                    self.search_box.set_search_active(true);
-                    self.search_box.render(frame, layout.status_bar, &state);
+                    self.fuzzy_finder.render(frame, frame.size(), &state);
+                }
+
+                // 渲染设置浮层
+                if self.current_mode == AppMode::Settings {
+                    self.settings_panel.render(frame, frame.size());
+                }
+
+                // 渲染新建标签浮层
+                if self.current_mode == AppMode::CreateTag {
+                    self.tag_dialog.render(frame, frame.size());
+                }
+
+                // 渲染命令面板
+                if self.current_mode == AppMode::Command {
+                    self.command_palette.render(frame, frame.size());
+                }
+
+                // 渲染仓库切换器
+                if self.current_mode == AppMode::Repos {
+                    self.repo_switcher.render(frame, frame.size());
+                }
+
+                // 渲染存储概况
+                if self.current_mode == AppMode::Storage {
+                    self.storage_panel.render(frame, frame.size());
+                }
+
+                // 渲染帮助浮层
+                if self.current_mode == AppMode::Help {
+                    self.help_panel.render(frame, frame.size());
+                }
+
+                // 渲染通知中心，否则在右上角显示提示
+                if self.current_mode == AppMode::Notifications {
+                    self.notification_center
+                        .render(frame, frame.size(), &state.notifications);
                 } else {
-                    self.search_box.set_focus(false);
-                    self.search_box.set_search_active(false);
-                    // 渲染状态栏
-                    self.render_status_bar(frame, layout.status_bar, &state);
+                    render_toasts(frame, frame.size(), &state.get_active_notifications());
                 }
             }
             Err(_) => {
@@ -148,6 +295,81 @@ impl TuiUnifiedApp {
         if let Some(modal) = modal_info.1 {
             self.render_modal(frame, &modal, frame.size());
         }
+
+        // 应用颜色主题
+        self.theme.apply(frame.buffer_mut());
+    }
+
+    /// 数据仍在后台加载时，用加载提示和骨架行覆盖面板内容（保留边框）
+    fn render_section_loading(
+        frame: &mut ratatui::Frame,
+        area: ratatui::layout::Rect,
+        state: &AppState,
+        section: DataSection,
+    ) {
+        use ratatui::{
+            layout::Rect,
+            style::{Color, Style},
+            text::{Line, Span},
+            widgets::{Clear, Paragraph},
+        };
+
+        /// 骨架行占面板宽度的比例，循环使用
+        const SKELETON_WIDTHS: [u16; 4] = [70, 45, 85, 60];
+
+        if !state.is_section_loading(section) || area.width < 4 || area.height < 3 {
+            return;
+        }
+        let inner = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width - 2,
+            height: area.height - 2,
+        };
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!("⏳ Loading {}…", section.label()),
+            Style::default().fg(Color::Yellow),
+        ))];
+        for i in 1..inner.height as usize {
+            let width = inner.width * SKELETON_WIDTHS[i % SKELETON_WIDTHS.len()] / 100;
+            lines.push(Line::from(Span::styled(
+                "░".repeat(width as usize),
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+
+        frame.render_widget(Clear, inner);
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+
+    /// 渲染后台任务的进度条，无法估计进度的任务显示为空进度
+    fn render_loading_tasks(
+        frame: &mut ratatui::Frame,
+        area: ratatui::layout::Rect,
+        state: &AppState,
+    ) {
+        use crate::tui_unified::components::widgets::progress_bar::ProgressBar;
+        use ratatui::{layout::Rect, widgets::Clear};
+
+        const BAR_HEIGHT: u16 = 3;
+        let mut tasks = state.get_loading_tasks();
+        tasks.sort_by_key(|task| task.started_at);
+        for (i, task) in tasks.iter().rev().enumerate() {
+            let offset = BAR_HEIGHT * (i as u16 + 1);
+            if offset > area.height {
+                break;
+            }
+            let bar_area = Rect {
+                x: area.x,
+                y: area.y + area.height - offset,
+                width: area.width,
+                height: BAR_HEIGHT,
+            };
+            frame.render_widget(Clear, bar_area);
+            ProgressBar::new(task.progress.unwrap_or(0.0), task.message.clone())
+                .render(bar_area, frame.buffer_mut());
+        }
     }
 
     /// 渲染状态栏
@@ -170,6 +392,11 @@ impl TuiUnifiedApp {
             AppMode::Help => "HELP",
             AppMode::Diff => "DIFF",
             AppMode::AICommit => "AI COMMIT",
+            AppMode::Settings => "SETTINGS",
+            AppMode::CreateTag => "NEW TAG",
+            AppMode::Notifications => "NOTIFICATIONS",
+            AppMode::Repos => "REPOS",
+            AppMode::Storage => "STORAGE",
         };
 
         let focus_text = match self.focus_manager.current_panel {
@@ -180,27 +407,53 @@ impl TuiUnifiedApp {
 
         let view_specific_keys = match state.current_view {
             crate::tui_unified::state::app_state::ViewType::GitLog => {
-                "p for pull, Enter to view diff"
+                "p for pull, Enter to view diff, y/Y copy hash/message, Space-mark, C-cherry-pick, v-revert, Tab to browse files"
             }
             crate::tui_unified::state::app_state::ViewType::Branches => {
-                "Enter to switch branch, Tab to show remotes"
+                "Enter-checkout, n-new, r-rename, d-delete, o-diff, Tab-remotes, F-fetch, p-pull, U-push"
+            }
+            crate::tui_unified::state::app_state::ViewType::Tags => {
+                "Enter to view tag details, n-new tag"
             }
-            crate::tui_unified::state::app_state::ViewType::Tags => "Enter to view tag details",
             crate::tui_unified::state::app_state::ViewType::Remotes => {
-                "Enter to view remote details"
+                "F-fetch remote, A-fetch all, p-pull, U-push current branch"
             }
             crate::tui_unified::state::app_state::ViewType::Stash => "Enter to view stash details",
             crate::tui_unified::state::app_state::ViewType::QueryHistory => {
-                "Enter to execute query"
+                "Enter-run query, s-pin/unpin, r-rename"
             }
             crate::tui_unified::state::app_state::ViewType::Staging => {
-                "Space-toggle, a-stage all, c-commit"
+                "Space-toggle, a-stage all, c-commit, y-copy path"
             }
+            crate::tui_unified::state::app_state::ViewType::Graph => {
+                "Enter to view diff, y/Y copy hash/message, / then Ctrl+F to filter"
+            }
+            crate::tui_unified::state::app_state::ViewType::Reflog => {
+                "Enter-diff, y-copy hash, o-checkout, S/M/H-reset soft/mixed/hard"
+            }
+            crate::tui_unified::state::app_state::ViewType::Blame => {
+                "Enter-commit diff, y/Y copy hash/message, / then Ctrl+F to find"
+            }
+            crate::tui_unified::state::app_state::ViewType::Worktrees => {
+                "Enter-switch, n-new, d-remove, P-prune, o-open shell, y-copy path"
+            }
+            crate::tui_unified::state::app_state::ViewType::Review => {
+                "w-review working tree, C-review selected commit, s-stored review, Tab-report, Enter-jump to diff, r-rerun"
+            }
+            crate::tui_unified::state::app_state::ViewType::Hotspots => {
+                "Enter-blame file, y-copy path, r-analyze again"
+            }
+        };
+
+        let watch_text = if self.repo_watcher.is_some() {
+            " | ⟳ Auto-refresh"
+        } else {
+            ""
         };
 
         let status_content = format!(
-            "[{}] Focus: {} | View: {:?} | {} | Tab-focus, c-AI commit, v-review, f-refactor, r-refresh, ?-help, q-quit",
-            mode_text, focus_text, state.current_view, view_specific_keys
+            "[{}] Focus: {} | View: {:?}{} | {} | /-find, :-commands, Tab-focus, z-zen, L-layout, c-AI commit, v-review, f-refactor, r-refresh, W-watch, O-repos, T-theme, ,-settings, N-notifications, ?-help, q-quit",
+            mode_text, focus_text, state.current_view, watch_text, view_specific_keys
         );
 
         let status_bar = Paragraph::new(Text::raw(status_content))
@@ -0,0 +1,121 @@
+// 仓库切换：从最近打开的仓库或目录浏览中选择，不重启 TUI 切换仓库
+use crossterm::event::KeyEvent;
+use std::path::Path;
+
+use super::app::AppMode;
+use crate::recent_repos::{self, RecentRepos};
+use crate::tui_unified::{
+    components::widgets::repo_switcher::RepoSwitcherAction, state::app_state::NotificationLevel,
+    Result,
+};
+
+impl super::app::TuiUnifiedApp {
+    /// 把当前仓库记入最近列表，失败时提示
+    pub(crate) async fn remember_current_repo(&mut self) {
+        let remembered = std::env::current_dir()
+            .map_err(anyhow::Error::from)
+            .and_then(|dir| recent_repos::repo_root(&dir))
+            .and_then(|root| RecentRepos::remember(&root));
+        if let Err(e) = remembered {
+            self.state.write().await.add_notification(
+                format!("Failed to update recent repositories: {}", e),
+                NotificationLevel::Warning,
+            );
+        }
+    }
+
+    pub(crate) async fn open_repo_switcher(&mut self) -> Result<()> {
+        let recent = match RecentRepos::load() {
+            Ok(recent) => recent,
+            Err(e) => {
+                self.state.write().await.add_notification(
+                    format!("Failed to load recent repositories: {}", e),
+                    NotificationLevel::Warning,
+                );
+                RecentRepos::default()
+            }
+        };
+        let current = std::env::current_dir()
+            .ok()
+            .and_then(|dir| recent_repos::repo_root(&dir).ok());
+        self.repo_switcher.open(recent.repos().to_vec(), current);
+        self.current_mode = AppMode::Repos;
+        Ok(())
+    }
+
+    fn close_repo_switcher(&mut self) {
+        self.repo_switcher.close();
+        self.current_mode = AppMode::Normal;
+    }
+
+    pub(crate) async fn handle_repo_switcher_key(&mut self, key: KeyEvent) -> Result<()> {
+        match self.repo_switcher.handle_key(key) {
+            RepoSwitcherAction::None => {}
+            RepoSwitcherAction::Close => self.close_repo_switcher(),
+            RepoSwitcherAction::Switch(path) => {
+                self.close_repo_switcher();
+                self.switch_repository(&path).await;
+            }
+            RepoSwitcherAction::Forget(path) => {
+                let forgotten = RecentRepos::load().and_then(|mut recent| {
+                    recent.remove(&path);
+                    recent.save()?;
+                    Ok(recent)
+                });
+                match forgotten {
+                    Ok(recent) => self.repo_switcher.set_recent(recent.repos().to_vec()),
+                    Err(e) => {
+                        self.state.write().await.add_notification(
+                            format!("Failed to update recent repositories: {}", e),
+                            NotificationLevel::Error,
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 切换到另一个仓库：丢弃当前仓库的状态、视图和缓存，重新创建应用并加载数据
+    pub(crate) async fn switch_repository(&mut self, path: &Path) {
+        let previous = std::env::current_dir().ok();
+        let root = match recent_repos::enter_repo(path) {
+            Ok(root) => root,
+            Err(e) => {
+                self.state
+                    .write()
+                    .await
+                    .add_notification(format!("{:#}", e), NotificationLevel::Error);
+                return;
+            }
+        };
+
+        let fresh = match Self::new().await {
+            Ok(app) => app,
+            Err(e) => {
+                // 留在原来的仓库
+                if let Some(dir) = previous {
+                    let _ = std::env::set_current_dir(dir);
+                }
+                self.state.write().await.add_notification(
+                    format!("Failed to open {}: {}", root.display(), e),
+                    NotificationLevel::Error,
+                );
+                return;
+            }
+        };
+        *self = fresh;
+
+        if let Err(e) = self.start_initial_load().await {
+            self.state.write().await.add_notification(
+                format!("Failed to load {}: {}", root.display(), e),
+                NotificationLevel::Error,
+            );
+            return;
+        }
+        self.state.write().await.add_notification(
+            format!("Switched to repository {}", root.display()),
+            NotificationLevel::Success,
+        );
+    }
+}
@@ -0,0 +1,209 @@
+// AI 审查视图：在后台运行审查并跳转到问题所在的 diff
+use crate::commands::review::build_review_report;
+use crate::config::Config;
+use crate::diff_viewer::DiffViewer;
+use crate::report::CodeReviewReport;
+use crate::storage::StorageManager;
+use crate::tui_unified::{
+    components::views::review::ReviewTarget,
+    state::app_state::{NotificationLevel, ReviewRequest, ViewType},
+    Result,
+};
+
+/// 审查任务名，同一时间只运行一个审查
+pub(crate) const REVIEW_TASK: &str = "review";
+
+/// 读取审查对象的 diff 并生成报告，结果序列化为 JSON 传回主循环
+async fn run_review(target: ReviewTarget) -> std::result::Result<String, String> {
+    let diff = match &target {
+        ReviewTarget::WorkingTree => crate::git::get_all_changes_diff().await,
+        ReviewTarget::Commit(hash) => crate::git::get_combined_diff(hash).await,
+    }
+    .map_err(|e| e.to_string())?;
+    if diff.trim().is_empty() {
+        return Err(format!("No changes to review in {}", target.label()));
+    }
+
+    let config = Config::new();
+    let report = build_review_report(&target.label(), &diff, &config)
+        .await
+        .map_err(|e| e.to_string())?;
+    // 提交的审查结果按 SHA 保存，之后可直接加载
+    if let ReviewTarget::Commit(hash) = &target {
+        if let Ok(storage) = StorageManager::from_config(&config) {
+            let _ = storage.save_report(hash, &report).await;
+        }
+    }
+    serde_json::to_string(&report).map_err(|e| e.to_string())
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 处理审查视图的请求
+    pub(crate) async fn handle_pending_review_request(&mut self) -> Result<()> {
+        let Some(request) = self.state.read().await.take_review_request() else {
+            return Ok(());
+        };
+
+        match request {
+            ReviewRequest::WorkingTree => self.start_review(ReviewTarget::WorkingTree).await,
+            ReviewRequest::SelectedCommit => {
+                match self.git_log_view.selected_commit().map(|c| c.hash.clone()) {
+                    Some(hash) => self.start_review(ReviewTarget::Commit(hash)).await,
+                    None => {
+                        self.state.write().await.add_notification(
+                            "Select a commit in the Git log first".to_string(),
+                            NotificationLevel::Warning,
+                        );
+                    }
+                }
+            }
+            ReviewRequest::StoredForSelectedCommit => {
+                match self.git_log_view.selected_commit().map(|c| c.hash.clone()) {
+                    Some(hash) => self.load_stored_review(&hash).await,
+                    None => {
+                        self.state.write().await.add_notification(
+                            "Select a commit in the Git log first".to_string(),
+                            NotificationLevel::Warning,
+                        );
+                    }
+                }
+            }
+            ReviewRequest::Rerun => {
+                if let Some(target) = self.review_view.target().cloned() {
+                    self.start_review(target).await;
+                }
+            }
+            ReviewRequest::Jump { file, line } => self.jump_to_finding(file, line).await?,
+        }
+        Ok(())
+    }
+
+    /// 加载提交已保存的审查报告（提交时的审查门禁或之前的审查生成）
+    async fn load_stored_review(&mut self, hash: &str) {
+        let result = match StorageManager::from_config(&Config::new()) {
+            Ok(storage) => storage.load_report(hash).await,
+            Err(e) => Err(e),
+        };
+        let mut state = self.state.write().await;
+        match result {
+            Ok(Some(stored)) => {
+                state.add_notification(
+                    format!(
+                        "Loaded review of {} stored at {}",
+                        &hash[..8.min(hash.len())],
+                        stored.stored_at
+                    ),
+                    NotificationLevel::Success,
+                );
+                self.review_view.set_stored_report(
+                    &stored.commit,
+                    stored.report,
+                    &stored.stored_at,
+                );
+            }
+            Ok(None) => {
+                state.add_notification(
+                    format!(
+                        "No stored review for commit {}, press C to review it",
+                        &hash[..8.min(hash.len())]
+                    ),
+                    NotificationLevel::Warning,
+                );
+            }
+            Err(e) => {
+                state.add_notification(
+                    format!("Failed to load stored review: {}", e),
+                    NotificationLevel::Error,
+                );
+            }
+        }
+    }
+
+    async fn start_review(&mut self, target: ReviewTarget) {
+        let mut state = self.state.write().await;
+        if self.task_manager.is_running(REVIEW_TASK) {
+            state.add_notification(
+                "A review is already running".to_string(),
+                NotificationLevel::Warning,
+            );
+            return;
+        }
+
+        state.add_loading_task(
+            REVIEW_TASK.to_string(),
+            format!("Reviewing {}", target.label()),
+        );
+        self.review_view.start(target.clone());
+        self.task_manager
+            .spawn_reported(REVIEW_TASK.to_string(), move |_| run_review(target));
+    }
+
+    /// 审查任务结束后显示报告
+    pub(crate) async fn finish_review(&mut self, result: std::result::Result<String, String>) {
+        let mut state = self.state.write().await;
+        state.remove_loading_task(REVIEW_TASK);
+        let report = result.and_then(|json| {
+            serde_json::from_str::<CodeReviewReport>(&json).map_err(|e| e.to_string())
+        });
+        match report {
+            Ok(report) => {
+                state.add_notification(
+                    format!(
+                        "Review of {} found {} issue(s)",
+                        report.target,
+                        report.findings.len()
+                    ),
+                    NotificationLevel::Success,
+                );
+                self.review_view.set_report(report);
+            }
+            Err(e) => {
+                state.add_notification(format!("Review failed: {}", e), NotificationLevel::Error);
+                self.review_view.set_error(e);
+            }
+        }
+    }
+
+    /// 提交的问题在 diff 查看器中打开并定位到行，工作区的问题在暂存视图中选中文件
+    async fn jump_to_finding(&mut self, file: String, line: Option<u32>) -> Result<()> {
+        match self.review_view.target().cloned() {
+            Some(ReviewTarget::Commit(hash)) => {
+                let mut viewer =
+                    match DiffViewer::with_options(&hash, self.config.diff.clone()).await {
+                        Ok(viewer) => viewer,
+                        Err(e) => {
+                            self.state.write().await.add_notification(
+                                format!("Failed to open diff for {}: {}", file, e),
+                                NotificationLevel::Error,
+                            );
+                            return Ok(());
+                        }
+                    };
+                if viewer.select_file(&file).await {
+                    if let Some(line) = line {
+                        viewer.jump_to_line(line);
+                    }
+                }
+                self.diff_viewer = Some(viewer);
+                self.state
+                    .write()
+                    .await
+                    .show_diff_modal(hash, String::new());
+            }
+            Some(ReviewTarget::WorkingTree) => {
+                self.switch_to_view(ViewType::Staging).await?;
+                let mut state = self.state.write().await;
+                if self.staging_view.select_path(&file) {
+                    self.staging_view.request_preview(&mut state);
+                } else {
+                    state.add_notification(
+                        format!("{} has no pending changes", file),
+                        NotificationLevel::Warning,
+                    );
+                }
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
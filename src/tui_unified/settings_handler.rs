@@ -0,0 +1,106 @@
+use crossterm::event::KeyEvent;
+
+use super::app::AppMode;
+use crate::tui_unified::{
+    components::widgets::settings_panel::{SettingsAction, SettingsDraft},
+    config::Theme,
+    state::{app_state::NotificationLevel, SimpleStatePersistence},
+    Result,
+};
+
+impl super::app::TuiUnifiedApp {
+    /// 打开设置浮层，以当前生效的配置作为草稿
+    pub(crate) fn open_settings(&mut self) {
+        let ai = crate::config::Config::new();
+        let draft = SettingsDraft {
+            provider: ai.provider,
+            model: ai.model,
+            config: self.config.clone(),
+        };
+        let providers = crate::config::ProviderRegistry::list_providers()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        self.settings_panel
+            .open(draft, providers, Theme::available());
+        self.current_mode = AppMode::Settings;
+    }
+
+    fn close_settings(&mut self) {
+        self.settings_panel.close();
+        self.current_mode = AppMode::Normal;
+    }
+
+    pub(crate) async fn handle_settings_key(&mut self, key: KeyEvent) -> Result<()> {
+        match self.settings_panel.handle_key(key) {
+            SettingsAction::None => Ok(()),
+            SettingsAction::Close => {
+                self.close_settings();
+                Ok(())
+            }
+            SettingsAction::Save(draft) => {
+                self.close_settings();
+                self.apply_settings(draft).await
+            }
+        }
+    }
+
+    /// 写回配置文件并立即应用，无需重启
+    async fn apply_settings(&mut self, draft: SettingsDraft) -> Result<()> {
+        let mut problems = Vec::new();
+
+        let ai = crate::config::Config::new();
+        if draft.provider != ai.provider || draft.model != ai.model {
+            match crate::config::save_user_env(&[
+                ("AI_COMMIT_PROVIDER", &draft.provider),
+                ("AI_COMMIT_MODEL", &draft.model),
+            ]) {
+                // 已创建的 Agent 保留了旧的提供商，下次使用时重新创建
+                Ok(_) => self.agent_manager = None,
+                Err(e) => problems.push(format!("AI provider not saved: {}", e)),
+            }
+        }
+
+        if draft.config.theme_name != self.config.theme_name {
+            match Theme::load(&draft.config.theme_name).and_then(|theme| theme.colors.palette()) {
+                Ok(palette) => self.theme = palette,
+                Err(e) => problems.push(format!("Failed to load theme: {}", e)),
+            }
+        }
+
+        let saved_to = match draft.config.save() {
+            Ok(path) => Some(path),
+            Err(e) => {
+                problems.push(format!("Settings not saved: {:#}", e));
+                None
+            }
+        };
+        self.config = draft.config;
+
+        let mut state = self.state.write().await;
+        // 上次选择的主题也记录在状态文件中，保持一致以免重启后被覆盖
+        if state.config.theme_name != self.config.theme_name {
+            state.config.theme_name = self.config.theme_name.clone();
+            let saved = match SimpleStatePersistence::new() {
+                Ok(persistence) => persistence.save_state(&state).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = saved {
+                problems.push(format!("Theme not saved: {}", e));
+            }
+        }
+
+        if problems.is_empty() {
+            if let Some(path) = saved_to {
+                state.add_notification(
+                    format!("Settings saved to {}", path.display()),
+                    NotificationLevel::Success,
+                );
+            }
+        } else {
+            state.add_notification(problems.join("; "), NotificationLevel::Warning);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+// 启动时在后台并行加载仓库数据
+use super::Result;
+use crate::tui_unified::components::base::component::Component;
+use crate::tui_unified::state::{app_state::NotificationLevel, DataSection};
+
+/// 启动加载任务名的前缀，后面接数据类别
+pub(crate) const LOAD_TASK_PREFIX: &str = "load:";
+
+impl super::app::TuiUnifiedApp {
+    /// 为每类数据启动一个后台任务，第一帧不必等待依次执行的 git 命令
+    pub(crate) async fn start_initial_load(&mut self) -> Result<()> {
+        let repo_path = std::env::current_dir()?;
+        {
+            let mut state = self.state.write().await;
+            for section in DataSection::ALL {
+                state.start_loading_section(section);
+            }
+        }
+
+        for section in DataSection::ALL {
+            let state = self.state.clone();
+            let repo_path = repo_path.clone();
+            self.task_manager.spawn_reported(
+                format!("{}{}", LOAD_TASK_PREFIX, section.label()),
+                move |_| super::git_operations::load_data_section(repo_path, section, state),
+            );
+        }
+
+        let favorites = self
+            .state
+            .read()
+            .await
+            .search_state
+            .favorite_queries
+            .clone();
+        self.query_history_view.load_history(&favorites).await;
+
+        self.remember_current_repo().await;
+
+        if self.config.watch_files {
+            if let Err(e) = self.start_file_watch() {
+                self.state.write().await.add_notification(
+                    format!("Failed to watch repository: {}", e),
+                    NotificationLevel::Warning,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// 某类数据加载完成后刷新依赖它的视图，失败时提示
+    pub(crate) async fn finish_initial_load(
+        &mut self,
+        label: &str,
+        result: std::result::Result<String, String>,
+    ) {
+        let Some(section) = DataSection::ALL
+            .into_iter()
+            .find(|section| section.label() == label)
+        else {
+            return;
+        };
+
+        if let Err(e) = result {
+            self.state.write().await.add_notification(
+                format!("Failed to load {}: {}", label, e),
+                NotificationLevel::Warning,
+            );
+            return;
+        }
+
+        let state = self.state.read().await;
+        match section {
+            DataSection::Commits => {
+                let commits = state.repo_state.commits.clone();
+                drop(state);
+                let has_commits = !commits.is_empty();
+                self.git_log_view.update_commits(commits);
+                if has_commits {
+                    self.git_log_view.set_focus(true);
+                }
+            }
+            DataSection::Remotes => self.remotes_view.load_remotes(&state).await,
+            DataSection::Stashes => self.stash_view.load_stashes(&state).await,
+            DataSection::Branches | DataSection::Tags | DataSection::Status => {}
+        }
+    }
+}
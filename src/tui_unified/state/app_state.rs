@@ -1,8 +1,10 @@
 use super::git_state::GitRepoState;
 use super::ui_state::{FocusState, LayoutState};
+use crate::query_history::FavoriteQuery;
 use crate::tui_unified::{config::AppConfig, focus::FocusPanel, Result};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use uuid::Uuid;
 
 #[derive(Debug, Clone)]
@@ -23,6 +25,8 @@ pub struct AppState {
 
     // 运行时状态
     pub loading_tasks: HashMap<String, LoadingTask>,
+    /// 仍在后台加载的数据，对应的面板显示加载占位
+    pub loading_sections: HashSet<DataSection>,
     pub notifications: Vec<Notification>,
 
     // 新布局状态
@@ -38,6 +42,45 @@ pub enum ViewType {
     Stash,
     QueryHistory,
     Staging,
+    Graph,
+    Reflog,
+    Blame,
+    Worktrees,
+    Review,
+    Hotspots,
+}
+
+/// 启动时并行加载的仓库数据
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataSection {
+    Commits,
+    Branches,
+    Tags,
+    Status,
+    Remotes,
+    Stashes,
+}
+
+impl DataSection {
+    pub const ALL: [DataSection; 6] = [
+        DataSection::Commits,
+        DataSection::Branches,
+        DataSection::Tags,
+        DataSection::Status,
+        DataSection::Remotes,
+        DataSection::Stashes,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DataSection::Commits => "commits",
+            DataSection::Branches => "branches",
+            DataSection::Tags => "tags",
+            DataSection::Status => "status",
+            DataSection::Remotes => "remotes",
+            DataSection::Stashes => "stashes",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -97,9 +140,51 @@ pub struct SelectionState {
     pub pending_diff_commit: std::sync::Mutex<Option<String>>, // 待显示diff的提交哈希
     pub pending_branch_switch: std::sync::Mutex<Option<String>>, // 待切换的分支名
     pub direct_branch_switch: std::sync::Mutex<Option<String>>, // 直接切换的分支名（不通过模态框）
-    pub pending_staging_toggle: std::sync::Mutex<Option<usize>>, // 待切换暂存状态的文件索引
-    pub pending_stage_all: std::sync::Mutex<bool>,             // 待暂存全部文件
-    pub pending_hunk_stage: std::sync::Mutex<Option<(String, String)>>, // (file_path, hunk_patch) 待暂存的 hunk
+    pub pending_staging_operation: std::sync::Mutex<Option<StagingOperation>>, // 待执行的暂存操作
+    pub pending_staging_diff: std::sync::Mutex<Option<(String, bool)>>, // (file_path, staged) 待预览的文件 diff
+    pub pending_graph_page: std::sync::Mutex<Option<usize>>,            // 待加载的提交图分页起点
+    pub pending_git_log_page: std::sync::Mutex<Option<usize>>,          // 待加载的 Git Log 分页起点
+    pub pending_reflog_action: std::sync::Mutex<Option<ReflogAction>>,  // 待确认的 reflog 操作
+    pub pending_blame_file: std::sync::Mutex<Option<String>>,           // 待加载 blame 的文件路径
+    pub pending_copy: std::sync::Mutex<Option<CopyRequest>>,            // 待复制到剪贴板的内容
+    pub pending_diff_file: std::sync::Mutex<Option<String>>,            // diff 打开后要选中的文件
+    pub pending_worktree_action: std::sync::Mutex<Option<WorktreeAction>>, // 待执行的 worktree 操作
+    pub pending_input: std::sync::Mutex<Option<InputPrompt>>,           // 输入框中正在编辑的内容
+    pub pending_remote_operation: std::sync::Mutex<Option<RemoteOperation>>, // 待执行的远程操作
+    pub pending_tag_creation: std::sync::Mutex<bool>,                   // 是否要打开新建标签浮层
+    pub pending_branch_action: std::sync::Mutex<Option<BranchAction>>,  // 待执行的分支操作
+    pub pending_commit_action: std::sync::Mutex<Option<CommitAction>>,  // 待确认的提交操作
+    pub pending_revert: std::sync::Mutex<Option<String>>,               // 待生成撤销说明的提交哈希
+    pub pending_review: std::sync::Mutex<Option<ReviewRequest>>,        // 审查视图请求的操作
+    pub pending_query_action: std::sync::Mutex<Option<QueryAction>>,    // 查询历史视图请求的操作
+}
+
+/// 视图请求复制到系统剪贴板的内容
+#[derive(Debug, Clone, PartialEq)]
+pub enum CopyRequest {
+    /// 直接复制文本，`label` 用于提示信息，如 "commit hash"
+    Text { label: String, text: String },
+    /// 复制提交的完整提交信息，需要从 Git 读取
+    CommitMessage(String),
+}
+
+/// 暂存视图请求的 Git 操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum StagingOperation {
+    /// 暂存单个文件
+    StageFile(String),
+    /// 取消暂存单个文件
+    UnstageFile(String),
+    /// 暂存全部改动
+    StageAll,
+    /// 取消暂存全部改动
+    UnstageAll,
+    /// 通过 `git apply --cached` 暂存（reverse 为 true 时取消暂存）单个 hunk
+    ApplyHunk {
+        file_path: String,
+        patch: String,
+        reverse: bool,
+    },
 }
 
 impl Clone for SelectionState {
@@ -130,24 +215,359 @@ impl Clone for SelectionState {
                     .unwrap_or_else(|e| e.into_inner())
                     .clone(),
             ),
-            pending_staging_toggle: std::sync::Mutex::new(
+            pending_staging_operation: std::sync::Mutex::new(
+                self.pending_staging_operation
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_staging_diff: std::sync::Mutex::new(
+                self.pending_staging_diff
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_graph_page: std::sync::Mutex::new(
                 *self
-                    .pending_staging_toggle
+                    .pending_graph_page
                     .lock()
                     .unwrap_or_else(|e| e.into_inner()),
             ),
-            pending_stage_all: std::sync::Mutex::new(
+            pending_git_log_page: std::sync::Mutex::new(
                 *self
-                    .pending_stage_all
+                    .pending_git_log_page
                     .lock()
                     .unwrap_or_else(|e| e.into_inner()),
             ),
-            pending_hunk_stage: std::sync::Mutex::new(
-                self.pending_hunk_stage
+            pending_reflog_action: std::sync::Mutex::new(
+                self.pending_reflog_action
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_blame_file: std::sync::Mutex::new(
+                self.pending_blame_file
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_copy: std::sync::Mutex::new(
+                self.pending_copy
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_diff_file: std::sync::Mutex::new(
+                self.pending_diff_file
                     .lock()
                     .unwrap_or_else(|e| e.into_inner())
                     .clone(),
             ),
+            pending_worktree_action: std::sync::Mutex::new(
+                self.pending_worktree_action
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_input: std::sync::Mutex::new(
+                self.pending_input
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_remote_operation: std::sync::Mutex::new(
+                self.pending_remote_operation
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_tag_creation: std::sync::Mutex::new(
+                *self
+                    .pending_tag_creation
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner()),
+            ),
+            pending_branch_action: std::sync::Mutex::new(
+                self.pending_branch_action
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_commit_action: std::sync::Mutex::new(
+                self.pending_commit_action
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_revert: std::sync::Mutex::new(
+                self.pending_revert
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_review: std::sync::Mutex::new(
+                self.pending_review
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+            pending_query_action: std::sync::Mutex::new(
+                self.pending_query_action
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone(),
+            ),
+        }
+    }
+}
+
+/// 审查视图请求的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReviewRequest {
+    /// 审查工作区的变更
+    WorkingTree,
+    /// 审查 Git Log 中选中的提交
+    SelectedCommit,
+    /// 按上一次的审查对象重新审查
+    Rerun,
+    /// 加载 Git Log 中选中的提交已保存的审查报告
+    StoredForSelectedCommit,
+    /// 打开问题所在文件的 diff，有行号时定位到对应的修改块
+    Jump { file: String, line: Option<u32> },
+}
+
+/// 查询历史视图请求的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryAction {
+    /// 重新执行查询
+    Run(String),
+    /// 收藏或取消收藏查询
+    TogglePin(String),
+}
+
+/// 分支视图请求的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum BranchAction {
+    /// 新建分支并切换过去，base 为空时基于 HEAD
+    Create {
+        name: String,
+        base: Option<String>,
+    },
+    Rename {
+        from: String,
+        to: String,
+    },
+    /// 删除分支，需要确认；force 为 true 时删除未合并的分支
+    Delete {
+        name: String,
+        force: bool,
+    },
+}
+
+impl BranchAction {
+    pub fn needs_confirmation(&self) -> bool {
+        matches!(self, BranchAction::Delete { .. })
+    }
+}
+
+/// 日志视图请求对选中提交执行的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommitAction {
+    /// 按从旧到新的顺序把提交 cherry-pick 到当前分支
+    CherryPick(Vec<String>),
+    /// 在当前分支上撤销提交，impact 为确认框中展示的预计影响
+    Revert {
+        hash: String,
+        message: String,
+        impact: String,
+    },
+}
+
+impl CommitAction {
+    /// 确认框中的说明
+    pub fn description(&self) -> String {
+        let short = |hash: &str| hash.chars().take(8).collect::<String>();
+        match self {
+            CommitAction::CherryPick(hashes) => {
+                let list = hashes
+                    .iter()
+                    .map(|hash| short(hash))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "Cherry-pick {} commit(s) onto the current branch?\n\n{}",
+                    hashes.len(),
+                    list
+                )
+            }
+            CommitAction::Revert {
+                hash,
+                message,
+                impact,
+            } => format!(
+                "Revert {} on the current branch?\n\n{}\n\nMessage:\n{}",
+                short(hash),
+                impact,
+                message
+            ),
+        }
+    }
+}
+
+/// worktree 视图请求的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorktreeAction {
+    /// 把应用的工作目录切换到该 worktree
+    Switch(PathBuf),
+    /// 删除 worktree，需要确认
+    Remove(PathBuf),
+    /// 清理已失效的 worktree 记录
+    Prune,
+    /// 暂停界面，在 worktree 中打开 shell
+    Shell(PathBuf),
+}
+
+impl WorktreeAction {
+    pub fn needs_confirmation(&self) -> bool {
+        matches!(self, WorktreeAction::Remove(_))
+    }
+}
+
+/// 在后台执行的远程仓库操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteOperation {
+    /// 抓取指定的远程，None 表示所有远程
+    Fetch(Option<String>),
+    /// 拉取当前分支
+    Pull,
+    /// 推送分支：有上游时推送到上游，否则推送到 origin 并设置上游
+    Push {
+        branch: String,
+        upstream: Option<String>,
+    },
+    /// 推送标签到 origin
+    PushTag(String),
+}
+
+impl RemoteOperation {
+    /// 对应的 git 参数，都带 `--progress` 以便在非终端输出中也能读到进度
+    pub fn git_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = match self {
+            RemoteOperation::Fetch(Some(remote)) => vec!["fetch".into(), remote.clone()],
+            RemoteOperation::Fetch(None) => vec!["fetch".into(), "--all".into()],
+            RemoteOperation::Pull => vec!["pull".into()],
+            RemoteOperation::PushTag(tag) => vec!["push".into(), "origin".into(), tag.clone()],
+            RemoteOperation::Push { branch, upstream } => {
+                match upstream.as_deref().and_then(|u| u.split_once('/')) {
+                    Some((remote, target)) => vec![
+                        "push".into(),
+                        remote.to_string(),
+                        format!("{}:{}", branch, target),
+                    ],
+                    None => vec![
+                        "push".into(),
+                        "--set-upstream".into(),
+                        "origin".into(),
+                        branch.clone(),
+                    ],
+                }
+            }
+        };
+        args.insert(1, "--progress".into());
+        args
+    }
+
+    /// 进度条和提示中显示的名称
+    pub fn label(&self) -> String {
+        match self {
+            RemoteOperation::Fetch(Some(remote)) => format!("Fetching {}", remote),
+            RemoteOperation::Fetch(None) => "Fetching all remotes".to_string(),
+            RemoteOperation::Pull => "Pulling".to_string(),
+            RemoteOperation::Push { branch, .. } => format!("Pushing {}", branch),
+            RemoteOperation::PushTag(tag) => format!("Pushing tag {}", tag),
+        }
+    }
+}
+
+/// 输入框的用途，决定提交后执行的操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputPurpose {
+    /// 新建 worktree，输入分支名
+    CreateWorktree,
+    /// 新建分支，输入 `名称 [基准]`
+    CreateBranch,
+    /// 重命名分支，输入新名称
+    RenameBranch(String),
+    /// 给收藏的查询命名，输入名称
+    RenameQuery(String),
+    /// 导出当前视图，输入文件路径
+    ExportView,
+}
+
+/// 输入框模态框中正在编辑的内容
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputPrompt {
+    pub purpose: InputPurpose,
+    pub value: String,
+}
+
+/// reset 模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetMode {
+    Soft,
+    Mixed,
+    Hard,
+}
+
+impl ResetMode {
+    pub fn as_flag(&self) -> &'static str {
+        match self {
+            ResetMode::Soft => "--soft",
+            ResetMode::Mixed => "--mixed",
+            ResetMode::Hard => "--hard",
+        }
+    }
+}
+
+/// 对 reflog 条目执行的恢复操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReflogAction {
+    /// 检出到条目对应的提交（分离 HEAD）
+    Checkout { selector: String, hash: String },
+    /// 将当前分支重置到条目对应的提交
+    Reset {
+        selector: String,
+        hash: String,
+        mode: ResetMode,
+    },
+}
+
+impl ReflogAction {
+    /// 确认框中的说明
+    pub fn description(&self) -> String {
+        let short = |hash: &str| hash.chars().take(8).collect::<String>();
+        match self {
+            ReflogAction::Checkout { selector, hash } => format!(
+                "Checkout {} ({})?\n\nHEAD will be detached at this commit.",
+                selector,
+                short(hash)
+            ),
+            ReflogAction::Reset {
+                selector,
+                hash,
+                mode,
+            } => {
+                let mut text = format!(
+                    "Reset current branch to {} ({}) with {}?",
+                    selector,
+                    short(hash),
+                    mode.as_flag()
+                );
+                if *mode == ResetMode::Hard {
+                    text.push_str("\n\nWARNING: uncommitted changes will be lost.");
+                }
+                text
+            }
         }
     }
 }
@@ -167,6 +587,8 @@ pub struct SearchState {
     pub filters: SearchFilters,
     pub results_count: usize,
     pub current_match: usize,
+    /// 收藏的查询，显示在查询历史的最前面
+    pub favorite_queries: Vec<FavoriteQuery>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -202,6 +624,11 @@ pub enum ModalType {
     BranchSwitch,
     AIReview,
     AIRefactor,
+    QueryResult,
+    ReflogAction,
+    WorktreeAction,
+    BranchAction,
+    CommitAction,
 }
 
 #[derive(Debug, Clone)]
@@ -230,6 +657,9 @@ pub struct LoadingTask {
     pub can_cancel: bool,
 }
 
+/// 通知历史保留的最大条数
+pub const MAX_NOTIFICATIONS: usize = 100;
+
 #[derive(Debug, Clone)]
 pub struct Notification {
     pub id: Uuid,
@@ -262,6 +692,7 @@ impl AppState {
             search_state: SearchState::default(),
             config: config.clone(),
             loading_tasks: HashMap::new(),
+            loading_sections: HashSet::new(),
             notifications: Vec::new(),
             new_layout: NewLayoutState::default(),
         })
@@ -281,6 +712,27 @@ impl AppState {
         self.selected_items.selected_commit = Some(commit_hash);
     }
 
+    /// 切换提交在多选中的标记，有标记时进入多选模式
+    pub fn toggle_multi_selection(&mut self, item: String) {
+        let selection = &mut self.selected_items.multi_selection;
+        match selection.iter().position(|selected| *selected == item) {
+            Some(index) => {
+                selection.remove(index);
+            }
+            None => selection.push(item),
+        }
+        self.selected_items.selection_mode = if selection.is_empty() {
+            SelectionMode::Single
+        } else {
+            SelectionMode::Multiple
+        };
+    }
+
+    pub fn clear_multi_selection(&mut self) {
+        self.selected_items.multi_selection.clear();
+        self.selected_items.selection_mode = SelectionMode::Single;
+    }
+
     pub fn select_branch(&mut self, branch_name: String) {
         self.selected_items.selected_branch = Some(branch_name);
     }
@@ -298,6 +750,12 @@ impl AppState {
             ViewType::Stash => self.selected_items.selected_stash.clone(),
             ViewType::QueryHistory => None,
             ViewType::Staging => None,
+            ViewType::Graph => None,
+            ViewType::Reflog => None,
+            ViewType::Blame => None,
+            ViewType::Worktrees => None,
+            ViewType::Review => None,
+            ViewType::Hotspots => None,
         }
     }
 
@@ -313,6 +771,24 @@ impl AppState {
             .unwrap_or_else(|e| e.into_inner()) = Some(commit_hash);
     }
 
+    /// 请求显示提交的diff并直接选中指定文件
+    pub fn request_file_diff(&mut self, commit_hash: String, file_path: String) {
+        self.request_diff(commit_hash);
+        *self
+            .selected_items
+            .pending_diff_file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(file_path);
+    }
+
+    pub fn take_pending_diff_file(&self) -> Option<String> {
+        self.selected_items
+            .pending_diff_file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
     pub fn get_pending_diff_commit(&self) -> Option<String> {
         self.selected_items
             .pending_diff_commit
@@ -321,6 +797,461 @@ impl AppState {
             .take()
     }
 
+    pub fn request_staging_operation(&mut self, operation: StagingOperation) {
+        *self
+            .selected_items
+            .pending_staging_operation
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(operation);
+    }
+
+    pub fn take_staging_operation(&self) -> Option<StagingOperation> {
+        self.selected_items
+            .pending_staging_operation
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_staging_diff(&mut self, file_path: String, staged: bool) {
+        *self
+            .selected_items
+            .pending_staging_diff
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some((file_path, staged));
+    }
+
+    pub fn take_staging_diff(&self) -> Option<(String, bool)> {
+        self.selected_items
+            .pending_staging_diff
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_graph_page(&mut self, skip: usize) {
+        *self
+            .selected_items
+            .pending_graph_page
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(skip);
+    }
+
+    pub fn take_graph_page(&self) -> Option<usize> {
+        self.selected_items
+            .pending_graph_page
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_git_log_page(&mut self, skip: usize) {
+        *self
+            .selected_items
+            .pending_git_log_page
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(skip);
+    }
+
+    pub fn take_git_log_page(&self) -> Option<usize> {
+        self.selected_items
+            .pending_git_log_page
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_reflog_action(&mut self, action: ReflogAction) {
+        let (title, label) = match &action {
+            ReflogAction::Checkout { .. } => ("Checkout Reflog Entry", "Checkout"),
+            ReflogAction::Reset { .. } => ("Reset to Reflog Entry", "Reset"),
+        };
+        let modal = ModalState {
+            modal_type: ModalType::ReflogAction,
+            title: title.to_string(),
+            content: action.description(),
+            buttons: vec![
+                ModalButton {
+                    label: label.to_string(),
+                    action: ModalAction::Yes,
+                },
+                ModalButton {
+                    label: "Cancel".to_string(),
+                    action: ModalAction::Cancel,
+                },
+            ],
+            default_button: 0,
+            can_cancel: true,
+        };
+        self.show_modal(modal);
+
+        *self
+            .selected_items
+            .pending_reflog_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(action);
+    }
+
+    pub fn take_reflog_action(&self) -> Option<ReflogAction> {
+        self.selected_items
+            .pending_reflog_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_commit_action(&mut self, action: CommitAction) {
+        let (title, label) = match &action {
+            CommitAction::CherryPick(_) => ("Cherry-pick Commits", "Cherry-pick"),
+            CommitAction::Revert { .. } => ("Revert Commit", "Revert"),
+        };
+        let modal = ModalState {
+            modal_type: ModalType::CommitAction,
+            title: title.to_string(),
+            content: action.description(),
+            buttons: vec![
+                ModalButton {
+                    label: label.to_string(),
+                    action: ModalAction::Yes,
+                },
+                ModalButton {
+                    label: "Cancel".to_string(),
+                    action: ModalAction::Cancel,
+                },
+            ],
+            default_button: 0,
+            can_cancel: true,
+        };
+        self.show_modal(modal);
+
+        *self
+            .selected_items
+            .pending_commit_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(action);
+    }
+
+    pub fn take_commit_action(&self) -> Option<CommitAction> {
+        self.selected_items
+            .pending_commit_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_revert(&mut self, commit_hash: String) {
+        *self
+            .selected_items
+            .pending_revert
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(commit_hash);
+    }
+
+    pub fn take_revert_request(&self) -> Option<String> {
+        self.selected_items
+            .pending_revert
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_review(&mut self, request: ReviewRequest) {
+        *self
+            .selected_items
+            .pending_review
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(request);
+    }
+
+    pub fn take_review_request(&self) -> Option<ReviewRequest> {
+        self.selected_items
+            .pending_review
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_query_action(&mut self, action: QueryAction) {
+        *self
+            .selected_items
+            .pending_query_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(action);
+    }
+
+    pub fn take_query_action(&self) -> Option<QueryAction> {
+        self.selected_items
+            .pending_query_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_blame(&mut self, file_path: String) {
+        *self
+            .selected_items
+            .pending_blame_file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(file_path);
+    }
+
+    pub fn take_blame_request(&self) -> Option<String> {
+        self.selected_items
+            .pending_blame_file
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    pub fn request_worktree_action(&mut self, action: WorktreeAction) {
+        if let WorktreeAction::Remove(path) = &action {
+            let modal = ModalState {
+                modal_type: ModalType::WorktreeAction,
+                title: "Remove Worktree".to_string(),
+                content: format!(
+                    "Remove worktree {}?\n\nGit refuses to remove worktrees with uncommitted changes.",
+                    path.display()
+                ),
+                buttons: vec![
+                    ModalButton {
+                        label: "Remove".to_string(),
+                        action: ModalAction::Yes,
+                    },
+                    ModalButton {
+                        label: "Cancel".to_string(),
+                        action: ModalAction::Cancel,
+                    },
+                ],
+                default_button: 0,
+                can_cancel: true,
+            };
+            self.show_modal(modal);
+        }
+
+        *self
+            .selected_items
+            .pending_worktree_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(action);
+    }
+
+    /// 取出待执行的 worktree 操作，需要确认的操作只有在确认后才取出
+    pub fn take_worktree_action(&self, confirmed: bool) -> Option<WorktreeAction> {
+        let mut pending = self
+            .selected_items
+            .pending_worktree_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if confirmed || pending.as_ref().is_some_and(|a| !a.needs_confirmation()) {
+            pending.take()
+        } else {
+            None
+        }
+    }
+
+    /// 请求分支操作，删除时先显示确认框
+    pub fn request_branch_action(&mut self, action: BranchAction) {
+        if let BranchAction::Delete { name, force } = &action {
+            let (title, content, label) = if *force {
+                (
+                    "Force Delete Branch",
+                    format!(
+                        "Branch '{}' is not fully merged.\n\nDelete it anyway? Its unmerged commits will only be reachable through the reflog.",
+                        name
+                    ),
+                    "Force Delete",
+                )
+            } else {
+                (
+                    "Delete Branch",
+                    format!("Delete branch '{}'?", name),
+                    "Delete",
+                )
+            };
+            let modal = ModalState {
+                modal_type: ModalType::BranchAction,
+                title: title.to_string(),
+                content,
+                buttons: vec![
+                    ModalButton {
+                        label: label.to_string(),
+                        action: ModalAction::Yes,
+                    },
+                    ModalButton {
+                        label: "Cancel".to_string(),
+                        action: ModalAction::Cancel,
+                    },
+                ],
+                default_button: 0,
+                can_cancel: true,
+            };
+            self.show_modal(modal);
+        }
+
+        *self
+            .selected_items
+            .pending_branch_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(action);
+    }
+
+    /// 取出待执行的分支操作；需要确认的操作只有在 confirmed 为 true 时返回
+    pub fn take_branch_action(&self, confirmed: bool) -> Option<BranchAction> {
+        let mut pending = self
+            .selected_items
+            .pending_branch_action
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if confirmed || pending.as_ref().is_some_and(|a| !a.needs_confirmation()) {
+            pending.take()
+        } else {
+            None
+        }
+    }
+
+    pub fn request_remote_operation(&mut self, operation: RemoteOperation) {
+        *self
+            .selected_items
+            .pending_remote_operation
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(operation);
+    }
+
+    /// 请求推送本地分支，None 表示当前分支
+    pub fn request_push(&mut self, branch: Option<&str>) {
+        let found = self.repo_state.branches.iter().find(|b| {
+            !b.is_remote
+                && match branch {
+                    Some(name) => b.name == name,
+                    None => b.is_current,
+                }
+        });
+        match found {
+            Some(b) => {
+                let operation = RemoteOperation::Push {
+                    branch: b.name.clone(),
+                    upstream: b.upstream.clone(),
+                };
+                self.request_remote_operation(operation);
+            }
+            None => {
+                self.add_notification(
+                    "No local branch to push".to_string(),
+                    NotificationLevel::Warning,
+                );
+            }
+        }
+    }
+
+    pub fn request_tag_creation(&mut self) {
+        *self
+            .selected_items
+            .pending_tag_creation
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = true;
+    }
+
+    pub fn take_tag_creation(&self) -> bool {
+        std::mem::take(
+            &mut *self
+                .selected_items
+                .pending_tag_creation
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+        )
+    }
+
+    pub fn take_remote_operation(&self) -> Option<RemoteOperation> {
+        self.selected_items
+            .pending_remote_operation
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    /// 打开输入框模态框，Enter 提交后由应用按用途执行
+    pub fn request_input(&mut self, purpose: InputPurpose, title: &str, prompt: &str) {
+        self.show_modal(ModalState {
+            modal_type: ModalType::Input,
+            title: title.to_string(),
+            content: prompt.to_string(),
+            buttons: vec![
+                ModalButton {
+                    label: "Submit".to_string(),
+                    action: ModalAction::Ok,
+                },
+                ModalButton {
+                    label: "Cancel".to_string(),
+                    action: ModalAction::Cancel,
+                },
+            ],
+            default_button: 0,
+            can_cancel: true,
+        });
+
+        *self
+            .selected_items
+            .pending_input
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(InputPrompt {
+            purpose,
+            value: String::new(),
+        });
+    }
+
+    pub fn input_value(&self) -> Option<String> {
+        self.selected_items
+            .pending_input
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map(|prompt| prompt.value.clone())
+    }
+
+    pub fn edit_input(&self, edit: impl FnOnce(&mut String)) {
+        if let Some(prompt) = self
+            .selected_items
+            .pending_input
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_mut()
+        {
+            edit(&mut prompt.value);
+        }
+    }
+
+    pub fn take_input(&self) -> Option<InputPrompt> {
+        self.selected_items
+            .pending_input
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    /// 请求复制文本到剪贴板
+    pub fn request_copy(&mut self, label: &str, text: String) {
+        self.request_copy_of(CopyRequest::Text {
+            label: label.to_string(),
+            text,
+        });
+    }
+
+    pub fn request_copy_of(&mut self, request: CopyRequest) {
+        *self
+            .selected_items
+            .pending_copy
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(request);
+    }
+
+    pub fn take_copy_request(&self) -> Option<CopyRequest> {
+        self.selected_items
+            .pending_copy
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
     pub fn request_git_pull(&mut self) {
         let modal = ModalState {
             modal_type: ModalType::GitPull,
@@ -453,6 +1384,18 @@ impl AppState {
         self.loading_tasks.values().collect()
     }
 
+    pub fn start_loading_section(&mut self, section: DataSection) {
+        self.loading_sections.insert(section);
+    }
+
+    pub fn finish_loading_section(&mut self, section: DataSection) {
+        self.loading_sections.remove(&section);
+    }
+
+    pub fn is_section_loading(&self, section: DataSection) -> bool {
+        self.loading_sections.contains(&section)
+    }
+
     // 通知管理
     pub fn add_notification(&mut self, message: String, level: NotificationLevel) -> Uuid {
         let notification = Notification {
@@ -461,22 +1404,75 @@ impl AppState {
             level,
             timestamp: Utc::now(),
             auto_dismiss: match level {
-                NotificationLevel::Info => Some(std::time::Duration::from_secs(3)),
-                NotificationLevel::Success => Some(std::time::Duration::from_secs(3)),
-                NotificationLevel::Warning => Some(std::time::Duration::from_secs(5)),
+                NotificationLevel::Info | NotificationLevel::Success => {
+                    Some(std::time::Duration::from_secs(self.config.toast_seconds))
+                }
+                NotificationLevel::Warning => {
+                    Some(std::time::Duration::from_secs(self.config.warning_seconds))
+                }
                 NotificationLevel::Error => None, // 不自动消失
             },
             dismissed: false,
         };
         let id = notification.id;
+        self.push_notification(notification);
+        id
+    }
+
+    /// 只写入通知历史、不弹出提示，用于后台刷新等频繁的结果；
+    /// 与上一条内容相同时只更新时间，避免刷屏
+    pub fn record_notification(&mut self, message: String, level: NotificationLevel) {
+        if let Some(last) = self.notifications.last_mut() {
+            if last.dismissed && last.level == level && last.message == message {
+                last.timestamp = Utc::now();
+                return;
+            }
+        }
+        self.push_notification(Notification {
+            id: Uuid::new_v4(),
+            message,
+            level,
+            timestamp: Utc::now(),
+            auto_dismiss: None,
+            dismissed: true,
+        });
+    }
+
+    fn push_notification(&mut self, notification: Notification) {
         self.notifications.push(notification);
 
-        // 限制通知数量
-        if self.notifications.len() > 10 {
-            self.notifications.remove(0);
+        // 限制通知历史数量
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            let overflow = self.notifications.len() - MAX_NOTIFICATIONS;
+            self.notifications.drain(..overflow);
         }
+    }
 
-        id
+    /// 隐藏超过自动消失时间的提示，通知本身保留在历史中
+    pub fn expire_notifications(&mut self) {
+        let now = Utc::now();
+        for notification in self.notifications.iter_mut().filter(|n| !n.dismissed) {
+            let expired = notification.auto_dismiss.is_some_and(|duration| {
+                (now - notification.timestamp)
+                    .to_std()
+                    .is_ok_and(|elapsed| elapsed >= duration)
+            });
+            if expired {
+                notification.dismissed = true;
+            }
+        }
+    }
+
+    /// 隐藏所有提示
+    pub fn dismiss_all_notifications(&mut self) {
+        for notification in &mut self.notifications {
+            notification.dismissed = true;
+        }
+    }
+
+    /// 从通知历史中删除
+    pub fn remove_notification(&mut self, id: Uuid) {
+        self.notifications.retain(|n| n.id != id);
     }
 
     pub fn dismiss_notification(&mut self, id: Uuid) {
@@ -582,6 +1578,21 @@ impl AppState {
         self.show_modal(modal);
     }
 
+    pub fn show_query_result_modal(&mut self, query: &str, content: String) {
+        let modal = ModalState {
+            modal_type: ModalType::QueryResult,
+            title: format!("Query · {}", query),
+            content,
+            buttons: vec![ModalButton {
+                label: "Close (Esc)".to_string(),
+                action: ModalAction::Cancel,
+            }],
+            default_button: 0,
+            can_cancel: true,
+        };
+        self.show_modal(modal);
+    }
+
     pub fn show_ai_refactor_modal(&mut self, content: String) {
         let modal = ModalState {
             modal_type: ModalType::AIRefactor,
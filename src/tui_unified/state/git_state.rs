@@ -46,6 +46,80 @@ pub enum ChangeType {
     TypeChange,
 }
 
+impl RepoStatus {
+    /// 解析 `git status --porcelain -z` 的输出
+    ///
+    /// 同时有暂存和未暂存改动的文件会分别出现在 staged_files 和 unstaged_files 中。
+    pub fn from_porcelain(output: &str) -> Self {
+        let mut status = RepoStatus::default();
+        let mut entries = output.split('\0').filter(|e| !e.is_empty());
+
+        while let Some(entry) = entries.next() {
+            if entry.len() < 4 {
+                continue;
+            }
+            let (code, path) = entry.split_at(3);
+            let mut code = code.chars();
+            let index = code.next().unwrap_or(' ');
+            let worktree = code.next().unwrap_or(' ');
+            let path = PathBuf::from(path);
+
+            // 重命名和复制条目后面紧跟原路径
+            let from = if matches!(index, 'R' | 'C') || matches!(worktree, 'R' | 'C') {
+                entries.next().map(PathBuf::from)
+            } else {
+                None
+            };
+
+            match (index, worktree) {
+                ('?', '?') => status.untracked_files.push(path),
+                ('!', '!') => {}
+                ('U', _) | (_, 'U') | ('A', 'A') | ('D', 'D') => status.conflicts.push(path),
+                _ => {
+                    if let Some(change) = Self::change_type(index, from.clone()) {
+                        status.staged_files.push(FileStatus {
+                            path: path.clone(),
+                            status: change,
+                            additions: 0,
+                            deletions: 0,
+                        });
+                    }
+                    if let Some(change) = Self::change_type(worktree, from) {
+                        status.unstaged_files.push(FileStatus {
+                            path,
+                            status: change,
+                            additions: 0,
+                            deletions: 0,
+                        });
+                    }
+                }
+            }
+        }
+
+        status.is_clean = status.staged_files.is_empty()
+            && status.unstaged_files.is_empty()
+            && status.untracked_files.is_empty()
+            && status.conflicts.is_empty();
+        status
+    }
+
+    fn change_type(code: char, from: Option<PathBuf>) -> Option<ChangeType> {
+        match code {
+            'A' => Some(ChangeType::Added),
+            'M' => Some(ChangeType::Modified),
+            'D' => Some(ChangeType::Deleted),
+            'T' => Some(ChangeType::TypeChange),
+            'R' => Some(ChangeType::Renamed {
+                from: from.unwrap_or_default(),
+            }),
+            'C' => Some(ChangeType::Copied {
+                from: from.unwrap_or_default(),
+            }),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Remote {
     pub name: String,
@@ -95,6 +169,8 @@ pub struct Commit {
     pub files_changed: usize,
     pub insertions: usize,
     pub deletions: usize,
+    /// 提交签名状态，None 表示未校验
+    pub signature: Option<crate::git::SignatureStatus>,
 }
 
 #[derive(Debug, Clone)]
@@ -9,8 +9,10 @@ pub mod ui_state;
 mod simple_tests;
 
 pub use app_state::{
-    AppState, LoadingTask, ModalAction, ModalState, ModalType, Notification, NotificationLevel,
-    SearchState, SelectionMode, SelectionState, ViewType,
+    AppState, BranchAction, CommitAction, CopyRequest, DataSection, InputPrompt, InputPurpose,
+    LoadingTask, ModalAction, ModalState, ModalType, Notification, NotificationLevel, QueryAction,
+    ReflogAction, RemoteOperation, ResetMode, ReviewRequest, SearchState, SelectionMode,
+    SelectionState, StagingOperation, ViewType, WorktreeAction,
 };
 pub use git_state::{
     Branch, ChangeType, Commit, FileStatus, GitRepoState, Remote, RepoStatus, RepoSummary, Stash,
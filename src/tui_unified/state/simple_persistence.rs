@@ -5,6 +5,8 @@ use std::fs;
 use std::path::PathBuf;
 
 use super::AppState;
+use crate::query_history::FavoriteQuery;
+use crate::tui_unified::layout::LayoutPreset;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SimplePersistentState {
@@ -14,19 +16,31 @@ pub struct SimplePersistentState {
     pub detail_width: u16,
     pub search_history: Vec<String>,
     pub last_view: String,
+    /// TUI 颜色主题名称
+    #[serde(default = "default_theme_name")]
+    pub theme_name: String,
+    /// 面板排列，旧版本的状态文件中没有这一项
+    #[serde(default)]
+    pub layout: Option<LayoutPreset>,
+    #[serde(default)]
+    pub layout_presets: Vec<LayoutPreset>,
+    /// 收藏的查询
+    #[serde(default)]
+    pub favorite_queries: Vec<FavoriteQuery>,
     pub last_saved: DateTime<Utc>,
 }
 
+fn default_theme_name() -> String {
+    "dark".to_string()
+}
+
 pub struct SimpleStatePersistence {
     state_file: PathBuf,
 }
 
 impl SimpleStatePersistence {
     pub fn new() -> Result<Self> {
-        let config_dir = dirs::home_dir()
-            .unwrap_or_default()
-            .join(".ai-commit")
-            .join("tui");
+        let config_dir = crate::config::data_dir()?.join("tui");
 
         fs::create_dir_all(&config_dir)?;
         let state_file = config_dir.join("simple_state.json");
@@ -42,6 +56,10 @@ impl SimpleStatePersistence {
             detail_width: app_state.layout.detail_width,
             search_history: app_state.search_state.history.clone(),
             last_view: format!("{:?}", app_state.current_view),
+            theme_name: app_state.config.theme_name.clone(),
+            layout: Some(app_state.layout.arrangement.clone()),
+            layout_presets: app_state.layout.presets.clone(),
+            favorite_queries: app_state.search_state.favorite_queries.clone(),
             last_saved: Utc::now(),
         };
 
@@ -71,9 +89,17 @@ impl SimpleStatePersistence {
         app_state.layout.sidebar_width = persistent_state.sidebar_width;
         app_state.layout.content_width = persistent_state.content_width;
         app_state.layout.detail_width = persistent_state.detail_width;
+        if let Some(layout) = &persistent_state.layout {
+            app_state.layout.arrangement = layout.clone();
+        }
+        app_state.layout.presets = persistent_state.layout_presets.clone();
 
         // 应用搜索历史
         app_state.search_state.history = persistent_state.search_history.clone();
+        app_state.search_state.favorite_queries = persistent_state.favorite_queries.clone();
+
+        // 应用主题
+        app_state.config.theme_name = persistent_state.theme_name.clone();
 
         // 应用视图设置
         match persistent_state.last_view.as_str() {
@@ -99,6 +125,10 @@ impl Default for SimplePersistentState {
             detail_width: 30,
             search_history: Vec::new(),
             last_view: "GitLog".to_string(),
+            theme_name: default_theme_name(),
+            layout: None,
+            layout_presets: Vec::new(),
+            favorite_queries: Vec::new(),
             last_saved: Utc::now(),
         }
     }
@@ -2,7 +2,8 @@
 mod simple_state_tests {
     use crate::tui_unified::config::AppConfig;
     use crate::tui_unified::state::{
-        AppState, GitRepoState, LayoutState, NotificationLevel, SimpleStatePersistence, ViewType,
+        AppState, ChangeType, DataSection, GitRepoState, LayoutState, NotificationLevel,
+        RepoStatus, SimplePersistentState, SimpleStatePersistence, ViewType,
     };
     use std::path::PathBuf;
 
@@ -91,6 +92,21 @@ mod simple_state_tests {
         assert_eq!(app_state.search_state.results_count, 0);
     }
 
+    #[tokio::test]
+    async fn test_loading_sections() {
+        let config = AppConfig::default();
+        let mut app_state = AppState::new(&config).await.unwrap();
+
+        for section in DataSection::ALL {
+            app_state.start_loading_section(section);
+        }
+        app_state.finish_loading_section(DataSection::Tags);
+        assert!(app_state.is_section_loading(DataSection::Commits));
+        assert!(!app_state.is_section_loading(DataSection::Tags));
+        // 分段加载不占用后台任务的进度条
+        assert!(!app_state.is_loading());
+    }
+
     #[tokio::test]
     async fn test_notification_management() {
         let config = AppConfig::default();
@@ -116,6 +132,40 @@ mod simple_state_tests {
         assert_eq!(app_state.notifications[0].id, id2);
     }
 
+    #[tokio::test]
+    async fn test_notification_history() {
+        let config = AppConfig::default();
+        let mut app_state = AppState::new(&config).await.unwrap();
+
+        // 后台结果只进入历史，重复内容合并为一条
+        app_state.record_notification("Refreshed".to_string(), NotificationLevel::Info);
+        app_state.record_notification("Refreshed".to_string(), NotificationLevel::Info);
+        assert_eq!(app_state.notifications.len(), 1);
+        assert!(app_state.get_active_notifications().is_empty());
+
+        // 过期的提示被隐藏但保留在历史中，错误不会自动消失
+        let info = app_state.add_notification("Done".to_string(), NotificationLevel::Success);
+        app_state.add_notification("Failed".to_string(), NotificationLevel::Error);
+        app_state.notifications[1].timestamp -= chrono::Duration::seconds(10);
+        app_state.expire_notifications();
+        let active = app_state.get_active_notifications();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].message, "Failed");
+        assert_eq!(app_state.notifications.len(), 3);
+
+        app_state.remove_notification(info);
+        assert_eq!(app_state.notifications.len(), 2);
+
+        for i in 0..150 {
+            app_state.add_notification(format!("n{}", i), NotificationLevel::Info);
+        }
+        assert_eq!(
+            app_state.notifications.len(),
+            crate::tui_unified::state::app_state::MAX_NOTIFICATIONS
+        );
+        assert_eq!(app_state.notifications.last().unwrap().message, "n149");
+    }
+
     #[test]
     fn test_git_repo_state_creation() {
         let repo_path = PathBuf::from("/test/repo");
@@ -128,6 +178,40 @@ mod simple_state_tests {
         assert!(repo_state.branches.is_empty());
     }
 
+    #[test]
+    fn test_repo_status_from_porcelain() {
+        let output = "MM src/lib.rs\0A  new.rs\0 D old.rs\0R  renamed.rs\0orig.rs\0?? notes.txt\0UU conflict.rs\0";
+        let status = RepoStatus::from_porcelain(output);
+
+        let staged: Vec<_> = status.staged_files.iter().map(|f| &f.path).collect();
+        assert_eq!(
+            staged,
+            vec![
+                &PathBuf::from("src/lib.rs"),
+                &PathBuf::from("new.rs"),
+                &PathBuf::from("renamed.rs")
+            ]
+        );
+        assert_eq!(
+            status.staged_files[2].status,
+            ChangeType::Renamed {
+                from: PathBuf::from("orig.rs")
+            }
+        );
+
+        let unstaged: Vec<_> = status.unstaged_files.iter().map(|f| &f.path).collect();
+        assert_eq!(
+            unstaged,
+            vec![&PathBuf::from("src/lib.rs"), &PathBuf::from("old.rs")]
+        );
+        assert_eq!(status.unstaged_files[1].status, ChangeType::Deleted);
+
+        assert_eq!(status.untracked_files, vec![PathBuf::from("notes.txt")]);
+        assert_eq!(status.conflicts, vec![PathBuf::from("conflict.rs")]);
+        assert!(!status.is_clean);
+        assert!(RepoStatus::from_porcelain("").is_clean);
+    }
+
     #[test]
     fn test_layout_state_creation() {
         use ratatui::layout::Rect;
@@ -167,4 +251,34 @@ mod simple_state_tests {
         let state = loaded_state.unwrap();
         assert_eq!(state.version, env!("CARGO_PKG_VERSION"));
     }
+
+    #[tokio::test]
+    async fn test_favorite_queries_persistence() {
+        use crate::query_history::FavoriteQuery;
+
+        // 旧版本的状态文件中没有收藏的查询
+        let old = r#"{"version":"0.1.0","sidebar_width":20,"content_width":50,"detail_width":30,
+            "search_history":[],"last_view":"GitLog","last_saved":"2024-01-01T00:00:00Z"}"#;
+        let saved: SimplePersistentState = serde_json::from_str(old).unwrap();
+        assert!(saved.favorite_queries.is_empty());
+
+        let saved = SimplePersistentState {
+            favorite_queries: vec![FavoriteQuery {
+                query: "author:john".to_string(),
+                name: Some("john".to_string()),
+            }],
+            ..Default::default()
+        };
+        let config = AppConfig::default();
+        let mut app_state = AppState::new(&config).await.unwrap();
+        SimpleStatePersistence::new()
+            .unwrap()
+            .apply_state(&mut app_state, &saved)
+            .await
+            .unwrap();
+        assert_eq!(
+            app_state.search_state.favorite_queries,
+            saved.favorite_queries
+        );
+    }
 }
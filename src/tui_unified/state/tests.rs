@@ -237,6 +237,7 @@ mod state_tests {
                 files_changed: 1,
                 insertions: 10,
                 deletions: 5,
+                signature: None,
             };
             
             repo_state.update_commits(vec![commit]);
@@ -276,6 +277,7 @@ mod state_tests {
                 files_changed: 0,
                 insertions: 0,
                 deletions: 0,
+                signature: None,
             }];
             
             let summary = repo_state.get_repo_summary();
@@ -10,6 +10,10 @@ pub struct LayoutState {
     pub layout_mode: LayoutMode,
     pub panel_ratios: PanelRatios,
     pub min_panel_sizes: MinPanelSizes,
+    /// 当前的面板排列，保存时写入状态文件
+    pub arrangement: crate::tui_unified::layout::LayoutPreset,
+    /// 用户保存的布局预设
+    pub presets: Vec<crate::tui_unified::layout::LayoutPreset>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -118,6 +122,8 @@ impl Default for LayoutState {
             layout_mode: LayoutMode::Normal,
             panel_ratios: PanelRatios::default(),
             min_panel_sizes: MinPanelSizes::default(),
+            arrangement: crate::tui_unified::layout::LayoutPreset::default(),
+            presets: Vec::new(),
         }
     }
 }
@@ -0,0 +1,62 @@
+use crossterm::event::KeyEvent;
+use std::time::{Duration, Instant, SystemTime};
+
+use super::app::AppMode;
+use crate::storage::{BackupManager, StorageManager};
+use crate::tui_unified::Result;
+
+/// 定时备份的后台任务名，完成时显示在通知中
+const BACKUP_TASK: &str = "Storage backup";
+
+/// 检查定时备份是否到期的间隔
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+impl super::app::TuiUnifiedApp {
+    /// 打开存储概况面板并统计已保存的数据
+    pub(crate) async fn open_storage_panel(&mut self) -> Result<()> {
+        self.storage_panel.open();
+        self.current_mode = AppMode::Storage;
+        let config = crate::config::Config::new();
+        let stats = match StorageManager::from_config(&config) {
+            Ok(storage) => storage.stats().await,
+            Err(e) => Err(e),
+        };
+        self.storage_panel.set_stats(stats);
+        Ok(())
+    }
+
+    /// 配置了 `[storage] backup_interval_hours` 时在后台执行到期的备份
+    pub(crate) fn handle_scheduled_backup(&mut self) {
+        if self
+            .last_backup_check
+            .is_some_and(|at| at.elapsed() < BACKUP_CHECK_INTERVAL)
+            || self.task_manager.is_running(BACKUP_TASK)
+        {
+            return;
+        }
+        self.last_backup_check = Some(Instant::now());
+
+        let config = crate::config::Config::new();
+        let due = StorageManager::from_config(&config)
+            .and_then(|storage| BackupManager::from_config(&config, storage.namespace()))
+            .and_then(|manager| manager.is_due(SystemTime::now()));
+        if !matches!(due, Ok(true)) {
+            return;
+        }
+        self.task_manager
+            .spawn_reported(BACKUP_TASK.to_string(), move |_| async move {
+                match crate::commands::storage::run_scheduled_backup(&config).await {
+                    Ok(summary) => Ok(summary.map(|s| s.format()).unwrap_or_default()),
+                    Err(e) => Err(e.to_string()),
+                }
+            });
+    }
+
+    pub(crate) fn handle_storage_panel_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.storage_panel.handle_key(key) {
+            self.storage_panel.close();
+            self.current_mode = AppMode::Normal;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use crossterm::event::KeyEvent;
+use tokio::task::JoinHandle;
+
+use super::app::AppMode;
+use crate::core::ai::agents::{AgentTask, TagAgent, TaskType};
+use crate::git::tag;
+use crate::tui_unified::{
+    components::widgets::tag_dialog::{TagDialogAction, TagRequest},
+    state::app_state::{NotificationLevel, RemoteOperation, ViewType},
+    Result,
+};
+
+/// 发给 AI 的提交数上限
+const MAX_TAG_COMMITS: usize = 200;
+
+/// 新建标签时的 AI 说明生成任务及其输入，重新生成时复用输入
+pub(crate) struct TagNoteGeneration {
+    /// 上一个版本号（不带 v 前缀）
+    current_version: String,
+    commits: String,
+    task: Option<JoinHandle<anyhow::Result<String>>>,
+}
+
+/// 根据上一个标签和之后的提交建议下一个标签名
+async fn suggest_tag_name(previous: Option<&str>, commits: &str) -> anyhow::Result<String> {
+    let current = previous.map_or("0.0.0", |t| t.trim_start_matches('v'));
+    match TagAgent::new().suggest_next_version(current, commits) {
+        Ok(version) => {
+            let name = format!("v{}", version);
+            if tag::tag_exists(&name).await? {
+                tag::get_next_tag_name(Some(&name)).await
+            } else {
+                Ok(name)
+            }
+        }
+        // 上一个标签不是语义化版本时按 --new-tag 的规则递增补丁号
+        Err(_) => tag::get_next_tag_name(None).await,
+    }
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 打开标签视图请求的新建标签浮层
+    pub(crate) async fn handle_pending_tag_creation(&mut self) -> Result<()> {
+        if !self.state.read().await.take_tag_creation() {
+            return Ok(());
+        }
+
+        let previous = tag::get_latest_tag_version().await;
+        let commits =
+            match tag::get_commit_subjects_since(previous.as_deref(), MAX_TAG_COMMITS).await {
+                Ok(commits) if commits.trim().is_empty() => {
+                    self.state.write().await.add_notification(
+                        format!(
+                            "No commits since {}",
+                            previous.as_deref().unwrap_or("the last tag")
+                        ),
+                        NotificationLevel::Info,
+                    );
+                    return Ok(());
+                }
+                Ok(commits) => commits,
+                Err(e) => {
+                    self.state
+                        .write()
+                        .await
+                        .add_notification(format!("{:#}", e), NotificationLevel::Error);
+                    return Ok(());
+                }
+            };
+
+        // 无法建议时留空，由用户输入
+        let suggested = suggest_tag_name(previous.as_deref(), &commits)
+            .await
+            .unwrap_or_default();
+        self.tag_dialog.open(suggested, previous.clone());
+        self.current_mode = AppMode::CreateTag;
+
+        self.tag_note = Some(TagNoteGeneration {
+            current_version: previous
+                .as_deref()
+                .map_or("0.0.0", |t| t.trim_start_matches('v'))
+                .to_string(),
+            commits,
+            task: None,
+        });
+        self.start_tag_note_generation().await;
+        Ok(())
+    }
+
+    /// 在后台用 TagAgent 生成标签说明
+    async fn start_tag_note_generation(&mut self) {
+        self.ensure_agent_manager();
+        let (Some(source), Some(manager)) = (self.tag_note.as_mut(), self.agent_manager.as_mut())
+        else {
+            return;
+        };
+
+        let agent = match Self::build_agent_context() {
+            Ok(context) => {
+                manager.update_context(context);
+                manager.get_or_create_agent("tag").await
+            }
+            Err(e) => Err(e),
+        };
+        let agent = match agent {
+            Ok(agent) => agent,
+            Err(e) => {
+                self.tag_dialog.set_note(Err(e.to_string()));
+                return;
+            }
+        };
+
+        let context = manager.context().clone();
+        let task = AgentTask::new(TaskType::GenerateTag, source.commits.clone())
+            .with_param("current_version", source.current_version.clone());
+        source.task = Some(tokio::spawn(async move {
+            let result = agent.execute(task, &context).await?;
+            result
+                .data
+                .get("release_notes")
+                .and_then(|notes| notes.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| anyhow::anyhow!("No release notes returned"))
+        }));
+    }
+
+    /// 生成完成后把标签说明填入浮层
+    pub(crate) async fn poll_tag_note_generation(&mut self) -> Result<()> {
+        let Some(source) = self.tag_note.as_mut() else {
+            return Ok(());
+        };
+        if !source.task.as_ref().is_some_and(|task| task.is_finished()) {
+            return Ok(());
+        }
+
+        if let Some(task) = source.task.take() {
+            let result = match task.await {
+                Ok(result) => result.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            self.tag_dialog.set_note(result);
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn handle_tag_dialog_key(&mut self, key: KeyEvent) -> Result<()> {
+        match self.tag_dialog.handle_key(key) {
+            TagDialogAction::None => {}
+            TagDialogAction::Close => self.close_tag_dialog(),
+            TagDialogAction::Regenerate => {
+                self.tag_dialog.set_generating();
+                self.start_tag_note_generation().await;
+            }
+            TagDialogAction::Create(request) => self.create_tag(request).await?,
+        }
+        Ok(())
+    }
+
+    fn close_tag_dialog(&mut self) {
+        if let Some(task) = self.tag_note.take().and_then(|source| source.task) {
+            task.abort();
+        }
+        self.tag_dialog.close();
+        self.current_mode = AppMode::Normal;
+    }
+
+    /// 创建带说明的标签，失败时保留浮层以便修改
+    async fn create_tag(&mut self, request: TagRequest) -> Result<()> {
+        let result = match tag::tag_exists(&request.name).await {
+            Ok(true) => Err(anyhow::anyhow!("Tag '{}' already exists", request.name)),
+            Ok(false) => tag::create_tag_with_note(&request.name, &request.note).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            self.tag_dialog.set_message(format!("{:#}", e));
+            return Ok(());
+        }
+        self.close_tag_dialog();
+
+        if let Err(e) = self.refresh_current_view(ViewType::Tags).await {
+            self.state.write().await.add_notification(
+                format!("Failed to refresh tags: {}", e),
+                NotificationLevel::Warning,
+            );
+        }
+
+        let mut state = self.state.write().await;
+        self.tags_view.select_tag(&request.name, &state);
+        state.add_notification(
+            format!("Created tag {}", request.name),
+            NotificationLevel::Success,
+        );
+        if request.push {
+            state.request_remote_operation(RemoteOperation::PushTag(request.name));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+// 文件监听：工作区文件或 .git 中的引用变化后自动刷新状态和 diff
+use crate::tui_unified::{
+    app::AppMode,
+    state::app_state::{NotificationLevel, ViewType},
+    Result,
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+/// 最后一次变化之后等待的时间，连续的变化只刷新一次
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// .git 目录中会影响界面的文件
+const GIT_WATCHED: &[&str] = &["HEAD", "index", "packed-refs", "refs", "MERGE_HEAD"];
+
+/// 变化是否需要刷新：工作区文件，或 .git 中的 HEAD、引用和暂存区（锁文件除外）
+fn is_relevant(path: &Path, git_dir: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(git_dir) else {
+        return true;
+    };
+    if relative.extension().is_some_and(|ext| ext == "lock") {
+        return false;
+    }
+    relative
+        .components()
+        .next()
+        .and_then(|first| first.as_os_str().to_str())
+        .is_some_and(|first| GIT_WATCHED.contains(&first))
+}
+
+/// 防抖：记录最后一次变化的时间，安静一段时间后才触发
+#[derive(Debug, Default)]
+struct Debouncer {
+    last_change: Option<Instant>,
+}
+
+impl Debouncer {
+    fn record(&mut self, now: Instant) {
+        self.last_change = Some(now);
+    }
+
+    fn ready(&mut self, now: Instant) -> bool {
+        match self.last_change {
+            Some(last) if now.duration_since(last) >= WATCH_DEBOUNCE => {
+                self.last_change = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 监听仓库目录的变化
+pub(crate) struct RepoWatcher {
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    git_dir: PathBuf,
+    debouncer: Debouncer,
+}
+
+impl RepoWatcher {
+    pub(crate) fn new(repo_root: &Path) -> notify::Result<Self> {
+        let (sender, events) = channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        watcher.watch(repo_root, RecursiveMode::Recursive)?;
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            git_dir: repo_root.join(".git"),
+            debouncer: Debouncer::default(),
+        })
+    }
+
+    /// 收集新的变化，变化平息后返回 true
+    fn poll(&mut self) -> bool {
+        let now = Instant::now();
+        while let Ok(event) = self.events.try_recv() {
+            let Ok(event) = event else { continue };
+            if !event.kind.is_access()
+                && event
+                    .paths
+                    .iter()
+                    .any(|path| is_relevant(path, &self.git_dir))
+            {
+                self.debouncer.record(now);
+            }
+        }
+        self.debouncer.ready(now)
+    }
+
+    /// 丢弃刷新期间产生的变化，git status 自身也会改写暂存区
+    fn discard_pending(&mut self) {
+        while self.events.try_recv().is_ok() {}
+        self.debouncer = Debouncer::default();
+    }
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 开始监听当前仓库
+    pub(crate) fn start_file_watch(&mut self) -> anyhow::Result<()> {
+        let repo_root = std::env::current_dir()?;
+        self.repo_watcher = Some(RepoWatcher::new(&repo_root)?);
+        Ok(())
+    }
+
+    /// 开启或关闭文件变化时的自动刷新
+    pub(crate) async fn toggle_file_watch(&mut self) -> Result<()> {
+        let (message, level) = if self.repo_watcher.take().is_some() {
+            (
+                "Auto-refresh on file changes disabled".to_string(),
+                NotificationLevel::Info,
+            )
+        } else {
+            match self.start_file_watch() {
+                Ok(()) => (
+                    "Auto-refresh on file changes enabled".to_string(),
+                    NotificationLevel::Info,
+                ),
+                Err(e) => (
+                    format!("Failed to watch repository: {}", e),
+                    NotificationLevel::Error,
+                ),
+            }
+        };
+        self.state.write().await.add_notification(message, level);
+        Ok(())
+    }
+
+    /// 文件变化平息后刷新状态和当前视图，浮层或模态框打开时等关闭后再刷新
+    pub(crate) async fn handle_file_changes(&mut self) -> Result<()> {
+        if self.repo_watcher.is_none() || self.current_mode != AppMode::Normal {
+            return Ok(());
+        }
+        let current_view = {
+            let state = self.state.read().await;
+            if state.is_modal_active() {
+                return Ok(());
+            }
+            state.current_view
+        };
+        if !self.repo_watcher.as_mut().is_some_and(RepoWatcher::poll) {
+            return Ok(());
+        }
+
+        // 状态刷新同时更新暂存视图的 diff 预览
+        let mut refreshed = self.refresh_staging().await;
+        if refreshed.is_ok() && current_view != ViewType::Staging {
+            refreshed = self.refresh_current_view(current_view).await;
+        }
+        if let Some(watcher) = self.repo_watcher.as_mut() {
+            watcher.discard_pending();
+        }
+
+        // 成功的刷新只记入通知历史，不弹出提示
+        let mut state = self.state.write().await;
+        match refreshed {
+            Ok(()) => state.record_notification(
+                format!("Refreshed {:?} after file changes", current_view),
+                NotificationLevel::Info,
+            ),
+            Err(e) => {
+                state.add_notification(
+                    format!("Auto refresh failed: {}", e),
+                    NotificationLevel::Warning,
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relevant_paths() {
+        let git_dir = Path::new("/repo/.git");
+        assert!(is_relevant(Path::new("/repo/src/main.rs"), git_dir));
+        assert!(is_relevant(Path::new("/repo/.git/index"), git_dir));
+        assert!(is_relevant(
+            Path::new("/repo/.git/refs/heads/main"),
+            git_dir
+        ));
+        assert!(!is_relevant(Path::new("/repo/.git/index.lock"), git_dir));
+        assert!(!is_relevant(
+            Path::new("/repo/.git/objects/ab/cdef"),
+            git_dir
+        ));
+        assert!(!is_relevant(Path::new("/repo/.git/logs/HEAD"), git_dir));
+    }
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_period() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::default();
+        assert!(!debouncer.ready(start));
+
+        debouncer.record(start);
+        debouncer.record(start + Duration::from_millis(300));
+        assert!(!debouncer.ready(start + Duration::from_millis(600)));
+        assert!(debouncer.ready(start + Duration::from_millis(800)));
+        // 触发后不再重复
+        assert!(!debouncer.ready(start + Duration::from_millis(900)));
+    }
+}
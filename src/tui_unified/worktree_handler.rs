@@ -0,0 +1,203 @@
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::path::{Path, PathBuf};
+
+use crate::git::worktree::{self, WorktreeInfo};
+use crate::tui_unified::{
+    components::views::worktrees::WorktreeEntry,
+    state::app_state::{NotificationLevel, ViewType, WorktreeAction},
+    Result,
+};
+
+/// 检查 worktree 是否有未提交的修改，目录不存在时返回 None
+async fn worktree_dirty(path: &Path) -> Option<bool> {
+    if !path.is_dir() {
+        return None;
+    }
+    let output = tokio::process::Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["status", "--porcelain"])
+        .output()
+        .await
+        .ok()?;
+    output.status.success().then_some(!output.stdout.is_empty())
+}
+
+/// 读取所有 worktree 及其状态
+async fn load_worktree_entries() -> anyhow::Result<Vec<WorktreeEntry>> {
+    let options = worktree::WorktreeListOptions {
+        porcelain: true,
+        ..Default::default()
+    };
+    let worktrees: Vec<WorktreeInfo> = worktree::list_worktrees_with_options(&options).await?;
+    let current = std::env::current_dir()?.canonicalize()?;
+
+    let entries = worktrees.into_iter().map(|info| async {
+        let is_current = info.path.canonicalize().is_ok_and(|path| path == current);
+        let dirty = if info.is_bare {
+            Some(false)
+        } else {
+            worktree_dirty(&info.path).await
+        };
+        WorktreeEntry {
+            info,
+            is_current,
+            dirty,
+        }
+    });
+    Ok(futures_util::future::join_all(entries).await)
+}
+
+impl super::app::TuiUnifiedApp {
+    /// 刷新Worktree视图
+    pub(crate) async fn refresh_worktrees(&mut self) -> Result<()> {
+        let entries = load_worktree_entries().await?;
+        self.worktrees_view.set_entries(entries);
+        Ok(())
+    }
+
+    /// 执行不需要确认的 worktree 操作，确认框打开时等待
+    pub(crate) async fn handle_pending_worktree_action(&mut self) -> Result<()> {
+        let action = {
+            let state = self.state.read().await;
+            if state.is_modal_active() {
+                return Ok(());
+            }
+            state.take_worktree_action(false)
+        };
+
+        match action {
+            Some(action) => self.execute_worktree_action(action).await,
+            None => Ok(()),
+        }
+    }
+
+    /// 执行确认后的 worktree 操作
+    pub(crate) async fn confirm_worktree_action(&mut self) -> Result<()> {
+        let action = {
+            let mut state = self.state.write().await;
+            state.hide_modal();
+            state.take_worktree_action(true)
+        };
+
+        match action {
+            Some(action) => self.execute_worktree_action(action).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn execute_worktree_action(&mut self, action: WorktreeAction) -> Result<()> {
+        let result = match &action {
+            WorktreeAction::Switch(path) => {
+                worktree::switch_to_worktree(&path.to_string_lossy()).await
+            }
+            WorktreeAction::Remove(path) => worktree::remove_worktree(&path.to_string_lossy())
+                .await
+                .map(|_| path.clone()),
+            WorktreeAction::Prune => worktree::prune_worktrees().await.map(|_| PathBuf::new()),
+            WorktreeAction::Shell(path) => {
+                self.pending_shell = Some(path.clone());
+                return Ok(());
+            }
+        };
+
+        let path = match result {
+            Ok(path) => path,
+            Err(e) => {
+                self.state
+                    .write()
+                    .await
+                    .add_notification(format!("{:#}", e), NotificationLevel::Error);
+                return Ok(());
+            }
+        };
+
+        let message = match action {
+            WorktreeAction::Switch(_) => {
+                // 工作目录已改变，重新加载整个仓库的数据
+                let _ = self.reload_git_data().await;
+                format!("Switched to worktree {}", path.display())
+            }
+            WorktreeAction::Remove(_) => format!("Removed worktree {}", path.display()),
+            _ => "Pruned stale worktree entries".to_string(),
+        };
+        let _ = self.refresh_worktrees().await;
+        self.state
+            .write()
+            .await
+            .add_notification(message, NotificationLevel::Success);
+        Ok(())
+    }
+
+    /// 为分支新建 worktree，分支不存在时一并创建
+    pub(crate) async fn create_worktree(&mut self, branch: &str) -> Result<()> {
+        let exists = {
+            let state = self.state.read().await;
+            state
+                .repo_state
+                .branches
+                .iter()
+                .any(|b| !b.is_remote && b.name == branch)
+        };
+        let result = if exists {
+            worktree::create_worktree(branch, None).await
+        } else {
+            worktree::create_worktree_with_new_branch(branch, None).await
+        };
+
+        let mut state = self.state.write().await;
+        match result {
+            Ok(path) => {
+                state.add_notification(
+                    format!("Created worktree {} for {}", path.display(), branch),
+                    NotificationLevel::Success,
+                );
+                drop(state);
+                let _ = self.refresh_worktrees().await;
+                if !exists {
+                    let _ = self.refresh_current_view(ViewType::Branches).await;
+                }
+            }
+            Err(e) => {
+                state.add_notification(format!("{:#}", e), NotificationLevel::Error);
+            }
+        }
+        Ok(())
+    }
+
+    /// 暂停界面并在目录中打开交互式 shell，退出 shell 后恢复
+    pub(crate) async fn run_shell<B>(
+        &mut self,
+        terminal: &mut ratatui::Terminal<B>,
+        dir: &Path,
+    ) -> Result<()>
+    where
+        B: ratatui::backend::Backend,
+    {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
+
+        disable_raw_mode()?;
+        execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+        println!("Opening {} in {} (exit to return)", shell, dir.display());
+
+        let status = std::process::Command::new(&shell).current_dir(dir).status();
+
+        enable_raw_mode()?;
+        execute!(std::io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        terminal.clear()?;
+
+        if let Err(e) = status {
+            self.state.write().await.add_notification(
+                format!("Failed to start {}: {}", shell, e),
+                NotificationLevel::Error,
+            );
+        }
+        // shell 中可能修改了文件或提交
+        let _ = self.refresh_worktrees().await;
+        Ok(())
+    }
+}
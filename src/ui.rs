@@ -18,19 +18,42 @@ pub enum ConfirmResult {
 /// - n/no: 拒绝并取消操作
 /// - e/edit: 启动编辑器编辑消息（支持 vim、vi、nano 等）
 pub fn confirm_commit_message(message: &str, skip_confirm: bool) -> anyhow::Result<ConfirmResult> {
+    confirm_commit_message_with_suggestions(message, &[], skip_confirm)
+}
+
+/// 确认 commit message，同时列出历史中修改过相同文件的提交信息作为快捷选项
+///
+/// 输入选项编号时直接使用对应的历史信息。
+pub fn confirm_commit_message_with_suggestions(
+    message: &str,
+    suggestions: &[String],
+    skip_confirm: bool,
+) -> anyhow::Result<ConfirmResult> {
     if skip_confirm {
         return Ok(ConfirmResult::Confirmed(message.to_string()));
     }
 
     // 显示生成的 commit message
     println!("🤖 AI: {}", message);
-    print!("确认? [Y/n/e]: ");
+    if suggestions.is_empty() {
+        print!("确认? [Y/n/e]: ");
+    } else {
+        println!("📜 修改相同文件的近期提交信息:");
+        for (i, suggestion) in suggestions.iter().enumerate() {
+            println!("  {}: {}", i + 1, suggestion);
+        }
+        print!("确认? [Y/n/e/1-{}]: ", suggestions.len());
+    }
     io::stdout().flush()?;
 
     let mut input = String::new();
     io::stdin().read_line(&mut input)?;
     let input = input.trim().to_lowercase();
 
+    if let Some(suggestion) = pick_suggestion(&input, suggestions) {
+        return Ok(ConfirmResult::Confirmed(suggestion.to_string()));
+    }
+
     match input.as_str() {
         "y" | "yes" | "" => Ok(ConfirmResult::Confirmed(message.to_string())), // 默认回车视为确认
         "n" | "no" => Ok(ConfirmResult::Rejected),
@@ -42,6 +65,12 @@ pub fn confirm_commit_message(message: &str, skip_confirm: bool) -> anyhow::Resu
     }
 }
 
+/// 把输入的编号解析为对应的快捷选项
+fn pick_suggestion<'a>(input: &str, suggestions: &'a [String]) -> Option<&'a str> {
+    let choice = input.parse::<usize>().ok()?;
+    suggestions.get(choice.checked_sub(1)?).map(String::as_str)
+}
+
 /// 允许用户使用外部编辑器编辑 commit message
 ///
 /// 功能特性：
@@ -260,6 +289,16 @@ pub fn confirm_action(prompt: &str) -> anyhow::Result<bool> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pick_suggestion() {
+        let suggestions = vec!["feat: a".to_string(), "fix: b".to_string()];
+        assert_eq!(pick_suggestion("2", &suggestions), Some("fix: b"));
+        assert_eq!(pick_suggestion("0", &suggestions), None);
+        assert_eq!(pick_suggestion("3", &suggestions), None);
+        assert_eq!(pick_suggestion("y", &suggestions), None);
+        assert_eq!(pick_suggestion("1", &[]), None);
+    }
+
     #[test]
     fn test_is_valid_commit_message() {
         // 有效的 commit messages
@@ -1,10 +1,10 @@
 use ai_commit::git::worktree::*;
 use std::path::PathBuf;
 
-/// Git Worktree 模块集成测试
-///
-/// 这些测试验证 worktree 模块各个子模块之间的协作
-/// 以及整体功能的正确性
+// Git Worktree 模块集成测试
+//
+// 这些测试验证 worktree 模块各个子模块之间的协作
+// 以及整体功能的正确性
 
 #[cfg(test)]
 mod worktree_integration_tests {
@@ -1,29 +1,9 @@
-/// UI交互测试
-/// 测试用户界面的各种交互场景和边界条件
+//! UI交互测试
+//! 测试用户界面的各种交互场景和边界条件
 
 #[cfg(test)]
 mod ui_interaction_tests {
     use ai_commit::ui;
-    use std::io::Cursor;
-
-    // Mock 输入输出结构，用于测试
-    struct MockIO {
-        input: Cursor<Vec<u8>>,
-        output: Vec<u8>,
-    }
-
-    impl MockIO {
-        fn new(input_data: &str) -> Self {
-            Self {
-                input: Cursor::new(input_data.as_bytes().to_vec()),
-                output: Vec::new(),
-            }
-        }
-
-        fn output_as_string(&self) -> String {
-            String::from_utf8_lossy(&self.output).to_string()
-        }
-    }
 
     #[test]
     fn test_confirm_result_variants() {
@@ -186,8 +166,8 @@ mod ui_interaction_tests {
         // 这里我们测试的是整个验证流程的逻辑正确性
         for test_case in test_cases {
             println!(
-                "测试案例: {} - {}",
-                test_case.description, test_case.message
+                "测试案例: {} - {} (预期有效: {})",
+                test_case.description, test_case.message, test_case.should_be_valid
             );
             // 实际的验证逻辑测试需要通过公共API或集成测试来完成
         }
@@ -343,7 +323,7 @@ mod ui_interaction_tests {
         ];
 
         for input in problematic_inputs {
-            let result = ui::confirm_commit_message(&input, true);
+            let result = ui::confirm_commit_message(input, true);
 
             // 即使是有问题的输入，跳过确认时也应该成功
             assert!(